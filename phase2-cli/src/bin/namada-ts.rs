@@ -1,26 +1,38 @@
 use phase2_coordinator::{
-    authentication::{KeyPair, Production, Signature},
+    authentication::{Authentication, KeyPair},
     commands::{Computation, RandomSource, SEED_LENGTH},
+    environment::CeremonyParameters,
     io::{self, verify_signature, KeyPairUser},
-    objects::{ContributionFileSignature, ContributionInfo, ContributionState, TrimmedContributionInfo},
+    objects::{
+        sanitize_memo, ContributionFileSignature, ContributionInfo, ContributionState, TrimmedContributionInfo,
+        MAX_MEMO_LENGTH,
+    },
     rest_utils::{ContributorStatus, PostChunkRequest, TOKENS_ZIP_FILE, UPDATE_TIME},
     storage::Object,
 };
 
 use reqwest::{Client, Url};
 
-use anyhow::Result;
-use async_stream::try_stream;
-use crossterm::{
-    execute,
-    terminal::{Clear, ClearType, ScrollDown},
-};
+use anyhow::{Context, Result};
 use ed25519_compact::{KeyPair as EdKeyPair, Seed};
-use futures_util::StreamExt;
 use phase2_cli::{
     ascii_logo::{ASCII_CONTRIBUTION_DONE, ASCII_LOGO},
-    keys::{self, EncryptedKeypair, TomlConfig},
-    requests, CeremonyOpt, CoordinatorUrl, Token, VerifySignatureContribution,
+    checkpoint::{ContributionCheckpoint, ContributionStep, CHECKPOINT_FILE_NAME},
+    disk_space,
+    estimate::{ContributionEstimate, BENCHMARK_SAMPLES},
+    fetch,
+    hash::{self, get_file_as_byte_vec, ContributionHashes, CHALLENGE_HASH_LENGTH},
+    i18n::{self, tr, Message},
+    info,
+    keys::{self, EncryptedKeypair, KeypairSource, TomlConfig},
+    manifest::{
+        TransferManifest, OFFLINE_CHALLENGE_FILE_NAME, OFFLINE_CONTRIBUTION_FILE_NAME, OFFLINE_MANIFEST_FILE_NAME,
+    },
+    mirror, output, progress,
+    requests::{self, RequestError, MAX_REQUEST_ATTEMPTS, REQUEST_RETRY_DELAY},
+    summary::ContributionSummary,
+    transcript,
+    CeremonyOpt, CliOpt, CoordinatorUrl, KeypairOpt, Token, VerifySignatureContribution,
 };
 use serde_json;
 use setup_utils::calculate_hash;
@@ -28,30 +40,28 @@ use structopt::StructOpt;
 
 use std::{
     collections::HashMap,
-    fs::{self, File, OpenOptions},
-    io::Read,
+    fs::{self, OpenOptions},
+    num::NonZeroU64,
+    path::{Path, PathBuf},
     process,
     sync::Arc,
     time::{Duration, Instant, UNIX_EPOCH},
 };
 
 use chrono::{DateTime, Utc};
-use indicatif::{ProgressBar, ProgressStyle};
 use owo_colors::OwoColorize;
 
 use regex::Regex;
 
-use tokio::{fs as async_fs, io::AsyncWriteExt, task::JoinHandle, time};
-use tokio_util::io::ReaderStream;
+use tokio::{fs as async_fs, io::AsyncWriteExt, sync::oneshot, task::JoinHandle, time};
 
-use tracing::{debug, trace};
+use tracing::{debug, trace, warn};
 
 use bs58;
 
-const OFFLINE_CONTRIBUTION_FILE_NAME: &str = "contribution.params";
-const OFFLINE_CHALLENGE_FILE_NAME: &str = "challenge.params";
-
 const CUSTOM_SEED_MSG_NO: &str = "Enter a variable-length random string to be used as entropy in combination with your OS randomness.\nYou can type frenetically, smash your keyboard, or enter a string representation of your alternative source of entropy.\nThe only limitation is your terminal’s max command length.\nThis string will be hashed together with your OS randomness to produce the seed for ChaCha RNG";
+/// How long the optional questions wait for a reply before taking their default answer.
+const PROMPT_TIMEOUT: Duration = Duration::from_secs(300);
 const CUSTOM_SEED_MSG_YES: &str = "Provide your custom random seed to initialize the ChaCha random number generator.\nYour seed might come from an external source of randomness like atmospheric noise, radioactive elements, lava lite etc. or an airgapped machine.";
 
 macro_rules! pretty_hash {
@@ -71,13 +81,20 @@ macro_rules! pretty_hash {
     }};
 }
 
-/// Asks the user a few questions to properly setup the contribution
+/// Asks the user a few questions to properly setup the contribution. An anonymous contribution skips the questions and
+/// only carries the public key and the hashes.
 #[inline(always)]
-fn initialize_contribution() -> Result<ContributionInfo> {
+fn initialize_contribution(anonymous: bool) -> Result<ContributionInfo> {
     let mut contrib_info = ContributionInfo::default();
-    let anonymous = io::get_user_input(
+    if anonymous {
+        return Ok(contrib_info);
+    }
+
+    let anonymous = io::get_user_input_or_default(
         "Do you want to participate anonymously (if not, you’ll be asked to provide us with your name and email address)? [y/n]".bright_yellow(),
         Some(&Regex::new(r"^(?i)[yn]$")?),
+        "y",
+        PROMPT_TIMEOUT,
     )?
     .to_lowercase();
 
@@ -93,35 +110,23 @@ fn initialize_contribution() -> Result<ContributionInfo> {
         )?);
     }
 
-    Ok(contrib_info)
-}
-
-#[inline(always)]
-fn get_file_as_byte_vec(filename: &str, round_height: u64, contribution_id: u64) -> Result<Vec<u8>> {
-    let mut f = File::open(filename)?;
-    let metadata = fs::metadata(filename)?;
-
-    let anoma_file_size: u64 = Object::anoma_contribution_file_size(round_height, contribution_id);
-    let mut buffer = vec![0; anoma_file_size as usize];
-    debug!(
-        "namada_contribution_file_size: round_height {}, contribution_id {}",
-        round_height, contribution_id
-    );
-    debug!("metadata file length {}", metadata.len());
-    f.read(&mut buffer)?;
-
-    Ok(buffer)
-}
-
-fn get_progress_bar(len: u64) -> ProgressBar {
-    let progress_bar = ProgressBar::new(len);
-    progress_bar.set_style(
-        ProgressStyle::default_bar()
-            .template("[{elapsed_precise}] {bar:40} {bytes_per_sec} {total_bytes}")
-            .progress_chars("#>-"),
-    );
+    // Ask for an optional public memo, signed with the rest of the contribution info
+    let with_memo = io::get_user_input_or_default(
+        "Would you like to attach a short public memo to your contribution? [y/n]".bright_yellow(),
+        Some(&Regex::new(r"^(?i)[yn]$")?),
+        "n",
+        PROMPT_TIMEOUT,
+    )?
+    .to_lowercase();
+    if with_memo == "y" {
+        let memo = sanitize_memo(&io::get_user_input(
+            format!("Please enter your memo (max {} characters):", MAX_MEMO_LENGTH).bright_yellow(),
+            Some(&Regex::new(r"\S")?),
+        )?);
+        contrib_info.memo = Some(memo);
+    }
 
-    progress_bar
+    Ok(contrib_info)
 }
 
 /// Contest and offline execution branches
@@ -143,10 +148,11 @@ fn compute_contribution_offline() -> Result<()> {
     );
     msg.push_str(
         format!("{}",format!(
-        "{:4}1) Copy both the challenge file \"{}\" and contribution file \"{}\" in the directory where you will execute the offline command\n",
+        "{:4}1) Copy the challenge file \"{}\", the contribution file \"{}\" and the transfer manifest \"{}\" in the directory where you will execute the offline command\n",
         "",
         OFFLINE_CHALLENGE_FILE_NAME,
-        OFFLINE_CONTRIBUTION_FILE_NAME
+        OFFLINE_CONTRIBUTION_FILE_NAME,
+        OFFLINE_MANIFEST_FILE_NAME
     ).as_str().bright_cyan()).as_str());
     msg.push_str(
         format!(
@@ -185,7 +191,12 @@ fn compute_contribution_offline() -> Result<()> {
 }
 
 /// Computes randomness
-fn compute_contribution(custom_seed: bool, challenge: &[u8], filename: &str) -> Result<()> {
+fn compute_contribution(
+    parameters: CeremonyParameters,
+    custom_seed: bool,
+    challenge: &[u8],
+    filename: &str,
+) -> Result<()> {
     let rand_source = if custom_seed {
         let seed_str = io::get_user_input(
             "Enter your custom random seed (64 characters / 32 bytes in hexadecimal format without a '0x' prefix):"
@@ -203,17 +214,20 @@ fn compute_contribution(custom_seed: bool, challenge: &[u8], filename: &str) ->
             "Frenetically type or enter your alternative source of entropy:".bright_yellow(),
             None,
         )?;
-        RandomSource::Entropy(entropy)
+        RandomSource::Mixed(entropy)
     };
 
     println!("Computation of your contribution in progress...");
 
     let writer = OpenOptions::new().append(true).open(filename)?;
+    let progress_bar = progress::computation_progress_bar();
+    let progress = |completed, total| {
+        progress_bar.set_length(total);
+        progress_bar.set_position(completed);
+    };
 
-    #[cfg(debug_assertions)]
-    Computation::contribute_test_masp(challenge, writer, &rand_source);
-    #[cfg(not(debug_assertions))]
-    Computation::contribute_masp(challenge, writer, &rand_source);
+    Computation::contribute_parameters_with_progress(parameters, challenge, writer, &rand_source, progress);
+    progress_bar.finish();
 
     println!(
         "{}",
@@ -224,7 +238,8 @@ fn compute_contribution(custom_seed: bool, challenge: &[u8], filename: &str) ->
     Ok(())
 }
 
-/// Performs the contribution sequence. Returns the round height of the contribution.
+/// Performs the contribution sequence, resuming it from the checkpoint of an interrupted contribution if any. Returns
+/// the summary of the contribution.
 #[inline(always)]
 async fn contribute(
     client: &Client,
@@ -232,34 +247,45 @@ async fn contribute(
     keypair: &KeyPair,
     mut contrib_info: ContributionInfo,
     heartbeat_handle: &JoinHandle<()>,
-) -> Result<u64> {
-    // Get the necessary info to compute the contribution
-    println!("{} Locking chunk", "[4/11]".bold().dimmed());
-    let locked_locators = requests::get_lock_chunk(client, coordinator, keypair).await?;
-    contrib_info.timestamps.challenge_locked = Utc::now();
-    let end_lock_time = contrib_info.timestamps.challenge_locked + chrono::Duration::minutes(20);
-    println!(
-        "{}",
-        format!("From now on, you will have a maximum of 20 minutes to contribute and upload your contribution after which you will be dropped out of the ceremony!\nYour time starts now on {} and ends in 20 minutes on {}  \nHave fun!",
-        contrib_info.timestamps.challenge_locked.to_rfc2822(),
-        end_lock_time.to_rfc2822()).bright_cyan()
-    );
+    max_upload_bytes_per_sec: Option<NonZeroU64>,
+    checkpoint: Option<ContributionCheckpoint>,
+) -> Result<ContributionSummary> {
+    let mut checkpoint = match checkpoint {
+        Some(checkpoint) => {
+            contrib_info = checkpoint.contrib_info.clone();
+            checkpoint
+        }
+        None => {
+            // Get the necessary info to compute the contribution
+            output::step(4, 11, tr(Message::StepLockingChunk));
+            let locked_locators = requests::get_lock_chunk(client, coordinator, keypair).await?;
+            contrib_info.timestamps.challenge_locked = Utc::now();
+            let end_lock_time = contrib_info.timestamps.challenge_locked + chrono::Duration::minutes(20);
+            println!(
+                "{}",
+                format!("From now on, you will have a maximum of 20 minutes to contribute and upload your contribution after which you will be dropped out of the ceremony!\nYour time starts now on {} and ends in 20 minutes on {}  \nHave fun!",
+                contrib_info.timestamps.challenge_locked.to_rfc2822(),
+                end_lock_time.to_rfc2822()).bright_cyan()
+            );
+            let response_locator = locked_locators.next_contribution();
+            contrib_info.ceremony_round = response_locator.round_height();
+
+            // Check that the challenge and the contribution fit on disk before downloading anything
+            let output_dir = std::env::current_dir()?;
+            disk_space::check_disk_space(
+                &output_dir,
+                response_locator.round_height(),
+                response_locator.contribution_id(),
+            )?;
+
+            let checkpoint = ContributionCheckpoint::new(keypair.clone(), locked_locators, contrib_info.clone());
+            checkpoint.write(CHECKPOINT_FILE_NAME)?;
+            checkpoint
+        }
+    };
+    let locked_locators = checkpoint.locked_locators.clone();
     let response_locator = locked_locators.next_contribution();
-    let round_height = response_locator.round_height();
-    contrib_info.ceremony_round = round_height;
-
-    let challenge_url = requests::get_challenge_url(client, coordinator, keypair, &round_height).await?;
-    println!("{} Getting challenge", "[5/11]".bold().dimmed());
-    let mut challenge_stream = requests::get_challenge(client, challenge_url.as_str()).await?;
-    let progress_bar = get_progress_bar(challenge_stream.1);
-    let mut challenge: Vec<u8> = Vec::new();
-    while let Some(b) = challenge_stream.0.next().await {
-        let b = b?;
-        challenge.extend_from_slice(&b);
-        progress_bar.inc(b.len() as u64);
-    }
-    progress_bar.finish();
-    contrib_info.timestamps.challenge_downloaded = Utc::now();
+    let round_height = checkpoint.round_height;
 
     // Saves the challenge locally, in case the contributor is paranoid and wants to double check himself. It is also used in the offline contrib path
     let challenge_filename = if contrib_info.is_another_machine {
@@ -267,15 +293,39 @@ async fn contribute(
     } else {
         format!("namada_challenge_round_{}.params", round_height)
     };
-    let mut challenge_writer = async_fs::File::create(challenge_filename.as_str()).await?;
-    challenge_writer.write_all(&challenge.as_slice()).await?;
+    let challenge = if checkpoint.completed < ContributionStep::Download {
+        output::step(5, 11, tr(Message::StepGettingChallenge));
+        let (challenge_stream, challenge_len) =
+            requests::download_challenge(client, coordinator, keypair, &round_height).await?;
+        let challenge =
+            progress::collect_with_progress(challenge_stream, progress::transfer_progress_bar(challenge_len)).await?;
+        contrib_info.timestamps.challenge_downloaded = Utc::now();
+
+        let mut challenge_writer = async_fs::File::create(challenge_filename.as_str()).await?;
+        challenge_writer.write_all(&challenge.as_slice()).await?;
+        challenge_writer.flush().await?;
+        checkpoint.set_challenge(Path::new(&challenge_filename))?;
+        checkpoint.complete(ContributionStep::Download, &contrib_info, CHECKPOINT_FILE_NAME)?;
+
+        challenge
+    } else {
+        async_fs::read(challenge_filename.as_str()).await?
+    };
 
     let challenge_hash = calculate_hash(challenge.as_ref());
     debug!("Challenge hash is {}", pretty_hash!(&challenge_hash));
     debug!("Challenge length {}", challenge.len());
 
-    // Prepare contribution file with the challege hash
-    println!("{} Setting up contribution file", "[6/11]".bold().dimmed());
+    // The parameters of the ceremony select the circuits of the challenge and the size of the contribution
+    let parameters = requests::get_ceremony_status(client, coordinator).await?.parameters;
+    let contribution_size = challenge.len() as u64 + Object::contribution_size_increase(parameters);
+
+    // Bind the challenge handed over to the offline machine to the locked contribution
+    let transfer_manifest = TransferManifest::new(&response_locator, &challenge_hash);
+    if contrib_info.is_another_machine && checkpoint.completed < ContributionStep::Compute {
+        transfer_manifest.write(OFFLINE_MANIFEST_FILE_NAME)?;
+    }
+
     let contrib_filename = if contrib_info.is_another_machine {
         Arc::new(OFFLINE_CONTRIBUTION_FILE_NAME.to_string())
     } else {
@@ -285,98 +335,119 @@ async fn contribute(
             keypair.pubkey()
         ))
     };
-    let mut response_writer = async_fs::File::create(contrib_filename.as_str()).await?;
-    response_writer.write_all(challenge_hash.to_vec().as_ref()).await?;
-
-    // Compute contribution
-    println!("{} Computing contribution", "[7/11]".bold().dimmed());
-
-    let contrib_filename_copy = contrib_filename.clone();
-    contrib_info.timestamps.start_computation = Utc::now();
-    if contrib_info.is_another_machine {
-        tokio::task::spawn_blocking(move || compute_contribution_offline()).await??;
-    } else {
-        let custom_seed = contrib_info.is_own_seed_of_randomness;
-        if custom_seed {
-            println!("{}", CUSTOM_SEED_MSG_YES.bright_cyan());
+    let contribution = if checkpoint.completed < ContributionStep::Compute {
+        // Prepare contribution file with the challege hash
+        output::step(6, 11, tr(Message::StepSettingUpContributionFile));
+        let mut response_writer = async_fs::File::create(contrib_filename.as_str()).await?;
+        response_writer.write_all(challenge_hash.to_vec().as_ref()).await?;
+
+        // Compute contribution
+        output::step(7, 11, tr(Message::StepComputingContribution));
+
+        let contrib_filename_copy = contrib_filename.clone();
+        contrib_info.timestamps.start_computation = Utc::now();
+        if contrib_info.is_another_machine {
+            tokio::task::spawn_blocking(move || compute_contribution_offline()).await??;
         } else {
-            println!("{}", CUSTOM_SEED_MSG_NO.bright_cyan());
+            let custom_seed = contrib_info.is_own_seed_of_randomness;
+            if custom_seed {
+                println!("{}", CUSTOM_SEED_MSG_YES.bright_cyan());
+            } else {
+                println!("{}", CUSTOM_SEED_MSG_NO.bright_cyan());
+            }
+            tokio::task::spawn_blocking(move || {
+                compute_contribution(
+                    parameters,
+                    custom_seed,
+                    challenge.as_ref(),
+                    contrib_filename_copy.as_str(),
+                )
+            })
+            .await??;
         }
-        tokio::task::spawn_blocking(move || {
-            compute_contribution(custom_seed, challenge.as_ref(), contrib_filename_copy.as_str())
+        let contrib_filename_copy = contrib_filename.clone();
+        let contribution = tokio::task::spawn_blocking(move || {
+            get_file_as_byte_vec(contrib_filename_copy.as_str(), contribution_size)
         })
         .await??;
-    }
-    let contrib_filename_copy = contrib_filename.clone();
-    let contribution = tokio::task::spawn_blocking(move || {
-        get_file_as_byte_vec(
-            contrib_filename_copy.as_str(),
-            round_height,
-            response_locator.contribution_id(),
-        )
-    })
-    .await??;
 
-    contrib_info.timestamps.end_computation = Utc::now();
-    trace!("Response writer {:?}", response_writer);
-    println!(
-        "{}",
-        format!(
-            "Completed contribution in {} seconds",
-            (contrib_info.timestamps.end_computation - contrib_info.timestamps.start_computation).num_seconds()
-        )
-        .green()
-        .bold()
-    );
-
-    // Update contribution info
-    println!("{} Updating contribution info", "[8/11]".bold().dimmed());
-    let contribution_file_hash = calculate_hash(contribution.as_ref());
-    let contribution_file_hash_str = hex::encode(contribution_file_hash);
-    debug!("Contribution hash is {}", contribution_file_hash_str);
-    debug!("Contribution length: {}", contribution.len());
-    contrib_info.contribution_file_hash = contribution_file_hash_str;
-    contrib_info.contribution_file_signature =
-        Production.sign(keypair.sigkey(), contrib_info.contribution_file_hash.as_str())?;
-    let challenge_hash_len = challenge_hash.len();
-    contrib_info.contribution_hash = hex::encode(calculate_hash(&contribution[challenge_hash_len..]));
-    contrib_info.contribution_hash_signature =
-        Production.sign(keypair.sigkey(), contrib_info.contribution_hash.as_str())?;
-
-    // Send contribution to the coordinator
-    let contribution_state = ContributionState::new(challenge_hash.to_vec(), contribution_file_hash.to_vec(), None)?;
-
-    let signature = Production.sign(keypair.sigkey(), &contribution_state.signature_message()?)?;
-    let contribution_file_signature = ContributionFileSignature::new(signature, contribution_state)?;
-
-    let (contribution_url, contribution_signature_url) =
-        requests::get_contribution_url(client, coordinator, keypair, &round_height).await?;
-    println!("{} Uploading contribution", "[9/11]".bold().dimmed());
-    let contrib_file = async_fs::File::open(contrib_filename.as_str()).await?;
-    let contrib_size = async_fs::metadata(contrib_filename.as_str()).await?.len();
-    let mut stream = ReaderStream::new(contrib_file);
-    let pb = get_progress_bar(contrib_size);
-    let pb_clone = pb.clone();
-
-    let contrib_stream = try_stream! {
-        while let Some(b) = stream.next().await {
-            let b = b?;
-            pb.inc(b.len() as u64);
-            yield b;
+        // Check that the offline contribution has been computed against the locked challenge
+        if contrib_info.is_another_machine {
+            TransferManifest::read(OFFLINE_MANIFEST_FILE_NAME)?
+                .verify_contribution(&transfer_manifest, &contribution)?;
         }
+
+        contrib_info.timestamps.end_computation = Utc::now();
+        trace!("Response writer {:?}", response_writer);
+        println!(
+            "{}",
+            format!(
+                "Completed contribution in {} seconds",
+                (contrib_info.timestamps.end_computation - contrib_info.timestamps.start_computation).num_seconds()
+            )
+            .green()
+            .bold()
+        );
+        checkpoint.set_contribution(Path::new(contrib_filename.as_str()))?;
+        checkpoint.complete(ContributionStep::Compute, &contrib_info, CHECKPOINT_FILE_NAME)?;
+
+        contribution
+    } else {
+        let contrib_filename_copy = contrib_filename.clone();
+        tokio::task::spawn_blocking(move || get_file_as_byte_vec(contrib_filename_copy.as_str(), contribution_size))
+            .await??
     };
 
-    requests::upload_chunk(
-        client,
-        contribution_url.as_str(),
-        contribution_signature_url.as_str(),
-        contrib_stream,
-        contrib_size,
-        &contribution_file_signature,
-    )
-    .await?;
-    pb_clone.finish();
-    contrib_info.timestamps.end_contribution = Utc::now();
+    // Update contribution info
+    output::step(8, 11, tr(Message::StepUpdatingContributionInfo));
+    let hashes = ContributionHashes::new(contribution.as_ref());
+    let contribution_file_hash = hashes.file_hash.clone();
+    debug!("Contribution hash is {}", hex::encode(&contribution_file_hash));
+    debug!("Contribution length: {}", contribution.len());
+    info::sign_contribution_hashes(&mut contrib_info, &hashes, keypair)?;
+
+    if checkpoint.completed < ContributionStep::Upload {
+        // Send contribution to the coordinator
+        let contribution_state =
+            ContributionState::new(challenge_hash.to_vec(), contribution_file_hash.to_vec(), None)?;
+
+        let signature = keypair.sign(&contribution_state.signature_message()?)?;
+        let contribution_file_signature = ContributionFileSignature::new(signature, contribution_state)?;
+
+        let (_, contribution_signature_url) =
+            requests::get_contribution_url(client, coordinator, keypair, &round_height).await?;
+        output::step(9, 11, tr(Message::StepUploadingContribution));
+        let pb = progress::transfer_progress_bar(contribution.len() as u64);
+        // Resume the upload from the offset committed before a restart, if any
+        let uploaded = checkpoint.uploaded;
+        pb.set_position(uploaded);
+
+        requests::upload_chunk(
+            client,
+            coordinator,
+            keypair,
+            &locked_locators.next_contribution(),
+            &contribution,
+            uploaded,
+            max_upload_bytes_per_sec,
+            |committed| {
+                pb.set_position(committed);
+                if let Err(e) = checkpoint.set_uploaded(committed, CHECKPOINT_FILE_NAME) {
+                    warn!("Couldn't record the progress of the upload: {}", e);
+                }
+            },
+        )
+        .await?;
+        requests::upload_contribution_signature(
+            client,
+            contribution_signature_url.as_str(),
+            &contribution_file_signature,
+        )
+        .await?;
+        pb.finish();
+        contrib_info.timestamps.end_contribution = Utc::now();
+        checkpoint.complete(ContributionStep::Upload, &contrib_info, CHECKPOINT_FILE_NAME)?;
+    }
 
     // Compute signature of contributor info
     contrib_info
@@ -384,7 +455,7 @@ async fn contribute(
         .expect(&format!("{}", "Error while signing the contribution info".red().bold()));
 
     // Write contribution info file and send it to the Coordinator
-    println!("{} Uploading contribution info", "[10/11]".bold().dimmed());
+    output::step(10, 11, tr(Message::StepUploadingContributionInfo));
     async_fs::write(
         format!("namada_contributor_info_round_{}.json", contrib_info.ceremony_round),
         &serde_json::to_vec(&contrib_info)?,
@@ -393,16 +464,21 @@ async fn contribute(
     requests::post_contribution_info(client, coordinator, keypair, &contrib_info).await?;
 
     // Notify contribution to the coordinator for the verification
-    println!(
-        "{} Notifying the coordinator of your uploaded contribution.\nYour contribution is being processed... This might take a minute...",
-        "[11/11]".bold().dimmed()
-    );
+    output::step(11, 11, tr(Message::StepNotifyingCoordinator));
     let post_chunk_req = PostChunkRequest::new(
         round_height,
         locked_locators.next_contribution(),
         locked_locators.next_contribution_file_signature(),
     );
-    requests::post_contribute_chunk(client, coordinator, keypair, &post_chunk_req).await?;
+    let receipt = requests::post_contribute_chunk(client, coordinator, keypair, &post_chunk_req).await?;
+
+    // Keep the receipt signed by the coordinator alongside the contribution info, as a proof of participation. The
+    // contribution has already been accepted, so failing to save the receipt must not fail it
+    let receipt_path = format!("namada_contribution_receipt_round_{}.json", receipt.round_height);
+    if let Err(e) = async_fs::write(&receipt_path, &serde_json::to_vec(&receipt)?).await {
+        warn!("Couldn't save the contribution receipt to \"{}\": {}", receipt_path, e);
+    }
+    checkpoint.complete(ContributionStep::Notify, &contrib_info, CHECKPOINT_FILE_NAME)?;
 
     // Interrupt heartbeat, to prevent heartbeating during verification
     // NOTE: need to manually cancel the heartbeat task because, by default, async runtimes use detach on drop strategy
@@ -413,10 +489,45 @@ async fn contribute(
     //  We don't need to await the hearbeat future
     heartbeat_handle.abort();
 
-    Ok(round_height)
+    Ok(ContributionSummary::new(
+        &contrib_info,
+        challenge.len() as u64,
+        contribution.len() as u64,
+    ))
+}
+
+/// Locks the chunk and saves its challenge and transfer manifest in `output`, without computing nor uploading the
+/// contribution.
+async fn fetch_challenge(client: &Client, coordinator: &Url, keypair: &KeyPair, output: &Path) -> Result<()> {
+    output::step(4, 11, tr(Message::StepLockingChunkAndGettingChallenge));
+    let fetched = fetch::fetch_challenge(client, coordinator, keypair, output).await?;
+    let end_lock_time = Utc::now() + chrono::Duration::minutes(20);
+
+    println!(
+        "{}",
+        format!(
+            "The challenge of {} has been saved in \"{}\" together with the transfer manifest \"{}\".",
+            fetched.manifest.label(),
+            fetched.challenge_path.display(),
+            fetched.manifest_path.display()
+        )
+        .green()
+        .bold()
+    );
+    println!(
+        "{}",
+        format!(
+            "The chunk is still locked: the contribution must be computed with \"namada-ts contribute offline\" and uploaded before {}, after which you will be dropped out of the ceremony!",
+            end_lock_time.to_rfc2822()
+        )
+        .bright_yellow()
+    );
+
+    Ok(())
 }
 
-/// Waits in line until it's time to contribute
+/// Waits in line until it's time to contribute. Transient errors while polling the coordinator are retried, fatal
+/// ones abort the contribution.
 #[inline(always)]
 async fn contribution_loop(
     client: Arc<Client>,
@@ -424,14 +535,22 @@ async fn contribution_loop(
     keypair: Arc<KeyPair>,
     token: String,
     mut contrib_info: ContributionInfo,
-) {
-    println!("{} Joining queue", "[3/11]".bold().dimmed());
-
-    let cohort = requests::post_join_queue(&client, &coordinator, &keypair, &token)
+    max_upload_bytes_per_sec: Option<NonZeroU64>,
+    fetch_output: Option<PathBuf>,
+    mut checkpoint: Option<ContributionCheckpoint>,
+) -> Result<()> {
+    // A resumed contribution already holds its lock
+    if checkpoint.is_none() {
+        output::step(3, 11, tr(Message::StepJoiningQueue));
+
+        let cohort = requests::retry_transient(MAX_REQUEST_ATTEMPTS, REQUEST_RETRY_DELAY, || {
+            requests::post_join_queue(&client, &coordinator, &keypair, &token)
+        })
         .await
-        .expect(&format!("{}", "Couldn't join the queue".red().bold()));
-    contrib_info.timestamps.joined_queue = Utc::now();
-    contrib_info.joined_cohort = cohort;
+        .context("Couldn't join the queue")?;
+        contrib_info.timestamps.joined_queue = Utc::now();
+        contrib_info.joined_cohort = cohort;
+    }
 
     // Spawn heartbeat task to prevent the Coordinator from
     // dropping the contributor out of the ceremony in the middle of a contribution.
@@ -439,43 +558,57 @@ async fn contribution_loop(
     let client_cnt = client.clone();
     let coordinator_cnt = coordinator.clone();
     let keypair_cnt = keypair.clone();
+    let (heartbeat_error_tx, mut heartbeat_error_rx) = oneshot::channel();
 
     let heartbeat_handle = tokio::task::spawn(async move {
-        loop {
-            if let Err(e) = requests::post_heartbeat(&client_cnt, &coordinator_cnt, &keypair_cnt).await {
-                eprintln!(
-                    "{}",
-                    format!("{}: {}", "Heartbeat error".red().bold(), e.to_string().red().bold())
-                );
-            }
-            time::sleep(UPDATE_TIME).await;
-        }
+        // Transient failures are retried in the loop, which only returns once heartbeating is pointless
+        let e = requests::heartbeat_loop(&client_cnt, &coordinator_cnt, keypair_cnt.as_ref(), UPDATE_TIME).await;
+        // The receiver is gone if the contribution has already stopped
+        heartbeat_error_tx.send(e).ok();
     });
+    // The contributor is not heartbeating anymore once its contribution has been notified
+    if checkpoint.as_ref().map_or(false, |c| c.next_step().is_none()) {
+        heartbeat_handle.abort();
+    }
 
-    let mut round_height = 0;
-    let mut status_count = 1;
+    let mut round_height = checkpoint.as_ref().map_or(0, |c| c.round_height);
+    let mut summary = None;
+    let mut status_output = output::QueueStatus::default();
     let queue_timer = Instant::now();
 
-    let init_queue_status = requests::get_contributor_queue_status(&client, &coordinator, &keypair)
-        .await
-        .expect(&format!("{}", "Couldn't get the status of contributor".red().bold()));
+    let init_queue_status = requests::retry_transient(MAX_REQUEST_ATTEMPTS, REQUEST_RETRY_DELAY, || {
+        requests::get_contributor_queue_status(&client, &coordinator, &keypair)
+    })
+    .await
+    .context("Couldn't get the status of contributor")?;
     let mut init_queue_position = 0;
     match init_queue_status {
-        ContributorStatus::Queue(position, _) => {
+        ContributorStatus::Queue(position, _, _) => {
             init_queue_position = position;
         }
         _ => {}
     }
 
     loop {
-        // Check the contributor's position in the queue
-        let queue_status = requests::get_contributor_queue_status(&client, &coordinator, &keypair)
-            .await
-            .expect(&format!("{}", "Couldn't get the status of contributor".red().bold()));
+        // Stop as soon as the coordinator rejects the heartbeats, e.g. because the contributor has been dropped
+        if let Ok(e) = heartbeat_error_rx.try_recv() {
+            return Err(anyhow::Error::new(e).context("The coordinator rejected the heartbeat"));
+        }
+
+        // Check the contributor's position in the queue. Transient errors are retried at the next poll
+        let queue_status = match requests::get_contributor_queue_status(&client, &coordinator, &keypair).await {
+            Ok(status) => status,
+            Err(e) if e.is_transient() => {
+                output::report_error(tr(Message::ContributorStatusFailed), &e.into());
+                time::sleep(UPDATE_TIME).await;
+                continue;
+            }
+            Err(e) => return Err(anyhow::Error::new(e).context(tr(Message::ContributorStatusFailed))),
+        };
 
         match queue_status {
-            ContributorStatus::Queue(position, size) => {
-                let msg = format!(
+            ContributorStatus::Queue(position, size, start_at) => {
+                let mut msg = format!(
                     "Queue position: {}\nQueue size: {}\nExpected waiting time: {} min\nMax waiting time: {} min\nElapsed time in queue: {} min",
                     position,
                     size,
@@ -483,32 +616,59 @@ async fn contribution_loop(
                     init_queue_position * 20,
                     queue_timer.elapsed().as_secs() / 60
                 );
-
-                let max_len = msg.split("\n").map(|x| x.len()).max().unwrap();
-                let stripe = "=".repeat(max_len);
-
-                if status_count > 1 {
-                    // Clear previous status from terminal
-                    execute!(std::io::stdout(), ScrollDown(8), Clear(ClearType::FromCursorDown)).unwrap();
+                if let Some(start_at) = start_at {
+                    msg.push_str(&format!("\nEstimated start time: {}", start_at));
                 }
-                println!(
-                    "{}{}\n{}\n{}\n{}",
-                    "Queue status - poll #", status_count, stripe, msg, stripe,
-                );
-                status_count += 1;
+
+                status_output.update(&mut std::io::stdout(), position, &msg).unwrap();
             }
-            ContributorStatus::Round => {
-                round_height = contribute(&client, &coordinator, &keypair, contrib_info.clone(), &heartbeat_handle)
+            ContributorStatus::Round if fetch_output.is_some() => {
+                fetch_challenge(&client, &coordinator, &keypair, fetch_output.as_deref().unwrap())
                     .await
-                    .expect(&format!("{}", "Contribution failed".red().bold()));
+                    .context("Couldn't fetch the challenge")?;
+                heartbeat_handle.abort();
+                break;
+            }
+            ContributorStatus::Round => {
+                let contribution_summary = contribute(
+                    &client,
+                    &coordinator,
+                    &keypair,
+                    contrib_info.clone(),
+                    &heartbeat_handle,
+                    max_upload_bytes_per_sec,
+                    checkpoint.take(),
+                )
+                .await
+                .context("Contribution failed")?;
+                round_height = contribution_summary.round_height;
+                summary = Some(contribution_summary);
+            }
+            ContributorStatus::Stale(evict_at) => {
+                // Heartbeats are retried in the background, keep polling until the connection recovers
+                println!(
+                    "{}",
+                    format!(
+                        "The coordinator is not receiving the heartbeats of this contributor: it will be dropped from the ceremony at {} unless the connection recovers.",
+                        evict_at
+                    )
+                    .yellow()
+                    .bold()
+                );
             }
             ContributorStatus::Finished => {
+                ContributionCheckpoint::remove(CHECKPOINT_FILE_NAME)?;
                 let content = fs::read(&format!("namada_contributor_info_round_{}.json", round_height))
-                    .expect(&format!("{}", "Couldn't read the contributor info file".red().bold()));
-                let contrib_info: ContributionInfo = serde_json::from_slice(&content).unwrap();
+                    .context("Couldn't read the contributor info file")?;
+                let contrib_info: ContributionInfo =
+                    serde_json::from_slice(&content).context("The contributor info file is not valid")?;
 
+                // A contribution notified before a restart wasn't transferred by this run, there's nothing to summarize
+                if let Some(summary) = &summary {
+                    println!("{}\n", summary.bright_cyan());
+                }
                 println!("{}\n{}\n\nI've contributed to @namadanetwork #NamadaTrustedSetup at round #{} with the contribution hash {}. Let's enable #interchain privacy.\n\n{}",
-                                                "Done! Thank you for your contribution! If your contribution is valid, it will appear on ceremony.namada.net. Check it out!".green().bold(),
+                                                tr(Message::ContributionDone).green().bold(),
                                                 "If you’d like to share that you contributed with your frens and the world, you can use:".bright_cyan(),
                                                 round_height,
                                 contrib_info.contribution_hash,
@@ -518,11 +678,12 @@ async fn contribution_loop(
 
                 // Attestation
                 if "n"
-                    == io::get_user_input(
+                    == io::get_user_input_or_default(
                         "Would you like to provide an attestation of your contribution? [y/n]".bright_yellow(),
                         Some(&Regex::new(r"^(?i)[yn]$").unwrap()),
-                    )
-                    .unwrap()
+                        "n",
+                        PROMPT_TIMEOUT,
+                    )?
                 {
                     break;
                 } else {
@@ -531,34 +692,32 @@ async fn contribution_loop(
                             "Please enter a valid url for your attestation (starting with http or https):"
                                 .bright_yellow(),
                             None,
-                        )
-                        .unwrap();
+                        )?;
                         if Url::parse(attestation_url.as_str()).is_ok() {
                             // Send attestation to coordinator
-                            requests::post_attestation(
-                                &client,
-                                &coordinator,
-                                &keypair,
-                                &(round_height, attestation_url),
-                            )
+                            let attestation = (round_height, attestation_url);
+                            requests::retry_transient(MAX_REQUEST_ATTEMPTS, REQUEST_RETRY_DELAY, || {
+                                requests::post_attestation(&client, &coordinator, &keypair, &attestation)
+                            })
                             .await
-                            .expect(&format!("{}", "Failed attestation upload".red().bold()));
-                            return;
+                            .context("Failed attestation upload")?;
+                            return Ok(());
                         }
                     }
                 }
             }
             ContributorStatus::Banned => {
-                println!(
-                    "{}",
-                    "This contributor has been banned from the ceremony because of an invalid contribution."
-                        .red()
-                        .bold()
-                );
+                ContributionCheckpoint::remove(CHECKPOINT_FILE_NAME)?;
+                println!("{}", tr(Message::ContributorBanned).red().bold());
+                break;
+            }
+            ContributorStatus::Rejected { reason } => {
+                ContributionCheckpoint::remove(CHECKPOINT_FILE_NAME)?;
+                println!("{} {}", tr(Message::ContributionRejected).red().bold(), reason.red());
                 break;
             }
             ContributorStatus::Other => {
-                println!("{}", "Did not retrieve a valid contributor state.".red().bold());
+                println!("{}", tr(Message::InvalidContributorStatus).red().bold());
                 break;
             }
         }
@@ -566,6 +725,21 @@ async fn contribution_loop(
         // Get status updates
         time::sleep(UPDATE_TIME).await;
     }
+
+    Ok(())
+}
+
+/// Builds the client of the coordinator, routing the requests through the SOCKS5 proxy and pinning the certificate of
+/// the coordinator if requested.
+fn http_client(url: &CoordinatorUrl) -> Client {
+    let cert_pin = url.cert_pin.as_ref().map(|pin| (&url.coordinator, pin));
+    match requests::build_client(url.socks5.as_deref(), cert_pin) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("{}", format!("Failed to configure the HTTP client: {}", e).red().bold());
+            process::exit(1);
+        }
+    }
 }
 
 #[inline(always)]
@@ -578,8 +752,17 @@ async fn close_ceremony(client: &Client, coordinator: &Url, keypair: &KeyPair) {
 
 #[cfg(debug_assertions)]
 #[inline(always)]
-async fn get_contributions(coordinator: &Url) {
-    match requests::get_contributions_info(coordinator).await {
+async fn get_contributions(range: &phase2_cli::ContributionsRange) {
+    match requests::get_contributions_info(
+        &http_client(&range.url),
+        &range.url.coordinator,
+        range.from_round,
+        range.to_round,
+        range.offset,
+        range.page_size,
+    )
+    .await
+    {
         Ok(contributions) => {
             let contributions_str = std::str::from_utf8(&contributions).unwrap();
             println!("Contributions:\n{}", contributions_str)
@@ -589,8 +772,8 @@ async fn get_contributions(coordinator: &Url) {
 }
 
 #[inline(always)]
-async fn get_coordinator_state(coordinator: &Url, secret: &str) {
-    match requests::get_coordinator_state(coordinator, secret).await {
+async fn get_coordinator_state(url: &CoordinatorUrl, secret: &str) {
+    match requests::get_coordinator_state(&http_client(url), &url.coordinator, secret).await {
         Ok(state) => {
             let state_str = std::str::from_utf8(&state).unwrap();
             println!("Coordinator state:\n{}", state_str)
@@ -632,110 +815,264 @@ async fn update_cohorts(client: &Client, coordinator: &Url, keypair: &KeyPair) {
 enum Branch {
     AnotherMachine,
     Default(bool),
+    FetchChallenge(PathBuf),
 }
 
 /// Performs the entire contribution cycle
 #[inline(always)]
-async fn contribution_prelude(url: CoordinatorUrl, token: String, branch: Branch) {
+async fn contribution_prelude(
+    url: CoordinatorUrl,
+    token: String,
+    branch: Branch,
+    max_upload_bytes_per_sec: Option<NonZeroU64>,
+    anonymous: bool,
+    keypair_opt: KeypairOpt,
+) {
     // Check the token info
     let decoded_bytes = bs58::decode(token.clone()).into_vec();
-    if let Ok(token_bytes) = decoded_bytes {
+    let mut token_data: Token = if let Ok(token_bytes) = decoded_bytes {
         let decoded_token = String::from_utf8(token_bytes).expect("Can't decode the token");
-        let token_data: Token = serde_json::from_str(&decoded_token).expect("Can't deserialize the token.");
-        match token_data.is_valid_cohort() {
-            phase2_cli::TokenCohort::Finished => {
-                println!("Your cohort round is {} and is already completed.", token_data.index);
-                process::exit(0);
-            }
-            phase2_cli::TokenCohort::Pending => {
-                let token_from_datetime = DateTime::<Utc>::from(UNIX_EPOCH + Duration::from_secs(token_data.from));
-                let token_to_datetime = DateTime::<Utc>::from(UNIX_EPOCH + Duration::from_secs(token_data.to));
-                println!(
-                    "Your cohort round is {} and will start at {} and finish at {}.",
-                    token_data.index, token_from_datetime, token_to_datetime
-                );
-                process::exit(0);
-            }
-            _ => (),
-        }
+        serde_json::from_str(&decoded_token).expect("Can't deserialize the token.")
     } else {
-        eprintln!("{}", "The token provided is not base58 encoded.".red().bold());
+        eprintln!("{}", tr(Message::TokenNotBase58).red().bold());
         process::exit(0);
     };
 
     // Check that the passed-in coordinator url is correct
-    let client = Client::new();
+    let client = http_client(&url);
     if requests::ping_coordinator(&client, &url.coordinator)
         .await.is_err() {
-            eprintln!("{}", "ERROR: could not contact the Coordinator, please check the url you provided".red().bold());
+            eprintln!("{}", tr(Message::CoordinatorUnreachable).red().bold());
             process::exit(1);
         };
 
+    // Check that this client speaks the same protocol of the coordinator before joining the queue
+    if let Err(e) = requests::get_protocol_version(&client, &url.coordinator).await {
+        match e {
+            RequestError::IncompatibleVersion(_) => eprintln!(
+                "{}",
+                "ERROR: this client is not compatible with the Coordinator, please upgrade your client to the latest version"
+                    .red()
+                    .bold()
+            ),
+            _ => eprintln!(
+                "{}",
+                format!("ERROR: could not check the version of the Coordinator: {}", e).red().bold()
+            ),
+        }
+        process::exit(1);
+    }
+
+    // Check that the cohort of the token is open, preferring the schedule of the coordinator to the times in the token
+    match requests::get_cohort_schedule(&client, &url.coordinator).await {
+        Ok(schedule) => token_data.update_window(&schedule),
+        Err(e) => debug!("Couldn't retrieve the cohort schedule, relying on the token: {}", e),
+    }
+    match token_data.is_valid_cohort() {
+        phase2_cli::TokenCohort::Finished => {
+            println!("Your cohort round is {} and is already completed.", token_data.index);
+            process::exit(0);
+        }
+        phase2_cli::TokenCohort::Pending => {
+            let token_from_datetime = DateTime::<Utc>::from(UNIX_EPOCH + Duration::from_secs(token_data.from));
+            let token_to_datetime = DateTime::<Utc>::from(UNIX_EPOCH + Duration::from_secs(token_data.to));
+            println!(
+                "Your cohort round is {} and will start at {} and finish at {}. You can't join the ceremony before it opens, please come back then.",
+                token_data.index, token_from_datetime, token_to_datetime
+            );
+            process::exit(0);
+        }
+        _ => (),
+    }
+
     println!("{}", ASCII_LOGO.bright_yellow());
-    println!("{}", "Welcome to the Namada Trusted Setup Ceremony!".bold());
+    println!("{}", tr(Message::Welcome).bold());
 
-    match branch {
+    match &branch {
         Branch::AnotherMachine => println!(
             "{}\n{}",
             "DISCLAIMER".bright_red().underline().bold(),
             "The \"--another-machine\" flag is active.\nThis feature is designed for advanced users that want to run the computation of the parameters on another machine.\n".bright_red()
         ),
-        Branch::Default(custom_seed) if custom_seed => println!(
+        Branch::Default(custom_seed) if *custom_seed => println!(
             "{}\n{}",
             "DISCLAIMER".bright_red().underline().bold(),
             "The \"--custom-seed\" flag is active.\nThis feature is designed for advanced users that want to give a custom random seed for the ChaCha RNG.\n".bright_red()
         ),
+        Branch::FetchChallenge(_) => println!(
+            "{}\n{}",
+            "DISCLAIMER".bright_red().underline().bold(),
+            "The \"fetch-challenge\" command only downloads the challenge, to be computed with \"namada-ts contribute offline\" on another machine.\nThe chunk stays locked once the challenge is saved: the lock deadline still applies, and you will be dropped out of the ceremony if the contribution isn't uploaded in time.\n".bright_red()
+        ),
         _ => ()
     }
 
-    // Contribute
-    println!("{} Initializing contribution", "[1/11]".bold().dimmed());
-    let mut contrib_info = tokio::task::spawn_blocking(initialize_contribution)
-        .await
-        .unwrap()
-        .expect(&format!("{}", "Error while initializing the contribution".red().bold()));
-    println!("{} Generating keypair", "[2/11]".bold().dimmed());
+    // Resume the contribution interrupted by a restart of the CLI, if any
+    let checkpoint = match &branch {
+        Branch::FetchChallenge(_) => None,
+        _ => match ContributionCheckpoint::resume(CHECKPOINT_FILE_NAME) {
+            Ok(checkpoint) => checkpoint,
+            Err(e) => {
+                output::report_error(tr(Message::ResumeFailed), &e.into());
+                ContributionCheckpoint::remove(CHECKPOINT_FILE_NAME).ok();
+                None
+            }
+        },
+    };
 
-    match branch {
-        Branch::AnotherMachine => contrib_info.is_another_machine = true,
-        Branch::Default(custom_seed) if custom_seed => contrib_info.is_own_seed_of_randomness = true,
-        _ => (),
-    }
+    let fetch_output = match &branch {
+        Branch::FetchChallenge(output) => Some(output.clone()),
+        _ => None,
+    };
 
-    io::get_user_input("Press enter to generate a keypair".bright_yellow(), None).unwrap();
-    let keypair = tokio::task::spawn_blocking(move || io::generate_keypair(KeyPairUser::Contributor))
-        .await
-        .unwrap()
-        .expect(&format!("{}", "Error while generating the keypair".red().bold()));
+    let (keypair, contrib_info) = match &checkpoint {
+        Some(checkpoint) => {
+            println!(
+                "{}",
+                format!(
+                    "Resuming the contribution to round {} interrupted after the {:?} step",
+                    checkpoint.round_height, checkpoint.completed
+                )
+                .bright_cyan()
+            );
+            (checkpoint.keypair.clone(), checkpoint.contrib_info.clone())
+        }
+        None => {
+            // Contribute
+            output::step(1, 11, tr(Message::StepInitializing));
+            let mut contrib_info = tokio::task::spawn_blocking(move || initialize_contribution(anonymous))
+                .await
+                .unwrap()
+                .expect(&format!("{}", "Error while initializing the contribution".red().bold()));
+            output::step(2, 11, tr(Message::StepGeneratingKeypair));
 
-    contrib_info.timestamps.start_contribution = Utc::now();
-    contrib_info.public_key = keypair.pubkey().to_string();
+            match branch {
+                Branch::AnotherMachine | Branch::FetchChallenge(_) => contrib_info.is_another_machine = true,
+                Branch::Default(custom_seed) => contrib_info.is_own_seed_of_randomness = custom_seed,
+            }
 
-    contribution_loop(
+            if keypair_opt.keypair_source.unwrap_or(KeypairSource::Generate) == KeypairSource::Generate {
+                io::get_user_input("Press enter to generate a keypair".bright_yellow(), None).unwrap();
+            }
+            let keypair = tokio::task::spawn_blocking(move || {
+                keypair_opt.load_keypair(KeypairSource::Generate, KeyPairUser::Contributor)
+            })
+            .await
+            .unwrap()
+            .expect(&format!("{}", "Error while loading the keypair".red().bold()));
+
+            contrib_info.timestamps.start_contribution = Utc::now();
+            contrib_info.public_key = keypair.pubkey().to_string();
+
+            (keypair, contrib_info)
+        }
+    };
+
+    if let Err(e) = contribution_loop(
         Arc::new(client),
         Arc::new(url.coordinator),
         Arc::new(keypair),
         token,
         contrib_info,
+        max_upload_bytes_per_sec,
+        fetch_output,
+        checkpoint,
     )
-    .await;
+    .await
+    {
+        output::report_error(tr(Message::ContributionAborted), &e);
+        process::exit(1);
+    }
+}
+
+/// Prints the expected sizes and computation time of a contribution to the ceremony, without joining the queue
+/// Prints the estimate of a contribution, benchmarking this machine only when it's the one computing it.
+async fn estimate_contribution(url: CoordinatorUrl, benchmark: bool) {
+    let client = http_client(&url);
+    let status = match requests::get_ceremony_status(&client, &url.coordinator).await {
+        Ok(status) => status,
+        Err(e) => {
+            output::report_error(tr(Message::CoordinatorUnreachable), &e.into());
+            process::exit(1);
+        }
+    };
+
+    let point_multiplication_time = if benchmark {
+        Some(
+            tokio::task::spawn_blocking(|| Computation::benchmark_point_multiplication(BENCHMARK_SAMPLES))
+                .await
+                .unwrap(),
+        )
+    } else {
+        None
+    };
+
+    println!(
+        "{}",
+        ContributionEstimate::new(&status, point_multiplication_time).bright_cyan()
+    );
 }
 
 #[tokio::main]
 async fn main() {
-    tracing_subscriber::fmt::init();
-    let opt = CeremonyOpt::from_args();
+    let cli = CliOpt::from_args();
+    output::init(cli.verbosity());
+    i18n::init(cli.lang);
+    let keypair_opt = cli.keypair;
+    let opt = cli.command;
+    if opt.coordinator_url().map_or(false, |url| url.trace_http) {
+        requests::enable_http_trace();
+    }
+    if let Some(pubkey) = opt.coordinator_url().and_then(|url| url.coordinator_pubkey) {
+        requests::verify_coordinator_signatures(pubkey);
+    }
 
     match opt {
         CeremonyOpt::Contribute(branch) => {
             match branch {
-                phase2_cli::Branches::AnotherMachine { request } => {
-                    contribution_prelude(request.url, request.token, Branch::AnotherMachine).await
+                phase2_cli::Branches::AnotherMachine {
+                    request, estimate_only, ..
+                } if estimate_only => estimate_contribution(request.url, false).await,
+                phase2_cli::Branches::Default {
+                    request, estimate_only, ..
+                } if estimate_only => estimate_contribution(request.url, true).await,
+                phase2_cli::Branches::AnotherMachine {
+                    request,
+                    max_upload_bytes_per_sec,
+                    anonymous,
+                    ..
+                } => {
+                    contribution_prelude(
+                        request.url,
+                        request.token.expect("The token is required unless estimating the contribution"),
+                        Branch::AnotherMachine,
+                        max_upload_bytes_per_sec,
+                        anonymous,
+                        keypair_opt,
+                    )
+                    .await
                 }
-                phase2_cli::Branches::Default { request, custom_seed } => {
-                    contribution_prelude(request.url, request.token, Branch::Default(custom_seed)).await
+                phase2_cli::Branches::Default {
+                    request,
+                    custom_seed,
+                    max_upload_bytes_per_sec,
+                    anonymous,
+                    ..
+                } => {
+                    contribution_prelude(
+                        request.url,
+                        request.token.expect("The token is required unless estimating the contribution"),
+                        Branch::Default(custom_seed),
+                        max_upload_bytes_per_sec,
+                        anonymous,
+                        keypair_opt,
+                    )
+                    .await
                 }
-                phase2_cli::Branches::Offline { custom_seed } => {
+                phase2_cli::Branches::Offline {
+                    custom_seed,
+                    parameters,
+                } => {
                     if custom_seed {
                         println!(
                     "{}\n{}",
@@ -744,12 +1081,19 @@ async fn main() {
                 );
                     }
                     // Only compute randomness. It expects a file called challenge.params to be available in the cwd and already filled with the challenge bytes
-                    println!("{} Reading challenge", "[1/2]".bold().dimmed());
+                    output::step(1, 2, tr(Message::StepReadingChallenge));
                     let challenge = async_fs::read(OFFLINE_CHALLENGE_FILE_NAME)
                         .await
                         .expect(&format!("{}", "Couldn't read the challenge file".red().bold()));
+                    let manifest = TransferManifest::read(OFFLINE_MANIFEST_FILE_NAME)
+                        .expect(&format!("{}", "Couldn't read the transfer manifest".red().bold()));
+                    manifest.verify_challenge(&calculate_hash(&challenge)).expect(&format!(
+                        "{}",
+                        "The challenge doesn't match the transfer manifest".red().bold()
+                    ));
+                    println!("Contributing to {}", manifest.label().bright_cyan());
 
-                    println!("{} Computing contribution", "[2/2]".bold().dimmed());
+                    output::step(2, 2, tr(Message::StepComputingContribution));
 
                     if custom_seed {
                         println!("{}", CUSTOM_SEED_MSG_YES.bright_cyan());
@@ -757,7 +1101,7 @@ async fn main() {
                         println!("{}", CUSTOM_SEED_MSG_NO.bright_cyan());
                     }
                     tokio::task::spawn_blocking(move || {
-                        compute_contribution(custom_seed, &challenge, OFFLINE_CONTRIBUTION_FILE_NAME)
+                        compute_contribution(parameters, custom_seed, &challenge, OFFLINE_CONTRIBUTION_FILE_NAME)
                     })
                     .await
                     .unwrap()
@@ -765,13 +1109,26 @@ async fn main() {
                 }
             }
         }
+        CeremonyOpt::FetchChallenge { request, output } => {
+            contribution_prelude(
+                request.url,
+                request.token,
+                Branch::FetchChallenge(output),
+                None,
+                false,
+                keypair_opt,
+            )
+            .await
+        }
         CeremonyOpt::CloseCeremony(url) => {
-            let keypair = tokio::task::spawn_blocking(|| io::keypair_from_mnemonic())
-                .await
-                .unwrap()
-                .expect(&format!("{}", "Error while generating the keypair".red().bold()));
+            let keypair = tokio::task::spawn_blocking(move || {
+                keypair_opt.load_keypair(KeypairSource::Mnemonic, KeyPairUser::Coordinator)
+            })
+            .await
+            .unwrap()
+            .expect(&format!("{}", "Error while loading the keypair".red().bold()));
 
-            let client = Client::new();
+            let client = http_client(&url);
             close_ceremony(&client, &url.coordinator, &keypair).await;
         }
         CeremonyOpt::ExportKeypair(mnemonic_path) => {
@@ -806,6 +1163,23 @@ async fn main() {
                 println!("{}", "Keypair was correctly generated in the \"keypair.toml\" file. You can copy its content to the \"wallet.toml\" file. Refer to the Namada documentation on how to generate a wallet.".bold().green());
             }).await.expect(&format!("{}", "Error while generating the keypair".red().bold()));
         }
+        CeremonyOpt::ShowPubkey(keypair_path) => {
+            tokio::task::spawn_blocking(move || {
+                let content = fs::read_to_string(keypair_path.keypair_file).expect(&format!("{}", "Failed to read the keypair file".red().bold()));
+                let toml_config: TomlConfig = toml::from_str(&content).expect(&format!("{}", "The keypair file is not valid".red().bold()));
+                let password = rpassword::prompt_password("Enter the password to decrypt the keypair: ".bright_yellow()).unwrap();
+
+                for (alias, encrypted_keypair) in toml_config.keys() {
+                    match encrypted_keypair.decrypt(&password) {
+                        Ok(keypair) => println!("{} {}", format!("{}:", alias).bold(), hex::encode(keypair.pk.as_ref())),
+                        Err(e) => {
+                            eprintln!("{}", format!("Failed to decrypt the keypair {}: {}", alias, e).red().bold());
+                            process::exit(1);
+                        }
+                    }
+                }
+            }).await.expect(&format!("{}", "Error while reading the keypair".red().bold()));
+        }
         CeremonyOpt::GenerateAddresses(contributors) => {
             tokio::task::spawn_blocking(move || {
                 let content = fs::read(&contributors.path).unwrap();
@@ -833,41 +1207,171 @@ async fn main() {
             .expect(&format!("{}", "Error while generating the addresses".red().bold()));
         }
         #[cfg(debug_assertions)]
-        CeremonyOpt::GetContributions(url) => {
-            get_contributions(&url.coordinator).await;
+        CeremonyOpt::GetContributions(range) => {
+            get_contributions(&range).await;
         }
         CeremonyOpt::GetState(state) => {
             let secret = state.token.as_str();
-            get_coordinator_state(&state.url.coordinator, secret).await;
+            get_coordinator_state(&state.url, secret).await;
+        }
+        CeremonyOpt::Mirror {
+            url,
+            output_dir,
+            poll_interval,
+        } => {
+            let client = http_client(&url);
+            let poll_interval = Duration::from_secs(poll_interval);
+            if let Err(e) = mirror::mirror_loop(&client, &url.coordinator, &output_dir, poll_interval).await {
+                eprintln!("{}", format!("{}", e).red().bold());
+                process::exit(1);
+            }
+        }
+        CeremonyOpt::Hash {
+            file,
+            round_height,
+            contribution_id,
+        } => {
+            let path = file.to_str().expect(&format!("{}", "Invalid file path".red().bold()));
+            let contribution = get_file_as_byte_vec(
+                path,
+                Object::anoma_contribution_file_size(round_height, contribution_id),
+            )
+            .expect(&format!("{}", "Failed to read file".red().bold()));
+            let hashes = ContributionHashes::new(&contribution);
+
+            println!(
+                "{}{}",
+                "Contribution file hash:".bright_cyan(),
+                pretty_hash!(&hashes.file_hash)
+            );
+            println!(
+                "{}{}",
+                "Contribution hash (without the challenge hash prelude):".bright_cyan(),
+                pretty_hash!(&hashes.contribution_hash)
+            );
+        }
+        CeremonyOpt::Inspect {
+            file,
+            round_height,
+            contribution_id,
+        } => {
+            let metadata = hash::ContributionFileMetadata::from_file(&file, round_height, contribution_id)
+                .expect(&format!("{}", "Failed to read file".red().bold()));
+
+            println!("{}", metadata);
+        }
+        CeremonyOpt::RebuildInfo {
+            round_height,
+            challenge_file,
+            contribution_file,
+            keypair,
+            contribution_id,
+            upload,
+            url,
+        } => {
+            let contrib_info = tokio::task::spawn_blocking(move || -> Result<(ContributionInfo, KeyPair)> {
+                let mnemonic = fs::read_to_string(keypair.path).context("Failed to read the mnemonic file")?;
+                let keypair = KeyPair::try_from_seed(&io::seed_from_string(mnemonic.as_str())?)?;
+                let challenge = fs::read(challenge_file).context("Failed to read the challenge file")?;
+                let contribution_path = contribution_file.to_str().context("Invalid contribution file path")?;
+                let contribution = get_file_as_byte_vec(
+                    contribution_path,
+                    Object::anoma_contribution_file_size(round_height, contribution_id),
+                )
+                .context("Failed to read the contribution file")?;
+
+                Ok((
+                    info::rebuild_contribution_info(round_height, &challenge, &contribution, &keypair)?,
+                    keypair,
+                ))
+            })
+            .await
+            .unwrap();
+            let (contrib_info, keypair) = match contrib_info {
+                Ok(rebuilt) => rebuilt,
+                Err(e) => {
+                    eprintln!(
+                        "{}",
+                        format!("Failed to rebuild the contribution info: {:#}", e).red().bold()
+                    );
+                    process::exit(1);
+                }
+            };
+
+            let info_filename = format!("namada_contributor_info_round_{}.json", round_height);
+            fs::write(&info_filename, serde_json::to_vec(&contrib_info).unwrap()).expect(&format!(
+                "{}",
+                "Failed to write the contribution info file".red().bold()
+            ));
+            println!(
+                "{}",
+                format!("Contribution info written to {}", info_filename).green().bold()
+            );
+
+            if upload {
+                let client = http_client(&url);
+                match requests::post_contribution_info(&client, &url.coordinator, &keypair, &contrib_info).await {
+                    Ok(()) => println!("{}", "Contribution info uploaded to the coordinator".green().bold()),
+                    Err(e) => {
+                        eprintln!(
+                            "{}",
+                            format!("Failed to upload the contribution info: {}", e).red().bold()
+                        );
+                        process::exit(1);
+                    }
+                }
+            }
         }
         CeremonyOpt::UpdateCohorts(url) => {
-            let keypair = tokio::task::spawn_blocking(|| io::keypair_from_mnemonic())
-                .await
-                .unwrap()
-                .expect(&format!("{}", "Error while generating the keypair".red().bold()));
+            let keypair = tokio::task::spawn_blocking(move || {
+                keypair_opt.load_keypair(KeypairSource::Mnemonic, KeyPairUser::Coordinator)
+            })
+            .await
+            .unwrap()
+            .expect(&format!("{}", "Error while loading the keypair".red().bold()));
 
-            let client = Client::new();
+            let client = http_client(&url);
             update_cohorts(&client, &url.coordinator, &keypair).await;
         }
         #[cfg(debug_assertions)]
         CeremonyOpt::VerifyContributions(url) => {
-            let keypair = tokio::task::spawn_blocking(|| io::keypair_from_mnemonic())
-                .await
-                .unwrap()
-                .expect(&format!("{}", "Error while generating the keypair".red().bold()));
+            let keypair = tokio::task::spawn_blocking(move || {
+                keypair_opt.load_keypair(KeypairSource::Mnemonic, KeyPairUser::Coordinator)
+            })
+            .await
+            .unwrap()
+            .expect(&format!("{}", "Error while loading the keypair".red().bold()));
 
-            let client = Client::new();
+            let client = http_client(&url);
             verify_contributions(&client, &url.coordinator, &keypair).await;
         }
         #[cfg(debug_assertions)]
         CeremonyOpt::UpdateCoordinator(url) => {
-            let keypair = tokio::task::spawn_blocking(|| io::keypair_from_mnemonic())
+            let keypair = tokio::task::spawn_blocking(move || {
+                keypair_opt.load_keypair(KeypairSource::Mnemonic, KeyPairUser::Coordinator)
+            })
+            .await
+            .unwrap()
+            .expect(&format!("{}", "Error while loading the keypair".red().bold()));
+
+            let client = http_client(&url);
+            update_coordinator(&client, &url.coordinator, &keypair).await;
+        }
+        CeremonyOpt::ValidateTranscript { transcript_file } => {
+            let checks = tokio::task::spawn_blocking(move || transcript::validate_transcript_file(&transcript_file))
                 .await
                 .unwrap()
-                .expect(&format!("{}", "Error while generating the keypair".red().bold()));
+                .unwrap_or_else(|e| {
+                    eprintln!("{}", format!("{}", e).red().bold());
+                    process::exit(1);
+                });
 
-            let client = Client::new();
-            update_coordinator(&client, &url.coordinator, &keypair).await;
+            transcript::print_checks(&checks);
+            if checks.iter().any(|check| check.failure.is_some()) {
+                eprintln!("{}", "The transcript is NOT valid".red().bold());
+                process::exit(1);
+            }
+            println!("{}", "The transcript is valid".green().bold());
         }
         CeremonyOpt::VerifyContribution(VerifySignatureContribution {
             pubkey,
@@ -877,8 +1381,8 @@ async fn main() {
         }) => {
             if let Some(path) = parameter_path {
                 // Check hash of the parameters file
-                let contribution = std::fs::read(path).expect(&format!("{}", "Failed to read file".red().bold()));
-                let contribution_file_hash = calculate_hash(&contribution[64..]);
+                let contribution_file_hash = hash::hash_file(path, CHALLENGE_HASH_LENGTH)
+                    .expect(&format!("{}", "Failed to read file".red().bold()));
                 if hex::encode(contribution_file_hash) != message {
                     eprintln!("{}", "The computed hash of the file does not match the provided one".red().bold());
                     process::exit(1);
@@ -0,0 +1,146 @@
+//! Pinning of the TLS certificate of the coordinator, so that a hijacked or mistyped coordinator url can't serve the
+//! contributor, even with a certificate trusted by the system.
+//!
+//! The pin is the SHA-256 fingerprint of the DER encoding of the certificate, as published by the ceremony operators
+//! and printed by `openssl x509 -noout -fingerprint -sha256`.
+
+use std::{fmt, str::FromStr, sync::Arc, time::SystemTime};
+
+use rustls::{
+    client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier},
+    Certificate, ClientConfig, RootCertStore, ServerName,
+};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+#[allow(missing_docs)]
+#[derive(Debug, Error, PartialEq)]
+pub enum CertPinError {
+    #[error("The certificate pin must be 64 hexadecimal digits, optionally separated by colons")]
+    InvalidFormat,
+}
+
+/// SHA-256 fingerprint of the TLS certificate of the coordinator.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CertPin([u8; 32]);
+
+impl CertPin {
+    /// Fingerprint of the DER encoded certificate.
+    pub fn of_certificate(der: &[u8]) -> Self {
+        Self(Sha256::digest(der).into())
+    }
+}
+
+impl FromStr for CertPin {
+    type Err = CertPinError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits: String = s.trim().chars().filter(|c| *c != ':').collect();
+        let mut fingerprint = [0u8; 32];
+        hex::decode_to_slice(digits, &mut fingerprint).map_err(|_| CertPinError::InvalidFormat)?;
+
+        Ok(Self(fingerprint))
+    }
+}
+
+impl fmt::Display for CertPin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits: Vec<String> = self.0.iter().map(|b| format!("{:02X}", b)).collect();
+        write!(f, "{}", digits.join(":"))
+    }
+}
+
+/// Accepts the certificate of `host` only if it matches the pin, which replaces the validation against the certificate
+/// authorities. The certificates of the other hosts, e.g. the CDN serving the challenges, are validated as usual.
+struct PinnedCertVerifier {
+    host: String,
+    pin: CertPin,
+    default: WebPkiVerifier,
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        match server_name {
+            ServerName::DnsName(name) if name.as_ref().eq_ignore_ascii_case(&self.host) => {
+                let fingerprint = CertPin::of_certificate(&end_entity.0);
+                if fingerprint == self.pin {
+                    Ok(ServerCertVerified::assertion())
+                } else {
+                    Err(rustls::Error::General(format!(
+                        "the certificate of {} has fingerprint {}, which doesn't match the pin {}",
+                        self.host, fingerprint, self.pin
+                    )))
+                }
+            }
+            _ => self
+                .default
+                .verify_server_cert(end_entity, intermediates, server_name, scts, ocsp_response, now),
+        }
+    }
+}
+
+/// TLS configuration pinning the certificate of `host`. The other hosts are validated against the certificate
+/// authorities trusted by the system.
+pub fn pinned_tls_config(host: &str, pin: &CertPin) -> std::io::Result<ClientConfig> {
+    let mut roots = RootCertStore::empty();
+    let native_certs: Vec<Vec<u8>> = rustls_native_certs::load_native_certs()?
+        .into_iter()
+        .map(|cert| cert.0)
+        .collect();
+    roots.add_parsable_certificates(&native_certs);
+
+    let verifier = PinnedCertVerifier {
+        host: host.to_owned(),
+        pin: pin.clone(),
+        default: WebPkiVerifier::new(roots, None),
+    };
+
+    Ok(ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth())
+}
+
+/// Returns `true` if the request failed because the TLS handshake was rejected, e.g. because the certificate doesn't
+/// match the pin: retrying won't help.
+pub fn is_tls_error(error: &reqwest::Error) -> bool {
+    let mut source: Option<&(dyn std::error::Error + 'static)> = Some(error);
+    while let Some(e) = source {
+        // IO errors hide the error they wrap from the chain of sources
+        if let Some(io_error) = e.downcast_ref::<std::io::Error>() {
+            if io_error.get_ref().map_or(false, |inner| inner.is::<rustls::Error>()) {
+                return true;
+            }
+        }
+        if e.is::<rustls::Error>() {
+            return true;
+        }
+        source = e.source();
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cert_pin() {
+        let colons = "22:9B:BD:9F:53:E0:90:7C:23:E9:C5:62:46:3A:9F:B9:3A:19:61:22:05:38:00:BF:69:ED:72:C0:29:FE:82:B5";
+        let pin = CertPin::from_str(colons).unwrap();
+        assert_eq!(colons, pin.to_string());
+        assert_eq!(pin, CertPin::from_str(&colons.replace(':', "").to_lowercase()).unwrap());
+
+        assert_eq!(Err(CertPinError::InvalidFormat), CertPin::from_str("22:9B:BD"));
+        assert_eq!(Err(CertPinError::InvalidFormat), CertPin::from_str(&"zz".repeat(32)));
+    }
+}
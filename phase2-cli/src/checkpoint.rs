@@ -0,0 +1,317 @@
+//! Checkpoint of the contribution, recorded after each step so that a restarted CLI continues the contribution where
+//! it stopped instead of starting it over.
+//!
+//! NOTE: the checkpoint stores the keypair of the contributor, since only the holder of the lock can complete the
+//! contribution. It is removed once the coordinator reports the end of the contribution.
+
+#[cfg(unix)]
+use std::os::unix::fs::OpenOptionsExt;
+use std::{
+    fs::OpenOptions,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use phase2_coordinator::{authentication::KeyPair, objects::ContributionInfo};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::{hash::hash_file, LockedLocators};
+
+/// Name of the checkpoint file, in the working directory of the contribution.
+pub const CHECKPOINT_FILE_NAME: &str = "namada_contribution_checkpoint.json";
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum CheckpointError {
+    #[error("The {0} file \"{1}\" doesn't match the checkpoint")]
+    FileMismatch(&'static str, PathBuf),
+    #[error("Error while accessing the checkpoint: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The checkpoint is not valid json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("The checkpoint has completed the {0:?} step but doesn't record the {1} file")]
+    MissingFile(ContributionStep, &'static str),
+    #[error("The checkpoint is for round {checkpoint} but its locators are for round {locators}")]
+    RoundMismatch { checkpoint: u64, locators: u64 },
+}
+
+/// The steps of the contribution which are recorded in the checkpoint, in order.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum ContributionStep {
+    /// The chunk has been locked.
+    Lock,
+    /// The challenge has been saved.
+    Download,
+    /// The contribution has been computed.
+    Compute,
+    /// The contribution and its signature have been uploaded.
+    Upload,
+    /// The coordinator has been notified of the contribution.
+    Notify,
+}
+
+/// A file of the contribution recorded in the checkpoint.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct CheckpointFile {
+    pub path: PathBuf,
+    /// Hex encoded hash of the file.
+    pub hash: String,
+}
+
+impl CheckpointFile {
+    fn new(path: &Path) -> Result<Self, CheckpointError> {
+        Ok(Self {
+            path: path.to_owned(),
+            hash: hex::encode(hash_file(path, 0)?),
+        })
+    }
+
+    /// Checks that the file hasn't changed since it was recorded.
+    fn validate(&self, name: &'static str) -> Result<(), CheckpointError> {
+        match hash_file(&self.path, 0) {
+            Ok(hash) if hex::encode(hash) == self.hash => Ok(()),
+            Ok(_) => Err(CheckpointError::FileMismatch(name, self.path.clone())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Err(CheckpointError::FileMismatch(name, self.path.clone())),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// The progress of an ongoing contribution.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ContributionCheckpoint {
+    /// The last completed step.
+    pub completed: ContributionStep,
+    pub round_height: u64,
+    /// The keypair of the contributor holding the lock.
+    pub keypair: KeyPair,
+    pub locked_locators: LockedLocators,
+    pub contrib_info: ContributionInfo,
+    /// The challenge, once downloaded.
+    pub challenge: Option<CheckpointFile>,
+    /// The contribution, once computed.
+    pub contribution: Option<CheckpointFile>,
+    /// The number of bytes of the contribution committed by the coordinator, from which an interrupted upload resumes.
+    #[serde(default)]
+    pub uploaded: u64,
+}
+
+impl ContributionCheckpoint {
+    /// Checkpoint of a freshly locked chunk.
+    pub fn new(keypair: KeyPair, locked_locators: LockedLocators, contrib_info: ContributionInfo) -> Self {
+        Self {
+            completed: ContributionStep::Lock,
+            round_height: locked_locators.next_contribution().round_height(),
+            keypair,
+            locked_locators,
+            contrib_info,
+            challenge: None,
+            contribution: None,
+            uploaded: 0,
+        }
+    }
+
+    /// Reads the checkpoint from file, `None` if no contribution is ongoing.
+    pub fn read(path: impl AsRef<Path>) -> Result<Option<Self>, CheckpointError> {
+        match std::fs::read(path) {
+            Ok(checkpoint) => Ok(Some(serde_json::from_slice(&checkpoint)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Reads the checkpoint of the ongoing contribution, if any, and checks that it can be resumed.
+    pub fn resume(path: impl AsRef<Path>) -> Result<Option<Self>, CheckpointError> {
+        let checkpoint = Self::read(path)?;
+        if let Some(checkpoint) = &checkpoint {
+            checkpoint.validate()?;
+        }
+
+        Ok(checkpoint)
+    }
+
+    /// Writes the checkpoint to file. The checkpoint is synced and replaced atomically, so that a crash can't leave it
+    /// truncated, and only its owner can read it since it holds the keypair.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        let path = path.as_ref();
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+
+        // A leftover temporary file would keep its permissions
+        Self::remove(&tmp_path)?;
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&tmp_path)?;
+        file.write_all(&serde_json::to_vec_pretty(self)?)?;
+        file.sync_all()?;
+
+        Ok(std::fs::rename(tmp_path, path)?)
+    }
+
+    /// Removes the checkpoint file, if any.
+    pub fn remove(path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        match std::fs::remove_file(path) {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the checkpoint is consistent and that the recorded files haven't changed since.
+    pub fn validate(&self) -> Result<(), CheckpointError> {
+        let locators_round = self.locked_locators.next_contribution().round_height();
+        if self.round_height != locators_round {
+            return Err(CheckpointError::RoundMismatch {
+                checkpoint: self.round_height,
+                locators: locators_round,
+            });
+        }
+
+        if self.completed >= ContributionStep::Download {
+            self.challenge
+                .as_ref()
+                .ok_or(CheckpointError::MissingFile(self.completed, "challenge"))?
+                .validate("challenge")?;
+        }
+        if self.completed >= ContributionStep::Compute {
+            self.contribution
+                .as_ref()
+                .ok_or(CheckpointError::MissingFile(self.completed, "contribution"))?
+                .validate("contribution")?;
+        }
+
+        Ok(())
+    }
+
+    /// The step the contribution resumes at, `None` if only the outcome of the contribution is left to wait for.
+    pub fn next_step(&self) -> Option<ContributionStep> {
+        match self.completed {
+            ContributionStep::Lock => Some(ContributionStep::Download),
+            ContributionStep::Download => Some(ContributionStep::Compute),
+            ContributionStep::Compute => Some(ContributionStep::Upload),
+            ContributionStep::Upload => Some(ContributionStep::Notify),
+            ContributionStep::Notify => None,
+        }
+    }
+
+    /// Records the challenge saved at the given path.
+    pub fn set_challenge(&mut self, path: &Path) -> Result<(), CheckpointError> {
+        self.challenge = Some(CheckpointFile::new(path)?);
+        Ok(())
+    }
+
+    /// Records the contribution computed at the given path.
+    pub fn set_contribution(&mut self, path: &Path) -> Result<(), CheckpointError> {
+        self.contribution = Some(CheckpointFile::new(path)?);
+        self.uploaded = 0;
+        Ok(())
+    }
+
+    /// Records the number of bytes of the contribution committed by the coordinator and writes the checkpoint to
+    /// file, so that a restarted upload doesn't send them again.
+    pub fn set_uploaded(&mut self, uploaded: u64, path: impl AsRef<Path>) -> Result<(), CheckpointError> {
+        self.uploaded = uploaded;
+        self.write(path)
+    }
+
+    /// Records the completion of a step, along with the contribution info gathered so far, and writes the checkpoint
+    /// to file.
+    pub fn complete(
+        &mut self,
+        step: ContributionStep,
+        contrib_info: &ContributionInfo,
+        path: impl AsRef<Path>,
+    ) -> Result<(), CheckpointError> {
+        self.completed = step;
+        self.contrib_info = contrib_info.clone();
+        self.write(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phase2_coordinator::storage::ContributionLocator;
+    use serde_json::json;
+
+    fn locked_locators(round_height: u64) -> LockedLocators {
+        let locator = |contribution_id, is_verified| {
+            json!({
+                "round_height": round_height,
+                "chunk_id": 0,
+                "contribution_id": contribution_id,
+                "is_verified": is_verified,
+            })
+        };
+        serde_json::from_value(json!({
+            "previous_contribution": locator(0, true),
+            "current_contribution": locator(0, false),
+            "next_contribution": locator(1, false),
+            "next_contribution_file_signature": locator(1, false),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resume_after_compute() {
+        let dir = tempfile::tempdir().unwrap();
+        let checkpoint_path = dir.path().join(CHECKPOINT_FILE_NAME);
+        let challenge_path = dir.path().join("challenge.params");
+        let contribution_path = dir.path().join("contribution.params");
+        let keypair = KeyPair::new();
+        let mut contrib_info = ContributionInfo::default();
+
+        // Run the contribution up to the compute step, then crash
+        let mut checkpoint = ContributionCheckpoint::new(keypair.clone(), locked_locators(3), contrib_info.clone());
+        checkpoint.write(&checkpoint_path).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&checkpoint_path).unwrap().permissions().mode();
+            assert_eq!(0o600, mode & 0o777);
+        }
+        std::fs::write(&challenge_path, b"challenge").unwrap();
+        checkpoint.set_challenge(&challenge_path).unwrap();
+        checkpoint
+            .complete(ContributionStep::Download, &contrib_info, &checkpoint_path)
+            .unwrap();
+        std::fs::write(&contribution_path, b"contribution").unwrap();
+        checkpoint.set_contribution(&contribution_path).unwrap();
+        contrib_info.ceremony_round = 3;
+        checkpoint
+            .complete(ContributionStep::Compute, &contrib_info, &checkpoint_path)
+            .unwrap();
+
+        // The upload is interrupted after the first part
+        checkpoint.set_uploaded(8, &checkpoint_path).unwrap();
+        drop(checkpoint);
+
+        // The restarted contribution continues at the upload, with the same keypair and locators
+        let resumed = ContributionCheckpoint::resume(&checkpoint_path).unwrap().unwrap();
+        assert_eq!(Some(ContributionStep::Upload), resumed.next_step());
+        assert_eq!(8, resumed.uploaded);
+        assert_eq!(3, resumed.round_height);
+        assert_eq!(3, resumed.contrib_info.ceremony_round);
+        assert_eq!(keypair.pubkey(), resumed.keypair.pubkey());
+        assert_eq!(
+            ContributionLocator::new(3, 0, 1, false),
+            resumed.locked_locators.next_contribution()
+        );
+        assert_eq!(
+            Some(contribution_path.clone()),
+            resumed.contribution.map(|file| file.path)
+        );
+
+        // A contribution altered since the checkpoint can't be resumed
+        std::fs::write(&contribution_path, b"tampered").unwrap();
+        assert!(matches!(
+            ContributionCheckpoint::resume(&checkpoint_path),
+            Err(CheckpointError::FileMismatch("contribution", _))
+        ));
+
+        ContributionCheckpoint::remove(&checkpoint_path).unwrap();
+        assert!(ContributionCheckpoint::resume(&checkpoint_path).unwrap().is_none());
+    }
+}
@@ -0,0 +1,81 @@
+//! Pre-flight check of the free disk space needed by the challenge and the contribution files.
+
+use std::path::Path;
+
+use phase2_coordinator::storage::Object;
+use thiserror::Error;
+
+const BYTES_PER_GB: f64 = 1_000_000_000.0;
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum DiskSpaceError {
+    #[error(
+        "Not enough disk space in {dir}: need {:.2} GB free but only {:.2} GB are available",
+        *.required as f64 / BYTES_PER_GB,
+        *.available as f64 / BYTES_PER_GB
+    )]
+    Insufficient { dir: String, required: u64, available: u64 },
+    #[error("Error while checking the free disk space: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// Returns the disk space in bytes needed to store both the challenge and the contribution files of the given
+/// contribution.
+pub fn required_space(round_height: u64, contribution_id: u64) -> u64 {
+    let challenge_size = Object::anoma_contribution_file_size(round_height, contribution_id.saturating_sub(1));
+    let contribution_size = Object::anoma_contribution_file_size(round_height, contribution_id);
+
+    challenge_size + contribution_size
+}
+
+/// Checks that the given available space is enough to store `required` bytes in `dir`.
+pub fn ensure_space(dir: &Path, required: u64, available: u64) -> Result<(), DiskSpaceError> {
+    if available < required {
+        return Err(DiskSpaceError::Insufficient {
+            dir: dir.display().to_string(),
+            required,
+            available,
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks that `dir` has enough free space for the challenge and the contribution files of the given contribution.
+pub fn check_disk_space(dir: &Path, round_height: u64, contribution_id: u64) -> Result<(), DiskSpaceError> {
+    ensure_space(
+        dir,
+        required_space(round_height, contribution_id),
+        fs2::available_space(dir)?,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_low_disk_space() {
+        let dir = tempfile::tempdir().unwrap();
+        let required = required_space(3, 1);
+        assert_eq!(
+            Object::anoma_contribution_file_size(3, 0) + Object::anoma_contribution_file_size(3, 1),
+            required
+        );
+
+        // Enough space
+        assert!(ensure_space(dir.path(), required, required).is_ok());
+
+        // Not enough space for both the challenge and the contribution
+        let available = Object::anoma_contribution_file_size(3, 1);
+        match ensure_space(dir.path(), required, available) {
+            Err(e @ DiskSpaceError::Insufficient { .. }) => {
+                assert!(e
+                    .to_string()
+                    .contains(&format!("need {:.2} GB free", required as f64 / BYTES_PER_GB)))
+            }
+            _ => panic!("Expected an insufficient disk space error"),
+        }
+    }
+}
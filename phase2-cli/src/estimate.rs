@@ -0,0 +1,88 @@
+//! Estimates of the download and upload sizes and of the computation time of a contribution, reported by the
+//! `--estimate-only` flag of the contribution before joining the queue.
+
+use std::{fmt, time::Duration};
+
+use indicatif::{HumanBytes, HumanDuration};
+use phase2_coordinator::{storage::Object, CeremonyStatus};
+
+/// Number of point multiplications timed to estimate the computation time.
+pub const BENCHMARK_SAMPLES: u32 = 64;
+/// Size of an uncompressed G1 point, the bulk of the parameters rescaled by the contribution.
+const G1_UNCOMPRESSED_SIZE: u64 = 96;
+
+/// The estimated resources of a contribution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContributionEstimate {
+    /// The round the contributor would contribute to when joining the queue now.
+    pub round_height: u64,
+    /// Size of the challenge to download.
+    pub challenge_size: u64,
+    /// Size of the contribution to upload.
+    pub contribution_size: u64,
+    /// Duration of the computation of the contribution on this machine, if it's the one computing it.
+    pub compute_time: Option<Duration>,
+}
+
+impl ContributionEstimate {
+    /// Estimates the contribution from the status of the ceremony and the time of a point multiplication on this
+    /// machine, as measured by `Computation::benchmark_point_multiplication`. Without it, only the sizes are estimated.
+    pub fn new(status: &CeremonyStatus, point_multiplication_time: Option<Duration>) -> Self {
+        // The ceremony has a single contributor per round, each contributor ahead in the queue adds a round
+        let round_height = status.round_height + status.queue_size as u64 + 1;
+        let contribution_size = Object::anoma_contribution_file_size(round_height, 1);
+        let points = contribution_size / G1_UNCOMPRESSED_SIZE;
+
+        Self {
+            round_height,
+            challenge_size: Object::anoma_contribution_file_size(round_height, 0),
+            contribution_size,
+            compute_time: point_multiplication_time.map(|time| time.mul_f64(points as f64)),
+        }
+    }
+}
+
+impl fmt::Display for ContributionEstimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Estimated contribution to round {}:", self.round_height)?;
+        writeln!(f, "  Challenge to download: {}", HumanBytes(self.challenge_size))?;
+        writeln!(f, "  Contribution to upload: {}", HumanBytes(self.contribution_size))?;
+        match self.compute_time {
+            Some(compute_time) => write!(f, "  Computation on this machine: about {}", HumanDuration(compute_time)),
+            None => write!(f, "  Computation: not estimated, it runs on the offline machine"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phase2_coordinator::commands::Computation;
+
+    #[test]
+    fn test_contribution_estimate() {
+        let status = CeremonyStatus {
+            round_height: 10,
+            queue_size: 2,
+            ..Default::default()
+        };
+        let point_multiplication_time = Computation::benchmark_point_multiplication(4);
+        assert!(point_multiplication_time > Duration::ZERO);
+
+        let estimate = ContributionEstimate::new(&status, Some(point_multiplication_time));
+        assert_eq!(13, estimate.round_height);
+        assert_eq!(Object::anoma_contribution_file_size(13, 0), estimate.challenge_size);
+        assert!(estimate.contribution_size > estimate.challenge_size);
+        assert!(estimate.compute_time.unwrap() >= point_multiplication_time);
+
+        let output = estimate.to_string();
+        assert!(output.contains("round 13"));
+        assert!(output.contains(&HumanBytes(estimate.challenge_size).to_string()));
+        assert!(output.contains(&HumanBytes(estimate.contribution_size).to_string()));
+        assert!(output.contains(&format!("about {}", HumanDuration(estimate.compute_time.unwrap()))));
+
+        let estimate = ContributionEstimate::new(&status, None);
+        assert_eq!(None, estimate.compute_time);
+        assert!(estimate.to_string().contains("not estimated"));
+    }
+}
@@ -0,0 +1,76 @@
+//! Download of the challenge alone, for contributors computing on an offline machine: the chunk is locked and the
+//! challenge saved with its transfer manifest, but nothing is computed nor uploaded.
+
+use std::path::{Path, PathBuf};
+
+use futures_util::StreamExt;
+use phase2_coordinator::{authentication::KeyPair, objects::round::LockedLocators};
+use reqwest::{Client, Url};
+use setup_utils::calculate_hash;
+use thiserror::Error;
+
+use crate::{
+    disk_space::{self, DiskSpaceError},
+    manifest::{ManifestError, TransferManifest, OFFLINE_CHALLENGE_FILE_NAME, OFFLINE_MANIFEST_FILE_NAME},
+    requests::{self, RequestError},
+};
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum FetchError {
+    #[error(transparent)]
+    DiskSpace(#[from] DiskSpaceError),
+    #[error("Error while saving the challenge: {0}")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Manifest(#[from] ManifestError),
+    #[error(transparent)]
+    Request(#[from] RequestError),
+}
+
+/// The challenge saved by [`fetch_challenge`].
+#[derive(Debug)]
+pub struct FetchedChallenge {
+    /// The locators of the locked chunk, still locked by the contributor.
+    pub locked_locators: LockedLocators,
+    pub manifest: TransferManifest,
+    pub challenge_path: PathBuf,
+    pub manifest_path: PathBuf,
+}
+
+/// Locks the chunk of the contributor, downloads its challenge and saves it in `output` together with the transfer
+/// manifest expected by the offline command. The chunk is left locked, so the lock deadline keeps running.
+pub async fn fetch_challenge(
+    client: &Client,
+    coordinator: &Url,
+    keypair: &KeyPair,
+    output: &Path,
+) -> Result<FetchedChallenge, FetchError> {
+    let locked_locators = requests::get_lock_chunk(client, coordinator, keypair).await?;
+    let response_locator = locked_locators.next_contribution();
+    let round_height = response_locator.round_height();
+
+    // The offline machine needs the same space, but check at least that the challenge fits here
+    disk_space::check_disk_space(output, round_height, response_locator.contribution_id())?;
+
+    let (mut challenge_stream, challenge_len) =
+        requests::download_challenge(client, coordinator, keypair, &round_height).await?;
+    let mut challenge: Vec<u8> = Vec::with_capacity(challenge_len as usize);
+    while let Some(b) = challenge_stream.next().await {
+        challenge.extend_from_slice(&b.map_err(RequestError::from)?);
+    }
+
+    let challenge_path = output.join(OFFLINE_CHALLENGE_FILE_NAME);
+    std::fs::write(&challenge_path, &challenge)?;
+
+    let manifest = TransferManifest::new(&response_locator, &calculate_hash(&challenge));
+    let manifest_path = output.join(OFFLINE_MANIFEST_FILE_NAME);
+    manifest.write(&manifest_path)?;
+
+    Ok(FetchedChallenge {
+        locked_locators,
+        manifest,
+        challenge_path,
+        manifest_path,
+    })
+}
@@ -0,0 +1,196 @@
+//! Hashes of the contribution files, computed the same way the coordinator records them.
+//!
+//! The files are memory-mapped rather than read in memory before being hashed, and the independent hashes of a
+//! contribution are computed in parallel. Each hash is still computed sequentially, since chunking it would change its
+//! value.
+
+use anyhow::Result;
+use memmap::Mmap;
+use phase2_coordinator::storage::Object;
+use setup_utils::calculate_hash;
+use std::{
+    fmt,
+    fs::{self, File},
+    io::Read,
+    path::Path,
+};
+use tracing::debug;
+
+/// Length of the prelude of a contribution file: the hash of the challenge the contribution has been computed against.
+pub const CHALLENGE_HASH_LENGTH: usize = 64;
+
+/// Reads a contribution file into a buffer of the size expected by the coordinator for the contribution.
+pub fn get_file_as_byte_vec(filename: &str, expected_size: u64) -> Result<Vec<u8>> {
+    let mut f = File::open(filename)?;
+    let metadata = fs::metadata(filename)?;
+
+    let mut buffer = vec![0; expected_size as usize];
+    debug!("namada_contribution_file_size: {}", expected_size);
+    debug!("metadata file length {}", metadata.len());
+    f.read(&mut buffer)?;
+
+    Ok(buffer)
+}
+
+/// The hashes of a contribution file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContributionHashes {
+    /// Hash of the whole contribution file, the one signed in the contribution file signature.
+    pub file_hash: Vec<u8>,
+    /// Hash of the contribution without its challenge hash prelude.
+    pub contribution_hash: Vec<u8>,
+}
+
+impl ContributionHashes {
+    pub fn new(contribution: &[u8]) -> Self {
+        let prelude_len = std::cmp::min(CHALLENGE_HASH_LENGTH, contribution.len());
+        let (file_hash, contribution_hash) = rayon::join(
+            || calculate_hash(contribution).to_vec(),
+            || calculate_hash(&contribution[prelude_len..]).to_vec(),
+        );
+
+        Self {
+            file_hash,
+            contribution_hash,
+        }
+    }
+
+    /// Hashes the contribution file at the given path.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(match map_file(path.as_ref())? {
+            Some(contribution) => Self::new(&contribution),
+            None => Self::new(&[]),
+        })
+    }
+}
+
+/// The metadata of a contribution file, reported by the `inspect` command to diagnose a file locally before uploading
+/// it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContributionFileMetadata {
+    /// Size of the file.
+    pub size: u64,
+    /// Size of the contribution expected by the coordinator for the round and contribution id.
+    pub expected_size: u64,
+    /// The prelude of the file: the hash of the challenge the contribution has been computed against.
+    pub challenge_hash: Vec<u8>,
+    pub hashes: ContributionHashes,
+}
+
+impl ContributionFileMetadata {
+    /// Inspects the contribution file at the given path, as a contribution to `round_height` with id
+    /// `contribution_id`.
+    pub fn from_file(path: impl AsRef<Path>, round_height: u64, contribution_id: u64) -> std::io::Result<Self> {
+        let path = path.as_ref();
+        let mut challenge_hash = Vec::with_capacity(CHALLENGE_HASH_LENGTH);
+        File::open(path)?
+            .take(CHALLENGE_HASH_LENGTH as u64)
+            .read_to_end(&mut challenge_hash)?;
+
+        Ok(Self {
+            size: fs::metadata(path)?.len(),
+            expected_size: Object::anoma_contribution_file_size(round_height, contribution_id),
+            challenge_hash,
+            hashes: ContributionHashes::from_file(path)?,
+        })
+    }
+
+    /// Whether the file has the size expected by the coordinator.
+    pub fn is_expected_size(&self) -> bool {
+        self.size == self.expected_size
+    }
+}
+
+impl fmt::Display for ContributionFileMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "File size: {} bytes", self.size)?;
+        if self.is_expected_size() {
+            writeln!(f, "Expected size: {} bytes, matching", self.expected_size)?;
+        } else {
+            writeln!(f, "Expected size: {} bytes, MISMATCH", self.expected_size)?;
+        }
+        writeln!(f, "Challenge hash (prelude): {}", hex::encode(&self.challenge_hash))?;
+        writeln!(f, "Contribution file hash: {}", hex::encode(&self.hashes.file_hash))?;
+        write!(
+            f,
+            "Contribution hash (without the challenge hash prelude): {}",
+            hex::encode(&self.hashes.contribution_hash)
+        )
+    }
+}
+
+/// Memory-maps the file, `None` if it's empty since empty files can't be mapped.
+fn map_file(path: &Path) -> std::io::Result<Option<Mmap>> {
+    let file = File::open(path)?;
+    if file.metadata()?.len() == 0 {
+        return Ok(None);
+    }
+
+    // SAFETY: the files of the ceremony are not modified while being hashed
+    Ok(Some(unsafe { Mmap::map(&file)? }))
+}
+
+/// Hashes the file at the given path, skipping its first `offset` bytes.
+pub fn hash_file(path: impl AsRef<Path>, offset: usize) -> std::io::Result<Vec<u8>> {
+    let hash = match map_file(path.as_ref())? {
+        Some(content) => calculate_hash(content.get(offset..).unwrap_or_default()),
+        None => calculate_hash(&[]),
+    };
+
+    Ok(hash.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parallel_hashes() {
+        // Large enough to span several pages of the mapping
+        let contribution: Vec<u8> = (0..16 << 20).map(|i: u32| (i % 251) as u8).collect();
+        let contribution_file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(contribution_file.path(), &contribution).unwrap();
+
+        // The hashes are identical to the sequential ones
+        let file_hash = calculate_hash(&contribution).to_vec();
+        let contribution_hash = calculate_hash(&contribution[CHALLENGE_HASH_LENGTH..]).to_vec();
+        let hashes = ContributionHashes::new(&contribution);
+        assert_eq!(file_hash, hashes.file_hash);
+        assert_eq!(contribution_hash, hashes.contribution_hash);
+        assert_eq!(hashes, ContributionHashes::from_file(contribution_file.path()).unwrap());
+        assert_eq!(file_hash, hash_file(contribution_file.path(), 0).unwrap());
+        assert_eq!(
+            contribution_hash,
+            hash_file(contribution_file.path(), CHALLENGE_HASH_LENGTH).unwrap()
+        );
+
+        let empty_file = tempfile::NamedTempFile::new().unwrap();
+        assert_eq!(calculate_hash(&[]).to_vec(), hash_file(empty_file.path(), 0).unwrap());
+    }
+
+    #[test]
+    fn test_contribution_file_metadata() {
+        let challenge_hash = calculate_hash(&[3u8; 256]);
+        let mut contribution = challenge_hash.to_vec();
+        contribution.extend_from_slice(&[7u8; 512]);
+        contribution.resize(Object::anoma_contribution_file_size(1, 1) as usize, 0);
+        let contribution_file = tempfile::NamedTempFile::new().unwrap();
+        fs::write(contribution_file.path(), &contribution).unwrap();
+
+        let metadata = ContributionFileMetadata::from_file(contribution_file.path(), 1, 1).unwrap();
+        assert_eq!(contribution.len() as u64, metadata.size);
+        assert!(metadata.is_expected_size());
+        assert_eq!(challenge_hash.to_vec(), metadata.challenge_hash);
+        assert_eq!(ContributionHashes::new(&contribution), metadata.hashes);
+        let output = metadata.to_string();
+        assert!(output.contains(&hex::encode(&challenge_hash)));
+        assert!(output.contains(&hex::encode(calculate_hash(&contribution))));
+
+        // A truncated file is reported as not matching the expected size
+        fs::write(contribution_file.path(), &contribution[..1024]).unwrap();
+        let metadata = ContributionFileMetadata::from_file(contribution_file.path(), 1, 1).unwrap();
+        assert_eq!(1024, metadata.size);
+        assert!(!metadata.is_expected_size());
+        assert!(metadata.to_string().contains("MISMATCH"));
+    }
+}
@@ -0,0 +1,185 @@
+//! Catalog of the user-facing messages of the CLI, in the language selected with the `--lang` flag or the `LANG`
+//! environment variable. Languages without a translation fall back to English.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The language of the messages, see [`init`].
+static LANG: AtomicU8 = AtomicU8::new(Lang::En as u8);
+
+/// Languages of the catalog.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[repr(u8)]
+pub enum Lang {
+    /// English, the default.
+    En,
+    /// French.
+    Fr,
+}
+
+impl Lang {
+    /// Parses the language of a locale, e.g. `fr` or `fr_FR.UTF-8`, falling back to English if it has no translation.
+    pub fn from_locale(locale: &str) -> Self {
+        let language = locale
+            .split(|c| c == '_' || c == '-' || c == '.')
+            .next()
+            .unwrap_or_default();
+
+        match language.to_lowercase().as_str() {
+            "fr" => Lang::Fr,
+            _ => Lang::En,
+        }
+    }
+
+    fn from_u8(lang: u8) -> Self {
+        match lang {
+            lang if lang == Lang::Fr as u8 => Lang::Fr,
+            _ => Lang::En,
+        }
+    }
+}
+
+/// The user-facing messages of the contribution.
+#[allow(missing_docs)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Message {
+    Welcome,
+    StepInitializing,
+    StepGeneratingKeypair,
+    StepJoiningQueue,
+    StepLockingChunk,
+    StepLockingChunkAndGettingChallenge,
+    StepGettingChallenge,
+    StepSettingUpContributionFile,
+    StepComputingContribution,
+    StepUpdatingContributionInfo,
+    StepUploadingContribution,
+    StepUploadingContributionInfo,
+    StepNotifyingCoordinator,
+    StepReadingChallenge,
+    ContributionAborted,
+    ContributionDone,
+    ContributorBanned,
+    ContributionRejected,
+    ContributorStatusFailed,
+    CoordinatorUnreachable,
+    InvalidContributorStatus,
+    ResumeFailed,
+    TokenNotBase58,
+}
+
+impl Message {
+    /// Returns the message in the given language.
+    pub fn text(self, lang: Lang) -> &'static str {
+        match lang {
+            Lang::En => self.en(),
+            Lang::Fr => self.fr(),
+        }
+    }
+
+    fn en(self) -> &'static str {
+        match self {
+            Message::Welcome => "Welcome to the Namada Trusted Setup Ceremony!",
+            Message::StepInitializing => "Initializing contribution",
+            Message::StepGeneratingKeypair => "Generating keypair",
+            Message::StepJoiningQueue => "Joining queue",
+            Message::StepLockingChunk => "Locking chunk",
+            Message::StepLockingChunkAndGettingChallenge => "Locking chunk and getting challenge",
+            Message::StepGettingChallenge => "Getting challenge",
+            Message::StepSettingUpContributionFile => "Setting up contribution file",
+            Message::StepComputingContribution => "Computing contribution",
+            Message::StepUpdatingContributionInfo => "Updating contribution info",
+            Message::StepUploadingContribution => "Uploading contribution",
+            Message::StepUploadingContributionInfo => "Uploading contribution info",
+            Message::StepNotifyingCoordinator => {
+                "Notifying the coordinator of your uploaded contribution.\nYour contribution is being processed... This might take a minute..."
+            }
+            Message::StepReadingChallenge => "Reading challenge",
+            Message::ContributionAborted => "The contribution has been aborted",
+            Message::ContributionDone => {
+                "Done! Thank you for your contribution! If your contribution is valid, it will appear on ceremony.namada.net. Check it out!"
+            }
+            Message::ContributorBanned => {
+                "This contributor has been banned from the ceremony because of an invalid contribution."
+            }
+            Message::ContributionRejected => "Your contribution failed the verification and has been rejected:",
+            Message::ContributorStatusFailed => "Couldn't get the status of contributor",
+            Message::CoordinatorUnreachable => {
+                "ERROR: could not contact the Coordinator, please check the url you provided"
+            }
+            Message::InvalidContributorStatus => "Did not retrieve a valid contributor state.",
+            Message::ResumeFailed => "Couldn't resume the interrupted contribution, starting over",
+            Message::TokenNotBase58 => "The token provided is not base58 encoded.",
+        }
+    }
+
+    fn fr(self) -> &'static str {
+        match self {
+            Message::Welcome => "Bienvenue dans la cérémonie Trusted Setup de Namada !",
+            Message::StepInitializing => "Initialisation de la contribution",
+            Message::StepGeneratingKeypair => "Génération de la paire de clés",
+            Message::StepJoiningQueue => "Entrée dans la file d'attente",
+            Message::StepLockingChunk => "Verrouillage du chunk",
+            Message::StepLockingChunkAndGettingChallenge => "Verrouillage du chunk et récupération du challenge",
+            Message::StepGettingChallenge => "Récupération du challenge",
+            Message::StepSettingUpContributionFile => "Préparation du fichier de contribution",
+            Message::StepComputingContribution => "Calcul de la contribution",
+            Message::StepUpdatingContributionInfo => "Mise à jour des informations de la contribution",
+            Message::StepUploadingContribution => "Envoi de la contribution",
+            Message::StepUploadingContributionInfo => "Envoi des informations de la contribution",
+            Message::StepNotifyingCoordinator => {
+                "Notification de l'envoi de votre contribution au coordinateur.\nVotre contribution est en cours de traitement... Cela peut prendre une minute..."
+            }
+            Message::StepReadingChallenge => "Lecture du challenge",
+            Message::ContributionAborted => "La contribution a été interrompue",
+            Message::ContributionDone => {
+                "Terminé ! Merci pour votre contribution ! Si elle est valide, elle apparaîtra sur ceremony.namada.net. Allez voir !"
+            }
+            Message::ContributorBanned => {
+                "Ce contributeur a été banni de la cérémonie en raison d'une contribution invalide."
+            }
+            Message::ContributionRejected => "Votre contribution n'a pas passé la vérification et a été rejetée :",
+            Message::ContributorStatusFailed => "Impossible d'obtenir le statut du contributeur",
+            Message::CoordinatorUnreachable => {
+                "ERREUR : impossible de contacter le coordinateur, veuillez vérifier l'url fournie"
+            }
+            Message::InvalidContributorStatus => "Aucun statut de contributeur valide n'a été reçu.",
+            Message::ResumeFailed => "Impossible de reprendre la contribution interrompue, elle recommence depuis le début",
+            Message::TokenNotBase58 => "Le jeton fourni n'est pas encodé en base58.",
+        }
+    }
+}
+
+/// Selects the language of the messages.
+pub fn init(lang: Lang) {
+    LANG.store(lang as u8, Ordering::Relaxed);
+}
+
+/// Returns the message in the selected language.
+pub fn tr(message: Message) -> &'static str {
+    message.text(Lang::from_u8(LANG.load(Ordering::Relaxed)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translated_message() {
+        let lang = Lang::from_locale("fr_FR.UTF-8");
+        assert_eq!(Lang::Fr, lang);
+        assert_eq!(
+            "Bienvenue dans la cérémonie Trusted Setup de Namada !",
+            Message::Welcome.text(lang)
+        );
+        assert_eq!("Verrouillage du chunk", Message::StepLockingChunk.text(lang));
+
+        // Languages without a translation fall back to English
+        for locale in ["en_US.UTF-8", "C", "de"] {
+            assert_eq!(Lang::En, Lang::from_locale(locale));
+        }
+        assert_eq!(
+            "Welcome to the Namada Trusted Setup Ceremony!",
+            Message::Welcome.text(Lang::from_locale("de"))
+        );
+    }
+}
@@ -0,0 +1,101 @@
+//! Contributor info signing the hashes of a contribution, also used to rebuild a lost info file from the challenge and
+//! contribution files left on disk.
+
+use phase2_coordinator::{
+    authentication::{Authentication, KeyPair},
+    objects::{ContributionInfo, ContributionInfoError},
+};
+use setup_utils::calculate_hash;
+use thiserror::Error;
+
+use crate::hash::{ContributionHashes, CHALLENGE_HASH_LENGTH};
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum RebuildInfoError {
+    #[error("The contribution file has not been computed against the given challenge file")]
+    ChallengeMismatch,
+    #[error("Error while signing the contribution hashes: {0}")]
+    HashSignature(String),
+    #[error(transparent)]
+    Info(#[from] ContributionInfoError),
+}
+
+/// Sets the hashes of the contribution, and their signatures, in the contributor info.
+pub fn sign_contribution_hashes(
+    contrib_info: &mut ContributionInfo,
+    hashes: &ContributionHashes,
+    keypair: &KeyPair,
+) -> Result<(), RebuildInfoError> {
+    let sign = |message: &str| {
+        keypair
+            .sign(message)
+            .map_err(|e| RebuildInfoError::HashSignature(e.to_string()))
+    };
+
+    contrib_info.contribution_file_hash = hex::encode(&hashes.file_hash);
+    contrib_info.contribution_file_signature = sign(contrib_info.contribution_file_hash.as_str())?;
+    contrib_info.contribution_hash = hex::encode(&hashes.contribution_hash);
+    contrib_info.contribution_hash_signature = sign(contrib_info.contribution_hash.as_str())?;
+
+    Ok(())
+}
+
+/// Rebuilds the signed contributor info of the contribution to `round_height` from the challenge and the contribution
+/// files. The personal details and the timestamps of the original info can't be recovered and are left empty.
+pub fn rebuild_contribution_info(
+    round_height: u64,
+    challenge: &[u8],
+    contribution: &[u8],
+    keypair: &KeyPair,
+) -> Result<ContributionInfo, RebuildInfoError> {
+    if contribution.get(..CHALLENGE_HASH_LENGTH) != Some(calculate_hash(challenge).as_ref()) {
+        return Err(RebuildInfoError::ChallengeMismatch);
+    }
+
+    let mut contrib_info = ContributionInfo::default();
+    contrib_info.public_key = keypair.pubkey().to_string();
+    contrib_info.ceremony_round = round_height;
+    sign_contribution_hashes(&mut contrib_info, &ContributionHashes::new(contribution), keypair)?;
+    contrib_info.try_sign(keypair)?;
+
+    Ok(contrib_info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phase2_coordinator::storage::Object;
+
+    #[test]
+    fn test_rebuild_contribution_info() {
+        let keypair = KeyPair::new();
+        let challenge = vec![3u8; 256];
+        let mut contribution = calculate_hash(&challenge).to_vec();
+        contribution.extend_from_slice(&[7u8; 512]);
+        contribution.resize(Object::anoma_contribution_file_size(1, 1) as usize, 0);
+
+        let contrib_info = rebuild_contribution_info(1, &challenge, &contribution, &keypair).unwrap();
+        assert!(contrib_info.verify_signature().unwrap());
+        assert_eq!(1, contrib_info.ceremony_round);
+
+        // Same hashes and signatures as the ones computed during the contribution
+        let hashes = ContributionHashes::new(&contribution);
+        assert_eq!(hex::encode(&hashes.file_hash), contrib_info.contribution_file_hash);
+        assert_eq!(hex::encode(&hashes.contribution_hash), contrib_info.contribution_hash);
+        assert!(keypair.verify(
+            &contrib_info.contribution_file_hash,
+            &contrib_info.contribution_file_signature
+        ));
+        assert!(keypair.verify(
+            &contrib_info.contribution_hash,
+            &contrib_info.contribution_hash_signature
+        ));
+
+        // Contribution computed against another challenge
+        assert!(matches!(
+            rebuild_contribution_info(1, &[4u8; 256], &contribution, &keypair),
+            Err(RebuildInfoError::ChallengeMismatch)
+        ));
+    }
+}
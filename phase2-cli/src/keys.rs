@@ -1,15 +1,22 @@
-use std::{collections::HashMap, fmt::Display, str::FromStr};
+use std::{collections::HashMap, fmt::Display, io::ErrorKind, path::Path, str::FromStr};
 
 use bech32::{ToBase32, Variant};
-use ed25519_compact::KeyPair;
+use ed25519_compact::{KeyPair, Seed};
 use orion::{aead, kdf};
+use phase2_coordinator::{
+    authentication::KeyPair as CeremonyKeyPair,
+    io::{self, IOError, KeyPairUser},
+};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use thiserror::Error;
+use tracing::warn;
 
 const ADDRESS_BECH32_VARIANT: bech32::Variant = Variant::Bech32m;
 const ADDRESS_HRP: &str = "atest";
 const ENCRYPTED_KEY_PREFIX: &str = "encrypted:";
+/// Length of the default [`kdf::Salt`] prepended to the encrypted keypair.
+const ENCRYPTION_SALT_LEN: usize = 16;
 /// An address string before bech32m encoding must be this size.
 const FIXED_LEN_STRING_BYTES: usize = 45;
 const PKH_HASH_LEN: usize = 40;
@@ -24,6 +31,107 @@ pub enum DeserializeStoredKeypairError {
     MissingPrefix,
 }
 
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum DecryptKeypairError {
+    #[error("The encrypted keypair is too short")]
+    DataTooShort,
+    #[error("Decryption of the keypair failed, the password may be wrong")]
+    DecryptionFailed,
+    #[error("The decrypted keypair is not valid: {0}")]
+    InvalidKeypair(#[from] ed25519_compact::Error),
+}
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum LoadKeypairError {
+    #[error("The keypair file contains {0} keypairs, select one with --keypair-alias")]
+    AmbiguousKeypair(usize),
+    #[error("Error while decrypting the keypair: {0}")]
+    Decrypt(#[from] DecryptKeypairError),
+    #[error("The keypair is not valid: {0}")]
+    InvalidKeypair(#[from] ed25519_compact::Error),
+    #[error("Error while reading the keypair file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Error while loading the keypair from the mnemonic: {0}")]
+    Mnemonic(#[from] IOError),
+    #[error("The keypair file doesn't contain any keypair")]
+    MissingKeypair,
+    #[error("The keypair file is not valid: {0}")]
+    Toml(#[from] toml::de::Error),
+    #[error("The keypair file doesn't contain a keypair with alias \"{0}\"")]
+    UnknownAlias(String),
+    #[error("Unknown keypair source \"{0}\", expected one of generate, mnemonic or file")]
+    UnknownSource(String),
+}
+
+/// Where the keypair of a command comes from.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KeypairSource {
+    /// A new keypair, generated from a random mnemonic.
+    Generate,
+    /// The keypair derived from a mnemonic file.
+    Mnemonic,
+    /// The encrypted keypair exported with the `export-keypair` command, falling back to the mnemonic file if there
+    /// is no such file. A file holding several keypairs requires the alias of the keypair.
+    File,
+}
+
+impl FromStr for KeypairSource {
+    type Err = LoadKeypairError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "generate" => Ok(Self::Generate),
+            "mnemonic" => Ok(Self::Mnemonic),
+            "file" => Ok(Self::File),
+            _ => Err(LoadKeypairError::UnknownSource(s.to_owned())),
+        }
+    }
+}
+
+impl KeypairSource {
+    /// Loads the keypair from this source. The mnemonic file defaults to the one of the `user`, and the keypair of the
+    /// keypair file is selected by its `alias`, which is only optional if the file holds a single keypair. The password
+    /// of the encrypted keypair is only asked for when reading the keypair file.
+    pub fn load_keypair(
+        self,
+        user: KeyPairUser,
+        mnemonic_file: Option<&Path>,
+        keypair_file: &Path,
+        alias: Option<&str>,
+        password: impl FnOnce() -> std::io::Result<String>,
+    ) -> Result<CeremonyKeyPair, LoadKeypairError> {
+        let mnemonic_file = mnemonic_file.unwrap_or_else(|| match user {
+            KeyPairUser::Coordinator => Path::new(io::COORDINATOR_MNEMONIC_FILE),
+            KeyPairUser::Contributor => Path::new(io::CONTRIBUTOR_MNEMONIC_FILE),
+        });
+
+        match self {
+            Self::Generate => Ok(io::generate_keypair(user)?),
+            Self::Mnemonic => Ok(io::keypair_from_mnemonic_file(mnemonic_file)?),
+            Self::File => match std::fs::read_to_string(keypair_file) {
+                Ok(content) => {
+                    let toml_config: TomlConfig = toml::from_str(&content)?;
+                    let keypair = toml_config.key(alias)?.decrypt(password()?)?;
+
+                    // The first half of the secret key is the seed of the keypair
+                    Ok(CeremonyKeyPair::try_from_seed(keypair.sk.as_ref())?)
+                }
+                Err(e) if e.kind() == ErrorKind::NotFound => {
+                    warn!(
+                        "The keypair file \"{}\" doesn't exist, falling back to the mnemonic file \"{}\"",
+                        keypair_file.display(),
+                        mnemonic_file.display()
+                    );
+                    Self::Mnemonic.load_keypair(user, Some(mnemonic_file), keypair_file, alias, password)
+                }
+                Err(e) => Err(e.into()),
+            },
+        }
+    }
+}
+
 #[derive(Deserialize, Serialize)]
 /// Represents a Namada wallet toml file.
 pub struct TomlConfig<'a> {
@@ -43,6 +151,23 @@ impl<'a> TomlConfig<'a> {
 
         Self { keys, addresses, pkhs }
     }
+
+    /// Returns the aliases and the encrypted keypairs stored in the file.
+    pub fn keys(&self) -> std::collections::hash_map::Iter<'_, &'a str, EncryptedKeypair> {
+        self.keys.iter()
+    }
+
+    /// Returns the encrypted keypair with the given alias or, without alias, the only keypair stored in the file.
+    pub fn key(&self, alias: Option<&str>) -> Result<&EncryptedKeypair, LoadKeypairError> {
+        match alias {
+            Some(alias) => self
+                .keys
+                .get(alias)
+                .ok_or_else(|| LoadKeypairError::UnknownAlias(alias.to_owned())),
+            None if self.keys.len() > 1 => Err(LoadKeypairError::AmbiguousKeypair(self.keys.len())),
+            None => self.keys.values().next().ok_or(LoadKeypairError::MissingKeypair),
+        }
+    }
 }
 
 /// An encrypted keypair stored in a wallet
@@ -113,6 +238,23 @@ impl EncryptedKeypair {
 
         Self(encrypted_data)
     }
+
+    /// Decrypt the stored [KeyPair] with the password used at encryption
+    pub fn decrypt(&self, password: impl AsRef<[u8]>) -> Result<KeyPair, DecryptKeypairError> {
+        if self.0.len() < ENCRYPTION_SALT_LEN {
+            return Err(DecryptKeypairError::DataTooShort);
+        }
+        let (salt, encrypted_keypair) = self.0.split_at(ENCRYPTION_SALT_LEN);
+        let salt = kdf::Salt::from_slice(salt).map_err(|_| DecryptKeypairError::DataTooShort)?;
+        let encryption_key = encryption_key(&salt, password.as_ref());
+
+        let sk = aead::open(&encryption_key, encrypted_keypair).map_err(|_| DecryptKeypairError::DecryptionFailed)?;
+
+        // Skip the initial 0 of the borsh encoding, the remaining 32 bytes are the seed of the keypair
+        let seed = sk.get(1..).ok_or(DecryptKeypairError::DataTooShort)?;
+
+        Ok(KeyPair::from_seed(Seed::from_slice(seed)?))
+    }
 }
 
 /// Make encryption secret key from a password.
@@ -143,3 +285,107 @@ pub fn bech_encode_address(address: &str) -> String {
 
     bech32::encode(ADDRESS_HRP, bytes.to_base32(), ADDRESS_BECH32_VARIANT).unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decrypt_exported_keypair() {
+        let keypair = KeyPair::from_seed(Seed::from_slice(&[7u8; 32]).unwrap());
+        let pubkey = hex::encode(keypair.pk.as_ref());
+        let password = "password";
+
+        // Export
+        let address = generate_address(&pubkey);
+        let bech_address = bech_encode_address(&address);
+        let toml_config = TomlConfig::new(
+            "alias",
+            EncryptedKeypair::from_keypair(&keypair, password),
+            &bech_address,
+            &address,
+        );
+        let content = toml::to_string(&toml_config).unwrap();
+
+        // Read back
+        let toml_config: TomlConfig = toml::from_str(&content).unwrap();
+        let (alias, encrypted_keypair) = toml_config.keys().next().unwrap();
+        assert_eq!(*alias, "alias");
+
+        let decrypted = encrypted_keypair.decrypt(password).unwrap();
+        assert_eq!(hex::encode(decrypted.pk.as_ref()), pubkey);
+        assert!(encrypted_keypair.decrypt("wrong password").is_err());
+    }
+
+    #[test]
+    fn test_load_keypair_sources() {
+        let dir = tempfile::tempdir().unwrap();
+        let mnemonic_file = dir.path().join("contributor.mnemonic");
+        let keypair_file = dir.path().join("keypair.toml");
+        let load = |source: KeypairSource, alias: Option<&str>, password: &str| {
+            source.load_keypair(
+                KeyPairUser::Contributor,
+                Some(&mnemonic_file),
+                &keypair_file,
+                alias,
+                || Ok(password.to_owned()),
+            )
+        };
+        std::fs::write(
+            &mnemonic_file,
+            "1. scheme     2. drift      3. lava       4. crystal
+            5. miracle    6. average    7. admit      8. tuna
+            9. all        10. initial   11. seat      12. crash
+            13. mask      14. depend    15. kangaroo  16. dove
+            17. olive     18. pumpkin   19. trap      20. minute
+            21. history   22. enter     23. immense   24. settle",
+        )
+        .unwrap();
+
+        // Without the keypair file, the file source falls back to the mnemonic
+        let from_mnemonic = load(KeypairSource::Mnemonic, None, "password").unwrap();
+        let fallback = load(KeypairSource::File, None, "password").unwrap();
+        assert_eq!(from_mnemonic.pubkey(), fallback.pubkey());
+
+        // Export the keypair of the mnemonic like the export-keypair command
+        let seed = io::seed_from_string(&std::fs::read_to_string(&mnemonic_file).unwrap()).unwrap();
+        let keypair = KeyPair::from_seed(Seed::from_slice(&seed[..32]).unwrap());
+        let address = generate_address(&hex::encode(keypair.pk.as_ref()));
+        let mut toml_config = TomlConfig::new(
+            "alias",
+            EncryptedKeypair::from_keypair(&keypair, "password"),
+            &bech_encode_address(&address),
+            &address,
+        );
+        std::fs::write(&keypair_file, toml::to_string(&toml_config).unwrap()).unwrap();
+
+        let from_file = load(KeypairSource::File, None, "password").unwrap();
+        assert_eq!(from_mnemonic.pubkey(), from_file.pubkey());
+        assert!(matches!(
+            load(KeypairSource::File, None, "wrong password"),
+            Err(LoadKeypairError::Decrypt(_))
+        ));
+
+        // A file with several keypairs requires the alias of the keypair
+        let other_keypair = KeyPair::from_seed(Seed::from_slice(&[7u8; 32]).unwrap());
+        toml_config
+            .keys
+            .insert("other", EncryptedKeypair::from_keypair(&other_keypair, "password"));
+        std::fs::write(&keypair_file, toml::to_string(&toml_config).unwrap()).unwrap();
+        assert!(matches!(
+            load(KeypairSource::File, None, "password"),
+            Err(LoadKeypairError::AmbiguousKeypair(2))
+        ));
+        let from_alias = load(KeypairSource::File, Some("alias"), "password").unwrap();
+        assert_eq!(from_mnemonic.pubkey(), from_alias.pubkey());
+        let from_other_alias = load(KeypairSource::File, Some("other"), "password").unwrap();
+        assert_eq!(hex::encode(other_keypair.pk.as_ref()), from_other_alias.pubkey());
+        assert!(matches!(
+            load(KeypairSource::File, Some("unknown"), "password"),
+            Err(LoadKeypairError::UnknownAlias(_))
+        ));
+
+        assert_eq!(KeypairSource::Generate, "generate".parse().unwrap());
+        assert!("wallet".parse::<KeypairSource>().is_err());
+    }
+}
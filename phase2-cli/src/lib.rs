@@ -1,17 +1,39 @@
 // Documentation
 #![doc = include_str!("../README.md")]
 
-use std::path::PathBuf;
+use std::{num::NonZeroU64, path::PathBuf};
 
 pub mod ascii_logo;
+pub mod cert_pin;
+pub mod checkpoint;
+pub mod disk_space;
+pub mod estimate;
+pub mod fetch;
+pub mod hash;
+pub mod i18n;
+pub mod info;
 pub mod keys;
+pub mod manifest;
+pub mod mirror;
+pub mod output;
+pub mod progress;
 pub mod requests;
+pub mod summary;
+pub mod transcript;
 
 use phase2_coordinator::{
+    authentication::KeyPair,
+    environment::CeremonyParameters,
+    io::KeyPairUser,
     objects::round::LockedLocators,
     rest_utils::{ContributorStatus, PostChunkRequest},
+    Cohort,
 };
 
+use cert_pin::CertPin;
+use i18n::Lang;
+use keys::{KeypairSource, LoadKeypairError};
+use output::Verbosity;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use structopt::StructOpt;
@@ -26,6 +48,28 @@ pub struct CoordinatorUrl {
         parse(try_from_str)
     )]
     pub coordinator: Url,
+    #[structopt(long, help = "Log the requests sent to the coordinator and its responses, truncated")]
+    pub trace_http: bool,
+    #[structopt(
+        long,
+        value_name = "ADDR",
+        help = "Send all the requests, including the download of the challenge, through the SOCKS5 proxy listening at this address, e.g. the Tor daemon at 127.0.0.1:9050"
+    )]
+    pub socks5: Option<String>,
+    #[structopt(
+        long,
+        value_name = "SHA256",
+        help = "Abort the connections to the coordinator unless its TLS certificate has this SHA-256 fingerprint, as published by the ceremony team",
+        parse(try_from_str)
+    )]
+    pub cert_pin: Option<CertPin>,
+    #[structopt(
+        long,
+        value_name = "HEX",
+        help = "Abort unless the url of the challenge and the queue status are signed with this public key of the coordinator, as published by the ceremony team",
+        parse(try_from_str = requests::parse_coordinator_pubkey)
+    )]
+    pub coordinator_pubkey: Option<ed25519_compact::PublicKey>,
 }
 
 /// Accepts both the ceremony token and the secret token for reserved endpoints
@@ -37,12 +81,97 @@ pub struct RequestWithToken {
     pub token: String,
 }
 
+/// The token of a contribution, only optional when estimating it
+#[derive(Debug, StructOpt)]
+pub struct ContributionRequest {
+    #[structopt(flatten)]
+    pub url: CoordinatorUrl,
+    #[structopt(
+        help = "The secret token required for the contribution",
+        required_unless = "estimate-only"
+    )]
+    pub token: Option<String>,
+}
+
+/// Selects the rounds of the contributions to retrieve
+#[derive(Debug, StructOpt)]
+pub struct ContributionsRange {
+    #[structopt(flatten)]
+    pub url: CoordinatorUrl,
+    #[structopt(long = "from", help = "The first round of the contributions to retrieve")]
+    pub from_round: Option<u64>,
+    #[structopt(long = "to", help = "The last round of the contributions to retrieve")]
+    pub to_round: Option<u64>,
+    #[structopt(long, help = "The number of contributions in the range to skip")]
+    pub offset: Option<usize>,
+    #[structopt(long, help = "The maximum number of contributions to retrieve")]
+    pub page_size: Option<usize>,
+}
+
 #[derive(Debug, StructOpt)]
 pub struct MnemonicPath {
     #[structopt(help = "The path to the mnemonic file", required = true, parse(try_from_str))]
     pub path: PathBuf,
 }
 
+#[derive(Debug, StructOpt)]
+pub struct KeypairPath {
+    #[structopt(
+        help = "The path to the keypair file",
+        required = true,
+        default_value = "keypair.toml",
+        parse(try_from_str)
+    )]
+    pub keypair_file: PathBuf,
+}
+
+/// Selects the keypair used by the commands signing their requests
+#[derive(Clone, Debug, StructOpt)]
+pub struct KeypairOpt {
+    #[structopt(
+        long,
+        global = true,
+        possible_values = &["generate", "mnemonic", "file"],
+        help = "Where to take the keypair from: a newly generated one, the mnemonic file or the encrypted keypair file, falling back to the mnemonic file if it doesn't exist. Defaults to \"generate\" for the contribution and to \"mnemonic\" for the other commands"
+    )]
+    pub keypair_source: Option<KeypairSource>,
+    #[structopt(
+        long,
+        global = true,
+        help = "The path to the mnemonic file of the \"mnemonic\" keypair source. Defaults to \"contributor.mnemonic\" for the contribution and to \"coordinator.mnemonic\" for the other commands",
+        parse(try_from_str)
+    )]
+    pub mnemonic_file: Option<PathBuf>,
+    #[structopt(
+        long = "keypair-file",
+        global = true,
+        default_value = "keypair.toml",
+        help = "The path to the encrypted keypair file of the \"file\" keypair source",
+        parse(try_from_str)
+    )]
+    pub encrypted_keypair_file: PathBuf,
+    #[structopt(
+        long,
+        global = true,
+        help = "The alias of the keypair to use, required if the keypair file of the \"file\" keypair source holds several keypairs"
+    )]
+    pub keypair_alias: Option<String>,
+}
+
+impl KeypairOpt {
+    /// Loads the keypair from the selected source, or from the given default one. The password of the encrypted
+    /// keypair is prompted to the user.
+    pub fn load_keypair(&self, default: KeypairSource, user: KeyPairUser) -> Result<KeyPair, LoadKeypairError> {
+        self.keypair_source.unwrap_or(default).load_keypair(
+            user,
+            self.mnemonic_file.as_deref(),
+            &self.encrypted_keypair_file,
+            self.keypair_alias.as_deref(),
+            || rpassword::prompt_password("Enter the password to decrypt the keypair: "),
+        )
+    }
+}
+
 #[derive(Debug, StructOpt)]
 pub struct Contributors {
     #[structopt(
@@ -63,17 +192,41 @@ pub enum Branches {
     )]
     AnotherMachine {
         #[structopt(flatten)]
-        request: RequestWithToken,
+        request: ContributionRequest,
+        #[structopt(long, help = "Cap the upload speed of the contribution, in bytes per second")]
+        max_upload_bytes_per_sec: Option<NonZeroU64>,
+        #[structopt(
+            long,
+            help = "Contribute anonymously: no name, email or memo is asked and the contribution info only holds the public key and the hashes"
+        )]
+        anonymous: bool,
+        #[structopt(
+            long,
+            help = "Only print the expected download and upload sizes, without joining the queue. The computation time is not estimated since it runs on the offline machine"
+        )]
+        estimate_only: bool,
     },
     #[structopt(about = "The default contribution path, executes both communication and computation on this machine")]
     Default {
         #[structopt(flatten)]
-        request: RequestWithToken,
+        request: ContributionRequest,
         #[structopt(
             long,
             help = "Give a custom random seed (32 bytes / 64 characters in hexadecimal) for the ChaCha RNG"
         )]
         custom_seed: bool,
+        #[structopt(long, help = "Cap the upload speed of the contribution, in bytes per second")]
+        max_upload_bytes_per_sec: Option<NonZeroU64>,
+        #[structopt(
+            long,
+            help = "Contribute anonymously: no name, email or memo is asked and the contribution info only holds the public key and the hashes"
+        )]
+        anonymous: bool,
+        #[structopt(
+            long,
+            help = "Only print the expected download and upload sizes and computation time, without joining the queue"
+        )]
+        estimate_only: bool,
     },
     #[structopt(
         about = "Performs only the computation of the contribution, to be used in conjunction with \"namada-ts contribute another-machine\" on a separate machine"
@@ -84,9 +237,35 @@ pub enum Branches {
             help = "Give a custom random seed (32 bytes / 64 characters in hexadecimal) for the ChaCha RNG"
         )]
         custom_seed: bool,
+        #[structopt(
+            long,
+            default_value = "masp",
+            help = "The parameters of the challenge: \"masp\" or \"circuits:N\" for a ceremony of N other circuits"
+        )]
+        parameters: CeremonyParameters,
     },
 }
 
+impl CeremonyOpt {
+    /// Returns the url of the coordinator for the commands interacting with it.
+    pub fn coordinator_url(&self) -> Option<&CoordinatorUrl> {
+        match self {
+            CeremonyOpt::Contribute(Branches::AnotherMachine { request, .. })
+            | CeremonyOpt::Contribute(Branches::Default { request, .. })
+            | CeremonyOpt::FetchChallenge { request, .. }
+            | CeremonyOpt::GetState(request) => Some(&request.url),
+            CeremonyOpt::CloseCeremony(url) | CeremonyOpt::UpdateCohorts(url) => Some(url),
+            CeremonyOpt::Mirror { url, .. } => Some(url),
+            CeremonyOpt::RebuildInfo { upload: true, url, .. } => Some(url),
+            #[cfg(debug_assertions)]
+            CeremonyOpt::GetContributions(range) => Some(&range.url),
+            #[cfg(debug_assertions)]
+            CeremonyOpt::VerifyContributions(url) | CeremonyOpt::UpdateCoordinator(url) => Some(url),
+            _ => None,
+        }
+    }
+}
+
 pub enum TokenCohort {
     Finished,
     InProgress,
@@ -112,6 +291,15 @@ impl Token {
             TokenCohort::Finished
         }
     }
+
+    /// Replaces the window of the cohort of the token with the one published by the coordinator, which is the one
+    /// enforced when joining the queue, if the schedule includes the cohort.
+    pub fn update_window(&mut self, schedule: &[Cohort]) {
+        if let Some(cohort) = schedule.iter().find(|cohort| cohort.index == self.index) {
+            self.from = cohort.start.unix_timestamp() as u64;
+            self.to = cohort.end.unix_timestamp() as u64;
+        }
+    }
 }
 
 #[derive(Debug, StructOpt)]
@@ -128,6 +316,45 @@ pub struct VerifySignatureContribution {
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "namada-ts", about = "Namada CLI for trusted setup.")]
+pub struct CliOpt {
+    #[structopt(
+        short,
+        long,
+        global = true,
+        conflicts_with = "verbose",
+        help = "Hide the progress of the contribution and the logs below the error level. The prompts and the results \
+                of the commands are still printed"
+    )]
+    pub quiet: bool,
+    #[structopt(short, long, global = true, help = "Print the debug and trace logs")]
+    pub verbose: bool,
+    #[structopt(
+        long,
+        global = true,
+        env = "LANG",
+        default_value = "en",
+        parse(from_str = Lang::from_locale),
+        help = "The language of the messages, e.g. \"fr\", English if it has no translation"
+    )]
+    pub lang: Lang,
+    #[structopt(flatten)]
+    pub keypair: KeypairOpt,
+    #[structopt(subcommand)]
+    pub command: CeremonyOpt,
+}
+
+impl CliOpt {
+    /// Returns the verbosity selected by the flags.
+    pub fn verbosity(&self) -> Verbosity {
+        match (self.quiet, self.verbose) {
+            (true, _) => Verbosity::Quiet,
+            (_, true) => Verbosity::Verbose,
+            _ => Verbosity::Normal,
+        }
+    }
+}
+
+#[derive(Debug, StructOpt)]
 pub enum CeremonyOpt {
     #[structopt(about = "Contribute to the ceremony")]
     Contribute(Branches),
@@ -135,21 +362,101 @@ pub enum CeremonyOpt {
     CloseCeremony(CoordinatorUrl),
     #[structopt(about = "Generate a Namada keypair from a mnemonic")]
     ExportKeypair(MnemonicPath),
+    #[structopt(
+        about = "Join the queue and, when it's your turn, download the challenge to compute with \"namada-ts contribute offline\" on another machine, without contributing"
+    )]
+    FetchChallenge {
+        #[structopt(flatten)]
+        request: RequestWithToken,
+        #[structopt(
+            long,
+            help = "The directory where to save the challenge and the transfer manifest",
+            default_value = ".",
+            parse(try_from_str)
+        )]
+        output: PathBuf,
+    },
     #[structopt(about = "Generate the list of addresses of the contributors")]
     GenerateAddresses(Contributors),
     #[cfg(debug_assertions)]
-    #[structopt(about = "Get a list of all the contributions received")]
-    GetContributions(CoordinatorUrl),
+    #[structopt(about = "Get a list of the contributions received, optionally restricted to a range of rounds")]
+    GetContributions(ContributionsRange),
     #[structopt(about = "Get the state of the coordinator")]
     GetState(RequestWithToken),
+    #[structopt(about = "Compute the hashes of a contribution file the same way the coordinator does")]
+    Hash {
+        #[structopt(help = "The path to the contribution file", parse(try_from_str))]
+        file: PathBuf,
+        #[structopt(help = "The round height of the contribution")]
+        round_height: u64,
+        #[structopt(help = "The id of the contribution in its round")]
+        contribution_id: u64,
+    },
+    #[structopt(
+        about = "Print the size and the hashes of a contribution file, and check its size against the one expected by the coordinator"
+    )]
+    Inspect {
+        #[structopt(help = "The path to the contribution file", parse(try_from_str))]
+        file: PathBuf,
+        #[structopt(help = "The round height of the contribution")]
+        round_height: u64,
+        #[structopt(help = "The id of the contribution in its round")]
+        contribution_id: u64,
+    },
+    #[structopt(
+        about = "Keep a mirror of the transcripts of the completed rounds, resuming from the last mirrored one"
+    )]
+    Mirror {
+        #[structopt(flatten)]
+        url: CoordinatorUrl,
+        #[structopt(
+            long,
+            help = "The directory where to store the transcripts and their manifest",
+            default_value = ".",
+            parse(try_from_str)
+        )]
+        output_dir: PathBuf,
+        #[structopt(
+            long,
+            help = "The number of seconds between two checks for newly completed rounds",
+            default_value = "60"
+        )]
+        poll_interval: u64,
+    },
+    #[structopt(about = "Rebuild the contribution info file from the challenge and contribution files")]
+    RebuildInfo {
+        #[structopt(help = "The round height of the contribution")]
+        round_height: u64,
+        #[structopt(help = "The path to the challenge file", parse(try_from_str))]
+        challenge_file: PathBuf,
+        #[structopt(help = "The path to the contribution file", parse(try_from_str))]
+        contribution_file: PathBuf,
+        #[structopt(flatten)]
+        keypair: MnemonicPath,
+        #[structopt(long, help = "The id of the contribution in its round", default_value = "1")]
+        contribution_id: u64,
+        #[structopt(long, help = "Upload the rebuilt contribution info to the coordinator")]
+        upload: bool,
+        #[structopt(flatten)]
+        url: CoordinatorUrl,
+    },
     #[cfg(debug_assertions)]
     #[structopt(about = "Verify the pending contributions")]
     VerifyContributions(CoordinatorUrl),
+    #[structopt(about = "Decrypt a keypair file and print its public key")]
+    ShowPubkey(KeypairPath),
     #[structopt(about = "Update the cohorts' tokens")]
     UpdateCohorts(CoordinatorUrl),
     #[cfg(debug_assertions)]
     #[structopt(about = "Update manually the coordinator")]
     UpdateCoordinator(CoordinatorUrl),
+    #[structopt(
+        about = "Validate the whole chain of contributions of a downloaded round transcript, independently of the coordinator"
+    )]
+    ValidateTranscript {
+        #[structopt(help = "The path to the transcript file", parse(try_from_str))]
+        transcript_file: PathBuf,
+    },
     #[structopt(about = "Verify a contribution")]
     VerifyContribution(VerifySignatureContribution),
 }
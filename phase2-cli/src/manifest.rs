@@ -0,0 +1,151 @@
+//! Transfer manifest binding the challenge and the contribution exchanged with an offline machine.
+
+use std::path::Path;
+
+use phase2_coordinator::storage::ContributionLocator;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Name of the challenge file handed over to the offline machine.
+pub const OFFLINE_CHALLENGE_FILE_NAME: &str = "challenge.params";
+/// Name of the contribution file computed on the offline machine.
+pub const OFFLINE_CONTRIBUTION_FILE_NAME: &str = "contribution.params";
+/// Name of the manifest written alongside the challenge file of the offline contribution.
+pub const OFFLINE_MANIFEST_FILE_NAME: &str = "transfer_manifest.json";
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum ManifestError {
+    #[error("The manifest challenge hash is {manifest} but the challenge hash is {challenge}")]
+    ChallengeHashMismatch { manifest: String, challenge: String },
+    #[error("The contribution doesn't start with the challenge hash of the manifest")]
+    ContributionMismatch,
+    #[error("Error while accessing the manifest: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The manifest is not valid json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("The manifest refers to {found} but the locked contribution is {expected}")]
+    LocatorMismatch { expected: String, found: String },
+}
+
+/// Describes the challenge handed over to the offline machine: the offline command checks it against the challenge
+/// file before computing and the online side checks it against the locked contribution before uploading.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct TransferManifest {
+    pub round_height: u64,
+    pub chunk_id: u64,
+    pub contribution_id: u64,
+    /// Hex encoded hash of the challenge.
+    pub challenge_hash: String,
+}
+
+impl TransferManifest {
+    pub fn new(locator: &ContributionLocator, challenge_hash: &[u8]) -> Self {
+        Self {
+            round_height: locator.round_height(),
+            chunk_id: locator.chunk_id(),
+            contribution_id: locator.contribution_id(),
+            challenge_hash: hex::encode(challenge_hash),
+        }
+    }
+
+    /// Reads the manifest from file.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, ManifestError> {
+        Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+    }
+
+    /// Writes the manifest to file.
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), ManifestError> {
+        Ok(std::fs::write(path, serde_json::to_vec_pretty(self)?)?)
+    }
+
+    /// A human readable label of the contribution described by the manifest.
+    pub fn label(&self) -> String {
+        format!(
+            "round {} chunk {} contribution {}",
+            self.round_height, self.chunk_id, self.contribution_id
+        )
+    }
+
+    /// Checks that the manifest refers to the expected challenge hash.
+    pub fn verify_challenge(&self, challenge_hash: &[u8]) -> Result<(), ManifestError> {
+        let challenge = hex::encode(challenge_hash);
+
+        if self.challenge_hash != challenge {
+            return Err(ManifestError::ChallengeHashMismatch {
+                manifest: self.challenge_hash.clone(),
+                challenge,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Checks that the manifest matches the expected one and that the contribution has been computed against its
+    /// challenge, i.e. that it starts with the challenge hash.
+    pub fn verify_contribution(&self, expected: &TransferManifest, contribution: &[u8]) -> Result<(), ManifestError> {
+        if (self.round_height, self.chunk_id, self.contribution_id)
+            != (expected.round_height, expected.chunk_id, expected.contribution_id)
+        {
+            return Err(ManifestError::LocatorMismatch {
+                expected: expected.label(),
+                found: self.label(),
+            });
+        }
+
+        let challenge_hash = hex::decode(&expected.challenge_hash).map_err(|_| ManifestError::ContributionMismatch)?;
+        self.verify_challenge(&challenge_hash)?;
+
+        if !contribution.starts_with(&challenge_hash) {
+            return Err(ManifestError::ContributionMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mismatched_manifest() {
+        let challenge_hash = [1u8; 64];
+        let expected = TransferManifest::new(&ContributionLocator::new(3, 0, 1, false), &challenge_hash);
+        let mut contribution = challenge_hash.to_vec();
+        contribution.extend_from_slice(&[7u8; 128]);
+
+        // Matching manifest
+        let manifest_file = tempfile::NamedTempFile::new().unwrap();
+        expected.write(manifest_file.path()).unwrap();
+        let manifest = TransferManifest::read(manifest_file.path()).unwrap();
+        assert!(manifest.verify_challenge(&challenge_hash).is_ok());
+        assert!(manifest.verify_contribution(&expected, &contribution).is_ok());
+
+        // Manifest of another round
+        let other_round = TransferManifest::new(&ContributionLocator::new(2, 0, 1, false), &challenge_hash);
+        assert!(matches!(
+            other_round.verify_contribution(&expected, &contribution),
+            Err(ManifestError::LocatorMismatch { .. })
+        ));
+
+        // Manifest of another challenge
+        let other_challenge = TransferManifest::new(&ContributionLocator::new(3, 0, 1, false), &[2u8; 64]);
+        assert!(matches!(
+            other_challenge.verify_challenge(&challenge_hash),
+            Err(ManifestError::ChallengeHashMismatch { .. })
+        ));
+        assert!(matches!(
+            other_challenge.verify_contribution(&expected, &contribution),
+            Err(ManifestError::ChallengeHashMismatch { .. })
+        ));
+
+        // Contribution computed against another challenge
+        let mut wrong_contribution = [2u8; 64].to_vec();
+        wrong_contribution.extend_from_slice(&[7u8; 128]);
+        assert!(matches!(
+            manifest.verify_contribution(&expected, &wrong_contribution),
+            Err(ManifestError::ContributionMismatch)
+        ));
+    }
+}
@@ -0,0 +1,212 @@
+//! Mirror of the transcripts of the completed rounds of the ceremony, kept up to date by polling the coordinator, for
+//! the archivists who want to preserve them independently.
+//!
+//! The transcripts are stored in the output directory along with a manifest of their hashes, which is also used to
+//! resume the mirror from the last mirrored round after a restart.
+
+use std::{io::ErrorKind, path::Path, time::Duration};
+
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use setup_utils::calculate_hash;
+use thiserror::Error;
+use tracing::{info, warn};
+
+use crate::requests::{self, RequestError};
+
+/// Name of the manifest of the mirrored transcripts, in the output directory.
+pub const MIRROR_MANIFEST_FILE_NAME: &str = "mirror_manifest.json";
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum MirrorError {
+    #[error("Error while accessing the mirror: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The manifest of the mirror is not valid json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Error while querying the coordinator: {0}")]
+    Request(#[from] RequestError),
+}
+
+/// A transcript stored in the mirror.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MirroredRound {
+    pub round_height: u64,
+    /// Name of the transcript file, in the output directory.
+    pub file_name: String,
+    /// Hex encoded hash of the transcript.
+    pub hash: String,
+}
+
+/// Lists the transcripts stored in the mirror, by increasing round height.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct MirrorManifest {
+    pub rounds: Vec<MirroredRound>,
+}
+
+impl MirrorManifest {
+    /// Reads the manifest from the output directory, empty if nothing has been mirrored yet.
+    pub fn read(output_dir: &Path) -> Result<Self, MirrorError> {
+        match std::fs::read(output_dir.join(MIRROR_MANIFEST_FILE_NAME)) {
+            Ok(manifest) => Ok(serde_json::from_slice(&manifest)?),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the manifest to the output directory. The manifest is replaced atomically, so that an interruption
+    /// can't leave it truncated.
+    fn write(&self, output_dir: &Path) -> Result<(), MirrorError> {
+        let tmp_path = output_dir.join(format!("{}.tmp", MIRROR_MANIFEST_FILE_NAME));
+        std::fs::write(&tmp_path, serde_json::to_vec_pretty(self)?)?;
+
+        Ok(std::fs::rename(tmp_path, output_dir.join(MIRROR_MANIFEST_FILE_NAME))?)
+    }
+
+    /// The first round which hasn't been mirrored yet. Round 0 only initializes the ceremony and has no transcript.
+    pub fn next_round(&self) -> u64 {
+        self.rounds.last().map_or(1, |round| round.round_height + 1)
+    }
+}
+
+/// Name of the transcript file of the given round.
+pub fn transcript_file_name(round_height: u64) -> String {
+    format!("round_{}.transcript", round_height)
+}
+
+/// Downloads the transcripts of the rounds completed since the last mirrored one, recording each of them in the
+/// manifest as soon as it's stored. Returns the heights of the newly mirrored rounds.
+pub async fn mirror_completed_rounds(
+    client: &Client,
+    coordinator: &Url,
+    output_dir: &Path,
+    manifest: &mut MirrorManifest,
+) -> Result<Vec<u64>, MirrorError> {
+    let status = requests::get_ceremony_status(client, coordinator).await?;
+    let mut mirrored = Vec::new();
+
+    // The current round is still in progress
+    for round_height in manifest.next_round()..status.round_height {
+        let transcript = requests::get_round_transcript(client, coordinator, round_height).await?;
+        let file_name = transcript_file_name(round_height);
+        std::fs::write(output_dir.join(&file_name), &transcript)?;
+
+        manifest.rounds.push(MirroredRound {
+            round_height,
+            file_name,
+            hash: hex::encode(calculate_hash(&transcript)),
+        });
+        manifest.write(output_dir)?;
+        mirrored.push(round_height);
+    }
+
+    Ok(mirrored)
+}
+
+/// Keeps the mirror in the output directory up to date, checking for newly completed rounds at every poll interval.
+/// The failures to reach the coordinator are retried at the next poll, so this only returns if the output directory
+/// can't be set up.
+pub async fn mirror_loop(
+    client: &Client,
+    coordinator: &Url,
+    output_dir: &Path,
+    poll_interval: Duration,
+) -> Result<(), MirrorError> {
+    std::fs::create_dir_all(output_dir)?;
+    let mut manifest = MirrorManifest::read(output_dir)?;
+    info!(
+        "Mirroring the transcripts to {}, starting from round {}",
+        output_dir.display(),
+        manifest.next_round()
+    );
+
+    loop {
+        match mirror_completed_rounds(client, coordinator, output_dir, &mut manifest).await {
+            Ok(rounds) => {
+                for round_height in rounds {
+                    info!("Mirrored the transcript of round {}", round_height);
+                }
+            }
+            Err(e) => warn!("Failed to mirror the transcripts, retrying at the next poll: {}", e),
+        }
+
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    #[tokio::test]
+    async fn test_mirror_completed_rounds() {
+        // Mock coordinator whose rounds 1 and 2 have been completed
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let coordinator = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let requested = Arc::new(Mutex::new(Vec::new()));
+        let server_requested = requested.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let len = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..len]);
+                }
+                let request = String::from_utf8(request).unwrap();
+                let path = request.split(' ').nth(1).unwrap().to_owned();
+                let body = match path.as_str() {
+                    "/ceremony/status" => r#"{"round_height":3,"queue_size":0,"verification_backlog":0}"#.to_owned(),
+                    _ => format!("transcript of {}", path),
+                };
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                server_requested.lock().unwrap().push(path);
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let output_dir = tempfile::tempdir().unwrap();
+        let mut manifest = MirrorManifest::read(output_dir.path()).unwrap();
+        let mirrored = mirror_completed_rounds(&Client::new(), &coordinator, output_dir.path(), &mut manifest)
+            .await
+            .unwrap();
+        assert_eq!(vec![1, 2], mirrored);
+
+        for (round, round_height) in manifest.rounds.iter().zip(1..) {
+            let transcript = std::fs::read(output_dir.path().join(transcript_file_name(round_height))).unwrap();
+            assert_eq!(
+                format!("transcript of /round/{}/transcript", round_height).as_bytes(),
+                transcript
+            );
+            assert_eq!(round_height, round.round_height);
+            assert_eq!(hex::encode(calculate_hash(&transcript)), round.hash);
+        }
+
+        // On restart the mirror resumes from the manifest, without downloading the transcripts again
+        let mut manifest = MirrorManifest::read(output_dir.path()).unwrap();
+        assert_eq!(2, manifest.rounds.len());
+        let mirrored = mirror_completed_rounds(&Client::new(), &coordinator, output_dir.path(), &mut manifest)
+            .await
+            .unwrap();
+        assert!(mirrored.is_empty());
+        assert_eq!(
+            vec![
+                "/ceremony/status",
+                "/round/1/transcript",
+                "/round/2/transcript",
+                "/ceremony/status"
+            ],
+            *requested.lock().unwrap()
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! Output of the CLI, whose amount depends on the verbosity selected with the `--quiet` and `--verbose` flags.
+
+use std::{
+    io::Write,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crossterm::{
+    execute,
+    terminal::{Clear, ClearType, ScrollDown},
+};
+use owo_colors::OwoColorize;
+use tracing_subscriber::EnvFilter;
+
+use crate::requests::RequestError;
+
+/// Directives of the tracing filter in verbose mode: debug messages from all the crates, and trace messages from the
+/// ones of the ceremony.
+const VERBOSE_FILTER: &str = "debug,namada_ts=trace,phase2_cli=trace,phase2_coordinator=trace";
+
+/// Number of polls after which the queue status is printed again, even if the position in the queue didn't change.
+pub const QUEUE_STATUS_KEEPALIVE: u64 = 10;
+
+/// Whether the progress of the contribution is hidden, see [`init`].
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Amount of output of the CLI.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Verbosity {
+    /// The progress of the contribution and the logs below the error level are hidden. The prompts and the results of
+    /// the commands are still printed.
+    Quiet,
+    /// The progress of the contribution is printed and the logs are filtered by `RUST_LOG`.
+    Normal,
+    /// The debug and trace logs are printed too.
+    Verbose,
+}
+
+/// Initializes the tracing subscriber with the level filter of the given verbosity, and hides the progress of the
+/// contribution in quiet mode.
+pub fn init(verbosity: Verbosity) {
+    QUIET.store(verbosity == Verbosity::Quiet, Ordering::Relaxed);
+
+    match verbosity {
+        Verbosity::Quiet => tracing_subscriber::fmt()
+            .with_env_filter(EnvFilter::new("error"))
+            .init(),
+        Verbosity::Normal => tracing_subscriber::fmt::init(),
+        Verbosity::Verbose => tracing_subscriber::fmt()
+            .with_env_filter(EnvFilter::new(VERBOSE_FILTER))
+            .init(),
+    }
+}
+
+/// Prints the step `index` of the `total` steps of the contribution, unless in quiet mode.
+pub fn step(index: usize, total: usize, description: &str) {
+    write_step(
+        &mut std::io::stdout(),
+        QUIET.load(Ordering::Relaxed),
+        index,
+        total,
+        description,
+    )
+    .ok();
+}
+
+/// Reports the error aborting the command, with a remedy if known. Errors are reported in quiet mode too.
+pub fn report_error(context: &str, error: &anyhow::Error) {
+    write_error(&mut std::io::stderr(), context, error).ok();
+}
+
+/// The status of the contributor waiting in the queue, printed in place of the previous one only when the position
+/// changes, or every [`QUEUE_STATUS_KEEPALIVE`] polls to show that the contributor is still waiting.
+#[derive(Debug, Default)]
+pub struct QueueStatus {
+    /// Number of polls of the status.
+    polls: u64,
+    /// The last printed position, and the poll it was printed at.
+    last_printed: Option<(u64, u64)>,
+    /// Number of lines of the last printed status, cleared before printing the next one.
+    lines: u16,
+}
+
+impl QueueStatus {
+    /// Records a poll of the status and prints `msg` if the position changed or the keepalive is due. Returns whether
+    /// the status has been printed.
+    pub fn update<W: Write>(&mut self, writer: &mut W, position: u64, msg: &str) -> std::io::Result<bool> {
+        self.polls += 1;
+        if let Some((last_position, last_poll)) = self.last_printed {
+            if last_position == position && self.polls - last_poll < QUEUE_STATUS_KEEPALIVE {
+                return Ok(false);
+            }
+        }
+
+        if self.lines > 0 {
+            // Clear the previous status from the terminal
+            execute!(writer, ScrollDown(self.lines), Clear(ClearType::FromCursorDown))?;
+        }
+        let stripe = "=".repeat(msg.split('\n').map(str::len).max().unwrap_or_default());
+        writeln!(
+            writer,
+            "Queue status - poll #{}\n{}\n{}\n{}",
+            self.polls, stripe, msg, stripe
+        )?;
+        // The estimate can appear or disappear between two polls
+        self.lines = msg.split('\n').count() as u16 + 3;
+        self.last_printed = Some((position, self.polls));
+
+        Ok(true)
+    }
+}
+
+fn write_step<W: Write>(
+    writer: &mut W,
+    quiet: bool,
+    index: usize,
+    total: usize,
+    description: &str,
+) -> std::io::Result<()> {
+    if quiet {
+        return Ok(());
+    }
+
+    writeln!(
+        writer,
+        "{} {}",
+        format!("[{}/{}]", index, total).bold().dimmed(),
+        description
+    )
+}
+
+fn write_error<W: Write>(writer: &mut W, context: &str, error: &anyhow::Error) -> std::io::Result<()> {
+    writeln!(writer, "{}: {}", context.red().bold(), error.to_string().red())?;
+    if let Some(e) = error.downcast_ref::<RequestError>() {
+        writeln!(writer, "{}", e.remedy().bright_yellow())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiet_output() {
+        let mut output = Vec::new();
+        write_step(&mut output, false, 4, 11, "Locking chunk").unwrap();
+        assert!(String::from_utf8(output).unwrap().contains("Locking chunk"));
+
+        // Steps are suppressed in quiet mode, failures are still reported
+        let mut output = Vec::new();
+        write_step(&mut output, true, 4, 11, "Locking chunk").unwrap();
+        assert!(output.is_empty());
+
+        let error = anyhow::Error::new(RequestError::Server(String::from("Internal error")));
+        write_error(&mut output, "The contribution has been aborted", &error).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("The contribution has been aborted"));
+        assert!(output.contains("Internal error"));
+        assert!(output.contains(RequestError::Server(String::new()).remedy()));
+    }
+
+    #[test]
+    fn test_queue_status_printed_on_change() {
+        let mut status = QueueStatus::default();
+        let mut output = Vec::new();
+        let mut poll = |position: u64| {
+            let msg = format!("Queue position: {}\nQueue size: 5", position);
+            status.update(&mut output, position, &msg).unwrap()
+        };
+
+        assert!(poll(3));
+        assert!(!poll(3));
+        assert!(!poll(3));
+        assert!(poll(2));
+
+        // The unchanged position is printed again once the keepalive is due
+        let reprinted: Vec<bool> = (0..QUEUE_STATUS_KEEPALIVE).map(|_| poll(2)).collect();
+        assert_eq!(Some(&true), reprinted.last());
+        assert!(reprinted[..reprinted.len() - 1].iter().all(|printed| !printed));
+
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(3, output.matches("Queue status - poll #").count());
+        assert_eq!(1, output.matches("Queue position: 3").count());
+        assert!(output.contains("poll #4"));
+        assert!(output.contains(&format!("poll #{}", 4 + QUEUE_STATUS_KEEPALIVE)));
+    }
+}
@@ -0,0 +1,121 @@
+//! Progress bars of the contribution, which never leave a dangling line in the terminal, even when the transfer or the
+//! computation they track fails midway.
+
+use std::ops::Deref;
+
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Progress bar finalized when dropped: unless explicitly finished, it's abandoned at its current position, so that an
+/// error interrupting the progress, e.g. a CDN timeout in the middle of the download, doesn't corrupt the terminal.
+pub struct ProgressGuard(ProgressBar);
+
+impl ProgressGuard {
+    pub fn new(progress_bar: ProgressBar) -> Self {
+        Self(progress_bar)
+    }
+}
+
+impl Deref for ProgressGuard {
+    type Target = ProgressBar;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Drop for ProgressGuard {
+    fn drop(&mut self) {
+        if !self.0.is_finished() {
+            self.0.abandon();
+        }
+    }
+}
+
+/// Progress bar of the download of the challenge and of the upload of the contribution, of `len` bytes.
+pub fn transfer_progress_bar(len: u64) -> ProgressGuard {
+    let progress_bar = ProgressBar::new(len);
+    progress_bar.set_style(
+        ProgressStyle::default_bar()
+            .template("[{elapsed_precise}] {bar:40} {bytes_per_sec} {total_bytes}")
+            .progress_chars("#>-"),
+    );
+
+    ProgressGuard::new(progress_bar)
+}
+
+/// Progress bar of the computation of the contribution. Ticks on its own to show the elapsed time while a step is being
+/// computed.
+pub fn computation_progress_bar() -> ProgressGuard {
+    let progress_bar = ProgressBar::new(0);
+    progress_bar.set_style(
+        ProgressStyle::default_bar()
+            .template("{spinner} [{elapsed_precise}] {bar:40} {percent}%")
+            .progress_chars("#>-"),
+    );
+    progress_bar.enable_steady_tick(1000);
+
+    ProgressGuard::new(progress_bar)
+}
+
+/// Collects the bytes of the stream, advancing the progress bar at each chunk. The bar is finished once the stream is
+/// exhausted, or abandoned at the first error, which is returned.
+pub async fn collect_with_progress<S, E>(mut stream: S, progress_bar: ProgressGuard) -> Result<Vec<u8>, E>
+where
+    S: Stream<Item = Result<Bytes, E>> + Unpin,
+{
+    let mut bytes = Vec::with_capacity(progress_bar.length() as usize);
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        bytes.extend_from_slice(&chunk);
+        progress_bar.inc(chunk.len() as u64);
+    }
+    progress_bar.finish();
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Error, ErrorKind};
+
+    #[tokio::test]
+    async fn test_progress_bar_finalized_on_error() {
+        // Hidden bars don't need a tty but keep track of their state
+        let progress_bar = ProgressBar::hidden();
+        progress_bar.set_length(4);
+        let chunks = vec![
+            Ok(Bytes::from_static(b"ab")),
+            Err(Error::new(ErrorKind::TimedOut, "CDN timeout")),
+            Ok(Bytes::from_static(b"cd")),
+        ];
+
+        let result = collect_with_progress(
+            futures_util::stream::iter(chunks),
+            ProgressGuard::new(progress_bar.clone()),
+        )
+        .await;
+
+        assert_eq!(ErrorKind::TimedOut, result.unwrap_err().kind());
+        assert!(progress_bar.is_finished());
+        assert_eq!(2, progress_bar.position());
+
+        // A complete transfer finishes the bar as well
+        let progress_bar = ProgressBar::hidden();
+        progress_bar.set_length(4);
+        let chunks: Vec<Result<_, Error>> = vec![Ok(Bytes::from_static(b"ab")), Ok(Bytes::from_static(b"cd"))];
+
+        let bytes = collect_with_progress(
+            futures_util::stream::iter(chunks),
+            ProgressGuard::new(progress_bar.clone()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(b"abcd".to_vec(), bytes);
+        assert!(progress_bar.is_finished());
+        assert_eq!(4, progress_bar.position());
+    }
+}
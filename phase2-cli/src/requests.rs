@@ -1,39 +1,66 @@
 //! Requests sent to the [Coordinator](`phase2-coordinator::Coordinator`) server.
 
+use async_stream::try_stream;
 use bytes::Bytes;
-use futures_util::Stream;
+use ed25519_compact::{PublicKey, Signature};
+use futures_util::{Stream, StreamExt};
+use once_cell::sync::OnceCell;
 use phase2_coordinator::{
-    authentication::{KeyPair, Production, Signature},
-    objects::ContributionInfo,
+    authentication::Authentication,
+    objects::{ContributionInfo, ContributionReceipt},
     rest_utils::{
-        RequestContent, SignatureHeaders, ACCESS_SECRET_HEADER, BODY_DIGEST_HEADER, CONTENT_LENGTH_HEADER,
-        PUBKEY_HEADER, SIGNATURE_HEADER,
+        format_traced_body, FinalizeChunkUploadRequest, RequestContent, SignatureHeaders, SignedJson,
+        ACCESS_SECRET_HEADER, BODY_DIGEST_HEADER, CONTENT_LENGTH_HEADER, COORDINATOR_SIGNATURE_HEADER,
+        MAX_UPLOAD_PART_SIZE, NONCE_HEADER, PROTOCOL_VERSION, PROTOCOL_VERSION_HEADER, PUBKEY_HEADER, SIGNATURE_HEADER,
     },
-    ContributionFileSignature,
+    storage::ContributionLocator,
+    CeremonyStatus, Cohort, ContributionFileSignature,
 };
 use reqwest::{
     header::{HeaderMap, HeaderValue, CONTENT_TYPE},
-    Client, RequestBuilder, Response, Url,
+    Client, Proxy, RequestBuilder, Response, Url,
 };
-use serde::Serialize;
+use serde::{de::DeserializeOwned, Serialize};
 use sha2::{Digest, Sha256};
-use std::convert::{TryFrom, TryInto};
+use std::{
+    convert::{TryFrom, TryInto},
+    num::NonZeroU64,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 use thiserror::Error;
-use tracing::debug;
+use tracing::{debug, info, warn};
 
-use crate::{ContributorStatus, LockedLocators, PostChunkRequest};
+use crate::{
+    cert_pin::{self, CertPin},
+    ContributorStatus,
+    LockedLocators,
+    PostChunkRequest,
+};
 
 /// Error returned from a request.
 #[derive(Debug, Error)]
 pub enum RequestError {
     #[error("Error while parsing the coordinator url")]
     AddressParseError,
+    #[error("The contributor is no longer part of the ceremony: {0}")]
+    Evicted(String),
+    #[error("The CDN refused to serve the challenge: {0}")]
+    CdnForbidden(String),
+    #[error("The url of the challenge has expired: {0}")]
+    ChallengeUrlExpired(String),
     #[error("Client-side error: {0}")]
     Client(String),
+    #[error("Invalid coordinator signature of the response: {0}")]
+    InvalidCoordinatorSignature(String),
     #[error("Invalid header value: {0}")]
     InvalidHeaderValue(#[from] reqwest::header::InvalidHeaderValue),
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
     #[error("Json serialization of body failed")]
     JsonError(#[from] serde_json::Error),
+    #[error("Incompatible client: {0}")]
+    IncompatibleVersion(String),
     #[error("CDN Error: {0}")]
     Proxy(String),
     #[error("Request error: {0}")]
@@ -44,7 +71,99 @@ pub enum RequestError {
     Server(String),
 }
 
+impl RequestError {
+    /// Returns `true` if the error is likely to go away by resubmitting the request, like a network hiccup or an
+    /// overloaded coordinator.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            RequestError::Proxy(_) | RequestError::Server(_) => true,
+            RequestError::Reqwest(e) => {
+                !cert_pin::is_tls_error(e) && (e.is_timeout() || e.is_connect() || e.is_request())
+            }
+            _ => false,
+        }
+    }
+
+    /// Suggests to the user how to recover from the error.
+    pub fn remedy(&self) -> &'static str {
+        match self {
+            RequestError::AddressParseError => "Check the coordinator url you provided.",
+            RequestError::Evicted(_) => {
+                "The coordinator dropped this contributor, e.g. because it missed too many heartbeats: restart the CLI to join the ceremony again."
+            }
+            RequestError::CdnForbidden(_) => {
+                "The CDN may block the Tor exit nodes: retry without the --socks5 option to download the challenge directly from your connection."
+            }
+            RequestError::ChallengeUrlExpired(_) => {
+                "The link to download the challenge kept expiring: check that the clock of your machine is correct and restart the CLI."
+            }
+            RequestError::Client(_) => {
+                "The coordinator rejected the request: check that your token is valid and that you are using the latest version of the CLI."
+            }
+            RequestError::IncompatibleVersion(_) => "Please upgrade your client to the latest version.",
+            RequestError::InvalidCoordinatorSignature(_) => {
+                "WARNING: the response may not come from the genuine coordinator, aborting. Check the coordinator url and the --coordinator-pubkey published by the ceremony team."
+            }
+            RequestError::Io(_) => "Check the permissions and the free space of the current directory.",
+            RequestError::Reqwest(e) if cert_pin::is_tls_error(e) => {
+                "The TLS certificate of the coordinator was rejected: check the coordinator url and the --cert-pin published by the ceremony team."
+            }
+            RequestError::Proxy(_) | RequestError::Reqwest(_) => {
+                "Check your internet connection, the request will be retried automatically."
+            }
+            RequestError::Server(_) => "The coordinator is having issues, the request will be retried automatically.",
+            RequestError::InvalidHeaderValue(_) | RequestError::JsonError(_) | RequestError::SigningError => {
+                "Please report this issue to the ceremony team."
+            }
+        }
+    }
+}
+
 type Result<T> = std::result::Result<T, RequestError>;
+
+/// Size of the parts in which a contribution is uploaded to the coordinator.
+pub const UPLOAD_PART_SIZE: usize = MAX_UPLOAD_PART_SIZE as usize;
+/// The maximum number of attempts to upload a part of a contribution.
+const MAX_UPLOAD_PART_ATTEMPTS: u32 = 5;
+/// The delay between two attempts to upload a part of a contribution.
+const UPLOAD_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// The maximum number of presigned urls of the challenge requested when the previous one expired.
+pub const MAX_CHALLENGE_URL_ATTEMPTS: u32 = 3;
+
+/// The maximum number of attempts to submit a request failing with a transient error.
+pub const MAX_REQUEST_ATTEMPTS: u32 = 5;
+/// The delay between two attempts to submit a request failing with a transient error.
+pub const REQUEST_RETRY_DELAY: Duration = Duration::from_secs(10);
+
+/// The delay before resending a heartbeat failing with a transient error, doubled at each consecutive failure.
+const HEARTBEAT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// The maximum number of bytes of the bodies logged when tracing the requests.
+const HTTP_TRACE_BODY_LIMIT: usize = 1024;
+/// Whether the requests and responses are logged, see [`enable_http_trace`].
+static TRACE_HTTP: AtomicBool = AtomicBool::new(false);
+/// The public key of the coordinator checking the signed responses, see [`verify_coordinator_signatures`].
+static COORDINATOR_PUBKEY: OnceCell<PublicKey> = OnceCell::new();
+
+/// Builds the client sending the requests, optionally through the SOCKS5 proxy listening at `socks5`, e.g. the Tor
+/// daemon. Host names are resolved by the proxy, so that they don't leak to the local resolver.
+///
+/// If `cert_pin` is given, the connections to the host of the coordinator url are aborted unless its TLS certificate
+/// matches the pin.
+pub fn build_client(socks5: Option<&str>, cert_pin: Option<(&Url, &CertPin)>) -> Result<Client> {
+    let mut builder = Client::builder().brotli(true);
+    if let Some(address) = socks5 {
+        builder = builder.proxy(Proxy::all(format!("socks5h://{}", address))?);
+    }
+    if let Some((coordinator, pin)) = cert_pin {
+        let host = coordinator.host_str().ok_or(RequestError::AddressParseError)?;
+        builder = builder.use_preconfigured_tls(cert_pin::pinned_tls_config(host, pin)?);
+    }
+
+    Ok(builder.build()?)
+}
+
 /// Wrapper type to convert [`SignatureHeaders`] into [`HeaderMap`]
 struct HeaderWrap(HeaderMap);
 
@@ -60,6 +179,7 @@ impl TryFrom<SignatureHeaders<'_>> for HeaderWrap {
     fn try_from(value: SignatureHeaders) -> std::result::Result<Self, Self::Error> {
         let mut result = HeaderMap::new();
         result.insert(PUBKEY_HEADER, HeaderValue::from_str(value.pubkey)?);
+        result.insert(NONCE_HEADER, value.nonce.into());
 
         if let Some(sig) = value.signature {
             result.insert(SIGNATURE_HEADER, HeaderValue::from_str(&sig)?);
@@ -76,26 +196,51 @@ impl TryFrom<SignatureHeaders<'_>> for HeaderWrap {
 }
 
 trait Sign {
-    fn try_sign(&mut self, sigkey: &str) -> Result<()>;
+    fn try_sign(&mut self, auth: &dyn Authentication) -> Result<()>;
 }
 
 impl Sign for SignatureHeaders<'_> {
-    fn try_sign(&mut self, sigkey: &str) -> Result<()> {
+    fn try_sign(&mut self, auth: &dyn Authentication) -> Result<()> {
         let msg = self.to_string();
-        self.signature = Some(
-            Production
-                .sign(sigkey, &msg)
-                .map_err(|_| RequestError::SigningError)?
-                .into(),
-        );
+        self.signature = Some(auth.sign(&msg).map_err(|_| RequestError::SigningError)?.into());
 
         Ok(())
     }
 }
 
+/// Returns a strictly increasing nonce for the signature of the requests. The nonce is based on the current time (in
+/// milliseconds) to stay monotonic across restarts of the client.
+fn next_nonce() -> u64 {
+    static LAST_NONCE: AtomicU64 = AtomicU64::new(0);
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default();
+    let mut last = LAST_NONCE.load(Ordering::SeqCst);
+
+    loop {
+        let next = std::cmp::max(last + 1, now);
+        match LAST_NONCE.compare_exchange(last, next, Ordering::SeqCst, Ordering::SeqCst) {
+            Ok(_) => return next,
+            Err(actual) => last = actual,
+        }
+    }
+}
+
 enum Request<'a, T: Serialize> {
     Get,
     Post(Option<&'a T>),
+    /// Post of raw bytes, sent as an octet stream.
+    PostBytes(&'a [u8]),
+}
+
+/// Returns the size and digest of a request body, to be signed along with the request.
+fn request_content(body: &[u8]) -> RequestContent<'static> {
+    let mut hasher = Sha256::new();
+    hasher.update(body);
+
+    RequestContent::new(body.len(), hasher.finalize())
 }
 
 /// Submit a signed json encoded request to the provided enpoint
@@ -103,10 +248,29 @@ async fn submit_request<T: Serialize>(
     client: &Client,
     coordinator_address: &Url,
     endpoint: &str,
-    keypair: Option<&KeyPair>,
+    auth: Option<&dyn Authentication>,
     custom_headers: Option<HeaderMap>,
     request: Request<'_, T>,
 ) -> Result<Response>
+where
+    T: Serialize,
+{
+    let (response, _) =
+        submit_request_with_nonce(client, coordinator_address, endpoint, auth, custom_headers, request).await?;
+
+    Ok(response)
+}
+
+/// Same as [`submit_request`], also returning the nonce of the submission which got the response, if signed, to check
+/// the responses signed by the coordinator.
+async fn submit_request_with_nonce<T: Serialize>(
+    client: &Client,
+    coordinator_address: &Url,
+    endpoint: &str,
+    auth: Option<&dyn Authentication>,
+    custom_headers: Option<HeaderMap>,
+    request: Request<'_, T>,
+) -> Result<(Response, Option<u64>)>
 where
     T: Serialize,
 {
@@ -120,12 +284,7 @@ where
         Request::Post(body) => match body {
             Some(b) => {
                 let json_body = serde_json::to_vec(b)?;
-
-                let mut hasher = Sha256::new();
-                hasher.update(&json_body);
-                let digest = hasher.finalize();
-
-                content = Some(RequestContent::new(json_body.len(), digest));
+                content = Some(request_content(&json_body));
                 client
                     .post(address)
                     .body(json_body)
@@ -133,26 +292,50 @@ where
             }
             None => client.post(address),
         },
+        Request::PostBytes(bytes) => {
+            content = Some(request_content(bytes));
+            client
+                .post(address)
+                .body(bytes.to_vec())
+                .header(CONTENT_TYPE, HeaderValue::from_static("application/octet-stream"))
+        }
     };
 
-    // Generate signatures headers if required
-    if let Some(kp) = keypair {
-        let mut headers = SignatureHeaders::new(kp.pubkey(), content, None);
-        headers.try_sign(kp.sigkey())?;
-        let header_map: HeaderWrap = headers.try_into()?;
-        req = req.headers(header_map.into());
-    }
-
     // Add custom headers if required
     if let Some(header_map) = custom_headers {
         req = req.headers(header_map);
     }
 
+    if TRACE_HTTP.load(Ordering::Relaxed) {
+        if let Some(Ok(traced)) = req.try_clone().map(|r| r.build()) {
+            let body = traced.body().and_then(|b| b.as_bytes()).unwrap_or_default();
+            info!(
+                "--> {} {} {}",
+                traced.method(),
+                traced.url(),
+                format_traced_body(body, None, HTTP_TRACE_BODY_LIMIT)
+            );
+        }
+    }
+
     loop {
-        let response = req.try_clone().expect("Expected request not stream").send().await?;
+        let mut signed_req = req.try_clone().expect("Expected request not stream");
+
+        // Generate signatures headers if required. Every submission gets a fresh nonce, otherwise the coordinator would
+        // reject it as a replay
+        let mut nonce = None;
+        if let Some(auth) = auth {
+            let mut headers = SignatureHeaders::new(auth.pubkey(), next_nonce(), content.clone(), None);
+            headers.try_sign(auth)?;
+            nonce = Some(headers.nonce);
+            let header_map: HeaderWrap = headers.try_into()?;
+            signed_req = signed_req.headers(header_map.into());
+        }
+
+        let response = signed_req.send().await?;
 
         match decapsulate_response(response).await {
-            Ok(response) => return Ok(response),
+            Ok(response) => return Ok((response, nonce)),
             Err(e) => match e {
                 RequestError::Proxy(_) => debug!("CDN timeout expired, resubmitting the request..."),
                 _ => return Err(e),
@@ -166,14 +349,121 @@ async fn decapsulate_response(response: Response) -> Result<Response> {
     let status = response.status();
 
     if status.is_success() {
-        Ok(response)
+        // The body of a successful response is left to the caller
+        if TRACE_HTTP.load(Ordering::Relaxed) {
+            info!("<-- {} {}", status, response.url());
+        }
+        return Ok(response);
+    }
+
+    let url = response.url().clone();
+    let body = response.text().await?;
+    if TRACE_HTTP.load(Ordering::Relaxed) {
+        info!(
+            "<-- {} {} {}",
+            status,
+            url,
+            format_traced_body(body.as_bytes(), None, HTTP_TRACE_BODY_LIMIT)
+        );
+    }
+
+    if status.as_u16() == reqwest::StatusCode::UPGRADE_REQUIRED.as_u16() {
+        Err(RequestError::IncompatibleVersion(body))
+    } else if status.as_u16() == reqwest::StatusCode::GONE.as_u16() {
+        Err(RequestError::Evicted(body))
     } else if status.is_client_error() {
-        Err(RequestError::Client(response.text().await?))
+        Err(RequestError::Client(body))
     } else {
         if status.as_u16() == reqwest::StatusCode::GATEWAY_TIMEOUT.as_u16() {
-            Err(RequestError::Proxy(response.text().await?))
+            Err(RequestError::Proxy(body))
         } else {
-            Err(RequestError::Server(response.text().await?))
+            Err(RequestError::Server(body))
+        }
+    }
+}
+
+/// Enables the logging of the requests sent to the coordinator and of its responses.
+pub fn enable_http_trace() {
+    TRACE_HTTP.store(true, Ordering::Relaxed);
+}
+
+/// Parses the hex encoded public key of the coordinator.
+pub fn parse_coordinator_pubkey(pubkey: &str) -> std::result::Result<PublicKey, String> {
+    let bytes = hex::decode(pubkey).map_err(|e| format!("The public key is not hex encoded: {}", e))?;
+
+    PublicKey::from_slice(&bytes).map_err(|_| String::from("The public key is not a valid ed25519 public key"))
+}
+
+/// Enables the verification of the responses signed by the coordinator (the url of the challenge and the queue
+/// status) against its public key, obtained out-of-band. Unsigned responses or invalid signatures are then rejected.
+pub fn verify_coordinator_signatures(pubkey: PublicKey) {
+    // The public key is given once on the command line
+    let _ = COORDINATOR_PUBKEY.set(pubkey);
+}
+
+/// Checks the hex encoded signature of a response against the public key of the coordinator. The signature must cover
+/// the body together with the nonce and the public key of the request, see [`SignedJson::message`], so that a response
+/// to another request or to another participant is rejected.
+fn check_coordinator_signature(
+    pubkey: &PublicKey,
+    nonce: u64,
+    participant: &str,
+    body: &[u8],
+    signature: Option<&str>,
+) -> Result<()> {
+    let body = std::str::from_utf8(body)
+        .map_err(|_| RequestError::InvalidCoordinatorSignature(String::from("the response is not valid UTF-8")))?;
+    let signature = signature
+        .ok_or_else(|| RequestError::InvalidCoordinatorSignature(String::from("the response is not signed")))?;
+    let signature = hex::decode(signature)
+        .ok()
+        .and_then(|signature| Signature::from_slice(&signature).ok())
+        .ok_or_else(|| RequestError::InvalidCoordinatorSignature(String::from("the signature is malformed")))?;
+
+    pubkey
+        .verify(SignedJson::message(nonce, participant, body), &signature)
+        .map_err(|_| {
+            RequestError::InvalidCoordinatorSignature(String::from("the signature doesn't match the public key"))
+        })
+}
+
+/// Parses the json body of a response signed by the coordinator, checking its signature if enabled with
+/// [`verify_coordinator_signatures`]. `nonce` is the one of the request sent by `participant`, see
+/// [`submit_request_with_nonce`].
+async fn signed_json<T: DeserializeOwned>(response: Response, nonce: Option<u64>, participant: &str) -> Result<T> {
+    let signature = response
+        .headers()
+        .get(COORDINATOR_SIGNATURE_HEADER)
+        .and_then(|signature| signature.to_str().ok())
+        .map(str::to_owned);
+    let body = response.bytes().await?;
+    if let Some(pubkey) = COORDINATOR_PUBKEY.get() {
+        let nonce = nonce
+            .ok_or_else(|| RequestError::InvalidCoordinatorSignature(String::from("the request is not signed")))?;
+        check_coordinator_signature(pubkey, nonce, participant, &body, signature.as_deref())?;
+    }
+
+    Ok(serde_json::from_slice(&body)?)
+}
+
+/// Submits a request through the given closure, retrying it up to `max_attempts` times, every `delay`, as long as
+/// it fails with a transient error. Fatal errors are returned right away.
+pub async fn retry_transient<T, F, Fut>(max_attempts: u32, delay: Duration, mut request: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut attempt = 1;
+
+    loop {
+        match request().await {
+            Ok(response) => return Ok(response),
+            Err(e) if e.is_transient() && attempt < max_attempts => {
+                warn!("Attempt {}/{} failed: {}. {}", attempt, max_attempts, e, e.remedy());
+                attempt += 1;
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err(e),
         }
     }
 }
@@ -182,7 +472,7 @@ async fn decapsulate_response(response: Response) -> Result<Response> {
 pub async fn post_join_queue(
     client: &Client,
     coordinator_address: &Url,
-    keypair: &KeyPair,
+    keypair: &dyn Authentication,
     token: &String,
 ) -> Result<u64> {
     let response = submit_request::<String>(
@@ -190,7 +480,7 @@ pub async fn post_join_queue(
         coordinator_address,
         "contributor/join_queue",
         Some(keypair),
-        None,
+        Some(protocol_version_header()),
         Request::Post(Some(token)),
     )
     .await?;
@@ -199,7 +489,11 @@ pub async fn post_join_queue(
 }
 
 /// Send a request to the [Coordinator](`phase2-coordinator::Coordinator`) to lock the next [Chunk](`phase2-coordinator::objects::Chunk`).
-pub async fn get_lock_chunk(client: &Client, coordinator_address: &Url, keypair: &KeyPair) -> Result<LockedLocators> {
+pub async fn get_lock_chunk(
+    client: &Client,
+    coordinator_address: &Url,
+    keypair: &dyn Authentication,
+) -> Result<LockedLocators> {
     let response = submit_request::<String>(
         client,
         coordinator_address,
@@ -217,10 +511,10 @@ pub async fn get_lock_chunk(client: &Client, coordinator_address: &Url, keypair:
 pub async fn get_challenge_url(
     client: &Client,
     coordinator_address: &Url,
-    keypair: &KeyPair,
+    keypair: &dyn Authentication,
     round_height: &u64,
 ) -> Result<String> {
-    let response = submit_request(
+    let (response, nonce) = submit_request_with_nonce(
         client,
         coordinator_address,
         "contributor/challenge",
@@ -230,7 +524,7 @@ pub async fn get_challenge_url(
     )
     .await?;
 
-    Ok(response.json().await?)
+    signed_json(response, nonce, keypair.pubkey()).await
 }
 
 /// Send a request to Amazon S3 to download the next challenge.
@@ -240,16 +534,56 @@ pub async fn get_challenge(
 ) -> Result<(impl Stream<Item = reqwest::Result<Bytes>>, u64)> {
     let req = client.get(challenge_url);
     let response = req.send().await?;
+    let status = response.status().as_u16();
+    // An expired presigned url is either refused or answered with the html error page of the CDN
+    let is_html = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map_or(false, |content_type| content_type.starts_with("text/html"));
+    if status == reqwest::StatusCode::NOT_FOUND.as_u16() || (response.status().is_success() && is_html) {
+        return Err(RequestError::ChallengeUrlExpired(response.text().await?));
+    }
+    if status == reqwest::StatusCode::FORBIDDEN.as_u16() {
+        let body = response.text().await?;
+        if body.contains("Request has expired") {
+            return Err(RequestError::ChallengeUrlExpired(body));
+        }
+        return Err(RequestError::CdnForbidden(body));
+    }
     let stream_len = response.content_length().unwrap();
 
     Ok((decapsulate_response(response).await?.bytes_stream(), stream_len))
 }
 
+/// Requests the url of the challenge of the given round and starts its download. The presigned url expires after a
+/// while, e.g. if the contributor waited long in the queue, in which case a fresh one is requested to the
+/// [Coordinator](`phase2-coordinator::Coordinator`).
+pub async fn download_challenge(
+    client: &Client,
+    coordinator_address: &Url,
+    keypair: &dyn Authentication,
+    round_height: &u64,
+) -> Result<(impl Stream<Item = reqwest::Result<Bytes>>, u64)> {
+    let mut attempt = 1;
+
+    loop {
+        let challenge_url = get_challenge_url(client, coordinator_address, keypair, round_height).await?;
+        match get_challenge(client, challenge_url.as_str()).await {
+            Err(RequestError::ChallengeUrlExpired(_)) if attempt < MAX_CHALLENGE_URL_ATTEMPTS => {
+                warn!("The url of the challenge has expired, requesting a fresh one");
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Send a request to the [Coordinator](`phase2-coordinator::Coordinator`) to get the target Strings where to upload the contribution and its signature.
 pub async fn get_contribution_url(
     client: &Client,
     coordinator_address: &Url,
-    keypair: &KeyPair,
+    keypair: &dyn Authentication,
     round_height: &u64,
 ) -> Result<(String, String)> {
     let response = submit_request::<u64>(
@@ -273,42 +607,149 @@ async fn upload_object(req: RequestBuilder) -> Result<()> {
     Ok(())
 }
 
-/// Upload a contribution and its signature to Amazon S3.
-pub async fn upload_chunk<S>(
+/// Upload a contribution to the [Coordinator](`phase2-coordinator::Coordinator`) in parts of [`UPLOAD_PART_SIZE`]
+/// bytes and finalize the upload. The upload starts from the `committed` offset, e.g. the one recorded before a
+/// restart of the CLI, and the offset committed by the coordinator is tracked so that a part which failed to upload
+/// is retried from there, resuming the upload instead of starting it over. `on_commit` is called with the committed
+/// offset after every part, so that it can be persisted. The size and hash of the whole contribution are sent along
+/// with the finalization, so that the coordinator rejects a truncated upload right away.
+pub async fn upload_chunk<F>(
     client: &Client,
-    contrib_url: &str,
-    contrib_sig_url: &str,
-    contribution_stream: S,
-    contribution_len: u64,
-    contribution_signature: &ContributionFileSignature,
+    coordinator_address: &Url,
+    keypair: &dyn Authentication,
+    contribution_locator: &ContributionLocator,
+    contribution: &[u8],
+    mut committed: u64,
+    max_bytes_per_sec: Option<NonZeroU64>,
+    mut on_commit: F,
 ) -> Result<()>
 where
-    S: Stream<Item = std::result::Result<Bytes, std::io::Error>> + std::marker::Send + std::marker::Sync + 'static,
+    F: FnMut(u64),
 {
+    let remaining = contribution.get(committed as usize..).unwrap_or_default();
+    let parts = throttle_stream(
+        futures_util::stream::iter(
+            remaining
+                .chunks(UPLOAD_PART_SIZE)
+                .map(|part| Ok::<_, std::io::Error>(Bytes::copy_from_slice(part))),
+        ),
+        max_bytes_per_sec,
+    );
+    futures_util::pin_mut!(parts);
+
+    while let Some(part) = parts.next().await {
+        let part = part?;
+        let mut attempt = 1;
+
+        committed = loop {
+            match post_chunk_part(client, coordinator_address, keypair, committed, &part).await {
+                Ok(committed) => break committed,
+                Err(e) if attempt < MAX_UPLOAD_PART_ATTEMPTS => {
+                    debug!(
+                        "Attempt {}/{} to upload the contribution from offset {} failed: {}",
+                        attempt, MAX_UPLOAD_PART_ATTEMPTS, committed, e
+                    );
+                    attempt += 1;
+                    tokio::time::sleep(UPLOAD_RETRY_DELAY).await;
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        on_commit(committed);
+    }
+
+    let request = FinalizeChunkUploadRequest::new(*contribution_locator, contribution);
+    submit_request(
+        client,
+        coordinator_address,
+        "upload/chunk/finalize",
+        Some(keypair),
+        None,
+        Request::Post(Some(&request)),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Upload a part of a contribution, starting at the given offset, to the
+/// [Coordinator](`phase2-coordinator::Coordinator`). Returns the number of bytes of the contribution committed so far.
+async fn post_chunk_part(
+    client: &Client,
+    coordinator_address: &Url,
+    keypair: &dyn Authentication,
+    offset: u64,
+    part: &[u8],
+) -> Result<u64> {
+    let response = submit_request::<()>(
+        client,
+        coordinator_address,
+        &format!("upload/chunk/part/{}", offset),
+        Some(keypair),
+        None,
+        Request::PostBytes(part),
+    )
+    .await?;
+
+    Ok(response.json().await?)
+}
+
+/// Upload the signature of a contribution to Amazon S3.
+pub async fn upload_contribution_signature(
+    client: &Client,
+    contrib_sig_url: &str,
+    contribution_signature: &ContributionFileSignature,
+) -> Result<()> {
     let json_sig = serde_json::to_vec(&contribution_signature)?;
-    let contrib_req = client
-        .put(contrib_url)
-        .body(reqwest::Body::wrap_stream(contribution_stream))
-        .header(CONTENT_TYPE, "application/octet-stream")
-        .header(CONTENT_LENGTH_HEADER, contribution_len);
     let contrib_sig_req = client
         .put(contrib_sig_url)
         .body(json_sig)
         .header(CONTENT_TYPE, HeaderValue::from_static("application/json"));
 
-    tokio::try_join!(upload_object(contrib_req), upload_object(contrib_sig_req))?;
+    upload_object(contrib_sig_req).await
+}
 
-    Ok(())
+/// Paces the byte chunks of a contribution stream so that no more than `max_bytes_per_sec` bytes are yielded per
+/// second. If no limit is provided the stream is left untouched.
+pub fn throttle_stream<S>(
+    stream: S,
+    max_bytes_per_sec: Option<NonZeroU64>,
+) -> impl Stream<Item = std::result::Result<Bytes, std::io::Error>>
+where
+    S: Stream<Item = std::result::Result<Bytes, std::io::Error>>,
+{
+    try_stream! {
+        futures_util::pin_mut!(stream);
+        let start = Instant::now();
+        let mut yielded: u64 = 0;
+
+        while let Some(b) = stream.next().await {
+            let b = b?;
+
+            if let Some(rate) = max_bytes_per_sec {
+                // Wait until the bytes of this chunk fit in the allowed rate
+                yielded += b.len() as u64;
+                let target = Duration::from_secs_f64(yielded as f64 / rate.get() as f64);
+                let elapsed = start.elapsed();
+                if target > elapsed {
+                    tokio::time::sleep(target - elapsed).await;
+                }
+            }
+
+            yield b;
+        }
+    }
 }
 
-/// Send a request to notify the [Coordinator](`phase2-coordinator::Coordinator`) of an uploaded contribution.
+/// Send a request to notify the [Coordinator](`phase2-coordinator::Coordinator`) of an uploaded contribution. Returns
+/// the receipt of the contribution, signed by the coordinator.
 pub async fn post_contribute_chunk(
     client: &Client,
     coordinator_address: &Url,
-    keypair: &KeyPair,
+    keypair: &dyn Authentication,
     request_body: &PostChunkRequest,
-) -> Result<()> {
-    submit_request(
+) -> Result<ContributionReceipt> {
+    let response = submit_request(
         client,
         coordinator_address,
         "contributor/contribute_chunk",
@@ -318,11 +759,11 @@ pub async fn post_contribute_chunk(
     )
     .await?;
 
-    Ok(())
+    Ok(response.json::<ContributionReceipt>().await?)
 }
 
 /// Let the [Coordinator](`phase2-coordinator::Coordinator`) know that the contributor is still alive.
-pub async fn post_heartbeat(client: &Client, coordinator_address: &Url, keypair: &KeyPair) -> Result<()> {
+pub async fn post_heartbeat(client: &Client, coordinator_address: &Url, keypair: &dyn Authentication) -> Result<()> {
     submit_request::<String>(
         client,
         coordinator_address,
@@ -336,9 +777,40 @@ pub async fn post_heartbeat(client: &Client, coordinator_address: &Url, keypair:
     Ok(())
 }
 
+/// Sends a heartbeat every `interval` for as long as the [Coordinator](`phase2-coordinator::Coordinator`) keeps track of
+/// the contributor. Heartbeats failing with a transient error are resent with an exponential backoff, starting from
+/// [`HEARTBEAT_RETRY_DELAY`] and capped at `interval` not to exceed the timeout of the coordinator. Returns the fatal
+/// error, e.g. [`RequestError::Evicted`], after which there is no point in heartbeating anymore.
+pub async fn heartbeat_loop(
+    client: &Client,
+    coordinator_address: &Url,
+    keypair: &dyn Authentication,
+    interval: Duration,
+) -> RequestError {
+    let mut failures = 0;
+
+    loop {
+        let delay = match post_heartbeat(client, coordinator_address, keypair).await {
+            Ok(()) => {
+                failures = 0;
+                interval
+            }
+            Err(e) if e.is_transient() => {
+                let delay = std::cmp::min(HEARTBEAT_RETRY_DELAY * 2u32.saturating_pow(failures), interval);
+                warn!("Heartbeat failed: {}. Retrying in {} s", e, delay.as_secs());
+                failures += 1;
+                delay
+            }
+            Err(e) => return e,
+        };
+
+        tokio::time::sleep(delay).await;
+    }
+}
+
 /// Request an update of the [Coordinator](`phase2-coordinator::Coordinator`) state.
 #[cfg(debug_assertions)]
-pub async fn get_update(client: &Client, coordinator_address: &Url, keypair: &KeyPair) -> Result<()> {
+pub async fn get_update(client: &Client, coordinator_address: &Url, keypair: &dyn Authentication) -> Result<()> {
     submit_request::<()>(
         client,
         coordinator_address,
@@ -353,7 +825,11 @@ pub async fn get_update(client: &Client, coordinator_address: &Url, keypair: &Ke
 }
 
 /// Stop the [Coordinator](`phase2-coordinator::Coordinator`).
-pub async fn get_stop_coordinator(client: &Client, coordinator_address: &Url, keypair: &KeyPair) -> Result<()> {
+pub async fn get_stop_coordinator(
+    client: &Client,
+    coordinator_address: &Url,
+    keypair: &dyn Authentication,
+) -> Result<()> {
     submit_request::<()>(client, coordinator_address, "/stop", Some(keypair), None, Request::Get).await?;
 
     Ok(())
@@ -361,7 +837,7 @@ pub async fn get_stop_coordinator(client: &Client, coordinator_address: &Url, ke
 
 /// Verify the pending contributions.
 #[cfg(debug_assertions)]
-pub async fn get_verify_chunks(client: &Client, coordinator_address: &Url, keypair: &KeyPair) -> Result<()> {
+pub async fn get_verify_chunks(client: &Client, coordinator_address: &Url, keypair: &dyn Authentication) -> Result<()> {
     submit_request::<()>(
         client,
         coordinator_address,
@@ -379,9 +855,9 @@ pub async fn get_verify_chunks(client: &Client, coordinator_address: &Url, keypa
 pub async fn get_contributor_queue_status(
     client: &Client,
     coordinator_address: &Url,
-    keypair: &KeyPair,
+    keypair: &dyn Authentication,
 ) -> Result<ContributorStatus> {
-    let response = submit_request::<()>(
+    let (response, nonce) = submit_request_with_nonce::<()>(
         client,
         coordinator_address,
         "contributor/queue_status",
@@ -391,14 +867,14 @@ pub async fn get_contributor_queue_status(
     )
     .await?;
 
-    Ok(response.json::<ContributorStatus>().await?)
+    signed_json(response, nonce, keypair.pubkey()).await
 }
 
 /// Send [`ContributionInfo`] to the Coordinator.
 pub async fn post_contribution_info(
     client: &Client,
     coordinator_address: &Url,
-    keypair: &KeyPair,
+    keypair: &dyn Authentication,
     request_body: &ContributionInfo,
 ) -> Result<()> {
     submit_request::<ContributionInfo>(
@@ -418,7 +894,7 @@ pub async fn post_contribution_info(
 pub async fn post_attestation(
     client: &Client,
     coordinator_address: &Url,
-    keypair: &KeyPair,
+    keypair: &dyn Authentication,
     request_body: &(u64, String),
 ) -> Result<()> {
     submit_request::<(u64, String)>(
@@ -434,39 +910,104 @@ pub async fn post_attestation(
     Ok(())
 }
 
-/// Query health endpoint of the Coordinator to check the connection
-pub async fn ping_coordinator(client: &Client, coordinator_address: &Url) -> Result<()> {
-    submit_request::<()>(client, coordinator_address, "/healthcheck", None, None, Request::Get).await?;
+/// Returns the header advertising the [`PROTOCOL_VERSION`] of this client.
+fn protocol_version_header() -> HeaderMap {
+    let mut header = HeaderMap::new();
+    header.insert(PROTOCOL_VERSION_HEADER, HeaderValue::from_static(PROTOCOL_VERSION));
 
-    Ok(())
+    header
 }
 
-/// Retrieve the list of contributions, json encoded
-#[cfg(debug_assertions)]
-pub async fn get_contributions_info(coordinator_address: &Url) -> Result<Vec<u8>> {
-    let client = Client::builder().brotli(true).build()?;
+/// Checks that the protocol version of this client is compatible with the Coordinator one. Returns the version of
+/// the Coordinator, or [`RequestError::IncompatibleVersion`] if the client must be upgraded.
+pub async fn get_protocol_version(client: &Client, coordinator_address: &Url) -> Result<String> {
+    let response = submit_request::<()>(
+        client,
+        coordinator_address,
+        "/version",
+        None,
+        Some(protocol_version_header()),
+        Request::Get,
+    )
+    .await?;
+
+    Ok(response.json::<String>().await?)
+}
+
+/// Retrieve the schedule of the cohorts of the ceremony
+pub async fn get_cohort_schedule(client: &Client, coordinator_address: &Url) -> Result<Vec<Cohort>> {
+    let response = submit_request::<()>(client, coordinator_address, "/cohorts", None, None, Request::Get).await?;
+
+    Ok(response.json::<Vec<Cohort>>().await?)
+}
 
+/// Retrieve the public status of the ceremony
+pub async fn get_ceremony_status(client: &Client, coordinator_address: &Url) -> Result<CeremonyStatus> {
     let response = submit_request::<()>(
-        &client,
+        client,
         coordinator_address,
-        "/contribution_info",
+        "/ceremony/status",
         None,
         None,
         Request::Get,
     )
     .await?;
 
+    Ok(response.json::<CeremonyStatus>().await?)
+}
+
+/// Retrieve the json encoded transcript of the given round, once aggregated. The bytes are returned as served, so that
+/// they can be stored and hashed as is
+pub async fn get_round_transcript(client: &Client, coordinator_address: &Url, round_height: u64) -> Result<Vec<u8>> {
+    let endpoint = format!("/round/{}/transcript", round_height);
+    let response = submit_request::<()>(client, coordinator_address, &endpoint, None, None, Request::Get).await?;
+
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Query health endpoint of the Coordinator to check the connection
+pub async fn ping_coordinator(client: &Client, coordinator_address: &Url) -> Result<()> {
+    submit_request::<()>(client, coordinator_address, "/healthcheck", None, None, Request::Get).await?;
+
+    Ok(())
+}
+
+/// Retrieve the list of contributions to the rounds in the given range, json encoded
+#[cfg(debug_assertions)]
+pub async fn get_contributions_info(
+    client: &Client,
+    coordinator_address: &Url,
+    from_round: Option<u64>,
+    to_round: Option<u64>,
+    offset: Option<usize>,
+    page_size: Option<usize>,
+) -> Result<Vec<u8>> {
+    let query: Vec<String> = vec![
+        ("from_round", from_round.map(|r| r.to_string())),
+        ("to_round", to_round.map(|r| r.to_string())),
+        ("offset", offset.map(|o| o.to_string())),
+        ("page_size", page_size.map(|s| s.to_string())),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| value.map(|v| format!("{}={}", key, v)))
+    .collect();
+    let endpoint = match query.is_empty() {
+        true => String::from("/contribution_info"),
+        false => format!("/contribution_info?{}", query.join("&")),
+    };
+
+    let response = submit_request::<()>(client, coordinator_address, &endpoint, None, None, Request::Get).await?;
+
     Ok(response.bytes().await?.to_vec())
 }
 
 /// Retrieve the state of the coordinator, json encoded. Needs to provide a secret access token to the endpoint
-pub async fn get_coordinator_state(coordinator_address: &Url, access_secret: &str) -> Result<Vec<u8>> {
-    let client = Client::builder().brotli(true).build()?;
+pub async fn get_coordinator_state(client: &Client, coordinator_address: &Url, access_secret: &str) -> Result<Vec<u8>> {
     let mut header = HeaderMap::new();
     header.insert(ACCESS_SECRET_HEADER, HeaderValue::from_str(access_secret)?);
 
     let response = submit_request::<()>(
-        &client,
+        client,
         coordinator_address,
         "/coordinator_status",
         None,
@@ -482,7 +1023,7 @@ pub async fn get_coordinator_state(coordinator_address: &Url, access_secret: &st
 pub async fn post_update_cohorts(
     client: &Client,
     coordinator_address: &Url,
-    keypair: &KeyPair,
+    keypair: &dyn Authentication,
     tokens: &Vec<u8>,
 ) -> Result<()> {
     submit_request::<Vec<u8>>(
@@ -497,3 +1038,337 @@ pub async fn post_update_cohorts(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phase2_coordinator::authentication::{KeyPair, Production, Signature as _};
+
+    #[tokio::test]
+    async fn test_throttled_upload() {
+        let chunks = (0..4).map(|_| Ok(Bytes::from(vec![0u8; 1024])));
+        let start = Instant::now();
+
+        let body: Vec<_> = throttle_stream(futures_util::stream::iter(chunks), NonZeroU64::new(2048))
+            .collect()
+            .await;
+
+        // 4096 bytes at 2048 bytes/s can't take less than 2 seconds
+        assert!(start.elapsed() >= Duration::from_secs(2));
+        assert_eq!(4096, body.into_iter().map(|b| b.unwrap().len()).sum::<usize>());
+    }
+
+    #[tokio::test]
+    async fn test_retry_transient() {
+        // A request failing once because of the CDN, then succeeding
+        let mut attempts = 0;
+        let result = retry_transient(MAX_REQUEST_ATTEMPTS, Duration::from_millis(10), || {
+            attempts += 1;
+            let outcome = if attempts == 1 {
+                Err(RequestError::Proxy("Gateway timeout".to_string()))
+            } else {
+                Ok(attempts)
+            };
+            async move { outcome }
+        })
+        .await;
+        assert_eq!(2, result.unwrap());
+
+        // A request always failing with a transient error gives up after the maximum number of attempts
+        let mut attempts = 0;
+        let result: Result<()> = retry_transient(3, Duration::from_millis(10), || {
+            attempts += 1;
+            async { Err(RequestError::Server("Internal server error".to_string())) }
+        })
+        .await;
+        assert!(matches!(result, Err(RequestError::Server(_))));
+        assert_eq!(3, attempts);
+
+        // Fatal errors are not retried
+        let mut attempts = 0;
+        let result: Result<()> = retry_transient(MAX_REQUEST_ATTEMPTS, Duration::from_millis(10), || {
+            attempts += 1;
+            async { Err(RequestError::Client("Participant is banned".to_string())) }
+        })
+        .await;
+        assert!(matches!(result, Err(RequestError::Client(_))));
+        assert_eq!(1, attempts);
+    }
+
+    #[test]
+    fn test_coordinator_signature() {
+        let coordinator_keypair = KeyPair::new();
+        let pubkey = parse_coordinator_pubkey(coordinator_keypair.pubkey()).unwrap();
+        let participant_keypair = KeyPair::new();
+        let participant = participant_keypair.pubkey();
+        let nonce = next_nonce();
+        let body = serde_json::to_string(&ContributorStatus::Round).unwrap();
+        let message = SignedJson::message(nonce, participant, &body);
+        let signature = Production.sign(coordinator_keypair.sigkey(), &message).unwrap();
+
+        // The response signed by the coordinator is accepted
+        assert!(check_coordinator_signature(&pubkey, nonce, participant, body.as_bytes(), Some(&signature)).is_ok());
+
+        // Tampered, unsigned, otherwise signed or replayed responses are rejected
+        let tampered = serde_json::to_string(&ContributorStatus::Finished).unwrap();
+        let other_signature = Production.sign(KeyPair::new().sigkey(), &message).unwrap();
+        let body_signature = Production.sign(coordinator_keypair.sigkey(), &body).unwrap();
+        let other_keypair = KeyPair::new();
+        for (nonce, participant, body, signature) in [
+            (nonce, participant, tampered.as_str(), Some(signature.as_str())),
+            (nonce, participant, body.as_str(), None),
+            (nonce, participant, body.as_str(), Some("not_hex")),
+            (nonce, participant, body.as_str(), Some(other_signature.as_str())),
+            (nonce, participant, body.as_str(), Some(body_signature.as_str())),
+            (nonce + 1, participant, body.as_str(), Some(signature.as_str())),
+            (nonce, other_keypair.pubkey(), body.as_str(), Some(signature.as_str())),
+        ] {
+            let error =
+                check_coordinator_signature(&pubkey, nonce, participant, body.as_bytes(), signature).unwrap_err();
+            assert!(matches!(error, RequestError::InvalidCoordinatorSignature(_)));
+            assert!(!error.is_transient());
+        }
+
+        assert!(parse_coordinator_pubkey("not_hex").is_err());
+        assert!(parse_coordinator_pubkey(&coordinator_keypair.pubkey()[..62]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_loop_evicted() {
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        };
+
+        // Mock coordinator failing the first heartbeat, then answering that the contributor has been dropped
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let coordinator = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let server = tokio::spawn(async move {
+            let responses = [
+                "HTTP/1.1 500 Internal Server Error\r\ncontent-length: 0\r\nconnection: close\r\n\r\n",
+                "HTTP/1.1 410 Gone\r\ncontent-length: 7\r\nconnection: close\r\n\r\nDropped",
+            ];
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let len = stream.read(&mut buffer).await.unwrap();
+                    request.extend_from_slice(&buffer[..len]);
+                }
+                assert!(request.starts_with(b"POST /contributor/heartbeat HTTP/1.1"));
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        // The transient failure is retried after a short delay, while the eviction ends the loop
+        let keypair = KeyPair::new();
+        let heartbeat = tokio::spawn(async move {
+            heartbeat_loop(&Client::new(), &coordinator, &keypair, Duration::from_secs(60)).await
+        });
+        let error = tokio::time::timeout(Duration::from_secs(10), heartbeat)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(matches!(error, RequestError::Evicted(ref body) if body == "Dropped"));
+        assert!(!error.is_transient());
+        server.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_download_challenge_expired_url() {
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::{TcpListener, TcpStream},
+        };
+
+        /// Reads a request up to the end of its body, returning its method and path.
+        async fn read_request(stream: &mut TcpStream) -> String {
+            let mut request = Vec::new();
+            let mut buffer = [0u8; 1024];
+            let headers_end = loop {
+                if let Some(i) = request.windows(4).position(|w| w == b"\r\n\r\n") {
+                    break i + 4;
+                }
+                let len = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..len]);
+            };
+            let headers = String::from_utf8(request[..headers_end].to_vec())
+                .unwrap()
+                .to_lowercase();
+            let body_len: usize = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .map_or(0, |len| len.parse().unwrap());
+            while request.len() < headers_end + body_len {
+                let len = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..len]);
+            }
+
+            headers.split(' ').take(2).collect::<Vec<_>>().join(" ")
+        }
+
+        // Mock coordinator and CDN: the first presigned url has expired by the time of the download
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let coordinator = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+        let presigned_url = |path: &str| format!("\"{}\"", coordinator.join(path).unwrap());
+        let expired = "<Error><Code>AccessDenied</Code><Message>Request has expired</Message></Error>";
+        let responses = [
+            ("200 OK", presigned_url("expired")),
+            ("403 Forbidden", expired.to_owned()),
+            ("200 OK", presigned_url("fresh")),
+            ("200 OK", String::from("challenge")),
+        ];
+        let server = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                requests.push(read_request(&mut stream).await);
+                let response = format!(
+                    "HTTP/1.1 {}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+
+            requests
+        });
+
+        // A fresh url is requested transparently
+        let (mut stream, len) = download_challenge(&Client::new(), &coordinator, &KeyPair::new(), &1)
+            .await
+            .unwrap();
+        let mut challenge = Vec::new();
+        while let Some(bytes) = stream.next().await {
+            challenge.extend_from_slice(&bytes.unwrap());
+        }
+
+        assert_eq!(9, len);
+        assert_eq!(b"challenge".to_vec(), challenge);
+        assert_eq!(
+            vec![
+                "post /contributor/challenge",
+                "get /expired",
+                "post /contributor/challenge",
+                "get /fresh"
+            ],
+            server.await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_socks5_proxy() {
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        };
+
+        // Mock SOCKS5 proxy, answering itself the tunneled request
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let proxy_address = listener.local_addr().unwrap();
+        let proxy = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            // Greeting: version and authentication methods, no authentication is required
+            let mut greeting = [0u8; 2];
+            stream.read_exact(&mut greeting).await.unwrap();
+            let mut methods = vec![0u8; greeting[1] as usize];
+            stream.read_exact(&mut methods).await.unwrap();
+            stream.write_all(&[5, 0]).await.unwrap();
+
+            // Connect request to a domain name, which must not be resolved locally
+            let mut request = [0u8; 5];
+            stream.read_exact(&mut request).await.unwrap();
+            assert_eq!([5, 1, 0, 3], request[..4]);
+            let mut host = vec![0u8; request[4] as usize];
+            stream.read_exact(&mut host).await.unwrap();
+            let mut port = [0u8; 2];
+            stream.read_exact(&mut port).await.unwrap();
+            stream.write_all(&[5, 0, 0, 1, 127, 0, 0, 1, 0, 0]).await.unwrap();
+
+            // Tunneled http request
+            let mut http_request = Vec::new();
+            let mut buffer = [0u8; 1024];
+            while !http_request.ends_with(b"\r\n\r\n") {
+                let len = stream.read(&mut buffer).await.unwrap();
+                http_request.extend_from_slice(&buffer[..len]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .await
+                .unwrap();
+
+            (
+                String::from_utf8(host).unwrap(),
+                u16::from_be_bytes(port),
+                String::from_utf8(http_request).unwrap(),
+            )
+        });
+
+        let client = build_client(Some(&proxy_address.to_string()), None).unwrap();
+        let coordinator = Url::parse("http://coordinator.invalid:8080").unwrap();
+        ping_coordinator(&client, &coordinator).await.unwrap();
+
+        let (host, port, http_request) = proxy.await.unwrap();
+        assert_eq!("coordinator.invalid", host);
+        assert_eq!(8080, port);
+        assert!(http_request.starts_with("GET /healthcheck HTTP/1.1"));
+    }
+
+    #[tokio::test]
+    async fn test_cert_pin() {
+        use rustls::{Certificate, PrivateKey, ServerConfig};
+        use tokio::{
+            io::{AsyncReadExt, AsyncWriteExt},
+            net::TcpListener,
+        };
+        use tokio_rustls::TlsAcceptor;
+
+        // Mock coordinator serving a self-signed certificate for localhost
+        let cert = include_bytes!("resources/test_coordinator_cert.der");
+        let key = include_bytes!("resources/test_coordinator_key.der");
+        let config = ServerConfig::builder()
+            .with_safe_defaults()
+            .with_no_client_auth()
+            .with_single_cert(vec![Certificate(cert.to_vec())], PrivateKey(key.to_vec()))
+            .unwrap();
+        let acceptor = TlsAcceptor::from(std::sync::Arc::new(config));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let coordinator = Url::parse(&format!("https://localhost:{}", listener.local_addr().unwrap().port())).unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                // The handshakes rejected by the client fail here
+                let mut stream = match acceptor.accept(stream).await {
+                    Ok(stream) => stream,
+                    Err(_) => continue,
+                };
+                let mut http_request = Vec::new();
+                let mut buffer = [0u8; 1024];
+                while !http_request.ends_with(b"\r\n\r\n") {
+                    let len = stream.read(&mut buffer).await.unwrap();
+                    http_request.extend_from_slice(&buffer[..len]);
+                }
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                    .await
+                    .unwrap();
+                stream.shutdown().await.ok();
+            }
+        });
+
+        // The self-signed certificate is accepted only because it matches the pin
+        let pin = CertPin::of_certificate(cert);
+        let client = build_client(None, Some((&coordinator, &pin))).unwrap();
+        ping_coordinator(&client, &coordinator).await.unwrap();
+
+        let wrong_pin = CertPin::of_certificate(b"another certificate");
+        let client = build_client(None, Some((&coordinator, &wrong_pin))).unwrap();
+        let error = ping_coordinator(&client, &coordinator).await.unwrap_err();
+        assert!(matches!(error, RequestError::Reqwest(ref e) if cert_pin::is_tls_error(e)));
+        assert!(!error.is_transient());
+    }
+}
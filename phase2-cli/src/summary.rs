@@ -0,0 +1,142 @@
+//! Summary of the transfers and of the durations of a contribution, printed once the contribution is done.
+
+use std::{fmt, time::Duration};
+
+use chrono::{DateTime, Utc};
+use indicatif::{HumanBytes, HumanDuration};
+use phase2_coordinator::objects::ContributionInfo;
+
+/// The transfers and durations of a completed contribution.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContributionSummary {
+    pub round_height: u64,
+    /// Size of the downloaded challenge.
+    pub challenge_size: u64,
+    /// Size of the uploaded contribution.
+    pub contribution_size: u64,
+    /// Duration of the whole contribution, from the start of the CLI to the end of the upload.
+    pub total_time: Duration,
+    /// Duration of the download of the challenge.
+    pub download_time: Duration,
+    /// Duration of the computation of the contribution.
+    pub compute_time: Duration,
+    /// Duration of the upload of the contribution.
+    pub upload_time: Duration,
+}
+
+/// Duration between two timestamps, zero if they are out of order.
+fn elapsed(from: DateTime<Utc>, to: DateTime<Utc>) -> Duration {
+    (to - from).to_std().unwrap_or_default()
+}
+
+/// Average throughput of a transfer, in bytes per second, `None` if the transfer took no measurable time.
+fn throughput(size: u64, time: Duration) -> Option<u64> {
+    if time.is_zero() {
+        None
+    } else {
+        Some((size as f64 / time.as_secs_f64()) as u64)
+    }
+}
+
+impl ContributionSummary {
+    /// Summarizes the contribution from the timestamps of its info and the sizes of the transferred challenge and
+    /// contribution.
+    pub fn new(contrib_info: &ContributionInfo, challenge_size: u64, contribution_size: u64) -> Self {
+        let round_height = contrib_info.ceremony_round;
+        let timestamps = &contrib_info.timestamps;
+
+        Self {
+            round_height,
+            challenge_size,
+            contribution_size,
+            total_time: elapsed(timestamps.start_contribution, timestamps.end_contribution),
+            download_time: elapsed(timestamps.challenge_locked, timestamps.challenge_downloaded),
+            compute_time: elapsed(timestamps.start_computation, timestamps.end_computation),
+            upload_time: elapsed(timestamps.end_computation, timestamps.end_contribution),
+        }
+    }
+
+    /// Average download throughput of the challenge, in bytes per second.
+    pub fn download_throughput(&self) -> Option<u64> {
+        throughput(self.challenge_size, self.download_time)
+    }
+
+    /// Average upload throughput of the contribution, in bytes per second.
+    pub fn upload_throughput(&self) -> Option<u64> {
+        throughput(self.contribution_size, self.upload_time)
+    }
+}
+
+impl fmt::Display for ContributionSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rate = |throughput: Option<u64>| match throughput {
+            Some(throughput) => format!("{}/s", HumanBytes(throughput)),
+            None => "n/a".to_string(),
+        };
+
+        writeln!(f, "Summary of your contribution to round {}:", self.round_height)?;
+        writeln!(
+            f,
+            "  Challenge downloaded: {} in {} ({})",
+            HumanBytes(self.challenge_size),
+            HumanDuration(self.download_time),
+            rate(self.download_throughput())
+        )?;
+        writeln!(
+            f,
+            "  Contribution uploaded: {} in {} ({})",
+            HumanBytes(self.contribution_size),
+            HumanDuration(self.upload_time),
+            rate(self.upload_throughput())
+        )?;
+        writeln!(f, "  Computation: {}", HumanDuration(self.compute_time))?;
+        write!(f, "  Total time: {}", HumanDuration(self.total_time))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_contribution_summary() {
+        let at = |secs| Utc.timestamp(1_660_000_000 + secs, 0);
+        let mut contrib_info = ContributionInfo::default();
+        contrib_info.ceremony_round = 4;
+        contrib_info.timestamps.start_contribution = at(0);
+        contrib_info.timestamps.joined_queue = at(5);
+        contrib_info.timestamps.challenge_locked = at(60);
+        contrib_info.timestamps.challenge_downloaded = at(70);
+        contrib_info.timestamps.start_computation = at(70);
+        contrib_info.timestamps.end_computation = at(190);
+        contrib_info.timestamps.end_contribution = at(210);
+
+        let summary = ContributionSummary::new(&contrib_info, 2_000, 2_544);
+        assert_eq!(2_000, summary.challenge_size);
+        assert_eq!(2_544, summary.contribution_size);
+        assert_eq!(Duration::from_secs(210), summary.total_time);
+        assert_eq!(Duration::from_secs(10), summary.download_time);
+        assert_eq!(Duration::from_secs(120), summary.compute_time);
+        assert_eq!(Duration::from_secs(20), summary.upload_time);
+        assert_eq!(Some(summary.challenge_size / 10), summary.download_throughput());
+        assert_eq!(Some(summary.contribution_size / 20), summary.upload_throughput());
+
+        let output = summary.to_string();
+        assert!(output.contains("round 4"));
+        assert!(output.contains(&format!(
+            "{} in {}",
+            HumanBytes(summary.challenge_size),
+            HumanDuration(Duration::from_secs(10))
+        )));
+        assert!(output.contains(&format!("{}/s", HumanBytes(summary.contribution_size / 20))));
+        assert!(output.contains(&format!("Total time: {}", HumanDuration(Duration::from_secs(210)))));
+
+        // Out of order timestamps don't make up a throughput
+        contrib_info.timestamps.challenge_downloaded = at(50);
+        let summary = ContributionSummary::new(&contrib_info, 2_000, 2_544);
+        assert_eq!(Duration::ZERO, summary.download_time);
+        assert_eq!(None, summary.download_throughput());
+        assert!(summary.to_string().contains("(n/a)"));
+    }
+}
@@ -0,0 +1,188 @@
+//! Independent validation of a downloaded round transcript, so that anybody can audit the chain of contributions
+//! without trusting the coordinator.
+//!
+//! A transcript is the json encoded [`RoundTranscript`] served by the coordinator: for each chunk, the hex encoded
+//! contribution files from the challenge of the round to its final contribution.
+
+use std::path::Path;
+
+use owo_colors::OwoColorize;
+use phase2_coordinator::{commands::verify_contribution_chain, environment::CeremonyParameters, RoundTranscript};
+use setup_utils::calculate_hash;
+use thiserror::Error;
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum TranscriptError {
+    #[error("Error while reading the transcript: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The transcript is not valid json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Contribution {index} of chunk {chunk_id} is not valid hex: {source}")]
+    Hex {
+        chunk_id: u64,
+        index: usize,
+        source: hex::FromHexError,
+    },
+}
+
+/// Decodes the contribution files of each chunk of a transcript, paired with the id of the chunk, along with the
+/// parameters the contributions are computed on.
+pub fn read_transcript(transcript: &[u8]) -> Result<(CeremonyParameters, Vec<(u64, Vec<Vec<u8>>)>), TranscriptError> {
+    let transcript: RoundTranscript = serde_json::from_slice(transcript)?;
+
+    let chunks = transcript
+        .chunks
+        .into_iter()
+        .map(|chunk| {
+            let contributions = chunk
+                .contributions
+                .iter()
+                .enumerate()
+                .map(|(index, contribution)| {
+                    hex::decode(contribution).map_err(|source| TranscriptError::Hex {
+                        chunk_id: chunk.chunk_id,
+                        index,
+                        source,
+                    })
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok((chunk.chunk_id, contributions))
+        })
+        .collect::<Result<_, TranscriptError>>()?;
+
+    Ok((transcript.parameters, chunks))
+}
+
+/// The outcome of the check of a contribution of the transcript.
+#[derive(Debug)]
+pub struct ContributionCheck {
+    pub chunk_id: u64,
+    /// Position of the contribution in the chain of its chunk, the challenge being at 0.
+    pub index: usize,
+    /// Hex encoded hash of the contribution file.
+    pub hash: String,
+    /// The reason of the failure of the check, if any.
+    pub failure: Option<String>,
+}
+
+/// Checks each contribution of the chain of a chunk against the previous one: its prelude must be the hash of the
+/// previous contribution and its parameters a valid transformation of the previous ones.
+pub fn validate_chunk(
+    parameters: CeremonyParameters,
+    chunk_id: u64,
+    contributions: &[&[u8]],
+) -> Vec<ContributionCheck> {
+    verify_contribution_chain(parameters, contributions)
+        .into_iter()
+        .zip(contributions.iter().skip(1))
+        .enumerate()
+        .map(|(i, (result, contribution))| ContributionCheck {
+            chunk_id,
+            index: i + 1,
+            hash: hex::encode(calculate_hash(contribution)),
+            failure: result.err().map(|e| e.to_string()),
+        })
+        .collect()
+}
+
+/// Reads and validates the transcript at the given path, chunk by chunk.
+pub fn validate_transcript_file(path: &Path) -> Result<Vec<ContributionCheck>, TranscriptError> {
+    let transcript = std::fs::read(path)?;
+
+    let (parameters, chunks) = read_transcript(&transcript)?;
+
+    Ok(chunks
+        .iter()
+        .flat_map(|(chunk_id, contributions)| {
+            let contributions: Vec<&[u8]> = contributions.iter().map(Vec::as_slice).collect();
+            validate_chunk(parameters, *chunk_id, &contributions)
+        })
+        .collect())
+}
+
+/// Prints the table of the checks of the contributions, one per line.
+pub fn print_checks(checks: &[ContributionCheck]) {
+    println!(
+        "{:>5}  {:>5}  {:<6}  {}",
+        "CHUNK".bold(),
+        "INDEX".bold(),
+        "RESULT".bold(),
+        "CONTRIBUTION HASH".bold()
+    );
+    for check in checks {
+        match &check.failure {
+            None => println!(
+                "{:>5}  {:>5}  {:<6}  {}",
+                check.chunk_id,
+                check.index,
+                "pass".green(),
+                check.hash
+            ),
+            Some(reason) => println!(
+                "{:>5}  {:>5}  {:<6}  {} ({})",
+                check.chunk_id,
+                check.index,
+                "FAIL".red().bold(),
+                check.hash,
+                reason
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use phase2_coordinator::ChunkTranscript;
+
+    #[test]
+    fn test_read_transcript() {
+        let transcript = RoundTranscript {
+            round_height: 1,
+            skipped: false,
+            parameters: CeremonyParameters::Circuits(2),
+            chunks: vec![
+                ChunkTranscript {
+                    chunk_id: 0,
+                    contributions: vec![hex::encode(b"challenge"), String::new(), hex::encode(b"contribution")],
+                },
+                ChunkTranscript {
+                    chunk_id: 1,
+                    contributions: vec![hex::encode(b"other challenge")],
+                },
+            ],
+        };
+        let encoded = serde_json::to_vec(&transcript).unwrap();
+
+        assert_eq!(
+            (
+                CeremonyParameters::Circuits(2),
+                vec![
+                    (0, vec![b"challenge".to_vec(), Vec::new(), b"contribution".to_vec()]),
+                    (1, vec![b"other challenge".to_vec()]),
+                ]
+            ),
+            read_transcript(&encoded).unwrap()
+        );
+
+        // A contribution which isn't hex encoded
+        let mut invalid = transcript;
+        invalid.chunks[1].contributions.push("not hex".to_owned());
+        assert!(matches!(
+            read_transcript(&serde_json::to_vec(&invalid).unwrap()),
+            Err(TranscriptError::Hex {
+                chunk_id: 1,
+                index: 1,
+                ..
+            })
+        ));
+
+        // A truncated transcript
+        assert!(matches!(
+            read_transcript(&encoded[..encoded.len() - 1]),
+            Err(TranscriptError::Json(_))
+        ));
+    }
+}
@@ -10,26 +10,26 @@ use phase2_coordinator::{
     authentication::{KeyPair, Production, Signature},
     commands::{Computation, RandomSource},
     coordinator_state::CoordinatorState,
+    distribution::{Distributor, S3Distributor},
     environment::Testing,
     objects::{ContributionInfo, LockedLocators, TrimmedContributionInfo},
     rest,
-    rest_utils::{self, PostChunkRequest, TOKENS_ZIP_FILE},
+    rest_utils::{self, NonceStore, PostChunkRequest, VerificationWorker, TOKENS_ZIP_FILE, UPDATE_TIME},
     storage::{ContributionLocator, ContributionSignatureLocator, Object},
     testing::coordinator,
     ContributionFileSignature, ContributionState, Coordinator, Participant,
 };
 use rocket::{
-    catchers, routes,
+    catchers,
     tokio::{
         self,
-        sync::RwLock,
+        sync::{watch, RwLock},
         task::JoinHandle,
         time::{self, Duration},
     },
     Error, Ignite, Rocket,
 };
 
-use async_stream::try_stream;
 use futures_util::StreamExt;
 use toml::Value;
 
@@ -37,6 +37,9 @@ use phase2_cli::requests;
 use reqwest::{Client, Url};
 use zip::write::FileOptions;
 
+mod test_support;
+use test_support::{spawn_test_coordinator, TEST_TOKENS};
+
 const ROUND_HEIGHT: u64 = 1;
 
 struct TestParticipant {
@@ -134,29 +137,20 @@ async fn test_prelude() -> (TestCtx, JoinHandle<Result<Rocket<Ignite>, Error>>)
 
     let coordinator: Arc<RwLock<Coordinator>> = Arc::new(RwLock::new(coordinator));
 
+    // Verify the contributions in the background, as the coordinator does
+    let verification_worker = VerificationWorker::default();
+    let (_, shutdown) = watch::channel(false);
+    tokio::spawn({
+        let (worker, coordinator) = (verification_worker.clone(), coordinator.clone());
+        async move { worker.run(coordinator, None, UPDATE_TIME, shutdown).await }
+    });
+
     let build = rocket::build()
-        .mount(
-            "/",
-            routes![
-                rest::join_queue,
-                rest::lock_chunk,
-                rest::contribute_chunk,
-                rest::update_coordinator,
-                rest::heartbeat,
-                rest::stop_coordinator,
-                rest::verify_chunks,
-                rest::get_contributor_queue_status,
-                rest::post_contribution_info,
-                rest::get_contributions_info,
-                rest::get_healthcheck,
-                rest::get_contribution_url,
-                rest::get_challenge_url,
-                rest::get_coordinator_state,
-                rest::update_cohorts,
-                rest::post_attestation
-            ],
-        )
+        .mount("/", rest::routes())
         .manage(coordinator)
+        .manage::<Distributor>(Arc::new(S3Distributor))
+        .manage(NonceStore::default())
+        .manage(verification_worker)
         .register(
             "/",
             catchers![
@@ -166,7 +160,12 @@ async fn test_prelude() -> (TestCtx, JoinHandle<Result<Rocket<Ignite>, Error>>)
                 rest_utils::io_error,
                 rest_utils::unprocessable_entity,
                 rest_utils::mismatching_checksum,
-                rest_utils::invalid_header
+                rest_utils::invalid_header,
+                rest_utils::replayed_request,
+                rest_utils::ceremony_not_started,
+                rest_utils::incompatible_protocol_version,
+                rest_utils::payload_too_large,
+                rest_utils::invalid_participant
             ],
         );
 
@@ -306,7 +305,8 @@ async fn get_status() {
 
     // Retrieve coordinator.json file with valid token
     let url = Url::parse(&ctx.coordinator_url).unwrap();
-    let response = requests::get_coordinator_state(&url, access_token).await;
+    let client = Client::new();
+    let response = requests::get_coordinator_state(&client, &url, access_token).await;
     assert!(response.is_ok());
 
     // Check deserialization
@@ -321,7 +321,7 @@ async fn get_status() {
     assert!(status.get_current_tokens().is_empty());
 
     // Provide invalid token
-    let response = requests::get_coordinator_state(&url, "wrong token").await;
+    let response = requests::get_coordinator_state(&client, &url, "wrong token").await;
     assert!(response.is_err());
 
     // Drop the server
@@ -603,10 +603,9 @@ async fn contribution() {
     }
 
     // Get contribution url
-    let (chunk_url, sig_url) =
-        requests::get_contribution_url(&client, &url, &ctx.contributors[0].keypair, &ROUND_HEIGHT)
-            .await
-            .unwrap();
+    let (_, sig_url) = requests::get_contribution_url(&client, &url, &ctx.contributors[0].keypair, &ROUND_HEIGHT)
+        .await
+        .unwrap();
 
     // Upload chunk
     let contribution_locator = ContributionLocator::new(ROUND_HEIGHT, 0, 1, false);
@@ -635,24 +634,23 @@ async fn contribution() {
 
     let contribution_file_signature = ContributionFileSignature::new(signature, contribution_state).unwrap();
 
-    let contribution_len = contribution.len() as u64;
-    let mut stream = futures_util::stream::iter(contribution);
-
-    let contrib_stream = try_stream! {
-        while let Some(b) = stream.next().await {
-            yield vec![b].into();
-        }
-    };
+    let mut committed = Vec::new();
     requests::upload_chunk(
         &client,
-        chunk_url.as_str(),
-        sig_url.as_str(),
-        contrib_stream,
-        contribution_len,
-        &contribution_file_signature,
+        &url,
+        &ctx.contributors[0].keypair,
+        &contribution_locator,
+        &contribution,
+        0,
+        None,
+        |offset| committed.push(offset),
     )
     .await
     .unwrap();
+    assert_eq!(Some(&(contribution.len() as u64)), committed.last());
+    requests::upload_contribution_signature(&client, sig_url.as_str(), &contribution_file_signature)
+        .await
+        .unwrap();
 
     // Post contribution info
     let mut contrib_info = ContributionInfo::default();
@@ -674,9 +672,10 @@ async fn contribution() {
     // Contribute
     let post_chunk = PostChunkRequest::new(ROUND_HEIGHT, contribution_locator, contribution_file_signature_locator);
 
-    requests::post_contribute_chunk(&client, &url, &ctx.contributors[0].keypair, &post_chunk)
+    let receipt = requests::post_contribute_chunk(&client, &url, &ctx.contributors[0].keypair, &post_chunk)
         .await
         .unwrap();
+    assert!(receipt.verify(ctx.coordinator.keypair.pubkey()));
 
     // Verify chunk
     requests::get_verify_chunks(&client, &url, &ctx.coordinator.keypair)
@@ -684,7 +683,9 @@ async fn contribution() {
         .unwrap();
 
     // Get contributions info
-    let summary_bytes = requests::get_contributions_info(&url).await.unwrap();
+    let summary_bytes = requests::get_contributions_info(&client, &url, None, None, None, None)
+        .await
+        .unwrap();
     let summary: Vec<TrimmedContributionInfo> = serde_json::from_slice(&summary_bytes).unwrap();
     assert_eq!(summary.len(), 1);
     assert_eq!(summary[0].public_key(), ctx.contributors[0].keypair.pubkey());
@@ -780,3 +781,188 @@ async fn contribution() {
     // Drop the server
     handle.abort()
 }
+
+/// Test a full contribution of a new participant through the test harness:
+///
+/// - join_queue
+/// - update the coordinator
+/// - lock_chunk
+/// - get_challenge
+/// - upload_chunk
+/// - post_contribution_info
+/// - contribute_chunk
+/// - verify_chunk
+///
+#[tokio::test]
+async fn harness_contribution() {
+    use phase2_cli::hash::{get_file_as_byte_vec, ContributionHashes};
+    use rand::Rng;
+    use setup_utils::calculate_hash;
+
+    let access_token = "test-access_token";
+    std::env::set_var("ACCESS_SECRET", access_token);
+    let (test_client, shutdown_handle) = spawn_test_coordinator().await;
+    let client = &test_client.client;
+    let url = &test_client.url;
+    let keypair = KeyPair::new();
+
+    // Join the queue and start the round
+    requests::post_join_queue(client, url, &keypair, &TEST_TOKENS[0].to_string())
+        .await
+        .unwrap();
+    requests::get_update(client, url, &test_client.coordinator)
+        .await
+        .unwrap();
+    let locked_locators = requests::get_lock_chunk(client, url, &keypair).await.unwrap();
+    let contribution_locator = locked_locators.next_contribution();
+    let round_height = contribution_locator.round_height();
+
+    // Get challenge
+    let challenge_url = requests::get_challenge_url(client, url, &keypair, &round_height)
+        .await
+        .unwrap();
+    let mut challenge_stream = requests::get_challenge(client, challenge_url.as_str()).await.unwrap();
+    let mut challenge: Vec<u8> = Vec::new();
+    while let Some(b) = challenge_stream.0.next().await {
+        challenge.extend_from_slice(&b.unwrap());
+    }
+
+    // Compute the contribution
+    let challenge_hash = calculate_hash(challenge.as_ref());
+    let mut contribution: Vec<u8> = Vec::new();
+    contribution.write_all(challenge_hash.as_slice()).unwrap();
+    let seed = RandomSource::Seed(rand::thread_rng().gen::<[u8; 32]>());
+    Computation::contribute_test_masp(&challenge, &mut contribution, &seed);
+    let contrib_size = Object::anoma_contribution_file_size(round_height, contribution_locator.contribution_id());
+    contribution.resize(contrib_size as usize, 0);
+
+    // Hash the contribution file the way the `hash` command does
+    let contribution_file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(contribution_file.path(), &contribution).unwrap();
+    let hashes = ContributionHashes::new(
+        &get_file_as_byte_vec(contribution_file.path().to_str().unwrap(), contrib_size).unwrap(),
+    );
+
+    let response_hash = calculate_hash(contribution.as_ref());
+    let contribution_state = ContributionState::new(challenge_hash.to_vec(), response_hash.to_vec(), None).unwrap();
+    let signature = Production
+        .sign(keypair.sigkey(), &contribution_state.signature_message().unwrap())
+        .unwrap();
+    let contribution_file_signature = ContributionFileSignature::new(signature, contribution_state).unwrap();
+
+    // Upload the contribution and its signature
+    let (_, sig_url) = requests::get_contribution_url(client, url, &keypair, &round_height)
+        .await
+        .unwrap();
+    requests::upload_chunk(
+        client,
+        url,
+        &keypair,
+        &contribution_locator,
+        &contribution,
+        0,
+        None,
+        |_| (),
+    )
+    .await
+    .unwrap();
+    requests::upload_contribution_signature(client, sig_url.as_str(), &contribution_file_signature)
+        .await
+        .unwrap();
+
+    let mut contrib_info = ContributionInfo::default();
+    contrib_info.public_key = keypair.pubkey().to_owned();
+    contrib_info.ceremony_round = round_height;
+    contrib_info.try_sign(&keypair).unwrap();
+    requests::post_contribution_info(client, url, &keypair, &contrib_info)
+        .await
+        .unwrap();
+
+    // Contribute and verify
+    let contribution_signature_locator = ContributionSignatureLocator::new(
+        round_height,
+        contribution_locator.chunk_id(),
+        contribution_locator.contribution_id(),
+        false,
+    );
+    let post_chunk = PostChunkRequest::new(round_height, contribution_locator, contribution_signature_locator);
+    let receipt = requests::post_contribute_chunk(client, url, &keypair, &post_chunk)
+        .await
+        .unwrap();
+    assert_eq!(hex::encode(&hashes.file_hash), receipt.contribution_hash);
+    requests::get_verify_chunks(client, url, &test_client.coordinator)
+        .await
+        .unwrap();
+
+    // The hash of the contribution computed by the coordinator on the uploaded file matches the one of the CLI
+    let state_bytes = requests::get_coordinator_state(client, url, access_token)
+        .await
+        .unwrap();
+    let state: CoordinatorState = serde_json::from_slice(&state_bytes).unwrap();
+    assert_eq!(
+        Some(round_height),
+        state.contribution_hash_round(&hex::encode(&hashes.contribution_hash))
+    );
+
+    let summary_bytes = requests::get_contributions_info(client, url, None, None, None, None)
+        .await
+        .unwrap();
+    let summary: Vec<TrimmedContributionInfo> = serde_json::from_slice(&summary_bytes).unwrap();
+    assert_eq!(summary.len(), 1);
+    assert_eq!(summary[0].public_key(), keypair.pubkey());
+    assert_eq!(summary[0].ceremony_round(), round_height);
+
+    shutdown_handle.shutdown().await;
+}
+
+/// Fetch the challenge without contributing: the challenge and its transfer manifest are saved and the chunk stays
+/// locked by the contributor.
+#[tokio::test]
+async fn fetch_challenge() {
+    use phase2_cli::{fetch, manifest::TransferManifest};
+    use setup_utils::calculate_hash;
+
+    let access_token = "test-access_token";
+    std::env::set_var("ACCESS_SECRET", access_token);
+    let (test_client, shutdown_handle) = spawn_test_coordinator().await;
+    let client = &test_client.client;
+    let url = &test_client.url;
+    let keypair = KeyPair::new();
+
+    // Join the queue and start the round
+    requests::post_join_queue(client, url, &keypair, &TEST_TOKENS[0].to_string())
+        .await
+        .unwrap();
+    requests::get_update(client, url, &test_client.coordinator)
+        .await
+        .unwrap();
+
+    let output = tempfile::tempdir().unwrap();
+    let fetched = fetch::fetch_challenge(client, url, &keypair, output.path())
+        .await
+        .unwrap();
+    assert_eq!(ROUND_HEIGHT, fetched.manifest.round_height);
+
+    // The challenge and the manifest are saved
+    let challenge = std::fs::read(&fetched.challenge_path).unwrap();
+    let manifest = TransferManifest::read(&fetched.manifest_path).unwrap();
+    assert_eq!(fetched.manifest, manifest);
+    assert!(manifest.verify_challenge(&calculate_hash(&challenge)).is_ok());
+
+    // The chunk is still locked by the contributor
+    let state_bytes = requests::get_coordinator_state(client, url, access_token)
+        .await
+        .unwrap();
+    let state: CoordinatorState = serde_json::from_slice(&state_bytes).unwrap();
+    let contributor = Participant::new_contributor(keypair.pubkey().as_ref());
+    let (_, info) = state
+        .current_contributors()
+        .into_iter()
+        .find(|(participant, _)| *participant == contributor)
+        .unwrap();
+    assert!(info
+        .locked_chunks()
+        .contains_key(&fetched.locked_locators.next_contribution().chunk_id()));
+
+    shutdown_handle.shutdown().await;
+}
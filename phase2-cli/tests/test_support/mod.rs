@@ -0,0 +1,163 @@
+//! Harness booting the coordinator REST server for the end-to-end tests.
+//!
+//! NOTE: the coordinator storage is kept at the same path for all the tests, which must then be run with
+//! --test-threads=1.
+
+use std::{io::Write, sync::Arc};
+
+use phase2_cli::requests;
+use phase2_coordinator::{
+    authentication::{KeyPair, Production},
+    distribution::{Distributor, S3Distributor},
+    environment::Testing,
+    rest,
+    rest_utils::{self, NonceStore, VerificationWorker, UPDATE_TIME},
+    testing::coordinator,
+    Coordinator,
+};
+use reqwest::{Client, Url};
+use rocket::{
+    catchers,
+    tokio::{
+        self,
+        sync::{watch, RwLock},
+        task::JoinHandle,
+        time::{self, Duration},
+    },
+    Config, Error, Ignite, Rocket, Shutdown,
+};
+
+/// Port the test coordinator listens on.
+const TEST_PORT: u16 = 8090;
+/// Maximum number of healthchecks to wait for the server startup.
+const STARTUP_ATTEMPTS: usize = 50;
+
+/// The tokens of the first cohort of the test coordinator.
+pub const TEST_TOKENS: [&str; 3] = [
+    "9nFeNpukSn1eVwNc2vkfP7rdLh2njm5ewmCGxSLTW3GYmKP51fKjbRUvHDmntjEaQiq7iFux9tumgWEWVHwHQCs31oitpqBpMWpMydo1DnuFyLpsD6C",
+    "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek",
+    "9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2",
+];
+
+/// Client of the test coordinator.
+pub struct TestClient {
+    pub client: Client,
+    /// Url of the test coordinator.
+    pub url: Url,
+    /// Keypair of the coordinator verifier, required by the endpoints reserved to the coordinator.
+    pub coordinator: KeyPair,
+}
+
+/// Stops the test coordinator.
+pub struct ShutdownHandle {
+    shutdown: Shutdown,
+    handle: JoinHandle<Result<Rocket<Ignite>, Error>>,
+    // Keep the tokens directory alive as long as the server
+    _tokens_tmp_dir: tempfile::TempDir,
+}
+
+impl ShutdownHandle {
+    /// Gracefully shuts the server down and waits for it to stop.
+    pub async fn shutdown(self) {
+        self.shutdown.notify();
+        self.handle.await.ok();
+    }
+}
+
+/// Boots a coordinator with the testing parameters, serving all the routes of the REST API, and waits for it to
+/// accept requests.
+pub async fn spawn_test_coordinator() -> (TestClient, ShutdownHandle) {
+    std::env::set_var("TOKEN_BLACKLIST", "true");
+
+    // Reset storage to prevent state conflicts between tests and initialize test environment
+    let environment = coordinator::initialize_test_environment(&Testing::default().into());
+
+    // Need a fixed-name temp dir because of the lazy_static variables based on env
+    let os_temp_dir = std::env::temp_dir();
+    std::fs::remove_dir_all(os_temp_dir.join("my-temporary-dir")).ok();
+    let tokens_tmp_dir = tempfile::Builder::new()
+        .prefix("my-temporary-dir")
+        .rand_bytes(0)
+        .tempdir()
+        .unwrap();
+    let mut token_file = std::fs::File::create(tokens_tmp_dir.path().join("namada_tokens_cohort_1.json")).unwrap();
+    token_file
+        .write_all(serde_json::to_string(&TEST_TOKENS).unwrap().as_bytes())
+        .unwrap();
+    std::env::set_var("NAMADA_TOKENS_PATH", tokens_tmp_dir.path());
+    std::env::set_var("TOKENS_FILE_PREFIX", "namada_tokens_cohort");
+
+    let mut coordinator = Coordinator::new(environment, Arc::new(Production)).unwrap();
+    coordinator.initialize().unwrap();
+    let coordinator_keypair = KeyPair::custom_new(
+        coordinator.environment().default_verifier_signing_key(),
+        coordinator.environment().coordinator_verifiers()[0].address(),
+    );
+    let coordinator: Arc<RwLock<Coordinator>> = Arc::new(RwLock::new(coordinator));
+
+    // Verify the contributions in the background, as the coordinator does
+    let verification_worker = VerificationWorker::default();
+    let (_, shutdown) = watch::channel(false);
+    tokio::spawn({
+        let (worker, coordinator) = (verification_worker.clone(), coordinator.clone());
+        async move { worker.run(coordinator, None, UPDATE_TIME, shutdown).await }
+    });
+
+    // Don't wait for the grace period of the production configuration at shutdown
+    let figment = Config::figment()
+        .merge(("port", TEST_PORT))
+        .merge(("shutdown.grace", 1))
+        .merge(("shutdown.mercy", 1));
+    let ignite = rocket::custom(figment)
+        .mount("/", rest::routes())
+        .manage(coordinator)
+        .manage::<Distributor>(Arc::new(S3Distributor))
+        .manage(NonceStore::default())
+        .manage(verification_worker)
+        .register(
+            "/",
+            catchers![
+                rest_utils::invalid_signature,
+                rest_utils::unauthorized,
+                rest_utils::missing_required_header,
+                rest_utils::io_error,
+                rest_utils::unprocessable_entity,
+                rest_utils::mismatching_checksum,
+                rest_utils::invalid_header,
+                rest_utils::replayed_request,
+                rest_utils::ceremony_not_started,
+                rest_utils::incompatible_protocol_version,
+                rest_utils::payload_too_large,
+                rest_utils::invalid_participant
+            ],
+        )
+        .ignite()
+        .await
+        .unwrap();
+
+    let url = Url::parse(&format!("http://{}:{}", ignite.config().address, ignite.config().port)).unwrap();
+    let shutdown = ignite.shutdown();
+    let handle = tokio::spawn(ignite.launch());
+
+    // Wait for server startup
+    let client = Client::new();
+    for _ in 0..STARTUP_ATTEMPTS {
+        if requests::ping_coordinator(&client, &url).await.is_ok() {
+            break;
+        }
+        time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let test_client = TestClient {
+        client,
+        url,
+        coordinator: coordinator_keypair,
+    };
+    let shutdown_handle = ShutdownHandle {
+        shutdown,
+        handle,
+        _tokens_tmp_dir: tokens_tmp_dir,
+    };
+
+    (test_client, shutdown_handle)
+}
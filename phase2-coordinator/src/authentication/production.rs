@@ -1,4 +1,4 @@
-use crate::authentication::Signature as SigTrait;
+use crate::authentication::{Authentication, Signature as SigTrait};
 use ed25519_compact::{Error, KeyPair as EdKeyPair, Noise, PublicKey, SecretKey, Seed, Signature};
 use serde::{Deserialize, Serialize};
 use std::ops::Deref;
@@ -51,6 +51,23 @@ impl KeyPair {
     }
 }
 
+impl Authentication for KeyPair {
+    /// Returns the [`hex`] encoded public key.
+    fn pubkey(&self) -> &str {
+        self.pubkey.as_ref()
+    }
+
+    /// Signs the given message with the [`Production`] signature scheme.
+    fn sign(&self, message: &str) -> anyhow::Result<String> {
+        Production.sign(&self.sigkey, message)
+    }
+
+    /// Verifies the given signature with the [`Production`] signature scheme.
+    fn verify(&self, message: &str, signature: &str) -> bool {
+        Production.verify(&self.pubkey, message, signature)
+    }
+}
+
 /// The authentication to be used in production, based on [`ed25519_compact`]
 pub struct Production;
 
@@ -104,4 +121,14 @@ mod tests {
 
         assert!(sig_scheme.verify(keypair.pubkey(), msg, signature.as_ref()));
     }
+
+    #[test]
+    fn keypair_authentication() {
+        let keypair = KeyPair::new();
+        let msg = "This is the message to sign";
+        let signature = Authentication::sign(&keypair, msg).unwrap();
+
+        assert!(Authentication::verify(&keypair, msg, &signature));
+        assert!(Production.verify(keypair.pubkey(), msg, &signature));
+    }
 }
@@ -1,3 +1,6 @@
+use super::Production;
+use serde::{Deserialize, Serialize};
+
 /// A core structure for authentication of contributions.
 pub trait Signature: Send + Sync {
     /// Returns the name of the signature scheme.
@@ -14,3 +17,95 @@ pub trait Signature: Send + Sync {
     /// and returns `true` if the signature is valid.
     fn verify(&self, public_key: &str, message: &str, signature: &str) -> bool;
 }
+
+/// Identifier of the signature scheme which produced a signature, recorded along with the signature so that it can
+/// still be verified, or rejected, if the scheme of the ceremony ever changes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SignatureSchemeId(u8);
+
+impl SignatureSchemeId {
+    /// The ed25519 scheme of the [`Production`] signer.
+    pub const ED25519: Self = Self(0);
+
+    /// Returns the signer of the scheme, `None` if the scheme is unknown.
+    pub fn signer(self) -> Option<&'static dyn Signature> {
+        match self {
+            Self::ED25519 => Some(&Production),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if this is the scheme the signatures are produced with, i.e. [`SignatureSchemeId::ED25519`].
+    pub fn is_current(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Verifies the given signature with the signer of the scheme. Signatures of an unknown scheme are invalid.
+    pub fn verify(self, public_key: &str, message: &str, signature: &str) -> bool {
+        self.signer()
+            .map_or(false, |signer| signer.verify(public_key, message, signature))
+    }
+}
+
+impl Default for SignatureSchemeId {
+    /// The signatures predating the scheme identifier were all produced with [`SignatureSchemeId::ED25519`].
+    fn default() -> Self {
+        Self::ED25519
+    }
+}
+
+/// A participant identity able to authenticate its own requests.
+///
+/// This abstracts over where the signing key lives, so that a signer backed by,
+/// e.g., a hardware token can be used in place of an in-memory [`KeyPair`](super::KeyPair).
+pub trait Authentication: Send + Sync {
+    /// Returns the public key identifying the participant.
+    fn pubkey(&self) -> &str;
+
+    /// Signs the given message and returns the signature as a string.
+    fn sign(&self, message: &str) -> anyhow::Result<String>;
+
+    /// Verifies the given signature for the given message against
+    /// the public key of this identity.
+    fn verify(&self, message: &str, signature: &str) -> bool;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use setup_utils::calculate_hash;
+
+    /// A mock authenticator simulating an external signing device.
+    struct MockToken {
+        pubkey: String,
+    }
+
+    impl Authentication for MockToken {
+        fn pubkey(&self) -> &str {
+            &self.pubkey
+        }
+
+        fn sign(&self, message: &str) -> anyhow::Result<String> {
+            let digest = calculate_hash(format!("{}{}", self.pubkey, message).as_bytes());
+            Ok(hex::encode(digest))
+        }
+
+        fn verify(&self, message: &str, signature: &str) -> bool {
+            self.sign(message).map(|s| s == signature).unwrap_or(false)
+        }
+    }
+
+    #[test]
+    fn test_mock_authentication_round_trip() {
+        let token = MockToken {
+            pubkey: String::from("token"),
+        };
+        let auth: &dyn Authentication = &token;
+        let msg = "This is the message to sign";
+
+        let signature = auth.sign(msg).unwrap();
+        assert!(auth.verify(msg, &signature));
+        assert!(!auth.verify("Another message", &signature));
+    }
+}
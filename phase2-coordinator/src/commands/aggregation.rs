@@ -1,99 +1,116 @@
 use crate::{
     environment::Environment,
     objects::Round,
-    storage::{ContributionLocator, Disk, DiskObjectReader, Locator, Object, StorageLocator, StorageObject},
+    storage::{ContributionLocator, Disk, Locator, Object, StorageLocator},
     CoordinatorError,
 };
 
-use std::time::Instant;
+use std::{io::Read, time::Instant};
 use tracing::{debug, error, trace};
 
+/// Size of the buffer the contribution files are streamed through during the aggregation.
+pub(crate) const AGGREGATION_BUFFER_SIZE: usize = 1024 * 1024;
+
 pub(crate) struct Aggregation;
 
 impl Aggregation {
     /// Runs aggregation for a given environment, storage, and round.
     /// NOTE: The Namada Trusted Setup runs only phase 2 and doesn't chunk the contributions. The aggregation has been left as a placeholder to avoid breaking the whole library logic. In practice, there is no aggregation.
     #[inline]
-    pub(crate) fn run(environment: &Environment, storage: &mut Disk, round: &Round) -> anyhow::Result<()> {
+    pub(crate) fn run(environment: &Environment, storage: &mut Disk, round: &Round) -> Result<(), CoordinatorError> {
+        Self::run_streaming(environment, storage, round, AGGREGATION_BUFFER_SIZE, |_, _| Ok(()))
+    }
+
+    /// Runs aggregation for a given environment, storage, and round, streaming the contribution of each chunk, one
+    /// chunk at a time, through a buffer of `buffer_size` bytes into `combine`, which receives the chunk ID and the
+    /// buffered bytes. The memory used is then bounded by the buffer rather than by the size of the round.
+    pub(crate) fn run_streaming<F>(
+        environment: &Environment,
+        storage: &mut Disk,
+        round: &Round,
+        buffer_size: usize,
+        mut combine: F,
+    ) -> Result<(), CoordinatorError>
+    where
+        F: FnMut(u64, &[u8]) -> Result<(), CoordinatorError>,
+    {
         let start = Instant::now();
 
         // Fetch the round height.
         let round_height = round.round_height();
         debug!("Starting aggregation on round {}", round_height);
 
-        // Fetch the compressed input setting for the final round file.
-        let _compressed_input = environment.compressed_inputs();
-        // Fetch the compressed output setting based on the round height.
-        let compressed_output = environment.compressed_outputs();
-
         // Fetch the round locator for the given round.
         let round_locator = Locator::RoundFile { round_height };
 
         // Check that the round locator does not already exist.
         if storage.exists(&round_locator) {
-            return Err(CoordinatorError::RoundLocatorAlreadyExists.into());
+            return Err(CoordinatorError::RoundLocatorAlreadyExists);
         }
 
+        // Check that every chunk has its final contribution before touching storage.
+        let contribution_locators = (0..environment.number_of_chunks())
+            .map(|chunk_id| Self::contribution_locator(storage, round, chunk_id))
+            .collect::<Result<Vec<_>, _>>()?;
+
         // Initialize the round locator.
-        storage.initialize(round_locator.clone(), Object::round_file_size(environment))?;
+        storage.initialize(round_locator, Object::round_file_size(environment))?;
+
+        // Stream the contribution files, the buffer is the only chunk content held in memory.
+        let mut buffer = vec![0; buffer_size];
+        for (chunk_id, contribution_locator) in (0..).zip(contribution_locators) {
+            trace!("Streaming contribution for round {} chunk {}", round_height, chunk_id);
+
+            let mut reader = storage.stream_reader(&contribution_locator)?;
+            loop {
+                let read = reader.read(&mut buffer)?;
+                if read == 0 {
+                    break;
+                }
+                combine(chunk_id, &buffer[..read])?;
+            }
 
-        // Load the contribution files.
-        let readers = Self::readers(environment, storage, round)?;
-        let _contribution_readers: Vec<_> = readers.iter().map(|r| (r.as_ref(), compressed_output)).collect();
+            trace!("Streamed contribution for round {} chunk {}", round_height, chunk_id);
+        }
 
         let elapsed = Instant::now().duration_since(start);
         debug!("Completed aggregation on round {} in {:?}", round_height, elapsed);
         Ok(())
     }
 
-    /// Attempts to open every contribution for the given round and
-    /// returns readers to each chunk contribution file.
+    /// Returns the locator of the final contribution of the given chunk,
+    /// checking that it has been verified.
     #[inline]
-    fn readers<'a>(
-        environment: &Environment,
-        storage: &'a Disk,
-        round: &Round,
-    ) -> anyhow::Result<Vec<DiskObjectReader>> {
-        let mut readers = vec![];
-
+    fn contribution_locator(storage: &Disk, round: &Round, chunk_id: u64) -> Result<Locator, CoordinatorError> {
         // Fetch the round height.
         let round_height = round.round_height();
 
         // Fetch the expected current contribution ID for each chunk in the given round.
         let expected_id = round.expected_number_of_contributions() - 1;
 
-        for chunk_id in 0..environment.number_of_chunks() {
-            trace!("Loading contribution for round {} chunk {}", round_height, chunk_id);
-
-            // Fetch the contribution ID.
-            let contribution_id = round.chunk(chunk_id)?.current_contribution_id();
-
-            // Sanity check that all chunks have all contributions present.
-            if expected_id != contribution_id {
-                error!("Expects {} contributions, found {}", expected_id, contribution_id);
-                return Err(CoordinatorError::NumberOfContributionsDiffer.into());
-            }
-
-            // Fetch the contribution locator.
-            let contribution_locator =
-                Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, contribution_id, false));
-            trace!("Loading contribution from {}", storage.to_path(&contribution_locator)?);
-
-            // Check the corresponding verified contribution locator exists.
-            let verified_contribution =
-                Locator::ContributionFile(ContributionLocator::new(round_height + 1, chunk_id, 0, true));
-            if !storage.exists(&verified_contribution) {
-                error!("{} is missing", storage.to_path(&verified_contribution)?);
-                return Err(CoordinatorError::ContributionMissingVerifiedLocator.into());
-            }
+        // Fetch the contribution ID.
+        let contribution_id = round.chunk(chunk_id)?.current_contribution_id();
 
-            // Fetch and save a reader for the contribution locator.
-            readers.push(storage.reader(&contribution_locator)?);
+        // Sanity check that all chunks have all contributions present.
+        if expected_id != contribution_id {
+            error!("Expects {} contributions, found {}", expected_id, contribution_id);
+            return Err(CoordinatorError::NumberOfContributionsDiffer);
+        }
 
-            trace!("Loaded contribution for round {} chunk {}", round_height, chunk_id);
+        // Fetch the contribution locator.
+        let contribution_locator =
+            Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, contribution_id, false));
+        trace!("Loading contribution from {}", storage.to_path(&contribution_locator)?);
+
+        // Check the corresponding verified contribution locator exists.
+        let verified_contribution =
+            Locator::ContributionFile(ContributionLocator::new(round_height + 1, chunk_id, 0, true));
+        if !storage.exists(&verified_contribution) {
+            error!("{} is missing", storage.to_path(&verified_contribution)?);
+            return Err(CoordinatorError::ContributionMissingVerifiedLocator);
         }
 
-        Ok(readers)
+        Ok(contribution_locator)
     }
 }
 
@@ -104,8 +121,8 @@ mod tests {
     use crate::{
         authentication::Dummy,
         commands::{Aggregation, Seed, SigningKey, SEED_LENGTH},
-        objects::Task,
-        storage::Locator,
+        objects::{Round, Task},
+        storage::{Locator, Object},
         testing::prelude::*,
         Coordinator,
     };
@@ -115,13 +132,8 @@ mod tests {
     use time::OffsetDateTime;
     use tracing::*;
 
-    #[test]
-    #[serial]
-    fn test_aggregation_run() {
-        initialize_test_environment(&TEST_ENVIRONMENT_3);
-
-        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy)).unwrap();
-
+    /// Contributes to and verifies every chunk of round 1, returning the round ready to be aggregated.
+    fn verified_round_1(coordinator: &mut Coordinator) -> Round {
         let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
         let contributor_signing_key: SigningKey = "secret_key".to_string();
 
@@ -221,7 +233,17 @@ mod tests {
         }
 
         // Fetch the current round state.
-        let round = coordinator.get_round(round_height).unwrap();
+        coordinator.get_round(round_height).unwrap()
+    }
+
+    #[test]
+    #[serial]
+    fn test_aggregation_run() {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy)).unwrap();
+        let round = verified_round_1(&mut coordinator);
+        let round_height = round.round_height();
 
         // Aggregate.
         {
@@ -236,4 +258,35 @@ mod tests {
             assert!(storage.exists(&round_locator));
         }
     }
+    #[test]
+    #[serial]
+    fn test_aggregation_run_streaming() {
+        const BUFFER_SIZE: usize = 100;
+
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy)).unwrap();
+        let round = verified_round_1(&mut coordinator);
+        let round_height = round.round_height();
+        let number_of_chunks = TEST_ENVIRONMENT_3.number_of_chunks();
+        assert!(number_of_chunks > 1);
+
+        // Aggregate through a buffer much smaller than a contribution.
+        let storage = coordinator.storage_mut();
+        let mut streamed = vec![0; number_of_chunks as usize];
+        Aggregation::run_streaming(&TEST_ENVIRONMENT_3, storage, &round, BUFFER_SIZE, |chunk_id, bytes| {
+            assert!(bytes.len() <= BUFFER_SIZE);
+            streamed[chunk_id as usize] += bytes.len() as u64;
+            Ok(())
+        })
+        .unwrap();
+
+        // Every chunk was streamed in full, one at a time.
+        let contribution_size = Object::anoma_contribution_file_size(round_height, 1);
+        assert!(contribution_size > BUFFER_SIZE as u64);
+        assert_eq!(vec![contribution_size; number_of_chunks as usize], streamed);
+
+        let round_locator = Locator::RoundFile { round_height };
+        assert!(storage.exists(&round_locator));
+    }
 }
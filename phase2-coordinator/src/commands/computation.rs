@@ -1,7 +1,7 @@
 use crate::{
     authentication::Signature,
     commands::SigningKey,
-    environment::Environment,
+    environment::{CeremonyParameters, Environment},
     storage::{Disk, Locator, StorageLocator, StorageObject},
     CoordinatorError,
 };
@@ -9,24 +9,89 @@ use phase2::helpers::CurveKind;
 
 use setup_utils::calculate_hash;
 
-use std::{io::Write, sync::Arc, time::Instant};
+use std::{
+    io::Write,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tracing::{debug, error, info, trace};
 
 pub const SEED_LENGTH: usize = 32;
 pub type Seed = [u8; SEED_LENGTH];
 
+/// Number of steps of the MASP contribution reported to the progress callback: the contribution to and the writing of
+/// the Spend, Output and Convert circuits
+pub const MASP_CONTRIBUTION_STEPS: u64 = 6;
+/// Number of steps of the test MASP contribution reported to the progress callback
+#[cfg(debug_assertions)]
+pub const MASP_TEST_CONTRIBUTION_STEPS: u64 = 2;
+
+/// Returns the number of steps of the contribution to the given parameters reported to the progress callback
+pub fn contribution_steps(parameters: CeremonyParameters) -> u64 {
+    match parameters {
+        #[cfg(debug_assertions)]
+        CeremonyParameters::Masp => MASP_TEST_CONTRIBUTION_STEPS,
+        #[cfg(not(debug_assertions))]
+        CeremonyParameters::Masp => MASP_CONTRIBUTION_STEPS,
+        // The contribution to and the writing of each circuit
+        CeremonyParameters::Circuits(circuits) => 2 * circuits as u64,
+    }
+}
+
 use blake2::{Blake2b512, Digest};
+use bls12_381::{G1Projective, Scalar};
 use itertools::Itertools;
 use masp_phase2::MPCParameters;
 
+/// Number of bytes of OS randomness mixed with the user entropy by [`RandomSource::Mixed`]
+const OS_RANDOMNESS_LENGTH: usize = 64;
+/// Domain separation of the derivation of the seed of [`RandomSource::Mixed`]
+const MIXED_SEED_DOMAIN: &[u8] = b"namada-trusted-setup/mixed-seed";
+
 /// Sources of randomness
 pub enum RandomSource {
-    /// A string to be used as entropy
-    Entropy(String),
-    /// A [`Seed`] of 32 bytes for rng
+    /// A string of user entropy, mixed with OS randomness so that even a weak input yields a strong seed. This is
+    /// the default source
+    Mixed(String),
+    /// A [`Seed`] of 32 bytes for rng, making the contribution reproducible
     Seed(Seed),
 }
 
+impl RandomSource {
+    /// Returns the seed of the rng:
+    ///  - for a [`RandomSource::Seed`], the seed itself
+    ///  - for a [`RandomSource::Mixed`], the seed derived from fresh OS randomness and the user entropy, hashed
+    ///    together with a domain separator. The lengths are hashed too, so that the inputs can't be shifted
+    pub fn seed(&self) -> Seed {
+        match self {
+            RandomSource::Mixed(entropy) => {
+                let mut os_randomness = [0u8; OS_RANDOMNESS_LENGTH];
+                getrandom::getrandom(&mut os_randomness).expect("Failed to gather OS randomness");
+
+                let mut h = Blake2b512::new();
+                h.update(MIXED_SEED_DOMAIN);
+                h.update(&(os_randomness.len() as u64).to_le_bytes());
+                h.update(&os_randomness);
+                h.update(&(entropy.len() as u64).to_le_bytes());
+                h.update(entropy.as_bytes());
+                let digest = h.finalize();
+
+                let mut seed = [0u8; SEED_LENGTH];
+                seed.copy_from_slice(&digest[..SEED_LENGTH]);
+                seed
+            }
+            RandomSource::Seed(seed) => *seed,
+        }
+    }
+
+    /// Creates the rng of the contribution from [`RandomSource::seed`]
+    pub fn rng(&self) -> rand_chacha::ChaChaRng {
+        use rand::SeedableRng;
+
+        rand_chacha::ChaChaRng::from_seed(self.seed())
+    }
+}
+
 pub struct Computation;
 
 impl Computation {
@@ -68,16 +133,20 @@ impl Computation {
         // Run computation on chunk.
         let settings = environment.parameters();
         let curve = settings.curve();
+        let parameters = environment.ceremony_parameters();
         if let Err(error) = match curve {
             CurveKind::Bls12_381 => Self::contribute(
+                parameters,
                 storage.reader(challenge_locator)?.as_ref(),
                 storage.writer(response_locator)?.as_mut(),
             ),
             CurveKind::Bls12_377 => Self::contribute(
+                parameters,
                 storage.reader(challenge_locator)?.as_ref(),
                 storage.writer(response_locator)?.as_mut(),
             ),
             CurveKind::BW6 => Self::contribute(
+                parameters,
                 storage.reader(challenge_locator)?.as_ref(),
                 storage.writer(response_locator)?.as_mut(),
             ),
@@ -123,7 +192,11 @@ impl Computation {
         Ok(())
     }
 
-    fn contribute(challenge_reader: &[u8], mut response_writer: &mut [u8]) -> Result<(), CoordinatorError> {
+    fn contribute(
+        parameters: CeremonyParameters,
+        challenge_reader: &[u8],
+        mut response_writer: &mut [u8],
+    ) -> Result<(), CoordinatorError> {
         trace!("Calculating previous contribution hash and writing it to the response");
 
         let challenge_hash = calculate_hash(&challenge_reader);
@@ -144,13 +217,15 @@ impl Computation {
         // Perform the transformation
         trace!("Computing and writing your contribution, this could take a while");
 
-        // Contribute to the MASP circuit
-        let rand_source = RandomSource::Entropy(String::from("entropy"));
-        #[cfg(debug_assertions)]
-        Self::contribute_test_masp(&challenge_reader, &mut response_writer, &rand_source);
-
-        #[cfg(not(debug_assertions))]
-        Self::contribute_masp(&challenge_reader, &mut response_writer, &rand_source);
+        // Contribute to the circuits of the ceremony
+        let rand_source = RandomSource::Mixed(String::from("entropy"));
+        Self::contribute_parameters_with_progress(
+            parameters,
+            &challenge_reader,
+            &mut response_writer,
+            &rand_source,
+            |_, _| {},
+        );
 
         trace!("Finishing writing your contribution to response file");
 
@@ -162,35 +237,19 @@ impl Computation {
     // | masp_mpc_new_parameters_contribution |
     // The masp-mpc commands are executed at offset 64 of the [`ContributionFile`]
     #[cfg(not(debug_assertions))]
-    pub fn contribute_masp<W: Write>(challenge_reader: &[u8], mut response_writer: W, rand_source: &RandomSource) {
-        // Create an RNG as following:
-        //  - if the user provides a seed, create the rng from that seed
-        //  - if the user provides entropy, create the rng from the combination of OS randomness and user entropy
-        let mut rng = {
-            use rand::{Rng, SeedableRng};
-            use rand_chacha::ChaChaRng;
-            use std::convert::TryInto;
-
-            match rand_source {
-                RandomSource::Entropy(e) => {
-                    let mut system_rng = rand::rngs::OsRng;
-                    let mut h = Blake2b512::new();
-
-                    // Gather 1024 bytes of entropy from the system
-                    for _ in 0..1024 {
-                        let r: u8 = system_rng.gen();
-                        h.update(&[r]);
-                    }
-
-                    // Hash it all up to make a seed
-                    h.update(e.as_bytes());
-                    let digest = h.finalize();
-
-                    ChaChaRng::from_seed(digest[0..32].try_into().unwrap())
-                }
-                RandomSource::Seed(s) => ChaChaRng::from_seed(*s),
-            }
-        };
+    pub fn contribute_masp<W: Write>(challenge_reader: &[u8], response_writer: W, rand_source: &RandomSource) {
+        Self::contribute_masp_with_progress(challenge_reader, response_writer, rand_source, |_, _| {})
+    }
+
+    /// Same as [`Computation::contribute_masp`], calling `progress` with the number of completed steps and the
+    /// total number of steps ([`MASP_CONTRIBUTION_STEPS`]) at the end of each step of the computation
+    pub fn contribute_masp_with_progress<W: Write, F: FnMut(u64, u64)>(
+        challenge_reader: &[u8],
+        mut response_writer: W,
+        rand_source: &RandomSource,
+        mut progress: F,
+    ) {
+        let mut rng = rand_source.rng();
 
         let mut masp_challenge_reader = &challenge_reader[64..];
         //
@@ -206,6 +265,7 @@ impl Computation {
         let spend_hash = spend_params.contribute(&mut rng, &progress_update_interval);
         debug!("MASP Spend hash is {}", pretty_hash!(&spend_hash));
         trace!("Contributed to MASP Spend!");
+        progress(1, MASP_CONTRIBUTION_STEPS);
 
         //
         // MASP Output circuit
@@ -220,6 +280,7 @@ impl Computation {
         let output_hash = output_params.contribute(&mut rng, &progress_update_interval);
         debug!("MASP Output hash is {}", pretty_hash!(&output_hash));
         trace!("Contributed to MASP Output!");
+        progress(2, MASP_CONTRIBUTION_STEPS);
 
         //
         // MASP Convert circuit
@@ -233,6 +294,7 @@ impl Computation {
         let convert_hash = convert_params.contribute(&mut rng, &progress_update_interval);
         debug!("MASP Convert hash is {}", pretty_hash!(&convert_hash));
         trace!("Contributed to MASP Convert!");
+        progress(3, MASP_CONTRIBUTION_STEPS);
 
         let mut h = Blake2b512::new();
         h.update(&spend_hash);
@@ -247,11 +309,13 @@ impl Computation {
         spend_params
             .write(&mut response_writer)
             .expect("failed to write updated MASP Spend parameters");
+        progress(4, MASP_CONTRIBUTION_STEPS);
 
         trace!("Writing MASP Output parameters to file...");
         output_params
             .write(&mut response_writer)
             .expect("failed to write updated MASP Output parameters");
+        progress(5, MASP_CONTRIBUTION_STEPS);
 
         trace!("Writing MASP Convert parameters to file...");
         convert_params
@@ -259,38 +323,119 @@ impl Computation {
             .expect("failed to write updated MASP Convert parameters");
 
         response_writer.flush().unwrap();
+        progress(6, MASP_CONTRIBUTION_STEPS);
     }
 
-    #[cfg(debug_assertions)]
-    pub fn contribute_test_masp<W: Write>(challenge_reader: &[u8], mut response_writer: W, rand_source: &RandomSource) {
-        // Create an RNG as following:
-        //  - if the user provides a seed, create the rng from that seed
-        //  - if the user provides entropy, create the rng from the combination of OS randomness and user entropy
-        let mut rng = {
-            use rand::{Rng, SeedableRng};
-            use rand_chacha::ChaChaRng;
-            use std::convert::TryInto;
-
-            match rand_source {
-                RandomSource::Entropy(e) => {
-                    let mut system_rng = rand::rngs::OsRng;
-                    let mut h = Blake2b512::new();
-
-                    // Gather 1024 bytes of entropy from the system
-                    for _ in 0..1024 {
-                        let r: u8 = system_rng.gen();
-                        h.update(&[r]);
-                    }
-
-                    // Hash it all up to make a seed
-                    h.update(e.as_bytes());
-                    let digest = h.finalize();
-
-                    ChaChaRng::from_seed(digest[0..32].try_into().unwrap())
-                }
-                RandomSource::Seed(s) => ChaChaRng::from_seed(*s),
+    /// Contributes to the given parameters, dispatching to the contribution to the MASP circuits (the test one in debug
+    /// builds) or to the contribution to the circuits of another ceremony. `progress` is called with the number of
+    /// completed steps and the total number of steps ([`contribution_steps`]) at the end of each step
+    pub fn contribute_parameters_with_progress<W: Write, F: FnMut(u64, u64)>(
+        parameters: CeremonyParameters,
+        challenge_reader: &[u8],
+        response_writer: W,
+        rand_source: &RandomSource,
+        progress: F,
+    ) {
+        match parameters {
+            #[cfg(debug_assertions)]
+            CeremonyParameters::Masp => {
+                Self::contribute_test_masp_with_progress(challenge_reader, response_writer, rand_source, progress)
             }
-        };
+            #[cfg(not(debug_assertions))]
+            CeremonyParameters::Masp => {
+                Self::contribute_masp_with_progress(challenge_reader, response_writer, rand_source, progress)
+            }
+            CeremonyParameters::Circuits(circuits) => Self::contribute_circuits_with_progress(
+                circuits,
+                challenge_reader,
+                response_writer,
+                rand_source,
+                progress,
+            ),
+        }
+    }
+
+    // The [`ContributionFile`] of a ceremony of `circuits` circuits has the following format
+    // | previous_contribution_file_hash (64 bytes) |
+    // | circuit_1_mpc_new_parameters_contribution |
+    // | ... |
+    // | circuit_n_mpc_new_parameters_contribution |
+    /// Contributes to the parameters of the given number of circuits, calling `progress` with the number of completed
+    /// steps and the total number of steps ([`contribution_steps`]) at the end of each step of the computation
+    pub fn contribute_circuits_with_progress<W: Write, F: FnMut(u64, u64)>(
+        circuits: usize,
+        challenge_reader: &[u8],
+        mut response_writer: W,
+        rand_source: &RandomSource,
+        mut progress: F,
+    ) {
+        let mut rng = rand_source.rng();
+        let steps = contribution_steps(CeremonyParameters::Circuits(circuits));
+
+        let mut challenge_reader = &challenge_reader[64..];
+        let mut h = Blake2b512::new();
+        let mut contributed = Vec::with_capacity(circuits);
+        for circuit in 1..=circuits {
+            trace!("Reading circuit {} parameters...", circuit);
+            let mut params = MPCParameters::read(&mut challenge_reader, false)
+                .unwrap_or_else(|_| panic!("unable to read circuit {} params", circuit));
+
+            trace!("Contributing to circuit {}...", circuit);
+            let progress_update_interval: u32 = 0;
+            let hash = params.contribute(&mut rng, &progress_update_interval);
+            debug!("Circuit {} hash is {}", circuit, pretty_hash!(&hash));
+            h.update(&hash);
+            contributed.push(params);
+            progress(circuit as u64, steps);
+        }
+        let h = h.finalize();
+        info!("Contribution hash: 0x{:02x}", h.iter().format(""));
+
+        for (i, params) in contributed.iter().enumerate() {
+            trace!("Writing circuit {} parameters to file...", i + 1);
+            params
+                .write(&mut response_writer)
+                .unwrap_or_else(|_| panic!("failed to write updated circuit {} params", i + 1));
+            progress((circuits + i + 1) as u64, steps);
+        }
+
+        response_writer.flush().unwrap();
+    }
+
+    /// Measures the average time of the multiplication of a G1 point by a scalar, over the given number of samples.
+    /// This is the bulk of the MASP contribution, which rescales the points of the parameters, so that the duration of
+    /// a contribution can be estimated on this machine without computing it.
+    pub fn benchmark_point_multiplication(samples: u32) -> Duration {
+        let samples = samples.max(1);
+        let mut point = G1Projective::generator();
+        let scalar = -Scalar::from(u64::from(samples));
+
+        let start = Instant::now();
+        for _ in 0..samples {
+            point = point * scalar;
+        }
+        let elapsed = start.elapsed();
+        // Use the result, for the multiplications not to be optimized away
+        trace!("Benchmarked point is the identity: {}", bool::from(point.is_identity()));
+
+        elapsed / samples
+    }
+
+    #[cfg(debug_assertions)]
+    pub fn contribute_test_masp<W: Write>(challenge_reader: &[u8], response_writer: W, rand_source: &RandomSource) {
+        Self::contribute_test_masp_with_progress(challenge_reader, response_writer, rand_source, |_, _| {})
+    }
+
+    /// Same as [`Computation::contribute_test_masp`], calling `progress` with the number of completed steps and the
+    /// total number of steps ([`MASP_TEST_CONTRIBUTION_STEPS`]) at the end of each step of the computation
+    #[cfg(debug_assertions)]
+    pub fn contribute_test_masp_with_progress<W: Write, F: FnMut(u64, u64)>(
+        challenge_reader: &[u8],
+        mut response_writer: W,
+        rand_source: &RandomSource,
+        mut progress: F,
+    ) {
+        let mut rng = rand_source.rng();
 
         let mut test_params =
             MPCParameters::read(&challenge_reader[64..], false).expect("unable to read MASP Test params");
@@ -299,6 +444,7 @@ impl Computation {
         let progress_update_interval: u32 = 0;
 
         let test_hash = test_params.contribute(&mut rng, &progress_update_interval);
+        progress(1, MASP_TEST_CONTRIBUTION_STEPS);
 
         let mut h = Blake2b512::new();
         h.update(&test_hash);
@@ -313,6 +459,7 @@ impl Computation {
             .expect("failed to write updated MASP Test parameters");
 
         response_writer.flush().unwrap();
+        progress(2, MASP_TEST_CONTRIBUTION_STEPS);
     }
 }
 
@@ -417,4 +564,94 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_mixed_random_source() {
+        use crate::commands::RandomSource;
+
+        // The OS randomness makes two seeds from the same user entropy differ
+        let entropy = String::from("weak entropy");
+        let first = RandomSource::Mixed(entropy.clone()).seed();
+        let second = RandomSource::Mixed(entropy).seed();
+        assert_ne!(first, second);
+        assert_ne!([0u8; SEED_LENGTH], first);
+
+        // A plain seed is used as is, for reproducible contributions
+        let seed: Seed = [7u8; SEED_LENGTH];
+        assert_eq!(seed, RandomSource::Seed(seed).seed());
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(debug_assertions)]
+    fn test_computation_progress() {
+        use crate::commands::{RandomSource, MASP_TEST_CONTRIBUTION_STEPS};
+
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        // Initialize a challenge for the computation.
+        let mut storage = test_storage(&TEST_ENVIRONMENT_ANOMA);
+        Initialization::run(&TEST_ENVIRONMENT_ANOMA, &mut storage, 0, 0).unwrap();
+        let challenge_locator = Locator::ContributionFile(ContributionLocator::new(1, 0, 0, true));
+        let challenge = storage.reader(&challenge_locator).unwrap().to_vec();
+
+        let mut steps = Vec::new();
+        let mut contribution: Vec<u8> = Vec::new();
+        Computation::contribute_test_masp_with_progress(
+            &challenge,
+            &mut contribution,
+            &RandomSource::Mixed(String::from("entropy")),
+            |completed, total| steps.push((completed, total)),
+        );
+
+        assert!(!contribution.is_empty());
+        assert_eq!(
+            steps,
+            (1..=MASP_TEST_CONTRIBUTION_STEPS)
+                .map(|step| (step, MASP_TEST_CONTRIBUTION_STEPS))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_computation_custom_circuits() {
+        use crate::{
+            commands::{contribution_steps, initialization::TestCircuit, RandomSource},
+            environment::CeremonyParameters,
+        };
+        use bls12_381::Scalar;
+        use masp_phase2::{verify_contribution, MPCParameters};
+
+        // A challenge of two small circuits instead of the MASP ones
+        let parameters = CeremonyParameters::Circuits(2);
+        let circuits = (0..2)
+            .map(|_| MPCParameters::new(TestCircuit { x: Some(Scalar::one()) }).unwrap())
+            .collect::<Vec<_>>();
+        let mut challenge = vec![0u8; 64];
+        for circuit in &circuits {
+            circuit.write(&mut challenge).unwrap();
+        }
+
+        let mut steps = Vec::new();
+        let mut contribution: Vec<u8> = Vec::new();
+        Computation::contribute_parameters_with_progress(
+            parameters,
+            &challenge,
+            &mut contribution,
+            &RandomSource::Mixed(String::from("entropy")),
+            |completed, total| steps.push((completed, total)),
+        );
+
+        // Each circuit is a valid transformation of the challenge one
+        let mut contribution_reader = contribution.as_slice();
+        for circuit in &circuits {
+            let contributed = MPCParameters::read(&mut contribution_reader, true).unwrap();
+            assert!(verify_contribution(circuit, &contributed).is_ok());
+        }
+        assert!(contribution_reader.is_empty());
+
+        let total = contribution_steps(parameters);
+        assert_eq!(4, total);
+        assert_eq!(steps, (1..=total).map(|step| (step, total)).collect::<Vec<_>>());
+    }
 }
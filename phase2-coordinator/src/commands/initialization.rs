@@ -1,5 +1,5 @@
 use crate::{
-    environment::Environment,
+    environment::{CeremonyParameters, Environment},
     storage::{ContributionLocator, Disk, Locator, Object, StorageObject},
     CoordinatorError,
 };
@@ -15,8 +15,8 @@ use masp_phase2::MPCParameters;
 use bellman::{Circuit, ConstraintSystem, SynthesisError};
 use bls12_381::Scalar;
 
-struct TestCircuit {
-    x: Option<Scalar>,
+pub(crate) struct TestCircuit {
+    pub(crate) x: Option<Scalar>,
 }
 impl Circuit<Scalar> for TestCircuit {
     fn synthesize<CS: ConstraintSystem<Scalar>>(self, cs: &mut CS) -> Result<(), SynthesisError> {
@@ -48,7 +48,7 @@ impl Initialization {
         let start = Instant::now();
 
         // Determine the expected challenge size.
-        let expected_challenge_size = Object::anoma_contribution_file_size(0, 0);
+        let expected_challenge_size = Object::ceremony_contribution_file_size(environment, 0, 0)?;
         trace!("Expected challenge file size is {}", expected_challenge_size);
 
         // Initialize and fetch a writer for the contribution locator so the output is saved.
@@ -59,9 +59,9 @@ impl Initialization {
         let settings = environment.parameters();
 
         if let Err(error) = match settings.curve() {
-            CurveKind::Bls12_381 => Self::initialization(storage.writer(&contribution_locator)?.as_mut()),
-            CurveKind::Bls12_377 => Self::initialization(storage.writer(&contribution_locator)?.as_mut()),
-            CurveKind::BW6 => Self::initialization(storage.writer(&contribution_locator)?.as_mut()),
+            CurveKind::Bls12_381 => Self::initialization(environment, storage.writer(&contribution_locator)?.as_mut()),
+            CurveKind::Bls12_377 => Self::initialization(environment, storage.writer(&contribution_locator)?.as_mut()),
+            CurveKind::BW6 => Self::initialization(environment, storage.writer(&contribution_locator)?.as_mut()),
         } {
             error!("Initialization failed with {}", error);
             return Err(CoordinatorError::InitializationFailed.into());
@@ -84,9 +84,56 @@ impl Initialization {
         Ok(hash)
     }
 
-    /// Runs Phase 2 initialization on the given parameters.
+    ///
+    /// Checks that the initialized transcripts of all the chunks are consistent: every
+    /// contribution 0 must start from the blank hash and hash to the same value. Their
+    /// copies for the next round are already compared by [`Self::check_hash`] when each
+    /// chunk is initialized.
+    ///
+    /// Returns the common contribution hash.
+    ///
+    pub(crate) fn check_transcripts(
+        storage: &Disk,
+        round_height: u64,
+        number_of_chunks: u64,
+    ) -> Result<Vec<u8>, CoordinatorError> {
+        let mut expected_hash: Option<Vec<u8>> = None;
+        for chunk_id in 0..number_of_chunks {
+            let current = storage.reader(&Locator::ContributionFile(ContributionLocator::new(
+                round_height,
+                chunk_id,
+                0,
+                true,
+            )))?;
+
+            if !current.as_ref().starts_with(blank_hash().as_slice()) {
+                error!("The transcript of chunk {} doesn't start from the blank hash", chunk_id);
+                return Err(CoordinatorError::InitializationTranscriptsDiffer);
+            }
+
+            let hash = calculate_hash(current.as_ref()).to_vec();
+
+            match &expected_hash {
+                Some(expected_hash) if *expected_hash != hash => {
+                    error!(
+                        "The transcript of chunk {} hashes to {} instead of {}",
+                        chunk_id,
+                        pretty_hash!(&hash),
+                        pretty_hash!(expected_hash)
+                    );
+                    return Err(CoordinatorError::InitializationTranscriptsDiffer);
+                }
+                Some(_) => (),
+                None => expected_hash = Some(hash),
+            }
+        }
+
+        expected_hash.ok_or(CoordinatorError::InitializationTranscriptsDiffer)
+    }
+
+    /// Runs Phase 2 initialization on the parameters of the environment.
     #[inline]
-    fn initialization(mut writer: &mut [u8]) -> Result<(), CoordinatorError> {
+    fn initialization(environment: &Environment, mut writer: &mut [u8]) -> Result<(), CoordinatorError> {
         info!("Initializing Phase 2 Initialization");
         // The initialization contribution file contains [blank_hash, init.params]
         // The circuit parameters are appended to the blank_hash
@@ -102,11 +149,13 @@ impl Initialization {
         //
         // NOTE: Add your MPC Parameters initialization function below
         //
-        #[cfg(debug_assertions)]
-        Self::initialize_test_masp(&mut writer);
-
-        #[cfg(not(debug_assertions))]
-        Self::initialize_masp(&mut writer);
+        match environment.ceremony_parameters() {
+            #[cfg(debug_assertions)]
+            CeremonyParameters::Masp => Self::initialize_test_masp(&mut writer),
+            #[cfg(not(debug_assertions))]
+            CeremonyParameters::Masp => Self::initialize_masp(&mut writer),
+            CeremonyParameters::Circuits(_) => Self::initialize_circuits(environment, &mut writer)?,
+        }
 
         trace!("Completed Phase 2 initialization operation");
 
@@ -206,6 +255,20 @@ impl Initialization {
         writer.flush().unwrap();
     }
 
+    /// Copies the initial parameters of the circuits from the file of the environment.
+    #[inline]
+    fn initialize_circuits(environment: &Environment, mut writer: &mut [u8]) -> Result<(), CoordinatorError> {
+        let path = environment
+            .initial_parameters_path()
+            .ok_or(CoordinatorError::InitialParametersMissing)?;
+        trace!("Copying the initial parameters of the circuits from {}", path);
+
+        writer.write_all(&std::fs::read(path)?)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
     #[inline]
     #[cfg(debug_assertions)]
     fn initialize_test_masp(mut writer: &mut [u8]) {
@@ -226,10 +289,14 @@ impl Initialization {
 #[cfg(test)]
 mod tests {
     use crate::{
-        commands::Initialization,
-        storage::{ContributionLocator, Locator, StorageObject},
+        commands::{initialization::TestCircuit, Initialization},
+        environment::{CeremonyParameters, Environment, Parameters, Testing},
+        storage::{ContributionLocator, Locator, Object, ObjectWriter, StorageObject},
         testing::prelude::*,
+        CoordinatorError,
     };
+    use bls12_381::Scalar;
+    use masp_phase2::MPCParameters;
     use setup_utils::{blank_hash, calculate_hash, GenericArray};
 
     use tracing::{debug, trace};
@@ -300,4 +367,80 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    #[serial]
+    fn test_initialization_transcripts_differ() {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
+
+        let round_height = 0;
+        let number_of_chunks = TEST_ENVIRONMENT_3.number_of_chunks();
+        let mut storage = test_storage(&TEST_ENVIRONMENT_3);
+        for chunk_id in 0..number_of_chunks {
+            Initialization::run(&TEST_ENVIRONMENT_3, &mut storage, round_height, chunk_id).unwrap();
+        }
+        assert!(Initialization::check_transcripts(&storage, round_height, number_of_chunks).is_ok());
+
+        // Corrupt the parameters of another chunk, which now disagrees with the first one
+        let locator = Locator::ContributionFile(ContributionLocator::new(round_height, 1, 0, true));
+        let mut writer = storage.writer(&locator).unwrap();
+        let last = writer.len() - 1;
+        writer[last] ^= 0xff;
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert!(matches!(
+            Initialization::check_transcripts(&storage, round_height, number_of_chunks),
+            Err(CoordinatorError::InitializationTranscriptsDiffer)
+        ));
+
+        // Corrupt the blank hash the transcript of the chunk starts from
+        let locator = Locator::ContributionFile(ContributionLocator::new(round_height, 0, 0, true));
+        let mut writer = storage.writer(&locator).unwrap();
+        writer[0] ^= 0xff;
+        writer.flush().unwrap();
+        drop(writer);
+
+        assert!(matches!(
+            Initialization::check_transcripts(&storage, round_height, number_of_chunks),
+            Err(CoordinatorError::InitializationTranscriptsDiffer)
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_initialization_circuits() {
+        // The initial parameters of two small circuits instead of the MASP ones
+        let mut parameters = Vec::new();
+        for _ in 0..2 {
+            MPCParameters::new(TestCircuit { x: Some(Scalar::one()) })
+                .unwrap()
+                .write(&mut parameters)
+                .unwrap();
+        }
+        let initial_parameters = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(initial_parameters.path(), &parameters).unwrap();
+
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .ceremony_parameters(CeremonyParameters::Circuits(2))
+        .initial_parameters_path(initial_parameters.path().to_str().unwrap())
+        .into();
+        initialize_test_environment(&environment);
+        let mut storage = test_storage(&environment);
+        Initialization::run(&environment, &mut storage, 0, 0).unwrap();
+
+        // The challenge is the blank hash followed by the initial parameters
+        let locator = Locator::ContributionFile(ContributionLocator::new(1, 0, 0, true));
+        let challenge = storage.reader(&locator).unwrap();
+        assert_eq!(
+            Object::ceremony_contribution_file_size(&environment, 1, 0).unwrap(),
+            challenge.len() as u64
+        );
+        assert_eq!(blank_hash().as_slice(), &challenge[..64]);
+        assert_eq!(parameters.as_slice(), &challenge[64..]);
+    }
 }
@@ -12,6 +12,8 @@ pub(crate) use initialization::*;
 #[cfg(any(test, feature = "operator"))]
 pub(crate) mod verification;
 #[cfg(any(test, feature = "operator"))]
+pub use verification::verify_contribution_chain;
+#[cfg(any(test, feature = "operator"))]
 pub(crate) use verification::*;
 
 #[cfg(any(test, feature = "operator"))]
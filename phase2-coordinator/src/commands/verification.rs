@@ -1,16 +1,21 @@
 use crate::{
     authentication::Signature,
     commands::SigningKey,
-    environment::Environment,
+    environment::{CeremonyParameters, Environment},
     storage::{
         ContributionLocator, ContributionSignatureLocator, Disk, Locator, Object, StorageLocator, StorageObject,
     },
     CoordinatorError,
+    VerificationFailureReason,
 };
 use phase2::helpers::CurveKind;
 use setup_utils::{calculate_hash, GenericArray, U64};
 
-use std::{io::Write, sync::Arc, time::Instant};
+use std::{
+    io::{Read, Write},
+    sync::Arc,
+    time::Instant,
+};
 use tracing::{debug, error, info, trace};
 
 use blake2::{Blake2b512, Digest};
@@ -19,6 +24,21 @@ use masp_phase2::{verify_contribution, MPCParameters};
 
 pub(crate) struct Verification;
 
+/// Independently verifies a chain of contributions, e.g. the one of a downloaded transcript, starting with the initial
+/// challenge. Each contribution is checked against the previous one as in [`Verification::run`]: its prelude must be
+/// the hash of the previous contribution and its parameters a valid transformation of the previous ones.
+///
+/// Returns the result of the check of each contribution after the initial challenge, in order.
+pub fn verify_contribution_chain(
+    parameters: CeremonyParameters,
+    contributions: &[&[u8]],
+) -> Vec<Result<(), CoordinatorError>> {
+    contributions
+        .windows(2)
+        .map(|pair| Verification::transform_pok_and_correctness(parameters, pair[0], pair[1]).map(|_| ()))
+        .collect()
+}
+
 impl Verification {
     ///
     /// Runs verification for a given environment, storage,
@@ -152,6 +172,66 @@ impl Verification {
         Ok(())
     }
 
+    ///
+    /// Checks again a verified contribution without writing anything: the contribution must
+    /// still be a valid transformation of the verified contribution it was computed on, and
+    /// its verified copy must still have the hash recorded at its verification, if any.
+    ///
+    pub(crate) fn check(
+        environment: &Environment,
+        storage: &Disk,
+        round_height: u64,
+        chunk_id: u64,
+        contribution_id: u64,
+        is_final_contribution: bool,
+        verified_hash: Option<&str>,
+    ) -> Result<(), CoordinatorError> {
+        let challenge_locator = Locator::ContributionFile(ContributionLocator::new(
+            round_height,
+            chunk_id,
+            contribution_id - 1,
+            true,
+        ));
+        let response_locator =
+            Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, contribution_id, false));
+        let verified_locator = match is_final_contribution {
+            true => Locator::ContributionFile(ContributionLocator::new(round_height + 1, chunk_id, 0, true)),
+            false => Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, contribution_id, true)),
+        };
+        if [&challenge_locator, &response_locator, &verified_locator]
+            .iter()
+            .any(|locator| !storage.exists(locator))
+        {
+            return Err(CoordinatorError::ContributionLocatorMissing);
+        }
+
+        let response_reader = match storage.reader(&response_locator) {
+            Ok(response_reader) => response_reader,
+            Err(CoordinatorError::ContributionFileSizeMismatch) => {
+                return Err(VerificationFailureReason::SizeMismatch.into());
+            }
+            Err(error) => return Err(error),
+        };
+        Self::transform_pok_and_correctness(
+            environment.ceremony_parameters(),
+            storage.reader(&challenge_locator)?.as_ref(),
+            response_reader.as_ref(),
+        )?;
+
+        if let Some(verified_hash) = verified_hash {
+            let hash = hex::encode(calculate_hash(storage.reader(&verified_locator)?.as_ref()));
+            if hash != verified_hash {
+                error!(
+                    "Verified contribution {} of round {} chunk {} has hash {}, but {} was recorded at verification",
+                    contribution_id, round_height, chunk_id, hash, verified_hash
+                );
+                return Err(CoordinatorError::ContributionHashMismatch);
+            }
+        }
+
+        Ok(())
+    }
+
     #[inline]
     fn verification(
         environment: &Environment,
@@ -168,27 +248,41 @@ impl Verification {
             return Err(CoordinatorError::ContributionLocatorMissing);
         }
 
+        // Check that the response has the expected size, the storage reader checks it against the locator.
+        let response_reader = match storage.reader(&response_locator) {
+            Ok(response_reader) => response_reader,
+            Err(CoordinatorError::ContributionFileSizeMismatch) => {
+                error!("The response file doesn't have the expected size");
+                return Err(VerificationFailureReason::SizeMismatch.into());
+            }
+            Err(error) => return Err(error),
+        };
+
         // Execute ceremony verification on chunk.
         let settings = environment.parameters();
+        let parameters = environment.ceremony_parameters();
         let result = match settings.curve() {
             CurveKind::Bls12_381 => Self::transform_pok_and_correctness(
+                parameters,
                 storage.reader(&challenge_locator)?.as_ref(),
-                storage.reader(&response_locator)?.as_ref(),
+                response_reader.as_ref(),
             ),
             CurveKind::Bls12_377 => Self::transform_pok_and_correctness(
+                parameters,
                 storage.reader(&challenge_locator)?.as_ref(),
-                storage.reader(&response_locator)?.as_ref(),
+                response_reader.as_ref(),
             ),
             CurveKind::BW6 => Self::transform_pok_and_correctness(
+                parameters,
                 storage.reader(&challenge_locator)?.as_ref(),
-                storage.reader(&response_locator)?.as_ref(),
+                response_reader.as_ref(),
             ),
         };
         let response_hash = match result {
             Ok(response_hash) => response_hash,
             Err(error) => {
                 error!("Verification failed with {}", error);
-                return Err(CoordinatorError::VerificationFailed.into());
+                return Err(error);
             }
         };
 
@@ -213,7 +307,7 @@ impl Verification {
             if !storage.exists(&next_challenge_locator) {
                 storage.initialize(
                     next_challenge_locator.clone(),
-                    Object::anoma_contribution_file_size(round_height, contribution_id),
+                    Object::ceremony_contribution_file_size(environment, round_height, contribution_id)?,
                 )?;
             }
 
@@ -264,6 +358,7 @@ impl Verification {
 
     #[inline]
     fn transform_pok_and_correctness(
+        parameters: CeremonyParameters,
         challenge_reader: &[u8],
         response_reader: &[u8],
     ) -> Result<GenericArray<u8, U64>, CoordinatorError> {
@@ -286,7 +381,7 @@ impl Verification {
                 true => challenge_hash,
                 false => {
                     error!("Challenge hash does not match saved challenge hash.");
-                    return Err(CoordinatorError::ContributionHashMismatch);
+                    return Err(VerificationFailureReason::ChallengeHashMismatch.into());
                 }
             }
         };
@@ -303,66 +398,78 @@ impl Verification {
 
         trace!("Starting verification");
 
-        #[cfg(debug_assertions)]
-        Self::verify_test_masp(&challenge_reader, &response_reader);
-
-        #[cfg(not(debug_assertions))]
-        Self::verify_masp(&challenge_reader, &response_reader);
+        match parameters {
+            #[cfg(debug_assertions)]
+            CeremonyParameters::Masp => Self::verify_test_masp(&challenge_reader, &response_reader)?,
+            #[cfg(not(debug_assertions))]
+            CeremonyParameters::Masp => Self::verify_masp(&challenge_reader, &response_reader)?,
+            CeremonyParameters::Circuits(circuits) => {
+                Self::verify_circuits(circuits, &challenge_reader, &response_reader)?
+            }
+        }
 
         trace!("Completed verification");
 
         Ok(response_hash)
     }
 
+    /// Reads the MASP parameters with the given name, failing with [`VerificationFailureReason::MalformedParameters`].
+    #[inline]
+    fn read_masp_parameters<R: Read>(reader: R, checked: bool, name: &str) -> Result<MPCParameters, CoordinatorError> {
+        MPCParameters::read(reader, checked).map_err(|error| {
+            error!("Couldn't deserialize the MASP {} parameters: {}", name, error);
+            VerificationFailureReason::MalformedParameters.into()
+        })
+    }
+
+    /// Verifies the MASP parameters transformation with the given name, failing with
+    /// [`VerificationFailureReason::InvalidTransformation`].
+    #[inline]
+    fn verify_masp_transformation(
+        parameters: &MPCParameters,
+        new_parameters: &MPCParameters,
+        name: &str,
+    ) -> Result<[u8; 64], CoordinatorError> {
+        verify_contribution(parameters, new_parameters).map_err(|_| {
+            error!("Invalid MASP {} transformation", name);
+            VerificationFailureReason::InvalidTransformation.into()
+        })
+    }
+
     #[inline]
     #[cfg(not(debug_assertions))]
-    fn verify_masp(challenge_reader: &[u8], response_reader: &[u8]) {
+    fn verify_masp(challenge_reader: &[u8], response_reader: &[u8]) -> Result<(), CoordinatorError> {
         trace!("Reading MASP Spend old parameters...");
         let mut masp_challenge_reader = &challenge_reader[64..];
         let mut masp_response_reader = &response_reader[64..];
 
-        let masp_spend =
-            MPCParameters::read(&mut masp_challenge_reader, false).expect("couldn't deserialize MASP Spend params");
+        let masp_spend = Self::read_masp_parameters(&mut masp_challenge_reader, false, "Spend")?;
 
         trace!("Reading MASP Output old parameters...");
-        let masp_output =
-            MPCParameters::read(&mut masp_challenge_reader, false).expect("couldn't deserialize MASP Output params");
+        let masp_output = Self::read_masp_parameters(&mut masp_challenge_reader, false, "Output")?;
 
         trace!("Reading MASP Convert old parameters...");
-        let masp_convert =
-            MPCParameters::read(&mut masp_challenge_reader, false).expect("couldn't deserialize MASP Convert params");
+        let masp_convert = Self::read_masp_parameters(&mut masp_challenge_reader, false, "Convert")?;
 
         trace!("Reading MASP Spend new parameters...");
-        let new_masp_spend =
-            MPCParameters::read(&mut masp_response_reader, true).expect("couldn't deserialize MASP Spend new_params");
+        let new_masp_spend = Self::read_masp_parameters(&mut masp_response_reader, true, "Spend")?;
 
         trace!("Reading MASP Output new parameters...");
-        let new_masp_output =
-            MPCParameters::read(&mut masp_response_reader, true).expect("couldn't deserialize MASP Output new_params");
+        let new_masp_output = Self::read_masp_parameters(&mut masp_response_reader, true, "Output")?;
 
         trace!("Reading MASP Convert new parameters...");
-        let new_masp_convert =
-            MPCParameters::read(&mut masp_response_reader, true).expect("couldn't deserialize MASP Convert new_params");
+        let new_masp_convert = Self::read_masp_parameters(&mut masp_response_reader, true, "Convert")?;
 
         trace!("Verifying MASP Spend...");
-        let spend_hash = match verify_contribution(&masp_spend, &new_masp_spend) {
-            Ok(hash) => hash,
-            Err(_) => panic!("invalid MASP Spend transformation!"),
-        };
+        let spend_hash = Self::verify_masp_transformation(&masp_spend, &new_masp_spend, "Spend")?;
         debug!("MASP Spend hash is {}", pretty_hash!(&spend_hash));
 
         trace!("Verifying MASP Output...");
-        let output_hash = match verify_contribution(&masp_output, &new_masp_output) {
-            Ok(hash) => hash,
-            Err(_) => panic!("invalid MASP Output transformation!"),
-        };
+        let output_hash = Self::verify_masp_transformation(&masp_output, &new_masp_output, "Output")?;
         debug!("MASP Output hash is {}", pretty_hash!(&output_hash));
 
         trace!("Verifying MASP Convert...");
-        let convert_hash = match verify_contribution(&masp_convert, &new_masp_convert) {
-            Ok(hash) => hash,
-            Err(_) => panic!("invalid MASP Convert transformation!"),
-        };
+        let convert_hash = Self::verify_masp_transformation(&masp_convert, &new_masp_convert, "Convert")?;
         debug!("MASP Convert hash is {}", pretty_hash!(&convert_hash));
 
         let mut h = Blake2b512::new();
@@ -373,27 +480,63 @@ impl Verification {
 
         info!("Verification hash: 0x{:02x}", h.iter().format(""));
         debug!("MASP Contribution hash is {}", pretty_hash!(&h));
+
+        Ok(())
     }
 
     #[inline]
     #[cfg(debug_assertions)]
-    fn verify_test_masp(challenge_reader: &[u8], response_reader: &[u8]) {
-        let masp_test =
-            MPCParameters::read(&challenge_reader[64..], false).expect("couldn't deserialize MASP Test params");
+    fn verify_test_masp(challenge_reader: &[u8], response_reader: &[u8]) -> Result<(), CoordinatorError> {
+        let masp_test = Self::read_masp_parameters(&challenge_reader[64..], false, "Test")?;
 
-        let new_masp_test =
-            MPCParameters::read(&response_reader[64..], true).expect("couldn't deserialize MASP Spend new_params");
+        let new_masp_test = Self::read_masp_parameters(&response_reader[64..], true, "Test")?;
 
-        let test_hash = match verify_contribution(&masp_test, &new_masp_test) {
-            Ok(hash) => hash,
-            Err(_) => panic!("invalid MASP Spend transformation!"),
-        };
+        let test_hash = Self::verify_masp_transformation(&masp_test, &new_masp_test, "Test")?;
 
         let mut h = Blake2b512::new();
         h.update(&test_hash);
         let h = h.finalize();
 
         debug!("Verification hash: 0x{:02x}", h.iter().format(""));
+
+        Ok(())
+    }
+
+    #[inline]
+    fn verify_circuits(
+        circuits: usize,
+        challenge_reader: &[u8],
+        response_reader: &[u8],
+    ) -> Result<(), CoordinatorError> {
+        let mut challenge_reader = &challenge_reader[64..];
+        let mut response_reader = &response_reader[64..];
+
+        let mut parameters = Vec::with_capacity(circuits);
+        for circuit in 1..=circuits {
+            trace!("Reading circuit {} old parameters...", circuit);
+            parameters.push(Self::read_masp_parameters(
+                &mut challenge_reader,
+                false,
+                &format!("circuit {}", circuit),
+            )?);
+        }
+
+        let mut h = Blake2b512::new();
+        for (i, parameters) in parameters.iter().enumerate() {
+            let name = format!("circuit {}", i + 1);
+            trace!("Reading {} new parameters...", name);
+            let new_parameters = Self::read_masp_parameters(&mut response_reader, true, &name)?;
+
+            trace!("Verifying {}...", name);
+            let hash = Self::verify_masp_transformation(parameters, &new_parameters, &name)?;
+            debug!("The {} hash is {}", name, pretty_hash!(&hash));
+            h.update(&hash);
+        }
+        let h = h.finalize();
+
+        info!("Verification hash: 0x{:02x}", h.iter().format(""));
+
+        Ok(())
     }
 
     #[inline]
@@ -416,16 +559,173 @@ mod tests {
 
     use crate::{
         authentication::Dummy,
-        commands::{Computation, Seed, Verification, SEED_LENGTH},
-        storage::{ContributionLocator, ContributionSignatureLocator, Locator, Object},
+        commands::{
+            verify_contribution_chain,
+            Computation,
+            Initialization,
+            RandomSource,
+            Seed,
+            Verification,
+            SEED_LENGTH,
+        },
+        environment::CeremonyParameters,
+        storage::{ContributionLocator, ContributionSignatureLocator, Locator, Object, StorageObject},
         testing::prelude::*,
         Coordinator,
+        CoordinatorError,
+        VerificationFailureReason,
     };
 
     use once_cell::sync::Lazy;
     use rand::RngCore;
+    use setup_utils::calculate_hash;
     use time::OffsetDateTime;
 
+    /// Computes the contribution to chunk 0 of round 1, replaces it with the output of `tamper`, given the challenge
+    /// and the contribution, and returns the result of its verification.
+    fn verify_tampered_contribution(tamper: impl FnOnce(&[u8], &mut Vec<u8>)) -> Result<(), CoordinatorError> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy)).unwrap();
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let signing_key = "secret_key".to_string();
+
+        coordinator.run_initialization(OffsetDateTime::now_utc()).unwrap();
+        coordinator.next_round(*TEST_STARTED_AT, vec![contributor]).unwrap();
+        let round_height = coordinator.current_round_height().unwrap();
+
+        let challenge_locator = Locator::ContributionFile(ContributionLocator::new(round_height, 0, 0, true));
+        let response_locator = Locator::ContributionFile(ContributionLocator::new(round_height, 0, 1, false));
+        let contribution_file_signature_locator =
+            Locator::ContributionFileSignature(ContributionSignatureLocator::new(round_height, 0, 1, false));
+
+        let signature = coordinator.signature();
+        let storage = coordinator.storage_mut();
+        storage
+            .initialize(
+                response_locator.clone(),
+                Object::anoma_contribution_file_size(round_height, 1),
+            )
+            .unwrap();
+        storage
+            .initialize(
+                contribution_file_signature_locator.clone(),
+                Object::contribution_file_signature_size(false),
+            )
+            .unwrap();
+
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        Computation::run(
+            &TEST_ENVIRONMENT_ANOMA,
+            storage,
+            signature.clone(),
+            &signing_key,
+            &challenge_locator,
+            &response_locator,
+            &contribution_file_signature_locator,
+            &seed,
+        )
+        .unwrap();
+
+        let challenge = storage.reader(&challenge_locator).unwrap().to_vec();
+        let mut response = storage.reader(&response_locator).unwrap().to_vec();
+        tamper(&challenge, &mut response);
+        storage
+            .update(&response_locator, Object::ContributionFile(response))
+            .unwrap();
+
+        Verification::run(
+            &TEST_ENVIRONMENT_ANOMA,
+            storage,
+            signature,
+            &signing_key,
+            round_height,
+            0,
+            1,
+            true,
+        )
+    }
+
+    fn assert_failure_reason(result: Result<(), CoordinatorError>, expected: VerificationFailureReason) {
+        match result {
+            Err(CoordinatorError::ContributionVerificationFailed { reason }) => assert_eq!(expected, reason),
+            result => panic!("Expected verification to fail with {:?}, got {:?}", expected, result),
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_verification_challenge_hash_mismatch() {
+        let result = verify_tampered_contribution(|_, response| response[0] ^= 1);
+        assert_failure_reason(result, VerificationFailureReason::ChallengeHashMismatch);
+    }
+
+    #[test]
+    #[serial]
+    fn test_verification_size_mismatch() {
+        let result = verify_tampered_contribution(|_, response| {
+            response.pop();
+        });
+        assert_failure_reason(result, VerificationFailureReason::SizeMismatch);
+    }
+
+    #[test]
+    #[serial]
+    fn test_verification_malformed_parameters() {
+        let result = verify_tampered_contribution(|_, response| {
+            response[64..].iter_mut().for_each(|byte| *byte = 0xff);
+        });
+        assert_failure_reason(result, VerificationFailureReason::MalformedParameters);
+    }
+
+    #[test]
+    #[serial]
+    fn test_verification_invalid_transformation() {
+        // The challenge parameters themselves, without any new contribution
+        let result = verify_tampered_contribution(|challenge, response| {
+            let size = response.len();
+            *response = calculate_hash(challenge).to_vec();
+            response.extend_from_slice(&challenge[64..]);
+            response.resize(size, 0);
+        });
+        assert_failure_reason(result, VerificationFailureReason::InvalidTransformation);
+    }
+
+    #[test]
+    #[serial]
+    #[cfg(debug_assertions)]
+    fn test_verify_contribution_chain() {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut storage = test_storage(&TEST_ENVIRONMENT_ANOMA);
+        Initialization::run(&TEST_ENVIRONMENT_ANOMA, &mut storage, 0, 0).unwrap();
+        let challenge_locator = Locator::ContributionFile(ContributionLocator::new(1, 0, 0, true));
+        let challenge = storage.reader(&challenge_locator).unwrap().to_vec();
+
+        // The contribution files start with the hash of the challenge they've been computed against
+        let contribute = |previous: &[u8]| {
+            let mut contribution = calculate_hash(previous).to_vec();
+            Computation::contribute_test_masp(previous, &mut contribution, &RandomSource::Seed([1; SEED_LENGTH]));
+            contribution
+        };
+        let first = contribute(&challenge);
+        let second = contribute(&first);
+
+        let results = verify_contribution_chain(CeremonyParameters::Masp, &[&challenge, &first, &second]);
+        assert_eq!(2, results.len());
+        assert!(results.iter().all(Result::is_ok));
+
+        // A contribution skipping the previous one breaks the chain at its index only
+        let skipping = contribute(&challenge);
+        let results = verify_contribution_chain(CeremonyParameters::Masp, &[&challenge, &first, &skipping]);
+        assert!(results[0].is_ok());
+        assert_failure_reason(
+            results.into_iter().nth(1).unwrap(),
+            VerificationFailureReason::ChallengeHashMismatch,
+        );
+    }
+
     #[test]
     #[serial]
     fn test_verification_run() {
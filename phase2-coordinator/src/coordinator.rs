@@ -7,36 +7,51 @@ use crate::{
     commands::{Aggregation, Initialization},
     coordinator_state::{
         CeremonyStorageAction, CoordinatorState, DropParticipant, ParticipantInfo, ResetCurrentRoundStorageAction,
-        RoundMetrics, IP_BAN, TOKEN_BLACKLIST,
+        RoundMetrics, VerificationTiming, IP_BAN, TOKEN_BLACKLIST,
     },
-    environment::{Deployment, Environment},
+    environment::{CeremonyParameters, Deployment, Environment, RoundBudgetPolicy},
     objects::{
-        participant::*, task::TaskInitializationError, ContributionFileSignature, ContributionInfo, LockedLocators,
-        Round, Task, TrimmedContributionInfo,
+        hash_email, participant::*, task::TaskInitializationError, ClientProvenance, ContributionFileSignature,
+        ContributionInfo, ContributionProvenance, ContributionReceipt, LockedLocators, Round, Task,
+        TrimmedContributionInfo,
     },
     storage::{
         ContributionLocator, ContributionSignatureLocator, Disk, Locator, LocatorPath, Object, StorageAction,
         StorageLocator, StorageObject, UpdateAction,
     },
+    webhook::{self, RoundTransitionEvent},
 };
+use blake2::{Blake2b512, Digest};
+use serde::{Deserialize, Serialize};
 use setup_utils::calculate_hash;
 
 use std::{
-    collections::HashSet,
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
     fmt,
     net::IpAddr,
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
 };
 use time::OffsetDateTime;
 use tracing::*;
 
-#[cfg(any(test, feature = "operator"))]
-use std::collections::HashMap;
+/// How long the outcome of a successful verification is remembered by the coordinator.
+const VERIFICATION_CACHE_TTL: time::Duration = time::Duration::minutes(10);
+/// Number of past contributions over which the queue drain rate is averaged.
+const QUEUE_DRAIN_WINDOW: usize = 10;
+/// Upper bounds, in seconds, of the buckets of the [`TimingHistogram`]. Longer durations fall in a last unbounded
+/// bucket.
+pub const TIMING_HISTOGRAM_BOUNDS: [u64; 6] = [60, 300, 600, 1200, 1800, 3600];
 
 #[derive(Debug)]
 pub enum CoordinatorError {
     AggregateContributionFileSizeMismatch,
+    CeremonyClosed,
     CeremonyIsOver,
+    CeremonyPaused,
     ChallengeHashSizeInvalid,
     ChunkAlreadyComplete,
     ChunkAlreadyVerified,
@@ -63,6 +78,7 @@ pub enum CoordinatorError {
     ContributionIdIsNonzero,
     ContributionIdMismatch,
     ContributionIdMustBeNonzero,
+    ContributionIntegrityMismatch { chunk_id: u64 },
     ContributionLocatorAlreadyExists,
     ContributionLocatorIncorrect,
     ContributionLocatorMissing,
@@ -72,7 +88,11 @@ pub enum CoordinatorError {
     ContributionMissingVerifier,
     ContributionShouldNotExist,
     ContributionSignatureFileSizeMismatch,
+    ContributionSignatureMissing,
     ContributionSignatureSizeMismatch,
+    ContributionStateMismatch,
+    ContributionUploadGap { expected: u64, found: u64 },
+    ContributionVerificationFailed { reason: VerificationFailureReason },
     ContributionsComplete,
     ContributorAlreadyContributed,
     ContributorSignatureInvalid,
@@ -85,9 +105,11 @@ pub enum CoordinatorError {
     CurrentRoundNotAggregated,
     CurrentRoundNotFinished,
     DropParticipantFailed,
+    DuplicateContribution { round_height: u64 },
     ExpectedContributor,
     ExpectedVerifier,
     Error(anyhow::Error),
+    InitialParametersMissing,
     InitializationFailed,
     InitializationTranscriptsDiffer,
     Integer(std::num::ParseIntError),
@@ -108,6 +130,7 @@ pub enum CoordinatorError {
     NextChallengeHashMissing,
     NextRoundAlreadyInPrecommit,
     NextRoundShouldBeEmpty,
+    NotYourTurn,
     NumberOfChunksInvalid,
     NumberOfContributionsDiffer,
     ParticipantAlreadyAdded,
@@ -131,6 +154,7 @@ pub enum CoordinatorError {
     ParticipantHasNoRemainingTasks,
     ParticipantHasRemainingTasks,
     ParticipantInCurrentRoundCannotJoinQueue,
+    ParticipantInvalid(String),
     ParticipantIpAlreadyAdded,
     ParticipantLockedChunkWithManyContributions,
     ParticipantMissing,
@@ -142,6 +166,7 @@ pub enum CoordinatorError {
     ParticipantRoundHeightMissing,
     ParticipantShouldHavePendingTasks,
     ParticipantShouldNotBeFinished,
+    ParticipantStale,
     ParticipantStillHasLock,
     ParticipantStillHasLocks,
     ParticipantStillHasTaskAsAssigned,
@@ -153,8 +178,10 @@ pub enum CoordinatorError {
     Phase2Setup(setup_utils::Error),
     QueueIsEmpty,
     QueueWaitTimeIncomplete,
+    ReplayedRequest,
     ResponseHashSizeInvalid,
     RoundAggregationFailed,
+    RoundAggregationRescheduled,
     RoundAlreadyInitialized,
     RoundAlreadyAggregated,
     RoundCommitFailedOrCorrupted,
@@ -165,6 +192,8 @@ pub enum CoordinatorError {
     RoundDoesNotExist,
     RoundFileMissing,
     RoundFileSizeMismatch,
+    RoundHasContributions,
+    RoundHasLockedChunks,
     RoundHeightIsZero,
     RoundHeightMismatch,
     RoundHeightNotSet,
@@ -175,14 +204,19 @@ pub enum CoordinatorError {
     RoundNotReady,
     RoundNumberOfContributorsUnauthorized,
     RoundNumberOfVerifiersUnauthorized,
+    RoundRangeInvalid,
     RoundShouldNotExist,
+    RoundSkipped,
     RoundStateMissing,
+    RoundTransitionInProgress,
     RoundUpdateCorruptedStateOfContributors,
     RoundUpdateCorruptedStateOfVerifiers,
     RoundVerifiersMissing,
     RoundVerifiersNotUnique,
     SignatureSchemeIsInsecure,
     StorageCopyFailed,
+    StorageDecryptionFailed,
+    StorageEncryptionFailed,
     StorageFailed,
     StorageInitializationFailed,
     StorageLocatorAlreadyExists,
@@ -199,13 +233,51 @@ pub enum CoordinatorError {
     TryFromSliceError(std::array::TryFromSliceError),
     UnauthorizedChunkContributor,
     UnauthorizedChunkVerifier,
-    VerificationFailed,
+    VerificationBacklog,
     VerificationOnContributionIdZero,
     VerifierMissing,
     VerifierSignatureInvalid,
     VerifiersMissing,
 }
 
+/// The check of the verification of a contribution which failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VerificationFailureReason {
+    /// The contribution has not been computed on top of the expected challenge.
+    ChallengeHashMismatch,
+    /// The contribution file doesn't have the expected size.
+    SizeMismatch,
+    /// The parameters of the contribution cannot be deserialized.
+    MalformedParameters,
+    /// The contribution is not a valid transformation of the challenge parameters.
+    InvalidTransformation,
+    /// The contribution info is attributed to another participant than the one uploading it.
+    InfoPublicKeyMismatch,
+    /// The signature of the contribution info is not valid for its public key.
+    InfoSignatureInvalid,
+}
+
+impl fmt::Display for VerificationFailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let reason = match self {
+            Self::ChallengeHashMismatch => "the contribution has not been computed on top of the expected challenge",
+            Self::SizeMismatch => "the contribution file doesn't have the expected size",
+            Self::MalformedParameters => "the parameters of the contribution cannot be deserialized",
+            Self::InvalidTransformation => "the contribution is not a valid transformation of the challenge parameters",
+            Self::InfoPublicKeyMismatch => "the contribution info is attributed to another participant",
+            Self::InfoSignatureInvalid => "the signature of the contribution info is not valid",
+        };
+
+        write!(f, "{}", reason)
+    }
+}
+
+impl From<VerificationFailureReason> for CoordinatorError {
+    fn from(reason: VerificationFailureReason) -> Self {
+        Self::ContributionVerificationFailed { reason }
+    }
+}
+
 impl From<TaskInitializationError> for CoordinatorError {
     fn from(error: TaskInitializationError) -> Self {
         Self::TaskInitializationFailed(error)
@@ -271,6 +343,12 @@ impl From<CoordinatorError> for anyhow::Error {
     }
 }
 
+/// Whether a failed aggregation is worth retrying: IO errors are transient, except for missing files which won't
+/// appear on their own.
+fn is_transient_aggregation_error(error: &CoordinatorError) -> bool {
+    matches!(error, CoordinatorError::IOError(error) if error.kind() != std::io::ErrorKind::NotFound)
+}
+
 /// A trait for providing a source of time to the coordinator, used
 /// for mocking system time during testing.
 pub trait TimeSource: Send + Sync {
@@ -327,6 +405,332 @@ impl TimeSource for MockTimeSource {
     }
 }
 
+/// Estimates when the contributor at the given position of the queue starts contributing: the queue
+/// drains by one contributor every average contribution duration from `drain_start`, so the
+/// contributor at position `n` starts `n` contributions later. The estimate is never in the past, as
+/// the current contribution may last longer than the average.
+fn estimate_start_time(
+    drain_start: OffsetDateTime,
+    average_contribution_duration: time::Duration,
+    queue_position: u64,
+    now: OffsetDateTime,
+) -> OffsetDateTime {
+    let start_at = drain_start + average_contribution_duration * queue_position as u32;
+
+    std::cmp::max(start_at, now)
+}
+
+/// Public status of the ceremony.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CeremonyStatus {
+    /// Height of the current round.
+    pub round_height: u64,
+    /// Number of contributors waiting in the queue.
+    pub queue_size: usize,
+    /// Number of contributions awaiting verification.
+    pub verification_backlog: usize,
+    /// Whether the intake of new contributors is paused.
+    pub paused: bool,
+    /// The parameters the ceremony contributes to.
+    #[serde(default)]
+    pub parameters: CeremonyParameters,
+}
+
+/// Transcript of an aggregated round, to audit the chain of its contributions independently of the coordinator.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoundTranscript {
+    /// Height of the round.
+    pub round_height: u64,
+    /// Whether the round was skipped, its chunks then only hold the challenge carried over to the next round.
+    #[serde(default)]
+    pub skipped: bool,
+    /// The parameters the contributions of the round are computed on.
+    #[serde(default)]
+    pub parameters: CeremonyParameters,
+    /// The chain of contributions of each chunk, by increasing chunk id.
+    pub chunks: Vec<ChunkTranscript>,
+}
+
+/// Chain of the verified contributions of a chunk in a round.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChunkTranscript {
+    pub chunk_id: u64,
+    /// Hex encoded contribution files, from the challenge of the round to its final contribution.
+    pub contributions: Vec<String>,
+}
+
+/// Report on whether the current round can be aggregated and the ceremony advanced to the next round.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoundReadiness {
+    /// Height of the current round.
+    pub round_height: u64,
+    /// Chunks which are locked or miss some contributions or verifications.
+    pub incomplete_chunks: Vec<u64>,
+    /// Chunks whose final unverified contribution or next challenge is missing from storage.
+    pub missing_locators: Vec<u64>,
+    /// Number of contributions awaiting verification.
+    pub pending_verifications: usize,
+    /// Number of participants still contributing to the round.
+    pub current_contributors: usize,
+    /// Whether the round has already been aggregated.
+    pub is_aggregated: bool,
+}
+
+impl RoundReadiness {
+    ///
+    /// Returns `true` if nothing blocks the transition to the next round.
+    ///
+    pub fn is_ready(&self) -> bool {
+        self.incomplete_chunks.is_empty()
+            && self.missing_locators.is_empty()
+            && self.pending_verifications == 0
+            && self.current_contributors == 0
+            && !self.is_aggregated
+    }
+}
+
+/// Report on the verification of all the contributions of a completed round, run again on demand.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RoundReverification {
+    /// Height of the round.
+    pub round_height: u64,
+    /// Number of contributions verified again.
+    pub checked: usize,
+    /// The contributions which failed the verification.
+    pub failures: Vec<ReverificationFailure>,
+}
+
+impl RoundReverification {
+    ///
+    /// Returns `true` if every contribution of the round verified again.
+    ///
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// A contribution which failed to verify again.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReverificationFailure {
+    pub chunk_id: u64,
+    pub contribution_id: u64,
+    /// The error the verification failed with.
+    pub reason: String,
+    /// Whether the verified copy of the contribution has been rewritten, and now verifies.
+    pub repaired: bool,
+}
+
+/// A cohort of the ceremony, in which only the holders of its tokens can join the queue.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Cohort {
+    /// Number of the cohort, starting from 1.
+    pub index: u64,
+    /// Time at which the cohort opens.
+    #[serde(with = "time::serde::timestamp")]
+    pub start: OffsetDateTime,
+    /// Time at which the cohort closes and the next one opens.
+    #[serde(with = "time::serde::timestamp")]
+    pub end: OffsetDateTime,
+    /// Number of tokens of the cohort, i.e. the maximum number of contributors joining the ceremony in it.
+    pub capacity: usize,
+}
+
+/// Analytics of the queue of the ceremony, for the operators.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct QueueAnalytics {
+    /// Number of contributors who joined the queue since the start of the ceremony.
+    pub joined: usize,
+    /// Number of contributors who joined the queue in the last hour.
+    pub joined_last_hour: usize,
+    /// Number of contributors currently waiting in the queue.
+    pub waiting: usize,
+    /// Number of contributors currently contributing to the round.
+    pub contributing: usize,
+    /// Number of contributors who completed their contribution.
+    pub completed: usize,
+    /// Number of contributors dropped from the queue or the round before completing their contribution.
+    pub dropped: usize,
+    /// Share of the dropped contributors among the ones who completed or dropped out, between 0 and 1.
+    pub dropout_rate: f64,
+    /// Average time, in seconds, between joining the queue and starting to contribute, if any contributor started.
+    pub average_wait_seconds: Option<i64>,
+    /// Number of the current cohort, starting from 1.
+    pub cohort: u64,
+    /// Number of tokens of the current cohort.
+    pub cohort_capacity: usize,
+    /// Number of tokens of the current cohort already used to join the queue.
+    pub cohort_fill: usize,
+}
+
+/// A bucket of a [`TimingHistogram`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimingBucket {
+    /// Upper bound, in seconds, of the durations counted in the bucket, `None` for the last unbounded bucket.
+    pub up_to_seconds: Option<u64>,
+    /// Number of contributions whose duration falls in the bucket.
+    pub count: usize,
+}
+
+/// Histogram of the durations of the contributions, measured by the coordinator between the lock of a chunk and the
+/// reception of its contribution, for the operators.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimingHistogram {
+    /// Number of measured contributions.
+    pub count: usize,
+    /// The buckets, in increasing order of duration, see [`TIMING_HISTOGRAM_BOUNDS`].
+    pub buckets: Vec<TimingBucket>,
+}
+
+impl TimingHistogram {
+    ///
+    /// Returns the histogram of the given durations, in seconds.
+    ///
+    pub fn new(durations: &[u64]) -> Self {
+        let mut buckets: Vec<TimingBucket> = TIMING_HISTOGRAM_BOUNDS
+            .iter()
+            .map(|bound| Some(*bound))
+            .chain(std::iter::once(None))
+            .map(|up_to_seconds| TimingBucket {
+                up_to_seconds,
+                count: 0,
+            })
+            .collect();
+
+        for duration in durations {
+            let index = TIMING_HISTOGRAM_BOUNDS
+                .iter()
+                .position(|bound| duration <= bound)
+                .unwrap_or(TIMING_HISTOGRAM_BOUNDS.len());
+            buckets[index].count += 1;
+        }
+
+        Self {
+            count: durations.len(),
+            buckets,
+        }
+    }
+}
+
+/// Statistics of the wall times of the verifications of the contributions, for the operators to tell the cost of the
+/// verification from the one of the contribution.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VerificationTimingStats {
+    /// Number of measured verifications.
+    pub count: usize,
+    /// Shortest verification, in microseconds.
+    pub min_micros: u64,
+    /// Mean duration of the verifications, in microseconds.
+    pub mean_micros: u64,
+    /// Longest verification, in microseconds.
+    pub max_micros: u64,
+}
+
+impl VerificationTimingStats {
+    ///
+    /// Returns the statistics of the given verification timings.
+    ///
+    pub fn new(timings: &[VerificationTiming]) -> Self {
+        let durations = timings.iter().map(|timing| timing.duration_micros);
+        match (durations.clone().min(), durations.clone().max()) {
+            (Some(min_micros), Some(max_micros)) => Self {
+                count: timings.len(),
+                min_micros,
+                mean_micros: durations.sum::<u64>() / timings.len() as u64,
+                max_micros,
+            },
+            _ => Self::default(),
+        }
+    }
+}
+
+/// The timings of the ceremony measured by the coordinator, for the operators.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CeremonyTimings {
+    /// Histogram of the durations of the contributions.
+    pub contributions: TimingHistogram,
+    /// Statistics of the durations of the verifications.
+    pub verifications: VerificationTimingStats,
+}
+
+/// A contributor waiting in the queue, as reported in the [`CeremonyDump`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueuedParticipant {
+    pub participant: Participant,
+    /// The reliability score of the contributor.
+    pub reliability: u8,
+    /// The round the contributor is assigned to, once the queue has been updated.
+    pub assigned_round: Option<u64>,
+    /// The time the contributor was last seen.
+    pub last_seen: OffsetDateTime,
+    /// The time the contributor joined the queue.
+    pub joined: OffsetDateTime,
+    /// Whether the contributor joined the queue with a priority token.
+    pub priority: bool,
+}
+
+/// Snapshot of the whole state of the participants of the ceremony, for the operators to debug a stuck ceremony.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CeremonyDump {
+    /// Height of the current round, if the ceremony has started.
+    pub round_height: Option<u64>,
+    /// Whether the intake of new contributors is paused.
+    pub paused: bool,
+    /// Whether the transition to the next round is held manually.
+    pub manual_lock: bool,
+    /// The contributors waiting in the queue, in the order they will be served.
+    pub queue: Vec<QueuedParticipant>,
+    /// The participants precommitted to the next round.
+    pub next: Vec<ParticipantInfo>,
+    /// The contributors of the current round, with their locks and tasks.
+    pub current_contributors: Vec<ParticipantInfo>,
+    /// The verifiers of the current round, with their locks and tasks.
+    pub current_verifiers: Vec<ParticipantInfo>,
+    /// The tasks of the current round pending verification, with the contributor who computed them.
+    pub pending_verifications: Vec<(Task, Participant)>,
+    /// The contributors who finished each round, by round height.
+    pub finished_contributors: HashMap<u64, Vec<ParticipantInfo>>,
+    /// The participants dropped from the current and past rounds.
+    pub dropped: Vec<ParticipantInfo>,
+    /// The participants banned from the ceremony.
+    pub banned: Vec<Participant>,
+    /// The tokens of the participants currently in the ceremony.
+    pub tokens_in_use: HashMap<String, Participant>,
+    /// The tokens which can't be used anymore.
+    pub blacklisted_tokens: HashMap<String, Participant>,
+}
+
+/// Flag raised while a coordinator aggregates the current round and transitions to the next one. Clones share the
+/// same flag, so that a single transition runs at a time among the coordinators of a ceremony sharing it.
+#[derive(Clone, Debug, Default)]
+pub struct RoundTransitionGuard(Arc<AtomicBool>);
+
+impl RoundTransitionGuard {
+    /// Returns `true` if a round transition is in progress.
+    pub fn is_in_progress(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    /// Raises the flag for the lifetime of the returned [`RoundTransition`], fails if it is already raised.
+    fn try_begin(&self) -> Result<RoundTransition, CoordinatorError> {
+        match self
+            .0
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+        {
+            Ok(_) => Ok(RoundTransition(self.0.clone())),
+            Err(_) => Err(CoordinatorError::RoundTransitionInProgress),
+        }
+    }
+}
+
+/// A round transition in progress, lowers the flag of its [`RoundTransitionGuard`] when dropped.
+struct RoundTransition(Arc<AtomicBool>);
+
+impl Drop for RoundTransition {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
 /// A core structure for operating the Phase 1 ceremony. This struct
 /// is designed to be [Send] + [Sync]. The state of the ceremony is
 /// stored in a [CoordinatorState] object.
@@ -343,6 +747,20 @@ pub struct Coordinator {
     time: Arc<dyn TimeSource>,
     /// Callback to call after aggregation is done
     aggregation_callback: Arc<dyn Fn(Vec<Participant>) -> () + Send + Sync>,
+    /// The recent successful verifications, keyed by contribution locator and hash of the response file, with the
+    /// path of the verified file they produced.
+    verification_cache: HashMap<(ContributionLocator, String), (OffsetDateTime, LocatorPath)>,
+    /// The number of verifications run by this coordinator.
+    verification_runs: u64,
+    /// Prevents concurrent aggregations and round transitions.
+    round_transition: RoundTransitionGuard,
+    /// The height of the current round and the end of its time budget, if any.
+    round_deadline: Option<(u64, OffsetDateTime)>,
+    /// The public keys of the contributors authorized to join the queue, if participation is restricted.
+    contributor_allowlist: Option<HashSet<String>>,
+    /// The height of the round whose aggregation failed transiently, the number of failed attempts and the time of
+    /// the next one, if any.
+    aggregation_retry: Option<(u64, u32, OffsetDateTime)>,
 }
 
 impl Coordinator {
@@ -372,6 +790,7 @@ impl Coordinator {
             Object::CoordinatorState(state) => state,
             _ => return Err(CoordinatorError::StorageFailed),
         };
+        let contributor_allowlist = Self::load_contributor_allowlist(&environment)?;
 
         Ok(Self {
             environment: environment.clone(),
@@ -380,6 +799,12 @@ impl Coordinator {
             state,
             time,
             aggregation_callback: Arc::new(|_| ()),
+            verification_cache: HashMap::new(),
+            verification_runs: 0,
+            round_transition: RoundTransitionGuard::default(),
+            round_deadline: None,
+            contributor_allowlist,
+            aggregation_retry: None,
         })
     }
 
@@ -391,6 +816,16 @@ impl Coordinator {
     pub fn set_aggregation_callback(&mut self, callback: Arc<dyn Fn(Vec<Participant>) -> () + Send + Sync>) {
         self.aggregation_callback = callback;
     }
+
+    /// Returns the guard of the round transitions of this coordinator.
+    pub fn round_transition_guard(&self) -> RoundTransitionGuard {
+        self.round_transition.clone()
+    }
+
+    /// Shares the given guard of the round transitions with another coordinator of the same ceremony.
+    pub fn set_round_transition_guard(&mut self, guard: RoundTransitionGuard) {
+        self.round_transition = guard;
+    }
 }
 
 impl Coordinator {
@@ -407,6 +842,13 @@ impl Coordinator {
         info!("Coordinator is booting up");
         info!("{:#?}", self.environment.parameters());
 
+        // A read-only coordinator only serves a ceremony which has already run.
+        if self.environment.read_only() {
+            let current_round_height = self.current_round_height()?;
+            info!("Coordinator has booted up read-only at round {}", current_round_height);
+            return Ok(());
+        }
+
         // Ensure the ceremony is initialized, if it has not started yet.
         {
             // Check if the ceremony has been initialized yet.
@@ -425,6 +867,12 @@ impl Coordinator {
         // this call will fail if the ceremony was not initialized.
         let current_round_height = self.current_round_height()?;
 
+        // Resume the verification of a partially verified round.
+        self.resume_pending_verifications()?;
+
+        // Reclaim the files left over by a crash, e.g. by a contribution in progress.
+        self.reclaim_orphaned_contribution_files()?;
+
         info!("Current round height is {}", current_round_height);
         info!("{}", serde_json::to_string_pretty(&self.current_round()?)?);
         info!("Coordinator has booted up");
@@ -432,6 +880,17 @@ impl Coordinator {
         Ok(())
     }
 
+    ///
+    /// Returns a `CoordinatorError::CeremonyClosed` if the coordinator is
+    /// read-only, so that the ceremony can't be mutated anymore.
+    ///
+    pub fn check_writable(&self) -> Result<(), CoordinatorError> {
+        match self.environment.read_only() {
+            true => Err(CoordinatorError::CeremonyClosed),
+            false => Ok(()),
+        }
+    }
+
     /// Save the current state of the coordinator to storage.
     pub fn save_state(&mut self) -> Result<(), CoordinatorError> {
         self.state.save(&mut self.storage)
@@ -519,10 +978,31 @@ impl Coordinator {
             }
         }
 
+        // Try advancing to the next round if the time budget of the current round elapsed.
+        let is_advanced_on_budget = match self.try_advance_on_budget() {
+            Ok(Some(next_round_height)) => {
+                info!(
+                    "Advanced ceremony to round {} at the end of the time budget",
+                    next_round_height
+                );
+                true
+            }
+            Ok(None) => false,
+            Err(CoordinatorError::RoundSkipped) => {
+                warn!("Dropped the contributors of the current round at the end of the time budget");
+                false
+            }
+            Err(e) => return Err(e),
+        };
+
         // Try advancing to the next round if the current round is finished,
         // the current round has been aggregated, and the precommit for
         // the next round is now ready.
-        if is_current_round_finished && is_current_round_aggregated && is_precommit_next_round_ready {
+        if !is_advanced_on_budget
+            && is_current_round_finished
+            && is_current_round_aggregated
+            && is_precommit_next_round_ready
+        {
             // Backup a copy of the current coordinator.
 
             // Fetch the current time.
@@ -552,6 +1032,7 @@ impl Coordinator {
 
         // Save the coordinator state to storage.
         self.save_state()?;
+        self.storage.flush()?;
         debug!("Coordinator has safely shutdown storage");
 
         // Print the final coordinator self.
@@ -586,6 +1067,56 @@ impl Coordinator {
         self.state.number_of_queue_contributors()
     }
 
+    ///
+    /// Returns the analytics of the queue: join rate, average wait,
+    /// dropout rate and fill of the current cohort.
+    ///
+    #[inline]
+    pub fn queue_analytics(&self) -> QueueAnalytics {
+        self.state.queue_analytics(self.time.as_ref())
+    }
+
+    ///
+    /// Returns the histogram of the durations of the contributions, measured
+    /// between the lock of a chunk and the reception of its contribution.
+    ///
+    #[inline]
+    pub fn timing_histogram(&self) -> TimingHistogram {
+        TimingHistogram::new(self.state.contribution_timings())
+    }
+
+    ///
+    /// Returns the histogram of the durations of the contributions along with
+    /// the statistics of the durations of their verifications.
+    ///
+    pub fn timings(&self) -> CeremonyTimings {
+        CeremonyTimings {
+            contributions: self.timing_histogram(),
+            verifications: VerificationTimingStats::new(self.state.verification_timings()),
+        }
+    }
+
+    ///
+    /// Returns a snapshot of every participant of the ceremony, with their
+    /// state, locks, timestamps, tokens and pending tasks. Heavier than the
+    /// other reports, it is meant to debug a stuck ceremony.
+    ///
+    pub fn full_dump(&self) -> CeremonyDump {
+        self.state.dump()
+    }
+
+    ///
+    /// Returns the wall time of the last verification of the given task of the
+    /// given round, if it has been measured.
+    ///
+    pub fn verification_duration(&self, round_height: u64, task: &Task) -> Option<std::time::Duration> {
+        self.state
+            .verification_timings()
+            .iter()
+            .find(|timing| timing.round_height == round_height && timing.task == *task)
+            .map(|timing| std::time::Duration::from_micros(timing.duration_micros))
+    }
+
     ///
     /// Returns a list of the contributors currently in the queue.
     ///
@@ -629,6 +1160,19 @@ impl Coordinator {
         token: String,
         reliability_score: u8,
     ) -> Result<(), CoordinatorError> {
+        // Check that the contributor is authorized to participate, in allowlist mode.
+        if let (Participant::Contributor(pubkey), Some(allowlist)) = (&participant, &self.contributor_allowlist) {
+            if !allowlist.contains(pubkey) {
+                return Err(CoordinatorError::UnauthorizedChunkContributor);
+            }
+        }
+
+        // Check that the intake of new contributors is not paused.
+        self.check_not_paused()?;
+
+        // Check that the verifier keeps up with the contributions.
+        self.check_verification_backlog()?;
+
         // Attempt to add the participant to the next round.
         self.state.add_to_queue(
             participant,
@@ -644,6 +1188,101 @@ impl Coordinator {
         Ok(())
     }
 
+    ///
+    /// Reloads the allowlist of the contributors from its file, so that it can be
+    /// amended without restarting the coordinator. The contributors already in the
+    /// queue are not affected.
+    ///
+    /// Returns the number of authorized contributors, or `None` if participation
+    /// is not restricted.
+    ///
+    pub fn reload_contributor_allowlist(&mut self) -> Result<Option<usize>, CoordinatorError> {
+        self.contributor_allowlist = Self::load_contributor_allowlist(&self.environment)?;
+        let size = self.contributor_allowlist.as_ref().map(HashSet::len);
+        info!("Reloaded the allowlist of the contributors: {:?} authorized", size);
+
+        Ok(size)
+    }
+
+    /// Reads the allowlist of the contributors, a json array of public keys, if configured.
+    fn load_contributor_allowlist(environment: &Environment) -> Result<Option<HashSet<String>>, CoordinatorError> {
+        match environment.contributor_allowlist_path() {
+            Some(path) => Ok(Some(serde_json::from_slice(&std::fs::read(path)?)?)),
+            None => Ok(None),
+        }
+    }
+
+    ///
+    /// Records the client the participant joined the queue from, to store it
+    /// privately alongside its contribution.
+    ///
+    #[inline]
+    pub fn set_client_provenance(&mut self, participant: Participant, client: ClientProvenance) {
+        self.state.set_client_provenance(participant, client);
+    }
+
+    ///
+    /// Returns the number of contributions awaiting verification.
+    ///
+    #[inline]
+    pub fn verification_backlog(&self) -> usize {
+        self.state.get_pending_verifications().len()
+    }
+
+    ///
+    /// Returns the public status of the ceremony.
+    ///
+    pub fn ceremony_status(&self) -> Result<CeremonyStatus, CoordinatorError> {
+        Ok(CeremonyStatus {
+            round_height: self.current_round_height()?,
+            queue_size: self.number_of_queue_contributors(),
+            verification_backlog: self.verification_backlog(),
+            paused: self.is_paused(),
+            parameters: self.environment.ceremony_parameters(),
+        })
+    }
+
+    /// Verifies the signature of a contribution file with its scheme, the signer of
+    /// the environment standing for the current one.
+    fn verify_file_signature(
+        &self,
+        public_key: &str,
+        contribution_file_signature: &ContributionFileSignature,
+    ) -> Result<bool, CoordinatorError> {
+        let message = serde_json::to_string(&contribution_file_signature.get_state())?;
+        let signature = contribution_file_signature.get_signature();
+
+        Ok(match contribution_file_signature.get_scheme() {
+            scheme if scheme.is_current() => self.signature.verify(public_key, &message, signature),
+            scheme => scheme.verify(public_key, &message, signature),
+        })
+    }
+
+    /// Rejects the new contributors while the ceremony is paused.
+    fn check_not_paused(&self) -> Result<(), CoordinatorError> {
+        match self.is_paused() {
+            true => Err(CoordinatorError::CeremonyPaused),
+            false => Ok(()),
+        }
+    }
+
+    /// Checks that the verification backlog doesn't exceed the high-water
+    /// mark of the environment, if any, to defer new contributors otherwise.
+    fn check_verification_backlog(&self) -> Result<(), CoordinatorError> {
+        if let Some(max_pending_verifications) = self.environment.max_pending_verifications() {
+            let backlog = self.verification_backlog();
+            if backlog > max_pending_verifications {
+                warn!(
+                    "{} contributions are pending verification, deferring new contributors until below {}",
+                    backlog, max_pending_verifications
+                );
+                return Err(CoordinatorError::VerificationBacklog);
+            }
+        }
+
+        Ok(())
+    }
+
     ///
     /// Removes the given participant from the queue if they are in the queue.
     ///
@@ -695,6 +1334,33 @@ impl Coordinator {
         Ok(())
     }
 
+    ///
+    /// Rejects the contribution of the current round which failed the verification
+    /// for the given reason: the round is reset, so that the invalid contribution
+    /// doesn't stall the ceremony, and its contributor is banned. The reason is
+    /// recorded to be reported to the contributor.
+    ///
+    /// On success, this function returns the contributor of the rejected contribution.
+    ///
+    pub fn reject_contribution(&mut self, reason: String) -> Result<Participant, CoordinatorError> {
+        // Get the participant who produced the contribution
+        let contributor = self
+            .state
+            .current_round_finished_contributors()?
+            .first()
+            .ok_or(CoordinatorError::ContributorsMissing)?
+            .clone();
+
+        // Reset the round to prevent a coordinator stall (the corrupted contribution is not automatically dropped)
+        self.reset_round()?;
+
+        // Must be banned after the reset because one can't ban a finished contributor
+        self.state.reject_contribution(&contributor, reason);
+        self.ban_participant(&contributor)?;
+
+        Ok(contributor)
+    }
+
     ///
     /// Unbans the given participant from joining the queue.
     ///
@@ -746,6 +1412,44 @@ impl Coordinator {
         Ok(())
     }
 
+    ///
+    /// Returns `true` if the ceremony is paused.
+    ///
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.state.is_paused()
+    }
+
+    ///
+    /// Pauses the intake of new contributors, for maintenance. While paused, contributors
+    /// can't join the queue nor lock a chunk, but the chunks already locked can be
+    /// contributed and the contributions are still verified.
+    ///
+    #[inline]
+    pub fn pause(&mut self) -> Result<(), CoordinatorError> {
+        info!("Pausing the ceremony");
+        self.state.set_paused(true);
+
+        // Save the coordinator state in storage, for a restart to stay paused.
+        self.save_state()?;
+
+        Ok(())
+    }
+
+    ///
+    /// Resumes the intake of new contributors.
+    ///
+    #[inline]
+    pub fn resume(&mut self) -> Result<(), CoordinatorError> {
+        info!("Resuming the ceremony");
+        self.state.set_paused(false);
+
+        // Save the coordinator state in storage.
+        self.save_state()?;
+
+        Ok(())
+    }
+
     ///
     /// Returns `true` if the given participant is authorized as a
     /// contributor and listed in the contributor IDs for this round.
@@ -773,54 +1477,153 @@ impl Coordinator {
     }
 
     ///
-    /// Returns `true` if the given participant has finished contributing in the provided round
-    ///
-    pub fn is_finished_contributor_at_round(&self, participant: &Participant, round: u64) -> bool {
-        self.state.is_finished_contributor_at_round(participant, round)
-    }
-
+    /// Returns the time at which the given current contributor will be
+    /// dropped if it is stale, i.e. it has not been seen by the coordinator
+    /// for longer than the contributor seen timeout.
     ///
-    /// Returns `true` if the given participant has finished contributing in the current round
+    /// If the participant is not stale, returns `None`.
     ///
     #[inline]
-    pub fn is_finished_contributor(&self, participant: &Participant) -> bool {
-        // Fetch the state of the current contributor.
-        self.state.is_finished_contributor(&participant)
+    pub fn stale_contributor_eviction_time(&self, participant: &Participant) -> Option<OffsetDateTime> {
+        self.state
+            .stale_contributor_eviction_time(participant, self.time.as_ref())
     }
 
     ///
-    /// Returns `true` if the given participant has been banned from the ceremony
+    /// Returns `true` if the given participant is authorized as a
+    /// verifier for this round, either because it's listed in the
+    /// verifier IDs of the round or because it's managed by the coordinator.
+    ///
+    /// If the participant is not a verifier, or if there are
+    /// no prior rounds, returns `false`.
     ///
     #[inline]
-    pub fn is_banned_participant(&self, participant: &Participant) -> bool {
-        self.state.is_banned_participant(participant)
+    pub fn is_current_verifier(&self, participant: &Participant) -> bool {
+        // Fetch the current round from storage.
+        let round = match Self::load_current_round(&self.storage) {
+            // Case 1 - This is a typical round of the ceremony.
+            Ok(round) => round,
+            // Case 2 - The ceremony has not started or storage has failed.
+            _ => return false,
+        };
+
+        // Check that the participant is a verifier and has not been banned.
+        if !self.state.is_authorized_verifier(participant) {
+            return false;
+        }
+
+        // Check that the participant is a verifier for the given round height.
+        round.is_verifier(participant) || self.state.is_coordinator_verifier(participant)
     }
 
     ///
-    /// Returns `true` if the given participant has been dropped from the ceremony,
-    /// `false` if it hasn't or if there's no info about the participant.
+    /// Returns the role of the given participant in the current round.
     ///
     #[inline]
-    pub fn is_dropped_participant(&self, participant: &Participant) -> bool {
-        match self.state.is_dropped_participant(participant) {
-            Ok(dropped) => dropped,
-            Err(_) => false,
+    pub fn participant_role(&self, participant: &Participant) -> ParticipantRole {
+        if self.is_current_contributor(participant) {
+            ParticipantRole::Contributor
+        } else if self.is_current_verifier(participant) {
+            ParticipantRole::Verifier
+        } else {
+            ParticipantRole::Unauthorized
         }
     }
 
     ///
-    /// Returns `true` if the given participant is a contributor managed
-    /// by the coordinator.
+    /// Checks that the given participant is a contributor of the current round.
+    ///
+    /// Returns `CoordinatorError::ExpectedContributor` if the participant is a verifier
+    /// and `CoordinatorError::ParticipantUnauthorized` if it's not a current contributor.
     ///
     #[inline]
-    pub fn is_coordinator_contributor(&self, participant: &Participant) -> bool {
-        // Check that the participant is a coordinator contributor.
-        self.state.is_coordinator_contributor(&participant)
+    pub fn check_current_contributor(&self, participant: &Participant) -> Result<(), CoordinatorError> {
+        if !participant.is_contributor() {
+            return Err(CoordinatorError::ExpectedContributor);
+        }
+
+        match self.participant_role(participant) {
+            ParticipantRole::Contributor => Ok(()),
+            _ => Err(CoordinatorError::ParticipantUnauthorized),
+        }
     }
 
     ///
-    /// Returns `true` if the given participant is a verifier managed
-    /// by the coordinator.
+    /// Checks that the given participant is a verifier of the current round.
+    ///
+    /// Returns `CoordinatorError::ExpectedVerifier` if the participant is a contributor
+    /// and `CoordinatorError::ParticipantUnauthorized` if it's not a current verifier.
+    ///
+    #[inline]
+    pub fn check_current_verifier(&self, participant: &Participant) -> Result<(), CoordinatorError> {
+        if !participant.is_verifier() {
+            return Err(CoordinatorError::ExpectedVerifier);
+        }
+
+        match self.participant_role(participant) {
+            ParticipantRole::Verifier => Ok(()),
+            _ => Err(CoordinatorError::ParticipantUnauthorized),
+        }
+    }
+
+    ///
+    /// Returns `true` if the given participant has finished contributing in the provided round
+    ///
+    pub fn is_finished_contributor_at_round(&self, participant: &Participant, round: u64) -> bool {
+        self.state.is_finished_contributor_at_round(participant, round)
+    }
+
+    ///
+    /// Returns `true` if the given participant has finished contributing in the current round
+    ///
+    #[inline]
+    pub fn is_finished_contributor(&self, participant: &Participant) -> bool {
+        // Fetch the state of the current contributor.
+        self.state.is_finished_contributor(&participant)
+    }
+
+    ///
+    /// Returns `true` if the given participant has been banned from the ceremony
+    ///
+    #[inline]
+    pub fn is_banned_participant(&self, participant: &Participant) -> bool {
+        self.state.is_banned_participant(participant)
+    }
+
+    ///
+    /// Returns the reason why the verification of the contribution of the given
+    /// participant failed, if it did.
+    ///
+    #[inline]
+    pub fn rejection_reason(&self, participant: &Participant) -> Option<&str> {
+        self.state.rejection_reason(participant)
+    }
+
+    ///
+    /// Returns `true` if the given participant has been dropped from the ceremony,
+    /// `false` if it hasn't or if there's no info about the participant.
+    ///
+    #[inline]
+    pub fn is_dropped_participant(&self, participant: &Participant) -> bool {
+        match self.state.is_dropped_participant(participant) {
+            Ok(dropped) => dropped,
+            Err(_) => false,
+        }
+    }
+
+    ///
+    /// Returns `true` if the given participant is a contributor managed
+    /// by the coordinator.
+    ///
+    #[inline]
+    pub fn is_coordinator_contributor(&self, participant: &Participant) -> bool {
+        // Check that the participant is a coordinator contributor.
+        self.state.is_coordinator_contributor(&participant)
+    }
+
+    ///
+    /// Returns `true` if the given participant is a verifier managed
+    /// by the coordinator.
     ///
     #[inline]
     pub fn is_coordinator_verifier(&self, participant: &Participant) -> bool {
@@ -828,6 +1631,18 @@ impl Coordinator {
         self.state.is_coordinator_verifier(&participant)
     }
 
+    ///
+    /// Returns `true` if the ceremony has moved past the initialization
+    /// round, i.e. if contributions can be locked and computed.
+    ///
+    #[inline]
+    pub fn is_ceremony_started(&self) -> bool {
+        match self.current_round_height() {
+            Ok(round_height) => round_height > 0,
+            Err(_) => false,
+        }
+    }
+
     ///
     /// Returns the current round height of the ceremony from storage,
     /// irrespective of the stage of its completion.
@@ -893,294 +1708,589 @@ impl Coordinator {
         }
     }
 
-    /// Lets the coordinator know that the participant is still alive
-    /// and participating (or waiting to participate) in the ceremony.
-    pub fn heartbeat(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
-        self.state.heartbeat(participant, self.time.as_ref())
+    ///
+    /// Returns the hex encoded aggregate hash of the round corresponding to
+    /// the given height, published once the round has been aggregated.
+    ///
+    /// If the round does not exist or has not been aggregated yet,
+    /// returns a `CoordinatorError`.
+    ///
+    pub fn get_round_aggregate_hash(&self, round_height: u64) -> Result<String, CoordinatorError> {
+        self.get_round(round_height)?
+            .aggregate_hash()
+            .map(str::to_owned)
+            .ok_or(CoordinatorError::RoundNotAggregated)
     }
 
     ///
-    /// Attempts to acquire the lock to a chunk for the given participant.
+    /// Returns the transcript of the round corresponding to the given height,
+    /// available once the round has been aggregated: for each chunk in order,
+    /// its verified contributions from the initial challenge to the final one.
     ///
-    /// On failure, this function returns a `CoordinatorError`.
+    /// If the round does not exist or has not been aggregated yet,
+    /// returns a `CoordinatorError`.
     ///
-    #[tracing::instrument(
-        level = "error",
-        skip(self),
-        fields(participant = %participant),
-        err
-    )]
-    pub fn try_lock(&mut self, participant: &Participant) -> Result<(u64, LockedLocators), CoordinatorError> {
-        if participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedContributor);
-        }
-
-        // Check that the participant is in the current round, and has not been dropped or finished.
-        if !self.state.is_current_contributor(participant) {
-            return Err(CoordinatorError::ParticipantUnauthorized);
-        }
-
-        // Check that the current round is not yet finished.
-        if self.state.is_current_round_finished() {
-            return Err(CoordinatorError::CurrentRoundFinished);
+    pub fn get_round_transcript(&self, round_height: u64) -> Result<RoundTranscript, CoordinatorError> {
+        let skipped = self.get_round(round_height)?.is_skipped();
+        let mut chunks = Vec::new();
+        for (chunk_id, locators) in self.get_round_transcript_locators(round_height)? {
+            let contributions = locators
+                .iter()
+                .map(|locator| self.get_transcript_contribution(locator))
+                .collect::<Result<_, _>>()?;
+            chunks.push(ChunkTranscript {
+                chunk_id,
+                contributions,
+            });
         }
 
-        // Check that the current round is not yet aggregating.
-        if self.state.is_current_round_aggregating() {
-            return Err(CoordinatorError::CurrentRoundAggregating);
-        }
+        Ok(RoundTranscript {
+            round_height,
+            skipped,
+            parameters: self.environment.ceremony_parameters(),
+            chunks,
+        })
+    }
 
-        // Check that the current round is not yet aggregated.
-        if self.state.is_current_round_aggregated() {
-            return Err(CoordinatorError::CurrentRoundAggregated);
+    ///
+    /// Returns the locators of the contributions of the transcript of the
+    /// round corresponding to the given height, by chunk, so that they can
+    /// be read one at a time with `get_transcript_contribution`.
+    ///
+    /// If the round does not exist or has not been aggregated yet,
+    /// returns a `CoordinatorError`.
+    ///
+    pub fn get_round_transcript_locators(
+        &self,
+        round_height: u64,
+    ) -> Result<Vec<(u64, Vec<ContributionLocator>)>, CoordinatorError> {
+        let round = self.get_round(round_height)?;
+        if round.aggregate_hash().is_none() {
+            return Err(CoordinatorError::RoundNotAggregated);
         }
 
-        // Attempt to fetch the next chunk ID and contribution ID for the given participant.
-        let current_task = self.state.fetch_task(participant, self.time.as_ref())?;
-        trace!("Fetched task {} for {}", current_task, participant);
+        // A skipped round has no contribution besides its challenge
+        let final_contribution_id = match round.is_skipped() {
+            true => 0,
+            false => round.expected_number_of_contributions() - 1,
+        };
+        let chunks = (0..self.environment.number_of_chunks())
+            .map(|chunk_id| {
+                let locators = (0..=final_contribution_id)
+                    .map(|contribution_id| match contribution_id == final_contribution_id {
+                        // The final verified contribution is the challenge of the next round
+                        true => ContributionLocator::new(round_height + 1, chunk_id, 0, true),
+                        false => ContributionLocator::new(round_height, chunk_id, contribution_id, true),
+                    })
+                    .collect();
+                (chunk_id, locators)
+            })
+            .collect();
+
+        Ok(chunks)
+    }
 
-        let round = Self::load_current_round(&self.storage)?;
-        let chunk = round.chunk(current_task.chunk_id())?;
-        if current_task.contribution_id() > (chunk.current_contribution_id() + 1) {
-            self.state
-                .rollback_pending_task(participant, current_task, &*self.time)?;
-            return Err(CoordinatorError::PreviousContributionMissing { current_task });
-        }
+    ///
+    /// Returns the hex encoded contribution file at the given locator, as
+    /// found in a round transcript.
+    ///
+    pub fn get_transcript_contribution(&self, locator: &ContributionLocator) -> Result<String, CoordinatorError> {
+        let contribution = self.storage.reader(&Locator::ContributionFile(*locator))?;
 
-        debug!("Locking chunk {} for {}", current_task.chunk_id(), participant);
-        match self.try_lock_chunk(current_task.chunk_id(), participant) {
-            // Case 1 - Participant acquired lock, return the locator.
-            Ok(locked_locators) => {
-                trace!("Incrementing the number of locks held by {}", participant);
-                self.state
-                    .acquired_lock(participant, current_task.chunk_id(), self.time.as_ref())?;
+        Ok(hex::encode(contribution.as_ref()))
+    }
 
-                // Save the coordinator state in storage.
-                self.save_state()?;
+    ///
+    /// Returns the contributors of the round corresponding to the given height,
+    /// in the order of their first contribution.
+    ///
+    /// If the round does not exist, returns a `CoordinatorError`.
+    ///
+    pub fn get_round_participants(&self, round_height: u64) -> Result<Vec<Participant>, CoordinatorError> {
+        let round = self.get_round(round_height)?;
 
-                info!("Acquired lock on chunk {} for {}", current_task.chunk_id(), participant);
-                Ok((current_task.chunk_id(), locked_locators))
+        let mut participants: Vec<Participant> = Vec::new();
+        for chunk in round.chunks() {
+            for contribution in chunk.get_contributions().values() {
+                if let Some(contributor) = contribution.get_contributor() {
+                    if !participants.contains(contributor) {
+                        participants.push(contributor.clone());
+                    }
+                }
             }
-            // Case 2 - Participant failed to acquire the lock, put the chunk ID back.
-            Err(error) => {
-                info!("Failed to acquire lock for {}", participant);
-
-                trace!("Adding task {} back to assigned tasks", current_task);
-                self.state
-                    .rollback_pending_task(participant, current_task, self.time.as_ref())?;
+        }
 
-                // Save the coordinator state in storage.
-                self.save_state()?;
+        Ok(participants)
+    }
 
-                error!("{}", error);
-                return Err(error);
-            }
-        }
+    ///
+    /// Returns the rolling average of the durations of the last contributions of the
+    /// ceremony, from the lock of their chunk to their reception. Returns `None` if no
+    /// contribution has been received yet.
+    ///
+    pub fn average_contribution_duration(&self) -> Option<time::Duration> {
+        self.state.average_contribution_duration(QUEUE_DRAIN_WINDOW)
     }
 
-    /// Returns previous contribution, current contribution and next contribution paths
-    pub fn get_chunk_locators_for_verifier(
+    ///
+    /// Returns the estimated time at which the given contributor, at the given position
+    /// of the queue, will start contributing. The queue drains at the rolling average
+    /// contribution duration, from the time the contributor joined the queue or from the
+    /// start of the current round if it started later. Returns `None` if there is no
+    /// history of contributions to estimate the rate from.
+    ///
+    pub fn estimated_start_time(
         &self,
         participant: &Participant,
-        chunk_id: u64,
-        contribution_id: u64,
-    ) -> Result<LockedLocators, CoordinatorError> {
-        if !participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedVerifier);
-        }
-        let round = Self::load_current_round(&self.storage)?;
-        round.get_chunk_locators_for_verifier(&self.storage, participant, chunk_id, contribution_id)
+        queue_position: u64,
+    ) -> Result<Option<OffsetDateTime>, CoordinatorError> {
+        let (joined_at, average) = match (
+            self.state.queue_join_time(participant),
+            self.average_contribution_duration(),
+        ) {
+            (Some(joined_at), Some(average)) => (joined_at, average),
+            _ => return Ok(None),
+        };
+
+        let drain_start = match self.current_round()?.started_at() {
+            Some(started_at) => std::cmp::max(started_at, joined_at),
+            None => joined_at,
+        };
+
+        Ok(Some(estimate_start_time(
+            drain_start,
+            average,
+            queue_position,
+            self.time.now_utc(),
+        )))
     }
 
-    /// Initialize the files for the next challenge
-    pub fn initialize_verifier_response_files(
-        &mut self,
-        participant: &Participant,
-        chunk_id: u64,
-        locators: &LockedLocators,
-    ) -> Result<(), CoordinatorError> {
-        if !participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedVerifier);
-        }
-        let round = Self::load_current_round(&self.storage)?;
-        round.initialize_verifier_response_files(&self.environment, &mut self.storage, participant, chunk_id, locators)
+    ///
+    /// Returns the summary of the contributions to the rounds between `from_round`
+    /// and `to_round` included, at most `page_size` of them after skipping the first
+    /// `offset` ones. The range defaults to all the rounds up to the current one.
+    ///
+    /// If the range is empty or goes beyond the current round, returns a `CoordinatorError`.
+    ///
+    pub fn get_contributions_summary(
+        &self,
+        from_round: Option<u64>,
+        to_round: Option<u64>,
+        offset: Option<usize>,
+        page_size: Option<usize>,
+    ) -> Result<Vec<TrimmedContributionInfo>, CoordinatorError> {
+        let (from_round, to_round) = self.round_range(from_round, to_round)?;
+
+        let summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary,
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
+
+        Ok(summary
+            .into_iter()
+            .filter(|contribution| (from_round..=to_round).contains(&contribution.ceremony_round()))
+            .skip(offset.unwrap_or_default())
+            .take(page_size.unwrap_or(usize::MAX))
+            .collect())
     }
 
     ///
-    /// Attempts to add a contribution for the given chunk ID from the given participant.
+    /// Returns the summary of the contributions to publish, see `get_contributions_summary`.
     ///
-    /// This function constructs the expected response locator for the given participant
-    /// and chunk ID, and checks that the participant has uploaded the response file and
-    /// contribution file signature prior to adding the unverified contribution to the
-    /// round self.
+    /// If the environment redacts the identity of the contributors, their names
+    /// are omitted and their emails replaced by their hashes.
     ///
-    /// On success, this function releases the lock from the contributor and returns
-    /// the response file locator.
+    pub fn get_public_contributions_summary(
+        &self,
+        from_round: Option<u64>,
+        to_round: Option<u64>,
+        offset: Option<usize>,
+        page_size: Option<usize>,
+    ) -> Result<Vec<TrimmedContributionInfo>, CoordinatorError> {
+        let redact_identity = self.environment.redact_contributor_identity();
+
+        Ok(self
+            .get_contributions_summary(from_round, to_round, offset, page_size)?
+            .into_iter()
+            .map(|contribution| contribution.into_public(redact_identity))
+            .collect())
+    }
+
     ///
-    /// On failure, it returns a `CoordinatorError`.
+    /// Returns the full info, including the identity of the contributor, of the
+    /// contributions of the rounds between `from_round` and `to_round` included.
     ///
-    #[tracing::instrument(
-        level = "error",
-        skip(self, participant, chunk_id),
-        fields(participant = %participant, chunk = chunk_id),
-        err
-    )]
-    pub fn try_contribute(
-        &mut self,
-        participant: &Participant,
-        chunk_id: u64,
-    ) -> Result<ContributionLocator, CoordinatorError> {
-        // Check that the participant is a contributor.
-        if !participant.is_contributor() {
-            return Err(CoordinatorError::ExpectedContributor);
-        }
+    /// If the range of rounds is invalid, returns a `CoordinatorError`.
+    ///
+    pub fn get_contributions_full_info(
+        &self,
+        from_round: Option<u64>,
+        to_round: Option<u64>,
+    ) -> Result<Vec<ContributionInfo>, CoordinatorError> {
+        let (from_round, to_round) = self.round_range(from_round, to_round)?;
+
+        let mut contributions = Vec::new();
+        let mut round_heights = self.storage.round_heights()?;
+        round_heights.retain(|height| (from_round..=to_round).contains(height));
+        for round_height in round_heights {
+            let locator = Locator::ContributionInfoFile { round_height };
+            if !self.storage.exists(&locator) {
+                continue;
+            }
 
-        // Check that the chunk ID is valid.
-        if chunk_id > self.environment.number_of_chunks() {
-            return Err(CoordinatorError::ChunkIdInvalid);
+            match self.storage.get(&locator)? {
+                Object::ContributionInfoFile(info) => contributions.push(info),
+                _ => return Err(CoordinatorError::StorageFailed),
+            }
         }
 
-        // Check that the participant is in the current round, and has not been dropped or finished.
-        if !self.state.is_current_contributor(participant) {
-            return Err(CoordinatorError::ParticipantUnauthorized);
-        }
+        Ok(contributions)
+    }
 
-        // Check that the current round is not yet finished.
-        if self.state.is_current_round_finished() {
-            return Err(CoordinatorError::CurrentRoundFinished);
-        }
+    ///
+    /// Returns the provenance of the contributions of the rounds between
+    /// `from_round` and `to_round` included, for abuse investigation.
+    ///
+    pub fn get_contributions_provenance(
+        &self,
+        from_round: Option<u64>,
+        to_round: Option<u64>,
+    ) -> Result<Vec<ContributionProvenance>, CoordinatorError> {
+        let (from_round, to_round) = self.round_range(from_round, to_round)?;
+
+        let mut provenance = Vec::new();
+        let mut round_heights = self.storage.round_heights()?;
+        round_heights.retain(|height| (from_round..=to_round).contains(height));
+        for round_height in round_heights {
+            let locator = Locator::ContributionProvenanceFile { round_height };
+            if !self.storage.exists(&locator) {
+                continue;
+            }
 
-        // Check that the current round is not yet aggregating.
-        if self.state.is_current_round_aggregating() {
-            return Err(CoordinatorError::CurrentRoundAggregating);
+            match self.storage.get(&locator)? {
+                Object::ContributionProvenanceFile(p) => provenance.extend(p),
+                _ => return Err(CoordinatorError::StorageFailed),
+            }
         }
 
-        // Check that the current round is not yet aggregated.
-        if self.state.is_current_round_aggregated() {
-            return Err(CoordinatorError::CurrentRoundAggregated);
-        }
+        Ok(provenance)
+    }
 
-        // Fetch the current round height from storage.
-        let round_height = Self::load_current_round_height(&self.storage)?;
-        trace!("Current round height in storage is {}", round_height);
+    /// Returns the range of rounds between `from_round`, by default the first one,
+    /// and `to_round`, by default the current one.
+    fn round_range(&self, from_round: Option<u64>, to_round: Option<u64>) -> Result<(u64, u64), CoordinatorError> {
+        let current_round_height = self.current_round_height()?;
+        let from_round = from_round.unwrap_or_default();
+        let to_round = to_round.unwrap_or(current_round_height);
 
-        // Check if the participant should dispose the response being contributed.
-        if let Some(task) = self.state.lookup_disposing_task(participant, chunk_id)?.cloned() {
-            let contribution_id = task.contribution_id();
-
-            // Move the task to the disposed tasks of the contributor.
-            self.state.disposed_task(participant, &task, self.time.as_ref())?;
+        if from_round > to_round {
+            return Err(CoordinatorError::RoundRangeInvalid);
+        }
+        if to_round > current_round_height {
+            return Err(CoordinatorError::RoundDoesNotExist);
+        }
 
-            // Remove the response file from storage.
-            let response = ContributionLocator::new(round_height, chunk_id, contribution_id, false);
-            self.storage.remove(&Locator::ContributionFile(response.clone()))?;
+        Ok((from_round, to_round))
+    }
 
-            // Blacklist participant's token and ip
-            if *TOKEN_BLACKLIST {
-                self.state.blacklist_participant_token(participant)?;
-            }
+    ///
+    /// Returns a report on whether the current round can be aggregated and the ceremony
+    /// advanced to the next round, without performing the transition.
+    ///
+    /// If there are no prior rounds in storage, returns a `CoordinatorError`.
+    ///
+    pub fn can_advance_round(&self) -> Result<RoundReadiness, CoordinatorError> {
+        let round = self.current_round()?;
+        let round_height = round.round_height();
+        let expected_contributions = round.expected_number_of_contributions();
 
-            if *IP_BAN {
-                self.state.blacklist_participant_ip(participant)?;
-            }
+        let incomplete_chunks = round
+            .chunks()
+            .iter()
+            .filter(|chunk| !chunk.is_complete(expected_contributions))
+            .map(|chunk| chunk.chunk_id())
+            .collect();
 
-            // Save the coordinator state in storage.
-            self.save_state()?;
+        // Round 0 is not aggregated, so there are no locators to check
+        let missing_locators = match round_height {
+            0 => vec![],
+            _ => (0..self.environment.number_of_chunks())
+                .filter(|chunk_id| {
+                    let unverified = Locator::ContributionFile(ContributionLocator::new(
+                        round_height,
+                        *chunk_id,
+                        expected_contributions - 1,
+                        false,
+                    ));
+                    let verified =
+                        Locator::ContributionFile(ContributionLocator::new(round_height + 1, *chunk_id, 0, true));
 
-            debug!("Removing lock for disposed task {} {}", chunk_id, contribution_id);
+                    !self.storage.exists(&unverified) || !self.storage.exists(&verified)
+                })
+                .collect(),
+        };
 
-            // Fetch the current round from storage.
-            let mut round = Self::load_current_round(&self.storage)?;
+        Ok(RoundReadiness {
+            round_height,
+            incomplete_chunks,
+            missing_locators,
+            pending_verifications: self.state.get_pending_verifications().len(),
+            current_contributors: self.state.current_contributors().len(),
+            is_aggregated: self.state.is_current_round_aggregated(),
+        })
+    }
 
-            // TODO (raychu86): Move this unsafe call out of `try_contribute`.
-            // Release the lock on this chunk from the contributor.
-            round.chunk_mut(chunk_id)?.set_lock_holder_unsafe(None);
+    ///
+    /// Verifies again every contribution of the given completed round, to detect the files
+    /// corrupted after their verification.
+    ///
+    /// The check is read-only unless `repair` is set: the verified copies of the failed
+    /// contributions are then rewritten by running [`Verification::run`] again. In both cases,
+    /// the verified flags of the round are left untouched.
+    ///
+    /// If the round doesn't exist or is not complete, returns a `CoordinatorError`.
+    ///
+    pub fn reverify_round(&mut self, round_height: u64, repair: bool) -> Result<RoundReverification, CoordinatorError> {
+        let round = self.get_round(round_height)?;
+        if !round.is_complete() {
+            return Err(CoordinatorError::RoundNotComplete);
+        }
 
-            // Save the updated round to storage.
-            self.storage.update(
-                &Locator::RoundState {
-                    round_height: round.round_height(),
-                },
-                Object::RoundState(round),
-            )?;
+        let environment = &self.environment;
+        let signature = &self.signature;
+        let storage = &mut self.storage;
+        let expected_contributions = round.expected_number_of_contributions();
+        let mut report = RoundReverification {
+            round_height,
+            ..Default::default()
+        };
+        for chunk in round.chunks() {
+            let chunk_id = chunk.chunk_id();
+            for contribution_id in 1..expected_contributions {
+                let is_final_contribution = contribution_id == expected_contributions - 1;
+                let verified_hash = chunk.get_contribution(contribution_id)?.get_verified_hash();
+                let check = |storage: &Disk| {
+                    Verification::check(
+                        environment,
+                        storage,
+                        round_height,
+                        chunk_id,
+                        contribution_id,
+                        is_final_contribution,
+                        verified_hash,
+                    )
+                };
+                report.checked += 1;
 
-            return Ok(response);
-        }
+                let error = match check(storage) {
+                    Ok(()) => continue,
+                    Err(error) => error,
+                };
+                warn!(
+                    "Contribution {} of round {} chunk {} failed to verify again: {:?}",
+                    contribution_id, round_height, chunk_id, error
+                );
 
-        // Check if the participant has this chunk ID in a pending task.
-        if let Some(task) = self.state.lookup_pending_task(participant, chunk_id)?.cloned() {
-            debug!("Adding contribution for chunk");
+                // The verified copy and its signature are only replaced once the new ones verify, the verified copy
+                // of the final contribution is the first contribution of the next round.
+                let repaired = repair && {
+                    let (verified_locator, signature_locator) = match is_final_contribution {
+                        true => (
+                            Locator::ContributionFile(ContributionLocator::new(round_height + 1, chunk_id, 0, true)),
+                            Locator::ContributionFileSignature(ContributionSignatureLocator::new(
+                                round_height + 1,
+                                chunk_id,
+                                0,
+                                true,
+                            )),
+                        ),
+                        false => (
+                            Locator::ContributionFile(ContributionLocator::new(
+                                round_height,
+                                chunk_id,
+                                contribution_id,
+                                true,
+                            )),
+                            Locator::ContributionFileSignature(ContributionSignatureLocator::new(
+                                round_height,
+                                chunk_id,
+                                contribution_id,
+                                true,
+                            )),
+                        ),
+                    };
+                    let signing_key = environment.default_verifier_signing_key();
+                    storage
+                        .rewrite(&verified_locator, |storage| {
+                            storage.rewrite(&signature_locator, |storage| {
+                                Verification::run(
+                                    environment,
+                                    storage,
+                                    signature.clone(),
+                                    &signing_key,
+                                    round_height,
+                                    chunk_id,
+                                    contribution_id,
+                                    is_final_contribution,
+                                )?;
+                                check(storage)
+                            })
+                        })
+                        .is_ok()
+                };
+                if repaired {
+                    info!(
+                        "Rewrote the verified contribution {} of round {} chunk {}",
+                        contribution_id, round_height, chunk_id
+                    );
+                }
 
-            match self.add_contribution(chunk_id, participant) {
-                // Case 1 - Participant added contribution, return the response file locator.
-                Ok((locator, contribution_id)) => {
-                    trace!("Release the lock on chunk");
-                    let completed_task = Task::new(chunk_id, contribution_id);
-                    self.state
-                        .completed_task(participant, &completed_task, self.time.as_ref())?;
+                report.failures.push(ReverificationFailure {
+                    chunk_id,
+                    contribution_id,
+                    reason: format!("{:?}", error),
+                    repaired,
+                });
+            }
+        }
 
-                    // Blacklist participant's token and ip
-                    if *TOKEN_BLACKLIST {
-                        self.state.blacklist_participant_token(participant)?;
-                    }
+        Ok(report)
+    }
 
-                    if *IP_BAN {
-                        self.state.blacklist_participant_ip(participant)?;
-                    }
+    ///
+    /// Resets the current round height in storage to the highest round whose state is
+    /// stored and consistent, to recover from a crash in the middle of a write. Rounds are
+    /// scanned from round 0 up to the stored round height, if it can be read.
+    ///
+    /// Returns the reconciled round height. If no round is stored, returns a `CoordinatorError`.
+    ///
+    pub fn reconcile_round_height(&mut self) -> Result<u64, CoordinatorError> {
+        let stored_round_height = Self::load_current_round_height(&self.storage).ok();
 
-                    // Save the coordinator state in storage.
-                    self.save_state()?;
+        // Find the highest round such that all the rounds up to it are stored and consistent.
+        let mut reconciled_round_height = None;
+        for round_height in self.storage.round_heights()? {
+            let is_next_round = round_height == reconciled_round_height.map_or(0, |height| height + 1);
+            if !is_next_round || stored_round_height.map_or(false, |stored| round_height > stored) {
+                break;
+            }
 
-                    info!("Added contribution");
-                    return Ok(locator);
+            match self.storage.get(&Locator::RoundState { round_height }) {
+                Ok(Object::RoundState(round)) if round.round_height() == round_height => {
+                    reconciled_round_height = Some(round_height)
                 }
-                // Case 2 - Participant failed to add their contribution, remove the contribution file.
-                Err(error) => {
-                    info!("Failed to add a contribution and removing the contribution file");
-                    // Remove the invalid response file from storage.
-                    let response = Locator::ContributionFile(ContributionLocator::new(
-                        round_height,
-                        chunk_id,
-                        task.contribution_id(),
-                        false,
-                    ));
-                    self.storage.remove(&response)?;
+                _ => break,
+            }
+        }
+        let reconciled_round_height = reconciled_round_height.ok_or(CoordinatorError::RoundDoesNotExist)?;
 
-                    error!("{}", error);
-                    return Err(error);
-                }
+        if stored_round_height != Some(reconciled_round_height) {
+            warn!(
+                "Resetting the current round height from {:?} to {}",
+                stored_round_height, reconciled_round_height
+            );
+
+            // An unreadable round height is written anew
+            if stored_round_height.is_none() && self.storage.exists(&Locator::RoundHeight) {
+                self.storage.remove(&Locator::RoundHeight)?;
             }
+            self.storage
+                .compare_and_set_round_height(stored_round_height, reconciled_round_height, None)?;
         }
 
-        Err(CoordinatorError::ContributionFailed)
+        // The coordinator state must point at the same round as the storage.
+        if self.state.current_round_height() != reconciled_round_height {
+            warn!(
+                "Resetting the round height of the coordinator state from {} to {}",
+                self.state.current_round_height(),
+                reconciled_round_height
+            );
+            self.state.reset_current_round_height(reconciled_round_height);
+            self.save_state()?;
+        }
+
+        Ok(reconciled_round_height)
+    }
+
+    /// Lets the coordinator know that the participant is still alive
+    /// and participating (or waiting to participate) in the ceremony.
+    pub fn heartbeat(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+        self.state.heartbeat(participant, self.time.as_ref())
     }
 
     ///
-    /// Attempts to add a verification for the given chunk ID from the given participant.
+    /// Returns the ID and the locators of the chunk locked by the given
+    /// participant in the current round, if any.
     ///
-    /// This function constructs the expected next challenge locator for the given participant
-    /// and chunk ID, and checks that the participant has uploaded the next challenge file and
-    /// contribution file signature prior to adding the verified contribution to the round state.
+    pub fn locked_locators(
+        &self,
+        participant: &Participant,
+    ) -> Result<Option<(u64, LockedLocators)>, CoordinatorError> {
+        Self::load_current_round(&self.storage)?.locked_locators(participant)
+    }
+
     ///
-    /// On success, this function releases the lock from the verifier.
+    /// Returns the locators issued to the given participant with the lock of
+    /// its chunk, against which the locators supplied by its client must be
+    /// checked: a client can't access the files of a chunk it didn't lock.
     ///
-    /// On failure, it returns a `CoordinatorError`.
+    /// If the participant holds no lock, returns `UnauthorizedChunkContributor`.
+    ///
+    pub fn issued_locators(&self, participant: &Participant) -> Result<LockedLocators, CoordinatorError> {
+        match self.locked_locators(participant)? {
+            Some((_, locked_locators)) => Ok(locked_locators),
+            None => {
+                warn!("{} supplied locators without holding a lock", participant);
+                Err(CoordinatorError::UnauthorizedChunkContributor)
+            }
+        }
+    }
+
+    ///
+    /// Returns the task the given participant is computing for the chunk it
+    /// locked. The task is assigned when the chunk is locked and persisted
+    /// with the state, so it stays the same across reconnections.
+    ///
+    pub fn pending_task(&self, participant: &Participant, chunk_id: u64) -> Result<Option<Task>, CoordinatorError> {
+        Ok(self.state.lookup_pending_task(participant, chunk_id)?.cloned())
+    }
+
+    ///
+    /// Attempts to acquire the lock to a chunk for the given participant.
+    ///
+    /// On failure, this function returns a `CoordinatorError`.
     ///
     #[tracing::instrument(
         level = "error",
-        skip(self, task),
+        skip(self),
         fields(participant = %participant),
         err
     )]
-    pub fn try_verify(&mut self, participant: &Participant, task: &Task) -> Result<(), CoordinatorError> {
-        // Check that the participant is a verifier.
-        if !participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedVerifier);
+    pub fn try_lock(&mut self, participant: &Participant) -> Result<(u64, LockedLocators), CoordinatorError> {
+        // Check that the participant is a contributor in the current round, and has not been dropped or finished.
+        self.check_current_contributor(participant)?;
+
+        // Check that the participant is not stale, it must be seen again before making progress.
+        if self.stale_contributor_eviction_time(participant).is_some() {
+            return Err(CoordinatorError::ParticipantStale);
         }
 
-        // Check that the chunk ID is valid.
-        if task.chunk_id() > self.environment.number_of_chunks() {
-            return Err(CoordinatorError::ChunkIdInvalid);
+        // A participant retrying a lock it already holds, e.g. after losing the response, gets the same locators.
+        if let Some((chunk_id, locked_locators)) = self.locked_locators(participant)? {
+            info!("{} already holds the lock on chunk {}", participant, chunk_id);
+            return Ok((chunk_id, locked_locators));
+        }
+
+        // Check that the intake of new contributors is not paused.
+        self.check_not_paused()?;
+
+        // Check that the verifier keeps up with the contributions.
+        self.check_verification_backlog()?;
+
+        // Check that the contributors that joined the queue before the participant locked their chunks, to serve the
+        // contributors in FIFO order.
+        if !self.state.is_head_of_current_round(participant) {
+            return Err(CoordinatorError::NotYourTurn);
         }
 
         // Check that the current round is not yet finished.
@@ -1198,2438 +2308,5749 @@ impl Coordinator {
             return Err(CoordinatorError::CurrentRoundAggregated);
         }
 
-        debug!(
-            "Adding verification from {} for chunk {} contribution {}",
-            participant,
-            task.chunk_id(),
-            task.contribution_id()
-        );
+        // Attempt to fetch the next chunk ID and contribution ID for the given participant.
+        let current_task = self.state.fetch_task(participant, self.time.as_ref())?;
+        trace!("Fetched task {} for {}", current_task, participant);
 
-        match self.verify_contribution(task, participant) {
-            // Case 1 - Participant verified contribution, return the response file locator.
-            Ok(contribution_id) => {
-                if contribution_id != task.contribution_id() {
-                    return Err(CoordinatorError::ContributionIdMismatch);
-                }
-                self.state.completed_task(participant, task, self.time.as_ref())?;
+        let round = Self::load_current_round(&self.storage)?;
+        let chunk = round.chunk(current_task.chunk_id())?;
+        if current_task.contribution_id() > (chunk.current_contribution_id() + 1) {
+            self.state
+                .rollback_pending_task(participant, current_task, &*self.time)?;
+            return Err(CoordinatorError::PreviousContributionMissing { current_task });
+        }
+
+        debug!("Locking chunk {} for {}", current_task.chunk_id(), participant);
+        match self.try_lock_chunk(current_task.chunk_id(), participant) {
+            // Case 1 - Participant acquired lock, return the locator.
+            Ok(locked_locators) => {
+                trace!("Incrementing the number of locks held by {}", participant);
+                self.state
+                    .acquired_lock(participant, current_task.chunk_id(), self.time.as_ref())?;
 
                 // Save the coordinator state in storage.
                 self.save_state()?;
 
-                info!("Added verification from {} for chunk {}", participant, task.chunk_id());
-                Ok(())
+                info!("Acquired lock on chunk {} for {}", current_task.chunk_id(), participant);
+                Ok((current_task.chunk_id(), locked_locators))
             }
-            // Case 2 - Participant failed to add their contribution, remove the contribution file.
+            // Case 2 - Participant failed to acquire the lock, put the chunk ID back.
             Err(error) => {
-                info!("Failed to add a verification and removing the contribution file");
-
-                // Fetch the current round from storage.
-                let round = Self::load_current_round(&self.storage)?;
+                info!("Failed to acquire lock for {}", participant);
 
-                // Fetch the next challenge locator.
-                let is_final_contribution = task.contribution_id() == round.expected_number_of_contributions() - 1;
-                let next_challenge = match is_final_contribution {
-                    true => Locator::ContributionFile(ContributionLocator::new(
-                        round.round_height() + 1,
-                        task.chunk_id(),
-                        0,
-                        true,
-                    )),
-                    false => Locator::ContributionFile(ContributionLocator::new(
-                        round.round_height(),
-                        task.chunk_id(),
-                        task.contribution_id(),
-                        true,
-                    )),
-                };
+                trace!("Adding task {} back to assigned tasks", current_task);
+                self.state
+                    .rollback_pending_task(participant, current_task, self.time.as_ref())?;
 
-                // Remove the invalid next challenge file from storage.
-                if self.storage.exists(&next_challenge) {
-                    self.storage.remove(&next_challenge)?;
-                }
+                // Save the coordinator state in storage.
+                self.save_state()?;
 
                 error!("{}", error);
-                Err(error)
+                return Err(error);
             }
         }
     }
 
-    ///
-    /// Attempts to aggregate the contributions of the current round of the ceremony.
-    ///
-    #[inline]
-    pub fn try_aggregate(&mut self) -> Result<(), CoordinatorError> {
-        // Check that the current round height matches in storage and self.
-        let current_round_height = {
-            // Fetch the current round height from storage.
-            let current_round_height_in_storage = Self::load_current_round_height(&self.storage)?;
-            trace!("Current round height in storage is {}", current_round_height_in_storage);
-
-            // Fetch the current round height from coordinator self.
-            let current_round_height = self.state.current_round_height();
-            trace!("Current round height in coordinator state is {}", current_round_height);
-
-            // Check that the current round height matches in storage and self.
-            match current_round_height_in_storage == current_round_height {
-                true => current_round_height,
-                false => return Err(CoordinatorError::RoundHeightMismatch),
-            }
-        };
-
-        // Check that the current round is finished by contributors and verifiers.
-        if !self.state.is_current_round_finished() {
-            error!("Coordinator state shows round {} is not finished", current_round_height);
-            return Err(CoordinatorError::RoundNotReady);
-        }
-
-        // Check that the current round has not been aggregated.
-        if self.state.is_current_round_aggregated() {
-            error!("Coordinator state shows round {} was aggregated", current_round_height);
-            return Err(CoordinatorError::RoundAlreadyAggregated);
-        }
-
-        // Update the coordinator state to set the start of aggregation for the current round.
-        self.state.aggregating_current_round(self.time.as_ref())?;
-
-        // Check if this is round 0, as coordinator may safely skip aggregation.
-        if current_round_height == 0 {
-            // Set the current round as aggregated in coordinator self.
-            self.state.aggregated_current_round(self.time.as_ref())?;
-
-            debug!("Coordinator is safely skipping aggregation for round 0");
-            return Ok(());
+    /// Returns previous contribution, current contribution and next contribution paths
+    pub fn get_chunk_locators_for_verifier(
+        &self,
+        participant: &Participant,
+        chunk_id: u64,
+        contribution_id: u64,
+    ) -> Result<LockedLocators, CoordinatorError> {
+        if !participant.is_verifier() {
+            return Err(CoordinatorError::ExpectedVerifier);
         }
+        let round = Self::load_current_round(&self.storage)?;
+        round.get_chunk_locators_for_verifier(&self.storage, participant, chunk_id, contribution_id)
+    }
 
-        // Attempt to aggregate the current round.
-        trace!("Trying to aggregate round {}", current_round_height);
-        match self.aggregate_contributions() {
-            // Case 1a - Coordinator aggregated the current round.
-            Ok(()) => {
-                info!("Coordinator has aggregated round {}", current_round_height);
-
-                // Set the current round as aggregated in coordinator self.
-                self.state.aggregated_current_round(self.time.as_ref())?;
-
-                // Check that the current round has now been aggregated.
-                if !self.state.is_current_round_aggregated() {
-                    error!("Coordinator state says round {} isn't aggregated", current_round_height);
-                    return Err(CoordinatorError::RoundAggregationFailed);
-                }
-
-                Ok(())
-            }
-            // Case 1b - Coordinator failed to aggregate the current round.
-            Err(error) => {
-                error!("Coordinator failed to aggregate the current round\n{}", error);
-
-                // Rollback the current round aggregation.
-                self.state.rollback_aggregating_current_round()?;
-
-                Err(error)
-            }
+    /// Initialize the files for the next challenge
+    pub fn initialize_verifier_response_files(
+        &mut self,
+        participant: &Participant,
+        chunk_id: u64,
+        locators: &LockedLocators,
+    ) -> Result<(), CoordinatorError> {
+        if !participant.is_verifier() {
+            return Err(CoordinatorError::ExpectedVerifier);
         }
+        let round = Self::load_current_round(&self.storage)?;
+        round.initialize_verifier_response_files(&self.environment, &mut self.storage, participant, chunk_id, locators)
     }
 
     ///
-    /// Attempts to advance the ceremony to the next round.
+    /// Writes the contribution of the given participant to the chunk it locked, along
+    /// with its signature, and adds it to the round. The contribution is `None` if it
+    /// has been uploaded in parts.
     ///
-    #[tracing::instrument(skip(self, started_at))]
-    pub fn try_advance(&mut self, started_at: OffsetDateTime) -> Result<u64, CoordinatorError> {
-        tracing::debug!("Trying to advance to the next round.");
-
-        // Check that the current round height matches in storage and self.
-        let current_round_height = {
-            // Fetch the current round height from storage.
-            let current_round_height_in_storage = Self::load_current_round_height(&self.storage)?;
-            debug!("Current round height in storage is {}", current_round_height_in_storage);
+    /// The lock is checked and the contribution written within the same call, i.e.
+    /// under the same write lock of the coordinator: a concurrent or retried request
+    /// for the same lock fails with `CoordinatorError::ChunkAlreadyComplete` once the
+    /// contribution has been added, instead of overwriting it.
+    ///
+    pub fn submit_contribution(
+        &mut self,
+        participant: &Participant,
+        chunk_id: u64,
+        contribution_locator: ContributionLocator,
+        contribution: Option<Vec<u8>>,
+        contribution_file_signature_locator: ContributionSignatureLocator,
+        contribution_file_signature: ContributionFileSignature,
+    ) -> Result<ContributionLocator, CoordinatorError> {
+        self.check_contribution_pending(participant, chunk_id)?;
 
-            // Fetch the current round height from coordinator self.
-            let current_round_height = self.state.current_round_height();
-            debug!("Current round height in coordinator state is {}", current_round_height);
+        match contribution {
+            Some(contribution) => self.write_contribution(contribution_locator, contribution)?,
+            None => self.write_uploaded_contribution(contribution_locator)?,
+        }
+        self.write_contribution_file_signature(contribution_file_signature_locator, contribution_file_signature)?;
 
-            // Check that the current round height matches in storage and self.
-            if current_round_height_in_storage == current_round_height {
-                current_round_height
-            } else {
-                tracing::error!(
-                    "Round height in storage ({}) does not match the \
-                    round height in coordinator state ({})",
-                    current_round_height_in_storage,
-                    current_round_height,
-                );
-                return Err(CoordinatorError::RoundHeightMismatch);
-            }
-        };
+        self.try_contribute(participant, chunk_id)
+    }
 
-        // Check that the current round is finished.
-        if !self.state.is_current_round_finished() {
-            return Err(CoordinatorError::CurrentRoundNotFinished);
+    /// Checks that the given participant still holds the lock on the chunk, i.e. that
+    /// its contribution has not been added yet.
+    fn check_contribution_pending(&self, participant: &Participant, chunk_id: u64) -> Result<(), CoordinatorError> {
+        let round = Self::load_current_round(&self.storage)?;
+        if round.is_chunk_locked_by(chunk_id, participant) {
+            return Ok(());
         }
 
-        // Check that the current round is aggregated, if this is not round 0.
-        if current_round_height > 0 && !self.state.is_current_round_aggregated() {
-            return Err(CoordinatorError::CurrentRoundNotAggregated);
+        let has_contributed = self.state.is_finished_contributor(participant)
+            || self.state.current_participant_info(participant).map_or(false, |info| {
+                info.completed_tasks().iter().any(|task| task.contains(chunk_id))
+            });
+        if has_contributed {
+            warn!("{} already contributed to chunk {}", participant, chunk_id);
+            return Err(CoordinatorError::ChunkAlreadyComplete);
         }
 
-        // Attempt to advance the round.
-        trace!("Running precommit for the next round");
-        let result = match self
-            .state
-            .precommit_next_round(current_round_height + 1, self.time.as_ref())
-        {
-            // Case 1 - Precommit succeed, attempt to advance the round.
-            Ok(contributors) => {
-                trace!("Trying to add advance to the next round");
-                match self.next_round(started_at, contributors) {
-                    // Case 1a - Coordinator advanced the round.
-                    Ok(next_round_height) => {
-                        // If success, update coordinator state to next round.
-                        info!("Coordinator has advanced to round {}", next_round_height);
-                        self.state.commit_next_round();
-                        Ok(next_round_height)
-                    }
-                    // Case 1b - Coordinator failed to advance the round.
-                    Err(error) => {
-                        // If failed, rollback coordinator state to the current round.
-                        error!("Coordinator failed to advance the next round, performing state rollback");
-                        self.state.rollback_next_round(self.time.as_ref());
-                        error!("{}", error);
-                        Err(error)
-                    }
-                }
-            }
-            // Case 2 - Precommit failed, roll back precommit.
-            Err(error) => {
-                // If failed, rollback coordinator state to the current round.
-                error!("Precommit for next round has failed, performing state rollback");
-                self.state.rollback_next_round(self.time.as_ref());
-                error!("{}", error);
-                Err(error)
-            }
-        };
-
-        // Save the coordinator state in storage.
-        self.save_state()?;
-
-        result
+        Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant)
     }
 
     ///
-    /// Returns the chunk ID from the given contribution file locator path.
-    ///
-    /// If the given locator path is invalid, returns a `CoordinatorError`.
+    /// Issues the receipt of an accepted contribution, with the given hash of the
+    /// contribution file, signed with the coordinator keypair.
     ///
-    #[inline]
-    pub fn contribution_locator_to_chunk_id(&self, locator_path: &LocatorPath) -> Result<u64, CoordinatorError> {
-        // Fetch the chunk ID corresponding to the given locator path.
-        let locator = self.storage.to_locator(&locator_path)?;
-        match &locator {
-            Locator::ContributionFile(contribution_locator) => match self.storage.exists(&locator) {
-                true => Ok(contribution_locator.chunk_id()),
-                false => Err(CoordinatorError::ContributionLocatorMissing),
-            },
-            _ => Err(CoordinatorError::ContributionLocatorIncorrect),
-        }
+    pub fn contribution_receipt(
+        &self,
+        contribution_locator: &ContributionLocator,
+        contribution_hash: String,
+    ) -> Result<ContributionReceipt, CoordinatorError> {
+        let mut receipt = ContributionReceipt {
+            round_height: contribution_locator.round_height(),
+            contribution_id: contribution_locator.contribution_id(),
+            contribution_hash,
+            accepted_at: self.time.now_utc(),
+            coordinator_signature: String::new(),
+        };
+        receipt.coordinator_signature = self.sign_message(&receipt.signature_message()?)?;
+
+        Ok(receipt)
     }
 
-    /// Convert a locator to a path string using the coordinator's
-    /// storage layer.
-    pub fn locator_to_path(&self, locator: Locator) -> Result<LocatorPath, CoordinatorError> {
-        self.storage.to_path(&locator)
+    ///
+    /// Signs the given message with the coordinator keypair, e.g. a response to the
+    /// contributors, which can check it against the public key of the coordinator.
+    ///
+    pub fn sign_message(&self, message: &str) -> Result<String, CoordinatorError> {
+        Ok(self
+            .signature
+            .sign(&self.environment.default_verifier_signing_key(), message)?)
     }
 
     ///
-    /// Attempts to acquire the lock for a given chunk ID and
-    /// participant.
+    /// Attempts to add a contribution for the given chunk ID from the given participant.
     ///
-    /// **Important**: The returned next contribution locator does not
-    /// always match the current task being performed. If it is the
-    /// final verification for a chunk, the a [Participant::Verifier]
-    /// will receive a locator to the contribution 0 of the same chunk
-    /// in the next round.
+    /// This function constructs the expected response locator for the given participant
+    /// and chunk ID, and checks that the participant has uploaded the response file and
+    /// contribution file signature prior to adding the unverified contribution to the
+    /// round self.
     ///
-    /// On failure, this function returns a `CoordinatorError`.
+    /// On success, this function releases the lock from the contributor and returns
+    /// the response file locator.
     ///
-    pub(crate) fn try_lock_chunk(
+    /// On failure, it returns a `CoordinatorError`.
+    ///
+    #[tracing::instrument(
+        level = "error",
+        skip(self, participant, chunk_id),
+        fields(participant = %participant, chunk = chunk_id),
+        err
+    )]
+    pub fn try_contribute(
         &mut self,
-        chunk_id: u64,
         participant: &Participant,
-    ) -> Result<LockedLocators, CoordinatorError> {
+        chunk_id: u64,
+    ) -> Result<ContributionLocator, CoordinatorError> {
+        // Check that the participant is a contributor in the current round, and has not been dropped or finished.
+        self.check_current_contributor(participant)?;
+
         // Check that the chunk ID is valid.
         if chunk_id > self.environment.number_of_chunks() {
             return Err(CoordinatorError::ChunkIdInvalid);
         }
 
-        // Fetch the current round height from storage.
-        let current_round_height = Self::load_current_round_height(&self.storage)?;
-        trace!("Current round height from storage is {}", current_round_height);
+        // Check that the participant is not stale, it must be seen again before making progress.
+        if self.stale_contributor_eviction_time(participant).is_some() {
+            return Err(CoordinatorError::ParticipantStale);
+        }
 
-        // Fetch the current round from storage.
-        let mut round = Self::load_current_round(&self.storage)?;
+        // Check that the current round is not yet finished.
+        if self.state.is_current_round_finished() {
+            return Err(CoordinatorError::CurrentRoundFinished);
+        }
 
-        // Attempt to acquire the chunk lock for participant.
-        trace!("Preparing to lock chunk {}", chunk_id);
-        let locked_locators = round.try_lock_chunk(&self.environment, &mut self.storage, chunk_id, &participant)?;
-        trace!("Participant {} locked chunk {}", participant, chunk_id);
+        // Check that the current round is not yet aggregating.
+        if self.state.is_current_round_aggregating() {
+            return Err(CoordinatorError::CurrentRoundAggregating);
+        }
 
-        // Add the updated round to storage.
-        match self.storage.update(
-            &Locator::RoundState {
-                round_height: current_round_height,
-            },
-            Object::RoundState(round),
-        ) {
-            Ok(_) => {
-                debug!("{} acquired lock on chunk {}", participant, chunk_id);
-                Ok(locked_locators)
-            }
-            _ => Err(CoordinatorError::StorageUpdateFailed),
+        // Check that the current round is not yet aggregated.
+        if self.state.is_current_round_aggregated() {
+            return Err(CoordinatorError::CurrentRoundAggregated);
         }
-    }
-
-    ///
-    /// Attempts to add a contribution for a given chunk ID from a given participant.
-    ///
-    /// This function checks that the participant is a contributor and has uploaded
-    /// a valid response file to the coordinator. The coordinator sanity checks
-    /// (however, does not verify) the contribution before accepting the response file.
-    ///
-    /// On success, this function releases the chunk lock from the contributor and
-    /// returns the response file locator and contribution ID of the response file.
-    ///
-    /// On failure, it returns a `CoordinatorError`.
-    ///
-    #[inline]
-    pub(crate) fn add_contribution(
-        &mut self,
-        chunk_id: u64,
-        participant: &Participant,
-    ) -> Result<(ContributionLocator, u64), CoordinatorError> {
-        debug!("Adding contribution from {} to chunk {}", participant, chunk_id);
 
         // Fetch the current round height from storage.
-        let current_round_height = Self::load_current_round_height(&self.storage)?;
-        trace!("Current round height from storage is {}", current_round_height);
+        let round_height = Self::load_current_round_height(&self.storage)?;
+        trace!("Current round height in storage is {}", round_height);
 
-        // Fetch the current round from storage.
-        let mut round = Self::load_current_round(&self.storage)?;
-        {
-            // Check that the participant is an authorized contributor to the current round.
-            if !round.is_contributor(participant) {
-                error!("{} is unauthorized to contribute to chunk {})", participant, chunk_id);
-                return Err(CoordinatorError::UnauthorizedChunkContributor);
+        // Check if the participant should dispose the response being contributed.
+        if let Some(task) = self.state.lookup_disposing_task(participant, chunk_id)?.cloned() {
+            let contribution_id = task.contribution_id();
+
+            // Move the task to the disposed tasks of the contributor.
+            self.state.disposed_task(participant, &task, self.time.as_ref())?;
+
+            // Remove the response file from storage.
+            let response = ContributionLocator::new(round_height, chunk_id, contribution_id, false);
+            self.storage.remove(&Locator::ContributionFile(response.clone()))?;
+
+            // Blacklist participant's token and ip
+            if *TOKEN_BLACKLIST {
+                self.state.blacklist_participant_token(participant)?;
             }
 
-            // Check that the chunk lock is currently held by this contributor.
-            if !round.is_chunk_locked_by(chunk_id, participant) {
-                error!("{} should have lock on chunk {} but does not", participant, chunk_id);
-                return Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant);
+            if *IP_BAN {
+                self.state.blacklist_participant_ip(participant)?;
             }
-        }
 
-        // Fetch the expected number of contributions for the current round.
-        let expected_num_contributions = round.expected_number_of_contributions();
-        // Fetch the chunk corresponding to the given chunk ID.
-        let chunk = round.chunk(chunk_id)?;
-        // Fetch the next contribution ID of the chunk.
-        let contribution_id = chunk.next_contribution_id(expected_num_contributions)?;
+            // Save the coordinator state in storage.
+            self.save_state()?;
 
-        // Check that the next contribution ID is one above the current contribution ID.
-        if !chunk.is_next_contribution_id(contribution_id, expected_num_contributions) {
-            return Err(CoordinatorError::ContributionIdMismatch);
-        }
+            debug!("Removing lock for disposed task {} {}", chunk_id, contribution_id);
 
-        // Fetch the challenge, response, and contribution file signature locators.
-        let challenge_file_locator = Locator::ContributionFile(ContributionLocator::new(
-            current_round_height,
-            chunk_id,
-            chunk.current_contribution_id(),
-            true,
-        ));
-        let response_file_locator = ContributionLocator::new(current_round_height, chunk_id, contribution_id, false);
-        let contribution_file_signature_locator = Locator::ContributionFileSignature(
-            ContributionSignatureLocator::new(current_round_height, chunk_id, contribution_id, false),
-        );
+            // Fetch the current round from storage.
+            let mut round = Self::load_current_round(&self.storage)?;
 
-        // Check the challenge-response hash chain.
-        let (challenge_hash, response_hash) = {
-            // Compute the challenge hash using the challenge file.
-            let challenge_reader = self.storage.reader(&challenge_file_locator)?;
-            let challenge_hash = calculate_hash(challenge_reader.as_ref());
-            info!(
-                "Challenge is located in {}",
-                self.storage.to_path(&challenge_file_locator)?
-            );
+            // TODO (raychu86): Move this unsafe call out of `try_contribute`.
+            // Release the lock on this chunk from the contributor.
+            round.chunk_mut(chunk_id)?.set_lock_holder_unsafe(None);
 
-            debug!("Challenge is {}", pretty_hash!(&challenge_reader));
-            debug!("Challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
+            // Save the updated round to storage.
+            self.storage.update(
+                &Locator::RoundState {
+                    round_height: round.round_height(),
+                },
+                Object::RoundState(round),
+            )?;
 
-            // Compute the response hash.
-            let response_reader = self.storage.reader(&Locator::ContributionFile(response_file_locator))?;
-            let response_hash = calculate_hash(response_reader.as_ref());
-            info!(
-                "Response is located in {}",
-                self.storage
-                    .to_path(&Locator::ContributionFile(response_file_locator))?
-            );
-            debug!("Response is {}", pretty_hash!(&response_reader));
-            info!("Response hash is {}", pretty_hash!(&response_hash.as_slice()));
+            return Ok(response);
+        }
 
-            // Fetch the challenge hash from the response file.
-            let challenge_hash_in_response = &response_reader
-                .get(0..64)
-                .ok_or(CoordinatorError::StorageReaderFailed)?[..];
-            let pretty_hash = pretty_hash!(&challenge_hash_in_response);
+        // Check if the participant has this chunk ID in a pending task.
+        if let Some(task) = self.state.lookup_pending_task(participant, chunk_id)?.cloned() {
+            debug!("Adding contribution for chunk");
 
-            // Check the starting hash in the response file is based on the challenge.
-            info!("The challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
-            info!("The challenge hash in response file is {}", pretty_hash);
-            if challenge_hash_in_response != challenge_hash.as_slice() {
-                error!("Challenge hash in response file does not match the expected challenge hash.");
-                return Err(CoordinatorError::ContributionHashMismatch);
-            }
+            match self.add_contribution(chunk_id, participant) {
+                // Case 1 - Participant added contribution, return the response file locator.
+                Ok((locator, contribution_id)) => {
+                    trace!("Release the lock on chunk");
+                    let completed_task = Task::new(chunk_id, contribution_id);
+                    self.state
+                        .completed_task(participant, &completed_task, self.time.as_ref())?;
 
-            (challenge_hash, response_hash)
-        };
+                    // Blacklist participant's token and ip
+                    if *TOKEN_BLACKLIST {
+                        self.state.blacklist_participant_token(participant)?;
+                    }
 
-        // Check the challenge-response contribution signature.
-        {
-            // Fetch the stored contribution file signature.
-            let contribution_file_signature: ContributionFileSignature =
-                serde_json::from_slice(&*self.storage.reader(&contribution_file_signature_locator)?)?;
+                    if *IP_BAN {
+                        self.state.blacklist_participant_ip(participant)?;
+                    }
 
-            // Check that the contribution file signature is valid.
-            let address = &participant.to_string();
+                    // Save the coordinator state in storage.
+                    self.save_state()?;
 
-            let address = address
-                .split(".")
-                .next()
-                .expect("splitting a string should yield at least one item");
+                    info!("Added contribution");
+                    return Ok(locator);
+                }
+                // Case 2 - Participant failed to add their contribution, remove the contribution file.
+                Err(error) => {
+                    info!("Failed to add a contribution and removing the contribution file");
+                    // Remove the invalid response file from storage.
+                    let response = Locator::ContributionFile(ContributionLocator::new(
+                        round_height,
+                        chunk_id,
+                        task.contribution_id(),
+                        false,
+                    ));
+                    self.storage.remove(&response)?;
 
-            if !self.signature.verify(
-                &address,
-                &serde_json::to_string(&contribution_file_signature.get_state())?,
-                contribution_file_signature.get_signature(),
-            ) {
-                error!("Contribution file signature failed to verify for {}", participant);
-                return Err(CoordinatorError::ContributorSignatureInvalid);
+                    error!("{}", error);
+                    return Err(error);
+                }
             }
+        }
 
-            // Check that the contribution file signature challenge hash is correct.
-            if hex::decode(contribution_file_signature.get_challenge_hash())? != challenge_hash.as_slice() {
-                error!("The signed challenge hash does not match the expected challenge hash.");
-                return Err(CoordinatorError::ContributionHashMismatch);
-            }
+        Err(CoordinatorError::ContributionFailed)
+    }
 
-            // Check that the contribution file signature response hash is correct.
-            if hex::decode(contribution_file_signature.get_response_hash())? != response_hash.as_slice() {
-                error!("The signed response hash does not match the expected response hash.");
-                return Err(CoordinatorError::ContributionHashMismatch);
-            }
+    ///
+    /// Attempts to add a verification for the given chunk ID from the given participant.
+    ///
+    /// This function constructs the expected next challenge locator for the given participant
+    /// and chunk ID, and checks that the participant has uploaded the next challenge file and
+    /// contribution file signature prior to adding the verified contribution to the round state.
+    ///
+    /// On success, this function releases the lock from the verifier.
+    ///
+    /// On failure, it returns a `CoordinatorError`.
+    ///
+    #[tracing::instrument(
+        level = "error",
+        skip(self, task),
+        fields(participant = %participant),
+        err
+    )]
+    pub fn try_verify(&mut self, participant: &Participant, task: &Task) -> Result<(), CoordinatorError> {
+        // Check that the participant is an authorized verifier of the current round.
+        self.check_current_verifier(participant)?;
 
-            // Check that the contribution file signature next challenge hash does not exist.
-            if contribution_file_signature.get_next_challenge_hash().is_some() {
-                error!("The signed next challenge hash should not exist");
-                return Err(CoordinatorError::NextChallengeHashAlreadyExists);
-            }
+        // Check that the chunk ID is valid.
+        if task.chunk_id() > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
         }
 
-        // Add the contribution response to the current chunk.
-        round.chunk_mut(chunk_id)?.add_contribution(
-            contribution_id,
-            participant,
-            self.storage
-                .to_path(&Locator::ContributionFile(response_file_locator))?,
-            self.storage.to_path(&contribution_file_signature_locator)?,
-        )?;
-
-        // Add the updated round to storage.
-        match self.storage.update(
-            &Locator::RoundState {
-                round_height: current_round_height,
-            },
-            Object::RoundState(round),
-        ) {
-            Ok(_) => {
-                debug!("Updated round {} in storage", current_round_height);
-                debug!("{} added a contribution to chunk {}", participant, chunk_id);
-                Ok((response_file_locator, contribution_id))
-            }
-            _ => Err(CoordinatorError::StorageUpdateFailed),
+        // Check that the current round is not yet finished.
+        if self.state.is_current_round_finished() {
+            return Err(CoordinatorError::CurrentRoundFinished);
         }
-    }
 
-    #[inline]
-    pub(crate) fn get_challenge(
-        &self,
-        round_height: u64,
-        chunk_id: u64,
-        contribution_id: u64,
-        is_verified: bool,
-    ) -> Result<Vec<u8>, CoordinatorError> {
-        // Fetch the challenge, response, and contribution file signature locators.
-        let challenge_file_locator = Locator::ContributionFile(ContributionLocator::new(
-            round_height,
-            chunk_id,
-            contribution_id,
-            is_verified,
-        ));
-        // Get the challenge from the challenge file locator
-        let challenge_reader = self.storage.reader(&challenge_file_locator)?;
+        // Check that the current round is not yet aggregating.
+        if self.state.is_current_round_aggregating() {
+            return Err(CoordinatorError::CurrentRoundAggregating);
+        }
 
-        Ok(challenge_reader.to_vec())
-    }
+        // Check that the current round is not yet aggregated.
+        if self.state.is_current_round_aggregated() {
+            return Err(CoordinatorError::CurrentRoundAggregated);
+        }
 
-    /// Writes the bytes of a contribution to storage at the appropriate file
-    /// locator.
-    pub(crate) fn write_contribution<T>(
-        &mut self,
-        contribution_locator: ContributionLocator,
-        contribution: T,
-    ) -> Result<(), CoordinatorError>
-    where
-        T: Into<Vec<u8>>,
-    {
-        // Can use update instead of insert because the path is already initialized by other functions
-        self.storage.update(
-            &Locator::ContributionFile(contribution_locator),
-            Object::ContributionFile(contribution.into()),
-        )
-    }
+        debug!(
+            "Adding verification from {} for chunk {} contribution {}",
+            participant,
+            task.chunk_id(),
+            task.contribution_id()
+        );
 
-    /// Writes the contribution metadata to storage at the appropriate locator.
-    pub(crate) fn write_contribution_info(
-        &mut self,
-        contribution_info: ContributionInfo,
-    ) -> Result<(), CoordinatorError> {
-        self.storage.insert(
-            Locator::ContributionInfoFile {
-                round_height: contribution_info.ceremony_round,
-            },
-            Object::ContributionInfoFile(contribution_info),
-        )
-    }
+        match self.verify_contribution(task, participant) {
+            // Case 1 - Participant verified contribution, return the response file locator.
+            Ok(contribution_id) => {
+                if contribution_id != task.contribution_id() {
+                    return Err(CoordinatorError::ContributionIdMismatch);
+                }
+                self.state.completed_task(participant, task, self.time.as_ref())?;
 
-    /// Updates the contribution attestation and summary to storage at the appropriate locator.
-    pub(crate) fn update_contribution_info_attestation(
-        &mut self,
-        round: u64,
-        attestation: String,
-    ) -> Result<(), CoordinatorError> {
-        // Retrieve current file to update
-        let updated_info = match self
-            .storage
-            .get(&Locator::ContributionInfoFile { round_height: round })?
-        {
-            Object::ContributionInfoFile(info) => ContributionInfo {
-                attestation: Some(attestation),
-                ..info
-            },
-            _ => return Err(CoordinatorError::StorageFailed),
-        };
+                // Save the coordinator state in storage.
+                self.save_state()?;
 
-        // Persist update to storage
-        self.storage.update(
-            &Locator::ContributionInfoFile { round_height: round },
-            Object::ContributionInfoFile(updated_info.clone()),
-        )?;
+                info!("Added verification from {} for chunk {}", participant, task.chunk_id());
+                Ok(())
+            }
+            // Case 2 - Participant failed to add their contribution, remove the contribution file.
+            Err(error) => {
+                info!("Failed to add a verification and removing the contribution file");
 
-        // Update the summary
-        let mut summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
-            Object::ContributionsInfoSummary(summary) => summary,
-            _ => return Err(CoordinatorError::StorageFailed),
-        };
+                // Fetch the current round from storage.
+                let round = Self::load_current_round(&self.storage)?;
 
-        match summary.get_mut((round - 1) as usize) {
-            Some(t) => *t = updated_info.into(),
-            None => return Err(CoordinatorError::StorageFailed),
-        };
+                // Fetch the next challenge locator.
+                let is_final_contribution = task.contribution_id() == round.expected_number_of_contributions() - 1;
+                let next_challenge = match is_final_contribution {
+                    true => Locator::ContributionFile(ContributionLocator::new(
+                        round.round_height() + 1,
+                        task.chunk_id(),
+                        0,
+                        true,
+                    )),
+                    false => Locator::ContributionFile(ContributionLocator::new(
+                        round.round_height(),
+                        task.chunk_id(),
+                        task.contribution_id(),
+                        true,
+                    )),
+                };
 
-        // Persist update to storage
-        self.storage.update(
-            &Locator::ContributionsInfoSummary,
-            Object::ContributionsInfoSummary(summary),
-        )
+                // Remove the invalid next challenge file from storage.
+                if self.storage.exists(&next_challenge) {
+                    self.storage.remove(&next_challenge)?;
+                }
+
+                error!("{}", error);
+                Err(error)
+            }
+        }
     }
 
-    /// Appends current round summary to storage at the appropriate locator.
-    pub(crate) fn update_contribution_summary(
-        &mut self,
-        contribution_summary: TrimmedContributionInfo,
-    ) -> Result<(), CoordinatorError> {
-        let mut summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
-            Object::ContributionsInfoSummary(summary) => summary,
-            _ => return Err(CoordinatorError::StorageFailed),
+    ///
+    /// Attempts to aggregate the contributions of the current round of the ceremony.
+    ///
+    #[inline]
+    pub fn try_aggregate(&mut self) -> Result<(), CoordinatorError> {
+        // Check that the current round height matches in storage and self.
+        let current_round_height = {
+            // Fetch the current round height from storage.
+            let current_round_height_in_storage = Self::load_current_round_height(&self.storage)?;
+            trace!("Current round height in storage is {}", current_round_height_in_storage);
+
+            // Fetch the current round height from coordinator self.
+            let current_round_height = self.state.current_round_height();
+            trace!("Current round height in coordinator state is {}", current_round_height);
+
+            // Check that the current round height matches in storage and self.
+            match current_round_height_in_storage == current_round_height {
+                true => current_round_height,
+                false => return Err(CoordinatorError::RoundHeightMismatch),
+            }
         };
-        summary.push(contribution_summary);
 
-        self.storage.update(
-            &Locator::ContributionsInfoSummary,
-            Object::ContributionsInfoSummary(summary),
-        )
-    }
+        // Check that the current round is finished by contributors and verifiers.
+        if !self.state.is_current_round_finished() {
+            error!("Coordinator state shows round {} is not finished", current_round_height);
+            return Err(CoordinatorError::RoundNotReady);
+        }
 
-    /// Writes the bytes of a contribution file signature to storage at the appropriate  
-    /// locator. Signature of a contribution is computed client-side, so there's no way to use the provided
-    /// write_contribution_file_signature function.
-    pub(crate) fn write_contribution_file_signature(
-        &mut self,
-        locator: ContributionSignatureLocator,
-        contribution_file_signature: ContributionFileSignature,
-    ) -> Result<(), CoordinatorError> {
-        self.storage.update(
-            &Locator::ContributionFileSignature(locator),
-            Object::ContributionFileSignature(contribution_file_signature),
-        )
+        // Check that the current round has not been aggregated.
+        if self.state.is_current_round_aggregated() {
+            error!("Coordinator state shows round {} was aggregated", current_round_height);
+            return Err(CoordinatorError::RoundAlreadyAggregated);
+        }
+
+        // Update the coordinator state to set the start of aggregation for the current round.
+        self.state.aggregating_current_round(self.time.as_ref())?;
+
+        // Check if this is round 0, as coordinator may safely skip aggregation.
+        if current_round_height == 0 {
+            // Set the current round as aggregated in coordinator self.
+            self.state.aggregated_current_round(self.time.as_ref())?;
+
+            debug!("Coordinator is safely skipping aggregation for round 0");
+            return Ok(());
+        }
+
+        // Attempt to aggregate the current round.
+        trace!("Trying to aggregate round {}", current_round_height);
+        match self.aggregate_contributions() {
+            // Case 1a - Coordinator aggregated the current round.
+            Ok(()) => {
+                info!("Coordinator has aggregated round {}", current_round_height);
+
+                // Set the current round as aggregated in coordinator self.
+                self.state.aggregated_current_round(self.time.as_ref())?;
+
+                // Check that the current round has now been aggregated.
+                if !self.state.is_current_round_aggregated() {
+                    error!("Coordinator state says round {} isn't aggregated", current_round_height);
+                    return Err(CoordinatorError::RoundAggregationFailed);
+                }
+
+                Ok(())
+            }
+            // Case 1b - Coordinator failed to aggregate the current round.
+            Err(error) => {
+                error!("Coordinator failed to aggregate the current round\n{}", error);
+
+                // Rollback the current round aggregation.
+                self.state.rollback_aggregating_current_round()?;
+
+                Err(error)
+            }
+        }
     }
 
     ///
-    /// Attempts to run verification in the current round for a given
-    /// chunk ID and participant.
-    ///
-    /// This function checks that the participant is a verifier and
-    /// has uploaded a valid next challenge file to the coordinator.
-    /// The coordinator sanity checks that the next challenge file
-    /// contains the hash of the corresponding response file.
-    ///
-    /// This function stores the next challenge locator into the round
-    /// transcript and releases the chunk lock from the verifier.
-    ///
-    /// On success, this function returns the contribution ID of the
-    /// unverified response file.
+    /// Advances the ceremony to the next round, without waiting for the queue
+    /// wait time, if the time budget of the current round elapsed and the
+    /// round collected at least one verified contribution.
     ///
-    /// **Important**: if the contribution is the final contribution
-    /// for the chunk for the round, its verification will be stored
-    /// in the next round's directory as contribution 0.
+    /// Contributors still working on the round are not cut off, as dropping
+    /// them would reset the verified contributions. A round without verified
+    /// contributions is either extended for another time budget or, with
+    /// [`RoundBudgetPolicy::Skip`], its contributors are dropped and
+    /// [`CoordinatorError::RoundSkipped`] is returned.
     ///
-    #[tracing::instrument(
-        skip(self, participant),
-        fields(participant = %participant)
-    )]
-    pub(crate) fn verify_contribution(
-        &mut self,
-        task: &Task,
-        participant: &Participant,
-    ) -> Result<u64, CoordinatorError> {
-        let chunk_id = task.chunk_id();
-        debug!("Attempting to verify a contribution for chunk {}", chunk_id);
-        if !participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedVerifier);
+    pub fn try_advance_on_budget(&mut self) -> Result<Option<u64>, CoordinatorError> {
+        let budget = match self.environment.round_time_budget() {
+            Some(budget) => budget,
+            None => return Ok(None),
+        };
+
+        // Round 0 has no contributions, the ceremony starts once the queue is ready
+        let current_round_height = self.state.current_round_height();
+        if current_round_height == 0 {
+            return Ok(None);
         }
 
-        // Fetch the current round height from storage.
-        let current_round_height = Self::load_current_round_height(&self.storage)?;
-        trace!("Current round height from storage is {}", current_round_height);
+        let now = self.time.now_utc();
+        let round = self.current_round()?;
+        // An extended budget outlasts the one from the start of the round, unless the round was restarted since
+        let round_deadline = round.started_at().unwrap_or(now) + budget;
+        let deadline = match self.round_deadline {
+            Some((round_height, deadline)) if round_height == current_round_height => {
+                std::cmp::max(deadline, round_deadline)
+            }
+            _ => round_deadline,
+        };
+        self.round_deadline = Some((current_round_height, deadline));
+        if now < deadline {
+            return Ok(None);
+        }
 
-        // Fetch the current round from storage.
-        let mut round = Self::load_current_round(&self.storage)?;
+        let verified_contributions = round
+            .chunks()
+            .iter()
+            .flat_map(|chunk| chunk.get_contributions())
+            .filter(|(contribution_id, contribution)| **contribution_id != 0 && contribution.is_verified())
+            .count();
+        if verified_contributions == 0 {
+            self.round_deadline = Some((current_round_height, now + budget));
+
+            let contributors = self.state.current_contributors();
+            if self.environment.round_budget_policy() == RoundBudgetPolicy::Extend || contributors.is_empty() {
+                info!(
+                    "Round {} has no verified contribution at the end of its time budget, extending it",
+                    current_round_height
+                );
+                return Ok(None);
+            }
 
-        // Fetch the chunk corresponding to the given chunk ID.
-        let chunk = round.chunk(chunk_id)?;
-        // Fetch the current contribution ID.
-        let contribution_id = chunk.current_contribution_id();
-        if contribution_id != task.contribution_id() {
-            return Err(CoordinatorError::ContributionIdMismatch);
+            warn!(
+                "Round {} has no verified contribution at the end of its time budget, skipping its contributors",
+                current_round_height
+            );
+            for (contributor, _) in contributors {
+                self.drop_participant(&contributor)?;
+            }
+
+            return Err(CoordinatorError::RoundSkipped);
         }
 
-        // Fetch the challenge, response, next challenge, and contribution file signature locators.
-        let challenge_file_locator = Locator::ContributionFile(ContributionLocator::new(
-            current_round_height,
-            chunk_id,
-            contribution_id - 1,
-            true,
-        ));
-        let response_file_locator = Locator::ContributionFile(ContributionLocator::new(
-            current_round_height,
-            chunk_id,
-            contribution_id,
-            false,
-        ));
-        let (next_challenge_locator, contribution_file_signature_locator) = {
-            // Fetch whether this is the final contribution of the specified chunk.
-            let is_final_contribution = chunk.only_contributions_complete(round.expected_number_of_contributions());
-            match is_final_contribution {
-                true => (
-                    Locator::ContributionFile(ContributionLocator::new(current_round_height + 1, chunk_id, 0, true)),
-                    Locator::ContributionFileSignature(ContributionSignatureLocator::new(
-                        current_round_height + 1,
-                        chunk_id,
-                        0,
-                        true,
-                    )),
-                ),
-                false => (
-                    Locator::ContributionFile(ContributionLocator::new(
-                        current_round_height,
-                        chunk_id,
-                        contribution_id,
-                        true,
-                    )),
-                    Locator::ContributionFileSignature(ContributionSignatureLocator::new(
-                        current_round_height,
-                        chunk_id,
-                        contribution_id,
-                        true,
-                    )),
-                ),
-            }
-        };
-
-        // Check the challenge-response hash chain.
-        let (challenge_hash, response_hash) = {
-            // Compute the challenge hash using the challenge file.
-            let challenge_reader = self.storage.reader(&challenge_file_locator)?;
-            let challenge_hash = calculate_hash(challenge_reader.as_ref());
-            trace!(
-                "Challenge is located in {}",
-                self.storage.to_path(&challenge_file_locator)?
-            );
-            debug!("Challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
-
-            // Compute the response hash.
-            let response_reader = self.storage.reader(&response_file_locator)?;
-            let response_hash = calculate_hash(response_reader.as_ref());
-            trace!(
-                "Response is located in {}",
-                self.storage.to_path(&response_file_locator)?
+        if !self.state.is_current_round_finished() || !self.state.is_current_round_aggregated() {
+            debug!(
+                "Round {} is over its time budget, waiting for its contributions to complete",
+                current_round_height
             );
-            info!("Response hash is {}", pretty_hash!(&response_hash.as_slice()));
-
-            // Fetch the challenge hash from the response file.
-            let challenge_hash_in_response = &response_reader
-                .get(0..64)
-                .ok_or(CoordinatorError::StorageReaderFailed)?[..];
-            let pretty_hash = pretty_hash!(&challenge_hash_in_response);
-
-            // Check the starting hash in the response file is based on the challenge.
-            info!("The challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
-            info!("The challenge hash in response file is {}", pretty_hash);
-            if challenge_hash_in_response != challenge_hash.as_slice() {
-                error!("Challenge hash in response file does not match the expected challenge hash.");
-                return Err(CoordinatorError::ContributionHashMismatch);
-            }
-
-            (challenge_hash, response_hash)
-        };
+            return Ok(None);
+        }
 
-        // Check the response-next_challenge hash chain.
-        let next_challenge_hash = {
-            // Compute the next challenge hash.
-            let next_challenge_reader = self.storage.reader(&next_challenge_locator)?;
-            let next_challenge_hash = calculate_hash(next_challenge_reader.as_ref());
-            trace!(
-                "Next challenge is located in {}",
-                self.storage.to_path(&next_challenge_locator)?
-            );
+        // The budget takes over the queue wait time
+        self.state.expire_queue_wait_time(self.time.as_ref());
+        if !self.state.is_precommit_next_round_ready(self.time.as_ref()) {
             debug!(
-                "Next challenge hash is {}",
-                pretty_hash!(&next_challenge_hash.as_slice())
+                "Round {} is over its time budget, waiting for contributors to join the queue",
+                current_round_height
             );
+            return Ok(None);
+        }
 
-            // Fetch the saved response hash in the next challenge file.
-            let saved_response_hash = next_challenge_reader.as_ref().chunks(64).next().unwrap().to_vec();
-            let pretty_hash = pretty_hash!(&saved_response_hash);
-
-            // Check that the response hash matches the next challenge hash.
-            info!("The response hash is {}", pretty_hash!(&response_hash));
-            info!("The response hash in next challenge file is {}", pretty_hash);
-            if response_hash.as_slice() != saved_response_hash {
-                error!("Response hash does not match the saved response hash.");
-                return Err(CoordinatorError::ContributionHashMismatch);
-            }
-
-            next_challenge_hash
-        };
+        self.try_advance(now).map(Some)
+    }
 
-        // Check the response-next_challenge contribution signature.
+    ///
+    /// Skips the current round, which must not have collected any contribution,
+    /// e.g. for a cohort without participants, and advances the ceremony to the
+    /// next round.
+    ///
+    /// The round is marked as skipped in storage and its initial challenges are
+    /// carried over to the next round, in place of the aggregation. Contributors
+    /// of the skipped round are moved back to the queue for the next round. If
+    /// the next round can't start yet, e.g. no contributor is queued for it, the
+    /// round stays skipped and the ceremony advances on a later update.
+    ///
+    /// On success, the function returns the new round height.
+    ///
+    pub fn skip_round(&mut self) -> Result<u64, CoordinatorError> {
         {
-            // Fetch the stored contribution file signature.
-            let contribution_file_signature: ContributionFileSignature =
-                serde_json::from_slice(&*self.storage.reader(&contribution_file_signature_locator)?)?;
+            let _transition = self.round_transition.try_begin()?;
 
-            // Check that the contribution file signature is valid.
-            let address = &participant.to_string();
+            // Round 0 has no contributions and advances without aggregation already.
+            let current_round_height = self.state.current_round_height();
+            if current_round_height == 0 {
+                return Err(CoordinatorError::RoundHeightIsZero);
+            }
+            if Self::load_current_round_height(&self.storage)? != current_round_height {
+                return Err(CoordinatorError::RoundHeightMismatch);
+            }
 
-            let address = address
-                .split(".")
-                .next()
-                .expect("splitting a string should yield at least one item");
+            let mut round = self.current_round()?;
+            if !round.is_skipped() {
+                // The contributors holding a lock may be about to upload their contribution.
+                if round.chunks().iter().any(|chunk| chunk.is_locked()) {
+                    error!("Round {} has locked chunks and can't be skipped", current_round_height);
+                    return Err(CoordinatorError::RoundHasLockedChunks);
+                }
 
-            if !self.signature.verify(
-                &address,
-                &serde_json::to_string(&contribution_file_signature.get_state())?,
-                contribution_file_signature.get_signature(),
-            ) {
-                error!("Contribution file signature failed to verify for {}", participant);
-                return Err(CoordinatorError::VerifierSignatureInvalid);
-            }
+                // Skipping a round with partial contributions would discard them.
+                if round.chunks().iter().any(|chunk| chunk.get_contributions().len() > 1) {
+                    error!("Round {} has contributions and can't be skipped", current_round_height);
+                    return Err(CoordinatorError::RoundHasContributions);
+                }
 
-            // Check that the contribution file signature challenge hash is correct.
-            if hex::decode(contribution_file_signature.get_challenge_hash())? != challenge_hash.as_slice() {
-                error!("The signed challenge hash does not match the expected challenge hash.");
-                return Err(CoordinatorError::ContributionHashMismatch);
-            }
+                // Carry the initial challenge of each chunk over to the next round.
+                for chunk_id in 0..self.environment.number_of_chunks() {
+                    self.storage.copy(
+                        &Locator::ContributionFile(ContributionLocator::new(current_round_height, chunk_id, 0, true)),
+                        &Locator::ContributionFile(ContributionLocator::new(
+                            current_round_height + 1,
+                            chunk_id,
+                            0,
+                            true,
+                        )),
+                    )?;
 
-            // Check that the contribution file signature response hash is correct.
-            if hex::decode(contribution_file_signature.get_response_hash())? != response_hash.as_slice() {
-                error!("The signed response hash does not match the expected response hash.");
-                return Err(CoordinatorError::ContributionHashMismatch);
-            }
+                    let signature_locator = Locator::ContributionFileSignature(ContributionSignatureLocator::new(
+                        current_round_height,
+                        chunk_id,
+                        0,
+                        true,
+                    ));
+                    if self.storage.exists(&signature_locator) {
+                        self.storage.copy(
+                            &signature_locator,
+                            &Locator::ContributionFileSignature(ContributionSignatureLocator::new(
+                                current_round_height + 1,
+                                chunk_id,
+                                0,
+                                true,
+                            )),
+                        )?;
+                    }
+                }
 
-            // Check that the contribution file signature next challenge hash exists.
-            if contribution_file_signature.get_next_challenge_hash().is_none() {
-                error!("The signed next challenge hash is missing");
-                return Err(CoordinatorError::NextChallengeHashMissing);
+                // The carried over challenges stand for the aggregation of the round, so that its transcript is
+                // published like the one of any completed round.
+                round.set_skipped();
+                round.set_aggregate_hash(Self::compute_aggregate_hash(
+                    &self.environment,
+                    &self.storage,
+                    current_round_height,
+                )?);
+                self.storage.update(
+                    &Locator::RoundState {
+                        round_height: current_round_height,
+                    },
+                    Object::RoundState(round),
+                )?;
+                warn!("Skipped round {}", current_round_height);
             }
 
-            // Check that the contribution file signature next challenge hash is correct.
-            let signed_next_challenge_hash = contribution_file_signature
-                .get_next_challenge_hash()
-                .as_ref()
-                .ok_or(CoordinatorError::NextChallengeHashMissing)?;
-            if hex::decode(signed_next_challenge_hash)? != next_challenge_hash.as_slice() {
-                error!("The signed next challenge hash does not match the expected next challenge hash.");
-                return Err(CoordinatorError::ContributionHashMismatch);
+            self.state.requeue_current_contributors(self.time.as_ref());
+            if !self.state.is_current_round_finished() {
+                return Err(CoordinatorError::CurrentRoundNotFinished);
             }
-        }
-
-        // Sets the current contribution as verified in the current round.
-        round.verify_contribution(
-            chunk_id,
-            contribution_id,
-            participant.clone(),
-            self.storage.to_path(&next_challenge_locator)?,
-            self.storage.to_path(&contribution_file_signature_locator)?,
-        )?;
 
-        // Add the updated round to storage.
-        match self.storage.update(
-            &Locator::RoundState {
-                round_height: current_round_height,
-            },
-            Object::RoundState(round),
-        ) {
-            Ok(_) => {
-                debug!("Updated round {} in storage", current_round_height);
-                debug!(
-                    "{} verified chunk {} contribution {}",
-                    participant, chunk_id, contribution_id
-                );
-                Ok(contribution_id)
+            // The skipped round stands for an aggregated one in the coordinator state, the next round starts
+            // without waiting for the queue.
+            if !self.state.is_current_round_aggregated() {
+                self.state.aggregating_current_round(self.time.as_ref())?;
+                self.state.aggregated_current_round(self.time.as_ref())?;
             }
-            _ => Err(CoordinatorError::StorageUpdateFailed),
+            self.state.expire_queue_wait_time(self.time.as_ref());
+            self.save_state()?;
         }
+
+        let started_at = self.time.now_utc();
+        self.try_advance(started_at)
     }
 
     ///
-    /// Aggregates the contributions for the current round of the ceremony.
-    ///
-    /// This function loads the current round from storage and checks that
-    /// it is fully verified before proceeding to aggregate the round, and
-    /// initialize the next round, saving it to storage for the coordinator.
-    ///
-    /// On success, the function returns the new round height.
-    /// Otherwise, it returns a `CoordinatorError`.
+    /// Attempts to advance the ceremony to the next round.
     ///
-    #[inline]
-    pub(crate) fn aggregate_contributions(&mut self) -> Result<(), CoordinatorError> {
-        // Fetch the current round height from storage.
-        let current_round_height = Self::load_current_round_height(&self.storage)?;
+    #[tracing::instrument(skip(self, started_at))]
+    pub fn try_advance(&mut self, started_at: OffsetDateTime) -> Result<u64, CoordinatorError> {
+        tracing::debug!("Trying to advance to the next round.");
+        let _transition = self.round_transition.try_begin()?;
 
-        trace!("Current round height from storage is {}", current_round_height);
+        // Check that the current round height matches in storage and self.
+        let current_round_height = {
+            // Fetch the current round height from storage.
+            let current_round_height_in_storage = Self::load_current_round_height(&self.storage)?;
+            debug!("Current round height in storage is {}", current_round_height_in_storage);
 
-        // This function executes aggregation of the current round in preparation
-        // for transitioning to the next round. If this is the initial round,
-        // there should be nothing to aggregate and we return a `CoordinatorError`.
-        if current_round_height == 0 {
-            error!("Unauthorized to aggregate round 0");
-            return Err(CoordinatorError::RoundDoesNotExist);
-        }
+            // Fetch the current round height from coordinator self.
+            let current_round_height = self.state.current_round_height();
+            debug!("Current round height in coordinator state is {}", current_round_height);
 
-        // Check that the current round state exists in storage.
-        if !self.storage.exists(&Locator::RoundState {
-            round_height: current_round_height,
-        }) {
-            return Err(CoordinatorError::RoundStateMissing);
-        }
+            // Check that the current round height matches in storage and self.
+            if current_round_height_in_storage == current_round_height {
+                current_round_height
+            } else {
+                tracing::error!(
+                    "Round height in storage ({}) does not match the \
+                    round height in coordinator state ({})",
+                    current_round_height_in_storage,
+                    current_round_height,
+                );
+                return Err(CoordinatorError::RoundHeightMismatch);
+            }
+        };
 
-        // Check that the next round state does not exist in storage.
-        if self.storage.exists(&Locator::RoundState {
-            round_height: current_round_height + 1,
-        }) {
-            return Err(CoordinatorError::RoundShouldNotExist);
+        // Check that the current round is finished.
+        if !self.state.is_current_round_finished() {
+            return Err(CoordinatorError::CurrentRoundNotFinished);
         }
 
-        // Check that the current round file does not exist.
-        let round_file = Locator::RoundFile {
-            round_height: current_round_height,
-        };
-        if self.storage.exists(&round_file) {
-            warn!(
-                "Round file locator already exists ({}), removing...",
-                self.storage.to_path(&round_file)?
-            );
-            self.storage.remove(&round_file)?;
+        // Check that the current round is aggregated, if this is not round 0.
+        if current_round_height > 0 && !self.state.is_current_round_aggregated() {
+            return Err(CoordinatorError::CurrentRoundNotAggregated);
         }
 
-        // Fetch the current round from storage.
-        let round = Self::load_current_round(&self.storage)?;
-
-        // Check that the final unverified and verified contribution locators exist.
-        let contribution_id = round.expected_number_of_contributions() - 1;
-        for chunk_id in 0..self.environment.number_of_chunks() {
-            // Check that the final unverified contribution locator exists.
-            let locator = Locator::ContributionFile(ContributionLocator::new(
-                current_round_height,
-                chunk_id,
-                contribution_id,
-                false,
-            ));
-            if !self.storage.exists(&locator) {
-                error!(
-                    "Unverified contribution is missing ({})",
-                    self.storage.to_path(&locator)?
-                );
-                return Err(CoordinatorError::ContributionMissing);
+        // Attempt to advance the round.
+        trace!("Running precommit for the next round");
+        let result = match self
+            .state
+            .precommit_next_round(current_round_height + 1, self.time.as_ref())
+        {
+            // Case 1 - Precommit succeed, attempt to advance the round.
+            Ok(contributors) => {
+                trace!("Trying to add advance to the next round");
+                match self.advance_round(started_at, contributors) {
+                    // Case 1a - Coordinator advanced the round.
+                    Ok(next_round_height) => {
+                        // If success, update coordinator state to next round.
+                        info!("Coordinator has advanced to round {}", next_round_height);
+                        self.state.commit_next_round();
+                        Ok(next_round_height)
+                    }
+                    // Case 1b - Coordinator failed to advance the round.
+                    Err(error) => {
+                        // If failed, rollback coordinator state to the current round.
+                        error!("Coordinator failed to advance the next round, performing state rollback");
+                        self.state.rollback_next_round(self.time.as_ref());
+                        error!("{}", error);
+                        Err(error)
+                    }
+                }
             }
-            // Check that the final verified contribution locator exists.
-            let locator =
-                Locator::ContributionFile(ContributionLocator::new(current_round_height + 1, chunk_id, 0, true));
-            if !self.storage.exists(&locator) {
-                error!("Verified contribution is missing ({})", self.storage.to_path(&locator)?);
-                return Err(CoordinatorError::ContributionMissing);
+            // Case 2 - Precommit failed, roll back precommit.
+            Err(error) => {
+                // If failed, rollback coordinator state to the current round.
+                error!("Precommit for next round has failed, performing state rollback");
+                self.state.rollback_next_round(self.time.as_ref());
+                error!("{}", error);
+                Err(error)
             }
-        }
+        };
 
-        // Check that all chunks in the current round are verified.
-        if !round.is_complete() {
-            error!("Round {} is not complete", current_round_height);
-            trace!("{:#?}", &round);
-            return Err(CoordinatorError::RoundNotComplete);
-        }
+        // Save the coordinator state in storage.
+        self.save_state()?;
 
-        // Execute round aggregation and aggregate verification for the current round.
-        {
-            debug!("Coordinator is starting aggregation and aggregate verification");
-            // NOTE: removed aggregation and CoordinatorError::RoundFileMissing. We don't need the aggregation function. Hopefully, it doesn't break anything.
-            Aggregation::run(&self.environment, &mut self.storage, &round)?;
-            debug!("Coordinator completed aggregation and aggregate verification");
+        result
+    }
+
+    ///
+    /// Returns the chunk ID from the given contribution file locator path.
+    ///
+    /// If the given locator path is invalid, returns a `CoordinatorError`.
+    ///
+    #[inline]
+    pub fn contribution_locator_to_chunk_id(&self, locator_path: &LocatorPath) -> Result<u64, CoordinatorError> {
+        // Fetch the chunk ID corresponding to the given locator path.
+        let locator = self.storage.to_locator(&locator_path)?;
+        match &locator {
+            Locator::ContributionFile(contribution_locator) => match self.storage.exists(&locator) {
+                true => Ok(contribution_locator.chunk_id()),
+                false => Err(CoordinatorError::ContributionLocatorMissing),
+            },
+            _ => Err(CoordinatorError::ContributionLocatorIncorrect),
         }
+    }
 
-        // Check that the round file for the current round now exists.
-        if !self.storage.exists(&round_file) {
-            error!("Round file locator is missing ({})", self.storage.to_path(&round_file)?);
-            return Err(CoordinatorError::RoundFileMissing);
+    /// Convert a locator to a path string using the coordinator's
+    /// storage layer.
+    pub fn locator_to_path(&self, locator: Locator) -> Result<LocatorPath, CoordinatorError> {
+        self.storage.to_path(&locator)
+    }
+
+    ///
+    /// Attempts to acquire the lock for a given chunk ID and
+    /// participant.
+    ///
+    /// **Important**: The returned next contribution locator does not
+    /// always match the current task being performed. If it is the
+    /// final verification for a chunk, the a [Participant::Verifier]
+    /// will receive a locator to the contribution 0 of the same chunk
+    /// in the next round.
+    ///
+    /// On failure, this function returns a `CoordinatorError`.
+    ///
+    pub(crate) fn try_lock_chunk(
+        &mut self,
+        chunk_id: u64,
+        participant: &Participant,
+    ) -> Result<LockedLocators, CoordinatorError> {
+        // Check that the chunk ID is valid.
+        if chunk_id > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
         }
 
-        Ok(())
+        // Fetch the current round height from storage.
+        let current_round_height = Self::load_current_round_height(&self.storage)?;
+        trace!("Current round height from storage is {}", current_round_height);
+
+        // Fetch the current round from storage.
+        let mut round = Self::load_current_round(&self.storage)?;
+
+        // Attempt to acquire the chunk lock for participant.
+        trace!("Preparing to lock chunk {}", chunk_id);
+        let locked_locators = round.try_lock_chunk(&self.environment, &mut self.storage, chunk_id, &participant)?;
+        trace!("Participant {} locked chunk {}", participant, chunk_id);
+
+        // Add the updated round to storage.
+        match self.storage.update(
+            &Locator::RoundState {
+                round_height: current_round_height,
+            },
+            Object::RoundState(round),
+        ) {
+            Ok(_) => {
+                debug!("{} acquired lock on chunk {}", participant, chunk_id);
+                Ok(locked_locators)
+            }
+            _ => Err(CoordinatorError::StorageUpdateFailed),
+        }
     }
 
     ///
-    /// Initiates the next round of the ceremony.
+    /// Attempts to add a contribution for a given chunk ID from a given participant.
     ///
-    /// If there are no prior rounds in storage, this initializes a new ceremony
-    /// by invoking `Initialization`, and saves it to storage.
+    /// This function checks that the participant is a contributor and has uploaded
+    /// a valid response file to the coordinator. The coordinator sanity checks
+    /// (however, does not verify) the contribution before accepting the response file.
     ///
-    /// Otherwise, this loads the current round from storage and checks that
-    /// it is fully verified before proceeding to aggregate the round, and
-    /// initialize the next round, saving it to storage for the coordinator.
+    /// On success, this function releases the chunk lock from the contributor and
+    /// returns the response file locator and contribution ID of the response file.
     ///
-    /// On success, the function returns the new round height.
-    /// Otherwise, it returns a `CoordinatorError`.
+    /// On failure, it returns a `CoordinatorError`.
     ///
     #[inline]
-    pub(crate) fn next_round(
+    pub(crate) fn add_contribution(
         &mut self,
-        started_at: OffsetDateTime,
-        contributors: Vec<Participant>,
-    ) -> Result<u64, CoordinatorError> {
-        // Check that the next round has at least one authorized contributor.
-        if contributors.is_empty() {
-            return Err(CoordinatorError::ContributorsMissing);
+        chunk_id: u64,
+        participant: &Participant,
+    ) -> Result<(ContributionLocator, u64), CoordinatorError> {
+        debug!("Adding contribution from {} to chunk {}", participant, chunk_id);
+
+        // Don't write contributions while the round is being aggregated.
+        if self.round_transition.is_in_progress() {
+            return Err(CoordinatorError::RoundTransitionInProgress);
         }
 
         // Fetch the current round height from storage.
         let current_round_height = Self::load_current_round_height(&self.storage)?;
-
         trace!("Current round height from storage is {}", current_round_height);
 
-        // Ensure the current round has been aggregated if this is not the initial round.
-        if current_round_height != 0 {
-            // Check that the round file for the current round exists.
-            let round_file = Locator::RoundFile {
-                round_height: current_round_height,
-            };
-            if !self.storage.exists(&round_file) {
-                error!("Round file locator is missing ({})", self.storage.to_path(&round_file)?);
-                warn!("Coordinator may be missing a call to `try_aggregate` for the current round");
-                return Err(CoordinatorError::RoundFileMissing);
+        // Fetch the current round from storage.
+        let mut round = Self::load_current_round(&self.storage)?;
+        {
+            // Check that the participant is an authorized contributor to the current round.
+            if !round.is_contributor(participant) {
+                error!("{} is unauthorized to contribute to chunk {})", participant, chunk_id);
+                return Err(CoordinatorError::UnauthorizedChunkContributor);
+            }
+
+            // Check that the chunk lock is currently held by this contributor.
+            if !round.is_chunk_locked_by(chunk_id, participant) {
+                error!("{} should have lock on chunk {} but does not", participant, chunk_id);
+                return Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant);
             }
-            self.aggregate_contributions()?;
         }
 
-        // Create the new round height.
-        let new_height = current_round_height + 1;
-        info!("Transitioning from round {} to {}", current_round_height, new_height);
+        // Fetch the expected number of contributions for the current round.
+        let expected_num_contributions = round.expected_number_of_contributions();
+        // Fetch the chunk corresponding to the given chunk ID.
+        let chunk = round.chunk(chunk_id)?;
+        // Fetch the next contribution ID of the chunk.
+        let contribution_id = chunk.next_contribution_id(expected_num_contributions)?;
 
-        // Check that the new round does not exist in storage.
-        // If it exists, this means the round was already initialized.
-        let locator = Locator::RoundState {
-            round_height: new_height,
-        };
-        if self.storage.exists(&locator) {
-            error!(
-                "Round {} already exists ({})",
-                new_height,
-                self.storage.to_path(&locator)?
-            );
-            return Err(CoordinatorError::RoundAlreadyInitialized);
+        // Check that the next contribution ID is one above the current contribution ID.
+        if !chunk.is_next_contribution_id(contribution_id, expected_num_contributions) {
+            return Err(CoordinatorError::ContributionIdMismatch);
         }
 
-        // Check that each contribution for the next round exists.
-        for chunk_id in 0..self.environment.number_of_chunks() {
-            debug!("Locating round {} chunk {} contribution 0", new_height, chunk_id);
-            let locator = Locator::ContributionFile(ContributionLocator::new(new_height, chunk_id, 0, true));
-            if !self.storage.exists(&locator) {
-                error!("Contribution locator is missing ({})", self.storage.to_path(&locator)?);
-                return Err(CoordinatorError::ContributionLocatorMissing);
+        // Fetch the challenge, response, and contribution file signature locators.
+        let challenge_file_locator = Locator::ContributionFile(ContributionLocator::new(
+            current_round_height,
+            chunk_id,
+            chunk.current_contribution_id(),
+            true,
+        ));
+        let response_file_locator = ContributionLocator::new(current_round_height, chunk_id, contribution_id, false);
+        let contribution_file_signature_locator = Locator::ContributionFileSignature(
+            ContributionSignatureLocator::new(current_round_height, chunk_id, contribution_id, false),
+        );
+
+        // Check the challenge-response hash chain.
+        let (challenge_hash, response_hash, contribution_hash) = {
+            // Compute the challenge hash using the challenge file.
+            let challenge_reader = self.storage.reader(&challenge_file_locator)?;
+            let challenge_hash = calculate_hash(challenge_reader.as_ref());
+            info!(
+                "Challenge is located in {}",
+                self.storage.to_path(&challenge_file_locator)?
+            );
+
+            debug!("Challenge is {}", pretty_hash!(&challenge_reader));
+            debug!("Challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
+
+            // Compute the response hash.
+            let response_reader = self.storage.reader(&Locator::ContributionFile(response_file_locator))?;
+            let response_hash = calculate_hash(response_reader.as_ref());
+            info!(
+                "Response is located in {}",
+                self.storage
+                    .to_path(&Locator::ContributionFile(response_file_locator))?
+            );
+            debug!("Response is {}", pretty_hash!(&response_reader));
+            info!("Response hash is {}", pretty_hash!(&response_hash.as_slice()));
+
+            // Fetch the challenge hash from the response file.
+            let challenge_hash_in_response = &response_reader
+                .get(0..64)
+                .ok_or(CoordinatorError::StorageReaderFailed)?[..];
+            let pretty_hash = pretty_hash!(&challenge_hash_in_response);
+
+            // Check the starting hash in the response file is based on the challenge.
+            info!("The challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
+            info!("The challenge hash in response file is {}", pretty_hash);
+            if challenge_hash_in_response != challenge_hash.as_slice() {
+                error!("Challenge hash in response file does not match the expected challenge hash.");
+                return Err(CoordinatorError::ContributionHashMismatch);
+            }
+
+            // Hash of the contribution without the challenge hash, which changes at every round.
+            let contribution_hash = hex::encode(calculate_hash(&response_reader[64..]));
+
+            (challenge_hash, response_hash, contribution_hash)
+        };
+
+        // Check that the same contribution has not been received in a prior round.
+        if let Some(round_height) = self.state.contribution_hash_round(&contribution_hash) {
+            if round_height < current_round_height {
+                error!(
+                    "The contribution of {} to chunk {} was already received in round {}",
+                    participant, chunk_id, round_height
+                );
+                return Err(CoordinatorError::DuplicateContribution { round_height });
+            }
+        }
+
+        // Check the challenge-response contribution signature.
+        {
+            // Fetch the stored contribution file signature.
+            let contribution_file_signature: ContributionFileSignature =
+                serde_json::from_slice(&*self.storage.reader(&contribution_file_signature_locator)?)?;
+
+            // Check that the contribution file signature is valid.
+            let address = &participant.to_string();
+
+            let address = address
+                .split(".")
+                .next()
+                .expect("splitting a string should yield at least one item");
+
+            if !self.verify_file_signature(address, &contribution_file_signature)? {
+                error!("Contribution file signature failed to verify for {}", participant);
+                return Err(CoordinatorError::ContributorSignatureInvalid);
+            }
+
+            // Check that the signed state refers to the stored challenge and response files.
+            if let Err(e) = contribution_file_signature
+                .get_state()
+                .validate_hashes(challenge_hash.as_slice(), response_hash.as_slice())
+            {
+                error!("The signed hashes do not match the stored challenge and response files.");
+                return Err(e);
+            }
+
+            // Check that the contribution file signature next challenge hash does not exist.
+            if contribution_file_signature.get_next_challenge_hash().is_some() {
+                error!("The signed next challenge hash should not exist");
+                return Err(CoordinatorError::NextChallengeHashAlreadyExists);
+            }
+        }
+
+        // Add the contribution response to the current chunk.
+        round.chunk_mut(chunk_id)?.add_contribution(
+            contribution_id,
+            participant,
+            self.storage
+                .to_path(&Locator::ContributionFile(response_file_locator))?,
+            self.storage.to_path(&contribution_file_signature_locator)?,
+        )?;
+
+        // Add the updated round to storage.
+        match self.storage.update(
+            &Locator::RoundState {
+                round_height: current_round_height,
+            },
+            Object::RoundState(round),
+        ) {
+            Ok(_) => {
+                debug!("Updated round {} in storage", current_round_height);
+                debug!("{} added a contribution to chunk {}", participant, chunk_id);
+                self.state
+                    .record_contribution_hash(contribution_hash, current_round_height);
+                self.write_contribution_provenance(participant, current_round_height, chunk_id, contribution_id)?;
+                Ok((response_file_locator, contribution_id))
+            }
+            _ => Err(CoordinatorError::StorageUpdateFailed),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_challenge(
+        &self,
+        round_height: u64,
+        chunk_id: u64,
+        contribution_id: u64,
+        is_verified: bool,
+    ) -> Result<Vec<u8>, CoordinatorError> {
+        // Fetch the challenge, response, and contribution file signature locators.
+        let challenge_file_locator = Locator::ContributionFile(ContributionLocator::new(
+            round_height,
+            chunk_id,
+            contribution_id,
+            is_verified,
+        ));
+        // Get the challenge from the challenge file locator
+        let challenge_reader = self.storage.reader(&challenge_file_locator)?;
+
+        Ok(challenge_reader.to_vec())
+    }
+
+    /// Writes the bytes of a contribution to storage at the appropriate file
+    /// locator. Fails if the contribution isn't of the expected contribution
+    /// file size. The contribution is moved in place only once fully
+    /// written, so the locator never holds a partial contribution.
+    pub(crate) fn write_contribution<T>(
+        &mut self,
+        contribution_locator: ContributionLocator,
+        contribution: T,
+    ) -> Result<(), CoordinatorError>
+    where
+        T: Into<Vec<u8>>,
+    {
+        let contribution = contribution.into();
+
+        // Check that the contribution size is correct.
+        let expected_size = Object::ceremony_contribution_file_size(
+            &self.environment,
+            contribution_locator.round_height(),
+            contribution_locator.contribution_id(),
+        )?;
+        if contribution.len() as u64 != expected_size {
+            error!(
+                "Contribution file size should be {} but found {}",
+                expected_size,
+                contribution.len()
+            );
+            return Err(CoordinatorError::ContributionFileSizeMismatch);
+        }
+
+        // Can use update instead of insert because the path is already initialized by other functions
+        self.storage.update_durably(
+            &Locator::ContributionFile(contribution_locator),
+            Object::ContributionFile(contribution),
+        )
+    }
+
+    /// Writes a part of the upload of a contribution of the current round
+    /// starting at the given offset. Returns the number of bytes of the
+    /// contribution committed so far, from which the upload can be resumed.
+    pub(crate) fn write_contribution_part(
+        &self,
+        contribution_locator: &ContributionLocator,
+        offset: u64,
+        bytes: &[u8],
+    ) -> Result<u64, CoordinatorError> {
+        self.check_upload_round_height(contribution_locator)?;
+        self.storage.write_upload_part(contribution_locator, offset, bytes)
+    }
+
+    /// Completes the upload of a contribution sent in parts. Fails if the
+    /// uploaded size doesn't match the expected size of the contribution, or
+    /// if the upload doesn't match the size and hash sent by the contributor.
+    pub(crate) fn finalize_contribution_upload(
+        &self,
+        contribution_locator: &ContributionLocator,
+        size: u64,
+        hash: &str,
+    ) -> Result<(), CoordinatorError> {
+        self.check_upload_round_height(contribution_locator)?;
+        self.storage.finalize_upload(contribution_locator, size, hash)
+    }
+
+    /// Returns `true` if the contribution has been uploaded in parts and
+    /// finalized, but not yet written to storage.
+    pub(crate) fn is_contribution_uploaded(&self, contribution_locator: &ContributionLocator) -> bool {
+        self.storage.is_upload_finalized(contribution_locator)
+    }
+
+    /// Writes the finalized upload of a contribution to storage at the
+    /// appropriate file locator.
+    pub(crate) fn write_uploaded_contribution(
+        &mut self,
+        contribution_locator: ContributionLocator,
+    ) -> Result<(), CoordinatorError> {
+        let contribution = self.storage.get_upload(&contribution_locator)?;
+        self.write_contribution(contribution_locator, contribution)?;
+        self.storage.remove_upload(&contribution_locator)
+    }
+
+    /// Checks that an upload targets a contribution of the current round.
+    fn check_upload_round_height(&self, contribution_locator: &ContributionLocator) -> Result<(), CoordinatorError> {
+        if contribution_locator.round_height() != self.current_round_height()? {
+            return Err(CoordinatorError::RoundHeightMismatch);
+        }
+
+        Ok(())
+    }
+
+    /// Writes the contribution metadata to storage at the appropriate locator.
+    pub(crate) fn write_contribution_info(
+        &mut self,
+        contribution_info: ContributionInfo,
+    ) -> Result<(), CoordinatorError> {
+        self.storage.insert(
+            Locator::ContributionInfoFile {
+                round_height: contribution_info.ceremony_round,
+            },
+            Object::ContributionInfoFile(contribution_info),
+        )
+    }
+
+    /// Writes privately the client the participant contributed from, if known,
+    /// alongside the provenance of the other contributions of the round.
+    fn write_contribution_provenance(
+        &mut self,
+        participant: &Participant,
+        round_height: u64,
+        chunk_id: u64,
+        contribution_id: u64,
+    ) -> Result<(), CoordinatorError> {
+        let client = match self.state.get_client_provenance(participant) {
+            Some(client) => client.clone(),
+            None => return Ok(()),
+        };
+
+        let locator = Locator::ContributionProvenanceFile { round_height };
+        let exists = self.storage.exists(&locator);
+        let mut provenance = match exists {
+            true => match self.storage.get(&locator)? {
+                Object::ContributionProvenanceFile(provenance) => provenance,
+                _ => return Err(CoordinatorError::StorageFailed),
+            },
+            false => Vec::new(),
+        };
+
+        // A contribution which was reset and contributed again replaces its previous provenance
+        provenance.retain(|p| p.chunk_id != chunk_id || p.contribution_id != contribution_id);
+        provenance.push(ContributionProvenance {
+            public_key: participant.address(),
+            ceremony_round: round_height,
+            chunk_id,
+            contribution_id,
+            client,
+        });
+
+        let provenance = Object::ContributionProvenanceFile(provenance);
+        match exists {
+            true => self.storage.update(&locator, provenance),
+            false => self.storage.insert(locator, provenance),
+        }
+    }
+
+    /// Updates the contribution attestation and summary to storage at the appropriate locator.
+    pub(crate) fn update_contribution_info_attestation(
+        &mut self,
+        round: u64,
+        attestation: String,
+    ) -> Result<(), CoordinatorError> {
+        // Retrieve current file to update
+        let updated_info = match self
+            .storage
+            .get(&Locator::ContributionInfoFile { round_height: round })?
+        {
+            Object::ContributionInfoFile(info) => ContributionInfo {
+                attestation: Some(attestation),
+                ..info
+            },
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
+
+        // Persist update to storage
+        self.storage.update(
+            &Locator::ContributionInfoFile { round_height: round },
+            Object::ContributionInfoFile(updated_info.clone()),
+        )?;
+
+        // Update the summary
+        let mut summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary,
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
+
+        match summary.get_mut((round - 1) as usize) {
+            Some(t) => *t = self.trim_contribution_info(updated_info),
+            None => return Err(CoordinatorError::StorageFailed),
+        };
+
+        // Persist update to storage
+        self.storage.update(
+            &Locator::ContributionsInfoSummary,
+            Object::ContributionsInfoSummary(summary),
+        )
+    }
+
+    /// Summarizes the given contribution info, identifying the contributor by the hash of its email if its identity is
+    /// redacted.
+    fn trim_contribution_info(&self, info: ContributionInfo) -> TrimmedContributionInfo {
+        let email_hash = match (self.environment.contributor_identity_key(), info.email.as_deref()) {
+            (Some(key), Some(email)) => Some(hash_email(key, email)),
+            _ => None,
+        };
+
+        TrimmedContributionInfo::from(info).with_email_hash(email_hash)
+    }
+
+    /// Appends current round summary to storage at the appropriate locator.
+    pub(crate) fn update_contribution_summary(&mut self, info: ContributionInfo) -> Result<(), CoordinatorError> {
+        let mut summary = match self.storage.get(&Locator::ContributionsInfoSummary)? {
+            Object::ContributionsInfoSummary(summary) => summary,
+            _ => return Err(CoordinatorError::StorageFailed),
+        };
+        summary.push(self.trim_contribution_info(info));
+
+        self.storage.update(
+            &Locator::ContributionsInfoSummary,
+            Object::ContributionsInfoSummary(summary),
+        )
+    }
+
+    ///
+    /// Returns the signature of the contribution file at the given locator, to verify
+    /// independently who signed the contribution.
+    ///
+    /// If the contribution has not been uploaded yet, returns a `ContributionSignatureMissing` error.
+    ///
+    pub fn get_contribution_file_signature(
+        &self,
+        locator: ContributionSignatureLocator,
+    ) -> Result<ContributionFileSignature, CoordinatorError> {
+        let locator = Locator::ContributionFileSignature(locator);
+
+        // The signature file is zero-filled when the chunk is locked, until the contribution is uploaded
+        if !self.storage.exists(&locator) || self.storage.reader(&locator)?.iter().all(|b| *b == 0) {
+            return Err(CoordinatorError::ContributionSignatureMissing);
+        }
+
+        match self.storage.get(&locator)? {
+            Object::ContributionFileSignature(signature) => Ok(signature),
+            _ => Err(CoordinatorError::StorageFailed),
+        }
+    }
+
+    /// Writes the bytes of a contribution file signature to storage at the appropriate  
+    /// locator. Signature of a contribution is computed client-side, so there's no way to use the provided
+    /// write_contribution_file_signature function.
+    pub(crate) fn write_contribution_file_signature(
+        &mut self,
+        locator: ContributionSignatureLocator,
+        contribution_file_signature: ContributionFileSignature,
+    ) -> Result<(), CoordinatorError> {
+        self.storage.update(
+            &Locator::ContributionFileSignature(locator),
+            Object::ContributionFileSignature(contribution_file_signature),
+        )
+    }
+
+    ///
+    /// Attempts to run verification in the current round for a given
+    /// chunk ID and participant.
+    ///
+    /// This function checks that the participant is a verifier and
+    /// has uploaded a valid next challenge file to the coordinator.
+    /// The coordinator sanity checks that the next challenge file
+    /// contains the hash of the corresponding response file.
+    ///
+    /// This function stores the next challenge locator into the round
+    /// transcript and releases the chunk lock from the verifier.
+    ///
+    /// On success, this function returns the contribution ID of the
+    /// unverified response file.
+    ///
+    /// **Important**: if the contribution is the final contribution
+    /// for the chunk for the round, its verification will be stored
+    /// in the next round's directory as contribution 0.
+    ///
+    #[tracing::instrument(
+        skip(self, participant),
+        fields(participant = %participant)
+    )]
+    pub(crate) fn verify_contribution(
+        &mut self,
+        task: &Task,
+        participant: &Participant,
+    ) -> Result<u64, CoordinatorError> {
+        let chunk_id = task.chunk_id();
+        debug!("Attempting to verify a contribution for chunk {}", chunk_id);
+        if !participant.is_verifier() {
+            return Err(CoordinatorError::ExpectedVerifier);
+        }
+
+        // Fetch the current round height from storage.
+        let current_round_height = Self::load_current_round_height(&self.storage)?;
+        trace!("Current round height from storage is {}", current_round_height);
+
+        // Fetch the current round from storage.
+        let mut round = Self::load_current_round(&self.storage)?;
+
+        // Fetch the chunk corresponding to the given chunk ID.
+        let chunk = round.chunk(chunk_id)?;
+        // Fetch the current contribution ID.
+        let contribution_id = chunk.current_contribution_id();
+        if contribution_id != task.contribution_id() {
+            return Err(CoordinatorError::ContributionIdMismatch);
+        }
+
+        // Fetch the challenge, response, next challenge, and contribution file signature locators.
+        let challenge_file_locator = Locator::ContributionFile(ContributionLocator::new(
+            current_round_height,
+            chunk_id,
+            contribution_id - 1,
+            true,
+        ));
+        let response_file_locator = Locator::ContributionFile(ContributionLocator::new(
+            current_round_height,
+            chunk_id,
+            contribution_id,
+            false,
+        ));
+        let (next_challenge_locator, contribution_file_signature_locator) = {
+            // Fetch whether this is the final contribution of the specified chunk.
+            let is_final_contribution = chunk.only_contributions_complete(round.expected_number_of_contributions());
+            match is_final_contribution {
+                true => (
+                    Locator::ContributionFile(ContributionLocator::new(current_round_height + 1, chunk_id, 0, true)),
+                    Locator::ContributionFileSignature(ContributionSignatureLocator::new(
+                        current_round_height + 1,
+                        chunk_id,
+                        0,
+                        true,
+                    )),
+                ),
+                false => (
+                    Locator::ContributionFile(ContributionLocator::new(
+                        current_round_height,
+                        chunk_id,
+                        contribution_id,
+                        true,
+                    )),
+                    Locator::ContributionFileSignature(ContributionSignatureLocator::new(
+                        current_round_height,
+                        chunk_id,
+                        contribution_id,
+                        true,
+                    )),
+                ),
+            }
+        };
+
+        // Check the challenge-response hash chain.
+        let (challenge_hash, response_hash) = {
+            // Compute the challenge hash using the challenge file.
+            let challenge_reader = self.storage.reader(&challenge_file_locator)?;
+            let challenge_hash = calculate_hash(challenge_reader.as_ref());
+            trace!(
+                "Challenge is located in {}",
+                self.storage.to_path(&challenge_file_locator)?
+            );
+            debug!("Challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
+
+            // Compute the response hash.
+            let response_reader = self.storage.reader(&response_file_locator)?;
+            let response_hash = calculate_hash(response_reader.as_ref());
+            trace!(
+                "Response is located in {}",
+                self.storage.to_path(&response_file_locator)?
+            );
+            info!("Response hash is {}", pretty_hash!(&response_hash.as_slice()));
+
+            // Fetch the challenge hash from the response file.
+            let challenge_hash_in_response = &response_reader
+                .get(0..64)
+                .ok_or(CoordinatorError::StorageReaderFailed)?[..];
+            let pretty_hash = pretty_hash!(&challenge_hash_in_response);
+
+            // Check the starting hash in the response file is based on the challenge.
+            info!("The challenge hash is {}", pretty_hash!(&challenge_hash.as_slice()));
+            info!("The challenge hash in response file is {}", pretty_hash);
+            if challenge_hash_in_response != challenge_hash.as_slice() {
+                error!("Challenge hash in response file does not match the expected challenge hash.");
+                return Err(CoordinatorError::ContributionHashMismatch);
+            }
+
+            (challenge_hash, response_hash)
+        };
+
+        // Check the response-next_challenge hash chain.
+        let next_challenge_hash = {
+            // Compute the next challenge hash.
+            let next_challenge_reader = self.storage.reader(&next_challenge_locator)?;
+            let next_challenge_hash = calculate_hash(next_challenge_reader.as_ref());
+            trace!(
+                "Next challenge is located in {}",
+                self.storage.to_path(&next_challenge_locator)?
+            );
+            debug!(
+                "Next challenge hash is {}",
+                pretty_hash!(&next_challenge_hash.as_slice())
+            );
+
+            // Fetch the saved response hash in the next challenge file.
+            let saved_response_hash = next_challenge_reader.as_ref().chunks(64).next().unwrap().to_vec();
+            let pretty_hash = pretty_hash!(&saved_response_hash);
+
+            // Check that the response hash matches the next challenge hash.
+            info!("The response hash is {}", pretty_hash!(&response_hash));
+            info!("The response hash in next challenge file is {}", pretty_hash);
+            if response_hash.as_slice() != saved_response_hash {
+                error!("Response hash does not match the saved response hash.");
+                return Err(CoordinatorError::ContributionHashMismatch);
+            }
+
+            next_challenge_hash
+        };
+
+        // Check the response-next_challenge contribution signature.
+        {
+            // Fetch the stored contribution file signature.
+            let contribution_file_signature: ContributionFileSignature =
+                serde_json::from_slice(&*self.storage.reader(&contribution_file_signature_locator)?)?;
+
+            // Check that the contribution file signature is valid.
+            let address = &participant.to_string();
+
+            let address = address
+                .split(".")
+                .next()
+                .expect("splitting a string should yield at least one item");
+
+            if !self.verify_file_signature(address, &contribution_file_signature)? {
+                error!("Contribution file signature failed to verify for {}", participant);
+                return Err(CoordinatorError::VerifierSignatureInvalid);
+            }
+
+            // Check that the contribution file signature challenge hash is correct.
+            if hex::decode(contribution_file_signature.get_challenge_hash())? != challenge_hash.as_slice() {
+                error!("The signed challenge hash does not match the expected challenge hash.");
+                return Err(CoordinatorError::ContributionHashMismatch);
+            }
+
+            // Check that the contribution file signature response hash is correct.
+            if hex::decode(contribution_file_signature.get_response_hash())? != response_hash.as_slice() {
+                error!("The signed response hash does not match the expected response hash.");
+                return Err(CoordinatorError::ContributionHashMismatch);
+            }
+
+            // Check that the contribution file signature next challenge hash exists.
+            if contribution_file_signature.get_next_challenge_hash().is_none() {
+                error!("The signed next challenge hash is missing");
+                return Err(CoordinatorError::NextChallengeHashMissing);
+            }
+
+            // Check that the contribution file signature next challenge hash is correct.
+            let signed_next_challenge_hash = contribution_file_signature
+                .get_next_challenge_hash()
+                .as_ref()
+                .ok_or(CoordinatorError::NextChallengeHashMissing)?;
+            if hex::decode(signed_next_challenge_hash)? != next_challenge_hash.as_slice() {
+                error!("The signed next challenge hash does not match the expected next challenge hash.");
+                return Err(CoordinatorError::ContributionHashMismatch);
+            }
+        }
+
+        // Sets the current contribution as verified in the current round.
+        round.verify_contribution(
+            chunk_id,
+            contribution_id,
+            participant.clone(),
+            self.storage.to_path(&next_challenge_locator)?,
+            self.storage.to_path(&contribution_file_signature_locator)?,
+            hex::encode(next_challenge_hash),
+        )?;
+
+        // Add the updated round to storage. The verification is persisted right away, so
+        // that it isn't redone if the coordinator restarts before saving its state.
+        match self.storage.update_durably(
+            &Locator::RoundState {
+                round_height: current_round_height,
+            },
+            Object::RoundState(round),
+        ) {
+            Ok(_) => {
+                debug!("Updated round {} in storage", current_round_height);
+                debug!(
+                    "{} verified chunk {} contribution {}",
+                    participant, chunk_id, contribution_id
+                );
+                Ok(contribution_id)
+            }
+            _ => Err(CoordinatorError::StorageUpdateFailed),
+        }
+    }
+
+    ///
+    /// Aggregates the contributions for the current round of the ceremony.
+    ///
+    /// This function loads the current round from storage and checks that
+    /// it is fully verified before proceeding to aggregate the round, and
+    /// initialize the next round, saving it to storage for the coordinator.
+    ///
+    /// On success, the function returns the new round height.
+    /// Otherwise, it returns a `CoordinatorError`.
+    ///
+    #[inline]
+    pub(crate) fn aggregate_contributions(&mut self) -> Result<(), CoordinatorError> {
+        let _transition = self.round_transition.try_begin()?;
+
+        self.run_aggregation()
+    }
+
+    /// Runs the aggregation of the given round. A transient failure isn't retried in place, which would hold the
+    /// coordinator during the backoff, but rescheduled: the aggregation is retried by a later update once the backoff
+    /// elapsed, doubling at each failed attempt. Deterministic failures, e.g. a missing contribution, are returned
+    /// right away.
+    fn aggregate_or_reschedule(&mut self, round: &Round, round_file: &Locator) -> Result<(), CoordinatorError> {
+        let round_height = round.round_height();
+        let failed_attempts = match self.aggregation_retry {
+            Some((retry_height, failed_attempts, _)) if retry_height == round_height => failed_attempts,
+            _ => 0,
+        };
+        let attempt = failed_attempts + 1;
+        let max_attempts = self.environment.aggregation_retries() + 1;
+
+        let error = match Aggregation::run(&self.environment, &mut self.storage, round) {
+            Ok(()) => {
+                if attempt > 1 {
+                    info!(
+                        "Aggregation of round {} succeeded at attempt {}/{}",
+                        round_height, attempt, max_attempts
+                    );
+                }
+                self.aggregation_retry = None;
+                return Ok(());
+            }
+            Err(error) => error,
+        };
+
+        // Don't leave a partial round file behind for the next attempt
+        if self.storage.exists(round_file) {
+            self.storage.remove(round_file)?;
+        }
+
+        if !is_transient_aggregation_error(&error) {
+            error!("Aggregation of round {} failed: {}", round_height, error);
+            self.aggregation_retry = None;
+            return Err(error);
+        }
+        if attempt >= max_attempts {
+            error!(
+                "Aggregation of round {} failed after {} attempts, giving up: {}",
+                round_height, max_attempts, error
+            );
+            self.aggregation_retry = None;
+            return Err(error);
+        }
+
+        let backoff = self.environment.aggregation_retry_backoff() * 2_u32.saturating_pow(failed_attempts);
+        warn!(
+            "Attempt {}/{} to aggregate round {} failed, retrying in {}: {}",
+            attempt, max_attempts, round_height, backoff, error
+        );
+        self.aggregation_retry = Some((round_height, attempt, self.time.now_utc() + backoff));
+        Err(error)
+    }
+
+    /// Aggregates the contributions of the current round, the caller must hold the round transition.
+    fn run_aggregation(&mut self) -> Result<(), CoordinatorError> {
+        // Fetch the current round height from storage.
+        let current_round_height = Self::load_current_round_height(&self.storage)?;
+
+        trace!("Current round height from storage is {}", current_round_height);
+
+        // This function executes aggregation of the current round in preparation
+        // for transitioning to the next round. If this is the initial round,
+        // there should be nothing to aggregate and we return a `CoordinatorError`.
+        if current_round_height == 0 {
+            error!("Unauthorized to aggregate round 0");
+            return Err(CoordinatorError::RoundDoesNotExist);
+        }
+
+        // Wait for the backoff of a transient aggregation failure to elapse before retrying.
+        if let Some((round_height, _, retry_at)) = self.aggregation_retry {
+            if round_height == current_round_height && self.time.now_utc() < retry_at {
+                debug!("Aggregation of round {} is rescheduled at {}", round_height, retry_at);
+                return Err(CoordinatorError::RoundAggregationRescheduled);
+            }
+        }
+
+        // Check that the current round state exists in storage.
+        if !self.storage.exists(&Locator::RoundState {
+            round_height: current_round_height,
+        }) {
+            return Err(CoordinatorError::RoundStateMissing);
+        }
+
+        // Check that the next round state does not exist in storage.
+        if self.storage.exists(&Locator::RoundState {
+            round_height: current_round_height + 1,
+        }) {
+            return Err(CoordinatorError::RoundShouldNotExist);
+        }
+
+        // Check that the current round file does not exist.
+        let round_file = Locator::RoundFile {
+            round_height: current_round_height,
+        };
+        if self.storage.exists(&round_file) {
+            warn!(
+                "Round file locator already exists ({}), removing...",
+                self.storage.to_path(&round_file)?
+            );
+            self.storage.remove(&round_file)?;
+        }
+
+        // Fetch the current round from storage.
+        let mut round = Self::load_current_round(&self.storage)?;
+
+        // Check that the final unverified and verified contribution locators exist.
+        let contribution_id = round.expected_number_of_contributions() - 1;
+        for chunk_id in 0..self.environment.number_of_chunks() {
+            // Check that the final unverified contribution locator exists.
+            let locator = Locator::ContributionFile(ContributionLocator::new(
+                current_round_height,
+                chunk_id,
+                contribution_id,
+                false,
+            ));
+            if !self.storage.exists(&locator) {
+                error!(
+                    "Unverified contribution is missing ({})",
+                    self.storage.to_path(&locator)?
+                );
+                return Err(CoordinatorError::ContributionMissing);
+            }
+            // Check that the final verified contribution locator exists.
+            let locator =
+                Locator::ContributionFile(ContributionLocator::new(current_round_height + 1, chunk_id, 0, true));
+            if !self.storage.exists(&locator) {
+                error!("Verified contribution is missing ({})", self.storage.to_path(&locator)?);
+                return Err(CoordinatorError::ContributionMissing);
+            }
+            // Check that the final verified contribution is still the one that was verified.
+            let verified_hash = round
+                .chunk(chunk_id)?
+                .get_contribution(contribution_id)?
+                .get_verified_hash()
+                .map(str::to_owned);
+            match verified_hash {
+                Some(verified_hash) => {
+                    let hash = hex::encode(calculate_hash(self.storage.reader(&locator)?.as_ref()));
+                    if hash != verified_hash {
+                        error!(
+                            "Verified contribution of chunk {} has hash {}, but {} was recorded at verification ({})",
+                            chunk_id,
+                            hash,
+                            verified_hash,
+                            self.storage.to_path(&locator)?
+                        );
+                        return Err(CoordinatorError::ContributionIntegrityMismatch { chunk_id });
+                    }
+                }
+                // Contributions verified before the hashes were recorded can't be checked
+                None => warn!(
+                    "No hash was recorded for the verified contribution of chunk {}",
+                    chunk_id
+                ),
+            }
+        }
+
+        // Check that all chunks in the current round are verified.
+        if !round.is_complete() {
+            error!("Round {} is not complete", current_round_height);
+            trace!("{:#?}", &round);
+            return Err(CoordinatorError::RoundNotComplete);
+        }
+
+        // Execute round aggregation and aggregate verification for the current round.
+        {
+            debug!("Coordinator is starting aggregation and aggregate verification");
+            // NOTE: removed aggregation and CoordinatorError::RoundFileMissing. We don't need the aggregation function. Hopefully, it doesn't break anything.
+            self.aggregate_or_reschedule(&round, &round_file)?;
+            debug!("Coordinator completed aggregation and aggregate verification");
+        }
+
+        // Check that the round file for the current round now exists.
+        if !self.storage.exists(&round_file) {
+            error!("Round file locator is missing ({})", self.storage.to_path(&round_file)?);
+            return Err(CoordinatorError::RoundFileMissing);
+        }
+
+        // Publish the aggregate hash of the round for external verification.
+        let aggregate_hash = Self::compute_aggregate_hash(&self.environment, &self.storage, current_round_height)?;
+        info!("Aggregate hash of round {} is {}", current_round_height, aggregate_hash);
+        round.set_aggregate_hash(aggregate_hash);
+        self.storage.update(
+            &Locator::RoundState {
+                round_height: current_round_height,
+            },
+            Object::RoundState(round),
+        )?;
+
+        Ok(())
+    }
+
+    ///
+    /// Returns the hex encoded hash of the verified aggregated transcript of
+    /// the given round: the hash of the verified final contributions of its
+    /// chunks, in order, which are the challenges of the next round.
+    ///
+    pub(crate) fn compute_aggregate_hash(
+        environment: &Environment,
+        storage: &Disk,
+        round_height: u64,
+    ) -> Result<String, CoordinatorError> {
+        let mut hasher = Blake2b512::default();
+        for chunk_id in 0..environment.number_of_chunks() {
+            let locator = Locator::ContributionFile(ContributionLocator::new(round_height + 1, chunk_id, 0, true));
+            hasher.update(storage.reader(&locator)?.as_ref());
+        }
+
+        Ok(hex::encode(hasher.finalize()))
+    }
+
+    ///
+    /// Initiates the next round of the ceremony.
+    ///
+    /// If there are no prior rounds in storage, this initializes a new ceremony
+    /// by invoking `Initialization`, and saves it to storage.
+    ///
+    /// Otherwise, this loads the current round from storage and checks that
+    /// it is fully verified before proceeding to aggregate the round, and
+    /// initialize the next round, saving it to storage for the coordinator.
+    ///
+    /// On success, the function returns the new round height.
+    /// Otherwise, it returns a `CoordinatorError`.
+    ///
+    #[inline]
+    pub(crate) fn next_round(
+        &mut self,
+        started_at: OffsetDateTime,
+        contributors: Vec<Participant>,
+    ) -> Result<u64, CoordinatorError> {
+        let _transition = self.round_transition.try_begin()?;
+
+        self.advance_round(started_at, contributors)
+    }
+
+    /// Initiates the next round of the ceremony, the caller must hold the round transition.
+    fn advance_round(
+        &mut self,
+        started_at: OffsetDateTime,
+        contributors: Vec<Participant>,
+    ) -> Result<u64, CoordinatorError> {
+        // Check that the next round has at least one authorized contributor.
+        if contributors.is_empty() {
+            return Err(CoordinatorError::ContributorsMissing);
+        }
+
+        // Check that the next round has enough contributors to collect the required contributions.
+        if contributors.len() < self.environment.minimum_contributors_per_round() {
+            error!(
+                "Next round has {} contributors, but at least {} are required",
+                contributors.len(),
+                self.environment.minimum_contributors_per_round()
+            );
+            return Err(CoordinatorError::RoundNumberOfContributorsUnauthorized);
+        }
+
+        // Fetch the current round height from storage.
+        let current_round_height = Self::load_current_round_height(&self.storage)?;
+
+        trace!("Current round height from storage is {}", current_round_height);
+
+        // Ensure the current round has been aggregated if this is not the initial round.
+        // A skipped round has nothing to aggregate, its initial challenge was carried over already.
+        if current_round_height != 0 && !Self::load_current_round(&self.storage)?.is_skipped() {
+            // Check that the round file for the current round exists.
+            let round_file = Locator::RoundFile {
+                round_height: current_round_height,
+            };
+            if !self.storage.exists(&round_file) {
+                error!("Round file locator is missing ({})", self.storage.to_path(&round_file)?);
+                warn!("Coordinator may be missing a call to `try_aggregate` for the current round");
+                return Err(CoordinatorError::RoundFileMissing);
+            }
+            self.run_aggregation()?;
+        }
+
+        // Create the new round height.
+        let new_height = current_round_height + 1;
+        info!("Transitioning from round {} to {}", current_round_height, new_height);
+
+        // Check that the new round does not exist in storage.
+        // If it exists, this means the round was already initialized.
+        let locator = Locator::RoundState {
+            round_height: new_height,
+        };
+        if self.storage.exists(&locator) {
+            error!(
+                "Round {} already exists ({})",
+                new_height,
+                self.storage.to_path(&locator)?
+            );
+            return Err(CoordinatorError::RoundAlreadyInitialized);
+        }
+
+        // Check that each contribution for the next round exists.
+        for chunk_id in 0..self.environment.number_of_chunks() {
+            debug!("Locating round {} chunk {} contribution 0", new_height, chunk_id);
+            let locator = Locator::ContributionFile(ContributionLocator::new(new_height, chunk_id, 0, true));
+            if !self.storage.exists(&locator) {
+                error!("Contribution locator is missing ({})", self.storage.to_path(&locator)?);
+                return Err(CoordinatorError::ContributionLocatorMissing);
+            }
+        }
+
+        // Instantiate the new round and height.
+        let new_round = Round::new(
+            &self.environment,
+            &mut self.storage,
+            new_height,
+            started_at,
+            contributors,
+        )?;
+
+        #[cfg(test)]
+        trace!("{:#?}", &new_round);
+
+        // Insert the new round into storage, then update the round height to reflect the new round. Both are
+        // persisted right away, together with any batched update.
+        self.storage
+            .compare_and_set_round_height(Some(current_round_height), new_height, Some(new_round))?;
+
+        debug!("Added round {} to storage", current_round_height);
+        info!("Transitioned from round {} to {}", current_round_height, new_height);
+
+        // Move the completed rounds to the archive, if any, including the ones which failed to be archived before. On
+        // failure, a round is still served from the local storage
+        match self.storage.round_heights() {
+            Ok(round_heights) => {
+                for round_height in round_heights.into_iter().filter(|height| *height < new_height) {
+                    if let Err(e) = self.storage.archive_round(round_height) {
+                        warn!("Failed to archive round {}: {}", round_height, e);
+                    }
+                }
+            }
+            Err(e) => warn!("Failed to list the rounds to archive: {}", e),
+        }
+
+        // Notify the webhook, if any, of the transition. Delivery failures must not abort the transition.
+        if let Some(url) = self.environment.round_webhook_url() {
+            webhook::notify_round_transition(url, RoundTransitionEvent {
+                old_height: current_round_height,
+                new_height,
+                timestamp: self.time.now_utc(),
+            });
+        }
+
+        Ok(new_height)
+    }
+
+    ///
+    /// Attempts to run initialization for the ceremony.
+    ///
+    /// If this is the initial round, ensure the round does not exist yet.
+    ///
+    /// If there is no round in storage, proceed to create a new round instance,
+    /// and run `Initialization` to start the ceremony.
+    ///
+    #[inline]
+    pub(super) fn run_initialization(&mut self, started_at: OffsetDateTime) -> Result<u64, CoordinatorError> {
+        // Check that the ceremony has not begun yet.
+        if Self::load_current_round_height(&self.storage).is_ok() {
+            return Err(CoordinatorError::RoundAlreadyInitialized);
+        }
+
+        // Establish the round height convention for initialization.
+        let round_height = 0;
+
+        // Check that the current round does not exist in storage.
+        if self.storage.exists(&Locator::RoundState { round_height }) {
+            return Err(CoordinatorError::RoundShouldNotExist);
+        }
+
+        // Check that the next round does not exist in storage.
+        if self.storage.exists(&Locator::RoundState {
+            round_height: round_height + 1,
+        }) {
+            return Err(CoordinatorError::RoundShouldNotExist);
+        }
+
+        // Create an instantiation of `Round` for round 0.
+        let mut round = {
+            // Initialize the contributors as an empty list as this is for initialization.
+            let contributors = vec![];
+
+            // Create a new round instance.
+            Round::new(
+                &self.environment,
+                &mut self.storage,
+                round_height,
+                started_at,
+                contributors,
+            )?
+        };
+
+        info!("Starting initialization of round {}", round_height);
+
+        // Execute initialization of contribution 0 for all chunks
+        // in the new round and check that the new locators exist.
+        for chunk_id in 0..self.environment.number_of_chunks() {
+            // 1 - Check that the contribution locator corresponding to this round's chunk does not exist.
+            let locator = Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, 0, true));
+            if self.storage.exists(&locator) {
+                error!(
+                    "Contribution locator already exists ({})",
+                    self.storage.to_path(&locator)?
+                );
+                return Err(CoordinatorError::ContributionLocatorAlreadyExists);
+            }
+
+            // 2 - Check that the contribution locator corresponding to the next round's chunk does not exists.
+            let locator = Locator::ContributionFile(ContributionLocator::new(round_height + 1, chunk_id, 0, true));
+            if self.storage.exists(&locator) {
+                error!(
+                    "Contribution locator already exists ({})",
+                    self.storage.to_path(&locator)?
+                );
+                return Err(CoordinatorError::ContributionLocatorAlreadyExists);
+            }
+
+            info!("Coordinator is starting initialization on chunk {}", chunk_id);
+            let _contribution_hash = Initialization::run(&self.environment, &mut self.storage, round_height, chunk_id)?;
+            info!("Coordinator completed initialization on chunk {}", chunk_id);
+
+            // 1 - Check that the contribution locator corresponding to this round's chunk now exists.
+            let locator = Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, 0, true));
+            if !self.storage.exists(&locator) {
+                error!("Contribution locator is missing ({})", self.storage.to_path(&locator)?);
+                return Err(CoordinatorError::ContributionLocatorMissing);
+            }
+
+            // 2 - Check that the contribution locator corresponding to the next round's chunk now exists.
+            let locator = Locator::ContributionFile(ContributionLocator::new(round_height + 1, chunk_id, 0, true));
+            if !self.storage.exists(&locator) {
+                error!("Contribution locator is missing ({})", self.storage.to_path(&locator)?);
+                return Err(CoordinatorError::ContributionLocatorMissing);
+            }
+        }
+
+        // Check that all the chunks were initialized to the same transcript before any contributor joins.
+        let transcript_hash =
+            Initialization::check_transcripts(&self.storage, round_height, self.environment.number_of_chunks())?;
+        debug!("The initialized transcripts hash to {}", pretty_hash!(&transcript_hash));
+
+        // Set the finished time for round 0.
+        round.try_finish(self.time.now_utc());
+
+        // Add the new round to storage, then the round height.
+        self.storage
+            .compare_and_set_round_height(None, round_height, Some(round))?;
+
+        info!("Completed initialization of round {}", round_height);
+
+        Ok(round_height)
+    }
+
+    /// Update the round on disk after a drop has occured.
+    #[inline]
+    fn drop_participant_from_storage(&mut self, drop: &DropParticipant) -> Result<(), CoordinatorError> {
+        debug!(
+            "Dropping participant from storage with the following information: {:#?}",
+            drop
+        );
+
+        // Check the justification and extract the tasks.
+        let drop_data = match drop {
+            DropParticipant::DropCurrent(data) => data,
+            DropParticipant::DropQueue(_) => {
+                // Participant is not part of the round, therefore
+                // there is nothing to do.
+                return Ok(());
+            }
+        };
+
+        match &drop_data.storage_action {
+            CeremonyStorageAction::ResetCurrentRound(reset_action) => {
+                self.reset_round_storage(reset_action)?;
+            }
+            CeremonyStorageAction::ReplaceContributor(replace_action) => {
+                warn!(
+                    "Replacing contributor {} with {}",
+                    replace_action.dropped_contributor, replace_action.replacement_contributor
+                );
+
+                // Fetch the current round from storage.
+                let mut round = Self::load_current_round(&self.storage)?;
+
+                // Remove the contributor from the round self.
+                round.remove_contributor_unsafe(
+                    &mut self.storage,
+                    &replace_action.dropped_contributor,
+                    &replace_action.locked_chunks,
+                    &replace_action.tasks,
+                )?;
+
+                // Remove contribution info and trimmed info
+                self.storage.clear_info_files(round.round_height());
+
+                // Assign a replacement contributor from the queue to the dropped tasks for the current round.
+                round.add_replacement_contributor_unsafe(replace_action.replacement_contributor.clone())?;
+                warn!(
+                    "Added a replacement contributor {}",
+                    replace_action.replacement_contributor
+                );
+
+                // Save the updated round to storage.
+                self.storage.update(
+                    &Locator::RoundState {
+                        round_height: round.round_height(),
+                    },
+                    Object::RoundState(round),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn load_current_round_height(storage: &Disk) -> Result<u64, CoordinatorError> {
+        if storage.exists(&Locator::RoundHeight) {
+            // Fetch the current round height from storage.
+            match storage.get(&Locator::RoundHeight)? {
+                // Case 1 - This is a typical round of the ceremony.
+                Object::RoundHeight(current_round_height) => Ok(current_round_height),
+                // Case 2 - Storage failed to fetch the round height.
+                _ => Err(CoordinatorError::StorageFailed),
+            }
+        } else {
+            Err(CoordinatorError::RoundHeightNotSet)
+        }
+    }
+
+    #[inline]
+    fn load_current_round(storage: &Disk) -> Result<Round, CoordinatorError> {
+        // Fetch the current round height from storage.
+        let current_round_height = Self::load_current_round_height(storage)?;
+
+        // Fetch the current round from storage.
+        Self::load_round(storage, current_round_height)
+    }
+
+    #[inline]
+    fn load_round(storage: &Disk, round_height: u64) -> Result<Round, CoordinatorError> {
+        // Fetch the current round height from storage.
+        let current_round_height = Self::load_current_round_height(storage)?;
+
+        // Fetch the specified round from storage.
+        match round_height <= current_round_height {
+            // Load the corresponding round data from storage.
+            true => match storage.get(&Locator::RoundState { round_height })? {
+                // Case 1 - The ceremony is running and the round state was fetched.
+                Object::RoundState(round) => Ok(round),
+                // Case 2 - Storage failed to fetch the round height.
+                _ => Err(CoordinatorError::StorageFailed),
+            },
+            // Case 3 - There are no prior rounds of the ceremony.
+            false => Err(CoordinatorError::RoundDoesNotExist),
+        }
+    }
+
+    ///
+    /// Returns a reference to the instantiation of `Storage` that this
+    /// coordinator is using.
+    ///
+    #[inline]
+    pub(super) fn storage(&self) -> &Disk {
+        &self.storage
+    }
+
+    ///
+    /// Returns a mutable reference to the instantiation of `Storage`
+    /// that this coordinator is using.
+    ///
+    #[cfg(test)]
+    #[inline]
+    pub(super) fn storage_mut(&mut self) -> &mut Disk {
+        &mut self.storage
+    }
+
+    ///
+    /// Returns a reference to the instantiation of `Signature` that this
+    /// coordinator is using.
+    ///
+    #[cfg(test)]
+    #[inline]
+    pub(super) fn signature(&self) -> Arc<dyn Signature> {
+        self.signature.clone()
+    }
+
+    ///
+    /// Resets the current round, with a rollback to the end of the
+    /// previous round to invite new participants into the round.
+    ///
+    pub fn reset_round(&mut self) -> Result<(), CoordinatorError> {
+        let reset_action = self.state.reset_current_round(true, &*self.time)?;
+        self.verification_cache.clear();
+
+        self.storage
+            .update(&Locator::CoordinatorState, Object::CoordinatorState(self.state.clone()))?;
+        self.reset_round_storage(&reset_action)?;
+
+        Ok(())
+    }
+
+    /// Reset the current round in storage.
+    ///
+    /// + `remove_participants` is a list of participants that will
+    ///   have their contributions removed from the round.
+    /// + If `rollback` is set to `true`, the current round will be
+    ///   decremented by 1. If `rollback` is set to `true` and the
+    ///   current round height is `0` then this will return an error
+    ///   [CoordinatorError::RoundHeightIsZero]
+    fn reset_round_storage(&mut self, reset_action: &ResetCurrentRoundStorageAction) -> Result<(), CoordinatorError> {
+        // Fetch the current height of the ceremony.
+        let current_round_height = Self::load_current_round_height(&mut self.storage)?;
+        let span = tracing::error_span!("reset_round", round = current_round_height);
+        let _guard = span.enter();
+        warn!("Resetting round {}", current_round_height);
+
+        let mut round = Self::load_round(&mut self.storage, current_round_height)?;
+
+        tracing::debug!("Resetting round and applying storage changes");
+        self.storage.process(round.reset(&reset_action.remove_participants))?;
+
+        // Clear all files
+        self.storage
+            .process(StorageAction::ClearRoundFiles(current_round_height))?;
+
+        if reset_action.rollback {
+            if current_round_height == 0 {
+                return Err(CoordinatorError::RoundHeightIsZero);
+            }
+
+            let new_round_height = current_round_height - 1;
+            tracing::debug!("Rolling back to round {} in storage.", new_round_height);
+
+            // Move the round height back before removing the round, never to point at a missing round
+            self.storage
+                .compare_and_set_round_height(Some(current_round_height), new_round_height, None)?;
+            self.storage.remove(&Locator::RoundState {
+                round_height: current_round_height,
+            })?;
+        }
+
+        warn!("Finished resetting round {} storage", current_round_height);
+
+        Ok(())
+    }
+}
+
+#[cfg(any(test, feature = "operator"))]
+use crate::commands::{Computation, Seed, SigningKey, Verification};
+
+#[cfg(any(test, feature = "operator"))]
+impl Coordinator {
+    #[tracing::instrument(
+        skip(self, contributor, contributor_signing_key, contributor_seed),
+        fields(contributor = %contributor),
+    )]
+    pub fn contribute(
+        &mut self,
+        contributor: &Participant,
+        contributor_signing_key: &SigningKey,
+        contributor_seed: &Seed,
+    ) -> Result<(), CoordinatorError> {
+        let (_chunk_id, locked_locators) = self.try_lock(contributor)?;
+        let response_locator = locked_locators.next_contribution();
+        let round_height = response_locator.round_height();
+        let chunk_id = response_locator.chunk_id();
+        let contribution_id = response_locator.contribution_id();
+
+        // check that the response matches a pending task
+        {
+            let response_task = Task::new(chunk_id, contribution_id);
+            let info = self.state.current_participant_info(contributor).unwrap().clone();
+
+            if info.pending_tasks().is_empty() {
+                return Err(CoordinatorError::ContributorPendingTasksCannotBeEmpty(
+                    contributor.clone(),
+                ));
+            }
+
+            if !info
+                .pending_tasks()
+                .iter()
+                .find(|pending_task| pending_task == &&response_task)
+                .is_some()
+            {
+                return Err(CoordinatorError::PendingTasksMustContainResponseTask { response_task });
+            }
+        }
+
+        debug!("Computing contributions for round {} chunk {}", round_height, chunk_id);
+        self.run_computation(
+            round_height,
+            chunk_id,
+            contribution_id,
+            contributor,
+            contributor_signing_key,
+            contributor_seed,
+        )?;
+        let _response = self.try_contribute(contributor, chunk_id)?;
+        debug!(
+            "Successfully computed contribution for round {} chunk {}",
+            round_height, chunk_id
+        );
+        Ok(())
+    }
+
+    ///
+    /// Returns the tasks pending verification, excluding the ones whose
+    /// contribution has already been verified in the current round.
+    ///
+    pub fn get_pending_verifications(&self) -> HashMap<Task, Participant> {
+        let pending = self.state.get_pending_verifications();
+
+        let round = match self.current_round() {
+            Ok(round) => round,
+            Err(_) => return pending.clone(),
+        };
+
+        pending
+            .iter()
+            .filter(|(task, _)| {
+                !round
+                    .chunk(task.chunk_id())
+                    .and_then(|chunk| chunk.get_contribution(task.contribution_id()))
+                    .map(|contribution| contribution.is_verified())
+                    .unwrap_or(false)
+            })
+            .map(|(task, verifier)| (*task, verifier.clone()))
+            .collect()
+    }
+
+    ///
+    /// Removes from the state the tasks pending verification whose
+    /// contribution is already verified in the current round, e.g. because
+    /// the coordinator stopped before saving its state after verifying it.
+    ///
+    fn resume_pending_verifications(&mut self) -> Result<(), CoordinatorError> {
+        let unverified = self.get_pending_verifications();
+        let verified: Vec<Task> = self
+            .state
+            .get_pending_verifications()
+            .keys()
+            .filter(|task| !unverified.contains_key(task))
+            .copied()
+            .collect();
+        if verified.is_empty() {
+            return Ok(());
+        }
+
+        for task in &verified {
+            info!(
+                "Chunk {} contribution {} was already verified",
+                task.chunk_id(),
+                task.contribution_id()
+            );
+            self.state.remove_pending_verification(task)?;
+        }
+
+        self.save_state()
+    }
+
+    /// Moves to the quarantine directory the contribution files of the current and previous
+    /// rounds, with their signatures and uploads, which belong neither to a contribution
+    /// recorded in the round nor to a contribution or verification in progress, e.g. the
+    /// partial files of a lock released by a crash, possibly while the round was advancing.
+    /// Left in place, they would collide with the next contribution to the same locator.
+    fn reclaim_orphaned_contribution_files(&mut self) -> Result<(), CoordinatorError> {
+        let mut rounds = vec![self.current_round()?];
+        if let Some(previous_round_height) = rounds[0].round_height().checked_sub(1) {
+            rounds.push(self.get_round(previous_round_height)?);
+        }
+
+        for round in rounds {
+            let mut recorded = HashSet::new();
+            for chunk in round.chunks() {
+                for contribution in chunk.get_contributions().values() {
+                    for path in contribution.get_locators() {
+                        recorded.insert(self.storage.to_locator(&path)?);
+                    }
+                }
+            }
+
+            for (path, locator) in self.storage.contribution_files(round.round_height())? {
+                let (chunk_id, contribution_id) = match &locator {
+                    Locator::ContributionFile(locator) => (locator.chunk_id(), locator.contribution_id()),
+                    Locator::ContributionFileSignature(locator) => (locator.chunk_id(), locator.contribution_id()),
+                    _ => continue,
+                };
+
+                // The challenge of the round and the files of a lock have no record (yet)
+                let is_in_progress = round.chunk(chunk_id).map_or(false, |chunk| {
+                    chunk.is_locked() && contribution_id >= chunk.current_contribution_id()
+                });
+                if contribution_id == 0 || is_in_progress || recorded.contains(&locator) {
+                    continue;
+                }
+
+                let quarantined_path = self.storage.quarantine(&path)?;
+                warn!(
+                    "Moved the orphaned contribution file {} to {}",
+                    path.display(),
+                    quarantined_path.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify a contribution using the coordinator's default verifier.
+    /// This is just an interface to [`verify`]
+    ///
+    /// # Error
+    /// This function assumes that the given task has been indeed assigned to the
+    /// default verifier.
+    pub fn default_verify(&mut self, task: &Task) -> anyhow::Result<()> {
+        let verifier = self
+            .environment
+            .coordinator_verifiers()
+            .first()
+            .ok_or_else(|| CoordinatorError::VerifierMissing)?
+            .clone();
+        let sigkey = self.environment.default_verifier_signing_key();
+
+        self.verify(&verifier, &sigkey, task)
+    }
+
+    #[tracing::instrument(
+        skip(self, verifier, verifier_signing_key),
+        fields(verifier = %verifier),
+    )]
+    pub fn verify(
+        &mut self,
+        verifier: &Participant,
+        verifier_signing_key: &SigningKey,
+        task: &Task,
+    ) -> anyhow::Result<()> {
+        let round_height = self.current_round_height()?;
+
+        // Don't recompute the verified file of a response that has recently been verified,
+        // the verification is still recorded and checked by try_verify
+        let cache_key = self.verification_cache_key(round_height, task).ok();
+        let cached = cache_key
+            .as_ref()
+            .and_then(|key| self.verification_cache.get(key))
+            .filter(|(verified_at, _)| self.time.now_utc() - *verified_at < VERIFICATION_CACHE_TTL)
+            .filter(|(_, verified_path)| {
+                self.storage
+                    .to_locator(verified_path)
+                    .map(|locator| self.storage.exists(&locator))
+                    .unwrap_or(false)
+            })
+            .cloned();
+
+        let verified_path = match cached {
+            Some((verified_at, verified_path)) => {
+                debug!(
+                    "Reusing the verification for round {} chunk {} done at {}",
+                    round_height,
+                    task.chunk_id(),
+                    verified_at
+                );
+                verified_path
+            }
+            None => {
+                debug!(
+                    "Running verification for round {} chunk {}",
+                    round_height,
+                    task.chunk_id()
+                );
+                self.verification_runs += 1;
+                self.run_verification(round_height, task, verifier, verifier_signing_key)?
+            }
+        };
+        self.try_verify(verifier, task)?;
+
+        if let Some(cache_key) = cache_key {
+            let now = self.time.now_utc();
+            self.verification_cache
+                .retain(|_, (verified_at, _)| now - *verified_at < VERIFICATION_CACHE_TTL);
+            self.verification_cache.insert(cache_key, (now, verified_path));
+        }
+
+        debug!(
+            "Successful verification for round {} chunk {}",
+            round_height,
+            task.chunk_id()
+        );
+        Ok(())
+    }
+
+    ///
+    /// Returns the key of the verification cache for the given task: the locator of
+    /// the contribution and the hash of the response file in storage.
+    ///
+    #[inline]
+    fn verification_cache_key(
+        &self,
+        round_height: u64,
+        task: &Task,
+    ) -> Result<(ContributionLocator, String), CoordinatorError> {
+        let contribution_locator =
+            ContributionLocator::new(round_height, task.chunk_id(), task.contribution_id(), false);
+        let response = self.storage.reader(&Locator::ContributionFile(contribution_locator))?;
+
+        Ok((contribution_locator, hex::encode(calculate_hash(response.as_ref()))))
+    }
+
+    ///
+    /// Returns the number of verifications run by this coordinator, the ones reusing
+    /// a cached verified file excluded.
+    ///
+    #[inline]
+    pub fn verification_runs(&self) -> u64 {
+        self.verification_runs
+    }
+
+    ///
+    /// Attempts to run computation for a given round height, given chunk ID, and contribution ID.
+    ///
+    /// This function is used for testing purposes and can also be used for completing contributions
+    /// of participants who may have dropped off and handed over control of their session.
+    ///
+    #[inline]
+    pub fn run_computation(
+        &mut self,
+        round_height: u64,
+        chunk_id: u64,
+        contribution_id: u64,
+        participant: &Participant,
+        participant_signing_key: &SigningKey,
+        participant_seed: &Seed,
+    ) -> Result<(), CoordinatorError> {
+        info!(
+            "Running computation for round {} chunk {} contribution {} as {}",
+            round_height, chunk_id, contribution_id, participant
+        );
+
+        // Check that the chunk ID is valid.
+        if chunk_id > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
+        }
+
+        // Check that the contribution ID is valid.
+        if contribution_id == 0 {
+            return Err(CoordinatorError::ContributionIdMustBeNonzero);
+        }
+
+        // Check that the participant is a contributor.
+        if !participant.is_contributor() {
+            return Err(CoordinatorError::ExpectedContributor);
+        }
+
+        // Fetch the specified round from storage.
+        let round = Self::load_round(&mut self.storage, round_height)?;
+
+        // Check that the chunk lock is currently held by this contributor.
+        if !round.is_chunk_locked_by(chunk_id, &participant) {
+            error!("{} should have lock on chunk {} but does not", &participant, chunk_id);
+            return Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant);
+        }
+
+        // Check that the given contribution ID does not exist yet.
+        if round.chunk(chunk_id)?.get_contribution(contribution_id).is_ok() {
+            return Err(CoordinatorError::ContributionShouldNotExist);
+        }
+
+        // Fetch the challenge locator.
+        let challenge_locator = &Locator::ContributionFile(ContributionLocator::new(
+            round_height,
+            chunk_id,
+            contribution_id - 1,
+            true,
+        ));
+        // Fetch the response locator.
+        let response_locator =
+            &Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, contribution_id, false));
+        // Fetch the contribution file signature locator.
+        let contribution_file_signature_locator = &Locator::ContributionFileSignature(
+            ContributionSignatureLocator::new(round_height, chunk_id, contribution_id, false),
+        );
+
+        info!(
+            "Starting computation on round {} chunk {} contribution {} as {}",
+            round_height, chunk_id, contribution_id, participant
+        );
+        Computation::run(
+            &self.environment,
+            &mut self.storage,
+            self.signature.clone(),
+            participant_signing_key,
+            challenge_locator,
+            response_locator,
+            contribution_file_signature_locator,
+            participant_seed,
+        )?;
+        info!(
+            "Completed computation on round {} chunk {} contribution {} as {}",
+            round_height, chunk_id, contribution_id, participant
+        );
+
+        Ok(())
+    }
+
+    ///
+    /// Attempts to run verification for a given round height, given chunk ID, and contribution ID.
+    ///
+    /// On success, this function returns the verified contribution locator.
+    ///
+    /// This function copies the unverified contribution into the verified contribution locator,
+    /// which is the next contribution ID within a round, or the next round height if this round
+    /// is complete.
+    ///
+    #[inline]
+    pub fn run_verification(
+        &mut self,
+        round_height: u64,
+        task: &Task,
+        participant: &Participant,
+        participant_signing_key: &SigningKey,
+    ) -> Result<LocatorPath, CoordinatorError> {
+        let chunk_id = task.chunk_id();
+        let contribution_id = task.contribution_id();
+        info!(
+            "Running verification for round {} chunk {} contribution {} as {}",
+            round_height, chunk_id, contribution_id, participant
+        );
+
+        // Check that the chunk ID is valid.
+        if chunk_id > self.environment.number_of_chunks() {
+            return Err(CoordinatorError::ChunkIdInvalid);
+        }
+
+        // Check that the contribution ID is valid.
+        if contribution_id == 0 {
+            return Err(CoordinatorError::ContributionIdMustBeNonzero);
+        }
+
+        // Check that the participant is a verifier.
+        if !participant.is_verifier() {
+            return Err(CoordinatorError::ExpectedVerifier);
+        }
+
+        // Fetch the specified round from storage.
+        let round = Self::load_round(&self.storage, round_height)?;
+
+        // Check that the contribution locator corresponding to the response file exists.
+        let response_locator =
+            Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, contribution_id, false));
+        if !self.storage.exists(&response_locator) {
+            error!(
+                "Response file at {} is missing",
+                self.storage.to_path(&response_locator)?
+            );
+            return Err(CoordinatorError::ContributionLocatorMissing);
+        }
+
+        // Fetch the chunk corresponding to the given chunk ID.
+        let chunk = round.chunk(chunk_id)?;
+
+        // Chat that the specified contribution ID has NOT been verified yet.
+        if chunk.get_contribution(contribution_id)?.is_verified() {
+            return Err(CoordinatorError::ContributionAlreadyVerified);
+        }
+
+        // Fetch whether this is the final contribution of the specified chunk.
+        let is_final_contribution = chunk.only_contributions_complete(round.expected_number_of_contributions());
+        info!(
+            "EXPECTED NUMBER OF CONTRIBUTIONS: {}",
+            round.expected_number_of_contributions()
+        );
+
+        // Fetch the verified response locator and the contribution file signature locator.
+        let verified_locator = match is_final_contribution {
+            true => Locator::ContributionFile(ContributionLocator::new(round_height + 1, chunk_id, 0, true)),
+            false => Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, contribution_id, true)),
+        };
+
+        info!(
+            "Starting verification on round {} chunk {} contribution {} as {}",
+            round_height, chunk_id, contribution_id, participant
+        );
+        let started_at = std::time::Instant::now();
+        Verification::run(
+            &self.environment,
+            &mut self.storage,
+            self.signature.clone(),
+            participant_signing_key,
+            round_height,
+            chunk_id,
+            contribution_id,
+            is_final_contribution,
+        )?;
+        let duration = started_at.elapsed();
+        self.state.record_verification_timing(round_height, *task, duration);
+        info!(
+            "Completed verification on round {} chunk {} contribution {} as {} in {:?}",
+            round_height, chunk_id, contribution_id, participant, duration
+        );
+
+        // Check that the verified contribution locator exists.
+        if !self.storage.exists(&verified_locator) {
+            let verified_response = self.storage.to_path(&verified_locator)?;
+            error!("Verified response file at {} is missing", verified_response);
+            return Err(CoordinatorError::ContributionLocatorMissing);
+        }
+
+        Ok(self.storage.to_path(&verified_locator)?)
+    }
+
+    ///
+    /// Returns a reference to the instantiation of `CoordinatorState` that this
+    /// coordinator is using.
+    ///
+    #[inline]
+    pub fn state(&self) -> &CoordinatorState {
+        &self.state
+    }
+
+    ///
+    /// Returns a reference to the instantiation of `Environment` that this
+    /// coordinator is using.
+    ///
+    #[inline]
+    pub fn environment(&self) -> &Environment {
+        &self.environment
+    }
+
+    ///
+    /// Rollback a task which was locked by a contributor. Should be used to unlock
+    /// chunks which become stuck during the ceremony.
+    ///
+    pub fn rollback_locked_task(&mut self, participant: &Participant, task: Task) -> Result<(), CoordinatorError> {
+        self.state.rollback_locked_task(participant, task, &*self.time)?;
+        self.save_state()?;
+
+        let mut round = self.current_round()?;
+        round.remove_locks_unsafe(&mut self.storage, participant, &[task.chunk_id()])?;
+
+        Ok(self.storage.process(StorageAction::Update(UpdateAction {
+            locator: Locator::RoundState {
+                round_height: self.current_round_height()?,
+            },
+            object: Object::RoundState(round),
+        }))?)
+    }
+
+    ///
+    /// Releases the lock on the given chunk, whoever holds it, and discards the
+    /// partial contribution of the lock holder, so that the chunk can be locked
+    /// again. Should be used to unlock a chunk whose holder is gone before the
+    /// lock is reclaimed automatically.
+    ///
+    /// On success, returns the participant which held the lock.
+    ///
+    pub fn force_release_lock(&mut self, chunk_id: u64) -> Result<Participant, CoordinatorError> {
+        let participant = self
+            .current_round()?
+            .chunk(chunk_id)?
+            .lock_holder()
+            .clone()
+            .ok_or(CoordinatorError::ChunkNotLockedOrByWrongParticipant)?;
+        let task = self
+            .pending_task(&participant, chunk_id)?
+            .ok_or(CoordinatorError::ChunkNotLockedOrByWrongParticipant)?;
+        let locked_locators = self.issued_locators(&participant)?;
+
+        self.rollback_locked_task(&participant, task)?;
+        self.storage.discard_upload(&locked_locators.next_contribution())?;
+
+        warn!("Released the lock of {} on chunk {}", participant, chunk_id);
+        Ok(participant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        authentication::{Dummy, Signature},
+        commands::{Seed, SigningKey, SEED_LENGTH},
+        environment::*,
+        objects::{
+            hash_email, ClientProvenance, ContributionFileSignature, ContributionInfo, ContributionProvenance,
+            ContributionState, Participant, Task, TrimmedContributionInfo,
+        },
+        storage::{ContributionLocator, ContributionSignatureLocator, Locator, Object, StorageLocator, StorageObject},
+        testing::prelude::*,
+        ChunkTranscript,
+        Coordinator,
+        CoordinatorError,
+        MockTimeSource,
+        RoundTranscript,
+        TimingHistogram,
+        VerificationFailureReason,
+        VerificationTimingStats,
+    };
+
+    use blake2::{Blake2b512, Digest};
+    use once_cell::sync::Lazy;
+    use rand::RngCore;
+    use setup_utils::calculate_hash;
+    use std::{
+        collections::HashMap,
+        net::{IpAddr, Ipv4Addr},
+        path::{Path, PathBuf},
+        sync::{Arc, Barrier, RwLock},
+    };
+    use time::OffsetDateTime;
+
+    fn initialize_to_round_1(
+        coordinator: &mut Coordinator,
+        contributors: &[(Participant, IpAddr)],
+    ) -> anyhow::Result<()> {
+        // Initialize the ceremony and add the contributors and verifiers to the queue.
+        {
+            // Run initialization.
+            info!("Initializing ceremony");
+            let round_height = coordinator.run_initialization(*TEST_STARTED_AT)?;
+            info!("Initialized ceremony");
+
+            // Check current round height is now 0.
+            assert_eq!(0, round_height);
+
+            // Initialize the coordinator state to round 0.
+            coordinator.state.initialize(round_height);
+            coordinator.state.save(&mut coordinator.storage)?;
+
+            // Add the contributor and verifier of the coordinator to execute round 1.
+            for (contributor, contributor_ip) in contributors {
+                coordinator.state.add_to_queue(
+                    contributor.clone(),
+                    Some(*contributor_ip),
+                    String::from("irrelevant_token"), // No check will be done on this token
+                    10,
+                    coordinator.time.as_ref(),
+                )?;
+            }
+
+            // Update the queue.
+            coordinator.state.update_queue()?;
+            coordinator.state.save(&mut coordinator.storage)?;
+        }
+
+        info!("Advancing ceremony to round 1");
+        coordinator.try_advance(*TEST_STARTED_AT)?;
+        info!("Advanced ceremony to round 1");
+
+        // Check current round height is now 1.
+        assert_eq!(1, coordinator.current_round_height()?);
+
+        // info!("Add contributions and verifications for round 1");
+        // for _ in 0..coordinator.environment.number_of_chunks() {
+        //     for contributor in contributors {
+        //         coordinator.contribute(contributor)?;
+        //     }
+        //     for verifier in verifiers {
+        //         coordinator.verify(verifier)?;
+        //     }
+        // }
+        // info!("Added contributions and verifications for round 1");
+
+        Ok(())
+    }
+
+    fn initialize_coordinator(coordinator: &mut Coordinator) -> anyhow::Result<()> {
+        // Load the contributors and verifiers.
+        let contributors = vec![
+            Lazy::force(&TEST_CONTRIBUTOR_ID).clone(),
+            Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone(),
+        ];
+
+        let contributor_ips = vec![
+            IpAddr::V4("0.0.0.1".parse().unwrap()),
+            IpAddr::V4("0.0.0.2".parse().unwrap()),
+        ];
+
+        let contributors: Vec<(Participant, IpAddr)> = contributors.into_iter().zip(contributor_ips).collect();
+
+        initialize_to_round_1(coordinator, &contributors)
+    }
+
+    fn initialize_coordinator_single_contributor(coordinator: &mut Coordinator) -> anyhow::Result<()> {
+        // Load the contributors and verifiers.
+        let contributors = vec![(
+            Lazy::force(&TEST_CONTRIBUTOR_ID).clone(),
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        )];
+
+        initialize_to_round_1(coordinator, &contributors)
+    }
+
+    /// Contributes once to each chunk of the current round as the given contributor and verifies the contributions.
+    fn contribute_to_current_round(coordinator: &mut Coordinator, contributor: &Participant) -> anyhow::Result<()> {
+        let round_height = coordinator.current_round_height()?;
+        let signing_key: SigningKey = "secret_key".to_string();
+        let verifier = Lazy::force(&TEST_VERIFIER_ID);
+
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+
+        for chunk_id in 0..coordinator.environment().number_of_chunks() {
+            let contribution_id = coordinator
+                .try_lock_chunk(chunk_id, contributor)?
+                .next_contribution()
+                .contribution_id();
+            coordinator.run_computation(
+                round_height,
+                chunk_id,
+                contribution_id,
+                contributor,
+                &signing_key,
+                &seed,
+            )?;
+            coordinator.add_contribution(chunk_id, contributor)?;
+
+            let task = Task::new(chunk_id, contribution_id);
+            coordinator.run_verification(round_height, &task, verifier, &signing_key)?;
+            coordinator.verify_contribution(&task, verifier)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_initialization_matches_json() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT.clone(), Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
+
+        // Check that round 0 matches the round 0 JSON specification.
+        {
+            let now = OffsetDateTime::now_utc();
+
+            // Fetch round 0 from coordinator.
+            let mut expected = test_round_0_json()?;
+            let mut candidate = coordinator.get_round(0)?;
+
+            // Set the finish time to match.
+            println!("{} {}", expected.is_complete(), candidate.is_complete());
+            expected.try_finish_testing_only_unsafe(now.clone());
+            candidate.try_finish_testing_only_unsafe(now);
+
+            print_diff(&expected, &candidate);
+            assert_eq!(expected, candidate);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_initialization() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+
+        {
+            // Run initialization.
+            info!("Initializing ceremony");
+            let round_height = coordinator.run_initialization(*TEST_STARTED_AT).unwrap();
+            info!("Initialized ceremony");
+
+            // Check current round height is now 0.
+            assert_eq!(0, round_height);
+
+            // Load the contributors and verifiers.
+            let contributors = vec![
+                Lazy::force(&TEST_CONTRIBUTOR_ID).clone(),
+                Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone(),
+            ];
+
+            // Start round 1.
+            coordinator.next_round(*TEST_STARTED_AT, contributors).unwrap();
+        }
+
+        {
+            // Check round 0 is complete.
+            assert!(coordinator.get_round(0)?.is_complete());
+
+            // Check current round height is now 1.
+            assert_eq!(1, coordinator.current_round_height()?);
+
+            // Check that the coordinator participants are established correctly.
+            assert!(coordinator.is_coordinator_contributor(&TEST_CONTRIBUTOR_ID));
+            assert!(coordinator.is_coordinator_verifier(&TEST_VERIFIER_ID));
+
+            // Check the current round has a matching height.
+            let current_round = coordinator.current_round()?;
+            assert_eq!(coordinator.current_round_height()?, current_round.round_height());
+
+            // Check round 1 contributors.
+            assert_eq!(2, current_round.number_of_contributors());
+            assert!(current_round.is_contributor(&TEST_CONTRIBUTOR_ID));
+            assert!(current_round.is_contributor(&TEST_CONTRIBUTOR_ID_2));
+            assert!(!current_round.is_contributor(&TEST_CONTRIBUTOR_ID_3));
+            assert!(!current_round.is_contributor(&TEST_VERIFIER_ID));
+
+            // Check round 1 verifiers.
+            assert_eq!(0, current_round.number_of_verifiers());
+
+            // Check round 1 is NOT complete.
+            assert!(!current_round.is_complete());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_force_release_lock() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
+
+        // The contributor locks a chunk and starts uploading its contribution before its client dies
+        let (chunk_id, locked_locators) = coordinator.try_lock(contributor)?;
+        let next_contribution = locked_locators.next_contribution();
+        assert_eq!(
+            64,
+            coordinator.write_contribution_part(&next_contribution, 0, &[0; 64])?
+        );
+        assert!(coordinator.try_lock_chunk(chunk_id, contributor_2).is_err());
+
+        assert_eq!(contributor, &coordinator.force_release_lock(chunk_id)?);
+        assert!(coordinator.current_round()?.chunk(chunk_id)?.is_unlocked());
+        assert!(coordinator.issued_locators(contributor).is_err());
+        assert!(matches!(
+            coordinator.force_release_lock(chunk_id),
+            Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant)
+        ));
+
+        // The partial upload was discarded
+        assert!(matches!(
+            coordinator.write_contribution_part(&next_contribution, 64, &[0; 64]),
+            Err(CoordinatorError::ContributionUploadGap { expected: 0, found: 64 })
+        ));
+
+        // Another participant can now lock the chunk
+        assert!(coordinator.try_lock_chunk(chunk_id, contributor_2).is_ok());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_contributor_try_lock_chunk() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
+
+        {
+            // Acquire the lock for chunk 0 as contributor 1.
+            assert!(coordinator.try_lock_chunk(0, &contributor).is_ok());
+
+            // Attempt to acquire the lock for chunk 0 as contributor 1 again.
+            assert!(coordinator.try_lock_chunk(0, &contributor).is_err());
+
+            // Acquire the lock for chunk 1 as contributor 1.
+            assert!(coordinator.try_lock_chunk(1, &contributor).is_ok());
+
+            // Attempt to acquire the lock for chunk 0 as contributor 2.
+            assert!(coordinator.try_lock_chunk(0, &contributor_2).is_err());
+
+            // Attempt to acquire the lock for chunk 1 as contributor 2.
+            assert!(coordinator.try_lock_chunk(1, &contributor_2).is_err());
+
+            // Acquire the lock for chunk 1 as contributor 2.
+            assert!(coordinator.try_lock_chunk(2, &contributor_2).is_ok());
+        }
+
+        {
+            // Check that chunk 0 is locked.
+            let round = coordinator.current_round()?;
+            let chunk = round.chunk(0)?;
+            assert!(chunk.is_locked());
+            assert!(!chunk.is_unlocked());
+
+            // Check that chunk 0 is locked by contributor 1.
+            assert!(chunk.is_locked_by(contributor));
+            assert!(!chunk.is_locked_by(contributor_2));
+
+            // Check that chunk 1 is locked.
+            let chunk = round.chunk(1)?;
+            assert!(chunk.is_locked());
+            assert!(!chunk.is_unlocked());
+
+            // Check that chunk 1 is locked by contributor 1.
+            assert!(chunk.is_locked_by(contributor));
+            assert!(!chunk.is_locked_by(contributor_2));
+
+            // Check that chunk 2 is locked.
+            let chunk = round.chunk(2)?;
+            assert!(chunk.is_locked());
+            assert!(!chunk.is_unlocked());
+
+            // Check that chunk 2 is locked by contributor 2.
+            assert!(chunk.is_locked_by(contributor_2));
+            assert!(!chunk.is_locked_by(contributor));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_contributor_add_contribution() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
+
+        {
+            // Acquire the lock for chunk 0 as contributor 1.
+            coordinator.try_lock_chunk(0, &contributor)?;
+        }
+
+        // Run computation on round 1 chunk 0 contribution 1.
+        {
+            // Check current round is 1.
+            let round = coordinator.current_round()?;
+            let round_height = round.round_height();
+            assert_eq!(1, round_height);
+
+            // Check chunk 0 is not verified.
+            let chunk_id = 0;
+            let chunk = round.chunk(chunk_id)?;
+            assert!(!chunk.is_complete(round.expected_number_of_contributions()));
+
+            // Check next contribution is 1.
+            let contribution_id = 1;
+            assert!(chunk.is_next_contribution_id(contribution_id, round.expected_number_of_contributions()));
+
+            // Run the computation
+            let mut seed: Seed = [0; SEED_LENGTH];
+            rand::thread_rng().fill_bytes(&mut seed[..]);
+            assert!(coordinator
+                .run_computation(
+                    round_height,
+                    chunk_id,
+                    contribution_id,
+                    &contributor,
+                    &contributor_signing_key,
+                    &seed
+                )
+                .is_ok());
+        }
+
+        // Add contribution for round 1 chunk 0 contribution 1.
+        let chunk_id = 0;
+        {
+            // Add round 1 chunk 0 contribution 1.
+            assert!(coordinator.add_contribution(chunk_id, &contributor).is_ok());
+        }
+        {
+            // Check chunk 0 lock is released.
+            let round = coordinator.current_round()?;
+            let chunk = round.chunk(chunk_id)?;
+            assert!(chunk.is_unlocked());
+            assert!(!chunk.is_locked());
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_verifier_verify_contribution() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
+
+        // Check current round height is now 1.
+        let round_height = coordinator.current_round_height()?;
+        assert_eq!(1, round_height);
+
+        // Acquire the lock for chunk 0 as contributor 1.
+        let chunk_id = 0;
+        let contribution_id = 1;
+        {
+            assert!(coordinator.try_lock_chunk(chunk_id, &contributor).is_ok());
+        }
+        {
+            // Run computation on round 1 chunk 0 contribution 1.
+            let mut seed: Seed = [0; SEED_LENGTH];
+            rand::thread_rng().fill_bytes(&mut seed[..]);
+            assert!(coordinator
+                .run_computation(
+                    round_height,
+                    chunk_id,
+                    contribution_id,
+                    contributor,
+                    &contributor_signing_key,
+                    &seed
+                )
+                .is_ok());
+
+            // Add round 1 chunk 0 contribution 1.
+            assert!(coordinator.add_contribution(chunk_id, &contributor).is_ok());
+        }
+
+        // Verify round 1 chunk 0 contribution 1.
+        {
+            let verifier = Lazy::force(&TEST_VERIFIER_ID).clone();
+            let verifier_signing_key: SigningKey = "secret_key".to_string();
+
+            // Run verification.
+            let task = Task::new(chunk_id, contribution_id);
+            let verify = coordinator.run_verification(round_height, &task, &verifier, &verifier_signing_key);
+            assert!(verify.is_ok());
+            // Verify contribution 1.
+            coordinator.verify_contribution(&task, &verifier)?;
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    // This test runs a round with a single coordinator and single verifier
+    // The verifier instances are run on a separate thread to simulate an environment where
+    // verification and contribution happen concurrently.
+    fn coordinator_concurrent_contribution_verification() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        // Check current round height is now 1.
+        let round_height = coordinator.current_round_height()?;
+        assert_eq!(1, round_height);
+
+        let coordinator = std::sync::Arc::new(std::sync::RwLock::new(coordinator));
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+        let verifier = Lazy::force(&TEST_VERIFIER_ID);
+
+        let mut verifier_threads = vec![];
+
+        let contribution_id = 1;
+
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+
+        for chunk_id in 0..TEST_ENVIRONMENT_3.number_of_chunks() {
+            {
+                // Acquire the lock as contributor.
+                let try_lock = coordinator.write().unwrap().try_lock_chunk(chunk_id, &contributor);
+                if try_lock.is_err() {
+                    println!(
+                        "Failed to acquire lock for chunk {} as contributor {:?}\n{}",
+                        chunk_id,
+                        &contributor,
+                        serde_json::to_string_pretty(&coordinator.read().unwrap().current_round()?)?
+                    );
+                    try_lock?;
+                }
+            }
+            {
+                // Run computation as contributor.
+                let contribute = coordinator.write().unwrap().run_computation(
+                    round_height,
+                    chunk_id,
+                    contribution_id,
+                    &contributor,
+                    &contributor_signing_key,
+                    &seed,
+                );
+                if contribute.is_err() {
+                    println!(
+                        "Failed to run computation for chunk {} as contributor {:?}\n{}",
+                        chunk_id,
+                        &contributor,
+                        serde_json::to_string_pretty(&coordinator.read().unwrap().current_round()?)?
+                    );
+                    contribute?;
+                }
+
+                // Add the contribution as the contributor.
+                let contribute = coordinator.write().unwrap().add_contribution(chunk_id, &contributor);
+                if contribute.is_err() {
+                    println!(
+                        "Failed to add contribution for chunk {} as contributor {:?}\n{}",
+                        chunk_id,
+                        &contributor,
+                        serde_json::to_string_pretty(&coordinator.read().unwrap().current_round()?)?
+                    );
+                    contribute?;
+                }
+            }
+
+            // Spawn a thread to concurrently verify the contributions.
+            let coordinator_clone = coordinator.clone();
+
+            let verifier_thread = std::thread::spawn(move || {
+                let verifier = verifier.clone();
+                let verifier_signing_key: SigningKey = "secret_key".to_string();
+
+                {
+                    // Run verification as verifier.
+                    let task = Task::new(chunk_id, contribution_id);
+                    let verify = coordinator_clone.write().unwrap().run_verification(
+                        round_height,
+                        &task,
+                        &verifier,
+                        &verifier_signing_key,
+                    );
+                    if verify.is_err() {
+                        error!(
+                            "Failed to run verification as verifier {:?}\n{}",
+                            &verifier,
+                            serde_json::to_string_pretty(&coordinator_clone.read().unwrap().current_round().unwrap())
+                                .unwrap()
+                        );
+                        verify.unwrap();
+                    }
+
+                    // Add the verification as the verifier.
+                    let verify = coordinator_clone.write().unwrap().verify_contribution(&task, &verifier);
+                    if verify.is_err() {
+                        println!(
+                            "Failed to run verification as verifier {:?}\n{}",
+                            verifier.clone(),
+                            serde_json::to_string_pretty(&coordinator_clone.read().unwrap().current_round().unwrap())
+                                .unwrap()
+                        );
+                        panic!("{:?}", verify.unwrap())
+                    }
+                }
+            });
+            verifier_threads.push(verifier_thread);
+        }
+
+        for verifier_thread in verifier_threads {
+            verifier_thread.join().expect("Couldn't join on the verifier thread");
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_aggregation() {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy)).unwrap();
+        initialize_coordinator(&mut coordinator).unwrap();
+
+        // Check current round height is now 1.
+        let round_height = coordinator.current_round_height().unwrap();
+        assert_eq!(1, round_height);
+
+        // Run computation and verification on each contribution in each chunk.
+        let contributors = vec![
+            Lazy::force(&TEST_CONTRIBUTOR_ID).clone(),
+            Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone(),
+        ];
+
+        let verifier = Lazy::force(&TEST_VERIFIER_ID).clone();
+        let verifier_signing_key: SigningKey = "secret_key".to_string();
+
+        let mut seeds = HashMap::new();
+
+        // Iterate over all chunk IDs.
+        for chunk_id in 0..TEST_ENVIRONMENT_3.number_of_chunks() {
+            // As contribution ID 0 is initialized by the coordinator, iterate from
+            // contribution ID 1 up to the expected number of contributions.
+            for contribution_id in 1..coordinator.current_round().unwrap().expected_number_of_contributions() {
+                let contributor = &contributors[contribution_id as usize - 1].clone();
+                let contributor_signing_key: SigningKey = "secret_key".to_string();
+
+                trace!("{} is processing contribution {}", contributor, contribution_id);
+                {
+                    // Acquire the lock as contributor.
+                    let try_lock = coordinator.try_lock_chunk(chunk_id, &contributor);
+                    if try_lock.is_err() {
+                        println!(
+                            "Failed to acquire lock for chunk {} as contributor {:?}\n{}",
+                            chunk_id,
+                            &contributor,
+                            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
+                        );
+                        try_lock.unwrap();
+                    }
+                }
+                {
+                    let seed = if seeds.contains_key(&contribution_id) {
+                        seeds[&contribution_id]
+                    } else {
+                        let mut seed: Seed = [0; SEED_LENGTH];
+                        rand::thread_rng().fill_bytes(&mut seed[..]);
+                        seeds.insert(contribution_id.clone(), seed);
+                        seed
+                    };
+
+                    // Run computation as contributor.
+                    let contribute = coordinator.run_computation(
+                        round_height,
+                        chunk_id,
+                        contribution_id,
+                        &contributor,
+                        &contributor_signing_key,
+                        &seed,
+                    );
+                    if contribute.is_err() {
+                        error!(
+                            "Failed to run computation as contributor {:?}\n{}",
+                            &contributor,
+                            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
+                        );
+                        contribute.unwrap();
+                    }
+
+                    // Add the contribution as the contributor.
+                    let contribute = coordinator.add_contribution(chunk_id, &contributor);
+                    if contribute.is_err() {
+                        error!(
+                            "Failed to add contribution as contributor {:?}\n{}",
+                            &contributor,
+                            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
+                        );
+                        contribute.unwrap();
+                    }
+                }
+                {
+                    // Run verification as verifier.
+                    let task = Task::new(chunk_id, contribution_id);
+                    let verify = coordinator.run_verification(round_height, &task, &verifier, &verifier_signing_key);
+                    if verify.is_err() {
+                        error!(
+                            "Failed to run verification as verifier {:?}\n{}",
+                            &verifier,
+                            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
+                        );
+                        verify.unwrap();
+                    }
+
+                    // Add the verification as the verifier.
+                    let verify = coordinator.verify_contribution(&task, &verifier);
+                    if verify.is_err() {
+                        error!(
+                            "Failed to run verification as verifier {:?}\n{}",
+                            &verifier,
+                            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
+                        );
+                        verify.unwrap();
+                    }
+                }
             }
         }
 
-        // Instantiate the new round and height.
-        let new_round = Round::new(
-            &self.environment,
-            &mut self.storage,
-            new_height,
-            started_at,
-            contributors,
-        )?;
-
-        #[cfg(test)]
-        trace!("{:#?}", &new_round);
+        println!(
+            "Starting aggregation with this transcript {}",
+            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
+        );
 
-        // Insert the new round into storage.
-        self.storage.insert(
-            Locator::RoundState {
-                round_height: new_height,
-            },
-            Object::RoundState(new_round),
-        )?;
+        {
+            // Run aggregation for round 1.
+            coordinator.aggregate_contributions().unwrap();
+        }
 
-        // Next, update the round height to reflect the new round.
-        self.storage
-            .update(&Locator::RoundHeight, Object::RoundHeight(new_height))?;
+        // Check that the round is still round 1, as try_advance has not been called.
+        assert_eq!(1, coordinator.current_round_height().unwrap());
 
-        debug!("Added round {} to storage", current_round_height);
-        info!("Transitioned from round {} to {}", current_round_height, new_height);
-        Ok(new_height)
+        println!(
+            "Finished aggregation with this transcript {}",
+            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
+        );
     }
 
-    ///
-    /// Attempts to run initialization for the ceremony.
-    ///
-    /// If this is the initial round, ensure the round does not exist yet.
-    ///
-    /// If there is no round in storage, proceed to create a new round instance,
-    /// and run `Initialization` to start the ceremony.
-    ///
-    #[inline]
-    pub(super) fn run_initialization(&mut self, started_at: OffsetDateTime) -> Result<u64, CoordinatorError> {
-        // Check that the ceremony has not begun yet.
-        if Self::load_current_round_height(&self.storage).is_ok() {
-            return Err(CoordinatorError::RoundAlreadyInitialized);
-        }
+    #[test]
+    #[serial]
+    fn coordinator_next_round() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
 
-        // Establish the round height convention for initialization.
-        let round_height = 0;
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        // Check that the current round does not exist in storage.
-        if self.storage.exists(&Locator::RoundState { round_height }) {
-            return Err(CoordinatorError::RoundShouldNotExist);
-        }
+        let round_height = 1;
+        assert_eq!(round_height, coordinator.current_round_height()?);
 
-        // Check that the next round does not exist in storage.
-        if self.storage.exists(&Locator::RoundState {
-            round_height: round_height + 1,
-        }) {
-            return Err(CoordinatorError::RoundShouldNotExist);
-        }
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_signing_key: SigningKey = "secret_key".to_string();
 
-        // Create an instantiation of `Round` for round 0.
-        let mut round = {
-            // Initialize the contributors as an empty list as this is for initialization.
-            let contributors = vec![];
+        let verifier = Lazy::force(&TEST_VERIFIER_ID);
+        let verifier_signing_key: SigningKey = "secret_key".to_string();
 
-            // Create a new round instance.
-            Round::new(
-                &self.environment,
-                &mut self.storage,
-                round_height,
-                started_at,
-                contributors,
-            )?
-        };
+        // Run computation and verification on each contribution in each chunk.
+        let mut seeds = HashMap::new();
+        for chunk_id in 0..TEST_ENVIRONMENT_3.number_of_chunks() {
+            // Ensure contribution ID 0 is already verified by the coordinator.
+            assert!(coordinator
+                .current_round()?
+                .chunk(chunk_id)?
+                .get_contribution(0)?
+                .is_verified());
 
-        info!("Starting initialization of round {}", round_height);
+            // As contribution ID 0 is initialized by the coordinator, iterate from
+            // contribution ID 1 up to the expected number of contributions.
+            for contribution_id in 1..coordinator.current_round()?.expected_number_of_contributions() {
+                {
+                    // Acquire the lock as contributor.
+                    let try_lock = coordinator.try_lock_chunk(chunk_id, &contributor);
+                    if try_lock.is_err() {
+                        error!(
+                            "Failed to acquire lock as contributor {:?}\n{}",
+                            &contributor,
+                            serde_json::to_string_pretty(&coordinator.current_round()?)?
+                        );
+                        try_lock?;
+                    }
+                }
+                {
+                    // Run computation as contributor.
+                    let seed = if seeds.contains_key(&contribution_id) {
+                        seeds[&contribution_id]
+                    } else {
+                        let mut seed: Seed = [0; SEED_LENGTH];
+                        rand::thread_rng().fill_bytes(&mut seed[..]);
+                        seeds.insert(contribution_id.clone(), seed);
+                        seed
+                    };
 
-        // Execute initialization of contribution 0 for all chunks
-        // in the new round and check that the new locators exist.
-        for chunk_id in 0..self.environment.number_of_chunks() {
-            // 1 - Check that the contribution locator corresponding to this round's chunk does not exist.
-            let locator = Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, 0, true));
-            if self.storage.exists(&locator) {
-                error!(
-                    "Contribution locator already exists ({})",
-                    self.storage.to_path(&locator)?
-                );
-                return Err(CoordinatorError::ContributionLocatorAlreadyExists);
-            }
+                    let contribute = coordinator.run_computation(
+                        round_height,
+                        chunk_id,
+                        contribution_id,
+                        &contributor,
+                        &contributor_signing_key,
+                        &seed,
+                    );
+                    if contribute.is_err() {
+                        error!(
+                            "Failed to run computation as contributor {:?}\n{}",
+                            &contributor,
+                            serde_json::to_string_pretty(&coordinator.current_round()?)?
+                        );
+                        contribute?;
+                    }
 
-            // 2 - Check that the contribution locator corresponding to the next round's chunk does not exists.
-            let locator = Locator::ContributionFile(ContributionLocator::new(round_height + 1, chunk_id, 0, true));
-            if self.storage.exists(&locator) {
-                error!(
-                    "Contribution locator already exists ({})",
-                    self.storage.to_path(&locator)?
-                );
-                return Err(CoordinatorError::ContributionLocatorAlreadyExists);
+                    // Add the contribution as the contributor.
+                    let contribute = coordinator.add_contribution(chunk_id, &contributor);
+                    if contribute.is_err() {
+                        error!(
+                            "Failed to add contribution as contributor {:?}\n{}",
+                            &contributor,
+                            serde_json::to_string_pretty(&coordinator.current_round()?)?
+                        );
+                        contribute?;
+                    }
+                }
+                {
+                    // Run verification as verifier.
+                    let task = Task::new(chunk_id, contribution_id);
+                    let verify = coordinator.run_verification(round_height, &task, &verifier, &verifier_signing_key);
+                    if verify.is_err() {
+                        error!(
+                            "Failed to run verification as verifier {:?}\n{}",
+                            &verifier,
+                            serde_json::to_string_pretty(&coordinator.current_round()?)?
+                        );
+                        verify?;
+                    }
+
+                    // Add the verification as the verifier.
+                    let verify = coordinator.verify_contribution(&task, &verifier);
+                    if verify.is_err() {
+                        error!(
+                            "Failed to run verification as verifier {:?}\n{}",
+                            &verifier,
+                            serde_json::to_string_pretty(&coordinator.current_round()?)?
+                        );
+                        verify?;
+                    }
+                }
             }
+        }
 
-            info!("Coordinator is starting initialization on chunk {}", chunk_id);
-            let _contribution_hash = Initialization::run(&self.environment, &mut self.storage, round_height, chunk_id)?;
-            info!("Coordinator completed initialization on chunk {}", chunk_id);
+        info!(
+            "Starting aggregation with this transcript {}",
+            serde_json::to_string_pretty(&coordinator.current_round()?)?
+        );
 
-            // 1 - Check that the contribution locator corresponding to this round's chunk now exists.
-            let locator = Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, 0, true));
-            if !self.storage.exists(&locator) {
-                error!("Contribution locator is missing ({})", self.storage.to_path(&locator)?);
-                return Err(CoordinatorError::ContributionLocatorMissing);
-            }
+        {
+            // Run aggregation for round 1.
+            coordinator.aggregate_contributions()?;
 
-            // 2 - Check that the contribution locator corresponding to the next round's chunk now exists.
-            let locator = Locator::ContributionFile(ContributionLocator::new(round_height + 1, chunk_id, 0, true));
-            if !self.storage.exists(&locator) {
-                error!("Contribution locator is missing ({})", self.storage.to_path(&locator)?);
-                return Err(CoordinatorError::ContributionLocatorMissing);
-            }
+            // Run aggregation and transition from round 1 to round 2.
+            coordinator.next_round(OffsetDateTime::now_utc(), vec![contributor.clone()])?;
         }
 
-        // Set the finished time for round 0.
-        round.try_finish(self.time.now_utc());
+        // Check that the ceremony has advanced to round 2.
+        assert_eq!(2, coordinator.current_round_height()?);
 
-        // Add the new round to storage.
-        self.storage
-            .insert(Locator::RoundState { round_height }, Object::RoundState(round))?;
+        info!(
+            "Finished aggregation with this transcript {}",
+            serde_json::to_string_pretty(&coordinator.current_round()?)?
+        );
 
-        // Next, add the round height to storage.
-        self.storage
-            .insert(Locator::RoundHeight, Object::RoundHeight(round_height))?;
+        Ok(())
+    }
 
-        info!("Completed initialization of round {}", round_height);
+    #[test]
+    #[serial]
+    #[ignore]
+    fn coordinator_number_of_chunks() {
+        let environment = &*Testing::from(Parameters::TestChunks { number_of_chunks: 4096 });
+        initialize_test_environment(environment);
 
-        Ok(round_height)
-    }
+        let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy)).unwrap();
+        initialize_coordinator(&mut coordinator).unwrap();
 
-    /// Update the round on disk after a drop has occured.
-    #[inline]
-    fn drop_participant_from_storage(&mut self, drop: &DropParticipant) -> Result<(), CoordinatorError> {
-        debug!(
-            "Dropping participant from storage with the following information: {:#?}",
-            drop
+        assert_eq!(
+            environment.number_of_chunks(),
+            coordinator.get_round(0).unwrap().chunks().len() as u64
         );
+    }
 
-        // Check the justification and extract the tasks.
-        let drop_data = match drop {
-            DropParticipant::DropCurrent(data) => data,
-            DropParticipant::DropQueue(_) => {
-                // Participant is not part of the round, therefore
-                // there is nothing to do.
-                return Ok(());
-            }
+    #[test]
+    #[serial]
+    fn coordinator_next_round_notifies_webhook() -> anyhow::Result<()> {
+        use crate::webhook::RoundTransitionEvent;
+        use std::{
+            io::{BufRead, BufReader, Read, Write},
+            net::TcpListener,
+            sync::mpsc,
+            time::Duration,
         };
 
-        match &drop_data.storage_action {
-            CeremonyStorageAction::ResetCurrentRound(reset_action) => {
-                self.reset_round_storage(reset_action)?;
+        // Spawn a mock webhook receiver forwarding the body of the first request it gets.
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let url = format!("http://{}/round", listener.local_addr()?);
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
             }
-            CeremonyStorageAction::ReplaceContributor(replace_action) => {
-                warn!(
-                    "Replacing contributor {} with {}",
-                    replace_action.dropped_contributor, replace_action.replacement_contributor
-                );
-
-                // Fetch the current round from storage.
-                let mut round = Self::load_current_round(&self.storage)?;
 
-                // Remove the contributor from the round self.
-                round.remove_contributor_unsafe(
-                    &mut self.storage,
-                    &replace_action.dropped_contributor,
-                    &replace_action.locked_chunks,
-                    &replace_action.tasks,
-                )?;
-
-                // Remove contribution info and trimmed info
-                self.storage.clear_info_files(round.round_height());
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                .unwrap();
+            sender.send(body).unwrap();
+        });
+
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .round_webhook_url(&url)
+        .into();
+        initialize_test_environment(&environment);
 
-                // Assign a replacement contributor from the queue to the dropped tasks for the current round.
-                round.add_replacement_contributor_unsafe(replace_action.replacement_contributor.clone())?;
-                warn!(
-                    "Added a replacement contributor {}",
-                    replace_action.replacement_contributor
-                );
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        coordinator.run_initialization(*TEST_STARTED_AT)?;
+        coordinator.next_round(*TEST_STARTED_AT, vec![Lazy::force(&TEST_CONTRIBUTOR_ID).clone()])?;
 
-                // Save the updated round to storage.
-                self.storage.update(
-                    &Locator::RoundState {
-                        round_height: round.round_height(),
-                    },
-                    Object::RoundState(round),
-                )?;
-            }
-        }
+        let body = receiver.recv_timeout(Duration::from_secs(10))?;
+        let event: RoundTransitionEvent = serde_json::from_slice(&body)?;
+        assert_eq!(0, event.old_height);
+        assert_eq!(1, event.new_height);
 
         Ok(())
     }
 
-    #[inline]
-    fn load_current_round_height(storage: &Disk) -> Result<u64, CoordinatorError> {
-        if storage.exists(&Locator::RoundHeight) {
-            // Fetch the current round height from storage.
-            match storage.get(&Locator::RoundHeight)? {
-                // Case 1 - This is a typical round of the ceremony.
-                Object::RoundHeight(current_round_height) => Ok(current_round_height),
-                // Case 2 - Storage failed to fetch the round height.
-                _ => Err(CoordinatorError::StorageFailed),
-            }
-        } else {
-            Err(CoordinatorError::RoundHeightNotSet)
-        }
-    }
+    #[test]
+    #[serial]
+    fn coordinator_round_participants() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
 
-    #[inline]
-    fn load_current_round(storage: &Disk) -> Result<Round, CoordinatorError> {
-        // Fetch the current round height from storage.
-        let current_round_height = Self::load_current_round_height(storage)?;
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        // Fetch the current round from storage.
-        Self::load_round(storage, current_round_height)
-    }
+        let contributor_1 = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
 
-    #[inline]
-    fn load_round(storage: &Disk, round_height: u64) -> Result<Round, CoordinatorError> {
-        // Fetch the current round height from storage.
-        let current_round_height = Self::load_current_round_height(storage)?;
+        // Complete round 1 with the first contributor.
+        contribute_to_current_round(&mut coordinator, &contributor_1)?;
+        coordinator.aggregate_contributions()?;
+        coordinator.next_round(OffsetDateTime::now_utc(), vec![contributor_2.clone()])?;
+        assert_eq!(2, coordinator.current_round_height()?);
 
-        // Fetch the specified round from storage.
-        match round_height <= current_round_height {
-            // Load the corresponding round data from storage.
-            true => match storage.get(&Locator::RoundState { round_height })? {
-                // Case 1 - The ceremony is running and the round state was fetched.
-                Object::RoundState(round) => Ok(round),
-                // Case 2 - Storage failed to fetch the round height.
-                _ => Err(CoordinatorError::StorageFailed),
-            },
-            // Case 3 - There are no prior rounds of the ceremony.
-            false => Err(CoordinatorError::RoundDoesNotExist),
-        }
-    }
+        // Complete round 2 with the second contributor.
+        contribute_to_current_round(&mut coordinator, &contributor_2)?;
+        coordinator.aggregate_contributions()?;
 
-    ///
-    /// Returns a reference to the instantiation of `Storage` that this
-    /// coordinator is using.
-    ///
-    #[inline]
-    pub(super) fn storage(&self) -> &Disk {
-        &self.storage
-    }
+        assert!(coordinator.get_round_participants(0)?.is_empty());
+        assert_eq!(vec![contributor_1], coordinator.get_round_participants(1)?);
+        assert_eq!(vec![contributor_2], coordinator.get_round_participants(2)?);
+        assert!(matches!(
+            coordinator.get_round_participants(3),
+            Err(CoordinatorError::RoundDoesNotExist)
+        ));
 
-    ///
-    /// Returns a mutable reference to the instantiation of `Storage`
-    /// that this coordinator is using.
-    ///
-    #[cfg(test)]
-    #[inline]
-    pub(super) fn storage_mut(&mut self) -> &mut Disk {
-        &mut self.storage
+        Ok(())
     }
 
-    ///
-    /// Returns a reference to the instantiation of `Signature` that this
-    /// coordinator is using.
-    ///
-    #[cfg(test)]
-    #[inline]
-    pub(super) fn signature(&self) -> Arc<dyn Signature> {
-        self.signature.clone()
-    }
+    #[test]
+    #[serial]
+    fn coordinator_round_readiness() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
 
-    ///
-    /// Resets the current round, with a rollback to the end of the
-    /// previous round to invite new participants into the round.
-    ///
-    pub fn reset_round(&mut self) -> Result<(), CoordinatorError> {
-        let reset_action = self.state.reset_current_round(true, &*self.time)?;
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        self.storage
-            .update(&Locator::CoordinatorState, Object::CoordinatorState(self.state.clone()))?;
-        self.reset_round_storage(&reset_action)?;
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let all_chunks: Vec<u64> = (0..coordinator.environment().number_of_chunks()).collect();
+
+        // No contribution yet, all the chunks block the transition.
+        let readiness = coordinator.can_advance_round()?;
+        assert_eq!(1, readiness.round_height);
+        assert_eq!(all_chunks, readiness.incomplete_chunks);
+        assert_eq!(all_chunks, readiness.missing_locators);
+        assert_eq!(1, readiness.current_contributors);
+        assert!(!readiness.is_ready());
+
+        // Contribute to chunk 0 without verifying the contribution.
+        let contribution_id = coordinator
+            .try_lock_chunk(0, &contributor)?
+            .next_contribution()
+            .contribution_id();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.run_computation(1, 0, contribution_id, &contributor, &"secret_key".to_string(), &seed)?;
+        coordinator.add_contribution(0, &contributor)?;
+
+        let readiness = coordinator.can_advance_round()?;
+        assert_eq!(all_chunks, readiness.incomplete_chunks);
+        assert_eq!(all_chunks, readiness.missing_locators);
+        assert_eq!(1, readiness.pending_verifications);
+        assert!(!readiness.is_ready());
 
         Ok(())
     }
 
-    /// Reset the current round in storage.
-    ///
-    /// + `remove_participants` is a list of participants that will
-    ///   have their contributions removed from the round.
-    /// + If `rollback` is set to `true`, the current round will be
-    ///   decremented by 1. If `rollback` is set to `true` and the
-    ///   current round height is `0` then this will return an error
-    ///   [CoordinatorError::RoundHeightIsZero]
-    fn reset_round_storage(&mut self, reset_action: &ResetCurrentRoundStorageAction) -> Result<(), CoordinatorError> {
-        // Fetch the current height of the ceremony.
-        let current_round_height = Self::load_current_round_height(&mut self.storage)?;
-        let span = tracing::error_span!("reset_round", round = current_round_height);
-        let _guard = span.enter();
-        warn!("Resetting round {}", current_round_height);
+    #[test]
+    #[serial]
+    fn coordinator_reconcile_round_height() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
 
-        let mut round = Self::load_round(&mut self.storage, current_round_height)?;
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        tracing::debug!("Resetting round and applying storage changes");
-        self.storage.process(round.reset(&reset_action.remove_participants))?;
+        // Consistent storage is left untouched.
+        assert_eq!(1, coordinator.reconcile_round_height()?);
 
-        // Clear all files
-        self.storage
-            .process(StorageAction::ClearRoundFiles(current_round_height))?;
+        // The round height points at a round which was never written, and so does the coordinator state.
+        coordinator
+            .storage
+            .update(&Locator::RoundHeight, Object::RoundHeight(3))?;
+        coordinator.state.reset_current_round_height(3);
+        assert!(matches!(
+            coordinator.current_round_height(),
+            Err(CoordinatorError::StorageFailed)
+        ));
 
-        if reset_action.rollback {
-            if current_round_height == 0 {
-                return Err(CoordinatorError::RoundHeightIsZero);
-            }
+        assert_eq!(1, coordinator.reconcile_round_height()?);
+        assert_eq!(1, coordinator.current_round_height()?);
+        assert_eq!(1, coordinator.current_round()?.round_height());
+        assert_eq!(1, coordinator.state.current_round_height());
+        match coordinator.storage.get(&Locator::CoordinatorState)? {
+            Object::CoordinatorState(state) => assert_eq!(1, state.current_round_height()),
+            _ => panic!("Unexpected object for the coordinator state"),
+        }
 
-            let new_round_height = current_round_height - 1;
-            tracing::debug!("Rolling back to round {} in storage.", new_round_height);
+        // The round height pointer is missing.
+        coordinator.storage.remove(&Locator::RoundHeight)?;
+        assert_eq!(1, coordinator.reconcile_round_height()?);
+        assert_eq!(1, coordinator.current_round_height()?);
 
-            self.storage.remove(&Locator::RoundState {
-                round_height: current_round_height,
-            })?;
-            self.storage
-                .update(&Locator::RoundHeight, Object::RoundHeight(new_round_height))?;
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_contributions_summary_range() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let contributor_1 = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+
+        // Advance the ceremony to round 3.
+        contribute_to_current_round(&mut coordinator, &contributor_1)?;
+        coordinator.aggregate_contributions()?;
+        coordinator.next_round(OffsetDateTime::now_utc(), vec![contributor_2.clone()])?;
+        contribute_to_current_round(&mut coordinator, &contributor_2)?;
+        coordinator.aggregate_contributions()?;
+        coordinator.next_round(OffsetDateTime::now_utc(), vec![contributor_1.clone()])?;
+        assert_eq!(3, coordinator.current_round_height()?);
+
+        for ceremony_round in [1, 1, 2, 3] {
+            let info = ContributionInfo {
+                ceremony_round,
+                ..Default::default()
+            };
+            coordinator.update_contribution_summary(info)?;
         }
+        let rounds = |summary: Vec<TrimmedContributionInfo>| -> Vec<u64> {
+            summary
+                .iter()
+                .map(|contribution| contribution.ceremony_round())
+                .collect()
+        };
 
-        warn!("Finished resetting round {} storage", current_round_height);
+        assert_eq!(
+            vec![1, 1, 2, 3],
+            rounds(coordinator.get_contributions_summary(None, None, None, None)?)
+        );
+        assert_eq!(
+            vec![2, 3],
+            rounds(coordinator.get_contributions_summary(Some(2), Some(3), None, None)?)
+        );
+        assert_eq!(
+            vec![1],
+            rounds(coordinator.get_contributions_summary(Some(1), Some(1), None, Some(1))?)
+        );
+        assert_eq!(
+            vec![1, 1, 2],
+            rounds(coordinator.get_contributions_summary(None, None, None, Some(3))?)
+        );
+        // The next pages
+        assert_eq!(
+            vec![2, 3],
+            rounds(coordinator.get_contributions_summary(None, None, Some(2), Some(2))?)
+        );
+        assert_eq!(
+            vec![3],
+            rounds(coordinator.get_contributions_summary(Some(2), Some(3), Some(1), Some(2))?)
+        );
+        assert!(coordinator
+            .get_contributions_summary(None, None, Some(4), Some(2))?
+            .is_empty());
+        assert!(matches!(
+            coordinator.get_contributions_summary(Some(3), Some(2), None, None),
+            Err(CoordinatorError::RoundRangeInvalid)
+        ));
+        assert!(matches!(
+            coordinator.get_contributions_summary(Some(1), Some(4), None, None),
+            Err(CoordinatorError::RoundDoesNotExist)
+        ));
 
         Ok(())
     }
-}
 
-#[cfg(any(test, feature = "operator"))]
-use crate::commands::{Computation, Seed, SigningKey, Verification};
+    #[test]
+    #[serial]
+    fn coordinator_duplicate_contribution() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
 
-#[cfg(any(test, feature = "operator"))]
-impl Coordinator {
-    #[tracing::instrument(
-        skip(self, contributor, contributor_signing_key, contributor_seed),
-        fields(contributor = %contributor),
-    )]
-    pub fn contribute(
-        &mut self,
-        contributor: &Participant,
-        contributor_signing_key: &SigningKey,
-        contributor_seed: &Seed,
-    ) -> Result<(), CoordinatorError> {
-        let (_chunk_id, locked_locators) = self.try_lock(contributor)?;
-        let response_locator = locked_locators.next_contribution();
-        let round_height = response_locator.round_height();
-        let chunk_id = response_locator.chunk_id();
-        let contribution_id = response_locator.contribution_id();
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        // check that the response matches a pending task
-        {
-            let response_task = Task::new(chunk_id, contribution_id);
-            let info = self.state.current_participant_info(contributor).unwrap().clone();
+        let contributor_1 = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
 
-            if info.pending_tasks().is_empty() {
-                return Err(CoordinatorError::ContributorPendingTasksCannotBeEmpty(
-                    contributor.clone(),
-                ));
-            }
+        // Contribute to round 1 and keep the contribution to chunk 0.
+        contribute_to_current_round(&mut coordinator, &contributor_1)?;
+        let round_1_locator = Locator::ContributionFile(ContributionLocator::new(1, 0, 1, false));
+        let round_1_contribution = coordinator.storage.reader(&round_1_locator)?.to_vec();
+        coordinator.aggregate_contributions()?;
+        coordinator.next_round(OffsetDateTime::now_utc(), vec![contributor_2.clone()])?;
 
-            if !info
-                .pending_tasks()
-                .iter()
-                .find(|pending_task| pending_task == &&response_task)
-                .is_some()
-            {
-                return Err(CoordinatorError::PendingTasksMustContainResponseTask { response_task });
-            }
-        }
+        // Submit the same contribution bytes to round 2, on top of the challenge hash of round 2.
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        let contribution_id = coordinator
+            .try_lock_chunk(0, &contributor_2)?
+            .next_contribution()
+            .contribution_id();
+        coordinator.run_computation(2, 0, contribution_id, &contributor_2, &signing_key, &seed)?;
+
+        let round_2_locator = Locator::ContributionFile(ContributionLocator::new(2, 0, contribution_id, false));
+        let mut duplicate = coordinator.storage.reader(&round_2_locator)?[..64].to_vec();
+        duplicate.extend_from_slice(&round_1_contribution[64..]);
+        coordinator
+            .storage
+            .update(&round_2_locator, Object::ContributionFile(duplicate))?;
+
+        assert!(matches!(
+            coordinator.add_contribution(0, &contributor_2),
+            Err(CoordinatorError::DuplicateContribution { round_height: 1 })
+        ));
 
-        debug!("Computing contributions for round {} chunk {}", round_height, chunk_id);
-        self.run_computation(
-            round_height,
-            chunk_id,
-            contribution_id,
-            contributor,
-            contributor_signing_key,
-            contributor_seed,
-        )?;
-        let _response = self.try_contribute(contributor, chunk_id)?;
-        debug!(
-            "Successfully computed contribution for round {} chunk {}",
-            round_height, chunk_id
-        );
         Ok(())
     }
 
-    pub fn get_pending_verifications(&self) -> &HashMap<Task, Participant> {
-        self.state.get_pending_verifications()
-    }
+    #[test]
+    #[serial]
+    fn coordinator_contribution_state_mismatch() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
 
-    /// Verify a contribution using the coordinator's default verifier.
-    /// This is just an interface to [`verify`]
-    ///
-    /// # Error
-    /// This function assumes that the given task has been indeed assigned to the
-    /// default verifier.
-    pub fn default_verify(&mut self, task: &Task) -> anyhow::Result<()> {
-        let verifier = self
-            .environment
-            .coordinator_verifiers()
-            .first()
-            .ok_or_else(|| CoordinatorError::VerifierMissing)?
-            .clone();
-        let sigkey = self.environment.default_verifier_signing_key();
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        self.verify(&verifier, &sigkey, task)
-    }
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        let contribution_id = coordinator
+            .try_lock_chunk(0, &contributor)?
+            .next_contribution()
+            .contribution_id();
+        coordinator.run_computation(1, 0, contribution_id, &contributor, &signing_key, &seed)?;
+
+        // Upload another response than the signed one, on top of the same challenge
+        let challenge = coordinator
+            .storage
+            .reader(&Locator::ContributionFile(ContributionLocator::new(1, 0, 0, true)))?
+            .to_vec();
+        let response_locator = Locator::ContributionFile(ContributionLocator::new(1, 0, contribution_id, false));
+        let response = coordinator.storage.reader(&response_locator)?.to_vec();
+        let mut other_response = response.clone();
+        *other_response.last_mut().unwrap() ^= 1;
+        coordinator
+            .storage
+            .update(&response_locator, Object::ContributionFile(other_response.clone()))?;
+
+        let signature: ContributionFileSignature = serde_json::from_slice(&*coordinator.storage.reader(
+            &Locator::ContributionFileSignature(ContributionSignatureLocator::new(1, 0, contribution_id, false)),
+        )?)?;
+        signature.get_state().validate_against(&challenge, &response)?;
+        assert!(matches!(
+            signature.get_state().validate_against(&challenge, &other_response),
+            Err(CoordinatorError::ContributionStateMismatch)
+        ));
+        assert!(matches!(
+            coordinator.add_contribution(0, &contributor),
+            Err(CoordinatorError::ContributionStateMismatch)
+        ));
 
-    #[tracing::instrument(
-        skip(self, verifier, verifier_signing_key),
-        fields(verifier = %verifier),
-    )]
-    pub fn verify(
-        &mut self,
-        verifier: &Participant,
-        verifier_signing_key: &SigningKey,
-        task: &Task,
-    ) -> anyhow::Result<()> {
-        let round_height = self.current_round_height()?;
-        debug!(
-            "Running verification for round {} chunk {}",
-            round_height,
-            task.chunk_id()
-        );
-        let _next_challenge = self.run_verification(round_height, task, verifier, verifier_signing_key)?;
-        self.try_verify(verifier, task)?;
-        debug!(
-            "Successful verification for round {} chunk {}",
-            round_height,
-            task.chunk_id()
-        );
         Ok(())
     }
 
-    ///
-    /// Attempts to run computation for a given round height, given chunk ID, and contribution ID.
-    ///
-    /// This function is used for testing purposes and can also be used for completing contributions
-    /// of participants who may have dropped off and handed over control of their session.
-    ///
-    #[inline]
-    pub fn run_computation(
-        &mut self,
-        round_height: u64,
-        chunk_id: u64,
-        contribution_id: u64,
-        participant: &Participant,
-        participant_signing_key: &SigningKey,
-        participant_seed: &Seed,
-    ) -> Result<(), CoordinatorError> {
-        info!(
-            "Running computation for round {} chunk {} contribution {} as {}",
-            round_height, chunk_id, contribution_id, participant
+    #[test]
+    fn coordinator_estimate_start_time() {
+        let started_at = OffsetDateTime::from_unix_timestamp(1_000_000).unwrap();
+        let average = time::Duration::minutes(4);
+
+        // Third in the queue, started 4 minutes ago
+        let now = started_at + time::Duration::minutes(4);
+        assert_eq!(
+            started_at + time::Duration::minutes(12),
+            super::estimate_start_time(started_at, average, 3, now)
         );
 
-        // Check that the chunk ID is valid.
-        if chunk_id > self.environment.number_of_chunks() {
-            return Err(CoordinatorError::ChunkIdInvalid);
-        }
+        // The current contribution is late: the next contributor starts at any time
+        let now = started_at + time::Duration::minutes(6);
+        assert_eq!(now, super::estimate_start_time(started_at, average, 1, now));
+    }
 
-        // Check that the contribution ID is valid.
-        if contribution_id == 0 {
-            return Err(CoordinatorError::ContributionIdMustBeNonzero);
-        }
+    #[test]
+    #[serial]
+    fn coordinator_estimated_start_time() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 3,
+            power: 8,
+            batch_size: 128,
+        })
+        .into();
+        initialize_test_environment(&environment);
 
-        // Check that the participant is a contributor.
-        if !participant.is_contributor() {
-            return Err(CoordinatorError::ExpectedContributor);
-        }
+        let time = Arc::new(MockTimeSource::new(*TEST_STARTED_AT));
+        let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        // Fetch the specified round from storage.
-        let round = Self::load_round(&mut self.storage, round_height)?;
+        // A contributor joins the queue after the start of the round
+        time.update(|now| now + time::Duration::minutes(1));
+        let queued = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        coordinator.state.add_to_queue(
+            queued.clone(),
+            None,
+            String::from("irrelevant_token"),
+            10,
+            coordinator.time.as_ref(),
+        )?;
+        let joined_at = time.time();
 
-        // Check that the chunk lock is currently held by this contributor.
-        if !round.is_chunk_locked_by(chunk_id, &participant) {
-            error!("{} should have lock on chunk {} but does not", &participant, chunk_id);
-            return Err(CoordinatorError::ChunkNotLockedOrByWrongParticipant);
-        }
+        // No contribution has been received yet
+        assert_eq!(None, coordinator.average_contribution_duration());
+        assert_eq!(None, coordinator.estimated_start_time(&queued, 2)?);
 
-        // Check that the given contribution ID does not exist yet.
-        if round.chunk(chunk_id)?.get_contribution(contribution_id).is_ok() {
-            return Err(CoordinatorError::ContributionShouldNotExist);
+        // Contribute to each chunk in a known time
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        for (chunk_id, duration) in [60, 120, 180].iter().enumerate() {
+            let chunk_id = chunk_id as u64;
+            let contribution_id = coordinator
+                .try_lock_chunk(chunk_id, &contributor)?
+                .next_contribution()
+                .contribution_id();
+            time.update(|now| now + time::Duration::seconds(*duration));
+            coordinator.run_computation(1, chunk_id, contribution_id, &contributor, &signing_key, &seed)?;
+            coordinator.add_contribution(chunk_id, &contributor)?;
         }
-
-        // Fetch the challenge locator.
-        let challenge_locator = &Locator::ContributionFile(ContributionLocator::new(
-            round_height,
-            chunk_id,
-            contribution_id - 1,
-            true,
-        ));
-        // Fetch the response locator.
-        let response_locator =
-            &Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, contribution_id, false));
-        // Fetch the contribution file signature locator.
-        let contribution_file_signature_locator = &Locator::ContributionFileSignature(
-            ContributionSignatureLocator::new(round_height, chunk_id, contribution_id, false),
+        assert_eq!(
+            Some(time::Duration::minutes(2)),
+            coordinator.average_contribution_duration()
         );
 
-        info!(
-            "Starting computation on round {} chunk {} contribution {} as {}",
-            round_height, chunk_id, contribution_id, participant
-        );
-        Computation::run(
-            &self.environment,
-            &mut self.storage,
-            self.signature.clone(),
-            participant_signing_key,
-            challenge_locator,
-            response_locator,
-            contribution_file_signature_locator,
-            participant_seed,
-        )?;
-        info!(
-            "Completed computation on round {} chunk {} contribution {} as {}",
-            round_height, chunk_id, contribution_id, participant
+        // The queue drains from the time the contributor joined it, one contribution at a time
+        time.set_time(joined_at);
+        assert_eq!(
+            Some(joined_at + time::Duration::minutes(4)),
+            coordinator.estimated_start_time(&queued, 2)?
         );
 
+        // The estimate is never in the past
+        time.update(|now| now + time::Duration::hours(1));
+        assert_eq!(Some(time.time()), coordinator.estimated_start_time(&queued, 2)?);
+
+        // A contributor who never joined the queue gets no estimate
+        let unknown = Lazy::force(&TEST_CONTRIBUTOR_ID_3).clone();
+        assert_eq!(None, coordinator.estimated_start_time(&unknown, 1)?);
+
         Ok(())
     }
 
-    ///
-    /// Attempts to run verification for a given round height, given chunk ID, and contribution ID.
-    ///
-    /// On success, this function returns the verified contribution locator.
-    ///
-    /// This function copies the unverified contribution into the verified contribution locator,
-    /// which is the next contribution ID within a round, or the next round height if this round
-    /// is complete.
-    ///
-    #[inline]
-    pub fn run_verification(
-        &mut self,
-        round_height: u64,
-        task: &Task,
-        participant: &Participant,
-        participant_signing_key: &SigningKey,
-    ) -> Result<LocatorPath, CoordinatorError> {
-        let chunk_id = task.chunk_id();
-        let contribution_id = task.contribution_id();
-        info!(
-            "Running verification for round {} chunk {} contribution {} as {}",
-            round_height, chunk_id, contribution_id, participant
+    #[test]
+    #[serial]
+    fn coordinator_contribution_upload_in_parts() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let locator = coordinator.try_lock_chunk(0, &contributor)?.next_contribution();
+
+        let mut contribution =
+            vec![0; Object::anoma_contribution_file_size(locator.round_height(), locator.contribution_id()) as usize];
+        rand::thread_rng().fill_bytes(&mut contribution);
+        let part_size = contribution.len() / 3 + 1;
+        let parts: Vec<&[u8]> = contribution.chunks(part_size).collect();
+
+        // A truncated contribution is refused before replacing the file.
+        assert!(matches!(
+            coordinator.write_contribution(locator, &contribution[..contribution.len() / 2]),
+            Err(CoordinatorError::ContributionFileSizeMismatch)
+        ));
+        let (second_offset, third_offset) = (part_size as u64, 2 * part_size as u64);
+
+        assert_eq!(
+            second_offset,
+            coordinator.write_contribution_part(&locator, 0, parts[0])?
         );
 
-        // Check that the chunk ID is valid.
-        if chunk_id > self.environment.number_of_chunks() {
-            return Err(CoordinatorError::ChunkIdInvalid);
-        }
+        // A part past the committed offset would leave a gap in the contribution.
+        assert!(matches!(
+            coordinator.write_contribution_part(&locator, third_offset, parts[2]),
+            Err(CoordinatorError::ContributionUploadGap { expected, found })
+                if expected == second_offset && found == third_offset
+        ));
 
-        // Check that the contribution ID is valid.
-        if contribution_id == 0 {
-            return Err(CoordinatorError::ContributionIdMustBeNonzero);
-        }
+        // A part past the end of the contribution is refused before being written.
+        assert!(matches!(
+            coordinator.write_contribution_part(&locator, second_offset, &contribution),
+            Err(CoordinatorError::ContributionFileSizeMismatch)
+        ));
 
-        // Check that the participant is a verifier.
-        if !participant.is_verifier() {
-            return Err(CoordinatorError::ExpectedContributor);
-        }
+        // An incomplete upload cannot be finalized.
+        let size = contribution.len() as u64;
+        let hash = hex::encode(calculate_hash(&contribution));
+        assert!(matches!(
+            coordinator.finalize_contribution_upload(&locator, size, &hash),
+            Err(CoordinatorError::ContributionFileSizeMismatch)
+        ));
 
-        // Fetch the specified round from storage.
-        let round = Self::load_round(&self.storage, round_height)?;
+        // Retry the second part, as if the response to the first attempt got lost, and resume from the committed offset.
+        assert_eq!(
+            third_offset,
+            coordinator.write_contribution_part(&locator, second_offset, parts[1])?
+        );
+        assert_eq!(
+            third_offset,
+            coordinator.write_contribution_part(&locator, second_offset, parts[1])?
+        );
+        assert_eq!(
+            contribution.len() as u64,
+            coordinator.write_contribution_part(&locator, third_offset, parts[2])?
+        );
 
-        // Check that the contribution locator corresponding to the response file exists.
-        let response_locator =
-            Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, contribution_id, false));
-        if !self.storage.exists(&response_locator) {
-            error!(
-                "Response file at {} is missing",
-                self.storage.to_path(&response_locator)?
-            );
-            return Err(CoordinatorError::ContributionLocatorMissing);
-        }
+        assert!(!coordinator.is_contribution_uploaded(&locator));
+        coordinator.finalize_contribution_upload(&locator, size, &hash)?;
+        assert!(coordinator.is_contribution_uploaded(&locator));
 
-        // Fetch the chunk corresponding to the given chunk ID.
-        let chunk = round.chunk(chunk_id)?;
+        coordinator.write_uploaded_contribution(locator)?;
+        assert!(!coordinator.is_contribution_uploaded(&locator));
 
-        // Chat that the specified contribution ID has NOT been verified yet.
-        if chunk.get_contribution(contribution_id)?.is_verified() {
-            return Err(CoordinatorError::ContributionAlreadyVerified);
+        match coordinator.storage().get(&Locator::ContributionFile(locator))? {
+            Object::ContributionFile(assembled) => {
+                assert_eq!(calculate_hash(&contribution), calculate_hash(&assembled))
+            }
+            _ => panic!("Expected a contribution file"),
         }
 
-        // Fetch whether this is the final contribution of the specified chunk.
-        let is_final_contribution = chunk.only_contributions_complete(round.expected_number_of_contributions());
-        info!(
-            "EXPECTED NUMBER OF CONTRIBUTIONS: {}",
-            round.expected_number_of_contributions()
-        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_concurrent_contribute() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
 
-        // Fetch the verified response locator and the contribution file signature locator.
-        let verified_locator = match is_final_contribution {
-            true => Locator::ContributionFile(ContributionLocator::new(round_height + 1, chunk_id, 0, true)),
-            false => Locator::ContributionFile(ContributionLocator::new(round_height, chunk_id, contribution_id, true)),
-        };
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        info!(
-            "Starting verification on round {} chunk {} contribution {} as {}",
-            round_height, chunk_id, contribution_id, participant
-        );
-        Verification::run(
-            &self.environment,
-            &mut self.storage,
-            self.signature.clone(),
-            participant_signing_key,
-            round_height,
+        // Compute the contribution and its signature, as uploaded by the contributor
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let (chunk_id, locked_locators) = coordinator.try_lock(&contributor)?;
+        let contribution_locator = locked_locators.next_contribution();
+        let signature_locator = locked_locators.next_contribution_file_signature();
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.run_computation(
+            1,
             chunk_id,
-            contribution_id,
-            is_final_contribution,
+            contribution_locator.contribution_id(),
+            &contributor,
+            &signing_key,
+            &seed,
         )?;
-        info!(
-            "Completed verification on round {} chunk {} contribution {} as {}",
-            round_height, chunk_id, contribution_id, participant
-        );
+        let contribution = match coordinator
+            .storage()
+            .get(&Locator::ContributionFile(contribution_locator))?
+        {
+            Object::ContributionFile(contribution) => contribution,
+            _ => panic!("Expected a contribution file"),
+        };
+        let signature = match coordinator
+            .storage()
+            .get(&Locator::ContributionFileSignature(signature_locator))?
+        {
+            Object::ContributionFileSignature(signature) => signature,
+            _ => panic!("Expected a contribution file signature"),
+        };
 
-        // Check that the verified contribution locator exists.
-        if !self.storage.exists(&verified_locator) {
-            let verified_response = self.storage.to_path(&verified_locator)?;
-            error!("Verified response file at {} is missing", verified_response);
-            return Err(CoordinatorError::ContributionLocatorMissing);
+        // The original request and its retry race to contribute with the same lock
+        let coordinator = Arc::new(RwLock::new(coordinator));
+        let barrier = Arc::new(Barrier::new(2));
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let coordinator = coordinator.clone();
+                let barrier = barrier.clone();
+                let contributor = contributor.clone();
+                let contribution = contribution.clone();
+                let signature: ContributionFileSignature = signature.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    coordinator.write().unwrap().submit_contribution(
+                        &contributor,
+                        chunk_id,
+                        contribution_locator,
+                        Some(contribution),
+                        signature_locator,
+                        signature,
+                    )
+                })
+            })
+            .collect();
+        let results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+        // Exactly one contribution is added, the other one is rejected without overwriting it
+        assert_eq!(1, results.iter().filter(|result| result.is_ok()).count());
+        assert!(results
+            .iter()
+            .any(|result| matches!(result, Err(CoordinatorError::ChunkAlreadyComplete))));
+        let coordinator = coordinator.read().unwrap();
+        assert_eq!(1, coordinator.get_pending_verifications().len());
+        match coordinator
+            .storage()
+            .get(&Locator::ContributionFile(contribution_locator))?
+        {
+            Object::ContributionFile(stored) => assert_eq!(calculate_hash(&contribution), calculate_hash(&stored)),
+            _ => panic!("Expected a contribution file"),
         }
 
-        Ok(self.storage.to_path(&verified_locator)?)
+        Ok(())
     }
 
-    ///
-    /// Returns a reference to the instantiation of `CoordinatorState` that this
-    /// coordinator is using.
-    ///
-    #[inline]
-    pub fn state(&self) -> &CoordinatorState {
-        &self.state
-    }
+    #[test]
+    #[serial]
+    fn coordinator_try_lock_fifo() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .maximum_contributors_per_round(3)
+        .into();
+        initialize_test_environment(&environment);
+
+        let time = Arc::new(MockTimeSource::new(*TEST_STARTED_AT));
+        let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+        let round_height = coordinator.run_initialization(*TEST_STARTED_AT)?;
+        coordinator.state.initialize(round_height);
+
+        let contributor_1 = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        let contributor_3 = Lazy::force(&TEST_CONTRIBUTOR_ID_3).clone();
+        let queue = vec![
+            (contributor_1.clone(), IpAddr::V4("0.0.0.1".parse()?)),
+            (contributor_2.clone(), IpAddr::V4("0.0.0.2".parse()?)),
+            (contributor_3.clone(), IpAddr::V4("0.0.0.3".parse()?)),
+        ];
+        for (contributor, contributor_ip) in queue {
+            time.update(|now| now + time::Duration::seconds(1));
+            coordinator.state.add_to_queue(
+                contributor,
+                Some(contributor_ip),
+                String::from("irrelevant_token"),
+                10,
+                coordinator.time.as_ref(),
+            )?;
+        }
+        coordinator.state.update_queue()?;
+        coordinator.state.save(&mut coordinator.storage)?;
+        coordinator.try_advance(*TEST_STARTED_AT)?;
+        assert_eq!(3, coordinator.current_contributors().len());
 
-    ///
-    /// Returns a reference to the instantiation of `Environment` that this
-    /// coordinator is using.
-    ///
-    #[inline]
-    pub fn environment(&self) -> &Environment {
-        &self.environment
+        // Only the first contributor to join the queue can lock the chunk, the others have to wait
+        assert!(matches!(
+            coordinator.try_lock(&contributor_3),
+            Err(CoordinatorError::NotYourTurn)
+        ));
+        assert!(matches!(
+            coordinator.try_lock(&contributor_2),
+            Err(CoordinatorError::NotYourTurn)
+        ));
+        coordinator.try_lock(&contributor_1)?;
+
+        // Then the second one, the third still waits for it
+        assert!(matches!(
+            coordinator.try_lock(&contributor_3),
+            Err(CoordinatorError::NotYourTurn)
+        ));
+        assert!(!matches!(
+            coordinator.try_lock(&contributor_2),
+            Err(CoordinatorError::NotYourTurn)
+        ));
+
+        Ok(())
     }
 
-    ///
-    /// Rollback a task which was locked by a contributor. Should be used to unlock
-    /// chunks which become stuck during the ceremony.
-    ///
-    pub fn rollback_locked_task(&mut self, participant: &Participant, task: Task) -> Result<(), CoordinatorError> {
-        self.state.rollback_locked_task(participant, task, &*self.time)?;
-        self.save_state()?;
+    #[test]
+    #[serial]
+    fn coordinator_queue_fifo() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .maximum_contributors_per_round(1)
+        .into();
+        initialize_test_environment(&environment);
+
+        let time = Arc::new(MockTimeSource::new(*TEST_STARTED_AT));
+        let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+        let round_height = coordinator.run_initialization(*TEST_STARTED_AT)?;
+        coordinator.state.initialize(round_height);
+
+        // Join the queue in the opposite order of reliability
+        let contributor_1 = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        let contributor_3 = Lazy::force(&TEST_CONTRIBUTOR_ID_3).clone();
+        let queue = vec![
+            (contributor_1.clone(), IpAddr::V4("0.0.0.1".parse()?), 8),
+            (contributor_2.clone(), IpAddr::V4("0.0.0.2".parse()?), 9),
+            (contributor_3.clone(), IpAddr::V4("0.0.0.3".parse()?), 10),
+        ];
+        for (contributor, contributor_ip, reliability) in queue {
+            time.update(|now| now + time::Duration::seconds(1));
+            coordinator.state.add_to_queue(
+                contributor,
+                Some(contributor_ip),
+                String::from("irrelevant_token"),
+                reliability,
+                coordinator.time.as_ref(),
+            )?;
+        }
+        coordinator.state.update_queue()?;
+        let assigned_round = |coordinator: &Coordinator, contributor: &Participant| {
+            coordinator
+                .state
+                .queue_contributor_info(contributor)
+                .and_then(|(_, round, _, _)| *round)
+        };
+        assert_eq!(Some(1), assigned_round(&coordinator, &contributor_1));
+        assert_eq!(Some(2), assigned_round(&coordinator, &contributor_2));
+        assert_eq!(Some(3), assigned_round(&coordinator, &contributor_3));
 
-        let mut round = self.current_round()?;
-        round.remove_locks_unsafe(&mut self.storage, participant, &[task.chunk_id()])?;
+        // Only the first contributor to join the queue is moved into the round, the others keep waiting in order
+        coordinator.state.save(&mut coordinator.storage)?;
+        coordinator.try_advance(*TEST_STARTED_AT)?;
+        let current_contributors: Vec<_> = coordinator.current_contributors().into_iter().map(|(p, _)| p).collect();
+        assert_eq!(vec![contributor_1.clone()], current_contributors);
+        assert_eq!(Some(2), assigned_round(&coordinator, &contributor_2));
+        assert_eq!(Some(3), assigned_round(&coordinator, &contributor_3));
+
+        assert!(matches!(
+            coordinator.try_lock(&contributor_3),
+            Err(CoordinatorError::ParticipantUnauthorized)
+        ));
+        coordinator.try_lock(&contributor_1)?;
 
-        Ok(self.storage.process(StorageAction::Update(UpdateAction {
-            locator: Locator::RoundState {
-                round_height: self.current_round_height()?,
-            },
-            object: Object::RoundState(round),
-        }))?)
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{
-        authentication::Dummy,
-        commands::{Seed, SigningKey, SEED_LENGTH},
-        environment::*,
-        objects::{Participant, Task},
-        testing::prelude::*,
-        Coordinator,
-    };
+    #[test]
+    #[serial]
+    fn coordinator_try_lock_retry() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .maximum_contributors_per_round(2)
+        .into();
+        initialize_test_environment(&environment);
 
-    use once_cell::sync::Lazy;
-    use rand::RngCore;
-    use std::{
-        collections::HashMap,
-        net::{IpAddr, Ipv4Addr},
-        sync::Arc,
-    };
-    use time::OffsetDateTime;
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
 
-    fn initialize_to_round_1(
-        coordinator: &mut Coordinator,
-        contributors: &[(Participant, IpAddr)],
-    ) -> anyhow::Result<()> {
-        // Initialize the ceremony and add the contributors and verifiers to the queue.
-        {
-            // Run initialization.
-            info!("Initializing ceremony");
-            let round_height = coordinator.run_initialization(*TEST_STARTED_AT)?;
-            info!("Initialized ceremony");
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let (chunk_id, locked_locators) = coordinator.try_lock(&contributor)?;
 
-            // Check current round height is now 0.
-            assert_eq!(0, round_height);
+        // Retrying the lock, as if the response got lost, returns the lock already held
+        let (retry_chunk_id, retry_locators) = coordinator.try_lock(&contributor)?;
+        assert_eq!(chunk_id, retry_chunk_id);
+        assert_eq!(
+            locked_locators.previous_contribution(),
+            retry_locators.previous_contribution()
+        );
+        assert_eq!(
+            locked_locators.current_contribution(),
+            retry_locators.current_contribution()
+        );
+        assert_eq!(locked_locators.next_contribution(), retry_locators.next_contribution());
+        assert_eq!(
+            locked_locators.next_contribution_file_signature(),
+            retry_locators.next_contribution_file_signature()
+        );
+        assert!(coordinator.current_round()?.chunk(chunk_id)?.is_locked_by(&contributor));
 
-            // Initialize the coordinator state to round 0.
-            coordinator.state.initialize(round_height);
-            coordinator.state.save(&mut coordinator.storage)?;
+        // The lock is still refused to another contributor
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        assert!(coordinator.try_lock(&contributor_2).is_err());
+        assert!(coordinator.current_round()?.chunk(chunk_id)?.is_locked_by(&contributor));
 
-            // Add the contributor and verifier of the coordinator to execute round 1.
-            for (contributor, contributor_ip) in contributors {
-                coordinator.state.add_to_queue(
-                    contributor.clone(),
-                    Some(*contributor_ip),
-                    String::from("irrelevant_token"), // No check will be done on this token
-                    10,
-                    coordinator.time.as_ref(),
-                )?;
-            }
+        Ok(())
+    }
 
-            // Update the queue.
-            coordinator.state.update_queue()?;
-            coordinator.state.save(&mut coordinator.storage)?;
-        }
+    #[test]
+    #[serial]
+    fn coordinator_verification_backlog() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .minimum_contributors_per_round(2)
+        .maximum_contributors_per_round(2)
+        .max_pending_verifications(0)
+        .into();
+        initialize_test_environment(&environment);
 
-        info!("Advancing ceremony to round 1");
-        coordinator.try_advance(*TEST_STARTED_AT)?;
-        info!("Advanced ceremony to round 1");
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
 
-        // Check current round height is now 1.
-        assert_eq!(1, coordinator.current_round_height()?);
+        // Contribute without verifying
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        let (chunk_id, locked_locators) = coordinator.try_lock(&contributor)?;
+        let contribution_id = locked_locators.next_contribution().contribution_id();
+        coordinator.run_computation(1, chunk_id, contribution_id, &contributor, &signing_key, &seed)?;
+        coordinator.add_contribution(chunk_id, &contributor)?;
+        assert_eq!(1, coordinator.verification_backlog());
+        assert_eq!(1, coordinator.ceremony_status()?.verification_backlog);
+
+        // Past the high-water mark new contributors are deferred
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        assert!(matches!(
+            coordinator.try_lock(&contributor_2),
+            Err(CoordinatorError::VerificationBacklog)
+        ));
+        let contributor_3 = Lazy::force(&TEST_CONTRIBUTOR_ID_3).clone();
+        assert!(matches!(
+            coordinator.add_to_queue(
+                contributor_3,
+                Some(IpAddr::V4("0.0.0.3".parse()?)),
+                String::from("irrelevant_token"),
+                10
+            ),
+            Err(CoordinatorError::VerificationBacklog)
+        ));
 
-        // info!("Add contributions and verifications for round 1");
-        // for _ in 0..coordinator.environment.number_of_chunks() {
-        //     for contributor in contributors {
-        //         coordinator.contribute(contributor)?;
-        //     }
-        //     for verifier in verifiers {
-        //         coordinator.verify(verifier)?;
-        //     }
-        // }
-        // info!("Added contributions and verifications for round 1");
+        // Once the backlog drains the next contributor can lock the chunk
+        let verifier = Lazy::force(&TEST_VERIFIER_ID);
+        let task = Task::new(chunk_id, contribution_id);
+        coordinator.run_verification(1, &task, verifier, &signing_key)?;
+        coordinator.verify_contribution(&task, verifier)?;
+        assert_eq!(0, coordinator.verification_backlog());
+        coordinator.try_lock(&contributor_2)?;
 
         Ok(())
     }
 
-    fn initialize_coordinator(coordinator: &mut Coordinator) -> anyhow::Result<()> {
-        // Load the contributors and verifiers.
-        let contributors = vec![
-            Lazy::force(&TEST_CONTRIBUTOR_ID).clone(),
-            Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone(),
-        ];
+    #[test]
+    #[serial]
+    fn coordinator_pause_and_resume() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .minimum_contributors_per_round(2)
+        .maximum_contributors_per_round(2)
+        .into();
+        initialize_test_environment(&environment);
 
-        let contributor_ips = vec![
-            IpAddr::V4("0.0.0.1".parse().unwrap()),
-            IpAddr::V4("0.0.0.2".parse().unwrap()),
-        ];
+        let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
 
-        let contributors: Vec<(Participant, IpAddr)> = contributors.into_iter().zip(contributor_ips).collect();
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let (chunk_id, locked_locators) = coordinator.try_lock(&contributor)?;
+        coordinator.pause()?;
+        assert!(coordinator.ceremony_status()?.paused);
+
+        // While paused new contributors can neither lock a chunk nor join the queue
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        assert!(matches!(
+            coordinator.try_lock(&contributor_2),
+            Err(CoordinatorError::CeremonyPaused)
+        ));
+        let contributor_3 = Lazy::force(&TEST_CONTRIBUTOR_ID_3).clone();
+        let join = |coordinator: &mut Coordinator| {
+            coordinator.add_to_queue(
+                contributor_3.clone(),
+                Some(IpAddr::V4("0.0.0.3".parse().unwrap())),
+                String::from("irrelevant_token"),
+                10,
+            )
+        };
+        assert!(matches!(join(&mut coordinator), Err(CoordinatorError::CeremonyPaused)));
 
-        initialize_to_round_1(coordinator, &contributors)
+        // The locked chunk can still be contributed and verified
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        let contribution_id = locked_locators.next_contribution().contribution_id();
+        coordinator.run_computation(1, chunk_id, contribution_id, &contributor, &signing_key, &seed)?;
+        coordinator.add_contribution(chunk_id, &contributor)?;
+        let verifier = Lazy::force(&TEST_VERIFIER_ID);
+        let task = Task::new(chunk_id, contribution_id);
+        coordinator.run_verification(1, &task, verifier, &signing_key)?;
+        coordinator.verify_contribution(&task, verifier)?;
+        assert_eq!(0, coordinator.verification_backlog());
+
+        // A restarted coordinator stays paused
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        assert!(coordinator.is_paused());
+        assert!(matches!(join(&mut coordinator), Err(CoordinatorError::CeremonyPaused)));
+
+        // Once resumed the contributors are accepted again
+        coordinator.resume()?;
+        assert!(!coordinator.ceremony_status()?.paused);
+        coordinator.try_lock(&contributor_2)?;
+        join(&mut coordinator)?;
+
+        Ok(())
     }
 
-    fn initialize_coordinator_single_contributor(coordinator: &mut Coordinator) -> anyhow::Result<()> {
-        // Load the contributors and verifiers.
-        let contributors = vec![(
-            Lazy::force(&TEST_CONTRIBUTOR_ID).clone(),
-            IpAddr::V4(Ipv4Addr::UNSPECIFIED),
-        )];
+    #[test]
+    #[serial]
+    fn coordinator_archive_completed_rounds() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::Test3Chunks)
+            .storage_archive_directory("./transcript/testing/archive")
+            .into();
+        initialize_test_environment(&environment);
 
-        initialize_to_round_1(coordinator, &contributors)
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        contribute_to_current_round(&mut coordinator, &contributor)?;
+        coordinator.aggregate_contributions()?;
+        let round_1 = coordinator.get_round(1)?;
+
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        coordinator.next_round(OffsetDateTime::now_utc(), vec![contributor_2])?;
+
+        // The completed round moved to the archive, the current one stays local
+        assert!(!Path::new("./transcript/testing/round_1").exists());
+        assert!(Path::new("./transcript/testing/archive/round_1/state.json").exists());
+        assert!(Path::new("./transcript/testing/round_2/state.json").exists());
+        assert!(coordinator
+            .storage
+            .to_path(&Locator::RoundState { round_height: 1 })?
+            .to_string()
+            .starts_with("./transcript/testing/archive"));
+
+        // The archived round is read through transparently
+        assert_eq!(round_1, coordinator.get_round(1)?);
+        let locator = Locator::ContributionFile(ContributionLocator::new(1, 0, 1, true));
+        assert!(coordinator.storage.exists(&locator));
+        assert_eq!(2, coordinator.current_round_height()?);
+
+        Ok(())
     }
 
     #[test]
     #[serial]
-    fn coordinator_initialization_matches_json() -> anyhow::Result<()> {
-        initialize_test_environment(&TEST_ENVIRONMENT);
+    fn coordinator_round_aggregate_hash() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
 
-        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT.clone(), Arc::new(Dummy))?;
-        initialize_coordinator(&mut coordinator)?;
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        // Check that round 0 matches the round 0 JSON specification.
-        {
-            let now = OffsetDateTime::now_utc();
+        // Not published before the aggregation
+        assert!(matches!(
+            coordinator.get_round_aggregate_hash(1),
+            Err(CoordinatorError::RoundNotAggregated)
+        ));
 
-            // Fetch round 0 from coordinator.
-            let mut expected = test_round_0_json()?;
-            let mut candidate = coordinator.get_round(0)?;
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        contribute_to_current_round(&mut coordinator, &contributor)?;
+        coordinator.aggregate_contributions()?;
+
+        // Recompute the hash of the transcript, the verified final contributions of the chunks in order
+        let mut transcript = Vec::new();
+        for chunk_id in 0..coordinator.environment().number_of_chunks() {
+            let locator = Locator::ContributionFile(ContributionLocator::new(2, chunk_id, 0, true));
+            transcript.extend_from_slice(coordinator.storage.reader(&locator)?.as_ref());
+        }
+        let expected = hex::encode(calculate_hash(&transcript));
+        assert_eq!(expected, coordinator.get_round_aggregate_hash(1)?);
+        assert_eq!(Some(expected.as_str()), coordinator.get_round(1)?.aggregate_hash());
+
+        // Still published once the ceremony advanced to the next round
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        coordinator.next_round(OffsetDateTime::now_utc(), vec![contributor_2])?;
+        assert_eq!(expected, coordinator.get_round_aggregate_hash(1)?);
+        assert!(matches!(
+            coordinator.get_round_aggregate_hash(3),
+            Err(CoordinatorError::RoundDoesNotExist)
+        ));
 
-            // Set the finish time to match.
-            println!("{} {}", expected.is_complete(), candidate.is_complete());
-            expected.try_finish_testing_only_unsafe(now.clone());
-            candidate.try_finish_testing_only_unsafe(now);
+        Ok(())
+    }
 
-            print_diff(&expected, &candidate);
-            assert_eq!(expected, candidate);
+    #[test]
+    #[serial]
+    fn coordinator_round_transcript() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        contribute_to_current_round(&mut coordinator, &contributor)?;
+        assert!(matches!(
+            coordinator.get_round_transcript(1),
+            Err(CoordinatorError::RoundNotAggregated)
+        ));
+        coordinator.aggregate_contributions()?;
+
+        // The chain of the verified contributions of each chunk
+        let mut chunks = Vec::new();
+        for chunk_id in 0..coordinator.environment().number_of_chunks() {
+            let locators = [
+                ContributionLocator::new(1, chunk_id, 0, true),
+                ContributionLocator::new(2, chunk_id, 0, true),
+            ];
+            let mut contributions = Vec::new();
+            for locator in locators {
+                let contribution = coordinator.storage.reader(&Locator::ContributionFile(locator))?;
+                contributions.push(hex::encode(contribution.as_ref()));
+            }
+            chunks.push(ChunkTranscript {
+                chunk_id,
+                contributions,
+            });
         }
+        let expected = RoundTranscript {
+            round_height: 1,
+            skipped: false,
+            parameters: CeremonyParameters::Masp,
+            chunks,
+        };
+        assert_eq!(expected, coordinator.get_round_transcript(1)?);
 
         Ok(())
     }
 
     #[test]
     #[serial]
-    fn coordinator_initialization() -> anyhow::Result<()> {
-        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+    fn coordinator_contributor_allowlist() -> anyhow::Result<()> {
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        let allowlist = tempfile::NamedTempFile::new()?;
+        std::fs::write(allowlist.path(), serde_json::to_vec(&[contributor.address()])?)?;
+
+        let environment: Environment = Testing::from(Parameters::Test3Chunks)
+            .contributor_allowlist_path(allowlist.path().to_str().unwrap())
+            .into();
+        initialize_test_environment(&environment);
+
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        coordinator.initialize()?;
+
+        // Only the allowlisted contributors can join the queue
+        coordinator.add_to_queue(
+            contributor.clone(),
+            Some(IpAddr::V4("0.0.0.1".parse()?)),
+            String::from("token"),
+            10,
+        )?;
+        assert!(coordinator.is_queue_contributor(&contributor));
+        assert!(matches!(
+            coordinator.add_to_queue(
+                contributor_2.clone(),
+                Some(IpAddr::V4("0.0.0.2".parse()?)),
+                String::from("token_2"),
+                10
+            ),
+            Err(CoordinatorError::UnauthorizedChunkContributor)
+        ));
+        assert!(!coordinator.is_queue_contributor(&contributor_2));
 
-        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        // The amended allowlist is applied once reloaded
+        std::fs::write(
+            allowlist.path(),
+            serde_json::to_vec(&[contributor.address(), contributor_2.address()])?,
+        )?;
+        assert_eq!(Some(2), coordinator.reload_contributor_allowlist()?);
+        coordinator.add_to_queue(
+            contributor_2.clone(),
+            Some(IpAddr::V4("0.0.0.2".parse()?)),
+            String::from("token_2"),
+            10,
+        )?;
+        assert!(coordinator.is_queue_contributor(&contributor_2));
 
-        {
-            // Run initialization.
-            info!("Initializing ceremony");
-            let round_height = coordinator.run_initialization(*TEST_STARTED_AT).unwrap();
-            info!("Initialized ceremony");
+        Ok(())
+    }
 
-            // Check current round height is now 0.
-            assert_eq!(0, round_height);
+    #[test]
+    #[serial]
+    fn coordinator_aggregation_integrity_check() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
 
-            // Load the contributors and verifiers.
-            let contributors = vec![
-                Lazy::force(&TEST_CONTRIBUTOR_ID).clone(),
-                Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone(),
-            ];
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-            // Start round 1.
-            coordinator.next_round(*TEST_STARTED_AT, contributors).unwrap();
-        }
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        contribute_to_current_round(&mut coordinator, &contributor)?;
+
+        // The hash of the verified contribution is recorded at verification
+        let locator = Locator::ContributionFile(ContributionLocator::new(2, 0, 0, true));
+        let mut verified = coordinator.storage.reader(&locator)?.as_ref().to_vec();
+        let round = coordinator.get_round(1)?;
+        let final_contribution = round
+            .chunk(0)?
+            .get_contribution(round.expected_number_of_contributions() - 1)?;
+        assert_eq!(
+            Some(hex::encode(calculate_hash(&verified)).as_str()),
+            final_contribution.get_verified_hash()
+        );
 
-        {
-            // Check round 0 is complete.
-            assert!(coordinator.get_round(0)?.is_complete());
+        // Flip a bit of the verified contribution after its verification
+        let last = verified.len() - 1;
+        verified[last] ^= 1;
+        std::fs::write(coordinator.storage.to_path(&locator)?, &verified)?;
 
-            // Check current round height is now 1.
-            assert_eq!(1, coordinator.current_round_height()?);
+        assert!(matches!(
+            coordinator.aggregate_contributions(),
+            Err(CoordinatorError::ContributionIntegrityMismatch { chunk_id: 0 })
+        ));
+        assert!(!coordinator.storage.exists(&Locator::RoundFile { round_height: 1 }));
 
-            // Check that the coordinator participants are established correctly.
-            assert!(coordinator.is_coordinator_contributor(&TEST_CONTRIBUTOR_ID));
-            assert!(coordinator.is_coordinator_verifier(&TEST_VERIFIER_ID));
+        Ok(())
+    }
 
-            // Check the current round has a matching height.
-            let current_round = coordinator.current_round()?;
-            assert_eq!(coordinator.current_round_height()?, current_round.round_height());
+    #[test]
+    #[serial]
+    fn coordinator_aggregation_retries() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::Test3Chunks)
+            .aggregation_retries(2, time::Duration::seconds(10))
+            .into();
+        initialize_test_environment(&environment);
+
+        let time = Arc::new(MockTimeSource::new(*TEST_STARTED_AT));
+        let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-            // Check round 1 contributors.
-            assert_eq!(2, current_round.number_of_contributors());
-            assert!(current_round.is_contributor(&TEST_CONTRIBUTOR_ID));
-            assert!(current_round.is_contributor(&TEST_CONTRIBUTOR_ID_2));
-            assert!(!current_round.is_contributor(&TEST_CONTRIBUTOR_ID_3));
-            assert!(!current_round.is_contributor(&TEST_VERIFIER_ID));
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        contribute_to_current_round(&mut coordinator, &contributor)?;
+
+        // A missing file is deterministic and isn't retried
+        let missing = CoordinatorError::IOError(std::io::ErrorKind::NotFound.into());
+        assert!(!super::is_transient_aggregation_error(&missing));
+
+        // A directory in place of the round file makes the storage fail to create it
+        let round_file = coordinator.storage.to_path(&Locator::RoundFile { round_height: 1 })?;
+        std::fs::create_dir_all(round_file.as_path())?;
+        assert!(matches!(
+            coordinator.aggregate_contributions(),
+            Err(CoordinatorError::IOError(_))
+        ));
 
-            // Check round 1 verifiers.
-            assert_eq!(0, current_round.number_of_verifiers());
+        // The retry waits for the backoff, doubled after each failed attempt
+        assert!(matches!(
+            coordinator.aggregate_contributions(),
+            Err(CoordinatorError::RoundAggregationRescheduled)
+        ));
+        time.update(|now| now + time::Duration::seconds(10));
+        assert!(matches!(
+            coordinator.aggregate_contributions(),
+            Err(CoordinatorError::IOError(_))
+        ));
+        time.update(|now| now + time::Duration::seconds(10));
+        assert!(matches!(
+            coordinator.aggregate_contributions(),
+            Err(CoordinatorError::RoundAggregationRescheduled)
+        ));
 
-            // Check round 1 is NOT complete.
-            assert!(!current_round.is_complete());
-        }
+        // Once the storage recovers, the retry succeeds and the round transitions
+        std::fs::remove_dir(round_file.as_path())?;
+        time.update(|now| now + time::Duration::seconds(10));
+        coordinator.aggregate_contributions()?;
+        assert!(coordinator.storage.exists(&Locator::RoundFile { round_height: 1 }));
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        coordinator.next_round(time.time(), vec![contributor_2])?;
+        assert_eq!(2, coordinator.current_round_height()?);
 
         Ok(())
     }
 
     #[test]
     #[serial]
-    fn coordinator_contributor_try_lock_chunk() -> anyhow::Result<()> {
-        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+    fn coordinator_reverify_round() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
 
-        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
-        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2);
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
-        initialize_coordinator(&mut coordinator)?;
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        contribute_to_current_round(&mut coordinator, &contributor)?;
+        let round = coordinator.get_round(1)?;
+
+        let report = coordinator.reverify_round(1, false)?;
+        assert!(report.is_valid());
+        let contributions_per_chunk = round.expected_number_of_contributions() as usize - 1;
+        assert_eq!(round.chunks().len() * contributions_per_chunk, report.checked);
+
+        // Corrupt the verified final contribution of chunk 0 after its verification
+        let locator = Locator::ContributionFile(ContributionLocator::new(2, 0, 0, true));
+        let mut verified = coordinator.storage.reader(&locator)?.as_ref().to_vec();
+        let last = verified.len() - 1;
+        verified[last] ^= 1;
+        std::fs::write(coordinator.storage.to_path(&locator)?, &verified)?;
+
+        // The check is read-only
+        for _ in 0..2 {
+            let report = coordinator.reverify_round(1, false)?;
+            assert_eq!(1, report.failures.len());
+            let failure = &report.failures[0];
+            assert_eq!(
+                (0, contributions_per_chunk as u64),
+                (failure.chunk_id, failure.contribution_id)
+            );
+            assert!(!failure.repaired);
+        }
+        assert_eq!(verified, coordinator.storage.reader(&locator)?.as_ref());
+        assert_eq!(round, coordinator.get_round(1)?);
 
-        {
-            // Acquire the lock for chunk 0 as contributor 1.
-            assert!(coordinator.try_lock_chunk(0, &contributor).is_ok());
+        // A rewrite which doesn't verify leaves the verified contribution in place
+        let response_locator =
+            Locator::ContributionFile(ContributionLocator::new(1, 0, contributions_per_chunk as u64, false));
+        let response = coordinator.storage.reader(&response_locator)?.to_vec();
+        let mut tampered = response.clone();
+        tampered[0] ^= 1;
+        coordinator
+            .storage
+            .update(&response_locator, Object::ContributionFile(tampered))?;
+        let report = coordinator.reverify_round(1, true)?;
+        assert!(!report.failures[0].repaired);
+        assert_eq!(verified, coordinator.storage.reader(&locator)?.as_ref());
+        coordinator
+            .storage
+            .update(&response_locator, Object::ContributionFile(response))?;
 
-            // Attempt to acquire the lock for chunk 0 as contributor 1 again.
-            assert!(coordinator.try_lock_chunk(0, &contributor).is_err());
+        // The verified contribution is rewritten on demand, without touching the round
+        let report = coordinator.reverify_round(1, true)?;
+        assert!(report.failures[0].repaired);
+        assert!(coordinator.reverify_round(1, false)?.is_valid());
+        assert_eq!(round, coordinator.get_round(1)?);
 
-            // Acquire the lock for chunk 1 as contributor 1.
-            assert!(coordinator.try_lock_chunk(1, &contributor).is_ok());
+        Ok(())
+    }
 
-            // Attempt to acquire the lock for chunk 0 as contributor 2.
-            assert!(coordinator.try_lock_chunk(0, &contributor_2).is_err());
+    #[test]
+    #[serial]
+    fn coordinator_get_contribution_file_signature() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
 
-            // Attempt to acquire the lock for chunk 1 as contributor 2.
-            assert!(coordinator.try_lock_chunk(1, &contributor_2).is_err());
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-            // Acquire the lock for chunk 1 as contributor 2.
-            assert!(coordinator.try_lock_chunk(2, &contributor_2).is_ok());
-        }
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let round_height = coordinator.current_round_height()?;
+        let locked_locators = coordinator.try_lock_chunk(0, &contributor)?;
+        let signature_locator = locked_locators.next_contribution_file_signature();
 
-        {
-            // Check that chunk 0 is locked.
-            let round = coordinator.current_round()?;
-            let chunk = round.chunk(0)?;
-            assert!(chunk.is_locked());
-            assert!(!chunk.is_unlocked());
+        // Nothing was uploaded yet
+        assert!(matches!(
+            coordinator.get_contribution_file_signature(signature_locator),
+            Err(CoordinatorError::ContributionSignatureMissing)
+        ));
 
-            // Check that chunk 0 is locked by contributor 1.
-            assert!(chunk.is_locked_by(contributor));
-            assert!(!chunk.is_locked_by(contributor_2));
+        let contribution_id = locked_locators.next_contribution().contribution_id();
+        coordinator.run_computation(
+            round_height,
+            0,
+            contribution_id,
+            &contributor,
+            &"secret_key".to_string(),
+            &[0; SEED_LENGTH],
+        )?;
+        coordinator.add_contribution(0, &contributor)?;
 
-            // Check that chunk 1 is locked.
-            let chunk = round.chunk(1)?;
-            assert!(chunk.is_locked());
-            assert!(!chunk.is_unlocked());
+        // The signature covers the uploaded contribution
+        let signature = coordinator.get_contribution_file_signature(signature_locator)?;
+        let response = coordinator
+            .storage
+            .reader(&Locator::ContributionFile(locked_locators.next_contribution()))?;
+        assert_eq!(
+            hex::encode(calculate_hash(response.as_ref())),
+            signature.get_response_hash()
+        );
 
-            // Check that chunk 1 is locked by contributor 1.
-            assert!(chunk.is_locked_by(contributor));
-            assert!(!chunk.is_locked_by(contributor_2));
+        Ok(())
+    }
 
-            // Check that chunk 2 is locked.
-            let chunk = round.chunk(2)?;
-            assert!(chunk.is_locked());
-            assert!(!chunk.is_unlocked());
+    #[test]
+    #[serial]
+    fn coordinator_redacted_contributions_info() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::Test3Chunks)
+            .redact_contributor_identity([7; 32])
+            .into();
+        initialize_test_environment(&environment);
 
-            // Check that chunk 2 is locked by contributor 2.
-            assert!(chunk.is_locked_by(contributor_2));
-            assert!(!chunk.is_locked_by(contributor));
-        }
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let info = ContributionInfo {
+            full_name: Some("Alice Doe".to_string()),
+            email: Some("Alice@Example.com".to_string()),
+            ceremony_round: 1,
+            ..Default::default()
+        };
+        coordinator.write_contribution_info(info.clone())?;
+        coordinator.update_contribution_summary(info)?;
+
+        // The public summary omits the name and hashes the email with the key of the coordinator
+        let public = coordinator.get_public_contributions_summary(None, None, None, None)?;
+        assert_eq!(1, public.len());
+        assert_eq!(None, public[0].full_name());
+        let email_hash = public[0].email_hash().unwrap();
+        assert_eq!(hash_email(&[7; 32], "Alice@Example.com"), email_hash);
+        assert_eq!(hash_email(&[7; 32], " alice@example.com "), email_hash);
+        assert_ne!(hash_email(&[8; 32], "Alice@Example.com"), email_hash);
+        assert!(!email_hash.contains("example"));
+
+        // The redaction is deterministic
+        let public_again = coordinator.get_public_contributions_summary(None, None, None, None)?;
+        assert_eq!(public_again[0].email_hash(), public[0].email_hash());
+
+        // The full info reveals the identity
+        let full = coordinator.get_contributions_full_info(None, None)?;
+        assert_eq!(1, full.len());
+        assert_eq!(Some("Alice Doe".to_string()), full[0].full_name);
+        assert_eq!(Some("Alice@Example.com".to_string()), full[0].email);
 
         Ok(())
     }
 
     #[test]
     #[serial]
-    fn coordinator_contributor_add_contribution() -> anyhow::Result<()> {
-        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
-
-        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
-        let contributor_signing_key: SigningKey = "secret_key".to_string();
+    fn coordinator_stale_contributor_eviction() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .contributor_seen_timeout(time::Duration::minutes(1))
+        .contributor_stale_grace(time::Duration::minutes(2))
+        .into();
+        initialize_test_environment(&environment);
 
-        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        let time = Arc::new(MockTimeSource::new(*TEST_STARTED_AT));
+        let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
         initialize_coordinator(&mut coordinator)?;
 
-        {
-            // Acquire the lock for chunk 0 as contributor 1.
-            coordinator.try_lock_chunk(0, &contributor)?;
-        }
-
-        // Run computation on round 1 chunk 0 contribution 1.
-        {
-            // Check current round is 1.
-            let round = coordinator.current_round()?;
-            let round_height = round.round_height();
-            assert_eq!(1, round_height);
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        coordinator.try_lock(&contributor)?;
+        assert!(coordinator.stale_contributor_eviction_time(&contributor).is_none());
 
-            // Check chunk 0 is not verified.
-            let chunk_id = 0;
-            let chunk = round.chunk(chunk_id)?;
-            assert!(!chunk.is_complete(round.expected_number_of_contributions()));
+        // Past the heartbeat timeout the contributor is stale: it keeps its lock but can't make progress
+        time.update(|now| now + time::Duration::seconds(90));
+        coordinator.update()?;
+        assert_eq!(
+            Some(*TEST_STARTED_AT + time::Duration::minutes(3)),
+            coordinator.stale_contributor_eviction_time(&contributor)
+        );
+        assert!(coordinator.is_current_contributor(&contributor));
+        assert!(!coordinator.is_dropped_participant(&contributor));
+        assert!(coordinator.current_round()?.chunk(0)?.is_locked_by(&contributor));
+        assert!(matches!(
+            coordinator.try_lock(&contributor),
+            Err(CoordinatorError::ParticipantStale)
+        ));
+        assert!(matches!(
+            coordinator.try_contribute(&contributor, 0),
+            Err(CoordinatorError::ParticipantStale)
+        ));
 
-            // Check next contribution is 1.
-            let contribution_id = 1;
-            assert!(chunk.is_next_contribution_id(contribution_id, round.expected_number_of_contributions()));
+        // A heartbeat within the grace window makes the contributor live again
+        coordinator.heartbeat(&contributor)?;
+        assert!(coordinator.stale_contributor_eviction_time(&contributor).is_none());
 
-            // Run the computation
-            let mut seed: Seed = [0; SEED_LENGTH];
-            rand::thread_rng().fill_bytes(&mut seed[..]);
-            assert!(coordinator
-                .run_computation(
-                    round_height,
-                    chunk_id,
-                    contribution_id,
-                    &contributor,
-                    &contributor_signing_key,
-                    &seed
-                )
-                .is_ok());
-        }
+        // Once the grace window expires the contributor is evicted and its lock released
+        time.update(|now| now + time::Duration::seconds(90));
+        coordinator.update()?;
+        assert!(coordinator.stale_contributor_eviction_time(&contributor).is_some());
+        assert!(!coordinator.is_dropped_participant(&contributor));
 
-        // Add contribution for round 1 chunk 0 contribution 1.
-        let chunk_id = 0;
-        {
-            // Add round 1 chunk 0 contribution 1.
-            assert!(coordinator.add_contribution(chunk_id, &contributor).is_ok());
-        }
-        {
-            // Check chunk 0 lock is released.
-            let round = coordinator.current_round()?;
-            let chunk = round.chunk(chunk_id)?;
-            assert!(chunk.is_unlocked());
-            assert!(!chunk.is_locked());
-        }
+        time.update(|now| now + time::Duration::minutes(2));
+        coordinator.update()?;
+        assert!(coordinator.is_dropped_participant(&contributor));
+        assert!(!coordinator.is_current_contributor(&contributor));
+        assert!(!coordinator.current_round()?.chunk(0)?.is_locked_by(&contributor));
 
         Ok(())
     }
 
     #[test]
     #[serial]
-    fn coordinator_verifier_verify_contribution() -> anyhow::Result<()> {
-        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
-
-        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID);
-        let contributor_signing_key: SigningKey = "secret_key".to_string();
-
-        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
-        initialize_coordinator(&mut coordinator)?;
-
-        // Check current round height is now 1.
-        let round_height = coordinator.current_round_height()?;
-        assert_eq!(1, round_height);
-
-        // Acquire the lock for chunk 0 as contributor 1.
-        let chunk_id = 0;
-        let contribution_id = 1;
-        {
-            assert!(coordinator.try_lock_chunk(chunk_id, &contributor).is_ok());
-        }
-        {
-            // Run computation on round 1 chunk 0 contribution 1.
-            let mut seed: Seed = [0; SEED_LENGTH];
-            rand::thread_rng().fill_bytes(&mut seed[..]);
-            assert!(coordinator
-                .run_computation(
-                    round_height,
-                    chunk_id,
-                    contribution_id,
-                    contributor,
-                    &contributor_signing_key,
-                    &seed
-                )
-                .is_ok());
-
-            // Add round 1 chunk 0 contribution 1.
-            assert!(coordinator.add_contribution(chunk_id, &contributor).is_ok());
-        }
-
-        // Verify round 1 chunk 0 contribution 1.
-        {
-            let verifier = Lazy::force(&TEST_VERIFIER_ID).clone();
-            let verifier_signing_key: SigningKey = "secret_key".to_string();
+    fn coordinator_contributions_per_round() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .minimum_contributors_per_round(3)
+        .maximum_contributors_per_round(3)
+        .into();
+        initialize_test_environment(&environment);
 
-            // Run verification.
-            let task = Task::new(chunk_id, contribution_id);
-            let verify = coordinator.run_verification(round_height, &task, &verifier, &verifier_signing_key);
-            assert!(verify.is_ok());
-            // Verify contribution 1.
-            coordinator.verify_contribution(&task, &verifier)?;
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        let contributors = vec![
+            Lazy::force(&TEST_CONTRIBUTOR_ID).clone(),
+            Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone(),
+            Lazy::force(&TEST_CONTRIBUTOR_ID_3).clone(),
+        ];
+        let contributor_ips: Vec<IpAddr> = vec![
+            IpAddr::V4("0.0.0.1".parse()?),
+            IpAddr::V4("0.0.0.2".parse()?),
+            IpAddr::V4("0.0.0.3".parse()?),
+        ];
+        let queue: Vec<(Participant, IpAddr)> = contributors.iter().cloned().zip(contributor_ips).collect();
+        initialize_to_round_1(&mut coordinator, &queue)?;
+        assert_eq!(4, coordinator.current_round()?.expected_number_of_contributions());
+
+        // The chunk is handed to each contributor in turn, the round cannot close before all of them contributed.
+        for contributor in &contributors {
+            assert!(coordinator.aggregate_contributions().is_err());
+            contribute_to_current_round(&mut coordinator, contributor)?;
         }
 
+        let round = coordinator.current_round()?;
+        let chunk = round.chunk(0)?;
+        assert_eq!(4, chunk.get_contributions().len());
+        assert!(chunk
+            .get_contributions()
+            .values()
+            .all(|contribution| contribution.is_verified()));
+
+        coordinator.aggregate_contributions()?;
+
+        // The next round needs enough contributors to collect three contributions.
+        assert!(matches!(
+            coordinator.next_round(OffsetDateTime::now_utc(), vec![contributors[0].clone()]),
+            Err(CoordinatorError::RoundNumberOfContributorsUnauthorized)
+        ));
+        assert_eq!(1, coordinator.current_round_height()?);
+
+        coordinator.next_round(OffsetDateTime::now_utc(), contributors)?;
+        assert_eq!(2, coordinator.current_round_height()?);
+
         Ok(())
     }
 
     #[test]
     #[serial]
-    // This test runs a round with a single coordinator and single verifier
-    // The verifier instances are run on a separate thread to simulate an environment where
-    // verification and contribution happen concurrently.
-    fn coordinator_concurrent_contribution_verification() -> anyhow::Result<()> {
+    fn coordinator_concurrent_round_transitions() -> anyhow::Result<()> {
         initialize_test_environment(&TEST_ENVIRONMENT_3);
 
         let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
         initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        // Check current round height is now 1.
-        let round_height = coordinator.current_round_height()?;
-        assert_eq!(1, round_height);
-
-        let coordinator = std::sync::Arc::new(std::sync::RwLock::new(coordinator));
+        let contributor_1 = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        contribute_to_current_round(&mut coordinator, &contributor_1)?;
+        coordinator.aggregate_contributions()?;
 
-        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
-        let contributor_signing_key: SigningKey = "secret_key".to_string();
+        // No transition nor contribution while another transition holds the guard
+        {
+            let _transition = coordinator.round_transition_guard().try_begin()?;
+            assert!(matches!(
+                coordinator.next_round(OffsetDateTime::now_utc(), vec![contributor_2.clone()]),
+                Err(CoordinatorError::RoundTransitionInProgress)
+            ));
+            assert!(matches!(
+                coordinator.add_contribution(0, &contributor_1),
+                Err(CoordinatorError::RoundTransitionInProgress)
+            ));
+        }
+        assert!(!coordinator.round_transition_guard().is_in_progress());
+
+        // Two coordinators of the same ceremony race to transition to round 2
+        let mut other = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        other.set_round_transition_guard(coordinator.round_transition_guard());
+        let barrier = Arc::new(std::sync::Barrier::new(2));
+        let handles: Vec<_> = vec![coordinator, other]
+            .into_iter()
+            .map(|mut coordinator| {
+                let barrier = barrier.clone();
+                let contributor = contributor_2.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    coordinator
+                        .next_round(OffsetDateTime::now_utc(), vec![contributor])
+                        .map(|round_height| (round_height, coordinator))
+                })
+            })
+            .collect();
+        let mut results: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+        assert_eq!(1, results.iter().filter(|result| result.is_ok()).count());
+        let (round_height, coordinator) = results.drain(..).find_map(Result::ok).unwrap();
+        assert_eq!(2, round_height);
+        assert_eq!(2, coordinator.current_round_height()?);
+        assert!(!coordinator.round_transition_guard().is_in_progress());
 
-        let verifier = Lazy::force(&TEST_VERIFIER_ID);
+        Ok(())
+    }
 
-        let mut verifier_threads = vec![];
+    #[test]
+    #[serial]
+    fn coordinator_round_time_budget() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .queue_wait_time(3600)
+        .round_time_budget(time::Duration::minutes(1))
+        .into();
+        initialize_test_environment(&environment);
 
-        let contribution_id = 1;
+        let time = Arc::new(MockTimeSource::new(*TEST_STARTED_AT));
+        let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        let mut seed: Seed = [0; SEED_LENGTH];
-        rand::thread_rng().fill_bytes(&mut seed[..]);
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        contribute_to_current_round(&mut coordinator, &contributor)?;
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        coordinator.add_to_queue(
+            contributor_2.clone(),
+            Some(IpAddr::V4("0.0.0.2".parse()?)),
+            String::from("irrelevant_token"),
+            10,
+        )?;
 
-        for chunk_id in 0..TEST_ENVIRONMENT_3.number_of_chunks() {
-            {
-                // Acquire the lock as contributor.
-                let try_lock = coordinator.write().unwrap().try_lock_chunk(chunk_id, &contributor);
-                if try_lock.is_err() {
-                    println!(
-                        "Failed to acquire lock for chunk {} as contributor {:?}\n{}",
-                        chunk_id,
-                        &contributor,
-                        serde_json::to_string_pretty(&coordinator.read().unwrap().current_round()?)?
-                    );
-                    try_lock?;
-                }
-            }
-            {
-                // Run computation as contributor.
-                let contribute = coordinator.write().unwrap().run_computation(
-                    round_height,
-                    chunk_id,
-                    contribution_id,
-                    &contributor,
-                    &contributor_signing_key,
-                    &seed,
-                );
-                if contribute.is_err() {
-                    println!(
-                        "Failed to run computation for chunk {} as contributor {:?}\n{}",
-                        chunk_id,
-                        &contributor,
-                        serde_json::to_string_pretty(&coordinator.read().unwrap().current_round()?)?
-                    );
-                    contribute?;
-                }
+        // Within the budget the round waits for the queue wait time
+        time.update(|now| now + time::Duration::seconds(30));
+        coordinator.update()?;
+        assert!(coordinator.state.is_current_round_aggregated());
+        assert_eq!(1, coordinator.current_round_height()?);
 
-                // Add the contribution as the contributor.
-                let contribute = coordinator.write().unwrap().add_contribution(chunk_id, &contributor);
-                if contribute.is_err() {
-                    println!(
-                        "Failed to add contribution for chunk {} as contributor {:?}\n{}",
-                        chunk_id,
-                        &contributor,
-                        serde_json::to_string_pretty(&coordinator.read().unwrap().current_round()?)?
-                    );
-                    contribute?;
-                }
-            }
+        // Past the deadline the round advances with its verified contribution
+        time.update(|now| now + time::Duration::seconds(60));
+        coordinator.update()?;
+        assert_eq!(2, coordinator.current_round_height()?);
+        assert!(coordinator.is_current_contributor(&contributor_2));
 
-            // Spawn a thread to concurrently verify the contributions.
-            let coordinator_clone = coordinator.clone();
+        Ok(())
+    }
 
-            let verifier_thread = std::thread::spawn(move || {
-                let verifier = verifier.clone();
-                let verifier_signing_key: SigningKey = "secret_key".to_string();
+    #[test]
+    #[serial]
+    fn coordinator_round_time_budget_skip() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .round_time_budget(time::Duration::minutes(1))
+        .round_budget_policy(RoundBudgetPolicy::Skip)
+        .into();
+        initialize_test_environment(&environment);
 
-                {
-                    // Run verification as verifier.
-                    let task = Task::new(chunk_id, contribution_id);
-                    let verify = coordinator_clone.write().unwrap().run_verification(
-                        round_height,
-                        &task,
-                        &verifier,
-                        &verifier_signing_key,
-                    );
-                    if verify.is_err() {
-                        error!(
-                            "Failed to run verification as verifier {:?}\n{}",
-                            &verifier,
-                            serde_json::to_string_pretty(&coordinator_clone.read().unwrap().current_round().unwrap())
-                                .unwrap()
-                        );
-                        verify.unwrap();
-                    }
+        let time = Arc::new(MockTimeSource::new(*TEST_STARTED_AT));
+        let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
 
-                    // Add the verification as the verifier.
-                    let verify = coordinator_clone.write().unwrap().verify_contribution(&task, &verifier);
-                    if verify.is_err() {
-                        println!(
-                            "Failed to run verification as verifier {:?}\n{}",
-                            verifier.clone(),
-                            serde_json::to_string_pretty(&coordinator_clone.read().unwrap().current_round().unwrap())
-                                .unwrap()
-                        );
-                        panic!("{:?}", verify.unwrap())
-                    }
-                }
-            });
-            verifier_threads.push(verifier_thread);
-        }
+        time.update(|now| now + time::Duration::seconds(30));
+        assert_eq!(None, coordinator.try_advance_on_budget()?);
+        assert!(coordinator.is_current_contributor(&contributor));
 
-        for verifier_thread in verifier_threads {
-            verifier_thread.join().expect("Couldn't join on the verifier thread");
-        }
+        // Without any contribution at the deadline the contributor is dropped and, with nobody left to contribute,
+        // the round is rolled back to wait for new contributors
+        time.update(|now| now + time::Duration::seconds(60));
+        assert!(matches!(
+            coordinator.try_advance_on_budget(),
+            Err(CoordinatorError::RoundSkipped)
+        ));
+        assert!(coordinator.is_dropped_participant(&contributor));
+        assert!(!coordinator.is_current_contributor(&contributor));
+        assert_eq!(0, coordinator.current_round_height()?);
+        assert_eq!(None, coordinator.try_advance_on_budget()?);
 
         Ok(())
     }
 
     #[test]
     #[serial]
-    fn coordinator_aggregation() {
-        initialize_test_environment(&TEST_ENVIRONMENT_3);
+    fn coordinator_skip_round() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .into();
+        initialize_test_environment(&environment);
 
-        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy)).unwrap();
-        initialize_coordinator(&mut coordinator).unwrap();
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
 
-        // Check current round height is now 1.
-        let round_height = coordinator.current_round_height().unwrap();
-        assert_eq!(1, round_height);
+        // The empty round is skipped, its contributor moves on to the next round with the same challenge
+        assert_eq!(2, coordinator.skip_round()?);
+        assert_eq!(2, coordinator.current_round_height()?);
+        assert!(coordinator.get_round(1)?.is_skipped());
+        assert!(!coordinator.get_round(2)?.is_skipped());
+        assert!(coordinator.is_current_contributor(&contributor));
+        let challenge = |round_height| -> anyhow::Result<Vec<u8>> {
+            let locator = Locator::ContributionFile(ContributionLocator::new(round_height, 0, 0, true));
+            Ok(coordinator.storage.reader(&locator)?.as_ref().to_vec())
+        };
+        assert_eq!(challenge(1)?, challenge(2)?);
 
-        // Run computation and verification on each contribution in each chunk.
-        let contributors = vec![
-            Lazy::force(&TEST_CONTRIBUTOR_ID).clone(),
-            Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone(),
-        ];
+        // The skipped round is published with its challenge as transcript
+        let transcript = coordinator.get_round_transcript(1)?;
+        assert!(transcript.skipped);
+        assert_eq!(
+            vec![ChunkTranscript {
+                chunk_id: 0,
+                contributions: vec![hex::encode(challenge(1)?)],
+            }],
+            transcript.chunks
+        );
+        assert_eq!(
+            hex::encode(Blake2b512::digest(&challenge(2)?)),
+            coordinator.get_round_aggregate_hash(1)?
+        );
 
-        let verifier = Lazy::force(&TEST_VERIFIER_ID).clone();
-        let verifier_signing_key: SigningKey = "secret_key".to_string();
+        // A round with contributions can't be skipped
+        contribute_to_current_round(&mut coordinator, &contributor)?;
+        assert!(matches!(
+            coordinator.skip_round(),
+            Err(CoordinatorError::RoundHasContributions)
+        ));
+        assert_eq!(2, coordinator.current_round_height()?);
+        assert!(!coordinator.get_round(2)?.is_skipped());
 
-        let mut seeds = HashMap::new();
+        Ok(())
+    }
 
-        // Iterate over all chunk IDs.
-        for chunk_id in 0..TEST_ENVIRONMENT_3.number_of_chunks() {
-            // As contribution ID 0 is initialized by the coordinator, iterate from
-            // contribution ID 1 up to the expected number of contributions.
-            for contribution_id in 1..coordinator.current_round().unwrap().expected_number_of_contributions() {
-                let contributor = &contributors[contribution_id as usize - 1].clone();
-                let contributor_signing_key: SigningKey = "secret_key".to_string();
+    #[test]
+    #[serial]
+    fn coordinator_file_signature_scheme() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT);
+        let coordinator = Coordinator::new(TEST_ENVIRONMENT.clone(), Arc::new(Dummy))?;
 
-                trace!("{} is processing contribution {}", contributor, contribution_id);
-                {
-                    // Acquire the lock as contributor.
-                    let try_lock = coordinator.try_lock_chunk(chunk_id, &contributor);
-                    if try_lock.is_err() {
-                        println!(
-                            "Failed to acquire lock for chunk {} as contributor {:?}\n{}",
-                            chunk_id,
-                            &contributor,
-                            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
-                        );
-                        try_lock.unwrap();
-                    }
-                }
-                {
-                    let seed = if seeds.contains_key(&contribution_id) {
-                        seeds[&contribution_id]
-                    } else {
-                        let mut seed: Seed = [0; SEED_LENGTH];
-                        rand::thread_rng().fill_bytes(&mut seed[..]);
-                        seeds.insert(contribution_id.clone(), seed);
-                        seed
-                    };
+        let state = ContributionState::new(
+            calculate_hash(&[1; 128]).to_vec(),
+            calculate_hash(&[2; 128]).to_vec(),
+            None,
+        )?;
+        let message = serde_json::to_string(&state)?;
+        let signature = Dummy.sign("secret_key", &message)?;
+        let contribution_file_signature = ContributionFileSignature::new(signature.clone(), state)?;
+
+        // The signatures of the current scheme are verified with the signer of the coordinator
+        assert!(contribution_file_signature.get_scheme().is_current());
+        assert!(coordinator.verify_file_signature("public_key", &contribution_file_signature)?);
+        assert!(!contribution_file_signature
+            .get_scheme()
+            .verify("public_key", &message, &signature));
+
+        // The signatures of an unknown scheme are rejected, even if valid for the signer of the coordinator
+        let mut serialized = serde_json::to_value(&contribution_file_signature)?;
+        serialized["scheme"] = serde_json::json!(42);
+        let unknown: ContributionFileSignature = serde_json::from_value(serialized)?;
+        assert!(unknown.get_scheme().signer().is_none());
+        assert!(!coordinator.verify_file_signature("public_key", &unknown)?);
 
-                    // Run computation as contributor.
-                    let contribute = coordinator.run_computation(
-                        round_height,
-                        chunk_id,
-                        contribution_id,
-                        &contributor,
-                        &contributor_signing_key,
-                        &seed,
-                    );
-                    if contribute.is_err() {
-                        error!(
-                            "Failed to run computation as contributor {:?}\n{}",
-                            &contributor,
-                            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
-                        );
-                        contribute.unwrap();
-                    }
+        Ok(())
+    }
 
-                    // Add the contribution as the contributor.
-                    let contribute = coordinator.add_contribution(chunk_id, &contributor);
-                    if contribute.is_err() {
-                        error!(
-                            "Failed to add contribution as contributor {:?}\n{}",
-                            &contributor,
-                            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
-                        );
-                        contribute.unwrap();
-                    }
-                }
-                {
-                    // Run verification as verifier.
-                    let task = Task::new(chunk_id, contribution_id);
-                    let verify = coordinator.run_verification(round_height, &task, &verifier, &verifier_signing_key);
-                    if verify.is_err() {
-                        error!(
-                            "Failed to run verification as verifier {:?}\n{}",
-                            &verifier,
-                            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
-                        );
-                        verify.unwrap();
-                    }
+    #[test]
+    #[serial]
+    fn coordinator_skip_round_locked() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .into();
+        initialize_test_environment(&environment);
 
-                    // Add the verification as the verifier.
-                    let verify = coordinator.verify_contribution(&task, &verifier);
-                    if verify.is_err() {
-                        error!(
-                            "Failed to run verification as verifier {:?}\n{}",
-                            &verifier,
-                            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
-                        );
-                        verify.unwrap();
-                    }
-                }
-            }
-        }
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+
+        // The contributor holding the lock may still upload a contribution
+        coordinator.try_lock_chunk(0, &contributor)?;
+        assert!(matches!(
+            coordinator.skip_round(),
+            Err(CoordinatorError::RoundHasLockedChunks)
+        ));
+        assert_eq!(1, coordinator.current_round_height()?);
+        assert!(!coordinator.get_round(1)?.is_skipped());
+        assert!(matches!(
+            coordinator.get_round_transcript(1),
+            Err(CoordinatorError::RoundNotAggregated)
+        ));
 
-        println!(
-            "Starting aggregation with this transcript {}",
-            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
-        );
+        Ok(())
+    }
 
-        {
-            // Run aggregation for round 1.
-            coordinator.aggregate_contributions().unwrap();
-        }
+    #[test]
+    #[serial]
+    fn coordinator_contribution_provenance() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .into();
+        initialize_test_environment(&environment);
 
-        // Check that the round is still round 1, as try_advance has not been called.
-        assert_eq!(1, coordinator.current_round_height().unwrap());
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
 
-        println!(
-            "Finished aggregation with this transcript {}",
-            serde_json::to_string_pretty(&coordinator.current_round().unwrap()).unwrap()
+        // Without a known client nothing is recorded
+        assert!(coordinator.get_contributions_provenance(None, None)?.is_empty());
+
+        // Every contribution of the round is recorded, not only the last one
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_2 = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        let client = ClientProvenance {
+            ip_address: Some(IpAddr::V4("0.0.0.1".parse()?)),
+            user_agent: Some(String::from("namada-ts")),
+            client_version: Some(String::from("1.0")),
+        };
+        let client_2 = ClientProvenance {
+            ip_address: Some(IpAddr::V4("0.0.0.2".parse()?)),
+            user_agent: Some(String::from("namada-ts")),
+            client_version: Some(String::from("1.1")),
+        };
+        coordinator.set_client_provenance(contributor.clone(), client.clone());
+        coordinator.set_client_provenance(contributor_2.clone(), client_2.clone());
+        contribute_to_current_round(&mut coordinator, &contributor)?;
+        contribute_to_current_round(&mut coordinator, &contributor_2)?;
+
+        let provenance = coordinator.get_contributions_provenance(None, None)?;
+        assert_eq!(
+            vec![
+                ContributionProvenance {
+                    public_key: contributor.address(),
+                    ceremony_round: 1,
+                    chunk_id: 0,
+                    contribution_id: 1,
+                    client,
+                },
+                ContributionProvenance {
+                    public_key: contributor_2.address(),
+                    ceremony_round: 1,
+                    chunk_id: 0,
+                    contribution_id: 2,
+                    client: client_2,
+                }
+            ],
+            provenance
         );
+        assert!(coordinator.get_contributions_provenance(Some(2), Some(2)).is_err());
+
+        Ok(())
     }
 
     #[test]
     #[serial]
-    fn coordinator_next_round() -> anyhow::Result<()> {
-        initialize_test_environment(&TEST_ENVIRONMENT_3);
+    fn coordinator_pending_task_stable_across_reconnect() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .into();
+        initialize_test_environment(&environment);
 
-        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
         initialize_coordinator_single_contributor(&mut coordinator)?;
 
-        let round_height = 1;
-        assert_eq!(round_height, coordinator.current_round_height()?);
-
         let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
-        let contributor_signing_key: SigningKey = "secret_key".to_string();
+        let (chunk_id, locked_locators) = coordinator.try_lock(&contributor)?;
+        let task = coordinator.pending_task(&contributor, chunk_id)?.unwrap();
+        assert_eq!(
+            Task::new(chunk_id, locked_locators.next_contribution().contribution_id()),
+            task
+        );
 
-        let verifier = Lazy::force(&TEST_VERIFIER_ID);
-        let verifier_signing_key: SigningKey = "secret_key".to_string();
+        // The contributor reconnects to a restarted coordinator and retrieves its chunk again
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        let (reconnect_chunk_id, reconnect_locators) = coordinator.locked_locators(&contributor)?.unwrap();
+        assert_eq!(chunk_id, reconnect_chunk_id);
+        assert_eq!(
+            locked_locators.next_contribution(),
+            reconnect_locators.next_contribution()
+        );
+        assert_eq!(Some(task), coordinator.pending_task(&contributor, chunk_id)?);
 
-        // Run computation and verification on each contribution in each chunk.
-        let mut seeds = HashMap::new();
-        for chunk_id in 0..TEST_ENVIRONMENT_3.number_of_chunks() {
-            // Ensure contribution ID 0 is already verified by the coordinator.
-            assert!(coordinator
-                .current_round()?
-                .chunk(chunk_id)?
-                .get_contribution(0)?
-                .is_verified());
+        // Retrying the lock hands the same chunk and task back
+        let (retry_chunk_id, retry_locators) = coordinator.try_lock(&contributor)?;
+        assert_eq!(chunk_id, retry_chunk_id);
+        assert_eq!(locked_locators.next_contribution(), retry_locators.next_contribution());
+        assert_eq!(Some(task), coordinator.pending_task(&contributor, chunk_id)?);
 
-            // As contribution ID 0 is initialized by the coordinator, iterate from
-            // contribution ID 1 up to the expected number of contributions.
-            for contribution_id in 1..coordinator.current_round()?.expected_number_of_contributions() {
-                {
-                    // Acquire the lock as contributor.
-                    let try_lock = coordinator.try_lock_chunk(chunk_id, &contributor);
-                    if try_lock.is_err() {
-                        error!(
-                            "Failed to acquire lock as contributor {:?}\n{}",
-                            &contributor,
-                            serde_json::to_string_pretty(&coordinator.current_round()?)?
-                        );
-                        try_lock?;
-                    }
-                }
-                {
-                    // Run computation as contributor.
-                    let seed = if seeds.contains_key(&contribution_id) {
-                        seeds[&contribution_id]
-                    } else {
-                        let mut seed: Seed = [0; SEED_LENGTH];
-                        rand::thread_rng().fill_bytes(&mut seed[..]);
-                        seeds.insert(contribution_id.clone(), seed);
-                        seed
-                    };
+        Ok(())
+    }
 
-                    let contribute = coordinator.run_computation(
-                        round_height,
-                        chunk_id,
-                        contribution_id,
-                        &contributor,
-                        &contributor_signing_key,
-                        &seed,
-                    );
-                    if contribute.is_err() {
-                        error!(
-                            "Failed to run computation as contributor {:?}\n{}",
-                            &contributor,
-                            serde_json::to_string_pretty(&coordinator.current_round()?)?
-                        );
-                        contribute?;
-                    }
+    #[test]
+    #[serial]
+    fn coordinator_reclaim_orphaned_contribution_files() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 2,
+            power: 8,
+            batch_size: 128,
+        })
+        .into();
+        initialize_test_environment(&environment);
 
-                    // Add the contribution as the contributor.
-                    let contribute = coordinator.add_contribution(chunk_id, &contributor);
-                    if contribute.is_err() {
-                        error!(
-                            "Failed to add contribution as contributor {:?}\n{}",
-                            &contributor,
-                            serde_json::to_string_pretty(&coordinator.current_round()?)?
-                        );
-                        contribute?;
-                    }
-                }
-                {
-                    // Run verification as verifier.
-                    let task = Task::new(chunk_id, contribution_id);
-                    let verify = coordinator.run_verification(round_height, &task, &verifier, &verifier_signing_key);
-                    if verify.is_err() {
-                        error!(
-                            "Failed to run verification as verifier {:?}\n{}",
-                            &verifier,
-                            serde_json::to_string_pretty(&coordinator.current_round()?)?
-                        );
-                        verify?;
-                    }
+        let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
 
-                    // Add the verification as the verifier.
-                    let verify = coordinator.verify_contribution(&task, &verifier);
-                    if verify.is_err() {
-                        error!(
-                            "Failed to run verification as verifier {:?}\n{}",
-                            &verifier,
-                            serde_json::to_string_pretty(&coordinator.current_round()?)?
-                        );
-                        verify?;
-                    }
-                }
+        // The contribution to the first chunk is in progress
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        let in_progress = coordinator.try_lock_chunk(0, &contributor)?.next_contribution();
+        coordinator.run_computation(1, 0, in_progress.contribution_id(), &contributor, &signing_key, &seed)?;
+
+        // A crash left a contribution and its partial upload to the second chunk, which isn't locked, and a
+        // contribution to the previous round
+        let write_orphan = |coordinator: &Coordinator, orphan: ContributionLocator| -> anyhow::Result<PathBuf> {
+            let orphan_path: PathBuf = coordinator
+                .storage()
+                .to_path(&Locator::ContributionFile(orphan))?
+                .into();
+            let orphan_upload_path = PathBuf::from(format!("{}.part", orphan_path.display()));
+            std::fs::write(&orphan_path, b"orphan")?;
+            std::fs::write(&orphan_upload_path, b"orph")?;
+
+            Ok(orphan_upload_path)
+        };
+        let orphan = ContributionLocator::new(1, 1, 1, false);
+        let orphan_upload_path = write_orphan(&coordinator, orphan)?;
+        let previous_orphan = ContributionLocator::new(0, 0, 1, false);
+        let previous_orphan_upload_path = write_orphan(&coordinator, previous_orphan)?;
+
+        // The orphans are moved to the quarantine on restart, the challenges and the contribution in progress stay
+        let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
+        coordinator.initialize()?;
+        let storage = coordinator.storage();
+        let quarantine = Path::new(environment.local_base_directory()).join("quarantine");
+        for (orphan, upload_path) in [
+            (orphan, &orphan_upload_path),
+            (previous_orphan, &previous_orphan_upload_path),
+        ] {
+            assert!(!storage.exists(&Locator::ContributionFile(orphan)));
+            assert!(!upload_path.exists());
+            let quarantined = quarantine.join(format!(
+                "round_{}/chunk_{}/contribution_{}.unverified",
+                orphan.round_height(),
+                orphan.chunk_id(),
+                orphan.contribution_id()
+            ));
+            assert_eq!(b"orphan".to_vec(), std::fs::read(&quarantined)?);
+            assert!(PathBuf::from(format!("{}.part", quarantined.display())).exists());
+        }
+        assert!(storage.exists(&Locator::ContributionFile(in_progress)));
+        for chunk_id in 0..2 {
+            for round_height in 0..2 {
+                assert!(storage.exists(&Locator::ContributionFile(ContributionLocator::new(
+                    round_height,
+                    chunk_id,
+                    0,
+                    true
+                ))));
             }
         }
 
-        info!(
-            "Starting aggregation with this transcript {}",
-            serde_json::to_string_pretty(&coordinator.current_round()?)?
-        );
+        // The contribution in progress can still be added
+        coordinator.add_contribution(0, &contributor)?;
+
+        // An orphan of the first chunk, which isn't locked by the contributor anymore, is reclaimed as well, the
+        // recorded contribution stays
+        let orphan = ContributionLocator::new(1, 0, in_progress.contribution_id() + 1, false);
+        let orphan_upload_path = write_orphan(&coordinator, orphan)?;
+        let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
+        coordinator.initialize()?;
+        let storage = coordinator.storage();
+        assert!(!storage.exists(&Locator::ContributionFile(orphan)));
+        assert!(!orphan_upload_path.exists());
+        let orphan_name = format!("contribution_{}.unverified", orphan.contribution_id());
+        assert!(quarantine.join("round_1/chunk_0").join(orphan_name).exists());
+        assert!(storage.exists(&Locator::ContributionFile(in_progress)));
 
-        {
-            // Run aggregation for round 1.
-            coordinator.aggregate_contributions()?;
+        Ok(())
+    }
 
-            // Run aggregation and transition from round 1 to round 2.
-            coordinator.next_round(OffsetDateTime::now_utc(), vec![contributor.clone()])?;
+    #[test]
+    #[serial]
+    fn coordinator_resume_partial_verification() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 3,
+            power: 8,
+            batch_size: 128,
+        })
+        .into();
+        initialize_test_environment(&environment);
+
+        let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        // Contribute to all the chunks, leaving the contributions pending verification
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let verifier = Lazy::force(&TEST_VERIFIER_ID).clone();
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        let mut tasks = Vec::new();
+        for chunk_id in 0..coordinator.environment().number_of_chunks() {
+            let contribution_id = coordinator
+                .try_lock_chunk(chunk_id, &contributor)?
+                .next_contribution()
+                .contribution_id();
+            coordinator.run_computation(1, chunk_id, contribution_id, &contributor, &signing_key, &seed)?;
+            coordinator.add_contribution(chunk_id, &contributor)?;
+            tasks.push(Task::new(chunk_id, contribution_id));
         }
+        assert_eq!(3, coordinator.get_pending_verifications().len());
 
-        // Check that the ceremony has advanced to round 2.
-        assert_eq!(2, coordinator.current_round_height()?);
+        // The first contribution is verified, the second one too but the coordinator stops before saving its state
+        coordinator.run_verification(1, &tasks[0], &verifier, &signing_key)?;
+        coordinator.try_verify(&verifier, &tasks[0])?;
+        coordinator.run_verification(1, &tasks[1], &verifier, &signing_key)?;
+        coordinator.verify_contribution(&tasks[1], &verifier)?;
+        assert_eq!(2, coordinator.state.get_pending_verifications().len());
 
-        info!(
-            "Finished aggregation with this transcript {}",
-            serde_json::to_string_pretty(&coordinator.current_round()?)?
+        // Only the last contribution is pending verification after the restart
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        assert_eq!(
+            vec![tasks[2]],
+            coordinator.get_pending_verifications().into_keys().collect::<Vec<_>>()
+        );
+        coordinator.initialize()?;
+        assert_eq!(
+            vec![&tasks[2]],
+            coordinator.state.get_pending_verifications().keys().collect::<Vec<_>>()
         );
 
+        coordinator.run_verification(1, &tasks[2], &verifier, &signing_key)?;
+        coordinator.try_verify(&verifier, &tasks[2])?;
+        assert!(coordinator.get_pending_verifications().is_empty());
+
         Ok(())
     }
 
     #[test]
     #[serial]
-    #[ignore]
-    fn coordinator_number_of_chunks() {
-        let environment = &*Testing::from(Parameters::TestChunks { number_of_chunks: 4096 });
-        initialize_test_environment(environment);
+    fn coordinator_queue_analytics() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .into();
+        initialize_test_environment(&environment);
 
-        let mut coordinator = Coordinator::new(environment.clone(), Arc::new(Dummy)).unwrap();
-        initialize_coordinator(&mut coordinator).unwrap();
+        let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+        initialize_coordinator(&mut coordinator)?;
+
+        // A third contributor joins the queue while the first two are in round 1
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let contributor_3 = Lazy::force(&TEST_CONTRIBUTOR_ID_3).clone();
+        coordinator.state.add_to_queue(
+            contributor_3.clone(),
+            Some(IpAddr::V4("0.0.0.3".parse()?)),
+            String::from("irrelevant_token"),
+            10,
+            coordinator.time.as_ref(),
+        )?;
+
+        let analytics = coordinator.queue_analytics();
+        assert_eq!(3, analytics.joined);
+        assert_eq!(3, analytics.joined_last_hour);
+        assert_eq!(1, analytics.waiting);
+        assert_eq!(2, analytics.contributing);
+        assert_eq!(0, analytics.completed);
+        assert_eq!(0, analytics.dropped);
+        assert_eq!(0.0, analytics.dropout_rate);
+        assert!(analytics.average_wait_seconds.is_some());
+
+        // The first contributor completes its contribution and the third one drops out of the queue
+        contribute_to_current_round(&mut coordinator, &contributor)?;
+        coordinator.update()?;
+        coordinator.drop_participant(&contributor_3)?;
+
+        let analytics = coordinator.queue_analytics();
+        assert_eq!(3, analytics.joined);
+        assert_eq!(0, analytics.waiting);
+        assert_eq!(1, analytics.contributing);
+        assert_eq!(1, analytics.completed);
+        assert_eq!(1, analytics.dropped);
+        assert_eq!(0.5, analytics.dropout_rate);
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_timing_histogram() -> anyhow::Result<()> {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 3,
+            power: 8,
+            batch_size: 128,
+        })
+        .into();
+        initialize_test_environment(&environment);
+
+        let time = Arc::new(MockTimeSource::new(*TEST_STARTED_AT));
+        let mut coordinator = Coordinator::new_with_time(environment, Arc::new(Dummy), time.clone())?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        assert_eq!(TimingHistogram::new(&[]), coordinator.timing_histogram());
 
+        // Contribute to each chunk in a known time
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        let durations: [u64; 3] = [30, 90, 4000];
+        for (chunk_id, duration) in durations.iter().enumerate() {
+            let chunk_id = chunk_id as u64;
+            let contribution_id = coordinator
+                .try_lock_chunk(chunk_id, &contributor)?
+                .next_contribution()
+                .contribution_id();
+            time.update(|now| now + time::Duration::seconds(*duration as i64));
+            coordinator.run_computation(1, chunk_id, contribution_id, &contributor, &signing_key, &seed)?;
+            coordinator.add_contribution(chunk_id, &contributor)?;
+        }
+        assert_eq!(&durations, coordinator.state.contribution_timings());
+
+        let histogram = coordinator.timing_histogram();
+        assert_eq!(3, histogram.count);
+        let counts: Vec<(Option<u64>, usize)> = histogram
+            .buckets
+            .iter()
+            .map(|bucket| (bucket.up_to_seconds, bucket.count))
+            .collect();
         assert_eq!(
-            environment.number_of_chunks(),
-            coordinator.get_round(0).unwrap().chunks().len() as u64
+            vec![
+                (Some(60), 1),
+                (Some(300), 1),
+                (Some(600), 0),
+                (Some(1200), 0),
+                (Some(1800), 0),
+                (Some(3600), 0),
+                (None, 1),
+            ],
+            counts
+        );
+
+        // The bounds are included in their bucket
+        let histogram = TimingHistogram::new(&[60, 61, 3600, 3601]);
+        assert_eq!(1, histogram.buckets[0].count);
+        assert_eq!(1, histogram.buckets[1].count);
+        assert_eq!(1, histogram.buckets[5].count);
+        assert_eq!(1, histogram.buckets[6].count);
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_verification_timing() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_3);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+        assert_eq!(VerificationTimingStats::default(), coordinator.timings().verifications);
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let verifier = Lazy::force(&TEST_VERIFIER_ID).clone();
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        let (chunk_id, locked_locators) = coordinator.try_lock(&contributor)?;
+        let task = Task::new(chunk_id, locked_locators.next_contribution().contribution_id());
+        coordinator.run_computation(1, chunk_id, task.contribution_id(), &contributor, &signing_key, &seed)?;
+        coordinator.add_contribution(chunk_id, &contributor)?;
+        assert_eq!(None, coordinator.verification_duration(1, &task));
+
+        coordinator.run_verification(1, &task, &verifier, &signing_key)?;
+        coordinator.try_verify(&verifier, &task)?;
+
+        // The duration of the verification is recorded with the contribution, and survives a restart
+        let duration = coordinator.verification_duration(1, &task).unwrap();
+        assert!(duration > std::time::Duration::ZERO);
+        let coordinator = Coordinator::new(TEST_ENVIRONMENT_3.clone(), Arc::new(Dummy))?;
+        assert_eq!(Some(duration), coordinator.verification_duration(1, &task));
+
+        let duration_micros = duration.as_micros() as u64;
+        assert_eq!(
+            VerificationTimingStats {
+                count: 1,
+                min_micros: duration_micros,
+                mean_micros: duration_micros,
+                max_micros: duration_micros,
+            },
+            coordinator.timings().verifications
         );
+        assert_eq!(coordinator.timing_histogram(), coordinator.timings().contributions);
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn coordinator_reject_contribution() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let verifier = Lazy::force(&TEST_VERIFIER_ID).clone();
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        let (chunk_id, locked_locators) = coordinator.try_lock(&contributor)?;
+        let task = Task::new(chunk_id, locked_locators.next_contribution().contribution_id());
+        coordinator.run_computation(1, chunk_id, task.contribution_id(), &contributor, &signing_key, &seed)?;
+        coordinator.try_contribute(&contributor, chunk_id)?;
+        coordinator.update()?;
+        assert_eq!(None, coordinator.rejection_reason(&contributor));
+
+        // Tamper the challenge hash at the start of the contribution
+        let response_locator = Locator::ContributionFile(locked_locators.next_contribution());
+        let mut response = coordinator.storage.reader(&response_locator)?.to_vec();
+        response[0] ^= 1;
+        coordinator
+            .storage
+            .update(&response_locator, Object::ContributionFile(response))?;
+
+        let error = coordinator.verify(&verifier, &signing_key, &task).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<CoordinatorError>(),
+            Some(CoordinatorError::ContributionVerificationFailed {
+                reason: VerificationFailureReason::ChallengeHashMismatch
+            })
+        ));
+
+        // The contributor is banned, and the reason is kept to be reported to it, even after a restart
+        let reason = VerificationFailureReason::ChallengeHashMismatch.to_string();
+        assert_eq!(contributor, coordinator.reject_contribution(reason.clone())?);
+        assert!(coordinator.is_banned_participant(&contributor));
+        assert!(coordinator.get_pending_verifications().is_empty());
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        assert_eq!(Some(reason.as_str()), coordinator.rejection_reason(&contributor));
+
+        coordinator.unban_participant(&contributor)?;
+        assert_eq!(None, coordinator.rejection_reason(&contributor));
+
+        Ok(())
+    }
+    #[test]
+    #[serial]
+    fn coordinator_full_dump() -> anyhow::Result<()> {
+        initialize_test_environment(&TEST_ENVIRONMENT_ANOMA);
+
+        let mut coordinator = Coordinator::new(TEST_ENVIRONMENT_ANOMA.clone(), Arc::new(Dummy))?;
+        initialize_coordinator_single_contributor(&mut coordinator)?;
+
+        let contributor = Lazy::force(&TEST_CONTRIBUTOR_ID).clone();
+        let queued_contributor = Lazy::force(&TEST_CONTRIBUTOR_ID_2).clone();
+        let signing_key: SigningKey = "secret_key".to_string();
+        let mut seed: Seed = [0; SEED_LENGTH];
+        rand::thread_rng().fill_bytes(&mut seed[..]);
+        coordinator.add_to_queue(
+            queued_contributor.clone(),
+            Some(IpAddr::V4("0.0.0.2".parse()?)),
+            String::from("token_2"),
+            10,
+        )?;
+        let (chunk_id, locked_locators) = coordinator.try_lock(&contributor)?;
+
+        // The contributor of the round holds its lock, the other one waits in the queue
+        let dump = coordinator.full_dump();
+        assert_eq!(Some(1), dump.round_height);
+        assert_eq!(1, dump.queue.len());
+        assert_eq!(queued_contributor, dump.queue[0].participant);
+        assert_eq!(10, dump.queue[0].reliability);
+        assert!(!dump.queue[0].priority);
+        assert_eq!(1, dump.current_contributors.len());
+        assert_eq!(&contributor, dump.current_contributors[0].id());
+        assert!(dump.current_contributors[0].locked_chunks().contains_key(&chunk_id));
+        assert!(dump.finished_contributors.get(&1).map_or(true, Vec::is_empty));
+
+        // Once done, the contributor is reported among the finished ones of the round, with its completed task
+        let task = Task::new(chunk_id, locked_locators.next_contribution().contribution_id());
+        coordinator.run_computation(1, chunk_id, task.contribution_id(), &contributor, &signing_key, &seed)?;
+        coordinator.try_contribute(&contributor, chunk_id)?;
+        coordinator.update()?;
+
+        let dump = coordinator.full_dump();
+        assert!(dump.current_contributors.is_empty());
+        let finished = &dump.finished_contributors[&1];
+        assert_eq!(1, finished.len());
+        assert_eq!(&contributor, finished[0].id());
+        assert!(finished[0].completed_tasks().contains(&task));
+        assert_eq!(vec![(task, contributor)], dump.pending_verifications);
+        assert_eq!(queued_contributor, dump.queue[0].participant);
+
+        // The dump is served as json
+        let json = serde_json::to_value(&dump)?;
+        assert!(json["finished_contributors"]["1"].is_array());
+
+        Ok(())
     }
 }
@@ -3,9 +3,10 @@ use crate::{
     objects::{
         participant::*,
         task::{initialize_tasks, Task},
+        ClientProvenance,
     },
     storage::{Disk, Locator, Object},
-    CoordinatorError, TimeSource,
+    CeremonyDump, Cohort, CoordinatorError, QueueAnalytics, QueuedParticipant, TimeSource,
 };
 use anyhow::anyhow;
 use lazy_static::lazy_static;
@@ -41,6 +42,15 @@ pub(super) enum CoordinatorStatus {
     Rollback,
 }
 
+/// The wall time of the verification of a contribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerificationTiming {
+    pub round_height: u64,
+    pub task: Task,
+    /// The duration of the verification, in microseconds.
+    pub duration_micros: u64,
+}
+
 /// Represents a participant's exclusive lock on a chunk with the
 /// specified `chunk_id`, which was obtained at the specified
 /// `lock_time`.
@@ -955,6 +965,8 @@ struct RuntimeState {
     tokens_in_use: HashMap<String, Participant>,
     /// The map of ip addresses currently in ceremony
     current_ips: HashMap<IpAddr, Participant>,
+    /// The map of the clients the contributors joined the queue from
+    client_provenance: HashMap<Participant, ClientProvenance>,
 }
 
 impl Default for RuntimeState {
@@ -965,6 +977,7 @@ impl Default for RuntimeState {
             tokens: CoordinatorState::load_tokens(),
             tokens_in_use: Default::default(),
             current_ips: Default::default(),
+            client_provenance: Default::default(),
         }
     }
 }
@@ -1008,6 +1021,27 @@ pub struct CoordinatorState {
     cohort_duration: u64,
     /// Map of tokens which have been used in the ceremony
     blacklisted_tokens: HashMap<String, Participant>,
+    /// The map of the hashes of the contributions received in the ceremony to the round they were received in.
+    #[serde(default)]
+    contribution_hashes: HashMap<String, u64>,
+    /// The map of the contributors who joined the queue to the time they last joined it.
+    #[serde(default)]
+    queue_joins: HashMap<Participant, OffsetDateTime>,
+    /// The durations, in seconds, between the lock of a chunk and the reception of its contribution.
+    #[serde(default)]
+    contribution_timings: Vec<u64>,
+    /// The durations of the verifications of the contributions, in the order they were run.
+    #[serde(default)]
+    verification_timings: Vec<VerificationTiming>,
+    /// The map of the contributors whose contribution failed the verification to the reason of the failure.
+    #[serde(default)]
+    rejected_contributions: HashMap<Participant, String>,
+    /// The contributors in the queue who joined it with a priority token.
+    #[serde(default)]
+    priority_contributors: HashSet<Participant>,
+    /// Whether the intake of new contributors is paused.
+    #[serde(default)]
+    paused: bool,
     /// Temporary runtime state, should not be persisted to storage to reset it in case of restart
     #[serde(skip)]
     runtime_state: RuntimeState,
@@ -1079,14 +1113,15 @@ impl CoordinatorState {
     /// NOTE: At startup the coordinator will try to recover this state from disk instead of calling this initializer
     /// So we need to clear the coordinator.json file if we want to reset the following variables:
     ///     - CEREMONY_START_TIMESTAMP
-    ///     - NAMADA_COHORT_TIME
+    ///     - NAMADA_COHORT_TIME, or the cohort duration of the environment
     /// These two parameters are meant to stay constant during the entire ceremony.
     /// The tokens are instead reloaded from files when restarting a coordinator to support a token update
     #[inline]
     pub(super) fn new(environment: Environment) -> Self {
-        let cohort_duration = match std::env::var("NAMADA_COHORT_TIME") {
-            Ok(n) => n.parse::<u64>().unwrap(),
-            Err(_) => 86400,
+        let cohort_duration = match (environment.cohort_duration(), std::env::var("NAMADA_COHORT_TIME")) {
+            (Some(n), _) => n,
+            (None, Ok(n)) => n.parse::<u64>().unwrap(),
+            (None, Err(_)) => 86400,
         };
 
         let ceremony_start_time = CoordinatorState::get_ceremony_start_time();
@@ -1110,6 +1145,13 @@ impl CoordinatorState {
             ceremony_start_time,
             cohort_duration,
             blacklisted_tokens: HashMap::default(),
+            contribution_hashes: HashMap::default(),
+            queue_joins: HashMap::default(),
+            contribution_timings: Vec::new(),
+            verification_timings: Vec::new(),
+            rejected_contributions: HashMap::new(),
+            priority_contributors: HashSet::new(),
+            paused: false,
             runtime_state: RuntimeState::default(),
         }
     }
@@ -1222,6 +1264,13 @@ impl CoordinatorState {
                 queue,
                 banned: std::mem::take(&mut self.banned),
                 blacklisted_tokens: std::mem::take(&mut self.blacklisted_tokens),
+                contribution_hashes: std::mem::take(&mut self.contribution_hashes),
+                queue_joins: std::mem::take(&mut self.queue_joins),
+                contribution_timings: std::mem::take(&mut self.contribution_timings),
+                verification_timings: std::mem::take(&mut self.verification_timings),
+                rejected_contributions: std::mem::take(&mut self.rejected_contributions),
+                priority_contributors: std::mem::take(&mut self.priority_contributors),
+                paused: self.paused,
                 runtime_state: std::mem::take(&mut self.runtime_state),
                 ..Self::new(self.environment.clone())
             };
@@ -1268,6 +1317,13 @@ impl CoordinatorState {
                 banned: std::mem::take(&mut self.banned),
                 dropped: std::mem::take(&mut self.dropped),
                 blacklisted_tokens: std::mem::take(&mut self.blacklisted_tokens),
+                contribution_hashes: std::mem::take(&mut self.contribution_hashes),
+                queue_joins: std::mem::take(&mut self.queue_joins),
+                contribution_timings: std::mem::take(&mut self.contribution_timings),
+                verification_timings: std::mem::take(&mut self.verification_timings),
+                rejected_contributions: std::mem::take(&mut self.rejected_contributions),
+                priority_contributors: std::mem::take(&mut self.priority_contributors),
+                paused: self.paused,
                 runtime_state: std::mem::take(&mut self.runtime_state),
                 ..Self::new(self.environment.clone())
             };
@@ -1337,6 +1393,61 @@ impl CoordinatorState {
         self.is_authorized_contributor(participant) && self.current_contributors.contains_key(participant)
     }
 
+    ///
+    /// Returns `true` if no current contributor that joined the queue
+    /// before the given participant still has tasks to lock.
+    ///
+    /// The buckets of the contributors are assigned in order of joining
+    /// the queue, see `precommit_next_round`.
+    ///
+    #[inline]
+    pub fn is_head_of_current_round(&self, participant: &Participant) -> bool {
+        let bucket_id = match self.current_contributors.get(participant) {
+            Some(info) => info.bucket_id,
+            None => return false,
+        };
+
+        !self.current_contributors.iter().any(|(contributor, info)| {
+            contributor != participant
+                && info.bucket_id < bucket_id
+                && !info.is_finished()
+                && !info.assigned_tasks.is_empty()
+        })
+    }
+
+    ///
+    /// Returns the time at which the given current contributor will be
+    /// dropped if it has not been seen by the coordinator for longer
+    /// than the contributor seen timeout, or `None` if it's not stale.
+    ///
+    /// A stale contributor keeps its lock for the grace window, but
+    /// can't make progress until it's seen again.
+    ///
+    pub fn stale_contributor_eviction_time(
+        &self,
+        participant: &Participant,
+        time: &dyn TimeSource,
+    ) -> Option<OffsetDateTime> {
+        if self.is_coordinator_contributor(participant) {
+            return None;
+        }
+
+        let last_seen = self.current_contributors.get(participant)?.last_seen;
+        if time.now_utc() - last_seen > self.environment.contributor_seen_timeout() {
+            Some(last_seen + self.environment.contributor_seen_timeout() + self.environment.contributor_stale_grace())
+        } else {
+            None
+        }
+    }
+
+    ///
+    /// Returns `true` if the given participant is a verifier and not banned.
+    ///
+    #[inline]
+    pub fn is_authorized_verifier(&self, participant: &Participant) -> bool {
+        participant.is_verifier() && !self.banned.contains(participant)
+    }
+
     ///
     /// Returns `true` if the given participant is banned.
     ///
@@ -1344,6 +1455,14 @@ impl CoordinatorState {
         self.banned.contains(participant)
     }
 
+    ///
+    /// Returns the reason why the verification of the contribution of the given
+    /// participant failed, if it did.
+    ///
+    pub fn rejection_reason(&self, participant: &Participant) -> Option<&str> {
+        self.rejected_contributions.get(participant).map(String::as_str)
+    }
+
     ///
     /// Returns `true` if the given participant is dropped.
     ///
@@ -1424,6 +1543,134 @@ impl CoordinatorState {
         self.queue.par_iter().filter(|(p, _)| p.is_contributor()).count()
     }
 
+    ///
+    /// Returns the durations, in seconds, between the lock of a chunk and the
+    /// reception of its contribution, in the order they were measured.
+    ///
+    #[inline]
+    pub fn contribution_timings(&self) -> &[u64] {
+        &self.contribution_timings
+    }
+
+    ///
+    /// Returns the average duration of the last `window` contributions, `None` if no
+    /// contribution has been received yet.
+    ///
+    pub fn average_contribution_duration(&self, window: usize) -> Option<Duration> {
+        let last = &self.contribution_timings[self.contribution_timings.len().saturating_sub(window)..];
+        if last.is_empty() {
+            return None;
+        }
+
+        Some(Duration::seconds((last.iter().sum::<u64>() / last.len() as u64) as i64))
+    }
+
+    ///
+    /// Returns the time at which the given participant last joined the queue, if it did.
+    ///
+    #[inline]
+    pub fn queue_join_time(&self, participant: &Participant) -> Option<OffsetDateTime> {
+        self.queue_joins.get(participant).copied()
+    }
+
+    ///
+    /// Returns the durations of the verifications of the contributions, in the
+    /// order they were run.
+    ///
+    #[inline]
+    pub fn verification_timings(&self) -> &[VerificationTiming] {
+        &self.verification_timings
+    }
+
+    ///
+    /// Records the duration of the verification of the given task of the given
+    /// round. A verification run again, e.g. after a restart, replaces the
+    /// previous record.
+    ///
+    pub(super) fn record_verification_timing(&mut self, round_height: u64, task: Task, duration: std::time::Duration) {
+        self.verification_timings
+            .retain(|timing| timing.round_height != round_height || timing.task != task);
+        self.verification_timings.push(VerificationTiming {
+            round_height,
+            task,
+            duration_micros: duration.as_micros() as u64,
+        });
+    }
+
+    ///
+    /// Returns the analytics of the queue, derived from the times the contributors
+    /// joined it and from the outcome of their participation.
+    ///
+    pub fn queue_analytics(&self, time: &dyn TimeSource) -> QueueAnalytics {
+        let now = time.now_utc();
+        let finished: HashMap<&Participant, &ParticipantInfo> = self
+            .finished_contributors
+            .values()
+            .flat_map(|contributors| contributors.iter())
+            .collect();
+        let dropped: HashMap<&Participant, &ParticipantInfo> = self
+            .dropped
+            .iter()
+            .filter(|info| info.id.is_contributor())
+            .map(|info| (&info.id, info))
+            .collect();
+
+        let joins: Vec<_> = self.queue_joins.iter().filter(|(p, _)| p.is_contributor()).collect();
+
+        let mut analytics = QueueAnalytics {
+            joined: joins.len(),
+            ..Default::default()
+        };
+        let mut waits = Vec::new();
+        for (participant, joined_at) in joins {
+            if now - *joined_at <= Duration::hours(1) {
+                analytics.joined_last_hour += 1;
+            }
+
+            // A contributor who rejoined after being dropped is accounted for its latest participation
+            let info = if let Some(info) = finished.get(participant) {
+                analytics.completed += 1;
+                Some(*info)
+            } else if let Some(info) = self.current_contributors.get(participant).filter(|i| !i.is_dropped()) {
+                analytics.contributing += 1;
+                Some(info)
+            } else if self.queue.contains_key(participant) || self.next.contains_key(participant) {
+                analytics.waiting += 1;
+                None
+            } else {
+                // Dropped either from the round or, if there's no record of it, while waiting in the queue
+                analytics.dropped += 1;
+                dropped.get(participant).copied()
+            };
+
+            if let Some(started_at) = info.and_then(|info| info.started_at) {
+                if started_at >= *joined_at {
+                    waits.push((started_at - *joined_at).whole_seconds());
+                }
+            }
+        }
+
+        let concluded = analytics.completed + analytics.dropped;
+        if concluded > 0 {
+            analytics.dropout_rate = analytics.dropped as f64 / concluded as f64;
+        }
+        if !waits.is_empty() {
+            analytics.average_wait_seconds = Some(waits.iter().sum::<i64>() / waits.len() as i64);
+        }
+
+        let cohort = self.get_current_cohort_index();
+        analytics.cohort = cohort as u64 + 1;
+        if let Some(tokens) = self.tokens(cohort) {
+            analytics.cohort_capacity = tokens.len();
+            analytics.cohort_fill = tokens
+                .iter()
+                .filter(|token| self.is_token_in_use(token) || self.is_token_blacklisted(token))
+                .count();
+        }
+
+        analytics
+    }
+
     ///
     /// Returns the information of a queued contributor.
     ///
@@ -1434,6 +1681,56 @@ impl CoordinatorState {
         self.queue.get(participant)
     }
 
+    ///
+    /// Returns a snapshot of every participant of the ceremony, see [`CeremonyDump`].
+    ///
+    pub fn dump(&self) -> CeremonyDump {
+        let mut queue: Vec<_> = self
+            .queue
+            .iter()
+            .map(
+                |(participant, (reliability, assigned_round, last_seen, joined))| QueuedParticipant {
+                    participant: participant.clone(),
+                    reliability: *reliability,
+                    assigned_round: *assigned_round,
+                    last_seen: *last_seen,
+                    joined: *joined,
+                    priority: self.is_priority_contributor(participant),
+                },
+            )
+            .collect();
+        queue.sort_by(|a, b| {
+            a.assigned_round
+                .cmp(&b.assigned_round)
+                .then(b.priority.cmp(&a.priority))
+                .then(a.joined.cmp(&b.joined))
+        });
+
+        CeremonyDump {
+            round_height: self.current_round_height,
+            paused: self.paused,
+            manual_lock: self.manual_lock,
+            queue,
+            next: self.next.values().cloned().collect(),
+            current_contributors: self.current_contributors.values().cloned().collect(),
+            current_verifiers: self.current_verifiers.values().cloned().collect(),
+            pending_verifications: self
+                .pending_verification
+                .iter()
+                .map(|(task, participant)| (*task, participant.clone()))
+                .collect(),
+            finished_contributors: self
+                .finished_contributors
+                .iter()
+                .map(|(round_height, contributors)| (*round_height, contributors.values().cloned().collect()))
+                .collect(),
+            dropped: self.dropped.clone(),
+            banned: self.banned.iter().cloned().collect(),
+            tokens_in_use: self.runtime_state.tokens_in_use.clone(),
+            blacklisted_tokens: self.blacklisted_tokens.clone(),
+        }
+    }
+
     ///
     /// Returns a list of the contributors currently in the queue.
     ///
@@ -1490,6 +1787,16 @@ impl CoordinatorState {
         self.current_round_height.unwrap_or_default()
     }
 
+    ///
+    /// Resets the current round height of the coordinator state to the given round height, when it
+    /// has been reconciled with the rounds in storage.
+    ///
+    pub(super) fn reset_current_round_height(&mut self, round_height: u64) {
+        self.current_round_height = Some(round_height);
+        self.finished_contributors.entry(round_height).or_default();
+        self.finished_verifiers.entry(round_height).or_default();
+    }
+
     ///
     /// Returns the metrics for the current round and current round participants.
     ///
@@ -1525,6 +1832,39 @@ impl CoordinatorState {
         self.runtime_state.tokens.get(cohort)
     }
 
+    ///
+    /// Returns the schedule of the cohorts of the ceremony, in order.
+    ///
+    pub fn cohort_schedule(&self) -> Vec<Cohort> {
+        let cohort_duration = Duration::seconds(self.cohort_duration as i64);
+
+        self.runtime_state
+            .tokens
+            .iter()
+            .enumerate()
+            .map(|(i, tokens)| {
+                let start = self.ceremony_start_time + cohort_duration * i as u32;
+
+                Cohort {
+                    index: i as u64 + 1,
+                    start,
+                    end: start + cohort_duration,
+                    capacity: tokens.len(),
+                }
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns the index, starting from 0, of the cohort the token belongs to, if any.
+    ///
+    pub fn token_cohort_index(&self, token: &str) -> Option<usize> {
+        self.runtime_state
+            .tokens
+            .iter()
+            .position(|tokens| tokens.contains(token))
+    }
+
     pub fn get_tokens(&self) -> &Vec<HashSet<String>> {
         &self.runtime_state.tokens
     }
@@ -1537,6 +1877,20 @@ impl CoordinatorState {
         &self.runtime_state.tokens_in_use
     }
 
+    ///
+    /// Returns the client the participant joined the queue from, if known.
+    ///
+    pub fn get_client_provenance(&self, participant: &Participant) -> Option<&ClientProvenance> {
+        self.runtime_state.client_provenance.get(participant)
+    }
+
+    ///
+    /// Sets the client the participant joined the queue from.
+    ///
+    pub(super) fn set_client_provenance(&mut self, participant: Participant, client: ClientProvenance) {
+        self.runtime_state.client_provenance.insert(participant, client);
+    }
+
     ///
     /// Moves the token from the list of currently in use to the black list
     ///
@@ -1607,6 +1961,38 @@ impl CoordinatorState {
         self.blacklisted_tokens.contains_key(token)
     }
 
+    ///
+    /// Returns `true` if the given token grants a priority slot in the queue.
+    ///
+    #[inline]
+    pub fn is_priority_token(&self, token: &str) -> bool {
+        self.environment.priority_tokens().contains(token)
+    }
+
+    ///
+    /// Returns `true` if the given participant joined the queue with a priority token.
+    ///
+    #[inline]
+    pub fn is_priority_contributor(&self, participant: &Participant) -> bool {
+        self.priority_contributors.contains(participant)
+    }
+
+    ///
+    /// Returns the round in which a contribution with the given hash has been received, if any.
+    ///
+    pub fn contribution_hash_round(&self, contribution_hash: &str) -> Option<u64> {
+        self.contribution_hashes.get(contribution_hash).copied()
+    }
+
+    ///
+    /// Records the hash of a contribution received in the given round.
+    ///
+    pub(super) fn record_contribution_hash(&mut self, contribution_hash: String, round_height: u64) {
+        self.contribution_hashes
+            .entry(contribution_hash)
+            .or_insert(round_height);
+    }
+
     ///
     /// Returns `true` if all participants in the current round have no more pending chunks.
     ///
@@ -1803,6 +2189,12 @@ impl CoordinatorState {
             participant.clone(),
             (reliability_score, None, time.now_utc(), time.now_utc()),
         );
+        self.queue_joins.insert(participant.clone(), time.now_utc());
+
+        // Serve the holders of a priority token ahead of the other contributors
+        if self.is_priority_token(&token) {
+            self.priority_contributors.insert(participant.clone());
+        }
 
         // Add ip (if env set and if any) to the set of currently known addresses
         if *IP_BAN {
@@ -1836,6 +2228,7 @@ impl CoordinatorState {
 
         // Remove the participant from the queue.
         self.queue.remove(participant);
+        self.priority_contributors.remove(participant);
 
         Ok(())
     }
@@ -2160,7 +2553,15 @@ impl CoordinatorState {
                 // Adds the task to the list of completed tasks for the contributor,
                 // and add the task to the pending verification set.
                 Some(participant_info) => {
+                    let locked_at = participant_info
+                        .locked_chunks
+                        .get(&task.chunk_id())
+                        .map(|lock| *lock.lock_time());
                     participant_info.completed_task(task, time)?;
+                    if let Some(locked_at) = locked_at {
+                        let duration = (time.now_utc() - locked_at).whole_seconds();
+                        self.contribution_timings.push(duration.max(0) as u64);
+                    }
                     self.stop_task_timer(participant, &task, time);
                     self.add_pending_verification(task)
                 }
@@ -2313,6 +2714,32 @@ impl CoordinatorState {
         }
     }
 
+    ///
+    /// Moves the contributors of the current round back into the queue, to
+    /// contribute to the next round instead.
+    ///
+    pub(super) fn requeue_current_contributors(&mut self, time: &dyn TimeSource) {
+        for (participant, participant_info) in self.current_contributors.drain() {
+            self.queue.insert(
+                participant,
+                (
+                    participant_info.reliability,
+                    Some(participant_info.round_height + 1),
+                    time.now_utc(),
+                    time.now_utc(),
+                ),
+            );
+        }
+    }
+
+    /// Set the `current_metrics` ([RoundMetrics]) `next_round_after`
+    /// field to now, allowing the next round to start right away.
+    pub(super) fn expire_queue_wait_time(&mut self, time: &dyn TimeSource) {
+        if let Some(metrics) = &mut self.current_metrics {
+            metrics.next_round_after = Some(time.now_utc());
+        }
+    }
+
     ///
     /// Rolls back the current round from aggregating in round metrics.
     ///
@@ -2379,6 +2806,9 @@ impl CoordinatorState {
 
             // Remove token from the list of current tokens
             self.runtime_state.tokens_in_use.retain(|_, part| part != participant);
+
+            // Forget the client of the participant, it will be recorded again at the next join
+            self.runtime_state.client_provenance.remove(participant);
         }
 
         // Remove the participant from the queue and precommit, if present.
@@ -2655,6 +3085,18 @@ impl CoordinatorState {
 
         // Unban ip
         self.blacklisted_ips.retain(|_, part| part != participant);
+
+        // A contributor allowed back is no longer reported as rejected
+        self.rejected_contributions.remove(participant);
+    }
+
+    ///
+    /// Records the reason why the verification of the contribution of the given
+    /// participant failed.
+    ///
+    #[inline]
+    pub(super) fn reject_contribution(&mut self, participant: &Participant, reason: String) {
+        self.rejected_contributions.insert(participant.clone(), reason);
     }
 
     ///
@@ -2736,6 +3178,22 @@ impl CoordinatorState {
         self.manual_lock = false;
     }
 
+    ///
+    /// Returns `true` if the intake of new contributors is paused.
+    ///
+    #[inline]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    ///
+    /// Sets whether the intake of new contributors is paused.
+    ///
+    #[inline]
+    pub(super) fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
     ///
     /// Returns the current round height stored in the coordinator state.
     ///
@@ -2757,14 +3215,25 @@ impl CoordinatorState {
             _ => return Err(CoordinatorError::RoundHeightNotSet),
         };
 
-        // Sort the participants in the queue by time joined.
+        // Forget the priority of the contributors who left the queue.
+        let current_queue = &self.queue;
+        self.priority_contributors.retain(|p| current_queue.contains_key(p));
+
+        // Sort the participants in the queue by time joined, the holders of a
+        // priority token first. The normal participants keep their FIFO order.
         let mut queue: Vec<_> = self
             .queue
             .clone()
             .into_par_iter()
             .map(|(p, (r, _, ls, j))| (p, r, ls, j))
             .collect();
-        queue.par_sort_by(|a, b| (a.3).cmp(&b.3));
+        let priority = &self.priority_contributors;
+        queue.par_sort_by(|a, b| {
+            priority
+                .contains(&b.0)
+                .cmp(&priority.contains(&a.0))
+                .then((a.3).cmp(&b.3))
+        });
 
         // Parse the queue participants into contributors and verifiers,
         // and check that they are not banned participants.
@@ -2966,6 +3435,8 @@ impl CoordinatorState {
     ) -> Result<Vec<DropParticipant>, CoordinatorError> {
         // Fetch the timeout threshold for contributors.
         let contributor_seen_timeout = self.environment.contributor_seen_timeout();
+        // Stale contributors are only dropped once the grace window has expired.
+        let contributor_eviction_timeout = contributor_seen_timeout + self.environment.contributor_stale_grace();
 
         // Fetch the current time.
         let now = time.now_utc();
@@ -2978,18 +3449,26 @@ impl CoordinatorState {
                 let elapsed = now - participant_info.last_seen;
 
                 // Check if the participant is still live and not a coordinator contributor.
-                if elapsed > contributor_seen_timeout && !self.is_coordinator_contributor(&participant) {
+                if elapsed <= contributor_seen_timeout || self.is_coordinator_contributor(&participant) {
+                    None
+                } else if elapsed <= contributor_eviction_timeout {
+                    tracing::debug!(
+                        "Participant {} is stale (last seen {:?}s ago), it will be dropped in {:?}s.",
+                        participant,
+                        elapsed.whole_seconds(),
+                        (contributor_eviction_timeout - elapsed).whole_seconds()
+                    );
+                    None
+                } else {
                     tracing::warn!(
                         "Dropping participant {} because it has exceeded the maximum ({:?}s) allowed time \
                         since it was last seen by the coordinator (last seen {:?}s ago).",
                         participant,
-                        contributor_seen_timeout.whole_seconds(),
+                        contributor_eviction_timeout.whole_seconds(),
                         elapsed.whole_seconds()
                     );
                     // Drop the participant.
                     Some(self.drop_participant(participant, time))
-                } else {
-                    None
                 }
             })
             .collect()
@@ -3264,7 +3743,7 @@ impl CoordinatorState {
              *
              * ***********************************************************************************
              *
-             *   1. Sort the round contributors from most reliable to least reliable.
+             *   1. Sort the round contributors in order of joining the queue.
              *
              *   2. Assign CONTRIBUTOR 1 to BUCKET 1, CONTRIBUTOR 2 to BUCKET 2,
              *      CONTRIBUTOR 3 to BUCKET 3, ..., CONTRIBUTOR N to BUCKET N,
@@ -3279,8 +3758,17 @@ impl CoordinatorState {
              * ***********************************************************************************
              */
 
-            // Sort the contributors by their reliability (in order of highest to lowest number).
-            contributors.par_sort_by(|a, b| ((b.1).0).cmp(&(&a.1).0));
+            // Sort the contributors by their time of joining the queue, to serve them in FIFO order,
+            // and then by their reliability (in order of highest to lowest number). The holders of
+            // a priority token are served first.
+            let priority = &self.priority_contributors;
+            contributors.par_sort_by(|a, b| {
+                priority
+                    .contains(&b.0)
+                    .cmp(&priority.contains(&a.0))
+                    .then(((a.1).3).cmp(&(b.1).3))
+                    .then(((b.1).0).cmp(&(a.1).0))
+            });
 
             // Fetch the number of chunks and bucket size.
             let number_of_chunks = self.environment.number_of_chunks() as u64;
@@ -3686,6 +4174,20 @@ mod tests {
         assert_eq!(Some(current_round_height), state.current_round_height);
     }
 
+    #[test]
+    fn test_average_contribution_duration() {
+        let mut state = CoordinatorState::new(TEST_ENVIRONMENT.clone());
+        assert_eq!(None, state.average_contribution_duration(3));
+
+        state.contribution_timings = vec![60];
+        assert_eq!(Some(Duration::minutes(1)), state.average_contribution_duration(3));
+
+        // Only the last contributions are accounted for
+        state.contribution_timings = vec![6000, 60, 120, 181];
+        assert_eq!(Some(Duration::seconds(120)), state.average_contribution_duration(3));
+        assert_eq!(Some(Duration::seconds(1590)), state.average_contribution_duration(10));
+    }
+
     #[test]
     fn test_add_to_queue_contributor() {
         let time = SystemTimeSource::new();
@@ -3730,6 +4232,48 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_priority_contributor_served_first() {
+        let time = MockTimeSource::new(OffsetDateTime::now_utc());
+        let environment: Environment = Testing::from(Parameters::Test8Chunks)
+            .priority_tokens(vec![String::from("priority_token")].into_iter().collect())
+            .into();
+        let normal_contributor = TEST_CONTRIBUTOR_ID.clone();
+        let priority_contributor = TEST_CONTRIBUTOR_ID_2.clone();
+
+        let mut state = CoordinatorState::new(environment);
+        let current_round_height = 5;
+        state.initialize(current_round_height);
+
+        // The normal contributor joins the queue before the priority one
+        state
+            .add_to_queue(normal_contributor.clone(), None, String::from("test_token"), 10, &time)
+            .unwrap();
+        time.update(|now| now + time::Duration::seconds(1));
+        state
+            .add_to_queue(
+                priority_contributor.clone(),
+                None,
+                String::from("priority_token"),
+                10,
+                &time,
+            )
+            .unwrap();
+        assert!(state.is_priority_contributor(&priority_contributor));
+        assert!(!state.is_priority_contributor(&normal_contributor));
+
+        // The priority contributor is assigned the next round, the normal one the round after
+        state.update_queue().unwrap();
+        assert_eq!(Some(6), state.queue.get(&priority_contributor).unwrap().1);
+        assert_eq!(Some(7), state.queue.get(&normal_contributor).unwrap().1);
+
+        // Leaving the queue drops the priority
+        state.remove_from_queue(&priority_contributor).unwrap();
+        assert!(!state.is_priority_contributor(&priority_contributor));
+        state.update_queue().unwrap();
+        assert_eq!(Some(6), state.queue.get(&normal_contributor).unwrap().1);
+    }
+
     #[test]
     fn test_add_duplicate_ip_to_queue_contributor() {
         let time = SystemTimeSource::new();
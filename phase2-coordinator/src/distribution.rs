@@ -0,0 +1,50 @@
+//! Distribution of the challenges to the contributors. The challenges are uploaded to a CDN, from which the contributors
+//! download them through presigned urls. The CDN is abstracted by the [`ChallengeDistributor`] trait, so that operators
+//! can plug in their own storage instead of Amazon S3.
+
+use std::sync::Arc;
+
+use thiserror::Error;
+
+use crate::s3::{S3Ctx, S3Error};
+
+#[derive(Error, Debug)]
+pub enum DistributionError {
+    #[error("{0}")]
+    Backend(String),
+    #[error("{0}")]
+    S3(#[from] S3Error),
+}
+
+/// A CDN serving the challenges through presigned urls.
+#[rocket::async_trait]
+pub trait ChallengeDistributor: Send + Sync {
+    /// Returns the presigned url of the challenge stored under the given key, `None` if it hasn't been uploaded yet.
+    async fn get_presigned_url(&self, key: &str) -> Result<Option<String>, DistributionError>;
+
+    /// Uploads the challenge under the given key. Returns the presigned url to get it.
+    async fn upload(&self, key: &str, challenge: Vec<u8>) -> Result<String, DistributionError>;
+}
+
+/// The distributor of the challenges, managed by the REST server.
+pub type Distributor = Arc<dyn ChallengeDistributor>;
+
+/// Distribution of the challenges through Amazon S3, configured by the `AWS_*` environment variables.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct S3Distributor;
+
+#[rocket::async_trait]
+impl ChallengeDistributor for S3Distributor {
+    async fn get_presigned_url(&self, key: &str) -> Result<Option<String>, DistributionError> {
+        // NOTE: the context is created for each request so that the credentials are refreshed before they expire
+        let s3_ctx = S3Ctx::new().await?;
+
+        Ok(s3_ctx.get_challenge_url(key.to_string()).await)
+    }
+
+    async fn upload(&self, key: &str, challenge: Vec<u8>) -> Result<String, DistributionError> {
+        let s3_ctx = S3Ctx::new().await?;
+
+        Ok(s3_ctx.upload_challenge(key.to_string(), challenge).await?)
+    }
+}
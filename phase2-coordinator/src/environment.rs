@@ -1,9 +1,15 @@
-use crate::{authentication::KeyPair, objects::Participant, storage::Disk};
+use crate::{
+    authentication::KeyPair,
+    objects::Participant,
+    storage::{Disk, EncryptionKey},
+};
 pub use phase2::{helpers::CurveKind, ContributionMode, ProvingSystem};
 use setup_utils::{CheckForCorrectness, UseCompression};
 
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, convert::TryFrom, path::Path, str::FromStr};
+use thiserror::Error;
 
 type BatchSize = usize;
 type ChunkSize = usize;
@@ -78,6 +84,68 @@ pub enum Deployment {
     Production,
 }
 
+/// What to do with a round which collected no verified contribution within its time budget.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RoundBudgetPolicy {
+    /// Keep waiting for a contribution for another time budget.
+    Extend,
+    /// Drop the contributors of the round, handing it to the next contributors of the queue.
+    Skip,
+}
+
+impl Default for RoundBudgetPolicy {
+    fn default() -> Self {
+        Self::Extend
+    }
+}
+
+/// The parameters the ceremony contributes to. The challenge and contribution files hold the parameters of each
+/// circuit one after the other, after the hash of the previous contribution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CeremonyParameters {
+    /// The Spend, Output and Convert circuits of the MASP, or its single test circuit in debug builds.
+    Masp,
+    /// The given number of circuits of another ceremony.
+    Circuits(usize),
+}
+
+impl CeremonyParameters {
+    /// Returns the number of circuits whose parameters are held by the challenge and contribution files.
+    pub const fn number_of_circuits(&self) -> usize {
+        match self {
+            #[cfg(debug_assertions)]
+            Self::Masp => 1,
+            #[cfg(not(debug_assertions))]
+            Self::Masp => 3,
+            Self::Circuits(circuits) => *circuits,
+        }
+    }
+}
+
+impl Default for CeremonyParameters {
+    fn default() -> Self {
+        Self::Masp
+    }
+}
+
+/// Parses the parameters from `masp` or `circuits:<number of circuits>`.
+impl FromStr for CeremonyParameters {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ConfigError::Invalid(format!("unknown ceremony parameters \"{}\"", s));
+
+        match s.split_once(':') {
+            None if s == "masp" => Ok(Self::Masp),
+            Some(("circuits", circuits)) => match circuits.parse() {
+                Ok(circuits) if circuits > 0 => Ok(Self::Circuits(circuits)),
+                _ => Err(invalid()),
+            },
+            _ => Err(invalid()),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Parameters {
     AleoInner,
@@ -247,6 +315,11 @@ pub struct Environment {
     /// being seen by the coordinator before it will be dropped from
     /// the ceremony by the coordinator.
     contributor_seen_timeout: time::Duration,
+    /// The grace window after the `contributor_seen_timeout` during which
+    /// a contributor is stale: it keeps its lock but can't make progress
+    /// until it's seen again, or it's dropped once the window expires.
+    #[serde(default)]
+    contributor_stale_grace: time::Duration,
     /// The maximum duration a verifier can go without being seen by
     /// the coordinator before it will be dropped from the ceremony by
     /// the coordinator.
@@ -279,6 +352,52 @@ pub struct Environment {
     deployment: Deployment,
     /// The base directory for disk storage of this coordinator.
     local_base_directory: String,
+    /// The url notified of the round transitions of the ceremony.
+    round_webhook_url: Option<String>,
+    /// The interval over which the writes of the coordinator and round states are coalesced, if any.
+    storage_batch_interval: Option<time::Duration>,
+    /// The key encrypting the contribution files at rest, if any. Never serialized.
+    #[serde(skip)]
+    storage_encryption_key: Option<EncryptionKey>,
+    /// The directory the completed rounds are moved to, if any.
+    storage_archive_directory: Option<String>,
+    /// The time after which the current round advances to the next one, regardless of the queue, if any.
+    round_time_budget: Option<time::Duration>,
+    /// What to do with a round which collected no verified contribution within its time budget.
+    #[serde(default)]
+    round_budget_policy: RoundBudgetPolicy,
+    /// The number of contributions pending verification above which new contributors are deferred, if any.
+    max_pending_verifications: Option<usize>,
+    /// The file listing the public keys of the contributors authorized to join the queue, if participation is restricted.
+    #[serde(default)]
+    contributor_allowlist_path: Option<String>,
+    /// The duration in seconds of each cohort of tokens, unless read from the `NAMADA_COHORT_TIME` environment variable.
+    #[serde(default)]
+    cohort_duration: Option<u64>,
+    /// The maximum number of bytes of the bodies logged when tracing the HTTP requests, if tracing is enabled.
+    http_trace_body_limit: Option<usize>,
+    /// The key of the HMAC identifying the contributors by their email in the public contributions' info, instead of
+    /// their name, if their identity is redacted. Never serialized.
+    #[serde(skip)]
+    contributor_identity_key: Option<EncryptionKey>,
+    /// Whether the coordinator only serves the ceremony, refusing any request which would mutate it.
+    #[serde(default)]
+    read_only: bool,
+    /// The parameters the ceremony contributes to.
+    #[serde(default)]
+    ceremony_parameters: CeremonyParameters,
+    /// The file of the initial parameters of the circuits, required by a [`CeremonyParameters::Circuits`] ceremony.
+    #[serde(default)]
+    initial_parameters_path: Option<String>,
+    /// The tokens granting a priority slot in the queue, ahead of the other contributors.
+    #[serde(default)]
+    priority_tokens: HashSet<String>,
+    /// The number of times the aggregation of a round is retried after a transient error.
+    #[serde(default)]
+    aggregation_retries: u32,
+    /// The delay before the first retry of the aggregation, doubled at each subsequent retry.
+    #[serde(default)]
+    aggregation_retry_backoff: time::Duration,
 
     disable_reliability_zeroing: bool,
 }
@@ -362,6 +481,15 @@ impl Environment {
         self.contributor_seen_timeout
     }
 
+    ///
+    /// Returns the grace window after the contributor seen timeout
+    /// during which a contributor is stale, before being dropped
+    /// from the ceremony by the coordinator.
+    ///
+    pub const fn contributor_stale_grace(&self) -> time::Duration {
+        self.contributor_stale_grace
+    }
+
     ///
     /// Returns the maximum duration a verifier can go without being
     /// seen by the coordinator before it will be dropped from the
@@ -464,6 +592,158 @@ impl Environment {
         &self.local_base_directory
     }
 
+    ///
+    /// Returns the url to which the coordinator posts the round
+    /// transitions of the ceremony, if any.
+    ///
+    pub fn round_webhook_url(&self) -> Option<&str> {
+        self.round_webhook_url.as_deref()
+    }
+
+    ///
+    /// Returns the interval over which the storage coalesces the writes
+    /// of the coordinator and round states. If `None`, every write is
+    /// persisted immediately.
+    ///
+    pub const fn storage_batch_interval(&self) -> Option<time::Duration> {
+        self.storage_batch_interval
+    }
+
+    ///
+    /// Returns the key encrypting the contribution files at rest.
+    /// If `None`, the contribution files are stored in plaintext.
+    ///
+    pub const fn storage_encryption_key(&self) -> Option<&EncryptionKey> {
+        self.storage_encryption_key.as_ref()
+    }
+
+    ///
+    /// Returns the directory the completed rounds are moved to, e.g. on
+    /// a cheaper file system. If `None`, all the rounds stay in the local
+    /// base directory.
+    ///
+    pub fn storage_archive_directory(&self) -> Option<&str> {
+        self.storage_archive_directory.as_deref()
+    }
+
+    ///
+    /// Returns the time budget of a round. Once elapsed, a round with at
+    /// least one verified contribution advances to the next one without
+    /// waiting for the queue. If `None`, rounds have no time limit.
+    ///
+    pub const fn round_time_budget(&self) -> Option<time::Duration> {
+        self.round_time_budget
+    }
+
+    ///
+    /// Returns the policy applied to a round which collected no verified
+    /// contribution within its time budget.
+    ///
+    pub const fn round_budget_policy(&self) -> RoundBudgetPolicy {
+        self.round_budget_policy
+    }
+
+    ///
+    /// Returns the high-water mark of the contributions pending verification.
+    /// Above it, new contributors can neither join the queue nor lock a
+    /// chunk until the backlog drains. If `None`, the backlog is unbounded.
+    ///
+    pub const fn max_pending_verifications(&self) -> Option<usize> {
+        self.max_pending_verifications
+    }
+
+    ///
+    /// Returns the path of the json file listing the public keys of the
+    /// contributors authorized to join the queue. If `None`, anyone holding
+    /// a valid token can participate.
+    ///
+    pub fn contributor_allowlist_path(&self) -> Option<&str> {
+        self.contributor_allowlist_path.as_deref()
+    }
+
+    ///
+    /// Returns the duration in seconds of each cohort of tokens. If `None`,
+    /// it's read from the `NAMADA_COHORT_TIME` environment variable.
+    ///
+    pub const fn cohort_duration(&self) -> Option<u64> {
+        self.cohort_duration
+    }
+
+    ///
+    /// Returns the maximum number of bytes of the request and response
+    /// bodies logged by the HTTP tracer. If `None`, the requests are not traced.
+    ///
+    pub const fn http_trace_body_limit(&self) -> Option<usize> {
+        self.http_trace_body_limit
+    }
+
+    ///
+    /// Returns `true` if the public contributions' info omits the name of
+    /// the contributors and identifies them by the hash of their email.
+    ///
+    pub const fn redact_contributor_identity(&self) -> bool {
+        self.contributor_identity_key.is_some()
+    }
+
+    ///
+    /// Returns the key of the HMAC of the emails of the contributors, if
+    /// their identity is redacted.
+    ///
+    pub const fn contributor_identity_key(&self) -> Option<&EncryptionKey> {
+        self.contributor_identity_key.as_ref()
+    }
+
+    ///
+    /// Returns `true` if the coordinator is read-only, e.g. to keep serving
+    /// the transcript and the status once the ceremony is over. The mutating
+    /// endpoints are refused and the coordinator is never updated.
+    ///
+    pub const fn read_only(&self) -> bool {
+        self.read_only
+    }
+
+    ///
+    /// Returns the parameters the ceremony contributes to, which select the
+    /// computation and the verification of the contributions.
+    ///
+    pub const fn ceremony_parameters(&self) -> CeremonyParameters {
+        self.ceremony_parameters
+    }
+
+    ///
+    /// Returns the file of the initial parameters of the circuits, which
+    /// the challenge of round 0 is initialized from in a
+    /// [`CeremonyParameters::Circuits`] ceremony.
+    ///
+    pub fn initial_parameters_path(&self) -> Option<&str> {
+        self.initial_parameters_path.as_deref()
+    }
+
+    ///
+    /// Returns the tokens granting a priority slot in the queue. The holders
+    /// of a priority token are served before the other contributors, in the
+    /// order they joined the queue.
+    ///
+    pub const fn priority_tokens(&self) -> &HashSet<String> {
+        &self.priority_tokens
+    }
+
+    ///
+    /// Returns the number of times the aggregation of a round is retried
+    /// after a transient (IO) error, before failing the round transition.
+    ///
+    pub const fn aggregation_retries(&self) -> u32 {
+        self.aggregation_retries
+    }
+
+    ///
+    /// Returns the delay before the first retry of the aggregation, doubled
+    /// at each subsequent retry.
+    ///
+    pub const fn aggregation_retry_backoff(&self) -> time::Duration {
+        self.aggregation_retry_backoff
+    }
+
     ///
     /// Returns the appropriate number of chunks for the coordinator
     /// to run given a proof system, power and chunk size.
@@ -500,6 +780,259 @@ impl From<Production> for Environment {
     }
 }
 
+/// Error while loading the [`Environment`] from a configuration file.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("Error while reading the config file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Error while parsing the config file: {0}")]
+    Parse(#[from] toml::de::Error),
+    #[error("Invalid config: {0}")]
+    Invalid(String),
+}
+
+/// Configuration file of the coordinator, in TOML. Every entry is optional and falls back to the [`Production`]
+/// default, while the `NAMADA_MPC_*` environment variables, when set, take precedence over the file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EnvironmentConfig {
+    parameters: Option<ParametersConfig>,
+    #[serde(default)]
+    storage: StorageConfig,
+    #[serde(default)]
+    timeouts: TimeoutsConfig,
+    #[serde(default)]
+    queue: QueueConfig,
+    #[serde(default)]
+    cohorts: CohortsConfig,
+    read_only: Option<bool>,
+}
+
+/// The [`Parameters::Namada`] of the ceremony.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ParametersConfig {
+    number_of_chunks: usize,
+    power: usize,
+    batch_size: usize,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct StorageConfig {
+    base_directory: Option<String>,
+    archive_directory: Option<String>,
+    batch_seconds: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TimeoutsConfig {
+    contributor_seen_seconds: Option<i64>,
+    contributor_stale_grace_seconds: Option<i64>,
+    participant_lock_seconds: Option<i64>,
+    queue_seen_seconds: Option<i64>,
+    round_time_budget_seconds: Option<i64>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct QueueConfig {
+    minimum_contributors_per_round: Option<usize>,
+    maximum_contributors_per_round: Option<usize>,
+    wait_time_seconds: Option<u64>,
+    max_pending_verifications: Option<usize>,
+    allowlist_path: Option<String>,
+}
+
+/// The schedule of the cohorts. The start of the ceremony is read by the
+/// [`CoordinatorState`](crate::coordinator_state::CoordinatorState) from the `CEREMONY_START_TIMESTAMP` environment
+/// variable.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CohortsConfig {
+    start_timestamp: Option<i64>,
+    duration_seconds: Option<u64>,
+}
+
+/// Returns the value of the config file unless the environment variable overriding it is set.
+fn unless_env<T>(var: &str, value: Option<T>) -> Option<T> {
+    match std::env::var_os(var) {
+        Some(_) => None,
+        None => value,
+    }
+}
+
+/// Fails with [`ConfigError::Invalid`] if the value is set but not strictly positive.
+fn positive<T: PartialOrd + Default>(name: &str, value: Option<T>) -> Result<Option<T>, ConfigError> {
+    match value {
+        Some(v) if v <= T::default() => Err(ConfigError::Invalid(format!("{} must be positive", name))),
+        _ => Ok(value),
+    }
+}
+
+impl EnvironmentConfig {
+    /// Checks the consistency of the configuration.
+    fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(parameters) = &self.parameters {
+            positive("parameters.number_of_chunks", Some(parameters.number_of_chunks))?;
+            positive("parameters.power", Some(parameters.power))?;
+            positive("parameters.batch_size", Some(parameters.batch_size))?;
+        }
+
+        if let Some(directory) = &self.storage.base_directory {
+            if directory.is_empty() {
+                return Err(ConfigError::Invalid(
+                    "storage.base_directory must not be empty".to_string(),
+                ));
+            }
+        }
+        positive("storage.batch_seconds", self.storage.batch_seconds)?;
+
+        positive(
+            "timeouts.contributor_seen_seconds",
+            self.timeouts.contributor_seen_seconds,
+        )?;
+        if let Some(grace) = self.timeouts.contributor_stale_grace_seconds {
+            if grace < 0 {
+                return Err(ConfigError::Invalid(
+                    "timeouts.contributor_stale_grace_seconds must not be negative".to_string(),
+                ));
+            }
+        }
+        positive(
+            "timeouts.participant_lock_seconds",
+            self.timeouts.participant_lock_seconds,
+        )?;
+        positive("timeouts.queue_seen_seconds", self.timeouts.queue_seen_seconds)?;
+        positive(
+            "timeouts.round_time_budget_seconds",
+            self.timeouts.round_time_budget_seconds,
+        )?;
+
+        positive(
+            "queue.minimum_contributors_per_round",
+            self.queue.minimum_contributors_per_round,
+        )?;
+        positive(
+            "queue.maximum_contributors_per_round",
+            self.queue.maximum_contributors_per_round,
+        )?;
+        positive("queue.max_pending_verifications", self.queue.max_pending_verifications)?;
+        if let Some(path) = &self.queue.allowlist_path {
+            if path.is_empty() {
+                return Err(ConfigError::Invalid(
+                    "queue.allowlist_path must not be empty".to_string(),
+                ));
+            }
+        }
+        if let (Some(min), Some(max)) = (
+            self.queue.minimum_contributors_per_round,
+            self.queue.maximum_contributors_per_round,
+        ) {
+            if min > max {
+                return Err(ConfigError::Invalid(
+                    "queue.minimum_contributors_per_round exceeds queue.maximum_contributors_per_round".to_string(),
+                ));
+            }
+        }
+
+        positive("cohorts.start_timestamp", self.cohorts.start_timestamp)?;
+        positive("cohorts.duration_seconds", self.cohorts.duration_seconds)?;
+
+        Ok(())
+    }
+}
+
+impl Environment {
+    ///
+    /// Loads the environment from the TOML config file at the given path, on top of the [`Production`] defaults.
+    /// The `NAMADA_MPC_*` environment variables take precedence over the values of the file.
+    ///
+    /// NOTE: the start of the ceremony is exported to the `CEREMONY_START_TIMESTAMP` environment variable, unless
+    /// already set, since that's where the coordinator state reads it from.
+    ///
+    pub fn from_config_path(path: &Path, keypair: &KeyPair) -> Result<Self, ConfigError> {
+        let config: EnvironmentConfig = toml::from_str(&std::fs::read_to_string(path)?)?;
+        config.validate()?;
+
+        let mut environment: Environment = Production::new(keypair)?.into();
+
+        if let Some(parameters) = config.parameters {
+            environment.parameters = Parameters::Namada {
+                number_of_chunks: parameters.number_of_chunks,
+                power: parameters.power,
+                batch_size: parameters.batch_size,
+            }
+            .to_settings();
+        }
+
+        if let Some(directory) = config.storage.base_directory {
+            environment.local_base_directory = directory;
+        }
+        if let Some(directory) = unless_env("NAMADA_MPC_STORAGE_ARCHIVE_DIR", config.storage.archive_directory) {
+            environment.storage_archive_directory = Some(directory);
+        }
+        if let Some(seconds) = unless_env("NAMADA_MPC_STORAGE_BATCH_SECONDS", config.storage.batch_seconds) {
+            environment.storage_batch_interval = Some(time::Duration::seconds(seconds));
+        }
+
+        if let Some(seconds) = unless_env("NAMADA_MPC_TIMEOUT_SECONDS", config.timeouts.contributor_seen_seconds) {
+            environment.contributor_seen_timeout = time::Duration::seconds(seconds);
+        }
+        if let Some(seconds) = unless_env(
+            "NAMADA_MPC_STALE_GRACE_SECONDS",
+            config.timeouts.contributor_stale_grace_seconds,
+        ) {
+            environment.contributor_stale_grace = time::Duration::seconds(seconds);
+        }
+        if let Some(seconds) = unless_env("NAMADA_MPC_TIMEOUT_SECONDS", config.timeouts.participant_lock_seconds) {
+            environment.participant_lock_timeout = time::Duration::seconds(seconds);
+        }
+        if let Some(seconds) = unless_env("NAMADA_MPC_TIMEOUT_SECONDS", config.timeouts.queue_seen_seconds) {
+            environment.queue_seen_timeout = time::Duration::seconds(seconds);
+        }
+        if let Some(seconds) = unless_env(
+            "NAMADA_MPC_ROUND_TIME_BUDGET_SECONDS",
+            config.timeouts.round_time_budget_seconds,
+        ) {
+            environment.round_time_budget = Some(time::Duration::seconds(seconds));
+        }
+
+        if let Some(minimum) = config.queue.minimum_contributors_per_round {
+            environment.minimum_contributors_per_round = minimum;
+        }
+        if let Some(maximum) = config.queue.maximum_contributors_per_round {
+            environment.maximum_contributors_per_round = maximum;
+        }
+        if let Some(seconds) = config.queue.wait_time_seconds {
+            environment.queue_wait_time = seconds;
+        }
+        if let Some(max) = unless_env(
+            "NAMADA_MPC_MAX_PENDING_VERIFICATIONS",
+            config.queue.max_pending_verifications,
+        ) {
+            environment.max_pending_verifications = Some(max);
+        }
+        if let Some(path) = unless_env("NAMADA_MPC_ALLOWLIST_PATH", config.queue.allowlist_path) {
+            environment.contributor_allowlist_path = Some(path);
+        }
+
+        if let Some(read_only) = unless_env("NAMADA_MPC_READ_ONLY", config.read_only) {
+            environment.read_only = read_only;
+        }
+
+        if let Some(timestamp) = unless_env("CEREMONY_START_TIMESTAMP", config.cohorts.start_timestamp) {
+            std::env::set_var("CEREMONY_START_TIMESTAMP", timestamp.to_string());
+        }
+        if let Some(seconds) = unless_env("NAMADA_COHORT_TIME", config.cohorts.duration_seconds) {
+            environment.cohort_duration = Some(seconds);
+        }
+
+        Ok(environment)
+    }
+}
+
 // TODO (howardwu): Convert the implementation to a procedural macro.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Testing {
@@ -552,6 +1085,12 @@ impl Testing {
         deployment
     }
 
+    pub fn contributor_stale_grace(&self, grace: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contributor_stale_grace = grace;
+        deployment
+    }
+
     pub fn participant_lock_timeout(&self, participant_lock_timeout: time::Duration) -> Self {
         let mut deployment = self.clone();
         deployment.environment.participant_lock_timeout = participant_lock_timeout;
@@ -564,6 +1103,109 @@ impl Testing {
         deployment
     }
 
+    pub fn round_webhook_url(&self, url: &str) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.round_webhook_url = Some(url.to_owned());
+        deployment
+    }
+
+    pub fn storage_batch_interval(&self, interval: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.storage_batch_interval = Some(interval);
+        deployment
+    }
+
+    pub fn storage_encryption_key(&self, key: EncryptionKey) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.storage_encryption_key = Some(key);
+        deployment
+    }
+
+    pub fn storage_archive_directory(&self, directory: &str) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.storage_archive_directory = Some(directory.to_owned());
+        deployment
+    }
+
+    pub fn round_time_budget(&self, budget: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.round_time_budget = Some(budget);
+        deployment
+    }
+
+    pub fn round_budget_policy(&self, policy: RoundBudgetPolicy) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.round_budget_policy = policy;
+        deployment
+    }
+
+    pub fn max_pending_verifications(&self, max: usize) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.max_pending_verifications = Some(max);
+        deployment
+    }
+
+    pub fn contributor_allowlist_path(&self, path: &str) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contributor_allowlist_path = Some(path.to_string());
+        deployment
+    }
+
+    pub fn cohort_duration(&self, seconds: u64) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.cohort_duration = Some(seconds);
+        deployment
+    }
+
+    pub fn read_only(&self, read_only: bool) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.read_only = read_only;
+        deployment
+    }
+
+    pub fn ceremony_parameters(&self, parameters: CeremonyParameters) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.ceremony_parameters = parameters;
+        deployment
+    }
+
+    pub fn initial_parameters_path(&self, path: &str) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.initial_parameters_path = Some(path.to_string());
+        deployment
+    }
+
+    pub fn priority_tokens(&self, tokens: HashSet<String>) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.priority_tokens = tokens;
+        deployment
+    }
+
+    pub fn aggregation_retries(&self, retries: u32, backoff: time::Duration) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.aggregation_retries = retries;
+        deployment.environment.aggregation_retry_backoff = backoff;
+        deployment
+    }
+
+    pub fn queue_wait_time(&self, queue_wait_time: u64) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.queue_wait_time = queue_wait_time;
+        deployment
+    }
+
+    pub fn http_trace_body_limit(&self, limit: usize) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.http_trace_body_limit = Some(limit);
+        deployment
+    }
+
+    pub fn redact_contributor_identity(&self, key: EncryptionKey) -> Self {
+        let mut deployment = self.clone();
+        deployment.environment.contributor_identity_key = Some(key);
+        deployment
+    }
+
     fn generate_namada_env(keypair: &KeyPair) -> Self {
         Self {
             environment: Environment {
@@ -584,6 +1226,7 @@ impl Testing {
                 contributor_lock_chunk_limit: 1,
                 verifier_lock_chunk_limit: 5,
                 contributor_seen_timeout: time::Duration::minutes(5),
+                contributor_stale_grace: time::Duration::ZERO,
                 verifier_seen_timeout: time::Duration::minutes(15),
                 participant_lock_timeout: time::Duration::minutes(20),
                 queue_seen_timeout: time::Duration::minutes(5),
@@ -599,6 +1242,23 @@ impl Testing {
                 software_version: 1,
                 deployment: Deployment::Testing,
                 local_base_directory: "./transcript/testing".to_string(),
+                round_webhook_url: None,
+                storage_batch_interval: None,
+                storage_encryption_key: None,
+                storage_archive_directory: None,
+                round_time_budget: None,
+                round_budget_policy: RoundBudgetPolicy::Extend,
+                max_pending_verifications: None,
+                contributor_allowlist_path: None,
+                cohort_duration: None,
+                http_trace_body_limit: None,
+                contributor_identity_key: None,
+                read_only: false,
+                ceremony_parameters: CeremonyParameters::Masp,
+                initial_parameters_path: None,
+                priority_tokens: HashSet::new(),
+                aggregation_retries: 3,
+                aggregation_retry_backoff: time::Duration::ZERO,
 
                 disable_reliability_zeroing: false,
             },
@@ -657,6 +1317,11 @@ impl Development {
         self
     }
 
+    pub fn contributor_stale_grace(mut self, grace: time::Duration) -> Self {
+        self.environment.contributor_stale_grace = grace;
+        self
+    }
+
     pub fn participant_lock_timeout(mut self, timeout: time::Duration) -> Self {
         self.environment.participant_lock_timeout = timeout;
         self
@@ -667,6 +1332,87 @@ impl Development {
         self
     }
 
+    pub fn round_webhook_url(mut self, url: &str) -> Self {
+        self.environment.round_webhook_url = Some(url.to_owned());
+        self
+    }
+
+    pub fn storage_batch_interval(mut self, interval: time::Duration) -> Self {
+        self.environment.storage_batch_interval = Some(interval);
+        self
+    }
+
+    pub fn storage_encryption_key(mut self, key: EncryptionKey) -> Self {
+        self.environment.storage_encryption_key = Some(key);
+        self
+    }
+
+    pub fn storage_archive_directory(mut self, directory: &str) -> Self {
+        self.environment.storage_archive_directory = Some(directory.to_owned());
+        self
+    }
+
+    pub fn round_time_budget(mut self, budget: time::Duration) -> Self {
+        self.environment.round_time_budget = Some(budget);
+        self
+    }
+
+    pub fn round_budget_policy(mut self, policy: RoundBudgetPolicy) -> Self {
+        self.environment.round_budget_policy = policy;
+        self
+    }
+
+    pub fn max_pending_verifications(mut self, max: usize) -> Self {
+        self.environment.max_pending_verifications = Some(max);
+        self
+    }
+
+    pub fn contributor_allowlist_path(mut self, path: &str) -> Self {
+        self.environment.contributor_allowlist_path = Some(path.to_string());
+        self
+    }
+
+    pub fn cohort_duration(mut self, seconds: u64) -> Self {
+        self.environment.cohort_duration = Some(seconds);
+        self
+    }
+
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.environment.read_only = read_only;
+        self
+    }
+
+    pub fn ceremony_parameters(mut self, parameters: CeremonyParameters) -> Self {
+        self.environment.ceremony_parameters = parameters;
+        self
+    }
+
+    pub fn initial_parameters_path(mut self, path: &str) -> Self {
+        self.environment.initial_parameters_path = Some(path.to_string());
+        self
+    }
+
+    pub fn priority_tokens(mut self, tokens: HashSet<String>) -> Self {
+        self.environment.priority_tokens = tokens;
+        self
+    }
+
+    pub fn aggregation_retries(mut self, retries: u32, backoff: time::Duration) -> Self {
+        self.environment.aggregation_retries = retries;
+        self.environment.aggregation_retry_backoff = backoff;
+        self
+    }
+
+    pub fn http_trace_body_limit(mut self, limit: usize) -> Self {
+        self.environment.http_trace_body_limit = Some(limit);
+        self
+    }
+
+    pub fn redact_contributor_identity(mut self, key: EncryptionKey) -> Self {
+        self.environment.contributor_identity_key = Some(key);
+        self
+    }
+
     #[inline]
     pub fn coordinator_contributors(&self, contributors: &[Participant]) -> Self {
         // Check that all participants are contributors.
@@ -711,6 +1457,7 @@ impl Development {
                 contributor_lock_chunk_limit: 1,
                 verifier_lock_chunk_limit: 5,
                 contributor_seen_timeout: time::Duration::minutes(1),
+                contributor_stale_grace: time::Duration::seconds(30),
                 verifier_seen_timeout: time::Duration::minutes(15),
                 participant_lock_timeout: time::Duration::minutes(20),
                 queue_seen_timeout: time::Duration::minutes(10),
@@ -726,6 +1473,23 @@ impl Development {
                 software_version: 1,
                 deployment: Deployment::Development,
                 local_base_directory: "./transcript/development".to_string(),
+                round_webhook_url: None,
+                storage_batch_interval: None,
+                storage_encryption_key: None,
+                storage_archive_directory: None,
+                round_time_budget: None,
+                round_budget_policy: RoundBudgetPolicy::Extend,
+                max_pending_verifications: None,
+                contributor_allowlist_path: None,
+                cohort_duration: None,
+                http_trace_body_limit: None,
+                contributor_identity_key: None,
+                read_only: false,
+                ceremony_parameters: CeremonyParameters::Masp,
+                initial_parameters_path: None,
+                priority_tokens: HashSet::new(),
+                aggregation_retries: 3,
+                aggregation_retry_backoff: time::Duration::seconds(1),
 
                 disable_reliability_zeroing: false,
             },
@@ -790,6 +1554,11 @@ impl Production {
         self
     }
 
+    pub fn contributor_stale_grace(mut self, grace: time::Duration) -> Self {
+        self.environment.contributor_stale_grace = grace;
+        self
+    }
+
     pub fn participant_lock_timeout(mut self, timeout: time::Duration) -> Self {
         self.environment.participant_lock_timeout = timeout;
         self
@@ -805,6 +1574,87 @@ impl Production {
         self
     }
 
+    pub fn round_webhook_url(mut self, url: &str) -> Self {
+        self.environment.round_webhook_url = Some(url.to_owned());
+        self
+    }
+
+    pub fn storage_batch_interval(mut self, interval: time::Duration) -> Self {
+        self.environment.storage_batch_interval = Some(interval);
+        self
+    }
+
+    pub fn storage_encryption_key(mut self, key: EncryptionKey) -> Self {
+        self.environment.storage_encryption_key = Some(key);
+        self
+    }
+
+    pub fn storage_archive_directory(mut self, directory: &str) -> Self {
+        self.environment.storage_archive_directory = Some(directory.to_owned());
+        self
+    }
+
+    pub fn round_time_budget(mut self, budget: time::Duration) -> Self {
+        self.environment.round_time_budget = Some(budget);
+        self
+    }
+
+    pub fn round_budget_policy(mut self, policy: RoundBudgetPolicy) -> Self {
+        self.environment.round_budget_policy = policy;
+        self
+    }
+
+    pub fn max_pending_verifications(mut self, max: usize) -> Self {
+        self.environment.max_pending_verifications = Some(max);
+        self
+    }
+
+    pub fn contributor_allowlist_path(mut self, path: &str) -> Self {
+        self.environment.contributor_allowlist_path = Some(path.to_string());
+        self
+    }
+
+    pub fn cohort_duration(mut self, seconds: u64) -> Self {
+        self.environment.cohort_duration = Some(seconds);
+        self
+    }
+
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.environment.read_only = read_only;
+        self
+    }
+
+    pub fn ceremony_parameters(mut self, parameters: CeremonyParameters) -> Self {
+        self.environment.ceremony_parameters = parameters;
+        self
+    }
+
+    pub fn initial_parameters_path(mut self, path: &str) -> Self {
+        self.environment.initial_parameters_path = Some(path.to_string());
+        self
+    }
+
+    pub fn priority_tokens(mut self, tokens: HashSet<String>) -> Self {
+        self.environment.priority_tokens = tokens;
+        self
+    }
+
+    pub fn aggregation_retries(mut self, retries: u32, backoff: time::Duration) -> Self {
+        self.environment.aggregation_retries = retries;
+        self.environment.aggregation_retry_backoff = backoff;
+        self
+    }
+
+    pub fn http_trace_body_limit(mut self, limit: usize) -> Self {
+        self.environment.http_trace_body_limit = Some(limit);
+        self
+    }
+
+    pub fn redact_contributor_identity(mut self, key: EncryptionKey) -> Self {
+        self.environment.contributor_identity_key = Some(key);
+        self
+    }
+
     #[inline]
     pub fn coordinator_contributors(&self, contributors: &[Participant]) -> Self {
         // Check that all participants are contributors.
@@ -829,13 +1679,82 @@ impl Production {
         deployment
     }
 
-    fn generate_namada_env(keypair: &KeyPair) -> Self {
+    fn generate_namada_env(keypair: &KeyPair) -> Result<Self, ConfigError> {
         let test_timeout = match std::env::var("NAMADA_MPC_TIMEOUT_SECONDS") {
             Ok(t) => Some(time::Duration::seconds(t.parse::<i64>().unwrap())),
             Err(_) => None,
         };
+        let stale_grace = match std::env::var("NAMADA_MPC_STALE_GRACE_SECONDS") {
+            Ok(t) => Some(time::Duration::seconds(t.parse::<i64>().unwrap())),
+            Err(_) => None,
+        };
+        let storage_batch_interval = match std::env::var("NAMADA_MPC_STORAGE_BATCH_SECONDS") {
+            Ok(t) => Some(time::Duration::seconds(t.parse::<i64>().unwrap())),
+            Err(_) => None,
+        };
+        let storage_encryption_key = match std::env::var("NAMADA_MPC_STORAGE_ENCRYPTION_KEY") {
+            Ok(k) => Some(
+                hex::decode(k)
+                    .ok()
+                    .and_then(|k| EncryptionKey::try_from(k).ok())
+                    .expect("The storage encryption key should be 32 hex encoded bytes"),
+            ),
+            Err(_) => None,
+        };
+        let http_trace_body_limit = match std::env::var("NAMADA_MPC_HTTP_TRACE_BODY_BYTES") {
+            Ok(l) => Some(l.parse::<usize>().unwrap()),
+            Err(_) => None,
+        };
+        let round_time_budget = match std::env::var("NAMADA_MPC_ROUND_TIME_BUDGET_SECONDS") {
+            Ok(t) => Some(time::Duration::seconds(t.parse::<i64>().unwrap())),
+            Err(_) => None,
+        };
+        let round_budget_policy = match std::env::var("NAMADA_MPC_ROUND_BUDGET_POLICY") {
+            Ok(p) if p == "skip" => RoundBudgetPolicy::Skip,
+            _ => RoundBudgetPolicy::Extend,
+        };
+        let max_pending_verifications = match std::env::var("NAMADA_MPC_MAX_PENDING_VERIFICATIONS") {
+            Ok(m) => Some(m.parse::<usize>().unwrap()),
+            Err(_) => None,
+        };
+        let contributor_identity_key = match std::env::var("NAMADA_MPC_CONTRIBUTOR_IDENTITY_KEY") {
+            Ok(k) => Some(
+                hex::decode(k)
+                    .ok()
+                    .and_then(|k| EncryptionKey::try_from(k).ok())
+                    .expect("The contributor identity key should be 32 hex encoded bytes"),
+            ),
+            Err(_) => None,
+        };
+        let read_only = match std::env::var("NAMADA_MPC_READ_ONLY") {
+            Ok(s) if s == "true" => true,
+            _ => false,
+        };
+        let ceremony_parameters = match std::env::var("NAMADA_MPC_CEREMONY_PARAMETERS") {
+            Ok(p) => p.parse()?,
+            Err(_) => CeremonyParameters::Masp,
+        };
+        let initial_parameters_path = std::env::var("NAMADA_MPC_INITIAL_PARAMETERS").ok();
+        if let (CeremonyParameters::Circuits(_), None) = (ceremony_parameters, &initial_parameters_path) {
+            return Err(ConfigError::Invalid(
+                "NAMADA_MPC_INITIAL_PARAMETERS must be set for a ceremony of other circuits".to_string(),
+            ));
+        }
+        let priority_tokens = match std::env::var("NAMADA_MPC_PRIORITY_TOKENS") {
+            Ok(tokens) => tokens
+                .split(',')
+                .map(str::trim)
+                .filter(|token| !token.is_empty())
+                .map(String::from)
+                .collect(),
+            Err(_) => HashSet::new(),
+        };
+        let aggregation_retries = match std::env::var("NAMADA_MPC_AGGREGATION_RETRIES") {
+            Ok(retries) => retries.parse().unwrap(),
+            Err(_) => 3,
+        };
 
-        Self {
+        Ok(Self {
             environment: Environment {
                 parameters: Parameters::Namada {
                     number_of_chunks: 1,
@@ -854,6 +1773,7 @@ impl Production {
                 contributor_lock_chunk_limit: 1,
                 verifier_lock_chunk_limit: 5,
                 contributor_seen_timeout: test_timeout.unwrap_or_else(|| time::Duration::seconds(150)),
+                contributor_stale_grace: stale_grace.unwrap_or_else(|| time::Duration::seconds(90)),
                 verifier_seen_timeout: test_timeout.unwrap_or_else(|| time::Duration::days(7)),
                 participant_lock_timeout: test_timeout.unwrap_or_else(|| time::Duration::minutes(20)),
                 queue_seen_timeout: test_timeout.unwrap_or_else(|| time::Duration::seconds(150)),
@@ -869,14 +1789,32 @@ impl Production {
                 software_version: 1,
                 deployment: Deployment::Production,
                 local_base_directory: "./transcript".to_string(),
+                round_webhook_url: std::env::var("NAMADA_MPC_ROUND_WEBHOOK_URL").ok(),
+                storage_batch_interval,
+                storage_encryption_key,
+                storage_archive_directory: std::env::var("NAMADA_MPC_STORAGE_ARCHIVE_DIR").ok(),
+                round_time_budget,
+                round_budget_policy,
+                max_pending_verifications,
+                contributor_allowlist_path: std::env::var("NAMADA_MPC_ALLOWLIST_PATH").ok(),
+                cohort_duration: None,
+                http_trace_body_limit,
+                contributor_identity_key,
+                read_only,
+                ceremony_parameters,
+                initial_parameters_path,
+                priority_tokens,
+                aggregation_retries,
+                aggregation_retry_backoff: time::Duration::seconds(5),
 
                 disable_reliability_zeroing: false,
             },
-        }
+        })
     }
 
-    /// Generate a new Production env with [`Parameters::Namada`] parameters
-    pub fn new(keypair: &KeyPair) -> Self {
+    /// Generate a new Production env with [`Parameters::Namada`] parameters, failing if the `NAMADA_MPC_*`
+    /// environment variables select invalid ceremony parameters.
+    pub fn new(keypair: &KeyPair) -> Result<Self, ConfigError> {
         Self::generate_namada_env(keypair)
     }
 }
@@ -901,7 +1839,7 @@ impl std::default::Default for Production {
     fn default() -> Self {
         let keypair = KeyPair::new();
 
-        Self::generate_namada_env(&keypair)
+        Self::generate_namada_env(&keypair).expect("Invalid ceremony parameters in the environment")
     }
 }
 
@@ -909,6 +1847,9 @@ impl std::default::Default for Production {
 mod tests {
     use crate::environment::*;
 
+    use serial_test::serial;
+    use std::io::Write;
+
     #[test]
     fn test_aleo_test_3_chunks() {
         let parameters = Parameters::Test3Chunks;
@@ -966,4 +1907,108 @@ mod tests {
         assert_eq!(ChunkSize::from(1639_usize), chunk_size);
         assert_eq!(number_of_chunks as u64, Testing::from(parameters).number_of_chunks());
     }
+
+    #[test]
+    #[serial]
+    fn test_environment_from_config_path() {
+        let keypair = KeyPair::new();
+        let mut config = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            config,
+            r#"
+[parameters]
+number_of_chunks = 1
+power = 8
+batch_size = 128
+
+[storage]
+base_directory = "./transcript/config"
+archive_directory = "./archive"
+batch_seconds = 30
+
+[timeouts]
+contributor_seen_seconds = 60
+contributor_stale_grace_seconds = 10
+participant_lock_seconds = 600
+queue_seen_seconds = 120
+round_time_budget_seconds = 3600
+
+[queue]
+minimum_contributors_per_round = 1
+maximum_contributors_per_round = 2
+wait_time_seconds = 15
+max_pending_verifications = 3
+allowlist_path = "./allowlist.json"
+
+[cohorts]
+duration_seconds = 7200
+"#
+        )
+        .unwrap();
+
+        // The environment variables take precedence over the file
+        std::env::set_var("NAMADA_MPC_MAX_PENDING_VERIFICATIONS", "7");
+        std::env::remove_var("NAMADA_COHORT_TIME");
+        let environment = Environment::from_config_path(config.path(), &keypair);
+        std::env::remove_var("NAMADA_MPC_MAX_PENDING_VERIFICATIONS");
+        let environment = environment.unwrap();
+
+        assert_eq!(Power::from(8_usize), environment.parameters().power());
+        assert_eq!(128, environment.parameters().batch_size());
+        assert_eq!("./transcript/config", environment.local_base_directory());
+        assert_eq!(Some("./archive"), environment.storage_archive_directory());
+        assert_eq!(Some(time::Duration::seconds(30)), environment.storage_batch_interval());
+        assert_eq!(time::Duration::seconds(60), environment.contributor_seen_timeout());
+        assert_eq!(time::Duration::seconds(10), environment.contributor_stale_grace());
+        assert_eq!(time::Duration::seconds(600), environment.participant_lock_timeout());
+        assert_eq!(time::Duration::seconds(120), environment.queue_seen_timeout());
+        assert_eq!(Some(time::Duration::hours(1)), environment.round_time_budget());
+        assert_eq!(1, environment.minimum_contributors_per_round());
+        assert_eq!(2, environment.maximum_contributors_per_round());
+        assert_eq!(15, environment.queue_wait_time());
+        assert_eq!(Some(7), environment.max_pending_verifications());
+        assert_eq!(Some("./allowlist.json"), environment.contributor_allowlist_path());
+        assert_eq!(Some(7200), environment.cohort_duration());
+
+        // Inconsistent and unknown entries are rejected
+        let mut config = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            config,
+            "[queue]\nminimum_contributors_per_round = 3\nmaximum_contributors_per_round = 2\n"
+        )
+        .unwrap();
+        assert!(matches!(
+            Environment::from_config_path(config.path(), &keypair),
+            Err(ConfigError::Invalid(_))
+        ));
+
+        let mut config = tempfile::NamedTempFile::new().unwrap();
+        write!(config, "[storage]\ntype = \"s3\"\n").unwrap();
+        assert!(matches!(
+            Environment::from_config_path(config.path(), &keypair),
+            Err(ConfigError::Parse(_))
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_ceremony_parameters_from_env() {
+        let keypair = KeyPair::new();
+
+        std::env::set_var("NAMADA_MPC_CEREMONY_PARAMETERS", "circuits:two");
+        let invalid = Production::new(&keypair);
+        // The initial parameters of other circuits are required
+        std::env::set_var("NAMADA_MPC_CEREMONY_PARAMETERS", "circuits:2");
+        let missing_initial_parameters = Production::new(&keypair);
+        std::env::set_var("NAMADA_MPC_INITIAL_PARAMETERS", "./initial.params");
+        let production = Production::new(&keypair);
+        std::env::remove_var("NAMADA_MPC_CEREMONY_PARAMETERS");
+        std::env::remove_var("NAMADA_MPC_INITIAL_PARAMETERS");
+
+        assert!(matches!(invalid, Err(ConfigError::Invalid(_))));
+        assert!(matches!(missing_initial_parameters, Err(ConfigError::Invalid(_))));
+        let production = production.unwrap();
+        assert_eq!(CeremonyParameters::Circuits(2), production.ceremony_parameters());
+        assert_eq!(Some("./initial.params"), production.initial_parameters_path());
+    }
 }
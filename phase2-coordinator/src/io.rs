@@ -1,4 +1,11 @@
-use std::{fmt::Display, io::Write, ops::Deref};
+use std::{
+    fmt::Display,
+    io::Write,
+    ops::Deref,
+    path::Path,
+    sync::{mpsc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
 
 #[cfg(not(debug_assertions))]
 use std::process;
@@ -8,16 +15,23 @@ use bip39::{Language, Mnemonic};
 use crossterm::{
     execute,
     terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+    tty::IsTty,
 };
+use once_cell::sync::Lazy;
 use owo_colors::OwoColorize;
 #[cfg(not(debug_assertions))]
 use rand::prelude::SliceRandom;
 use regex::Regex;
 use thiserror::Error;
 
-const COORDINATOR_MNEMONIC_FILE: &str = "coordinator.mnemonic";
+pub const COORDINATOR_MNEMONIC_FILE: &str = "coordinator.mnemonic";
+pub const CONTRIBUTOR_MNEMONIC_FILE: &str = "contributor.mnemonic";
 const MNEMONIC_LEN: usize = 24;
 
+/// The lines of stdin, shared by all the prompts.
+static STDIN: Lazy<Mutex<LineReader>> =
+    Lazy::new(|| Mutex::new(LineReader::new(|line| std::io::stdin().read_line(line))));
+
 #[derive(Debug, Error)]
 pub enum IOError {
     #[error("Wrong answer in mnemonic check")]
@@ -61,6 +75,64 @@ impl From<MnemonicWrap> for Mnemonic {
     }
 }
 
+/// Reads the lines of an input in a long-lived thread, one line per request. A prompt which times out leaves its
+/// request pending, so that the line typed afterwards goes to the next prompt rather than being lost.
+struct LineReader {
+    requests: mpsc::Sender<()>,
+    lines: mpsc::Receiver<std::io::Result<Option<String>>>,
+    /// Whether a line was requested and not received yet.
+    pending: bool,
+}
+
+impl LineReader {
+    /// Spawns the thread reading the input with `read_line`, which appends a line to the given buffer and returns the
+    /// number of bytes read, 0 at the end of the input.
+    fn new<F>(mut read_line: F) -> Self
+    where
+        F: FnMut(&mut String) -> std::io::Result<usize> + Send + 'static,
+    {
+        let (requests, request_receiver) = mpsc::channel();
+        let (line_sender, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for () in request_receiver {
+                let mut line = String::new();
+                let line = read_line(&mut line).map(|read| if read == 0 { None } else { Some(line) });
+                if line_sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Self {
+            requests,
+            lines,
+            pending: false,
+        }
+    }
+
+    /// Returns the next line of the input, or `None` at the end of the input or if no line is read within `timeout`.
+    fn read_line(&mut self, timeout: Option<Duration>) -> std::io::Result<Option<String>> {
+        if !self.pending {
+            if self.requests.send(()).is_err() {
+                return Ok(None);
+            }
+            self.pending = true;
+        }
+
+        let line = match timeout {
+            Some(timeout) => self.lines.recv_timeout(timeout).ok(),
+            None => self.lines.recv().ok(),
+        };
+        match line {
+            Some(line) => {
+                self.pending = false;
+                line
+            }
+            None => Ok(None),
+        }
+    }
+}
+
 impl Display for MnemonicWrap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         // Get longest word
@@ -107,13 +179,13 @@ pub fn get_user_input<S>(request: S, expected: Option<&Regex>) -> Result<String>
 where
     S: std::fmt::Display,
 {
-    let mut response = String::new();
+    let mut stdin = STDIN.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut response;
 
     loop {
         print!("{} ", request);
         std::io::stdout().flush()?;
-        std::io::stdin().read_line(&mut response)?;
-        response = response.trim().to_owned();
+        response = stdin.read_line(None)?.unwrap_or_default().trim().to_owned();
 
         match expected {
             Some(re) => {
@@ -131,6 +203,59 @@ where
     Ok(response)
 }
 
+/// Like [`get_user_input`], but returns `default` if no valid reply is typed within `timeout`, or right away if stdin
+/// is not a terminal, so that a prompt reached by a non-interactive run doesn't block it forever.
+pub fn get_user_input_or_default<S>(
+    request: S,
+    expected: Option<&Regex>,
+    default: &str,
+    timeout: Duration,
+) -> Result<String>
+where
+    S: std::fmt::Display,
+{
+    if !std::io::stdin().is_tty() {
+        println!("{} {}", request, default);
+        return Ok(default.to_owned());
+    }
+
+    let mut stdin = STDIN.lock().unwrap_or_else(PoisonError::into_inner);
+    read_reply_or_default(request, expected, default, timeout, &mut stdin)
+}
+
+/// Reads the reply to the request from the given input, falling back to `default` on timeout or end of input.
+fn read_reply_or_default<S>(
+    request: S,
+    expected: Option<&Regex>,
+    default: &str,
+    timeout: Duration,
+    input: &mut LineReader,
+) -> Result<String>
+where
+    S: std::fmt::Display,
+{
+    let deadline = Instant::now() + timeout;
+    loop {
+        print!("{} ", request);
+        std::io::stdout().flush()?;
+
+        let response = match input.read_line(Some(deadline.saturating_duration_since(Instant::now())))? {
+            Some(line) => line.trim().to_owned(),
+            None => {
+                println!("{}", default);
+                return Ok(default.to_owned());
+            }
+        };
+
+        match expected {
+            Some(re) if !re.is_match(response.as_str()) => {
+                println!("{}", "Invalid reply, please type a valid answer...".red().bold())
+            }
+            _ => return Ok(response),
+        }
+    }
+}
+
 /// Generates a seed from a string representing a mnemonic. This string is supposed to have the same format of the
 /// one produced by the fmt method of [MnemonicWrap]
 pub fn seed_from_string(input: &str) -> Result<[u8; 64]> {
@@ -152,7 +277,12 @@ pub fn seed_from_string(input: &str) -> Result<[u8; 64]> {
 
 /// Generates a new [`KeyPair`] from a mnemonic retrieved from the coordinator.mnemonic file in the current working directory.
 pub fn keypair_from_mnemonic() -> Result<KeyPair> {
-    let mnemonic_str = std::fs::read_to_string(COORDINATOR_MNEMONIC_FILE)?;
+    keypair_from_mnemonic_file(COORDINATOR_MNEMONIC_FILE)
+}
+
+/// Generates a new [`KeyPair`] from a mnemonic retrieved from the given file.
+pub fn keypair_from_mnemonic_file(path: impl AsRef<Path>) -> Result<KeyPair> {
+    let mnemonic_str = std::fs::read_to_string(path)?;
     let seed = seed_from_string(&mnemonic_str)?;
 
     Ok(KeyPair::try_from_seed(&seed)?)
@@ -260,7 +390,47 @@ fn check_mnemonic(mnemonic: &Mnemonic) -> Result<()> {
 
 #[cfg(test)]
 mod tests {
-    use super::seed_from_string;
+    use super::{read_reply_or_default, seed_from_string, LineReader};
+    use regex::Regex;
+    use std::{
+        io::{BufRead, Cursor},
+        sync::mpsc,
+        time::Duration,
+    };
+
+    #[test]
+    fn test_reply_or_default() {
+        let expected = Regex::new(r"^(?i)[yn]$").unwrap();
+        let timeout = Duration::from_secs(10);
+
+        // No input at all
+        let mut input = LineReader::new(|_| Ok(0));
+        let reply = read_reply_or_default("Continue? [y/n]", Some(&expected), "n", timeout, &mut input).unwrap();
+        assert_eq!("n", reply);
+
+        // Invalid replies are skipped
+        let mut lines = Cursor::new("maybe\n y \n");
+        let mut input = LineReader::new(move |line| lines.read_line(line));
+        let reply = read_reply_or_default("Continue? [y/n]", Some(&expected), "n", timeout, &mut input).unwrap();
+        assert_eq!("y", reply);
+
+        // A prompt which times out doesn't lose the line typed afterwards, it goes to the next prompt
+        let (sender, receiver) = mpsc::channel::<&str>();
+        let mut input = LineReader::new(move |line| {
+            let read = receiver.recv().unwrap_or_default();
+            line.push_str(read);
+            Ok(read.len())
+        });
+        let short_timeout = Duration::from_millis(100);
+        let reply = read_reply_or_default("Continue? [y/n]", Some(&expected), "n", short_timeout, &mut input).unwrap();
+        assert_eq!("n", reply);
+        sender.send("y\n").unwrap();
+        sender.send("n\n").unwrap();
+        let reply = read_reply_or_default("Continue? [y/n]", Some(&expected), "n", timeout, &mut input).unwrap();
+        assert_eq!("y", reply);
+        let reply = read_reply_or_default("Continue? [y/n]", Some(&expected), "y", timeout, &mut input).unwrap();
+        assert_eq!("n", reply);
+    }
 
     #[test]
     fn test_seed_from_string() {
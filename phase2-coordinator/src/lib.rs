@@ -51,6 +51,8 @@ pub mod io;
 pub mod objects;
 pub use objects::{ContributionFileSignature, ContributionState, Participant, Round};
 
+pub mod distribution;
+
 pub mod storage;
 
 pub mod rest;
@@ -58,6 +60,8 @@ pub mod rest_utils;
 
 pub mod s3;
 
+pub mod webhook;
+
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
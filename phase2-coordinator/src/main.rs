@@ -1,8 +1,12 @@
 use phase2_coordinator::{
     authentication::Production as ProductionSig,
+    distribution::{Distributor, S3Distributor},
+    environment::Environment,
     io::{self, KeyPairUser},
     rest,
-    rest_utils::{self, ResponseError, TOKENS_PATH, TOKENS_ZIP_FILE, UPDATE_TIME},
+    rest_utils::{
+        self, NonceStore, ResponseError, SignatureScheme, VerificationWorker, TOKENS_PATH, TOKENS_ZIP_FILE, UPDATE_TIME,
+    },
     s3::{S3Ctx, REGION},
     Coordinator,
 };
@@ -14,7 +18,7 @@ use phase2_coordinator::environment::Testing;
 use phase2_coordinator::environment::Production;
 
 use rocket::{
-    self, catchers, routes,
+    self, catchers,
     tokio::{
         self,
         sync::{
@@ -27,12 +31,35 @@ use rocket::{
 use anyhow::Result;
 use rand::Rng;
 use rusoto_ssm::{Ssm, SsmClient};
-use std::{convert::TryInto, io::Write, sync::Arc};
+use std::{convert::TryInto, io::Write, path::PathBuf, sync::Arc};
+use structopt::StructOpt;
 
 use tracing::{error, info, warn};
 
+/// Command line options of the coordinator
+#[derive(Debug, StructOpt)]
+#[structopt(name = "phase2-coordinator")]
+struct CoordinatorOpt {
+    /// TOML config file of the environment of the coordinator. The NAMADA_MPC_* env variables override its values
+    #[structopt(long, parse(from_os_str))]
+    config: Option<PathBuf>,
+}
+
+/// Waits for the shutdown signal of a read-only [`Coordinator`], which is neither updated nor verifies contributions.
+async fn wait_shutdown(mut recv: Receiver<bool>) -> Result<()> {
+    while !*recv.borrow() {
+        recv.changed().await?;
+    }
+
+    Ok(())
+}
+
 /// Periodically updates the [`Coordinator`]
 async fn update_coordinator(coordinator: Arc<RwLock<Coordinator>>, recv: Receiver<bool>) -> Result<()> {
+    if coordinator.read().await.environment().read_only() {
+        return wait_shutdown(recv).await;
+    }
+
     loop {
         tokio::time::sleep(UPDATE_TIME).await;
 
@@ -60,32 +87,21 @@ async fn update_coordinator(coordinator: Arc<RwLock<Coordinator>>, recv: Receive
     }
 }
 
-/// Periodically verifies the pending contributions. Pending contributions are added to the queue by the try_contribute function,
-/// no need to call an update on the coordinator.
-/// NOTE: a possible improvement could be to perform the verification when the try_contribute function gets called, allowing us to remove this task and
-/// speed up the verification process. This would also allow us to immediately provide to a client the state of validity of its contribution. This improvement could
-/// be possible because we only have one contribution per round and one verifier (the coordinator's one). To implement this logic though, it would require a major rework of the phase2_coordinator logic.
-async fn verify_contributions(coordinator: Arc<RwLock<Coordinator>>, recv: Receiver<bool>) -> Result<()> {
-    let s3_ctx = S3Ctx::new().await?;
-
-    loop {
-        tokio::time::sleep(UPDATE_TIME).await;
+/// Runs the [`VerificationWorker`], verifying the pending contributions whenever woken up by the `/verify` endpoint or
+/// periodically otherwise. Pending contributions are added to the queue by the try_contribute function, no need to call
+/// an update on the coordinator.
+async fn verify_contributions(
+    coordinator: Arc<RwLock<Coordinator>>,
+    worker: VerificationWorker,
+    recv: Receiver<bool>,
+) -> Result<()> {
+    if coordinator.read().await.environment().read_only() {
+        return wait_shutdown(recv).await;
+    }
 
-        info!("Verifying contributions...");
-        let start = std::time::Instant::now();
-        rest_utils::perform_verify_chunks(coordinator.clone(), &s3_ctx).await?;
-        info!(
-            "Verification of contributions completed in {:#?}. {:#?} to the next verification round...",
-            start.elapsed(),
-            UPDATE_TIME
-        );
+    let s3_ctx = S3Ctx::new().await?;
 
-        // Return if shutdown signal has been received on the channel
-        if *recv.borrow() {
-            info!("Received shutdown signal, exiting verify task");
-            return Ok(());
-        }
-    }
+    Ok(worker.run(coordinator, Some(s3_ctx), UPDATE_TIME, recv).await?)
 }
 
 /// Checks and prints the env variables of interest for the ceremony
@@ -148,6 +164,11 @@ async fn generate_secret() -> Result<()> {
 
 /// Perform the steps to finalize the ceremony state before shut down
 async fn finalize_ceremony(coordinator: Arc<RwLock<Coordinator>>) -> Result<()> {
+    if coordinator.read().await.environment().read_only() {
+        info!("Coordinator is read-only, leaving the ceremony state untouched");
+        return Ok(());
+    }
+
     info!("Performing last contribution verification (if any)...");
     if let Err(e) = rest_utils::perform_verify_chunks(coordinator.clone(), &S3Ctx::new().await?).await {
         // Log any error without interrupting the shutdown procedure
@@ -169,6 +190,7 @@ async fn finalize_ceremony(coordinator: Arc<RwLock<Coordinator>>) -> Result<()>
 /// Rocket main function using the [`tokio`] runtime
 #[rocket::main]
 pub async fn main() {
+    let opt = CoordinatorOpt::from_args();
     let tracing_enable_color = std::env::var("RUST_LOG_COLOR").is_ok();
     tracing_subscriber::fmt().with_ansi(tracing_enable_color).init();
     print_env!(
@@ -177,6 +199,14 @@ pub async fn main() {
         "AWS_REGION",
         "NAMADA_MPC_IP_BAN",
         "NAMADA_MPC_TIMEOUT_SECONDS",
+        "NAMADA_MPC_STALE_GRACE_SECONDS",
+        "NAMADA_MPC_ROUND_WEBHOOK_URL",
+        "NAMADA_MPC_STORAGE_BATCH_SECONDS",
+        "NAMADA_MPC_STORAGE_ARCHIVE_DIR",
+        "NAMADA_MPC_ROUND_TIME_BUDGET_SECONDS",
+        "NAMADA_MPC_ROUND_BUDGET_POLICY",
+        "NAMADA_MPC_MAX_PENDING_VERIFICATIONS",
+        "NAMADA_MPC_READ_ONLY",
         "HEALTH_PATH",
         "NAMADA_TOKENS_PATH",
         "CEREMONY_START_TIMESTAMP",
@@ -194,24 +224,32 @@ pub async fn main() {
         .unwrap()
         .expect("Error while generating the keypair");
 
-    #[cfg(debug_assertions)]
-    let environment: Testing = {
-        phase2_coordinator::testing::clear_test_storage(&Testing::default().into());
-        Testing::new(&keypair)
+    let environment: Environment = match opt.config {
+        Some(path) => Environment::from_config_path(&path, &keypair).expect("Error while loading the config file"),
+        #[cfg(debug_assertions)]
+        None => {
+            phase2_coordinator::testing::clear_test_storage(&Testing::default().into());
+            Testing::new(&keypair).into()
+        }
+        #[cfg(not(debug_assertions))]
+        None => Production::new(&keypair)
+            .expect("Error while loading the environment")
+            .into(),
     };
 
-    #[cfg(not(debug_assertions))]
-    let environment: Production = { Production::new(&keypair) };
-
     // Always download token files from S3 to check for updates
     download_tokens().await.expect("Error while retrieving tokens");
 
     // Initialize the coordinator
+    let signature_scheme: SignatureScheme = Arc::new(ProductionSig);
     let coordinator =
-        Coordinator::new(environment.into(), Arc::new(ProductionSig)).expect("Failed to instantiate coordinator");
+        Coordinator::new(environment, signature_scheme.clone()).expect("Failed to instantiate coordinator");
+    let http_trace_body_limit = coordinator.environment().http_trace_body_limit();
     let coordinator: Arc<RwLock<Coordinator>> = Arc::new(RwLock::new(coordinator));
     let up_coordinator = coordinator.clone();
     let verify_coordinator = coordinator.clone();
+    let verification_worker = VerificationWorker::default();
+    let distributor: Distributor = Arc::new(S3Distributor);
 
     let mut write_lock = coordinator.clone().write_owned().await;
 
@@ -220,55 +258,31 @@ pub async fn main() {
         .expect("Initialization task panicked");
 
     // Build Rocket REST server
-    #[cfg(debug_assertions)]
-    let routes = routes![
-        rest::join_queue,
-        rest::lock_chunk,
-        rest::get_challenge_url,
-        rest::get_contribution_url,
-        rest::contribute_chunk,
-        rest::update_coordinator,
-        rest::heartbeat,
-        rest::stop_coordinator,
-        rest::verify_chunks,
-        rest::get_contributor_queue_status,
-        rest::post_contribution_info,
-        rest::get_contributions_info,
-        rest::get_coordinator_state,
-        rest::get_healthcheck,
-        rest::update_cohorts,
-        rest::post_attestation
-    ];
-
-    #[cfg(not(debug_assertions))]
-    let routes = routes![
-        rest::join_queue,
-        rest::lock_chunk,
-        rest::get_challenge_url,
-        rest::get_contribution_url,
-        rest::contribute_chunk,
-        rest::heartbeat,
-        rest::stop_coordinator,
-        rest::get_contributor_queue_status,
-        rest::post_contribution_info,
-        rest::get_coordinator_state,
-        rest::get_healthcheck,
-        rest::update_cohorts,
-        rest::post_attestation
-    ];
-
-    let build_rocket = rocket::build().mount("/", routes).manage(coordinator.clone()).register(
-        "/",
-        catchers![
-            rest_utils::invalid_signature,
-            rest_utils::unauthorized,
-            rest_utils::missing_required_header,
-            rest_utils::io_error,
-            rest_utils::unprocessable_entity,
-            rest_utils::mismatching_checksum,
-            rest_utils::invalid_header
-        ],
-    );
+    let build_rocket = rocket::build()
+        .mount("/", rest::routes())
+        .attach(rest_utils::HttpTracer::new(http_trace_body_limit))
+        .manage(coordinator.clone())
+        .manage(signature_scheme)
+        .manage(distributor)
+        .manage(NonceStore::default())
+        .manage(verification_worker.clone())
+        .register(
+            "/",
+            catchers![
+                rest_utils::invalid_signature,
+                rest_utils::unauthorized,
+                rest_utils::missing_required_header,
+                rest_utils::io_error,
+                rest_utils::unprocessable_entity,
+                rest_utils::mismatching_checksum,
+                rest_utils::invalid_header,
+                rest_utils::replayed_request,
+                rest_utils::ceremony_not_started,
+                rest_utils::incompatible_protocol_version,
+                rest_utils::payload_too_large,
+                rest_utils::invalid_participant
+            ],
+        );
     let ignite_rocket = build_rocket.ignite().await.expect("Coordinator server didn't ignite");
 
     // Sleep until ceremony start time has been reached
@@ -302,8 +316,12 @@ pub async fn main() {
     // Spawn task to update the coordinator periodically
     let mut update_handle = rocket::tokio::spawn(update_coordinator(up_coordinator, rx.clone()));
 
-    // Spawn task to verify the contributions periodically
-    let mut verify_handle = rocket::tokio::spawn(verify_contributions(verify_coordinator, rx));
+    // Spawn the worker verifying the contributions
+    let mut verify_handle = rocket::tokio::spawn(verify_contributions(
+        verify_coordinator,
+        verification_worker.clone(),
+        rx,
+    ));
 
     // Spawn Rocket server task
     let mut rocket_handle = rocket::tokio::spawn(ignite_rocket.launch());
@@ -321,6 +339,7 @@ pub async fn main() {
                     // Cancel concurrent tasks
                     info!("Cancelling concurrent tasks...");
                     tx.send(true).expect("Error while sending shutdown notification to concurrent tasks, channel is already closed");
+                    verification_worker.wake();
                     shutdown.notify();
 
                     let (v_res, r_res) = tokio::join!(
@@ -358,6 +377,7 @@ pub async fn main() {
                     // Cancel concurrent tasks
                     info!("Cancelling concurrent tasks...");
                     tx.send(true).expect("Error while sending shutdown notification to concurrent tasks, channel is already closed");
+                    verification_worker.wake();
 
                     let (v_res, u_res) = tokio::join!(
                         verify_handle,
@@ -23,6 +23,8 @@ pub struct Chunk {
     /// including in other rounds, as is the case with the
     /// verification for the final contribution of a chunk for a given
     /// round, which is stored in the next round's directory.
+    ///
+    /// Ordered by contribution id, so that the chunk serializes deterministically.
     #[serde_diff(opaque)]
     contributions: BTreeMap<u64, Contribution>,
 }
@@ -404,7 +406,7 @@ impl Chunk {
     /// The underlying function checks that the contribution has a verifier assigned to it.
     ///
     #[tracing::instrument(
-        skip(self, verifier, contribution_id, verified_locator, verified_signature_locator),
+        skip(self, verifier, contribution_id, verified_locator, verified_signature_locator, verified_hash),
         fields(contribution = contribution_id)
     )]
     pub fn verify_contribution(
@@ -413,6 +415,7 @@ impl Chunk {
         verifier: Participant,
         verified_locator: LocatorPath,
         verified_signature_locator: LocatorPath,
+        verified_hash: String,
     ) -> Result<(), CoordinatorError> {
         // Check that the participant is a verifier.
         if !verifier.is_verifier() {
@@ -435,7 +438,7 @@ impl Chunk {
             // Case 2 - If the contribution is not verified, attempt to set it to verified.
             false => {
                 // Attempt set the contribution as verified.
-                contribution.set_verified(&verifier)?;
+                contribution.set_verified(&verifier, verified_hash)?;
 
                 // Release the lock on this chunk from the verifier.
                 self.set_lock_holder(None);
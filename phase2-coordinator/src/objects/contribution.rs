@@ -17,6 +17,9 @@ pub struct Contribution {
     #[serde(rename = "verifiedSignatureLocation")]
     verified_signature_locator: Option<LocatorPath>,
     verified: bool,
+    /// Hex encoded hash of the verified contribution file, recorded at verification to detect its corruption.
+    #[serde(default)]
+    verified_hash: Option<String>,
 }
 
 impl Contribution {
@@ -70,6 +73,13 @@ impl Contribution {
         &self.verified_signature_locator
     }
 
+    /// Returns the hex encoded hash of the verified contribution file,
+    /// if it was recorded at verification. Otherwise returns `None`.
+    #[inline]
+    pub fn get_verified_hash(&self) -> Option<&str> {
+        self.verified_hash.as_deref()
+    }
+
     ///
     /// Creates a new contributor instance of `Contribution`.
     ///
@@ -97,6 +107,7 @@ impl Contribution {
             verified_locator: None,
             verified_signature_locator: None,
             verified: false,
+            verified_hash: None,
         })
     }
 
@@ -137,6 +148,7 @@ impl Contribution {
             verified_locator: Some(verified_locator),
             verified_signature_locator: Some(verified_signature_locator),
             verified: true,
+            verified_hash: None,
         };
 
         Ok(contribution)
@@ -187,18 +199,23 @@ impl Contribution {
     }
 
     /// Updates `verified` to `true` in this instance of `Contribution`,
-    /// if the verifier ID and verified location are valid.
+    /// if the verifier ID and verified location are valid, and records
+    /// the hash of the verified contribution file.
     ///
     /// If this contribution has already been verified,
     /// returns a `CoordinatorError`.
     ///
     #[tracing::instrument(
         level = "error",
-        skip(self, participant),
+        skip(self, participant, verified_hash),
         fields(participant = %participant),
         err
     )]
-    pub(crate) fn set_verified(&mut self, participant: &Participant) -> Result<(), CoordinatorError> {
+    pub(crate) fn set_verified(
+        &mut self,
+        participant: &Participant,
+        verified_hash: String,
+    ) -> Result<(), CoordinatorError> {
         // Check that the participant is a verifier.
         if !participant.is_verifier() {
             return Err(CoordinatorError::ExpectedVerifier);
@@ -227,12 +244,12 @@ impl Contribution {
 
         trace!("Setting contribution to verified");
         self.verified = true;
+        self.verified_hash = Some(verified_hash);
         Ok(())
     }
 
     /// Get a list containing all the file locators associated with
     /// this contribution.
-    #[allow(dead_code)]
     pub(crate) fn get_locators(&self) -> Vec<LocatorPath> {
         let mut paths: Vec<LocatorPath> = Vec::new();
 
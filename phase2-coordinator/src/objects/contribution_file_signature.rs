@@ -1,5 +1,8 @@
+use crate::authentication::SignatureSchemeId;
 #[cfg(any(test, feature = "operator"))]
 use crate::coordinator::CoordinatorError;
+#[cfg(any(test, feature = "operator"))]
+use setup_utils::calculate_hash;
 
 use serde::{Deserialize, Serialize};
 use serde_diff::SerdeDiff;
@@ -62,6 +65,23 @@ impl ContributionState {
     pub fn signature_message(&self) -> Result<String, CoordinatorError> {
         Ok(serde_json::to_string(&self)?)
     }
+
+    /// Checks that the state refers to the given challenge and response files, i.e. that the participant signed the
+    /// files actually received.
+    #[inline]
+    pub fn validate_against(&self, challenge: &[u8], response: &[u8]) -> Result<(), CoordinatorError> {
+        self.validate_hashes(&calculate_hash(challenge), &calculate_hash(response))
+    }
+
+    /// Checks that the state refers to the challenge and response files with the given hashes.
+    #[inline]
+    pub fn validate_hashes(&self, challenge_hash: &[u8], response_hash: &[u8]) -> Result<(), CoordinatorError> {
+        if hex::decode(&self.challenge_hash)? != challenge_hash || hex::decode(&self.response_hash)? != response_hash {
+            return Err(CoordinatorError::ContributionStateMismatch);
+        }
+
+        Ok(())
+    }
 }
 
 ///
@@ -73,6 +93,10 @@ pub struct ContributionFileSignature {
     signature: String,
     /// The state of the contribution that is signed.
     state: ContributionState,
+    /// The scheme of the signature, the current one for the signatures predating it.
+    #[serde(default)]
+    #[serde_diff(opaque)]
+    scheme: SignatureSchemeId,
 }
 
 impl ContributionFileSignature {
@@ -86,7 +110,11 @@ impl ContributionFileSignature {
             return Err(CoordinatorError::ContributionSignatureSizeMismatch);
         }
         tracing::debug!("Completed creating contribution signature");
-        Ok(Self { signature, state })
+        Ok(Self {
+            signature,
+            state,
+            scheme: SignatureSchemeId::default(),
+        })
     }
 
     /// Returns a reference to the signature.
@@ -95,6 +123,12 @@ impl ContributionFileSignature {
         &self.signature
     }
 
+    /// Returns the scheme of the signature.
+    #[inline]
+    pub fn get_scheme(&self) -> SignatureSchemeId {
+        self.scheme
+    }
+
     /// Returns a reference to the contribution state.
     #[inline]
     pub fn get_state(&self) -> &ContributionState {
@@ -125,8 +159,6 @@ impl ContributionFileSignature {
 mod tests {
     use super::*;
 
-    use setup_utils::calculate_hash;
-
     #[test]
     pub fn test_contribution_signature() {
         // Construct the dummy challenge, response, and next_challenge files.
@@ -249,4 +281,48 @@ mod tests {
 
         assert!(contribution_state.is_err())
     }
+
+    #[test]
+    pub fn test_contribution_state_validate_against() {
+        // Construct the dummy challenge and response files.
+        let dummy_challenge = vec![1; 128];
+        let dummy_response = vec![2; 128];
+
+        // Construct the contribution state
+        let contribution_state = ContributionState::new(
+            calculate_hash(&dummy_challenge).to_vec(),
+            calculate_hash(&dummy_response).to_vec(),
+            None,
+        )
+        .unwrap();
+
+        assert!(contribution_state
+            .validate_against(&dummy_challenge, &dummy_response)
+            .is_ok());
+    }
+
+    #[test]
+    pub fn test_contribution_state_validate_against_mismatch() {
+        // Construct the dummy challenge and response files.
+        let dummy_challenge = vec![1; 128];
+        let dummy_response = vec![2; 128];
+        let other_response = vec![5; 128];
+
+        // Construct the contribution state signing another response file
+        let contribution_state = ContributionState::new(
+            calculate_hash(&dummy_challenge).to_vec(),
+            calculate_hash(&other_response).to_vec(),
+            None,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            contribution_state.validate_against(&dummy_challenge, &dummy_response),
+            Err(CoordinatorError::ContributionStateMismatch)
+        ));
+        assert!(matches!(
+            contribution_state.validate_against(&dummy_response, &other_response),
+            Err(CoordinatorError::ContributionStateMismatch)
+        ));
+    }
 }
@@ -1,12 +1,15 @@
-use crate::authentication::{KeyPair, Production, Signature};
+use crate::authentication::{KeyPair, SignatureSchemeId};
 
 use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum ContributionInfoError {
+    #[error("Invalid memo: {0}")]
+    InvalidMemo(String),
     #[error("Keypair doesn't match the pubkey")]
     InvalidSigKey,
     #[error("Error while serializing ContributionInfo: {0}")]
@@ -17,6 +20,31 @@ pub enum ContributionInfoError {
     UnexpectedSerializationFormat,
 }
 
+/// Maximum length, in characters, of the memo of a contribution.
+pub const MAX_MEMO_LENGTH: usize = 280;
+
+/// Removes the control characters and the surrounding whitespaces from a memo and truncates it to
+/// [`MAX_MEMO_LENGTH`] characters.
+pub fn sanitize_memo(memo: &str) -> String {
+    memo.chars()
+        .filter(|c| !c.is_control())
+        .collect::<String>()
+        .trim()
+        .chars()
+        .take(MAX_MEMO_LENGTH)
+        .collect()
+}
+
+/// Returns the hex encoded HMAC of an email, ignoring its case and surrounding whitespaces, so that the same email
+/// always maps to the same hash. The secret `key` of the coordinator prevents recovering the emails by hashing a list
+/// of known addresses.
+pub fn hash_email(key: &[u8], email: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(email.trim().to_lowercase().as_bytes());
+
+    hex::encode(mac.finalize().into_bytes())
+}
+
 /// Timestamps of the contribution
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ContributionTimeStamps {
@@ -96,6 +124,14 @@ pub struct ContributionInfo {
     pub contribution_file_signature: String,
     /// Url providing an attestation of the contribution
     pub attestation: Option<String>,
+    /// Short public note of the contributor, signed with the rest of the info. Omitted when missing to keep the
+    /// signatures of the infos predating it valid
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    /// Scheme of the signatures of the contributor, signed with the rest of the info. Omitted for the current scheme
+    /// to keep the signatures of the infos predating it valid
+    #[serde(default, skip_serializing_if = "SignatureSchemeId::is_current")]
+    pub signature_scheme: SignatureSchemeId,
     // Some timestamps to get performance metrics of the ceremony
     pub timestamps: ContributionTimeStamps,
     // Signature of this struct, computed on the json string encoding of all the other fields of this struct
@@ -132,7 +168,10 @@ impl ContributionInfo {
             return Err(ContributionInfoError::InvalidSigKey);
         }
 
-        let contrib_info_signature = Production
+        let signer = self.signature_scheme.signer().ok_or_else(|| {
+            ContributionInfoError::SignatureError(format!("Unknown signature scheme {:?}", self.signature_scheme))
+        })?;
+        let contrib_info_signature = signer
             .sign(keypair.sigkey(), digest.as_str())
             .map_err(|e| ContributionInfoError::SignatureError(format!("{}", e)))?;
         self.contributor_info_signature = contrib_info_signature;
@@ -140,12 +179,45 @@ impl ContributionInfo {
         Ok(())
     }
 
-    /// Verifies the signature.
-    #[cfg(test)]
-    fn verify_signature(&self) -> Result<bool, ContributionInfoError> {
+    /// Checks that the memo, if any, is not longer than [`MAX_MEMO_LENGTH`] and has been sanitized. The memo is
+    /// signed, so it must be sanitized by the contributor before signing.
+    pub fn validate_memo(&self) -> Result<(), ContributionInfoError> {
+        if let Some(memo) = &self.memo {
+            if memo.chars().count() > MAX_MEMO_LENGTH {
+                return Err(ContributionInfoError::InvalidMemo(format!(
+                    "longer than {} characters",
+                    MAX_MEMO_LENGTH
+                )));
+            }
+            if *memo != sanitize_memo(memo) {
+                return Err(ContributionInfoError::InvalidMemo(
+                    "contains control characters or surrounding whitespaces".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verifies the signature with its scheme. A malformed public key or signature, or an unknown scheme, is reported
+    /// as an invalid signature.
+    pub fn verify_signature(&self) -> Result<bool, ContributionInfoError> {
         let serialized_contrib_info = self.hash_for_signature()?;
 
-        Ok(Production.verify(
+        let signer = match self.signature_scheme.signer() {
+            Some(signer) => signer,
+            None => return Ok(false),
+        };
+
+        // The signature scheme expects well formed keys and signatures
+        let is_well_formed = |value: &str, len: usize| hex::decode(value).map_or(false, |bytes| bytes.len() == len);
+        if !is_well_formed(&self.public_key, ed25519_compact::PublicKey::BYTES)
+            || !is_well_formed(&self.contributor_info_signature, ed25519_compact::Signature::BYTES)
+        {
+            return Ok(false);
+        }
+
+        Ok(signer.verify(
             self.public_key.as_str(),
             serialized_contrib_info.as_str(),
             self.contributor_info_signature.as_str(),
@@ -157,6 +229,9 @@ impl ContributionInfo {
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct TrimmedContributionInfo {
     full_name: Option<String>,
+    /// Hash of the email of the contributor, see [`hash_email`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    email_hash: Option<String>,
     public_key: String,
     is_another_machine: bool,
     is_own_seed_of_randomness: bool,
@@ -165,6 +240,7 @@ pub struct TrimmedContributionInfo {
     contribution_hash: String,
     contribution_hash_signature: String,
     attestation: Option<String>,
+    memo: Option<String>,
     timestamps: TrimmedContributionTimeStamps,
 }
 
@@ -172,6 +248,7 @@ impl From<ContributionInfo> for TrimmedContributionInfo {
     fn from(parent: ContributionInfo) -> Self {
         Self {
             full_name: parent.full_name,
+            email_hash: None,
             public_key: parent.public_key,
             is_another_machine: parent.is_another_machine,
             is_own_seed_of_randomness: parent.is_own_seed_of_randomness,
@@ -180,20 +257,51 @@ impl From<ContributionInfo> for TrimmedContributionInfo {
             contribution_hash: parent.contribution_file_hash,
             contribution_hash_signature: parent.contribution_file_signature,
             attestation: parent.attestation,
+            memo: parent.memo,
             timestamps: parent.timestamps.into(),
         }
     }
 }
 
 impl TrimmedContributionInfo {
+    pub fn full_name(&self) -> Option<&str> {
+        self.full_name.as_deref()
+    }
+
+    pub fn email_hash(&self) -> Option<&str> {
+        self.email_hash.as_deref()
+    }
+
+    /// Sets the hash of the email of the contributor, see [`hash_email`].
+    pub fn with_email_hash(mut self, email_hash: Option<String>) -> Self {
+        self.email_hash = email_hash;
+        self
+    }
+
     pub fn public_key(&self) -> &str {
         self.public_key.as_ref()
     }
 
+    /// Returns the info to publish. When `redact_identity` is set, the name of the contributor is omitted and only
+    /// the hash of its email identifies it, otherwise only the name is published.
+    pub fn into_public(mut self, redact_identity: bool) -> Self {
+        if redact_identity {
+            self.full_name = None;
+        } else {
+            self.email_hash = None;
+        }
+
+        self
+    }
+
     pub fn ceremony_round(&self) -> u64 {
         self.ceremony_round
     }
 
+    pub fn memo(&self) -> Option<&str> {
+        self.memo.as_deref()
+    }
+
     #[cfg(debug_assertions)]
     pub fn is_another_machine(&self) -> bool {
         self.is_another_machine
@@ -207,9 +315,9 @@ impl TrimmedContributionInfo {
 
 #[cfg(test)]
 mod tests {
-    use crate::authentication::KeyPair;
+    use crate::authentication::{KeyPair, SignatureSchemeId};
 
-    use super::ContributionInfo;
+    use super::{sanitize_memo, ContributionInfo, MAX_MEMO_LENGTH};
 
     #[test]
     fn sign_and_verify() {
@@ -232,5 +340,64 @@ mod tests {
 
         test_info.try_sign(&keypair).unwrap();
         assert!(test_info.verify_signature().unwrap());
+
+        // Forged: signed by another keypair
+        let other_keypair = KeyPair::new();
+        test_info.public_key = other_keypair.pubkey().to_owned();
+        test_info.try_sign(&other_keypair).unwrap();
+        test_info.public_key = keypair.pubkey().to_owned();
+        assert!(!test_info.verify_signature().unwrap());
+
+        // Missing signature
+        test_info.contributor_info_signature = String::new();
+        assert!(!test_info.verify_signature().unwrap());
+    }
+
+    #[test]
+    fn signed_memo() {
+        let keypair = KeyPair::new();
+        let mut test_info = ContributionInfo::default();
+        test_info.public_key = keypair.pubkey().to_owned();
+        test_info.memo = Some(sanitize_memo("  Contributed from my laptop\u{7}, offline\n"));
+        assert_eq!(Some("Contributed from my laptop, offline"), test_info.memo.as_deref());
+        test_info.validate_memo().unwrap();
+
+        test_info.try_sign(&keypair).unwrap();
+        let read_back: ContributionInfo = serde_json::from_str(&serde_json::to_string(&test_info).unwrap()).unwrap();
+        assert_eq!(test_info.memo, read_back.memo);
+        assert!(read_back.verify_signature().unwrap());
+
+        // The memo is covered by the signature
+        let mut tampered = read_back.clone();
+        tampered.memo = Some(String::from("Tampered memo"));
+        assert!(!tampered.verify_signature().unwrap());
+
+        // Unsanitized or too long memos are rejected
+        tampered.memo = Some(String::from("Memo\r\n"));
+        assert!(tampered.validate_memo().is_err());
+        tampered.memo = Some("a".repeat(MAX_MEMO_LENGTH + 1));
+        assert!(tampered.validate_memo().is_err());
+        assert_eq!(MAX_MEMO_LENGTH, sanitize_memo(&"a".repeat(MAX_MEMO_LENGTH + 1)).len());
+    }
+
+    #[test]
+    fn signature_scheme() {
+        let keypair = KeyPair::new();
+        let mut test_info = ContributionInfo::default();
+        test_info.public_key = keypair.pubkey().to_owned();
+        test_info.try_sign(&keypair).unwrap();
+
+        // Infos of the current scheme are verified, and read back as such when the scheme is omitted
+        let mut serialized = serde_json::to_value(&test_info).unwrap();
+        assert!(serialized.get("signature_scheme").is_none());
+        let read_back: ContributionInfo = serde_json::from_value(serialized.clone()).unwrap();
+        assert_eq!(SignatureSchemeId::ED25519, read_back.signature_scheme);
+        assert!(read_back.verify_signature().unwrap());
+
+        // Infos tagged with an unknown scheme are rejected
+        serialized["signature_scheme"] = serde_json::json!(42);
+        let unknown: ContributionInfo = serde_json::from_value(serialized).unwrap();
+        assert!(unknown.signature_scheme.signer().is_none());
+        assert!(!unknown.verify_signature().unwrap());
     }
 }
@@ -0,0 +1,30 @@
+use std::net::IpAddr;
+
+use serde::{Deserialize, Serialize};
+
+/// The client a contributor joined the ceremony from.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientProvenance {
+    /// The ip address of the request to join the queue.
+    pub ip_address: Option<IpAddr>,
+    /// The `User-Agent` header of the request to join the queue.
+    pub user_agent: Option<String>,
+    /// The protocol version announced by the client.
+    pub client_version: Option<String>,
+}
+
+/// The provenance of a contribution, recorded privately for abuse investigation. Unlike the
+/// [`ContributionInfo`](super::ContributionInfo), it's never published and only reachable via the admin endpoints.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContributionProvenance {
+    /// The public key of the contributor.
+    pub public_key: String,
+    /// The round of the contribution.
+    pub ceremony_round: u64,
+    /// The chunk of the contribution.
+    pub chunk_id: u64,
+    /// The id of the contribution in its chunk.
+    pub contribution_id: u64,
+    /// The client the contributor joined the ceremony from.
+    pub client: ClientProvenance,
+}
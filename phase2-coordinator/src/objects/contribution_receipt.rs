@@ -0,0 +1,83 @@
+use crate::authentication::{Production, Signature};
+
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+
+/// The receipt of a contribution accepted by the coordinator, signed with the coordinator keypair. It's returned to the
+/// contributor as an independent proof of their participation in the ceremony.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContributionReceipt {
+    /// The round of the contribution.
+    pub round_height: u64,
+    /// The id of the contribution in its round.
+    pub contribution_id: u64,
+    /// The hex encoded hash of the contribution file.
+    pub contribution_hash: String,
+    /// The time at which the coordinator accepted the contribution.
+    #[serde(with = "time::serde::timestamp")]
+    pub accepted_at: OffsetDateTime,
+    /// The signature of the receipt by the coordinator, computed on the json encoding of all the other fields.
+    pub coordinator_signature: String,
+}
+
+impl ContributionReceipt {
+    /// Returns the message signed by the coordinator: the json encoding of the receipt, except for the signature
+    /// itself.
+    pub fn signature_message(&self) -> Result<String, serde_json::Error> {
+        let mut receipt = serde_json::to_value(self)?;
+        if let Some(map) = receipt.as_object_mut() {
+            map.remove("coordinator_signature");
+        }
+
+        Ok(receipt.to_string())
+    }
+
+    /// Verifies the signature of the receipt against the public key of the coordinator. A malformed public key or
+    /// signature is reported as an invalid signature.
+    pub fn verify(&self, coordinator_pubkey: &str) -> bool {
+        let is_well_formed = |value: &str, len: usize| hex::decode(value).map_or(false, |bytes| bytes.len() == len);
+        if !is_well_formed(coordinator_pubkey, ed25519_compact::PublicKey::BYTES)
+            || !is_well_formed(&self.coordinator_signature, ed25519_compact::Signature::BYTES)
+        {
+            return false;
+        }
+
+        match self.signature_message() {
+            Ok(message) => Production.verify(coordinator_pubkey, &message, &self.coordinator_signature),
+            Err(_) => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::KeyPair;
+
+    #[test]
+    fn test_contribution_receipt_signature() {
+        let coordinator_keypair = KeyPair::new();
+        let mut receipt = ContributionReceipt {
+            round_height: 3,
+            contribution_id: 1,
+            contribution_hash: hex::encode([7u8; 64]),
+            accepted_at: OffsetDateTime::from_unix_timestamp(1_660_000_000).unwrap(),
+            coordinator_signature: String::new(),
+        };
+        receipt.coordinator_signature = Production
+            .sign(coordinator_keypair.sigkey(), &receipt.signature_message().unwrap())
+            .unwrap();
+        assert!(receipt.verify(coordinator_keypair.pubkey()));
+
+        // The receipt read back by the contributor still verifies
+        let read_back: ContributionReceipt = serde_json::from_slice(&serde_json::to_vec(&receipt).unwrap()).unwrap();
+        assert!(read_back.verify(coordinator_keypair.pubkey()));
+
+        // Altered receipts or other keys are rejected
+        let mut tampered = read_back.clone();
+        tampered.round_height = 4;
+        assert!(!tampered.verify(coordinator_keypair.pubkey()));
+        assert!(!read_back.verify(KeyPair::new().pubkey()));
+        assert!(!read_back.verify("not a public key"));
+    }
+}
@@ -10,6 +10,12 @@ pub use contribution_file_signature::*;
 pub mod contribution_info;
 pub use contribution_info::*;
 
+pub mod contribution_provenance;
+pub use contribution_provenance::*;
+
+pub mod contribution_receipt;
+pub use contribution_receipt::*;
+
 pub mod participant;
 pub use participant::*;
 
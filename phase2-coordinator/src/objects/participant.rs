@@ -1,10 +1,14 @@
-use itertools::Itertools;
+use crate::CoordinatorError;
+
 use serde::{
     de::{Deserializer, Error},
     Deserialize, Serialize, Serializer,
 };
 use serde_diff::SerdeDiff;
-use std::fmt::{self};
+use std::{
+    fmt::{self},
+    str::FromStr,
+};
 
 pub type ContributorId = String;
 pub type VerifierId = String;
@@ -21,6 +25,17 @@ pub enum Participant {
     Verifier(VerifierId),
 }
 
+/// The role of a [Participant] in the current round of the ceremony.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParticipantRole {
+    /// An authorized contributor of the current round.
+    Contributor,
+    /// An authorized verifier of the current round.
+    Verifier,
+    /// A participant with no role in the current round.
+    Unauthorized,
+}
+
 impl Participant {
     /// Creates a new contributor instance of `Participant`.
     pub fn new_contributor(participant: &str) -> Self {
@@ -54,6 +69,15 @@ impl Participant {
             Participant::Verifier(a) => a.to_string(),
         }
     }
+
+    /// Checks that the given public key, identifying a participant, is a well-formed [`hex`] encoded ed25519 public
+    /// key. The encoding must be the canonical lowercase one, so that a participant has a single identity.
+    pub fn validate_pubkey(pubkey: &str) -> Result<(), CoordinatorError> {
+        match hex::decode(pubkey) {
+            Ok(bytes) if bytes.len() == ed25519_compact::PublicKey::BYTES && hex::encode(&bytes) == pubkey => Ok(()),
+            _ => Err(CoordinatorError::ParticipantInvalid(pubkey.to_owned())),
+        }
+    }
 }
 
 impl fmt::Display for Participant {
@@ -87,16 +111,74 @@ impl<'de> Deserialize<'de> for Participant {
     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Participant, D::Error> {
         let s = String::deserialize(deserializer)?;
 
+        s.parse().map_err(|_| D::Error::custom("unknown participant type"))
+    }
+}
+
+/// Parses a participant from its [`Display`](fmt::Display) representation, the id followed by the role, e.g.
+/// `<pubkey>.contributor`.
+impl FromStr for Participant {
+    type Err = CoordinatorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // The role is the last component, the id may contain dots
         let (id, participant_type) = s
-            .splitn(2, ".")
-            .collect_tuple()
-            .ok_or(D::Error::custom("unknown participant type"))?;
-        let participant = match participant_type {
-            "contributor" => Participant::Contributor(id.to_string()),
-            "verifier" => Participant::Verifier(id.to_string()),
-            _ => return Err(D::Error::custom("unknown participant type")),
-        };
-
-        Ok(participant)
+            .rsplit_once('.')
+            .ok_or_else(|| CoordinatorError::ParticipantInvalid(s.to_owned()))?;
+
+        match participant_type {
+            "contributor" => Ok(Participant::Contributor(id.to_string())),
+            "verifier" => Ok(Participant::Verifier(id.to_string())),
+            _ => Err(CoordinatorError::ParticipantInvalid(s.to_owned())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authentication::KeyPair;
+
+    #[test]
+    fn test_participant_round_trip() {
+        let pubkey = KeyPair::new().pubkey().to_owned();
+        for participant in [
+            Participant::new_contributor(&pubkey),
+            Participant::new_verifier(&pubkey),
+            Participant::new_contributor("id.with.dots"),
+        ] {
+            assert_eq!(participant, participant.to_string().parse().unwrap());
+            assert_eq!(
+                participant,
+                serde_json::from_str(&serde_json::to_string(&participant).unwrap()).unwrap()
+            );
+        }
+
+        for malformed in ["no_role", "id.unknown_role"] {
+            assert!(matches!(
+                malformed.parse::<Participant>(),
+                Err(CoordinatorError::ParticipantInvalid(s)) if s == malformed
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_pubkey() {
+        assert!(Participant::validate_pubkey(KeyPair::new().pubkey()).is_ok());
+
+        let truncated = &KeyPair::new().pubkey()[..62];
+        let uppercase = KeyPair::new().pubkey().to_uppercase();
+        for malformed in [
+            "",
+            "not_hex",
+            truncated,
+            &format!("{}00", KeyPair::new().pubkey()),
+            &uppercase,
+        ] {
+            assert!(matches!(
+                Participant::validate_pubkey(malformed),
+                Err(CoordinatorError::ParticipantInvalid(_))
+            ));
+        }
     }
 }
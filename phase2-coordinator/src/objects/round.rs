@@ -57,8 +57,40 @@ impl LockedLocators {
     pub fn next_contribution_file_signature(&self) -> ContributionSignatureLocator {
         self.next_contribution_file_signature
     }
+
+    /// Checks that the round height supplied by a client is the one of the locked chunk.
+    pub fn check_round_height(&self, round_height: u64) -> Result<(), CoordinatorError> {
+        match self.next_contribution.round_height() == round_height {
+            true => Ok(()),
+            false => Err(CoordinatorError::UnauthorizedChunkContributor),
+        }
+    }
+
+    /// Checks that the contribution locator supplied by a client is the one of the next contribution.
+    pub fn check_next_contribution(&self, locator: &ContributionLocator) -> Result<(), CoordinatorError> {
+        match self.next_contribution == *locator {
+            true => Ok(()),
+            false => Err(CoordinatorError::UnauthorizedChunkContributor),
+        }
+    }
+
+    /// Checks that the signature locator supplied by a client is the one of the next contribution.
+    pub fn check_next_contribution_file_signature(
+        &self,
+        locator: &ContributionSignatureLocator,
+    ) -> Result<(), CoordinatorError> {
+        match self.next_contribution_file_signature == *locator {
+            true => Ok(()),
+            false => Err(CoordinatorError::UnauthorizedChunkContributor),
+        }
+    }
 }
 
+/// The state of a round, as recorded in storage.
+///
+/// NOTE: the serialization of a round must be byte-for-byte reproducible, so that the hashes of the stored rounds can
+/// be audited. The fields are serialized in declaration order, so the round and its chunks and contributions must
+/// only hold ordered collections (`Vec`, `BTreeMap`), never hash maps.
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, SerdeDiff)]
 #[serde(rename_all = "camelCase")]
 pub struct Round {
@@ -75,6 +107,12 @@ pub struct Round {
     contributor_ids: Vec<Participant>,
     verifier_ids: Vec<Participant>,
     chunks: Vec<Chunk>,
+    /// Hex encoded hash of the verified aggregated transcript, published once the round is aggregated.
+    #[serde(default)]
+    aggregate_hash: Option<String>,
+    /// Whether the round was skipped without contributions, its initial challenge is carried over to the next round.
+    #[serde(default)]
+    skipped: bool,
 }
 
 impl Round {
@@ -159,6 +197,8 @@ impl Round {
             contributor_ids,
             verifier_ids: vec![],
             chunks,
+            aggregate_hash: None,
+            skipped: false,
         })
     }
 
@@ -174,6 +214,36 @@ impl Round {
         self.height
     }
 
+    /// Returns the time at which the round started, if any.
+    #[inline]
+    pub fn started_at(&self) -> Option<OffsetDateTime> {
+        self.started_at
+    }
+
+    /// Returns the hex encoded hash of the verified aggregated transcript, if the round has been aggregated.
+    #[inline]
+    pub fn aggregate_hash(&self) -> Option<&str> {
+        self.aggregate_hash.as_deref()
+    }
+
+    /// Sets the hex encoded hash of the verified aggregated transcript.
+    #[inline]
+    pub(crate) fn set_aggregate_hash(&mut self, aggregate_hash: String) {
+        self.aggregate_hash = Some(aggregate_hash);
+    }
+
+    /// Returns `true` if the round was skipped without contributions.
+    #[inline]
+    pub fn is_skipped(&self) -> bool {
+        self.skipped
+    }
+
+    /// Marks the round as skipped.
+    #[inline]
+    pub(crate) fn set_skipped(&mut self) {
+        self.skipped = true;
+    }
+
     /// Returns the number of contributors authorized for this round.
     #[inline]
     pub fn number_of_contributors(&self) -> u64 {
@@ -215,6 +285,23 @@ impl Round {
         }
     }
 
+    ///
+    /// Returns `true` if the given participant is authorized as a
+    /// verifier and listed in the verifier IDs for this round.
+    ///
+    /// If the participant is not a verifier, or if there are
+    /// no prior rounds, returns `false`.
+    ///
+    #[inline]
+    pub fn is_verifier(&self, participant: &Participant) -> bool {
+        // Check that the participant is a verifier.
+        match participant {
+            // Check that the participant is a verifier for the given round height.
+            Participant::Verifier(_) => self.verifier_ids.contains(participant),
+            Participant::Contributor(_) => false,
+        }
+    }
+
     ///
     /// Returns a reference to the chunk, if it exists.
     /// Otherwise returns `None`.
@@ -538,33 +625,8 @@ impl Round {
                     return Err(CoordinatorError::UnauthorizedChunkContributor);
                 }
 
-                // Fetch the current round height.
-                let current_round_height = self.round_height();
-                // Fetch the current contribution ID.
-                let current_contribution_id = self.chunk(chunk_id)?.current_contribution_id();
-                // Fetch if this is the first round.
-                let is_initial_round = current_round_height == 1;
-                // Fetch if this is the initial contribution.
-                let is_initial_contribution = current_contribution_id == 0;
-                // Fetch the final contribution ID from the previous round.
-                let previous_final_id = self.expected_number_of_contributions() - 1;
-                // Fetch the previous contribution locator.
-                let previous_contribution = match (is_initial_round, is_initial_contribution) {
-                    // This is the initial contribution in the initial round, return the verified response from the previous round.
-                    (true, true) => ContributionLocator::new(0, chunk_id, 0, true),
-                    // This is the initial contribution in the chunk, return the final response from the previous round.
-                    (false, true) => {
-                        ContributionLocator::new(current_round_height - 1, chunk_id, previous_final_id, false)
-                    }
-                    // This is a typical contribution in the chunk, return the previous response from this round.
-                    (true, false) | (false, false) => {
-                        ContributionLocator::new(current_round_height, chunk_id, current_contribution_id - 1, false)
-                    }
-                };
-
-                // Fetch the current contribution locator.
-                let current_contribution =
-                    ContributionLocator::new(current_round_height, chunk_id, current_contribution_id, true);
+                // Fetch the previous and current contribution locators.
+                let (previous_contribution, current_contribution) = self.locked_contribution_locators(chunk_id)?;
 
                 // This call enforces a strict check that the
                 // next contribution locator does NOT exist and
@@ -614,10 +676,11 @@ impl Round {
                 // Initialize the unverified response file.
                 storage.initialize(
                     Locator::ContributionFile(locked_locators.next_contribution.clone()),
-                    Object::anoma_contribution_file_size(
+                    Object::ceremony_contribution_file_size(
+                        environment,
                         locked_locators.next_contribution.round_height(),
                         locked_locators.next_contribution.contribution_id(),
-                    ),
+                    )?,
                 )?;
 
                 // Initialize the contribution file signature.
@@ -635,10 +698,76 @@ impl Round {
         Ok(locked_locators)
     }
 
+    /// Returns the previous and current contribution locators handed to
+    /// the contributor locking the given chunk ID.
+    fn locked_contribution_locators(
+        &self,
+        chunk_id: u64,
+    ) -> Result<(ContributionLocator, ContributionLocator), CoordinatorError> {
+        // Fetch the current round height.
+        let current_round_height = self.round_height();
+        // Fetch the current contribution ID.
+        let current_contribution_id = self.chunk(chunk_id)?.current_contribution_id();
+        // Fetch if this is the first round.
+        let is_initial_round = current_round_height == 1;
+        // Fetch if this is the initial contribution.
+        let is_initial_contribution = current_contribution_id == 0;
+        // Fetch the final contribution ID from the previous round.
+        let previous_final_id = self.expected_number_of_contributions() - 1;
+        // Fetch the previous contribution locator.
+        let previous_contribution = match (is_initial_round, is_initial_contribution) {
+            // This is the initial contribution in the initial round, return the verified response from the previous round.
+            (true, true) => ContributionLocator::new(0, chunk_id, 0, true),
+            // This is the initial contribution in the chunk, return the final response from the previous round.
+            (false, true) => ContributionLocator::new(current_round_height - 1, chunk_id, previous_final_id, false),
+            // This is a typical contribution in the chunk, return the previous response from this round.
+            (true, false) | (false, false) => {
+                ContributionLocator::new(current_round_height, chunk_id, current_contribution_id - 1, false)
+            }
+        };
+
+        // Fetch the current contribution locator.
+        let current_contribution =
+            ContributionLocator::new(current_round_height, chunk_id, current_contribution_id, true);
+
+        Ok((previous_contribution, current_contribution))
+    }
+
+    ///
+    /// Returns the chunk ID and the locators of the chunk locked by the
+    /// given contributor, as returned when the lock was acquired, or `None`
+    /// if the contributor holds no lock.
+    ///
+    pub(crate) fn locked_locators(
+        &self,
+        participant: &Participant,
+    ) -> Result<Option<(u64, LockedLocators)>, CoordinatorError> {
+        let chunk = match self.chunks.iter().find(|chunk| chunk.is_locked_by(participant)) {
+            Some(chunk) => chunk,
+            None => return Ok(None),
+        };
+        let chunk_id = chunk.chunk_id();
+
+        let (previous_contribution, current_contribution) = self.locked_contribution_locators(chunk_id)?;
+        let next_contribution_id = chunk.next_contribution_id(self.expected_number_of_contributions())?;
+
+        Ok(Some((chunk_id, LockedLocators {
+            previous_contribution,
+            current_contribution,
+            next_contribution: ContributionLocator::new(self.round_height(), chunk_id, next_contribution_id, false),
+            next_contribution_file_signature: ContributionSignatureLocator::new(
+                self.round_height(),
+                chunk_id,
+                next_contribution_id,
+                false,
+            ),
+        })))
+    }
+
     /// Initialize the files for the next challenge
     pub fn initialize_verifier_response_files(
         &self,
-        _environment: &Environment,
+        environment: &Environment,
         storage: &mut Disk,
         participant: &Participant,
         _chunk_id: u64,
@@ -650,10 +779,11 @@ impl Round {
         // Initialize the next challenge file.
         storage.initialize(
             Locator::ContributionFile(locators.next_contribution.clone()),
-            Object::anoma_contribution_file_size(
+            Object::ceremony_contribution_file_size(
+                environment,
                 locators.next_contribution.round_height(),
                 locators.next_contribution.contribution_id(),
-            ),
+            )?,
         )?;
 
         // Initialize the contribution file signature.
@@ -748,6 +878,7 @@ impl Round {
         participant: Participant,
         verified_locator: LocatorPath,
         verified_signature_locator: LocatorPath,
+        verified_hash: String,
     ) -> Result<(), CoordinatorError> {
         // Set the current contribution as verified for the given chunk ID.
         self.chunk_mut(chunk_id)?.verify_contribution(
@@ -755,6 +886,7 @@ impl Round {
             participant,
             verified_locator,
             verified_signature_locator,
+            verified_hash,
         )?;
 
         // If all chunks are complete and the finished at timestamp has not been set yet,
@@ -1148,6 +1280,9 @@ impl Round {
             .filter(|v| remove_participants.iter().find(|p| p == &v).is_none())
             .collect();
 
+        // The transcript of the round changes, the aggregate hash must be recomputed.
+        self.aggregate_hash = None;
+
         StorageAction::Update(UpdateAction {
             locator: Locator::RoundState {
                 round_height: self.height,
@@ -1281,4 +1416,14 @@ mod tests {
         let round_1 = test_round_1_initial_json().unwrap();
         assert!(!round_1.is_complete());
     }
+
+    #[test]
+    fn test_deterministic_serialization() {
+        // Several chunks, with several contributions each, in the order they are serialized
+        let expected = include_str!("../testing/resources/round_serialized.json").trim_end();
+        let round: Round = serde_json::from_str(expected).unwrap();
+
+        let bytes = Object::RoundState(round).to_bytes();
+        assert_eq!(expected, std::str::from_utf8(&bytes).unwrap());
+    }
 }
@@ -1,37 +1,107 @@
 //! REST API endpoints exposed by the [Coordinator](`crate::Coordinator`).
 
+use chrono::{DateTime, Utc};
 use std::{
     collections::{HashMap, HashSet},
     io::{Cursor, Read, Write},
+    time::SystemTime,
 };
-use tracing::warn;
+use tracing::{error, warn};
 
 use crate::{
-    objects::{ContributionInfo, LockedLocators},
+    distribution::Distributor,
+    objects::{
+        ContributionFileSignature, ContributionInfo, ContributionProvenance, ContributionReceipt, LockedLocators, Task,
+    },
     rest_utils::{
-        self, ContributorStatus, Coordinator, CurrentContributor, LazyJson, NewParticipant, PostChunkRequest,
-        ResponseError, Result, Secret, ServerAuth, HEALTH_PATH, TOKENS_PATH, TOKENS_ZIP_FILE,
+        self, CeremonyStarted, ContributorStatus, Coordinator, CurrentContributor, FinalizeChunkUploadRequest,
+        LazyBytes, LazyJson, NewParticipant, PostChunkRequest, ProtocolVersion, ResponseError, Result, Secret,
+        ServerAuth, SignedJson, SignedRequest, VerificationWorker, HEALTH_PATH, MAX_TOKENS_BODY_SIZE, TOKENS_PATH,
+        TOKENS_ZIP_FILE,
     },
-    s3::S3Ctx,
-    storage::{Locator, Object},
-    CoordinatorState, Participant,
+    s3::{self, S3Ctx},
+    storage::{ContributionSignatureLocator, Locator, Object},
+    CeremonyDump, CeremonyStatus, CeremonyTimings, Cohort, CoordinatorError, CoordinatorState, Participant,
+    QueueAnalytics, RoundReadiness, RoundReverification, VerificationFailureReason,
 };
 use rocket::{
-    get, post,
+    get,
+    http::ContentType,
+    post,
+    response::stream::ByteStream,
+    routes,
     serde::json::Json,
     tokio::{fs, task},
-    Shutdown, State,
+    Route, Shutdown, State,
 };
 
 use url::Url;
 
+/// Returns the routes of the REST API. The routes to manually update the coordinator, verify the pending contributions
+/// and get the summary of the contributions are only served by the debug builds.
+pub fn routes() -> Vec<Route> {
+    let routes = routes![
+        join_queue,
+        lock_chunk,
+        get_chunk,
+        get_challenge_url,
+        get_contribution_url,
+        upload_chunk_part,
+        finalize_chunk_upload,
+        contribute_chunk,
+        heartbeat,
+        stop_coordinator,
+        get_contributor_queue_status,
+        post_contribution_info,
+        get_coordinator_state,
+        get_round_participants,
+        get_round_aggregate_hash,
+        get_round_transcript,
+        get_contribution_file_signature,
+        get_contributions_full_info,
+        get_contributions_provenance,
+        get_queue_analytics,
+        get_timing_histogram,
+        get_ceremony_dump,
+        get_round_readiness,
+        reconcile_round_height,
+        reverify_round,
+        reload_contributor_allowlist,
+        force_release_lock,
+        pause_ceremony,
+        resume_ceremony,
+        get_healthcheck,
+        get_protocol_version,
+        get_cohort_schedule,
+        get_ceremony_status,
+        update_cohorts,
+        post_attestation
+    ];
+
+    #[cfg(debug_assertions)]
+    let routes = {
+        let debug_routes = routes![update_coordinator, verify_chunks, get_contributions_info];
+        [routes, debug_routes].concat()
+    };
+
+    routes
+}
+
 /// Add the incoming contributor to the queue of contributors.
 #[post("/contributor/join_queue", format = "json", data = "<token>")]
 pub async fn join_queue(
     coordinator: &State<Coordinator>,
+    _version: ProtocolVersion,
     new_participant: NewParticipant,
     token: LazyJson<String>,
 ) -> Result<Json<u64>> {
+    rest_utils::check_writable(coordinator).await?;
+
+    // Round 0 is the one in which the queue gets populated, so it's fine to join it. The ceremony must have been initialized though
+    if let Err(CoordinatorError::RoundHeightNotSet) = coordinator.read().await.current_round_height() {
+        return Err(ResponseError::CeremonyNotStarted);
+    }
+
     // NOTE: check on the token happens only here meaning that a contributor can join the ceremony at the very last moment of a cohort and
     // contribute effectively in the following cohort. Forcing the contribution to happen in the correct cohort would take more complicated checks
     // and could lower the amount of contributions received
@@ -40,11 +110,14 @@ pub async fn join_queue(
 
     task::spawn_blocking(move || {
         write_lock.add_to_queue(
-            new_participant.participant,
+            new_participant.participant.clone(),
             new_participant.ip_address,
             token.clone(),
             10,
-        )
+        )?;
+        write_lock.set_client_provenance(new_participant.participant, new_participant.client);
+
+        Ok::<_, CoordinatorError>(())
     })
     .await?
     .map_err(|e| ResponseError::CoordinatorError(e))?;
@@ -56,8 +129,11 @@ pub async fn join_queue(
 #[get("/contributor/lock_chunk", format = "json")]
 pub async fn lock_chunk(
     coordinator: &State<Coordinator>,
+    _started: CeremonyStarted,
     participant: CurrentContributor,
 ) -> Result<Json<LockedLocators>> {
+    rest_utils::check_writable(coordinator).await?;
+
     let mut write_lock = (*coordinator).clone().write_owned().await;
     match task::spawn_blocking(move || write_lock.try_lock(&participant)).await? {
         Ok((_, locked_locators)) => Ok(Json(locked_locators)),
@@ -65,52 +141,162 @@ pub async fn lock_chunk(
     }
 }
 
-/// Get the challenge key on Amazon S3 from the [Coordinator](`crate::Coordinator`).
+/// Get the task of the [Chunk](`crate::objects::Chunk`) locked by the contributor, with its locators. The task is the
+/// one assigned to the contributor when it locked the chunk, so that a contributor reconnecting gets the same one.
+#[get("/contributor/chunk")]
+pub async fn get_chunk(
+    coordinator: &State<Coordinator>,
+    _started: CeremonyStarted,
+    participant: CurrentContributor,
+) -> Result<Json<(Task, LockedLocators)>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    task::spawn_blocking(move || {
+        let (chunk_id, locked_locators) = read_lock
+            .locked_locators(&participant)
+            .and_then(|locked| locked.ok_or(CoordinatorError::ParticipantDidntLockChunkId))
+            .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+        match read_lock
+            .pending_task(&participant, chunk_id)
+            .map_err(|e| ResponseError::CoordinatorError(e))?
+        {
+            Some(task) => Ok(Json((task, locked_locators))),
+            None => Err(ResponseError::UnknownTask(Task::new(
+                chunk_id,
+                locked_locators.next_contribution().contribution_id(),
+            ))),
+        }
+    })
+    .await?
+}
+
+/// Get the presigned url of the challenge from the [Coordinator](`crate::Coordinator`), uploading the challenge through
+/// the [`Distributor`] on the first request. The url is signed by the coordinator, see [`SignedJson`].
 #[post("/contributor/challenge", format = "json", data = "<round_height>")]
 pub async fn get_challenge_url(
     coordinator: &State<Coordinator>,
-    _participant: CurrentContributor,
+    distributor: &State<Distributor>,
+    _started: CeremonyStarted,
+    participant: CurrentContributor,
+    signed_request: SignedRequest,
     round_height: LazyJson<u64>,
-) -> Result<Json<String>> {
-    let s3_ctx = S3Ctx::new().await?;
-    let key = format!("round_{}/chunk_0/contribution_0.verified", *round_height);
+) -> Result<SignedJson> {
+    // The challenge must be the one of the chunk locked by the contributor
+    let round_height = *round_height;
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let challenge_locator = task::spawn_blocking(move || {
+        let locked_locators = read_lock.issued_locators(&participant)?;
+        locked_locators.check_round_height(round_height)?;
+
+        Ok::<_, CoordinatorError>(locked_locators.current_contribution())
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    // The challenge is the current contribution of the locked chunk, e.g. round_1/chunk_0/contribution_0.verified for
+    // the first contributor of round 1
+    let key = s3::contribution_key(&challenge_locator);
 
-    // If challenge is already on S3 (round rollback) immediately return the key
-    if let Some(url) = s3_ctx.get_challenge_url(key.clone()).await {
-        return Ok(Json(url));
+    // If challenge is already uploaded (round rollback) immediately return the key
+    if let Some(url) = distributor.get_presigned_url(&key).await? {
+        return SignedJson::new(&url, &signed_request, &*coordinator.read().await);
     }
 
-    // Since we don't chunk the parameters, we have one chunk and one allowed contributor per round. Thus the challenge will always be located at round_{i}/chunk_0/contribution_0.verified
-    // For example, the 1st challenge (after the initialization) is located at round_1/chunk_0/contribution_0.verified
     let read_lock = (*coordinator).clone().read_owned().await;
-    let challenge = match task::spawn_blocking(move || read_lock.get_challenge(*round_height, 0, 0, true)).await? {
+    let challenge = match task::spawn_blocking(move || {
+        read_lock.get_challenge(
+            challenge_locator.round_height(),
+            challenge_locator.chunk_id(),
+            challenge_locator.contribution_id(),
+            challenge_locator.is_verified(),
+        )
+    })
+    .await?
+    {
         Ok(challenge) => challenge,
         Err(e) => return Err(ResponseError::CoordinatorError(e)),
     };
 
-    // Upload challenge to S3 and return url
-    let url = s3_ctx.upload_challenge(key, challenge).await?;
+    // Upload challenge and return url
+    let url = distributor.upload(&key, challenge).await?;
 
-    Ok(Json(url))
+    SignedJson::new(&url, &signed_request, &*coordinator.read().await)
 }
 
 /// Request the urls where to upload a [Chunk](`crate::objects::Chunk`) contribution and the ContributionFileSignature.
 #[post("/upload/chunk", format = "json", data = "<round_height>")]
 pub async fn get_contribution_url(
-    _participant: CurrentContributor,
+    coordinator: &State<Coordinator>,
+    participant: CurrentContributor,
     round_height: LazyJson<u64>,
 ) -> Result<Json<(String, String)>> {
-    let contrib_key = format!("round_{}/chunk_0/contribution_1.unverified", *round_height);
-    let contrib_sig_key = format!("round_{}/chunk_0/contribution_1.unverified.signature", *round_height);
+    rest_utils::check_writable(coordinator).await?;
+
+    // The contribution must be the one of the chunk locked by the contributor
+    let round_height = *round_height;
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let locked_locators = task::spawn_blocking(move || {
+        let locked_locators = read_lock.issued_locators(&participant)?;
+        locked_locators.check_round_height(round_height)?;
+
+        Ok::<_, CoordinatorError>(locked_locators)
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
 
     // Prepare urls for the upload
     let s3_ctx = S3Ctx::new().await?;
-    let urls = s3_ctx.get_contribution_urls(contrib_key, contrib_sig_key);
+    let urls = s3_ctx.get_contribution_urls(
+        &locked_locators.next_contribution(),
+        &locked_locators.next_contribution_file_signature(),
+    );
 
     Ok(Json(urls))
 }
 
+/// Upload a part of a [Chunk](`crate::objects::Chunk`) contribution to the [Coordinator](`crate::Coordinator`) storage. The part is sent as raw bytes, to be written at the given offset of the contribution issued with the lock of the participant. Returns the number of bytes of the contribution committed so far, from which an interrupted upload can be resumed.
+#[post("/upload/chunk/part/<offset>", format = "application/octet-stream", data = "<part>")]
+pub async fn upload_chunk_part(
+    coordinator: &State<Coordinator>,
+    participant: CurrentContributor,
+    offset: u64,
+    part: LazyBytes,
+) -> Result<Json<u64>> {
+    rest_utils::check_writable(coordinator).await?;
+
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let committed = task::spawn_blocking(move || {
+        let contribution_locator = read_lock.issued_locators(&participant)?.next_contribution();
+        read_lock.write_contribution_part(&contribution_locator, offset, &part)
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(committed))
+}
+
+/// Complete the upload of a [Chunk](`crate::objects::Chunk`) contribution sent in parts. Fails if the uploaded size doesn't match the expected size of the contribution, or if the upload is truncated or corrupted with respect to the size and hash sent along.
+#[post("/upload/chunk/finalize", format = "json", data = "<request>")]
+pub async fn finalize_chunk_upload(
+    coordinator: &State<Coordinator>,
+    participant: CurrentContributor,
+    request: LazyJson<FinalizeChunkUploadRequest>,
+) -> Result<()> {
+    rest_utils::check_writable(coordinator).await?;
+
+    let read_lock = (*coordinator).clone().read_owned().await;
+    task::spawn_blocking(move || {
+        read_lock
+            .issued_locators(&participant)?
+            .check_next_contribution(&request.contribution_locator)?;
+        read_lock.finalize_contribution_upload(&request.contribution_locator, request.size, &request.hash)
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
 /// Notify the [Coordinator](`crate::Coordinator`) of a finished and uploaded [Contribution](`crate::objects::Contribution`). This will unlock the given [Chunk](`crate::objects::Chunk`).
+/// Returns the [receipt](`crate::objects::ContributionReceipt`) of the contribution, signed by the coordinator.
 #[post(
     "/contributor/contribute_chunk",
     format = "json",
@@ -120,34 +306,79 @@ pub async fn contribute_chunk(
     coordinator: &State<Coordinator>,
     participant: CurrentContributor,
     contribute_chunk_request: LazyJson<PostChunkRequest>,
-) -> Result<()> {
-    // Download contribution and its signature from S3 to local disk from the provided Urls
+) -> Result<Json<ContributionReceipt>> {
+    rest_utils::check_writable(coordinator).await?;
+
+    // The contribution must be the one issued to the contributor with the lock of its chunk
+    let request = (*contribute_chunk_request).clone();
+    let contributor = participant.clone();
+    let read_lock = (*coordinator).clone().read_owned().await;
+    task::spawn_blocking(move || {
+        let locked_locators = read_lock.issued_locators(&contributor)?;
+        locked_locators.check_round_height(request.round_height)?;
+        locked_locators.check_next_contribution(&request.contribution_locator)?;
+        locked_locators.check_next_contribution_file_signature(&request.contribution_signature_locator)
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    // Download contribution and its signature from S3 to local disk from the provided Urls. If the contribution has
+    // already been uploaded in parts to the coordinator, only its signature is downloaded
     let s3_ctx = S3Ctx::new().await?;
-    let (contribution, contribution_sig) = s3_ctx.get_contribution(contribute_chunk_request.round_height).await?;
+    let uploaded = coordinator
+        .read()
+        .await
+        .is_contribution_uploaded(&contribute_chunk_request.contribution_locator);
+    let (contribution, contribution_sig) = if uploaded {
+        let contribution_sig = s3_ctx
+            .get_contribution_signature(&contribute_chunk_request.contribution_signature_locator)
+            .await?;
+        (None, contribution_sig)
+    } else {
+        let (contribution, contribution_sig) = s3_ctx
+            .get_contribution(
+                &contribute_chunk_request.contribution_locator,
+                &contribute_chunk_request.contribution_signature_locator,
+            )
+            .await?;
+        (Some(contribution), contribution_sig)
+    };
     let mut write_lock = (*coordinator).clone().write_owned().await;
 
     task::spawn_blocking(move || {
-        write_lock.write_contribution(contribute_chunk_request.contribution_locator, contribution)?;
-        write_lock.write_contribution_file_signature(
+        let contribution_file_signature: ContributionFileSignature = serde_json::from_slice(&contribution_sig)?;
+        let contribution_hash = contribution_file_signature.get_response_hash().to_owned();
+        // Only 1 chunk per round, chunk_id is always 0
+        let contribution_locator = write_lock.submit_contribution(
+            &participant,
+            0,
+            contribute_chunk_request.contribution_locator,
+            contribution,
             contribute_chunk_request.contribution_signature_locator,
-            serde_json::from_slice(&contribution_sig)?,
+            contribution_file_signature,
         )?;
-        write_lock.try_contribute(&participant, 0) // Only 1 chunk per round, chunk_id is always 0
+
+        // The signed hash of the contribution has been checked against the received file
+        write_lock.contribution_receipt(&contribution_locator, contribution_hash)
     })
     .await?
-    .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |_| Ok(()))
+    .map_or_else(|e| Err(ResponseError::CoordinatorError(e)), |receipt| Ok(Json(receipt)))
 }
 
 /// Update the [Coordinator](`crate::Coordinator`) state. This endpoint is accessible only by the coordinator itself.
 #[cfg(debug_assertions)]
 #[get("/update")]
 pub async fn update_coordinator(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<()> {
+    rest_utils::check_writable(coordinator).await?;
+
     rest_utils::perform_coordinator_update((*coordinator).clone()).await
 }
 
 /// Let the [Coordinator](`crate::Coordinator`) know that the participant is still alive and participating (or waiting to participate) in the ceremony.
 #[post("/contributor/heartbeat")]
 pub async fn heartbeat(coordinator: &State<Coordinator>, participant: Participant) -> Result<()> {
+    rest_utils::check_writable(coordinator).await?;
+
     coordinator
         .write()
         .await
@@ -162,11 +393,19 @@ pub async fn stop_coordinator(_auth: ServerAuth, shutdown: Shutdown) {
     shutdown.notify();
 }
 
-/// Verify all the pending contributions. This endpoint is accessible only by the coordinator itself.
+/// Wake the verification worker up to verify all the pending contributions, without waiting for the outcome. This
+/// endpoint is accessible only by the coordinator itself.
 #[cfg(debug_assertions)]
 #[get("/verify")]
-pub async fn verify_chunks(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<()> {
-    rest_utils::perform_verify_chunks((*coordinator).clone(), &S3Ctx::new().await?).await
+pub async fn verify_chunks(
+    coordinator: &State<Coordinator>,
+    worker: &State<VerificationWorker>,
+    _auth: ServerAuth,
+) -> Result<()> {
+    rest_utils::check_writable(coordinator).await?;
+
+    worker.wake();
+    Ok(())
 }
 
 /// Load new tokens to update the future cohorts. The `tokens` parameter is the serialized zip folder
@@ -174,8 +413,10 @@ pub async fn verify_chunks(coordinator: &State<Coordinator>, _auth: ServerAuth)
 pub async fn update_cohorts(
     coordinator: &State<Coordinator>,
     _auth: ServerAuth,
-    tokens: LazyJson<Vec<u8>>,
+    tokens: LazyJson<Vec<u8>, MAX_TOKENS_BODY_SIZE>,
 ) -> Result<()> {
+    rest_utils::check_writable(coordinator).await?;
+
     let reader = Cursor::new(tokens.clone());
     let mut zip = zip::ZipArchive::new(reader).map_err(|e| ResponseError::IoError(e.to_string()))?;
     let mut zip_clone = zip.clone();
@@ -251,21 +492,41 @@ pub async fn update_cohorts(
     Ok(())
 }
 
-/// Get the queue status of the contributor.
+/// Get the queue status of the contributor, signed by the coordinator, see [`SignedJson`].
 #[get("/contributor/queue_status", format = "json")]
 pub async fn get_contributor_queue_status(
     coordinator: &State<Coordinator>,
     participant: Participant,
-) -> Json<ContributorStatus> {
+    signed_request: SignedRequest,
+) -> Result<SignedJson> {
+    let status = contributor_queue_status(coordinator, participant).await;
+
+    SignedJson::new(&status, &signed_request, &*coordinator.read().await)
+}
+
+/// Computes the queue status of the contributor.
+async fn contributor_queue_status(coordinator: &State<Coordinator>, participant: Participant) -> ContributorStatus {
     let contributor = participant.clone();
 
     let read_lock = (*coordinator).clone().read_owned().await;
     // Check that the contributor is authorized to lock a chunk in the current round.
-    if task::spawn_blocking(move || read_lock.is_current_contributor(&contributor))
-        .await
-        .unwrap()
-    {
-        return Json(ContributorStatus::Round);
+    let round_status = task::spawn_blocking(move || {
+        if !read_lock.is_current_contributor(&contributor) {
+            return None;
+        }
+
+        match read_lock.stale_contributor_eviction_time(&contributor) {
+            Some(evict_at) => {
+                let evict_at = DateTime::<Utc>::from(SystemTime::from(evict_at));
+                Some(ContributorStatus::Stale(evict_at))
+            }
+            None => Some(ContributorStatus::Round),
+        }
+    })
+    .await
+    .unwrap();
+    if let Some(status) = round_status {
+        return status;
     }
 
     let read_lock = coordinator.read().await;
@@ -276,22 +537,37 @@ pub async fn get_contributor_queue_status(
         let queue_position = match read_lock.state().queue_contributor_info(&participant) {
             Some((_, Some(round), _, _)) => round - read_lock.state().current_round_height(),
             Some((_, None, _, _)) => queue_size,
-            None => return Json(ContributorStatus::Other),
+            None => return ContributorStatus::Other,
         };
 
-        return Json(ContributorStatus::Queue(queue_position, queue_size));
+        // Recomputed at every poll to follow the drain rate of the queue
+        let start_at = match read_lock.estimated_start_time(&participant, queue_position) {
+            Ok(start_at) => start_at.map(|t| DateTime::<Utc>::from(SystemTime::from(t))),
+            Err(e) => {
+                warn!("Error while estimating the start time of {}: {}", participant, e);
+                None
+            }
+        };
+
+        return ContributorStatus::Queue(queue_position, queue_size, start_at);
     }
 
     if read_lock.is_finished_contributor(&participant) {
-        return Json(ContributorStatus::Finished);
+        return ContributorStatus::Finished;
+    }
+
+    if let Some(reason) = read_lock.rejection_reason(&participant) {
+        return ContributorStatus::Rejected {
+            reason: reason.to_owned(),
+        };
     }
 
     if read_lock.is_banned_participant(&participant) {
-        return Json(ContributorStatus::Banned);
+        return ContributorStatus::Banned;
     }
 
     // Not in the queue, not finished, nor in the current round
-    Json(ContributorStatus::Other)
+    ContributorStatus::Other
 }
 
 /// Write [`ContributionInfo`] to disk
@@ -301,13 +577,28 @@ pub async fn post_contribution_info(
     participant: CurrentContributor,
     request: LazyJson<ContributionInfo>,
 ) -> Result<()> {
-    // Validate info
+    rest_utils::check_writable(coordinator).await?;
+
+    // Validate info, which must be signed by the contributor uploading it
     if request.public_key != participant.address() {
-        return Err(ResponseError::InvalidContributionInfo(format!(
-            "Public key in info {} doesnt' match the participant one {}",
+        warn!(
+            "Public key in info {} doesn't match the participant one {}",
             request.public_key,
             participant.address()
-        )));
+        );
+        return Err(ResponseError::CoordinatorError(
+            VerificationFailureReason::InfoPublicKeyMismatch.into(),
+        ));
+    }
+
+    if !request.verify_signature().unwrap_or(false) {
+        return Err(ResponseError::CoordinatorError(
+            VerificationFailureReason::InfoSignatureInvalid.into(),
+        ));
+    }
+
+    if let Err(e) = request.validate_memo() {
+        return Err(ResponseError::InvalidContributionInfo(e.to_string()));
     }
 
     let current_round_height = match coordinator.read().await.current_round_height() {
@@ -329,7 +620,7 @@ pub async fn post_contribution_info(
     task::spawn_blocking(move || {
         write_lock.write_contribution_info(request.clone())?;
 
-        write_lock.update_contribution_summary(request.0.into())
+        write_lock.update_contribution_summary(request.0)
     })
     .await?
     .map_err(|e| ResponseError::CoordinatorError(e))
@@ -342,6 +633,8 @@ pub async fn post_attestation(
     participant: Participant,
     request: LazyJson<(u64, String)>,
 ) -> Result<()> {
+    rest_utils::check_writable(coordinator).await?;
+
     let (round, attestation) = request.0;
 
     // Check url format
@@ -388,16 +681,282 @@ pub async fn post_attestation(
         .map_err(|e| ResponseError::CoordinatorError(e))
 }
 
-/// Retrieve the contributions' info. This endpoint is accessible by anyone and does not require a signed request.
+/// Retrieve the contributions' info of the rounds between `from_round` and `to_round` included, at most `page_size` of
+/// them after skipping the first `offset` ones. Without parameters, retrieves the info of all the contributions. This endpoint is accessible by anyone and does
+/// not require a signed request: the identity of the contributors is redacted if the coordinator is configured so.
 #[cfg(debug_assertions)]
-#[get("/contribution_info")]
-pub async fn get_contributions_info(coordinator: &State<Coordinator>) -> Result<Vec<u8>> {
+#[get("/contribution_info?<from_round>&<to_round>&<offset>&<page_size>")]
+pub async fn get_contributions_info(
+    coordinator: &State<Coordinator>,
+    from_round: Option<u64>,
+    to_round: Option<u64>,
+    offset: Option<usize>,
+    page_size: Option<usize>,
+) -> Result<Json<Vec<crate::objects::TrimmedContributionInfo>>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let summary = task::spawn_blocking(move || {
+        read_lock.get_public_contributions_summary(from_round, to_round, offset, page_size)
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(summary))
+}
+
+/// Retrieve the signature of the contribution at the given locator, to verify independently who signed it. This
+/// endpoint is accessible by anyone.
+#[post("/contribution/signature", format = "json", data = "<locator>")]
+pub async fn get_contribution_file_signature(
+    coordinator: &State<Coordinator>,
+    locator: LazyJson<ContributionSignatureLocator>,
+) -> Result<Json<ContributionFileSignature>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let signature = task::spawn_blocking(move || read_lock.get_contribution_file_signature(*locator))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(signature))
+}
+
+/// Retrieve the full contributions' info, including the identity of the contributors, of the rounds between
+/// `from_round` and `to_round` included. This endpoint is accessible only by the coordinator itself.
+#[get("/admin/contribution_info?<from_round>&<to_round>")]
+pub async fn get_contributions_full_info(
+    coordinator: &State<Coordinator>,
+    _auth: ServerAuth,
+    from_round: Option<u64>,
+    to_round: Option<u64>,
+) -> Result<Json<Vec<ContributionInfo>>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let contributions = task::spawn_blocking(move || read_lock.get_contributions_full_info(from_round, to_round))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(contributions))
+}
+
+/// Retrieve the provenance of the contributions, the ip address and client of the contributors, of the rounds between
+/// `from_round` and `to_round` included. This endpoint is accessible only by the coordinator itself.
+#[get("/admin/contribution_provenance?<from_round>&<to_round>")]
+pub async fn get_contributions_provenance(
+    coordinator: &State<Coordinator>,
+    _auth: ServerAuth,
+    from_round: Option<u64>,
+    to_round: Option<u64>,
+) -> Result<Json<Vec<ContributionProvenance>>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let provenance = task::spawn_blocking(move || read_lock.get_contributions_provenance(from_round, to_round))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(provenance))
+}
+
+/// Retrieve the analytics of the queue: join rate, average wait, dropout rate and fill of the current cohort. This
+/// endpoint is accessible only by the coordinator itself.
+#[get("/admin/queue/analytics")]
+pub async fn get_queue_analytics(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Json<QueueAnalytics> {
+    Json(coordinator.read().await.queue_analytics())
+}
+
+/// Retrieve the histogram of the durations of the contributions, measured by the coordinator between the lock of a
+/// chunk and the reception of its contribution, and the statistics of the durations of their verifications. This
+/// endpoint is accessible only by the coordinator itself.
+#[get("/admin/timings")]
+pub async fn get_timing_histogram(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Json<CeremonyTimings> {
+    Json(coordinator.read().await.timings())
+}
+
+/// Retrieve a snapshot of every participant of the ceremony, with their state, locks, timestamps, tokens and pending
+/// tasks, to debug a stuck ceremony. This endpoint is accessible only by the coordinator itself.
+#[get("/admin/dump")]
+pub async fn get_ceremony_dump(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<Json<CeremonyDump>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let dump = task::spawn_blocking(move || read_lock.full_dump()).await?;
+
+    Ok(Json(dump))
+}
+
+/// Retrieve the contributors of the given round. This endpoint is accessible by anyone and does not require a signed
+/// request.
+#[get("/round/<round_height>/participants", format = "json")]
+pub async fn get_round_participants(
+    coordinator: &State<Coordinator>,
+    round_height: u64,
+) -> Result<Json<Vec<Participant>>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let participants = task::spawn_blocking(move || read_lock.get_round_participants(round_height))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(participants))
+}
+
+/// Retrieve the aggregate hash of the given round, the hash of its verified aggregated transcript, to pin it in
+/// external records. This endpoint is accessible by anyone and does not require a signed request.
+#[get("/round/<round_height>/hash", format = "json")]
+pub async fn get_round_aggregate_hash(coordinator: &State<Coordinator>, round_height: u64) -> Result<Json<String>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let aggregate_hash = task::spawn_blocking(move || read_lock.get_round_aggregate_hash(round_height))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(aggregate_hash))
+}
+
+/// Retrieve the json encoded [`RoundTranscript`](crate::RoundTranscript) of the given round, once aggregated, so that
+/// it can be audited and mirrored independently of the coordinator. This endpoint is accessible by anyone and does not
+/// require a signed request.
+///
+/// The transcript is streamed: its contribution files are read one at a time, without holding the coordinator lock
+/// while they are sent, so that a download neither loads the whole round in memory nor stalls the ceremony.
+#[get("/round/<round_height>/transcript", format = "json")]
+pub async fn get_round_transcript(
+    coordinator: &State<Coordinator>,
+    round_height: u64,
+) -> Result<(ContentType, ByteStream![Vec<u8>])> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let (skipped, chunks) = task::spawn_blocking(move || {
+        let chunks = read_lock.get_round_transcript_locators(round_height)?;
+        Ok::<_, CoordinatorError>((read_lock.get_round(round_height)?.is_skipped(), chunks))
+    })
+    .await?
+    .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    let coordinator = (*coordinator).clone();
+    let transcript = ByteStream! {
+        yield format!("{{\"round_height\":{},\"skipped\":{},\"chunks\":[", round_height, skipped).into_bytes();
+        for (i, (chunk_id, locators)) in chunks.into_iter().enumerate() {
+            let separator = if i == 0 { "" } else { "," };
+            yield format!("{}{{\"chunk_id\":{},\"contributions\":[", separator, chunk_id).into_bytes();
+            for (j, locator) in locators.into_iter().enumerate() {
+                let read_lock = coordinator.clone().read_owned().await;
+                let contribution = task::spawn_blocking(move || read_lock.get_transcript_contribution(&locator))
+                    .await
+                    .map_err(|e| e.to_string())
+                    .and_then(|contribution| contribution.map_err(|e| e.to_string()));
+                let contribution = match contribution {
+                    Ok(contribution) => contribution,
+                    Err(e) => {
+                        // The response is already under way, it's left truncated
+                        error!("Failed to read {:?} of the transcript of round {}: {}", locator, round_height, e);
+                        return;
+                    }
+                };
+                let separator = if j == 0 { "" } else { "," };
+                yield format!("{}\"{}\"", separator, contribution).into_bytes();
+            }
+            yield b"]}".to_vec();
+        }
+        yield b"]}".to_vec();
+    };
+
+    Ok((ContentType::JSON, transcript))
+}
+
+/// Check whether the current round can be aggregated and the ceremony advanced to the next round, without performing
+/// the transition. This endpoint is accessible only by the coordinator itself.
+#[get("/admin/round/readiness", format = "json")]
+pub async fn get_round_readiness(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<Json<RoundReadiness>> {
     let read_lock = (*coordinator).clone().read_owned().await;
-    let summary = task::spawn_blocking(move || read_lock.storage().get_contributions_summary())
+    let readiness = task::spawn_blocking(move || read_lock.can_advance_round())
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(readiness))
+}
+
+/// Verify again every contribution of the given completed round, to detect the files corrupted after their
+/// verification. The check is read-only unless `repair` is set, in which case the verified copies of the failed
+/// contributions are rewritten. This endpoint is accessible only by the coordinator itself.
+#[post("/admin/round/<round_height>/reverify?<repair>")]
+pub async fn reverify_round(
+    coordinator: &State<Coordinator>,
+    _auth: ServerAuth,
+    round_height: u64,
+    repair: Option<bool>,
+) -> Result<Json<RoundReverification>> {
+    if repair == Some(true) {
+        rest_utils::check_writable(coordinator).await?;
+    }
+
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+    let report = task::spawn_blocking(move || write_lock.reverify_round(round_height, repair.unwrap_or_default()))
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(report))
+}
+
+/// Reset the current round height to the highest consistent round in storage, to recover from a crash in the middle
+/// of a write. This endpoint is accessible only by the coordinator itself.
+#[post("/admin/round/reconcile")]
+pub async fn reconcile_round_height(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<Json<u64>> {
+    rest_utils::check_writable(coordinator).await?;
+
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+    let round_height = task::spawn_blocking(move || write_lock.reconcile_round_height())
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(round_height))
+}
+
+/// Reload the allowlist of the contributors from its file, without restarting the coordinator. Returns the number of
+/// authorized contributors, if participation is restricted. This endpoint is accessible only by the coordinator itself.
+#[post("/admin/allowlist/reload")]
+pub async fn reload_contributor_allowlist(
+    coordinator: &State<Coordinator>,
+    _auth: ServerAuth,
+) -> Result<Json<Option<usize>>> {
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+    let size = task::spawn_blocking(move || write_lock.reload_contributor_allowlist())
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(size))
+}
+
+/// Release the lock on a chunk, whoever holds it, and discard the partial contribution of its holder, so that a chunk
+/// stuck by a client gone without unlocking it can be locked again. Returns the participant which held the lock. This
+/// endpoint is accessible only by the coordinator itself.
+#[post("/admin/release_lock", format = "json", data = "<chunk_id>")]
+pub async fn force_release_lock(
+    coordinator: &State<Coordinator>,
+    _auth: ServerAuth,
+    chunk_id: LazyJson<u64>,
+) -> Result<Json<Participant>> {
+    rest_utils::check_writable(coordinator).await?;
+
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+    let participant = task::spawn_blocking(move || write_lock.force_release_lock(*chunk_id))
         .await?
         .map_err(|e| ResponseError::CoordinatorError(e))?;
 
-    Ok(summary)
+    Ok(Json(participant))
+}
+
+/// Pause the intake of new contributors, for maintenance: joining the queue and locking a chunk are rejected until
+/// resumed, while the ongoing contributions can finish. This endpoint is accessible only by the coordinator itself.
+#[post("/admin/pause")]
+pub async fn pause_ceremony(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<()> {
+    rest_utils::check_writable(coordinator).await?;
+
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+    task::spawn_blocking(move || write_lock.pause())
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
+
+/// Resume the intake of new contributors. This endpoint is accessible only by the coordinator itself.
+#[post("/admin/resume")]
+pub async fn resume_ceremony(coordinator: &State<Coordinator>, _auth: ServerAuth) -> Result<()> {
+    rest_utils::check_writable(coordinator).await?;
+
+    let mut write_lock = (*coordinator).clone().write_owned().await;
+    task::spawn_blocking(move || write_lock.resume())
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))
 }
 
 /// Retrieve the coordinator.json status file
@@ -411,6 +970,32 @@ pub async fn get_coordinator_state(coordinator: &State<Coordinator>, _auth: Secr
     Ok(state)
 }
 
+/// Returns the schedule of the cohorts of the ceremony, so that the holders of a token know when they can join the
+/// queue. This endpoint does not require a signed request.
+#[get("/cohorts", format = "json")]
+pub async fn get_cohort_schedule(coordinator: &State<Coordinator>) -> Json<Vec<Cohort>> {
+    Json(coordinator.read().await.state().cohort_schedule())
+}
+
+/// Returns the public status of the ceremony, including the number of contributions awaiting verification. This endpoint
+/// does not require a signed request.
+#[get("/ceremony/status", format = "json")]
+pub async fn get_ceremony_status(coordinator: &State<Coordinator>) -> Result<Json<CeremonyStatus>> {
+    let read_lock = (*coordinator).clone().read_owned().await;
+    let status = task::spawn_blocking(move || read_lock.ceremony_status())
+        .await?
+        .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+    Ok(Json(status))
+}
+
+/// Returns the protocol version of the coordinator. Clients sending an incompatible version are rejected, so they
+/// can check their compatibility before joining the queue. This endpoint does not require a signed request.
+#[get("/version")]
+pub async fn get_protocol_version(_version: ProtocolVersion) -> Json<&'static str> {
+    Json(rest_utils::PROTOCOL_VERSION)
+}
+
 /// Retrieve healthcheck info. This endpoint is accessible by anyone and does not require a signed request.
 #[get("/healthcheck", format = "json")]
 pub async fn get_healthcheck() -> Result<String> {
@@ -3,7 +3,8 @@
 use crate::{
     authentication::{Production, Signature},
     coordinator_state::TOKEN_BLACKLIST,
-    objects::Task,
+    distribution::DistributionError,
+    objects::{ClientProvenance, Task},
     s3::{S3Ctx, S3Error},
     storage::{ContributionLocator, ContributionSignatureLocator},
     CoordinatorError, Participant,
@@ -13,25 +14,42 @@ pub use crate::{coordinator_state::TOKENS_PATH, s3::TOKENS_ZIP_FILE};
 use blake2::Digest;
 use rocket::{
     catch,
-    data::FromData,
+    data::{Data, FromData},
     error,
+    fairing::{Fairing, Info, Kind},
     http::{ContentType, Status},
     request::{FromRequest, Outcome, Request},
     response::{Responder, Response},
     serde::{Deserialize, DeserializeOwned, Serialize},
-    tokio::{sync::RwLock, task},
+    tokio::{
+        io::AsyncReadExt,
+        sync::{watch::Receiver, Notify, RwLock},
+        task,
+    },
     State,
 };
 
 use anyhow::anyhow;
+use chrono::{DateTime, Utc};
 
+use setup_utils::calculate_hash;
 use sha2::Sha256;
 use subtle::ConstantTimeEq;
 
 use lazy_static::lazy_static;
-use std::{borrow::Cow, convert::TryFrom, io::Cursor, net::IpAddr, ops::Deref, sync::Arc, time::Duration};
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashMap},
+    convert::TryFrom,
+    io::Cursor,
+    net::IpAddr,
+    ops::Deref,
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use thiserror::Error;
-use tracing::warn;
+use time::OffsetDateTime;
+use tracing::{debug, info, warn};
 
 #[cfg(debug_assertions)]
 pub const UPDATE_TIME: Duration = Duration::from_secs(5);
@@ -47,6 +65,19 @@ pub const PUBKEY_HEADER: &str = "ATS-Pubkey";
 pub const SIGNATURE_HEADER: &str = "ATS-Signature";
 pub const CONTENT_LENGTH_HEADER: &str = "Content-Length";
 pub const ACCESS_SECRET_HEADER: &str = "Access-Secret";
+pub const NONCE_HEADER: &str = "ATS-Nonce";
+pub const PROTOCOL_VERSION_HEADER: &str = "ATS-Protocol-Version";
+pub const USER_AGENT_HEADER: &str = "User-Agent";
+pub const COORDINATOR_SIGNATURE_HEADER: &str = "ATS-Coordinator-Signature";
+
+/// Version of the protocol spoken between the CLI and the coordinator, in the `major.minor` format. Bump the minor
+/// for backward compatible changes of the API, the major for breaking ones.
+pub const PROTOCOL_VERSION: &str = "4.1";
+
+/// Width of the window, below the highest nonce received from a participant, in which out of order nonces are still
+/// accepted (if never seen before). Nonces are timestamps in milliseconds and must also be within this window of the
+/// clock of the coordinator.
+pub const NONCE_WINDOW: u64 = 60_000;
 
 lazy_static! {
     pub(crate) static ref HEALTH_PATH: String = match std::env::var("HEALTH_PATH") {
@@ -59,17 +90,28 @@ lazy_static! {
 
 pub(crate) type Coordinator = Arc<RwLock<crate::Coordinator>>;
 
+/// The [`Signature`] scheme used to verify the incoming requests. Can be managed by Rocket to replace the default [`Production`] one.
+pub type SignatureScheme = Arc<dyn Signature>;
+
 /// Server errors. Also includes errors generated by the managed [Coordinator](`crate::Coordinator`).
 #[derive(Error, Debug)]
 pub enum ResponseError {
     #[error("Ceremony is over, no more contributions are allowed")]
     CeremonyIsOver,
+    #[error("The ceremony hasn't begun yet")]
+    CeremonyNotStarted,
+    #[error("The cohort {0} of the token hasn't started yet, it opens at {1}")]
+    CohortNotStarted(u64, OffsetDateTime),
     #[error("Coordinator failed: {0}")]
     CoordinatorError(CoordinatorError),
+    #[error("Error while distributing the challenge: {0}")]
+    DistributionError(#[from] DistributionError),
     #[error("Contribution info is not valid: {0}")]
     InvalidContributionInfo(String),
     #[error("The required access secret is either missing or invalid")]
     InvalidSecret,
+    #[error("Client protocol version {0} is not compatible with the coordinator one {1}, please upgrade your client")]
+    IncompatibleProtocolVersion(String, &'static str),
     #[error("Header {0} is badly formatted")]
     InvalidHeader(&'static str),
     #[error("Updated tokens for current cohort don't match the old ones")]
@@ -118,6 +160,39 @@ impl<'r> Responder<'r, 'static> for ResponseError {
         let response_code = match self {
             ResponseError::BlacklistedToken => Status::Unauthorized,
             ResponseError::CeremonyIsOver => Status::Unauthorized,
+            ResponseError::CeremonyNotStarted => Status::Conflict,
+            ResponseError::CohortNotStarted(_, _) => Status::Forbidden,
+            // The coordinator is read-only, the request is not worth resubmitting
+            ResponseError::CoordinatorError(CoordinatorError::CeremonyClosed) => Status::Forbidden,
+            // The ceremony is paused for maintenance, the request can be resubmitted once resumed
+            ResponseError::CoordinatorError(CoordinatorError::CeremonyPaused) => Status::ServiceUnavailable,
+            // The contribution of the lock has already been added, e.g. by a concurrent request
+            ResponseError::CoordinatorError(CoordinatorError::ChunkAlreadyComplete) => Status::Conflict,
+            ResponseError::CoordinatorError(CoordinatorError::ContributionFileSizeMismatch) => Status::PayloadTooLarge,
+            ResponseError::CoordinatorError(CoordinatorError::ContributionHashMismatch) => Status::UnprocessableEntity,
+            ResponseError::CoordinatorError(CoordinatorError::ContributionSignatureMissing) => Status::NotFound,
+            ResponseError::CoordinatorError(CoordinatorError::ContributionUploadGap { .. }) => Status::Conflict,
+            ResponseError::CoordinatorError(CoordinatorError::ContributionVerificationFailed { .. }) => {
+                Status::BadRequest
+            }
+            // The contributor has to wait for the ones that joined the queue before it, the request can be resubmitted
+            ResponseError::CoordinatorError(CoordinatorError::NotYourTurn) => Status::ServiceUnavailable,
+            // The participant is no longer part of the ceremony, the request is not worth resubmitting
+            ResponseError::CoordinatorError(CoordinatorError::ParticipantBanned) => Status::Gone,
+            ResponseError::CoordinatorError(CoordinatorError::ParticipantDidntLockChunkId) => Status::NotFound,
+            ResponseError::CoordinatorError(CoordinatorError::ParticipantInvalid(_)) => Status::BadRequest,
+            ResponseError::CoordinatorError(CoordinatorError::ParticipantNotFound(_)) => Status::NotFound,
+            ResponseError::CoordinatorError(CoordinatorError::ParticipantWasDropped) => Status::Gone,
+            ResponseError::CoordinatorError(CoordinatorError::ReplayedRequest) => Status::Unauthorized,
+            ResponseError::CoordinatorError(CoordinatorError::RoundDoesNotExist) => Status::NotFound,
+            ResponseError::CoordinatorError(CoordinatorError::RoundNotAggregated) => Status::NotFound,
+            ResponseError::CoordinatorError(CoordinatorError::RoundRangeInvalid) => Status::BadRequest,
+            // The contribution can be resubmitted once the round transition is over
+            ResponseError::CoordinatorError(CoordinatorError::RoundTransitionInProgress) => Status::ServiceUnavailable,
+            ResponseError::CoordinatorError(CoordinatorError::UnauthorizedChunkContributor) => Status::Forbidden,
+            // The verifier is lagging behind, the request can be resubmitted once the backlog drained
+            ResponseError::CoordinatorError(CoordinatorError::VerificationBacklog) => Status::ServiceUnavailable,
+            ResponseError::IncompatibleProtocolVersion(_, _) => Status::UpgradeRequired,
             ResponseError::InvalidHeader(_) => Status::BadRequest,
             ResponseError::InvalidSecret => Status::Unauthorized,
             ResponseError::InvalidSignature => Status::BadRequest,
@@ -129,6 +204,7 @@ impl<'r> Responder<'r, 'static> for ResponseError {
             ResponseError::SerdeError(_) => Status::UnprocessableEntity,
             ResponseError::TokenAlreadyInUse => Status::Unauthorized,
             ResponseError::UnauthorizedParticipant(_, _, _) => Status::Unauthorized,
+            ResponseError::UnknownTask(_) => Status::NotFound,
             ResponseError::WrongDigestEncoding(_) => Status::BadRequest,
             _ => Status::InternalServerError,
         };
@@ -182,6 +258,33 @@ pub fn invalid_header(req: &Request) -> ResponseError {
     ResponseError::InvalidHeader(header)
 }
 
+#[catch(458)]
+pub fn replayed_request() -> ResponseError {
+    ResponseError::CoordinatorError(CoordinatorError::ReplayedRequest)
+}
+
+#[catch(459)]
+pub fn ceremony_not_started() -> ResponseError {
+    ResponseError::CeremonyNotStarted
+}
+
+#[catch(460)]
+pub fn incompatible_protocol_version(req: &Request) -> ResponseError {
+    let version = req.local_cache(|| UNKNOWN.to_string());
+    ResponseError::IncompatibleProtocolVersion(version.to_owned(), PROTOCOL_VERSION)
+}
+
+#[catch(461)]
+pub fn payload_too_large() -> ResponseError {
+    ResponseError::CoordinatorError(CoordinatorError::ContributionFileSizeMismatch)
+}
+
+#[catch(462)]
+pub fn invalid_participant(req: &Request) -> ResponseError {
+    let pubkey = req.headers().get_one(PUBKEY_HEADER).unwrap_or(UNKNOWN);
+    ResponseError::CoordinatorError(CoordinatorError::ParticipantInvalid(pubkey.to_owned()))
+}
+
 #[catch(512)]
 pub fn io_error(req: &Request) -> ResponseError {
     let message = req.local_cache(|| UNKNOWN.to_string());
@@ -189,6 +292,7 @@ pub fn io_error(req: &Request) -> ResponseError {
 }
 
 /// Content info
+#[derive(Clone)]
 pub struct RequestContent<'a> {
     len: usize,
     digest: Cow<'a, str>,
@@ -234,6 +338,7 @@ impl<'a> RequestContent<'a> {
 #[derive(Default)]
 pub struct SignatureHeaders<'r> {
     pub pubkey: &'r str,
+    pub nonce: u64,
     pub content: Option<RequestContent<'r>>,
     pub signature: Option<Cow<'r, str>>,
 }
@@ -242,22 +347,28 @@ impl<'r> SignatureHeaders<'r> {
     /// Produces the message on which to compute the signature
     pub fn to_string(&self) -> Cow<'_, str> {
         match &self.content {
-            Some(content) => format!("{}{}{}", self.pubkey, content.len, content.digest).into(),
-            None => self.pubkey.into(),
+            Some(content) => format!("{}{}{}{}", self.pubkey, self.nonce, content.len, content.digest).into(),
+            None => format!("{}{}", self.pubkey, self.nonce).into(),
         }
     }
 
-    pub fn new(pubkey: &'r str, content: Option<RequestContent<'r>>, signature: Option<Cow<'r, str>>) -> Self {
+    pub fn new(
+        pubkey: &'r str,
+        nonce: u64,
+        content: Option<RequestContent<'r>>,
+        signature: Option<Cow<'r, str>>,
+    ) -> Self {
         Self {
             pubkey,
+            nonce,
             content,
             signature,
         }
     }
 
-    fn try_verify_signature(&self) -> Result<bool> {
+    fn try_verify_signature(&self, scheme: &dyn Signature) -> Result<bool> {
         match &self.signature {
-            Some(sig) => Ok(Production.verify(self.pubkey, &self.to_string(), &sig)),
+            Some(sig) => Ok(scheme.verify(self.pubkey, &self.to_string(), &sig)),
             None => Err(ResponseError::MissingSigningKey),
         }
     }
@@ -276,6 +387,11 @@ impl<'r> TryFrom<&'r Request<'_>> for SignatureHeaders<'r> {
         let sig = headers
             .get_one(SIGNATURE_HEADER)
             .ok_or(ResponseError::InvalidHeader(SIGNATURE_HEADER))?;
+        let nonce = headers
+            .get_one(NONCE_HEADER)
+            .ok_or(ResponseError::InvalidHeader(NONCE_HEADER))?
+            .parse()
+            .map_err(|_| ResponseError::InvalidHeader(NONCE_HEADER))?;
 
         // If post request, also get the hash of body from header (if any and if base64 encoded)
         if request.method() == rocket::http::Method::Post {
@@ -289,7 +405,101 @@ impl<'r> TryFrom<&'r Request<'_>> for SignatureHeaders<'r> {
             }
         }
 
-        Ok(SignatureHeaders::new(pubkey, body, Some(sig.into())))
+        Ok(SignatureHeaders::new(pubkey, nonce, body, Some(sig.into())))
+    }
+}
+
+/// The nonces recently received from a participant.
+#[derive(Default)]
+struct NonceWindow {
+    highest: u64,
+    seen: BTreeSet<u64>,
+}
+
+impl NonceWindow {
+    /// Records the nonce, returning [`CoordinatorError::ReplayedRequest`] if it was already used or is too old
+    fn try_insert(&mut self, nonce: u64, window: u64) -> std::result::Result<(), CoordinatorError> {
+        if nonce.saturating_add(window) <= self.highest || !self.seen.insert(nonce) {
+            return Err(CoordinatorError::ReplayedRequest);
+        }
+
+        if nonce > self.highest {
+            self.highest = nonce;
+            // Forget the nonces which fell out of the window
+            self.seen = self.seen.split_off(&self.highest.saturating_sub(window));
+        }
+
+        Ok(())
+    }
+}
+
+/// Current time in milliseconds, the unit of the nonces.
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// The nonces recently used by the participants, managed by Rocket, to prevent the replay of signed requests. Only the
+/// nonces within the window (by default [`NONCE_WINDOW`]) of the current time are accepted, so the participants idle
+/// for longer are forgotten.
+///
+/// NOTE: the nonces are not persisted. Instead, the nonces issued before the store was created, i.e. before a restart
+/// of the coordinator, are rejected: a request captured before a restart can't be replayed after it.
+#[derive(Clone)]
+pub struct NonceStore {
+    created_at: u64,
+    window: u64,
+    windows: Arc<Mutex<HashMap<String, NonceWindow>>>,
+}
+
+impl Default for NonceStore {
+    fn default() -> Self {
+        Self::with_window(Duration::from_millis(NONCE_WINDOW))
+    }
+}
+
+impl NonceStore {
+    /// Store accepting the nonces within the given window of the current time.
+    pub fn with_window(window: Duration) -> Self {
+        Self {
+            created_at: now_millis(),
+            window: window.as_millis() as u64,
+            windows: Default::default(),
+        }
+    }
+
+    /// Checks that the nonce is recent and has never been used by the participant.
+    pub fn check(&self, pubkey: &str, nonce: u64) -> std::result::Result<(), CoordinatorError> {
+        let now = now_millis();
+        if nonce < self.created_at || nonce.saturating_add(self.window) < now || nonce > now.saturating_add(self.window)
+        {
+            return Err(CoordinatorError::ReplayedRequest);
+        }
+
+        let mut windows = self.windows.lock().expect("Nonces mutex should not be poisoned");
+        // The nonces of the idle participants are out of the window, they would be rejected anyway
+        windows.retain(|_, window| window.highest.saturating_add(self.window) >= now);
+
+        windows
+            .entry(pubkey.to_owned())
+            .or_default()
+            .try_insert(nonce, self.window)
+    }
+
+    /// Number of participants whose nonces are remembered.
+    pub fn tracked_participants(&self) -> usize {
+        self.windows.lock().expect("Nonces mutex should not be poisoned").len()
+    }
+}
+
+/// Maps a failed signature verification to the custom status code of the matching catcher
+fn signature_failure(e: ResponseError) -> (Status, ResponseError) {
+    match e {
+        ResponseError::CoordinatorError(CoordinatorError::ReplayedRequest) => (Status::new(458), e),
+        ResponseError::CoordinatorError(CoordinatorError::ParticipantInvalid(_)) => (Status::new(462), e),
+        _ => (Status::new(452), e),
     }
 }
 
@@ -299,14 +509,29 @@ trait VerifySignature<'r> {
 }
 
 impl<'r> VerifySignature<'r> for Request<'_> {
-    /// Check signature of request and return the pubkey of the participant
+    /// Check signature and nonce of request and return the pubkey of the participant. The signature scheme is the
+    /// one managed by Rocket, if any, or [`Production`] otherwise
     fn verify_signature(&'r self) -> Result<&str> {
         let headers = SignatureHeaders::try_from(self)?;
+        // Reject the malformed public keys before they reach the signature scheme and the state of the coordinator
+        Participant::validate_pubkey(headers.pubkey).map_err(ResponseError::CoordinatorError)?;
 
-        match headers.try_verify_signature()? {
-            true => Ok(headers.pubkey),
-            false => Err(ResponseError::InvalidSignature),
+        let scheme: &dyn Signature = match self.rocket().state::<SignatureScheme>() {
+            Some(scheme) => scheme.as_ref(),
+            None => &Production,
+        };
+
+        if !headers.try_verify_signature(scheme)? {
+            return Err(ResponseError::InvalidSignature);
         }
+
+        self.rocket()
+            .state::<NonceStore>()
+            .expect("Managed state should always be retrievable")
+            .check(headers.pubkey, headers.nonce)
+            .map_err(ResponseError::CoordinatorError)?;
+
+        Ok(headers.pubkey)
     }
 }
 
@@ -317,7 +542,29 @@ impl<'r> FromRequest<'r> for Participant {
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
         match request.verify_signature() {
             Ok(pubkey) => Outcome::Success(Participant::new_contributor(pubkey)),
-            Err(e) => Outcome::Failure((Status::new(452), e)),
+            Err(e) => Outcome::Failure(signature_failure(e)),
+        }
+    }
+}
+
+/// The public key and nonce of a signed request, which the [`SignedJson`] response is bound to. The signature itself
+/// is verified by the participant guards.
+pub struct SignedRequest {
+    pub pubkey: String,
+    pub nonce: u64,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for SignedRequest {
+    type Error = ResponseError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match SignatureHeaders::try_from(request) {
+            Ok(headers) => Outcome::Success(Self {
+                pubkey: headers.pubkey.to_owned(),
+                nonce: headers.nonce,
+            }),
+            Err(e) => Outcome::Failure(signature_failure(e)),
         }
     }
 }
@@ -326,6 +573,7 @@ impl<'r> FromRequest<'r> for Participant {
 pub struct NewParticipant {
     pub participant: Participant,
     pub ip_address: Option<IpAddr>,
+    pub client: ClientProvenance,
 }
 
 #[rocket::async_trait]
@@ -335,7 +583,7 @@ impl<'r> FromRequest<'r> for NewParticipant {
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
         let pubkey = match request.verify_signature() {
             Ok(h) => h,
-            Err(e) => return Outcome::Failure((Status::new(452), e)),
+            Err(e) => return Outcome::Failure(signature_failure(e)),
         };
 
         // Check that the signature comes from an unknown contributor
@@ -363,13 +611,67 @@ impl<'r> FromRequest<'r> for NewParticipant {
             ));
         }
 
+        let client = ClientProvenance {
+            ip_address,
+            user_agent: request.headers().get_one(USER_AGENT_HEADER).map(ToOwned::to_owned),
+            client_version: request
+                .headers()
+                .get_one(PROTOCOL_VERSION_HEADER)
+                .map(ToOwned::to_owned),
+        };
+
         Outcome::Success(Self {
             participant,
             ip_address,
+            client,
         })
     }
 }
 
+/// Parses a protocol version in the `major.minor` format.
+fn parse_protocol_version(version: &str) -> Option<(u64, u64)> {
+    let (major, minor) = version.split_once('.')?;
+
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+/// Returns `true` if a client speaking the given protocol version can interact with this coordinator: it must share
+/// the major version and not be ahead on the minor one.
+pub fn is_compatible_protocol_version(client_version: &str) -> bool {
+    match (
+        parse_protocol_version(client_version),
+        parse_protocol_version(PROTOCOL_VERSION),
+    ) {
+        (Some((client_major, client_minor)), Some((major, minor))) => client_major == major && client_minor <= minor,
+        _ => false,
+    }
+}
+
+/// Implements the check of the [`PROTOCOL_VERSION`] of the client via [`FromRequest`]. Clients not sending their
+/// version predate the check and are rejected as incompatible.
+pub struct ProtocolVersion;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for ProtocolVersion {
+    type Error = ResponseError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let client_version = request.headers().get_one(PROTOCOL_VERSION_HEADER).unwrap_or(UNKNOWN);
+
+        if !is_compatible_protocol_version(client_version) {
+            // Cache error data for the error catcher
+            request.local_cache(|| client_version.to_string());
+
+            return Outcome::Failure((
+                Status::new(460),
+                ResponseError::IncompatibleProtocolVersion(client_version.to_string(), PROTOCOL_VERSION),
+            ));
+        }
+
+        Outcome::Success(Self)
+    }
+}
+
 /// Implements the signature verification on the incoming current contributor request via [`FromRequest`].
 pub struct CurrentContributor(Participant);
 
@@ -388,7 +690,7 @@ impl<'r> FromRequest<'r> for CurrentContributor {
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
         let pubkey = match request.verify_signature() {
             Ok(h) => h,
-            Err(e) => return Outcome::Failure((Status::new(452), e)),
+            Err(e) => return Outcome::Failure(signature_failure(e)),
         };
 
         // Check that the signature comes from the current contributor by matching the public key
@@ -426,6 +728,29 @@ impl<'r> FromRequest<'r> for CurrentContributor {
     }
 }
 
+/// Checks that the ceremony has begun, i.e. that the [Coordinator](`crate::Coordinator`) is past round 0, via [`FromRequest`].
+/// Must precede the other guards of the endpoint to report [`ResponseError::CeremonyNotStarted`] instead of their errors.
+pub struct CeremonyStarted;
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for CeremonyStarted {
+    type Error = ResponseError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let coordinator = request
+            .guard::<&State<Coordinator>>()
+            .await
+            .succeeded()
+            .expect("Managed state should always be retrievable");
+
+        if coordinator.read().await.is_ceremony_started() {
+            Outcome::Success(Self)
+        } else {
+            Outcome::Failure((Status::new(459), ResponseError::CeremonyNotStarted))
+        }
+    }
+}
+
 /// Implements the secret token verification on the incoming server request via [`FromRequest`]. Used to restrict access to endpoints only when headers contain the valid secret.
 /// Can be used as an alternative to [`ServerAuth`] when the body of the request carries no data (and thus doesn't need a signature on that)
 pub struct Secret;
@@ -442,7 +767,9 @@ impl<'r> FromRequest<'r> for Secret {
     }
 }
 
-/// Implements the signature verification on the incoming server request via [`FromRequest`].
+/// Implements the signature verification on the incoming server request via [`FromRequest`]. Restricts access to the
+/// endpoints reserved to the coordinator itself: requests which are not signed with the coordinator keypair are rejected
+/// as unauthorized.
 pub struct ServerAuth;
 
 #[rocket::async_trait]
@@ -452,7 +779,20 @@ impl<'r> FromRequest<'r> for ServerAuth {
     async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
         let pubkey = match request.verify_signature() {
             Ok(h) => h,
-            Err(e) => return Outcome::Failure((Status::new(452), e)),
+            Err(e @ ResponseError::CoordinatorError(CoordinatorError::ReplayedRequest)) => {
+                return Outcome::Failure(signature_failure(e))
+            }
+            Err(e) => {
+                // Cache error data for the error catcher
+                let participant = Participant::new_contributor(UNKNOWN);
+                request.local_cache(|| participant.clone());
+                request.local_cache(|| (request.uri().to_string(), e.to_string()));
+
+                return Outcome::Failure((
+                    Status::new(453),
+                    ResponseError::UnauthorizedParticipant(participant, request.uri().to_string(), e.to_string()),
+                ));
+            }
         };
 
         // Check that the signature comes from the coordinator by matching the default verifier key
@@ -479,10 +819,20 @@ impl<'r> FromRequest<'r> for ServerAuth {
     }
 }
 
-/// Type to handle lazy deserialization of json encoded inputs.
-pub struct LazyJson<T>(pub T);
+/// Maximum number of bytes of the json encoding of a single byte (e.g. `255,`).
+const JSON_ENCODED_BYTE_SIZE: u64 = 4;
+/// Maximum size of the parts in which a contribution is uploaded, the limit of the body of a [`LazyBytes`].
+pub const MAX_UPLOAD_PART_SIZE: u64 = 8 * 1024 * 1024;
+/// Default limit of the body of a [`LazyJson`], all the json requests but the tokens upload are small.
+pub const MAX_JSON_BODY_SIZE: u64 = 64 * 1024;
+/// Limit of the json encoding of the zip archive of the tokens uploaded to update the cohorts.
+pub const MAX_TOKENS_BODY_SIZE: u64 = JSON_ENCODED_BYTE_SIZE * 16 * 1024 * 1024;
 
-impl<T> Deref for LazyJson<T> {
+/// Type to handle lazy deserialization of json encoded inputs. Bodies larger than `MAX_SIZE` are refused before
+/// being read.
+pub struct LazyJson<T, const MAX_SIZE: u64 = MAX_JSON_BODY_SIZE>(pub T);
+
+impl<T, const MAX_SIZE: u64> Deref for LazyJson<T, MAX_SIZE> {
     type Target = T;
 
     fn deref(&self) -> &Self::Target {
@@ -490,14 +840,49 @@ impl<T> Deref for LazyJson<T> {
     }
 }
 
-impl<T> std::ops::DerefMut for LazyJson<T> {
+impl<T, const MAX_SIZE: u64> std::ops::DerefMut for LazyJson<T, MAX_SIZE> {
     fn deref_mut(&mut self) -> &mut Self::Target {
         &mut self.0
     }
 }
 
 #[rocket::async_trait]
-impl<'r, T: DeserializeOwned> FromData<'r> for LazyJson<T> {
+impl<'r, T: DeserializeOwned, const MAX_SIZE: u64> FromData<'r> for LazyJson<T, MAX_SIZE> {
+    type Error = ResponseError;
+
+    async fn from_data(req: &'r Request<'_>, data: rocket::data::Data<'r>) -> rocket::data::Outcome<'r, Self> {
+        let body = match LazyBytes::<MAX_SIZE>::from_data(req, data).await {
+            rocket::data::Outcome::Success(body) => body.0,
+            rocket::data::Outcome::Failure(e) => return rocket::data::Outcome::Failure(e),
+            rocket::data::Outcome::Forward(data) => return rocket::data::Outcome::Forward(data),
+        };
+
+        // Deserialize data and pass it to the request handler
+        match serde_json::from_slice::<T>(&body) {
+            Ok(obj) => rocket::data::Outcome::Success(LazyJson(obj)),
+            Err(e) => {
+                // Cache error data for the error catcher
+                req.local_cache(|| (e.to_string()));
+                rocket::data::Outcome::Failure((Status::new(455), ResponseError::SerdeError(e.to_string())))
+            }
+        }
+    }
+}
+
+/// Type to handle raw binary inputs, e.g. the parts of a contribution. As for [`LazyJson`], the body is only read
+/// once its announced size has been checked against `MAX_SIZE`, and it must match the digest signed by the participant.
+pub struct LazyBytes<const MAX_SIZE: u64 = MAX_UPLOAD_PART_SIZE>(pub Vec<u8>);
+
+impl<const MAX_SIZE: u64> Deref for LazyBytes<MAX_SIZE> {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[rocket::async_trait]
+impl<'r, const MAX_SIZE: u64> FromData<'r> for LazyBytes<MAX_SIZE> {
     type Error = ResponseError;
 
     async fn from_data(req: &'r Request<'_>, data: rocket::data::Data<'r>) -> rocket::data::Outcome<'r, Self> {
@@ -543,6 +928,19 @@ impl<'r, T: DeserializeOwned> FromData<'r> for LazyJson<T> {
             }
         };
 
+        // Refuse bodies larger than the limit of the route before reading them
+        if expected_content.len as u64 > MAX_SIZE {
+            warn!(
+                "Refusing a request body of {} bytes, the maximum is {}",
+                expected_content.len, MAX_SIZE
+            );
+
+            return rocket::data::Outcome::Failure((
+                Status::new(461),
+                ResponseError::CoordinatorError(CoordinatorError::ContributionFileSizeMismatch),
+            ));
+        }
+
         let body = match data.open(expected_content.len.into()).into_bytes().await {
             Ok(bytes) => bytes.into_inner(),
             Err(e) => {
@@ -566,25 +964,143 @@ impl<'r, T: DeserializeOwned> FromData<'r> for LazyJson<T> {
             ));
         }
 
-        // Deserialize data and pass it to the request handler
-        match serde_json::from_slice::<T>(&body) {
-            Ok(obj) => rocket::data::Outcome::Success(LazyJson(obj)),
-            Err(e) => {
-                // Cache error data for the error catcher
-                req.local_cache(|| (e.to_string()));
-                rocket::data::Outcome::Failure((Status::new(455), ResponseError::SerdeError(e.to_string())))
+        rocket::data::Outcome::Success(LazyBytes(body))
+    }
+}
+
+/// Json response signed with the coordinator keypair, so that the contributors can check that it comes from the
+/// genuine coordinator. The hex encoded signature is sent in the [`COORDINATOR_SIGNATURE_HEADER`], see
+/// [`SignedJson::message`] for the signed message.
+pub struct SignedJson {
+    body: String,
+    signature: String,
+}
+
+impl SignedJson {
+    /// Serializes the value and signs it, bound to the request it answers, with the keypair of the coordinator.
+    pub fn new<T: Serialize>(value: &T, request: &SignedRequest, coordinator: &crate::Coordinator) -> Result<Self> {
+        let body = serde_json::to_string(value).map_err(|e| ResponseError::SerdeError(e.to_string()))?;
+        let signature = coordinator
+            .sign_message(&Self::message(request.nonce, &request.pubkey, &body))
+            .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+        Ok(Self { body, signature })
+    }
+
+    /// Produces the signed message of the response: the nonce and the public key of the request followed by the body,
+    /// so that a response can't be replayed to another request or another participant.
+    pub fn message(nonce: u64, pubkey: &str, body: &str) -> String {
+        format!("{}{}{}", nonce, pubkey, body)
+    }
+}
+
+impl<'r> Responder<'r, 'static> for SignedJson {
+    fn respond_to(self, _request: &'r Request<'_>) -> rocket::response::Result<'static> {
+        Response::build()
+            .header(ContentType::JSON)
+            .raw_header(COORDINATOR_SIGNATURE_HEADER, self.signature)
+            .sized_body(self.body.len(), Cursor::new(self.body))
+            .ok()
+    }
+}
+
+/// Formats a request or response body for logging, truncated to `limit` bytes. `body` may hold only the beginning of
+/// the body, whose whole size is then given by `size`.
+pub fn format_traced_body(body: &[u8], size: Option<usize>, limit: usize) -> String {
+    let shown = String::from_utf8_lossy(&body[..std::cmp::min(body.len(), limit)]);
+    let size = size.unwrap_or_else(|| body.len());
+
+    if size > limit {
+        format!("{}... ({} bytes)", shown, size)
+    } else {
+        shown.into_owned()
+    }
+}
+
+/// Destination of the lines logged by the [`HttpTracer`].
+pub type HttpTraceSink = Arc<dyn Fn(String) + Send + Sync>;
+
+/// [`Fairing`] logging the method, path, status and body, truncated to the configured limit, of every request and
+/// response, to debug the issues of the contributors. Request bodies are only peeked, so at most 512 bytes of them are
+/// logged, and only the logged part of response bodies is read ahead. The tracer is silent if no limit is configured.
+pub struct HttpTracer {
+    body_limit: Option<usize>,
+    sink: HttpTraceSink,
+}
+
+impl HttpTracer {
+    /// Creates a tracer logging through `tracing`.
+    pub fn new(body_limit: Option<usize>) -> Self {
+        Self::with_sink(body_limit, Arc::new(|line| info!("{}", line)))
+    }
+
+    /// Creates a tracer logging to the given sink.
+    pub fn with_sink(body_limit: Option<usize>, sink: HttpTraceSink) -> Self {
+        Self { body_limit, sink }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for HttpTracer {
+    fn info(&self) -> Info {
+        Info {
+            name: "HTTP tracer",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, req: &mut Request<'_>, data: &mut Data<'_>) {
+        if let Some(limit) = self.body_limit {
+            let size = req
+                .headers()
+                .get_one(CONTENT_LENGTH_HEADER)
+                .and_then(|length| length.parse().ok());
+            let body = data.peek(limit).await;
+            (self.sink)(format!(
+                "--> {} {} {}",
+                req.method(),
+                req.uri(),
+                format_traced_body(body, size, limit)
+            ));
+        }
+    }
+
+    async fn on_response<'r>(&self, req: &'r Request<'_>, res: &mut Response<'r>) {
+        if let Some(limit) = self.body_limit {
+            let size = res.body_mut().size().await;
+            let mut body = res.body_mut().take();
+            let mut head = Vec::with_capacity(limit);
+            let _ = AsyncReadExt::take(&mut body, limit as u64).read_to_end(&mut head).await;
+            (self.sink)(format!(
+                "<-- {} {} {} {}",
+                req.method(),
+                req.uri(),
+                res.status(),
+                format_traced_body(&head, size, limit)
+            ));
+
+            // The beginning of the body has been consumed, put it back in front of the rest
+            if size == Some(head.len()) {
+                res.set_sized_body(head.len(), Cursor::new(head));
+            } else {
+                res.set_streamed_body(Cursor::new(head).chain(body));
             }
         }
     }
 }
 
-/// The status of the contributor related to the current round.
+/// The status of the contributor related to the current round. A contributor in the queue gets
+/// its position, the size of the queue and, if available, the estimated time it starts contributing.
+/// A contributor of the round whose heartbeats are no longer received gets the time it will be dropped at.
+/// A contributor whose contribution failed the verification gets the reason of the failure.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum ContributorStatus {
-    Queue(u64, u64),
+    Queue(u64, u64, Option<DateTime<Utc>>),
     Round,
+    Stale(DateTime<Utc>),
     Finished,
     Banned,
+    Rejected { reason: String },
     Other,
 }
 
@@ -610,6 +1126,25 @@ impl PostChunkRequest {
     }
 }
 
+/// Request to complete the upload of a [Chunk](`crate::objects::Chunk`) contribution sent in parts, with the size and
+/// hex encoded hash of the whole contribution to detect a truncated upload.
+#[derive(Clone, Deserialize, Serialize)]
+pub struct FinalizeChunkUploadRequest {
+    pub contribution_locator: ContributionLocator,
+    pub size: u64,
+    pub hash: String,
+}
+
+impl FinalizeChunkUploadRequest {
+    pub fn new(contribution_locator: ContributionLocator, contribution: &[u8]) -> Self {
+        Self {
+            contribution_locator,
+            size: contribution.len() as u64,
+            hash: hex::encode(calculate_hash(contribution)),
+        }
+    }
+}
+
 /// Checks the validity of the token for the ceremony.
 /// Returns the current cohort index
 pub(crate) async fn token_check(coordinator: Coordinator, token: &str) -> Result<u64> {
@@ -634,14 +1169,23 @@ pub(crate) async fn token_check(coordinator: Coordinator, token: &str) -> Result
         None => return Err(ResponseError::CeremonyIsOver),
     };
 
-    if !tokens.contains(token) {
+    // A priority token is valid in any cohort
+    if !tokens.contains(token) && !read_lock.state().is_priority_token(token) {
+        // Tell the holders of the tokens of a future cohort when they can join
+        if let Some(token_cohort) = read_lock.state().token_cohort_index(token).filter(|c| *c > cohort) {
+            let opens_at = read_lock.state().cohort_schedule()[token_cohort].start;
+            return Err(ResponseError::CohortNotStarted(token_cohort as u64 + 1, opens_at));
+        }
+
         return Err(ResponseError::InvalidToken(cohort + 1));
     }
 
     Ok((cohort + 1) as u64)
 }
 
-/// Performs the verification of the pending contributions
+/// Verifies the contributions pending verification with the default verifier of the [Coordinator](`crate::Coordinator`),
+/// returning the number of verified contributions. The write lock is taken for a single contribution at a time, so
+/// that the requests are served in between the verifications.
 ///
 /// # Cancel safety
 ///
@@ -649,45 +1193,73 @@ pub(crate) async fn token_check(coordinator: Coordinator, token: &str) -> Result
 ///
 /// Because of the use of [`tokio::sync::rwlock::RwLock::write_owned`], which is not cancel safe, and a spawned blocking
 /// task, which cannot be cancelled, this function is not cancel safe.
-pub async fn perform_verify_chunks(coordinator: Coordinator, s3_ctx: &S3Ctx) -> Result<()> {
-    // Get all the pending verifications, loop on each one of them and perform verification
-    // Technically, since we don't chunk contributions and we only have one contribution per round, we will always get
-    // one pending verification at max.
-    let mut write_lock = coordinator.write_owned().await;
+pub async fn verify_pending_contributions(coordinator: Coordinator) -> Result<usize> {
+    let tasks: Vec<Task> = coordinator
+        .read()
+        .await
+        .get_pending_verifications()
+        .into_keys()
+        .collect();
+    let mut verified = 0;
 
     // NOTE: we are going to rely on the single default verifier built in the coordinator itself,
     //  no external verifiers
-    let contributions_info = task::spawn_blocking(move || -> Result<Vec<u8>> {
-        for (task, _) in write_lock.get_pending_verifications().to_owned() {
+    for task in tasks {
+        let mut write_lock = coordinator.clone().write_owned().await;
+
+        verified += task::spawn_blocking(move || -> Result<usize> {
+            // The task might have been dropped in the meantime, e.g. by the reset of the round
+            if !write_lock.get_pending_verifications().contains_key(&task) {
+                return Ok(0);
+            }
+
             if let Err(e) = write_lock.default_verify(&task) {
+                // A contribution verified by a previous invocation doesn't need to be verified again
+                if let Some(CoordinatorError::ContributionAlreadyVerified) = e.downcast_ref::<CoordinatorError>() {
+                    debug!("Task {} has already been verified, skipping", task);
+                    return Ok(0);
+                }
+
                 warn!("Error while verifying a contribution: {}. Restarting the round...", e);
                 // FIXME: the verify_masp function may panic but the program doesn't shut down because we are executing it on a separate thread. It would be better though to make that function return a Result instead of panicking. Revert of round should be moved inside default_verify
+                let reason = rejection_reason(&e);
 
-                // Get the participant who produced the contribution
-                let finished_contributor = write_lock
-                    .state()
-                    .current_round_finished_contributors()
-                    .unwrap()
-                    .first()
-                    .unwrap()
-                    .clone();
-
-                // Reset the round to prevent a coordinator stall (the corrupted contribution is not automatically dropped)
+                // Reset the round and ban the participant who produced the invalid contribution
                 write_lock
-                    .reset_round()
+                    .reject_contribution(reason)
                     .map_err(|e| ResponseError::CoordinatorError(e))?;
 
-                // Ban the participant who produced the invalid contribution. Must be banned after the reset beacuse one can't ban a finished contributor
-                write_lock
-                    .ban_participant(&finished_contributor)
-                    .map_err(|e| ResponseError::CoordinatorError(e))?;
+                return Ok(0);
             }
-        }
 
-        write_lock
-            .storage()
-            .get_contributions_summary()
-            .map_err(|e| ResponseError::CoordinatorError(e))
+            Ok(1)
+        })
+        .await??;
+    }
+
+    Ok(verified)
+}
+
+/// Returns the reason reported to the contributor whose contribution failed the verification with the given error.
+/// Only the known verification failures are detailed, the other errors could leak the internals of the coordinator,
+/// e.g. its paths, and are only logged.
+pub fn rejection_reason(error: &anyhow::Error) -> String {
+    match error.downcast_ref::<CoordinatorError>() {
+        Some(CoordinatorError::ContributionVerificationFailed { reason }) => reason.to_string(),
+        _ => String::from("the contribution could not be verified"),
+    }
+}
+
+/// Publishes the summary of the contributions to S3, with the identity of the contributors redacted if required.
+pub async fn publish_contributions_summary(coordinator: Coordinator, s3_ctx: &S3Ctx) -> Result<()> {
+    let read_lock = coordinator.read_owned().await;
+
+    let contributions_info = task::spawn_blocking(move || -> Result<Vec<u8>> {
+        let summary = read_lock
+            .get_public_contributions_summary(None, None, None, None)
+            .map_err(|e| ResponseError::CoordinatorError(e))?;
+
+        serde_json::to_vec(&summary).map_err(|e| ResponseError::SerdeError(e.to_string()))
     })
     .await??;
 
@@ -698,6 +1270,70 @@ pub async fn perform_verify_chunks(coordinator: Coordinator, s3_ctx: &S3Ctx) ->
         .map_err(|e| ResponseError::CoordinatorError(CoordinatorError::Error(anyhow!(e.to_string()))))
 }
 
+/// Verifies all the pending contributions and publishes the updated summary of the contributions.
+///
+/// # Cancel safety
+///
+/// https://docs.rs/tokio/latest/tokio/macro.select.html#cancellation-safety
+///
+/// Because of the use of [`tokio::sync::rwlock::RwLock::write_owned`], which is not cancel safe, and a spawned blocking
+/// task, which cannot be cancelled, this function is not cancel safe.
+pub async fn perform_verify_chunks(coordinator: Coordinator, s3_ctx: &S3Ctx) -> Result<()> {
+    verify_pending_contributions(coordinator.clone()).await?;
+    publish_contributions_summary(coordinator, s3_ctx).await
+}
+
+/// Handle to the background worker verifying the contributions, so that the `/verify` endpoint only has to wake it up
+/// instead of verifying under the write lock of the request. The queue of the pending verifications is part of the
+/// state of the [Coordinator](`crate::Coordinator`), persisted in storage, so that the worker resumes the verifications
+/// left pending by a restart.
+#[derive(Clone, Default)]
+pub struct VerificationWorker {
+    wake: Arc<Notify>,
+}
+
+impl VerificationWorker {
+    /// Wakes the worker up to verify the pending contributions. A wake up received while the worker is busy is not
+    /// lost: the worker runs again right after.
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+
+    /// Runs the worker until the shutdown signal is received. The pending contributions are verified whenever the
+    /// worker is woken up, or every `interval` otherwise, and the summary of the contributions is then published to S3
+    /// if a context is provided.
+    pub async fn run(
+        &self,
+        coordinator: Coordinator,
+        s3_ctx: Option<S3Ctx>,
+        interval: Duration,
+        shutdown: Receiver<bool>,
+    ) -> Result<()> {
+        loop {
+            rocket::tokio::select! {
+                _ = self.wake.notified() => (),
+                _ = rocket::tokio::time::sleep(interval) => (),
+            }
+
+            // Return if shutdown signal has been received on the channel
+            if *shutdown.borrow() {
+                info!("Received shutdown signal, exiting verification worker");
+                return Ok(());
+            }
+
+            let start = std::time::Instant::now();
+            let verified = verify_pending_contributions(coordinator.clone()).await?;
+            if let Some(s3_ctx) = &s3_ctx {
+                publish_contributions_summary(coordinator.clone(), s3_ctx).await?;
+            }
+
+            if verified > 0 {
+                info!("Verified {} contributions in {:#?}", verified, start.elapsed());
+            }
+        }
+    }
+}
+
 /// Performs the update of the [Coordinator](`crate::Coordinator`)
 ///
 /// # Cancel safety
@@ -713,3 +1349,13 @@ pub async fn perform_coordinator_update(coordinator: Coordinator) -> Result<()>
         .await?
         .map_err(|e| ResponseError::CoordinatorError(e))
 }
+
+/// Fails with [`CoordinatorError::CeremonyClosed`] if the [Coordinator](`crate::Coordinator`) is read-only, to guard the
+/// endpoints mutating the ceremony.
+pub async fn check_writable(coordinator: &Coordinator) -> Result<()> {
+    coordinator
+        .read()
+        .await
+        .check_writable()
+        .map_err(|e| ResponseError::CoordinatorError(e))
+}
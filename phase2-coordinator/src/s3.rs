@@ -16,6 +16,8 @@ use std::str::FromStr;
 use thiserror::Error;
 use tracing::warn;
 
+use crate::storage::{ContributionLocator, ContributionSignatureLocator};
+
 pub const TOKENS_ZIP_FILE: &str = "tokens.zip";
 const BACKOFF_SLEEP_TIME_MILLISECS: u32 = 100;
 const MAX_REQUEST_RETRY: u32 = 8; // This gives max 50 seconds before giving up and returning an error
@@ -34,6 +36,34 @@ lazy_static! {
     };
 }
 
+/// Returns the key of a contribution in the bucket, which mirrors its path in the transcript of the coordinator, e.g.
+/// `round_1/chunk_0/contribution_1.unverified`.
+pub fn contribution_key(contribution_locator: &ContributionLocator) -> String {
+    format!(
+        "round_{}/chunk_{}/contribution_{}.{}",
+        contribution_locator.round_height(),
+        contribution_locator.chunk_id(),
+        contribution_locator.contribution_id(),
+        if contribution_locator.is_verified() {
+            "verified"
+        } else {
+            "unverified"
+        }
+    )
+}
+
+/// Returns the key of the signature of a contribution in the bucket, next to the contribution.
+pub fn contribution_signature_key(signature_locator: &ContributionSignatureLocator) -> String {
+    let contribution_locator = ContributionLocator::new(
+        signature_locator.round_height(),
+        signature_locator.chunk_id(),
+        signature_locator.contribution_id(),
+        signature_locator.is_verified(),
+    );
+
+    format!("{}.signature", contribution_key(&contribution_locator))
+}
+
 #[derive(Error, Debug)]
 pub enum S3Error {
     #[error("Error while creating the http client: {0}")]
@@ -231,15 +261,19 @@ impl S3Ctx {
     }
 
     /// Get the urls of a contribution and its signature.
-    pub(crate) fn get_contribution_urls(&self, contrib_key: String, contrib_sig_key: String) -> (String, String) {
+    pub(crate) fn get_contribution_urls(
+        &self,
+        contribution_locator: &ContributionLocator,
+        signature_locator: &ContributionSignatureLocator,
+    ) -> (String, String) {
         let get_contrib = PutObjectRequest {
             bucket: self.bucket.clone(),
-            key: contrib_key,
+            key: contribution_key(contribution_locator),
             ..Default::default()
         };
         let get_sig = PutObjectRequest {
             bucket: self.bucket.clone(),
-            key: contrib_sig_key,
+            key: contribution_signature_key(signature_locator),
             ..Default::default()
         };
 
@@ -289,19 +323,35 @@ impl S3Ctx {
     }
 
     /// Retrieve a contribution and its signature from S3.
-    pub(crate) async fn get_contribution(&self, round_height: u64) -> Result<(Vec<u8>, Vec<u8>)> {
+    pub(crate) async fn get_contribution(
+        &self,
+        contribution_locator: &ContributionLocator,
+        signature_locator: &ContributionSignatureLocator,
+    ) -> Result<(Vec<u8>, Vec<u8>)> {
         let get_contrib = GetObjectRequest {
             bucket: self.bucket.clone(),
-            key: format!("round_{}/chunk_0/contribution_1.unverified", round_height),
+            key: contribution_key(contribution_locator),
             ..Default::default()
         };
+
+        rocket::tokio::try_join!(
+            self.get_object(get_contrib),
+            self.get_contribution_signature(signature_locator)
+        )
+    }
+
+    /// Retrieve the signature of a contribution from S3.
+    pub(crate) async fn get_contribution_signature(
+        &self,
+        signature_locator: &ContributionSignatureLocator,
+    ) -> Result<Vec<u8>> {
         let get_sig = GetObjectRequest {
             bucket: self.bucket.clone(),
-            key: format!("round_{}/chunk_0/contribution_1.unverified.signature", round_height),
+            key: contribution_signature_key(signature_locator),
             ..Default::default()
         };
 
-        rocket::tokio::try_join!(self.get_object(get_contrib), self.get_object(get_sig))
+        self.get_object(get_sig).await
     }
 
     /// Retrieve the compressed token folder.
@@ -1,33 +1,53 @@
 use crate::{
     environment::Environment,
-    objects::{ContributionFileSignature, ContributionInfo, Round, TrimmedContributionInfo},
+    objects::{ContributionFileSignature, ContributionInfo, ContributionProvenance, Round, TrimmedContributionInfo},
     storage::{
-        ContributionLocator, ContributionSignatureLocator, Locator, Object, ObjectReader, ObjectWriter, StorageLocator,
-        StorageObject,
+        encryption, ContributionLocator, ContributionSignatureLocator, Locator, Object, ObjectReader, ObjectWriter,
+        StorageLocator, StorageObject,
     },
     CoordinatorError, CoordinatorState,
 };
 
 use anyhow::Result;
+use blake2::{Blake2b512, Digest};
 use fs_err::{self as fs, File, OpenOptions};
 use itertools::Itertools;
 use memmap::MmapOptions;
 
 use std::{
+    collections::{BTreeSet, HashMap},
     convert::TryFrom,
-    io::{Read, Write},
+    io::{Read, Seek, SeekFrom, Write},
     ops::{Deref, DerefMut},
     path::{Path, PathBuf},
     str::FromStr,
+    time::{Duration, Instant},
 };
 use tracing::{debug, error, trace};
 
 use super::{LocatorPath, StorageAction};
 
+/// Size of the blocks in which an upload is read to be hashed.
+const UPLOAD_HASH_BLOCK_SIZE: usize = 1024 * 1024;
+
+/// Disk storage of the ceremony.
+///
+/// If the [Environment] sets a storage batch interval, the updates of the coordinator state, round height and
+/// round states are kept in memory and coalesced: they are persisted at most once per interval, on an explicit
+/// [`Disk::flush`] (round transition and shutdown) and when the storage is dropped. Any other object is persisted
+/// immediately. Without a batch interval, every update is persisted immediately.
+///
+/// If the [Environment] sets a storage archive directory, the rounds completed are moved there by
+/// [`Disk::archive_round`], e.g. to a cheaper or remote file system, while the current round stays in the base
+/// directory. The locators of the archived rounds are transparently resolved to the archive.
 #[derive(Debug)]
 pub struct Disk {
     environment: Environment,
     resolver: DiskResolver,
+    archive: Option<DiskResolver>,
+    batch_interval: Option<Duration>,
+    pending: HashMap<Locator, Object>,
+    last_flush: Instant,
 }
 
 impl Disk {
@@ -45,10 +65,22 @@ impl Disk {
             fs::create_dir_all(contributors_dir).expect("unable to create the contributors directory");
         }
 
+        // Check the private contribution provenance directory exists.
+        let provenance_dir = Path::new(environment.local_base_directory()).join("provenance");
+        if !provenance_dir.exists() {
+            fs::create_dir_all(provenance_dir).expect("unable to create the provenance directory");
+        }
+
         // Create a new `Storage` instance, and set the `Environment`.
         let mut storage = Self {
             environment: environment.clone(),
             resolver: DiskResolver::new(environment.local_base_directory()),
+            archive: environment.storage_archive_directory().map(DiskResolver::new),
+            batch_interval: environment
+                .storage_batch_interval()
+                .and_then(|interval| Duration::try_from(interval).ok()),
+            pending: HashMap::new(),
+            last_flush: Instant::now(),
         };
 
         // Create the coordinator state locator if it does not exist yet.
@@ -91,6 +123,11 @@ impl Disk {
                 .chunk_directory_init(contribution_locator.round_height(), contribution_locator.chunk_id());
         }
 
+        // Initialize the directory of the file, e.g. of the state of a new round.
+        if let Some(parent) = locator_path.as_path().parent() {
+            fs::create_dir_all(parent)?;
+        }
+
         // Open the file.
         let file = OpenOptions::new()
             .read(true)
@@ -149,6 +186,10 @@ impl Disk {
             return Err(CoordinatorError::StorageLocatorMissing);
         }
 
+        if let Some(object) = self.pending.get(&Locator::CoordinatorState) {
+            return Ok(object.to_bytes());
+        }
+
         let path = self.to_path(&Locator::CoordinatorState)?;
 
         Ok(fs::read(path)?)
@@ -165,8 +206,14 @@ impl Disk {
             return Err(CoordinatorError::StorageLocatorMissing);
         }
 
+        // Return the batched object, if any, as it's more recent than the file
+        if let Some(object) = self.pending.get(locator) {
+            trace!("Fetched batched {}", path);
+            return Ok(object.clone());
+        }
+
         // read the file to a byte array
-        let file_bytes = fs::read(path)?;
+        let file_bytes = self.decrypt(locator, fs::read(path)?)?;
 
         let object = match locator {
             Locator::CoordinatorState => {
@@ -195,10 +242,11 @@ impl Disk {
             }
             Locator::ContributionFile(contribution_locator) => {
                 // Check that the contribution size is correct.
-                let expected_size = Object::anoma_contribution_file_size(
+                let expected_size = Object::ceremony_contribution_file_size(
+                    &self.environment,
                     contribution_locator.round_height(),
                     contribution_locator.contribution_id(),
-                );
+                )?;
                 let found_size = file_bytes.len() as u64;
                 debug!(
                     "Round {} chunk {} filesize is {}",
@@ -248,6 +296,10 @@ impl Disk {
                 let summary: Vec<TrimmedContributionInfo> = serde_json::from_slice(&file_bytes)?;
                 Ok(Object::ContributionsInfoSummary(summary))
             }
+            Locator::ContributionProvenanceFile { round_height: _ } => {
+                let provenance: Vec<ContributionProvenance> = serde_json::from_slice(&file_bytes)?;
+                Ok(Object::ContributionProvenanceFile(provenance))
+            }
         };
 
         trace!("Fetched {}", self.to_path(locator)?);
@@ -285,13 +337,317 @@ impl Disk {
             return Err(CoordinatorError::StorageLocatorMissing);
         }
 
-        let mut file = OpenOptions::new().write(true).open(path)?;
-        file.set_len(object.size())?;
-        file.write_all(&object.to_bytes())?;
+        if let Some(interval) = self.batch_interval {
+            if Self::is_batchable(locator) {
+                self.pending.insert(*locator, object);
+                trace!("Batched update of {}", path);
+
+                if self.last_flush.elapsed() >= interval {
+                    self.flush()?;
+                }
+
+                return Ok(());
+            }
+        }
+
+        self.write(locator, &object)?;
+
+        trace!("Updated {}", self.to_path(&locator)?);
+        Ok(())
+    }
+
+    /// Updates an existing object for the given locator in storage, bypassing the batching: the update is synced to disk
+    /// when this returns, and supersedes any batched update of the same locator. The object is replaced with a rename,
+    /// so that a crash while writing leaves the previous object in place.
+    pub fn update_durably(&mut self, locator: &Locator, object: Object) -> Result<(), CoordinatorError> {
+        // Check that the given locator exists in storage.
+        if !self.exists(locator) {
+            error!("Locator missing in call to update_durably() in storage.");
+            return Err(CoordinatorError::StorageLocatorMissing);
+        }
+
+        self.pending.remove(locator);
+        self.replace(locator, &object)?;
+
+        trace!("Durably updated {}", self.to_path(locator)?);
+        Ok(())
+    }
+
+    /// Atomically moves the round height from `expected_height`, `None` if no round height has been stored yet, to
+    /// `new_height`, storing first the given round at `new_height`, if any. If the current round height is not
+    /// `expected_height`, the storage is left untouched and a `RoundHeightMismatch` error is returned.
+    ///
+    /// The round and the height bypass the batching: each file is replaced with a single rename, the round before the
+    /// height, so that a reader never observes a height pointing at a missing or partially written round.
+    pub fn compare_and_set_round_height(
+        &mut self,
+        expected_height: Option<u64>,
+        new_height: u64,
+        round: Option<Round>,
+    ) -> Result<(), CoordinatorError> {
+        let current_height = match self.exists(&Locator::RoundHeight) {
+            true => match self.get(&Locator::RoundHeight)? {
+                Object::RoundHeight(round_height) => Some(round_height),
+                _ => return Err(CoordinatorError::StorageFailed),
+            },
+            false => None,
+        };
+        if current_height != expected_height {
+            error!(
+                "Expected the round height to be {:?} but found {:?}",
+                expected_height, current_height
+            );
+            return Err(CoordinatorError::RoundHeightMismatch);
+        }
+
+        // Persist the batched updates first, so that the next flush doesn't overwrite the new round and height
+        self.flush()?;
+
+        if let Some(round) = round {
+            let locator = Locator::RoundState {
+                round_height: new_height,
+            };
+            self.replace(&locator, &Object::RoundState(round))?;
+        }
+        self.replace(&Locator::RoundHeight, &Object::RoundHeight(new_height))?;
+
+        trace!("Moved the round height from {:?} to {}", expected_height, new_height);
+        Ok(())
+    }
+
+    /// Moves the directory of the given round, which must be completed, to the archive, if any. The files of the round
+    /// are then read and written from there.
+    pub fn archive_round(&mut self, round_height: u64) -> Result<(), CoordinatorError> {
+        let archive = match &self.archive {
+            Some(archive) => archive,
+            None => return Ok(()),
+        };
+
+        let source = self.resolver.round_directory(round_height);
+        if !Path::new(&source).is_dir() {
+            debug!("Round {} is already archived", round_height);
+            return Ok(());
+        }
+        let destination = archive.round_directory(round_height);
+
+        // The batched updates of the round must reach its files before they move
+        self.flush()?;
+
+        fs::create_dir_all(&archive.base)?;
+        Self::move_dir(Path::new(&source), Path::new(&destination))?;
+
+        debug!("Archived round {} to {}", round_height, destination);
+        Ok(())
+    }
+
+    /// Lists the heights of the rounds whose state is stored, locally, in the archive or in the batched updates, in
+    /// increasing order. Only the round directories are listed, rather than looking up every round height.
+    pub fn round_heights(&self) -> Result<Vec<u64>, CoordinatorError> {
+        let mut round_heights: BTreeSet<u64> = self
+            .pending
+            .keys()
+            .filter_map(|locator| match locator {
+                Locator::RoundState { round_height } => Some(*round_height),
+                _ => None,
+            })
+            .collect();
+
+        for resolver in std::iter::once(&self.resolver).chain(self.archive.as_ref()) {
+            if !Path::new(&resolver.base).is_dir() {
+                continue;
+            }
+
+            for entry in fs::read_dir(&resolver.base)? {
+                let name = entry?.file_name();
+                let round_height = match name
+                    .to_str()
+                    .and_then(|name| name.strip_prefix("round_"))
+                    .and_then(|round_height| round_height.parse().ok())
+                {
+                    Some(round_height) => round_height,
+                    None => continue,
+                };
+
+                let locator = Locator::RoundState { round_height };
+                if fs::metadata(resolver.to_path(&locator)?).map_or(false, |metadata| metadata.is_file()) {
+                    round_heights.insert(round_height);
+                }
+            }
+        }
+
+        Ok(round_heights.into_iter().collect())
+    }
+
+    /// Returns the directory of the given round: the local one, unless the round has been archived.
+    fn round_directory(&self, round_height: u64) -> String {
+        let local_directory = self.resolver.round_directory(round_height);
+
+        match &self.archive {
+            // A round being archived is still read locally until it's fully moved
+            Some(archive) if !Path::new(&local_directory).exists() => {
+                let archived_directory = archive.round_directory(round_height);
+                match Path::new(&archived_directory).exists() {
+                    true => archived_directory,
+                    false => local_directory,
+                }
+            }
+            _ => local_directory,
+        }
+    }
+
+    /// Moves a directory, copying it if it can't be renamed, e.g. to another file system.
+    fn move_dir(source: &Path, destination: &Path) -> Result<(), CoordinatorError> {
+        if fs::rename(source, destination).is_ok() {
+            return Ok(());
+        }
+
+        Self::copy_dir(source, destination)?;
+        fs::remove_dir_all(source)?;
+
+        Ok(())
+    }
+
+    /// Copies recursively the content of a directory.
+    fn copy_dir(source: &Path, destination: &Path) -> Result<(), CoordinatorError> {
+        fs::create_dir_all(destination)?;
+        for entry in fs::read_dir(source)? {
+            let entry = entry?;
+            let path = entry.path();
+            let target = destination.join(entry.file_name());
+            if path.is_dir() {
+                Self::copy_dir(&path, &target)?;
+            } else {
+                fs::copy(&path, &target)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Persists all the batched updates to disk.
+    pub fn flush(&mut self) -> Result<(), CoordinatorError> {
+        let pending = std::mem::take(&mut self.pending);
+        for (locator, object) in pending {
+            self.write(&locator, &object)?.sync_data()?;
+        }
+        self.last_flush = Instant::now();
+
+        trace!("Flushed batched updates");
+        Ok(())
+    }
+
+    /// Captures the whole content of the storage, including the batched updates not persisted yet, so that it can
+    /// be brought back with [`Disk::restore`].
+    pub fn snapshot(&self) -> Result<DiskSnapshot, CoordinatorError> {
+        let mut files = HashMap::new();
+        Self::read_dir_files(Path::new(self.environment.local_base_directory()), &mut files)?;
+
+        Ok(DiskSnapshot {
+            files,
+            pending: self.pending.clone(),
+        })
+    }
+
+    /// Brings the storage back to the state captured by the given snapshot. The files created after the snapshot
+    /// are removed.
+    pub fn restore(&mut self, snapshot: DiskSnapshot) -> Result<(), CoordinatorError> {
+        let base_directory = Path::new(self.environment.local_base_directory());
+        let mut current_files = HashMap::new();
+        Self::read_dir_files(base_directory, &mut current_files)?;
+
+        for path in current_files.keys().filter(|path| !snapshot.files.contains_key(*path)) {
+            fs::remove_file(path)?;
+        }
+        for (path, content) in snapshot.files {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, content)?;
+        }
+        self.pending = snapshot.pending;
+
+        trace!("Restored storage snapshot");
+        Ok(())
+    }
+
+    /// Reads the content of all the files in the given directory and its subdirectories.
+    fn read_dir_files(path: &Path, files: &mut HashMap<PathBuf, Vec<u8>>) -> Result<(), CoordinatorError> {
+        for entry in fs::read_dir(path)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                Self::read_dir_files(&path, files)?;
+            } else {
+                let content = fs::read(&path)?;
+                files.insert(path, content);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Decrypts the content of the file at the given locator, if it has been encrypted at rest.
+    fn decrypt(&self, locator: &Locator, data: Vec<u8>) -> Result<Vec<u8>, CoordinatorError> {
+        match (locator, self.environment.storage_encryption_key()) {
+            (Locator::ContributionFile(_), Some(key)) => encryption::decrypt(key, data),
+            _ => Ok(data),
+        }
+    }
+
+    /// Returns `true` if the updates of the given locator can be batched.
+    fn is_batchable(locator: &Locator) -> bool {
+        matches!(
+            locator,
+            Locator::CoordinatorState | Locator::RoundHeight | Locator::RoundState { .. }
+        )
+    }
+
+    /// Returns the bytes of the object as stored at the given locator, encrypted if the locator is encrypted at rest.
+    fn to_stored_bytes(&self, locator: &Locator, object: &Object) -> Result<Vec<u8>, CoordinatorError> {
+        match (locator, self.environment.storage_encryption_key()) {
+            (Locator::ContributionFile(_), Some(key)) => encryption::encrypt(key, &object.to_bytes()),
+            _ => Ok(object.to_bytes()),
+        }
+    }
+
+    /// Writes the object at the given locator and returns the written file.
+    fn write(&self, locator: &Locator, object: &Object) -> Result<File, CoordinatorError> {
+        let bytes = self.to_stored_bytes(locator, object)?;
+
+        let mut file = OpenOptions::new().write(true).open(self.to_path(locator)?)?;
+        file.set_len(bytes.len() as u64)?;
+        file.write_all(&bytes)?;
         // Sync all in-memory data to disk.
         file.flush()?;
 
-        trace!("Updated {}", self.to_path(&locator)?);
+        Ok(file)
+    }
+
+    /// Replaces the file at the given locator, creating it if missing, by renaming over it a temporary file holding the
+    /// object. The temporary file is only renamed once it has been fully written, and the rename is synced.
+    fn replace(&self, locator: &Locator, object: &Object) -> Result<(), CoordinatorError> {
+        let path = self.to_path(locator)?;
+        let tmp_path = format!("{}.tmp", path);
+        let bytes = self.to_stored_bytes(locator, object)?;
+
+        // The directory of the file may not exist yet, e.g. for the round about to start
+        if let Some(parent) = path.as_path().parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&tmp_path)?;
+        file.write_all(&bytes)?;
+        file.sync_data()?;
+        fs::rename(&tmp_path, &path)?;
+
+        // The rename is only durable once the directory is synced
+        if let Some(parent) = path.as_path().parent() {
+            File::open(parent)?.sync_all()?;
+        }
+
         Ok(())
     }
 
@@ -343,11 +699,45 @@ impl Disk {
         // detect whether the path is a directory of a file and call
         // the appropriate function.
         fs::remove_file(path.clone())?;
+        self.pending.remove(locator);
 
         trace!("Removed {}", path);
         Ok(())
     }
 
+    /// Writes the file at the given locator anew with `rewrite`. The previous file is moved aside meanwhile, and is
+    /// only discarded once `rewrite` succeeds: it is put back if `rewrite` fails, so that a failed rewrite never loses
+    /// it.
+    pub fn rewrite<T, F>(&mut self, locator: &Locator, rewrite: F) -> Result<T, CoordinatorError>
+    where
+        F: FnOnce(&mut Self) -> Result<T, CoordinatorError>,
+    {
+        let path = self.to_path(locator)?;
+        let previous_path = format!("{}.previous", path);
+        let existed = self.exists(locator);
+        if existed {
+            fs::rename(path.as_path(), &previous_path)?;
+        }
+
+        match rewrite(self) {
+            Ok(value) => {
+                if existed {
+                    fs::remove_file(&previous_path)?;
+                }
+                trace!("Rewrote {}", path);
+                Ok(value)
+            }
+            Err(error) => {
+                if existed {
+                    fs::rename(&previous_path, path.as_path())?;
+                } else if self.exists(locator) {
+                    self.remove(locator)?;
+                }
+                Err(error)
+            }
+        }
+    }
+
     /// Returns the size of the object stored at the given locator.
     pub fn size(&self, locator: &Locator) -> Result<u64, CoordinatorError> {
         let path = self.to_path(locator)?;
@@ -359,11 +749,330 @@ impl Disk {
             return Err(CoordinatorError::StorageLocatorMissing);
         }
 
+        if let Some(object) = self.pending.get(locator) {
+            return Ok(object.size());
+        }
+
         // Open the file.
         let file = OpenOptions::new().read(true).write(true).open(path.clone())?;
+        let size = file.metadata()?.len();
 
         trace!("Fetched size of {}", path);
-        Ok(file.metadata()?.len())
+        match (locator, self.environment.storage_encryption_key()) {
+            (Locator::ContributionFile(_), Some(_)) => {
+                // The size of an encrypted contribution is the one of its plaintext.
+                let mut header = Vec::with_capacity(encryption::HEADER_LENGTH);
+                file.file()
+                    .take(encryption::HEADER_LENGTH as u64)
+                    .read_to_end(&mut header)?;
+
+                Ok(encryption::plaintext_size(&header, size))
+            }
+            _ => Ok(size),
+        }
+    }
+
+    /// Returns a reader streaming the object stored at the given locator from disk, rather than loading it in memory
+    /// like [`reader`](StorageObject::reader). Encrypted contribution files can only be authenticated as a whole, so
+    /// they're still decrypted in memory.
+    pub fn stream_reader(&self, locator: &Locator) -> Result<Box<dyn Read>, CoordinatorError> {
+        let path = self.to_path(locator)?;
+
+        // Check that the locator exists in storage.
+        if !self.exists(locator) {
+            error!("Locator {} missing in call to stream_reader() in storage.", path);
+            return Err(CoordinatorError::StorageLocatorMissing);
+        }
+
+        if let Some(object) = self.pending.get(locator) {
+            return Ok(Box::new(std::io::Cursor::new(object.to_bytes())));
+        }
+
+        if let (Locator::ContributionFile(_), Some(_)) = (locator, self.environment.storage_encryption_key()) {
+            return Ok(Box::new(std::io::Cursor::new(self.reader(locator)?.data)));
+        }
+
+        let file = OpenOptions::new().read(true).open(path)?;
+
+        if let Locator::ContributionFile(contribution_locator) = locator {
+            // Check that the contribution size is correct.
+            let expected_size = Object::ceremony_contribution_file_size(
+                &self.environment,
+                contribution_locator.round_height(),
+                contribution_locator.contribution_id(),
+            )?;
+            let found_size = file.metadata()?.len();
+            if found_size != expected_size {
+                error!(
+                    "Contribution file size should be {} but found {}",
+                    expected_size, found_size
+                );
+                return Err(CoordinatorError::ContributionFileSizeMismatch);
+            }
+        }
+
+        Ok(Box::new(file))
+    }
+
+    /// Writes a part of the upload of the given contribution starting at `offset` and returns the number of bytes
+    /// committed so far. A part starting before the committed size (a retried part) overwrites the upload from its
+    /// offset on, while a part starting past it is rejected as it would leave a gap in the contribution.
+    pub fn write_upload_part(
+        &self,
+        contribution_locator: &ContributionLocator,
+        offset: u64,
+        bytes: &[u8],
+    ) -> Result<u64, CoordinatorError> {
+        let path = self.upload_path(contribution_locator, false)?;
+        trace!("Writing upload part at offset {} of {}", offset, path.display());
+
+        let expected_size = Object::ceremony_contribution_file_size(
+            &self.environment,
+            contribution_locator.round_height(),
+            contribution_locator.contribution_id(),
+        )?;
+        let end = offset.saturating_add(bytes.len() as u64);
+        if end > expected_size {
+            error!(
+                "Upload part of {} ends at {} past the contribution file size of {}",
+                path.display(),
+                end,
+                expected_size
+            );
+            return Err(CoordinatorError::ContributionFileSizeMismatch);
+        }
+
+        let mut file = OpenOptions::new().read(true).write(true).create(true).open(&path)?;
+        let nonce = self.upload_nonce(&mut file, true)?;
+        let header_length = nonce.as_ref().map_or(0, |_| encryption::UPLOAD_HEADER_LENGTH as u64);
+        let committed = file.metadata()?.len() - header_length;
+        if offset > committed {
+            error!(
+                "Upload part of {} starts at {} but only {} bytes are committed",
+                path.display(),
+                offset,
+                committed
+            );
+            return Err(CoordinatorError::ContributionUploadGap {
+                expected: committed,
+                found: offset,
+            });
+        }
+
+        file.set_len(header_length + offset)?;
+        file.seek(SeekFrom::Start(header_length + offset))?;
+        match nonce {
+            Some(nonce) => {
+                let mut ciphertext = bytes.to_vec();
+                encryption::apply_upload_keystream(self.upload_key()?, &nonce, offset, &mut ciphertext);
+                file.write_all(&ciphertext)?;
+            }
+            None => file.write_all(bytes)?,
+        }
+        file.sync_data()?;
+
+        Ok(end)
+    }
+
+    /// Completes the upload of the given contribution, checking that its size matches both the expected contribution
+    /// file size and the `size` announced by the contributor, and that its content matches the hex encoded `hash`
+    /// of the contribution, so that a truncated or corrupted upload is rejected before reaching the verification.
+    pub fn finalize_upload(
+        &self,
+        contribution_locator: &ContributionLocator,
+        size: u64,
+        hash: &str,
+    ) -> Result<(), CoordinatorError> {
+        let path = self.upload_path(contribution_locator, false)?;
+
+        let expected_size = Object::ceremony_contribution_file_size(
+            &self.environment,
+            contribution_locator.round_height(),
+            contribution_locator.contribution_id(),
+        )?;
+        let (found_size, found_hash) = self.hash_upload(&path)?;
+        if found_size != expected_size || found_size != size {
+            error!(
+                "Uploaded contribution file size should be {} ({} announced) but found {}",
+                expected_size, size, found_size
+            );
+            return Err(CoordinatorError::ContributionFileSizeMismatch);
+        }
+
+        if found_hash != hash {
+            error!("Uploaded contribution hash should be {} but found {}", hash, found_hash);
+            return Err(CoordinatorError::ContributionHashMismatch);
+        }
+
+        fs::rename(&path, self.upload_path(contribution_locator, true)?)?;
+
+        Ok(())
+    }
+
+    /// Returns `true` if the upload of the given contribution has been finalized.
+    pub fn is_upload_finalized(&self, contribution_locator: &ContributionLocator) -> bool {
+        match self.upload_path(contribution_locator, true) {
+            Ok(path) => path.exists(),
+            Err(_) => false,
+        }
+    }
+
+    /// Returns the finalized upload of the given contribution.
+    pub fn get_upload(&self, contribution_locator: &ContributionLocator) -> Result<Vec<u8>, CoordinatorError> {
+        self.read_upload(&self.upload_path(contribution_locator, true)?)
+    }
+
+    /// Removes the finalized upload of the given contribution.
+    pub fn remove_upload(&self, contribution_locator: &ContributionLocator) -> Result<(), CoordinatorError> {
+        Ok(fs::remove_file(self.upload_path(contribution_locator, true)?)?)
+    }
+
+    /// Discards the upload of the given contribution, whether finalized or not, if any.
+    pub fn discard_upload(&self, contribution_locator: &ContributionLocator) -> Result<(), CoordinatorError> {
+        for finalized in [false, true] {
+            let path = self.upload_path(contribution_locator, finalized)?;
+            if path.exists() {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the nonce the parts of the given upload file are encrypted with, `None` if they are written in plaintext.
+    /// If `create` is set, the header of an empty upload is written first when the storage is encrypted.
+    fn upload_nonce(&self, file: &mut File, create: bool) -> Result<Option<Vec<u8>>, CoordinatorError> {
+        let size = file.metadata()?.len();
+        if size == 0 {
+            if !create || self.environment.storage_encryption_key().is_none() {
+                return Ok(None);
+            }
+
+            let header = encryption::upload_header();
+            file.write_all(&header)?;
+            return Ok(encryption::upload_nonce(&header).map(<[u8]>::to_vec));
+        }
+
+        let mut header = vec![0; encryption::UPLOAD_HEADER_LENGTH.min(size as usize)];
+        file.seek(SeekFrom::Start(0))?;
+        file.read_exact(&mut header)?;
+
+        Ok(encryption::upload_nonce(&header).map(<[u8]>::to_vec))
+    }
+
+    /// Returns the key the uploads are encrypted with.
+    fn upload_key(&self) -> Result<&encryption::EncryptionKey, CoordinatorError> {
+        self.environment
+            .storage_encryption_key()
+            .ok_or(CoordinatorError::StorageDecryptionFailed)
+    }
+
+    /// Reads the plaintext of the given upload file.
+    fn read_upload(&self, path: &Path) -> Result<Vec<u8>, CoordinatorError> {
+        let mut file = File::open(path)?;
+        let mut data = Vec::new();
+        match self.upload_nonce(&mut file, false)? {
+            Some(nonce) => {
+                file.read_to_end(&mut data)?;
+                encryption::apply_upload_keystream(self.upload_key()?, &nonce, 0, &mut data);
+            }
+            None => {
+                file.seek(SeekFrom::Start(0))?;
+                file.read_to_end(&mut data)?;
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Returns the size and the hex encoded hash of the decrypted upload at the given path, read block by block so that
+    /// the contribution is never loaded whole in memory.
+    fn hash_upload(&self, path: &Path) -> Result<(u64, String), CoordinatorError> {
+        let mut file = File::open(path)?;
+        let nonce = self.upload_nonce(&mut file, false)?;
+        if nonce.is_none() {
+            file.seek(SeekFrom::Start(0))?;
+        }
+
+        let mut hasher = Blake2b512::default();
+        let mut block = vec![0; UPLOAD_HASH_BLOCK_SIZE];
+        let mut size = 0;
+        loop {
+            let len = file.read(&mut block)?;
+            if len == 0 {
+                break;
+            }
+            if let Some(nonce) = &nonce {
+                encryption::apply_upload_keystream(self.upload_key()?, nonce, size, &mut block[..len]);
+            }
+            hasher.update(&block[..len]);
+            size += len as u64;
+        }
+
+        Ok((size, hex::encode(hasher.finalize())))
+    }
+
+    /// Returns the path of the upload of the given contribution, which lives next to the contribution file.
+    fn upload_path(
+        &self,
+        contribution_locator: &ContributionLocator,
+        finalized: bool,
+    ) -> Result<PathBuf, CoordinatorError> {
+        let path = self.to_path(&Locator::ContributionFile(*contribution_locator))?;
+        let extension = if finalized { "uploaded" } else { "part" };
+
+        Ok(PathBuf::from(format!("{}.{}", path, extension)))
+    }
+
+    /// Lists the contribution files of the given round, with their signatures and uploads, along with their locator.
+    /// An upload is listed with the locator of the contribution file it completes.
+    pub fn contribution_files(&self, round_height: u64) -> Result<Vec<(PathBuf, Locator)>, CoordinatorError> {
+        let mut files = Vec::new();
+        let round_directory = PathBuf::from(self.round_directory(round_height));
+        if !round_directory.exists() {
+            return Ok(files);
+        }
+
+        for chunk_entry in fs::read_dir(&round_directory)? {
+            let chunk_directory = chunk_entry?.path();
+            if !chunk_directory.is_dir() {
+                continue;
+            }
+
+            for entry in fs::read_dir(&chunk_directory)? {
+                let path = entry?.path();
+                let file_path = path.to_str().ok_or(CoordinatorError::StorageLocatorFormatIncorrect)?;
+                let file_path = file_path
+                    .strip_suffix(".part")
+                    .or_else(|| file_path.strip_suffix(".uploaded"))
+                    .unwrap_or(file_path);
+
+                match self.to_locator(&LocatorPath::new(file_path.to_owned())) {
+                    Ok(locator @ (Locator::ContributionFile(_) | Locator::ContributionFileSignature(_))) => {
+                        files.push((path, locator))
+                    }
+                    _ => trace!("Skipping {}, not a contribution file", path.display()),
+                }
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Moves the given file of the storage to the quarantine directory, at the same path relative to the base
+    /// directory, and returns its new path.
+    pub fn quarantine(&self, path: &Path) -> Result<PathBuf, CoordinatorError> {
+        let base = Path::new(self.environment.local_base_directory());
+        let relative_path = path
+            .strip_prefix(base)
+            .map_err(|_| CoordinatorError::StorageLocatorFormatIncorrect)?;
+        let quarantined_path = base.join("quarantine").join(relative_path);
+        if let Some(parent) = quarantined_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::rename(path, &quarantined_path)?;
+
+        Ok(quarantined_path)
     }
 
     /// Process a [StorageAction] which mutates the storage.
@@ -382,11 +1091,11 @@ impl Disk {
     fn clear_round_files(&mut self, round_height: u64) {
         // Let's first fully clear any files in the next round - these will be
         // verifications and represent the initial challenges.
-        let next_round_dir = self.resolver.round_directory(round_height + 1);
+        let next_round_dir = self.round_directory(round_height + 1);
         self.clear_dir_files(next_round_dir.into(), true);
 
         // Now, let's clear all the contributions made on this round.
-        let round_dir = self.resolver.round_directory(round_height);
+        let round_dir = self.round_directory(round_height);
         self.clear_dir_files(round_dir.into(), false);
 
         // Delete contribution file and trim contribution summary file if these files exist
@@ -399,6 +1108,10 @@ impl Disk {
             tracing::warn!("Could not delete contribution file: {}", e);
         }
 
+        if let Err(e) = self.remove(&Locator::ContributionProvenanceFile { round_height }) {
+            tracing::warn!("Could not delete contribution provenance file: {}", e);
+        }
+
         match self.get(&Locator::ContributionsInfoSummary) {
             Ok(o) => {
                 if let Object::ContributionsInfoSummary(mut s) = o {
@@ -451,7 +1164,7 @@ impl Disk {
                         continue;
                     }
 
-                    let locator = match self.resolver.to_locator(&LocatorPath::new(file_path)) {
+                    let locator = match self.to_locator(&LocatorPath::new(file_path)) {
                         Ok(locator) => locator,
                         Err(e) => {
                             tracing::error!("Could not turn file path into locator - {:?}", e);
@@ -468,18 +1181,54 @@ impl Disk {
     }
 }
 
+impl Drop for Disk {
+    fn drop(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        if let Err(e) = self.flush() {
+            error!("Error while flushing the batched updates: {}", e);
+        }
+    }
+}
+
 impl StorageLocator for Disk {
     #[inline]
     fn to_path(&self, locator: &Locator) -> Result<LocatorPath, CoordinatorError> {
-        self.resolver.to_path(locator)
+        let round_height = match locator {
+            Locator::RoundState { round_height } | Locator::RoundFile { round_height } => Some(*round_height),
+            Locator::ContributionFile(locator) => Some(locator.round_height()),
+            Locator::ContributionFileSignature(locator) => Some(locator.round_height()),
+            _ => None,
+        };
+
+        match (&self.archive, round_height) {
+            (Some(archive), Some(round_height))
+                if self.round_directory(round_height) == archive.round_directory(round_height) =>
+            {
+                archive.to_path(locator)
+            }
+            _ => self.resolver.to_path(locator),
+        }
     }
 
     #[inline]
     fn to_locator(&self, path: &LocatorPath) -> Result<Locator, CoordinatorError> {
-        self.resolver.to_locator(path)
+        match (self.resolver.to_locator(path), &self.archive) {
+            (Err(_), Some(archive)) => archive.to_locator(path),
+            (result, _) => result,
+        }
     }
 }
 
+/// The content of the [`Disk`] storage at a given time, see [`Disk::snapshot`].
+#[derive(Debug, Clone)]
+pub struct DiskSnapshot {
+    files: HashMap<PathBuf, Vec<u8>>,
+    pending: HashMap<Locator, Object>,
+}
+
 pub struct DiskObjectReader {
     data: Vec<u8>,
 }
@@ -500,34 +1249,86 @@ impl AsRef<[u8]> for DiskObjectReader {
 
 impl ObjectReader for DiskObjectReader {}
 
+/// A writer of an object stored on disk. The contribution files encrypted at rest are written in memory, then
+/// encrypted to disk when the writer is flushed or dropped.
 pub struct DiskObjectWriter {
-    _file: File,
-    memmap: memmap::MmapMut,
+    file: File,
+    content: DiskObjectContent,
+}
+
+enum DiskObjectContent {
+    Mapped(memmap::MmapMut),
+    Encrypted {
+        key: encryption::EncryptionKey,
+        data: Vec<u8>,
+    },
+}
+
+impl DiskObjectContent {
+    fn len(&self) -> usize {
+        match self {
+            Self::Mapped(memmap) => memmap.len(),
+            Self::Encrypted { data, .. } => data.len(),
+        }
+    }
+}
+
+impl DiskObjectWriter {
+    /// Encrypts the content of the writer to its file.
+    fn write_encrypted(&self, key: &encryption::EncryptionKey, data: &[u8]) -> std::io::Result<()> {
+        let bytes = encryption::encrypt(key, data)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e.to_string()))?;
+
+        let mut file = self.file.file();
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(&bytes)?;
+        file.sync_data()
+    }
 }
 
 impl Deref for DiskObjectWriter {
     type Target = [u8];
 
     fn deref(&self) -> &Self::Target {
-        &*self.memmap
+        match &self.content {
+            DiskObjectContent::Mapped(memmap) => &*memmap,
+            DiskObjectContent::Encrypted { data, .. } => &*data,
+        }
     }
 }
 
 impl DerefMut for DiskObjectWriter {
     fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut *self.memmap
+        match &mut self.content {
+            DiskObjectContent::Mapped(memmap) => &mut *memmap,
+            DiskObjectContent::Encrypted { data, .. } => &mut *data,
+        }
     }
 }
 
 impl AsMut<[u8]> for DiskObjectWriter {
     fn as_mut(&mut self) -> &mut [u8] {
-        self.memmap.as_mut()
+        self.deref_mut()
+    }
+}
+
+impl Drop for DiskObjectWriter {
+    fn drop(&mut self) {
+        if let DiskObjectContent::Encrypted { key, data } = &self.content {
+            if let Err(e) = self.write_encrypted(key, data) {
+                error!("Failed to write the encrypted file {:?}: {}", self.file.path(), e);
+            }
+        }
     }
 }
 
 impl ObjectWriter for DiskObjectWriter {
     fn flush(&self) -> std::io::Result<()> {
-        self.memmap.flush()
+        match &self.content {
+            DiskObjectContent::Mapped(memmap) => memmap.flush(),
+            DiskObjectContent::Encrypted { key, data } => self.write_encrypted(key, data),
+        }
     }
 }
 
@@ -553,6 +1354,7 @@ impl StorageObject for Disk {
         file.file()
             .read_to_end(&mut data)
             .map_err(|e| CoordinatorError::IOError(e))?;
+        let data = self.decrypt(locator, data)?;
 
         match locator {
             Locator::RoundFile { round_height } => {
@@ -570,10 +1372,11 @@ impl StorageObject for Disk {
             }
             Locator::ContributionFile(contribution_locator) => {
                 // Check that the contribution size is correct.
-                let expected_size = Object::anoma_contribution_file_size(
+                let expected_size = Object::ceremony_contribution_file_size(
+                    &self.environment,
                     contribution_locator.round_height(),
                     contribution_locator.contribution_id(),
-                );
+                )?;
                 let found_size = data.len() as u64;
                 debug!(
                     "Round {} chunk {} filesize is {}",
@@ -608,13 +1411,23 @@ impl StorageObject for Disk {
 
         let file = OpenOptions::new().read(true).write(true).open(path)?;
 
-        // Load the file into memory.
-        let memmap = unsafe { MmapOptions::new().map_mut(&file.file())? };
+        // Load the file into memory. An encrypted contribution file can't be mapped, it's decrypted in memory instead.
+        let content = match (locator, self.environment.storage_encryption_key()) {
+            (Locator::ContributionFile(_), Some(key)) => {
+                let mut data = vec![];
+                file.file().read_to_end(&mut data)?;
+                DiskObjectContent::Encrypted {
+                    key: *key,
+                    data: self.decrypt(locator, data)?,
+                }
+            }
+            _ => DiskObjectContent::Mapped(unsafe { MmapOptions::new().map_mut(&file.file())? }),
+        };
         match locator {
             Locator::RoundFile { round_height: _ } => {
                 // Check that the round size is correct.
                 let expected_size = Object::round_file_size(&self.environment);
-                let found_size = memmap.len() as u64;
+                let found_size = content.len() as u64;
                 debug!("File size of {} is {}", self.to_path(locator)?, found_size);
                 if found_size != expected_size {
                     error!(
@@ -626,11 +1439,12 @@ impl StorageObject for Disk {
             }
             Locator::ContributionFile(contribution_locator) => {
                 // Check that the contribution size is correct.
-                let expected_size = Object::anoma_contribution_file_size(
+                let expected_size = Object::ceremony_contribution_file_size(
+                    &self.environment,
                     contribution_locator.round_height(),
                     contribution_locator.contribution_id(),
-                );
-                let found_size = memmap.len() as u64;
+                )?;
+                let found_size = content.len() as u64;
                 debug!("File size of {} is {}", self.to_path(locator)?, found_size);
                 if found_size != expected_size {
                     error!(
@@ -643,7 +1457,7 @@ impl StorageObject for Disk {
             _ => {}
         }
 
-        Ok(DiskObjectWriter { _file: file, memmap })
+        Ok(DiskObjectWriter { file, content })
     }
 }
 
@@ -714,6 +1528,9 @@ impl StorageLocator for DiskResolver {
                 self.base, round_height
             ),
             Locator::ContributionsInfoSummary => format!("{}/contributors.json", self.base),
+            Locator::ContributionProvenanceFile { round_height } => {
+                format!("{}/provenance/round_{}.json", self.base, round_height)
+            }
         };
         // Sanitize the path.
         LocatorPath::try_from(Path::new(&path))
@@ -901,8 +1718,286 @@ impl DiskResolver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{
+        environment::{Parameters, Testing},
+        testing::prelude::{
+            initialize_test_environment, serial, test_round_0, test_round_1_initial_json, TEST_ENVIRONMENT,
+        },
+    };
+    use setup_utils::calculate_hash;
     // use crate::testing::prelude::*;
 
+    #[test]
+    #[serial]
+    fn test_batched_updates_persisted_on_flush() {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .storage_batch_interval(time::Duration::hours(1))
+        .into();
+        initialize_test_environment(&environment);
+
+        let mut storage = Disk::load(&environment).unwrap();
+        let round_height_path = storage.to_path(&Locator::RoundHeight).unwrap();
+        let state_path = storage.to_path(&Locator::CoordinatorState).unwrap();
+
+        storage.insert(Locator::RoundHeight, Object::RoundHeight(0)).unwrap();
+        let mut state = CoordinatorState::new(environment.clone());
+        for round_height in 1..=5 {
+            storage
+                .update(&Locator::RoundHeight, Object::RoundHeight(round_height))
+                .unwrap();
+            state.initialize(round_height);
+            storage
+                .update(&Locator::CoordinatorState, Object::CoordinatorState(state.clone()))
+                .unwrap();
+        }
+
+        // Batched updates are visible to reads but not persisted yet
+        assert!(matches!(
+            storage.get(&Locator::RoundHeight).unwrap(),
+            Object::RoundHeight(5)
+        ));
+        assert_ne!(b"5".to_vec(), fs::read(&round_height_path).unwrap());
+
+        storage.flush().unwrap();
+
+        assert_eq!(b"5".to_vec(), fs::read(&round_height_path).unwrap());
+        assert_eq!(
+            Object::CoordinatorState(state).to_bytes(),
+            fs::read(&state_path).unwrap()
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_round_height_never_points_at_missing_round() {
+        use std::sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        };
+
+        let environment = initialize_test_environment(&TEST_ENVIRONMENT);
+        let mut storage = Disk::load(&environment).unwrap();
+        let round = test_round_0().unwrap();
+        storage
+            .compare_and_set_round_height(None, 0, Some(round.clone()))
+            .unwrap();
+
+        // A concurrent reader follows the round height while the writer moves it forward
+        let done = Arc::new(AtomicBool::new(false));
+        let reader = {
+            let done = done.clone();
+            let reader_storage = Disk::load(&environment).unwrap();
+            std::thread::spawn(move || loop {
+                let round_height = match reader_storage.get(&Locator::RoundHeight).unwrap() {
+                    Object::RoundHeight(round_height) => round_height,
+                    _ => panic!("Unexpected object at the round height locator"),
+                };
+                assert!(matches!(
+                    reader_storage.get(&Locator::RoundState { round_height }),
+                    Ok(Object::RoundState(_))
+                ));
+
+                if done.load(Ordering::SeqCst) {
+                    break;
+                }
+            })
+        };
+
+        for round_height in 1..=50 {
+            storage
+                .compare_and_set_round_height(Some(round_height - 1), round_height, Some(round.clone()))
+                .unwrap();
+        }
+        done.store(true, Ordering::SeqCst);
+        reader.join().unwrap();
+
+        // The swap fails, leaving the storage untouched, if the round height has moved in the meantime
+        assert!(matches!(
+            storage.compare_and_set_round_height(Some(10), 51, Some(round)),
+            Err(CoordinatorError::RoundHeightMismatch)
+        ));
+        assert!(matches!(
+            storage.get(&Locator::RoundHeight).unwrap(),
+            Object::RoundHeight(50)
+        ));
+        assert!(!storage.exists(&Locator::RoundState { round_height: 51 }));
+    }
+
+    #[test]
+    #[serial]
+    fn test_round_heights() {
+        let environment: Environment = Testing::from(Parameters::Test3Chunks)
+            .storage_archive_directory("./transcript/testing/archive")
+            .into();
+        initialize_test_environment(&environment);
+        let mut storage = Disk::load(&environment).unwrap();
+        assert!(storage.round_heights().unwrap().is_empty());
+
+        let round = test_round_0().unwrap();
+        let mut previous_round_height = None;
+        for round_height in [0, 1, 2, 5] {
+            storage
+                .compare_and_set_round_height(previous_round_height, round_height, Some(round.clone()))
+                .unwrap();
+            previous_round_height = Some(round_height);
+        }
+        // A round directory without a state isn't listed
+        fs::create_dir_all(storage.resolver.round_directory(7)).unwrap();
+
+        // Archived rounds are listed too
+        storage.archive_round(1).unwrap();
+        assert!(!Path::new(&storage.resolver.round_directory(1)).exists());
+
+        assert_eq!(vec![0, 1, 2, 5], storage.round_heights().unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn test_snapshot_restore() {
+        let environment = initialize_test_environment(&TEST_ENVIRONMENT);
+        let mut storage = Disk::load(&environment).unwrap();
+
+        let round = test_round_0().unwrap();
+        let locator = Locator::RoundState { round_height: 0 };
+        storage.insert(locator, Object::RoundState(round.clone())).unwrap();
+
+        let snapshot = storage.snapshot().unwrap();
+
+        // Overwrite the round and add a new object
+        let mutated_round = test_round_1_initial_json().unwrap();
+        assert_ne!(round, mutated_round);
+        storage.update(&locator, Object::RoundState(mutated_round)).unwrap();
+        storage.insert(Locator::RoundHeight, Object::RoundHeight(1)).unwrap();
+
+        storage.restore(snapshot).unwrap();
+
+        assert!(matches!(storage.get(&locator).unwrap(), Object::RoundState(r) if r == round));
+        assert!(!storage.exists(&Locator::RoundHeight));
+    }
+
+    #[test]
+    #[serial]
+    fn test_contribution_encrypted_at_rest() {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .storage_encryption_key([7u8; 32])
+        .into();
+        initialize_test_environment(&environment);
+
+        let mut storage = Disk::load(&environment).unwrap();
+        let locator = ContributionLocator::new(1, 0, 1, false);
+        let size = Object::anoma_contribution_file_size(locator.round_height(), locator.contribution_id());
+        let contribution: Vec<u8> = (0..size).map(|i| i as u8).collect();
+
+        storage.initialize(Locator::ContributionFile(locator), size).unwrap();
+        storage
+            .update(
+                &Locator::ContributionFile(locator),
+                Object::ContributionFile(contribution.clone()),
+            )
+            .unwrap();
+
+        // The bytes on disk are not the plaintext
+        let on_disk = fs::read(storage.to_path(&Locator::ContributionFile(locator)).unwrap()).unwrap();
+        assert!(encryption::is_encrypted(&on_disk));
+        assert!(!on_disk.windows(64).any(|window| window == &contribution[..64]));
+
+        // Reading the contribution back yields the plaintext
+        assert!(matches!(
+            storage.get(&Locator::ContributionFile(locator)).unwrap(),
+            Object::ContributionFile(c) if c == contribution
+        ));
+        assert_eq!(
+            contribution,
+            storage.reader(&Locator::ContributionFile(locator)).unwrap().to_vec()
+        );
+        assert_eq!(size, storage.size(&Locator::ContributionFile(locator)).unwrap());
+
+        // The writer encrypts the contribution it writes
+        let mut writer = storage.writer(&Locator::ContributionFile(locator)).unwrap();
+        assert_eq!(contribution, writer.to_vec());
+        writer[0] = !contribution[0];
+        drop(writer);
+
+        let on_disk = fs::read(storage.to_path(&Locator::ContributionFile(locator)).unwrap()).unwrap();
+        assert!(encryption::is_encrypted(&on_disk));
+        let written = storage.reader(&Locator::ContributionFile(locator)).unwrap().to_vec();
+        assert_eq!(!contribution[0], written[0]);
+        assert_eq!(contribution[1..], written[1..]);
+        assert_eq!(size, storage.size(&Locator::ContributionFile(locator)).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn test_upload_encrypted_at_rest() {
+        let environment: Environment = Testing::from(Parameters::TestCustom {
+            number_of_chunks: 1,
+            power: 8,
+            batch_size: 128,
+        })
+        .storage_encryption_key([7u8; 32])
+        .into();
+        initialize_test_environment(&environment);
+
+        let mut storage = Disk::load(&environment).unwrap();
+        let locator = ContributionLocator::new(1, 0, 1, false);
+        let size = Object::anoma_contribution_file_size(locator.round_height(), locator.contribution_id());
+        let contribution: Vec<u8> = (0..size).map(|i| i as u8).collect();
+        let half = contribution.len() / 2;
+        storage.initialize(Locator::ContributionFile(locator), size).unwrap();
+
+        // Upload in two parts, the second one being retried
+        let (first, second) = contribution.split_at(half);
+        assert_eq!(half as u64, storage.write_upload_part(&locator, 0, first).unwrap());
+        storage.write_upload_part(&locator, half as u64, &second[..1]).unwrap();
+        assert_eq!(size, storage.write_upload_part(&locator, half as u64, second).unwrap());
+
+        // The part on disk is not the plaintext
+        let part = fs::read(storage.upload_path(&locator, false).unwrap()).unwrap();
+        assert!(encryption::upload_nonce(&part).is_some());
+        assert!(!part.windows(64).any(|window| window == &contribution[..64]));
+        assert!(!part.windows(64).any(|window| window == &contribution[half..half + 64]));
+
+        // The finalized upload yields the plaintext
+        let hash = hex::encode(calculate_hash(&contribution));
+        storage.finalize_upload(&locator, size, &hash).unwrap();
+        assert_eq!(contribution, storage.get_upload(&locator).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn test_interrupted_contribution_write() {
+        let environment = initialize_test_environment(&TEST_ENVIRONMENT);
+        let mut storage = Disk::load(&environment).unwrap();
+        let locator = Locator::ContributionFile(ContributionLocator::new(1, 0, 1, false));
+        let size = Object::anoma_contribution_file_size(1, 1);
+        let contribution: Vec<u8> = (0..size).map(|i| i as u8).collect();
+        storage.initialize(locator, size).unwrap();
+        let path = storage.to_path(&locator).unwrap();
+        let tmp_path = format!("{}.tmp", path);
+
+        // A crash in the middle of the write leaves the partial contribution in the temporary file only
+        fs::write(&tmp_path, &contribution[..size as usize / 2]).unwrap();
+        drop(storage);
+        let mut storage = Disk::load(&environment).unwrap();
+        assert!(storage.exists(&locator));
+        assert_eq!(vec![0u8; size as usize], storage.reader(&locator).unwrap().to_vec());
+
+        // The next write replaces the leftover temporary file and moves the full contribution in place
+        storage
+            .update_durably(&locator, Object::ContributionFile(contribution.clone()))
+            .unwrap();
+        assert_eq!(contribution, storage.reader(&locator).unwrap().to_vec());
+        assert!(!Path::new(&tmp_path).exists());
+    }
+
     #[test]
     fn test_to_path_coordinator_state() {
         let locator = DiskResolver::new("./transcript/test");
@@ -0,0 +1,110 @@
+//! Encryption at rest of the contribution files.
+//!
+//! Encrypted files start with [`ENCRYPTED_FILE_MAGIC`] followed by the nonce and the ciphertext.
+//! Files without the prefix, like the challenges computed by the coordinator itself, are read as
+//! plaintext.
+//!
+//! The uploads of the contributions are written in parts, possibly retried, so they can't be sealed
+//! whole: they start with [`ENCRYPTED_UPLOAD_MAGIC`] followed by the nonce, and their parts are
+//! encrypted with the ChaCha20 keystream at their offset. Their integrity is checked against the
+//! hash of the contribution when the upload is finalized.
+
+use crate::CoordinatorError;
+
+use chacha20::{
+    cipher::{NewCipher, StreamCipher, StreamCipherSeek},
+    ChaCha20,
+};
+use chacha20poly1305::{
+    aead::{Aead, NewAead},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use rand::RngCore;
+
+/// Length in bytes of the key used to encrypt the contribution files.
+pub const ENCRYPTION_KEY_LENGTH: usize = 32;
+pub type EncryptionKey = [u8; ENCRYPTION_KEY_LENGTH];
+
+/// Prefix of the encrypted files.
+const ENCRYPTED_FILE_MAGIC: &[u8] = b"namada-encrypted";
+const NONCE_LENGTH: usize = 12;
+/// Length of the authentication tag appended to the ciphertext.
+const TAG_LENGTH: usize = 16;
+
+/// Length of the header of the encrypted files: the magic prefix and the nonce.
+pub(crate) const HEADER_LENGTH: usize = ENCRYPTED_FILE_MAGIC.len() + NONCE_LENGTH;
+
+/// Prefix of the encrypted uploads.
+const ENCRYPTED_UPLOAD_MAGIC: &[u8] = b"namada-encrypted-upload";
+/// Length of the header of the encrypted uploads: the magic prefix and the nonce.
+pub(crate) const UPLOAD_HEADER_LENGTH: usize = ENCRYPTED_UPLOAD_MAGIC.len() + NONCE_LENGTH;
+
+/// Encrypts the given plaintext.
+pub(crate) fn encrypt(key: &EncryptionKey, plaintext: &[u8]) -> Result<Vec<u8>, CoordinatorError> {
+    let mut nonce = [0u8; NONCE_LENGTH];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+    let ciphertext = ChaCha20Poly1305::new(Key::from_slice(key))
+        .encrypt(Nonce::from_slice(&nonce), plaintext)
+        .map_err(|_| CoordinatorError::StorageEncryptionFailed)?;
+
+    let mut data = Vec::with_capacity(ENCRYPTED_FILE_MAGIC.len() + NONCE_LENGTH + ciphertext.len());
+    data.extend_from_slice(ENCRYPTED_FILE_MAGIC);
+    data.extend_from_slice(&nonce);
+    data.extend_from_slice(&ciphertext);
+
+    Ok(data)
+}
+
+/// Decrypts the given data if it has been encrypted, returns it unchanged otherwise.
+pub(crate) fn decrypt(key: &EncryptionKey, data: Vec<u8>) -> Result<Vec<u8>, CoordinatorError> {
+    if !is_encrypted(&data) {
+        return Ok(data);
+    }
+
+    let (nonce, ciphertext) = data[ENCRYPTED_FILE_MAGIC.len()..].split_at(NONCE_LENGTH);
+    ChaCha20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| CoordinatorError::StorageDecryptionFailed)
+}
+
+/// Returns `true` if the given data has been encrypted.
+pub(crate) fn is_encrypted(data: &[u8]) -> bool {
+    data.len() >= ENCRYPTED_FILE_MAGIC.len() + NONCE_LENGTH && data.starts_with(ENCRYPTED_FILE_MAGIC)
+}
+
+/// Returns the size of the plaintext of a stored file of `size` bytes, given the first [`HEADER_LENGTH`] bytes of the
+/// file.
+pub(crate) fn plaintext_size(header: &[u8], size: u64) -> u64 {
+    if is_encrypted(header) {
+        size.saturating_sub((HEADER_LENGTH + TAG_LENGTH) as u64)
+    } else {
+        size
+    }
+}
+
+/// Returns the header of a new encrypted upload, with a random nonce.
+pub(crate) fn upload_header() -> Vec<u8> {
+    let mut nonce = [0u8; NONCE_LENGTH];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+
+    [ENCRYPTED_UPLOAD_MAGIC, &nonce[..]].concat()
+}
+
+/// Returns the nonce of an encrypted upload given the first [`UPLOAD_HEADER_LENGTH`] bytes of the upload, `None` if the
+/// upload isn't encrypted.
+pub(crate) fn upload_nonce(header: &[u8]) -> Option<&[u8]> {
+    if header.len() >= UPLOAD_HEADER_LENGTH && header.starts_with(ENCRYPTED_UPLOAD_MAGIC) {
+        Some(&header[ENCRYPTED_UPLOAD_MAGIC.len()..UPLOAD_HEADER_LENGTH])
+    } else {
+        None
+    }
+}
+
+/// Encrypts, or decrypts, in place the bytes found at `offset` in the plaintext of an upload encrypted with the given
+/// nonce.
+pub(crate) fn apply_upload_keystream(key: &EncryptionKey, nonce: &[u8], offset: u64, data: &mut [u8]) {
+    let mut cipher = ChaCha20::new(Key::from_slice(key), Nonce::from_slice(nonce));
+    cipher.seek(offset);
+    cipher.apply_keystream(data);
+}
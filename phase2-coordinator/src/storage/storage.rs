@@ -1,6 +1,6 @@
 use crate::{
-    environment::Environment,
-    objects::{ContributionFileSignature, ContributionInfo, Round, TrimmedContributionInfo},
+    environment::{CeremonyParameters, Environment},
+    objects::{ContributionFileSignature, ContributionInfo, ContributionProvenance, Round, TrimmedContributionInfo},
     CoordinatorError, CoordinatorState,
 };
 use phase2::helpers::CurveKind;
@@ -33,6 +33,10 @@ pub const ANOMA_BASE_FILE_SIZE: u64 = 2_332; // prod: 84_720_244, testing: 2_332
 pub const ANOMA_PER_ROUND_FILE_SIZE_INCREASE: u64 = 1_632; // prod: 1_632, testing: 544
 #[cfg(debug_assertions)]
 pub const ANOMA_PER_ROUND_FILE_SIZE_INCREASE: u64 = 544; // prod: 1_632, testing: 544
+/// Each contribution appends a public key of 544 bytes to the parameters of every circuit.
+pub const CIRCUIT_CONTRIBUTION_SIZE_INCREASE: u64 = 544;
+/// Length of the hash of the previous contribution placed at the head of a contribution file.
+const CONTRIBUTION_HASH_SIZE: u64 = 64;
 
 impl ContributionLocator {
     pub fn new(round_height: u64, chunk_id: u64, contribution_id: u64, is_verified: bool) -> Self {
@@ -107,6 +111,7 @@ pub enum Locator {
     ContributionFileSignature(ContributionSignatureLocator),
     ContributionInfoFile { round_height: u64 },
     ContributionsInfoSummary,
+    ContributionProvenanceFile { round_height: u64 },
 }
 
 impl From<ContributionLocator> for Locator {
@@ -132,6 +137,7 @@ pub enum Object {
     ContributionFileSignature(ContributionFileSignature),
     ContributionInfoFile(ContributionInfo),
     ContributionsInfoSummary(Vec<TrimmedContributionInfo>),
+    ContributionProvenanceFile(Vec<ContributionProvenance>),
 }
 
 impl Object {
@@ -153,6 +159,9 @@ impl Object {
             Object::ContributionsInfoSummary(summary) => {
                 serde_json::to_vec_pretty(summary).expect("Contribution info summary to bytes failed")
             }
+            Object::ContributionProvenanceFile(provenance) => {
+                serde_json::to_vec_pretty(provenance).expect("Contribution provenance file to bytes failed")
+            }
         }
     }
 
@@ -167,6 +176,7 @@ impl Object {
             Object::ContributionFileSignature(_) => self.to_bytes().len() as u64,
             Object::ContributionInfoFile(_) => self.to_bytes().len() as u64,
             Object::ContributionsInfoSummary(_) => self.to_bytes().len() as u64,
+            Object::ContributionProvenanceFile(_) => self.to_bytes().len() as u64,
         }
     }
 
@@ -208,9 +218,49 @@ impl Object {
 
     /// Returns dynamically the expected file size of a contribution file.
     pub fn anoma_contribution_file_size(round_height: u64, contribution_id: u64) -> u64 {
+        Self::grown_contribution_file_size(
+            ANOMA_BASE_FILE_SIZE,
+            ANOMA_PER_ROUND_FILE_SIZE_INCREASE,
+            round_height,
+            contribution_id,
+        )
+    }
+
+    /// Returns dynamically the expected file size of a contribution file to the parameters of the environment. The
+    /// sizes of a [`CeremonyParameters::Circuits`] ceremony derive from the size of its initial parameters file.
+    pub fn ceremony_contribution_file_size(
+        environment: &Environment,
+        round_height: u64,
+        contribution_id: u64,
+    ) -> Result<u64, CoordinatorError> {
+        let parameters = environment.ceremony_parameters();
+        match parameters {
+            CeremonyParameters::Masp => Ok(Self::anoma_contribution_file_size(round_height, contribution_id)),
+            CeremonyParameters::Circuits(_) => {
+                let path = environment
+                    .initial_parameters_path()
+                    .ok_or(CoordinatorError::InitialParametersMissing)?;
+                let base_size = CONTRIBUTION_HASH_SIZE + std::fs::metadata(path)?.len();
+
+                Ok(Self::grown_contribution_file_size(
+                    base_size,
+                    Self::contribution_size_increase(parameters),
+                    round_height,
+                    contribution_id,
+                ))
+            }
+        }
+    }
+
+    /// Returns the number of bytes a contribution adds to the file of the previous one.
+    pub fn contribution_size_increase(parameters: CeremonyParameters) -> u64 {
+        parameters.number_of_circuits() as u64 * CIRCUIT_CONTRIBUTION_SIZE_INCREASE
+    }
+
+    fn grown_contribution_file_size(base_size: u64, increase: u64, round_height: u64, contribution_id: u64) -> u64 {
         match round_height {
-            0 => ANOMA_BASE_FILE_SIZE,
-            _ => ANOMA_BASE_FILE_SIZE + (ANOMA_PER_ROUND_FILE_SIZE_INCREASE * (round_height + contribution_id - 1)),
+            0 => base_size,
+            _ => base_size + (increase * (round_height + contribution_id - 1)),
         }
     }
 
@@ -2,7 +2,8 @@ use crate::{
     authentication::Dummy,
     commands::{Seed, SigningKey, SEED_LENGTH},
     environment::{Environment, Parameters, Settings, Testing},
-    objects::Task,
+    objects::{ParticipantRole, Task},
+    rest_utils::VerificationWorker,
     storage::{Disk, StorageLocator},
     testing::prelude::*,
     Coordinator, CoordinatorError, MockTimeSource, Participant, Round,
@@ -13,11 +14,13 @@ use time::OffsetDateTime;
 use fs_err as fs;
 use rand::RngCore;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rocket::tokio::sync::{watch, RwLock};
 use std::{
     collections::{HashSet, LinkedList},
     iter::FromIterator,
     net::{IpAddr, Ipv4Addr},
     sync::Arc,
+    time::Duration,
 };
 
 fn create_contributor(id: &str) -> (Participant, SigningKey, Seed) {
@@ -204,6 +207,109 @@ fn fetch_task_for_verifier(coordinator: &Coordinator) -> Option<Task> {
     coordinator.get_pending_verifications().keys().next().cloned()
 }
 
+#[test]
+#[serial]
+/// Checks the role of contributors and verifiers in the current round.
+fn coordinator_participant_role() {
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        6,  /* power */
+        16, /* batch_size */
+        16, /* chunk_size */
+    ));
+    let environment = initialize_test_environment(&Testing::from(parameters).into());
+    let verifier = environment.coordinator_verifiers().first().unwrap().clone();
+
+    // Instantiate a coordinator.
+    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy)).unwrap();
+
+    // Initialize the ceremony to round 0.
+    coordinator.initialize().unwrap();
+    let (contributor1, _, _) = create_contributor("1");
+    let (contributor2, _, _) = create_contributor("2");
+    let (unknown_verifier, _) = create_verifier("unknown");
+    coordinator
+        .add_to_queue(
+            contributor1.clone(),
+            Some(IpAddr::V4("0.0.0.1".parse().unwrap())),
+            String::from("test_token"),
+            10,
+        )
+        .unwrap();
+    assert_eq!(
+        ParticipantRole::Unauthorized,
+        coordinator.participant_role(&contributor1)
+    );
+
+    // Update the ceremony to round 1.
+    coordinator.update().unwrap();
+    assert_eq!(1, coordinator.current_round_height().unwrap());
+
+    assert_eq!(
+        ParticipantRole::Contributor,
+        coordinator.participant_role(&contributor1)
+    );
+    assert_eq!(
+        ParticipantRole::Verifier,
+        coordinator.participant_role(&verifier)
+    );
+    assert_eq!(
+        ParticipantRole::Unauthorized,
+        coordinator.participant_role(&contributor2)
+    );
+    assert_eq!(
+        ParticipantRole::Unauthorized,
+        coordinator.participant_role(&unknown_verifier)
+    );
+
+    // A contributor passed to the verifier check
+    assert!(!coordinator.is_current_verifier(&contributor1));
+    assert!(matches!(
+        coordinator.check_current_verifier(&contributor1),
+        Err(CoordinatorError::ExpectedVerifier)
+    ));
+
+    // A verifier passed to the contributor check
+    assert!(!coordinator.is_current_contributor(&verifier));
+    assert!(matches!(
+        coordinator.check_current_contributor(&verifier),
+        Err(CoordinatorError::ExpectedContributor)
+    ));
+
+    // Participants of the expected type but without a role in the round
+    assert!(matches!(
+        coordinator.check_current_contributor(&contributor2),
+        Err(CoordinatorError::ParticipantUnauthorized)
+    ));
+    assert!(matches!(
+        coordinator.check_current_verifier(&unknown_verifier),
+        Err(CoordinatorError::ParticipantUnauthorized)
+    ));
+
+    assert!(coordinator.check_current_contributor(&contributor1).is_ok());
+    assert!(coordinator.check_current_verifier(&verifier).is_ok());
+
+    // The same checks guard the lock, contribute and verify paths
+    assert!(matches!(
+        coordinator.try_lock(&verifier),
+        Err(CoordinatorError::ExpectedContributor)
+    ));
+    assert!(matches!(
+        coordinator.try_lock(&contributor2),
+        Err(CoordinatorError::ParticipantUnauthorized)
+    ));
+    assert!(matches!(
+        coordinator.try_contribute(&contributor2, 0),
+        Err(CoordinatorError::ParticipantUnauthorized)
+    ));
+    assert!(matches!(
+        coordinator.try_verify(&unknown_verifier, &Task::new(0, 1)),
+        Err(CoordinatorError::ParticipantUnauthorized)
+    ));
+}
+
 #[test]
 #[serial]
 /// Drops a contributor who does not affect other contributors or verifiers.
@@ -2487,3 +2593,75 @@ fn round_on_groth16_bw6_761() {
 fn round_on_marlin_bls12_377() {
     execute_round(ProvingSystem::Marlin, CurveKind::Bls12_377).unwrap();
 }
+
+#[rocket::async_test]
+#[serial]
+async fn verification_worker() -> anyhow::Result<()> {
+    let parameters = Parameters::Custom(Settings::new(
+        ContributionMode::Chunked,
+        ProvingSystem::Groth16,
+        CurveKind::Bls12_377,
+        7,  /* power */
+        32, /* batch_size */
+        32, /* chunk_size */
+    ));
+    let environment = initialize_test_environment(&Testing::from(parameters).into());
+    let number_of_chunks = environment.number_of_chunks() as usize;
+
+    let mut coordinator = Coordinator::new(environment, Arc::new(Dummy))?;
+    coordinator.initialize()?;
+    let (contributor, contributor_signing_key, seed) = create_contributor("1");
+    let contributor_ip = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+    coordinator.add_to_queue(
+        contributor.clone(),
+        Some(contributor_ip),
+        String::from("test_token"),
+        10,
+    )?;
+    coordinator.update()?;
+
+    // Contribute to all the chunks, enqueuing a verification for each one of them
+    for _ in 0..number_of_chunks {
+        coordinator.contribute(&contributor, &contributor_signing_key, &seed)?;
+    }
+    assert!(number_of_chunks > 1);
+    assert_eq!(number_of_chunks, coordinator.get_pending_verifications().len());
+
+    let coordinator = Arc::new(RwLock::new(coordinator));
+    let worker = VerificationWorker::default();
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let handle = rocket::tokio::spawn({
+        let (worker, coordinator) = (worker.clone(), coordinator.clone());
+        async move {
+            worker
+                .run(coordinator, None, Duration::from_secs(3600), shutdown_rx)
+                .await
+        }
+    });
+
+    // The worker processes all the pending verifications once woken up
+    worker.wake();
+    let start = std::time::Instant::now();
+    while !coordinator.read().await.get_pending_verifications().is_empty() {
+        assert!(
+            start.elapsed() < Duration::from_secs(120),
+            "Verifications still pending"
+        );
+        rocket::tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    {
+        let coordinator = coordinator.read().await;
+        let round = coordinator.current_round()?;
+        for chunk in round.chunks() {
+            assert!(chunk.get_contribution(1)?.is_verified());
+        }
+        assert!(round.is_complete());
+    }
+
+    shutdown_tx.send(true)?;
+    worker.wake();
+    handle.await??;
+
+    Ok(())
+}
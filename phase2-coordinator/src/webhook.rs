@@ -0,0 +1,67 @@
+//! Notifications of the ceremony events to the webhook configured in the [Environment](`crate::environment::Environment`).
+
+use serde::{Deserialize, Serialize};
+use std::{thread, time::Duration};
+use time::OffsetDateTime;
+use tracing::{debug, error, warn};
+
+/// The maximum number of delivery attempts of an event.
+const WEBHOOK_MAX_ATTEMPTS: u32 = 3;
+/// The delay between two delivery attempts of an event.
+const WEBHOOK_RETRY_DELAY: Duration = Duration::from_secs(2);
+/// The timeout of a single delivery attempt.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The event posted to the webhook when the ceremony transitions to a new round.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RoundTransitionEvent {
+    pub old_height: u64,
+    pub new_height: u64,
+    pub timestamp: OffsetDateTime,
+}
+
+/// Posts the [`RoundTransitionEvent`] to the webhook. The delivery happens on a separate thread so that
+/// it never blocks the coordinator: failures are retried a bounded number of times and then logged.
+pub(crate) fn notify_round_transition(url: &str, event: RoundTransitionEvent) -> thread::JoinHandle<()> {
+    let url = url.to_owned();
+
+    thread::spawn(move || {
+        let client = match reqwest::blocking::Client::builder().timeout(WEBHOOK_TIMEOUT).build() {
+            Ok(client) => client,
+            Err(e) => {
+                error!("Failed to build the webhook client: {}", e);
+                return;
+            }
+        };
+
+        for attempt in 1..=WEBHOOK_MAX_ATTEMPTS {
+            match client
+                .post(url.as_str())
+                .json(&event)
+                .send()
+                .and_then(|response| response.error_for_status())
+            {
+                Ok(_) => {
+                    debug!(
+                        "Delivered round transition {} -> {} to webhook",
+                        event.old_height, event.new_height
+                    );
+                    return;
+                }
+                Err(e) => warn!(
+                    "Attempt {}/{} to deliver round transition to webhook failed: {}",
+                    attempt, WEBHOOK_MAX_ATTEMPTS, e
+                ),
+            }
+
+            if attempt < WEBHOOK_MAX_ATTEMPTS {
+                thread::sleep(WEBHOOK_RETRY_DELAY);
+            }
+        }
+
+        error!(
+            "Giving up delivering round transition {} -> {} to webhook",
+            event.old_height, event.new_height
+        );
+    })
+}
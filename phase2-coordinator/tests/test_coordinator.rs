@@ -5,9 +5,16 @@
 //  NOTE: these tests require the phase1radix files to be placed in the phase2-coordinator folder
 
 use std::{
+    collections::HashMap,
     io::Write,
     net::{IpAddr, SocketAddr},
-    sync::Arc,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
 };
 
 use blake2::Digest;
@@ -15,24 +22,32 @@ use phase2_coordinator::{
     authentication::{KeyPair, Production, Signature},
     commands::{Computation, RandomSource},
     coordinator_state::CoordinatorState,
+    distribution::{ChallengeDistributor, DistributionError, Distributor, S3Distributor},
     environment::Testing,
-    objects::{ContributionInfo, LockedLocators, TrimmedContributionInfo},
+    objects::{
+        hash_email, ClientProvenance, ContributionInfo, ContributionProvenance, ContributionReceipt, LockedLocators,
+        Task, TrimmedContributionInfo,
+    },
     rest,
     rest_utils::{
-        self, ContributorStatus, PostChunkRequest, ACCESS_SECRET_HEADER, BODY_DIGEST_HEADER, CONTENT_LENGTH_HEADER,
-        PUBKEY_HEADER, SIGNATURE_HEADER, TOKENS_ZIP_FILE,
+        self, ContributorStatus, FinalizeChunkUploadRequest, NonceStore, PostChunkRequest, SignedJson,
+        VerificationWorker, ACCESS_SECRET_HEADER, BODY_DIGEST_HEADER, CONTENT_LENGTH_HEADER, COORDINATOR_SIGNATURE_HEADER,
+        MAX_JSON_BODY_SIZE, MAX_UPLOAD_PART_SIZE, NONCE_HEADER, NONCE_WINDOW, PROTOCOL_VERSION,
+        PROTOCOL_VERSION_HEADER, PUBKEY_HEADER, SIGNATURE_HEADER, TOKENS_ZIP_FILE, UPDATE_TIME, USER_AGENT_HEADER,
     },
     storage::{ContributionLocator, ContributionSignatureLocator, Object},
     testing::coordinator,
-    ContributionFileSignature, ContributionState, Coordinator, Participant,
+    CeremonyStatus, Cohort, ContributionFileSignature, ContributionState, Coordinator, CoordinatorError, Participant,
+    QueueAnalytics, VerificationFailureReason,
 };
 use reqwest::header::{HeaderValue, CONTENT_TYPE};
 use rocket::{
     catchers,
+    fairing::AdHoc,
     http::{ContentType, Header, Status},
     local::blocking::{Client, LocalRequest},
     routes,
-    tokio::sync::RwLock,
+    tokio::sync::{watch, RwLock},
     Build, Rocket,
 };
 use serde::Serialize;
@@ -41,6 +56,8 @@ use zip::write::FileOptions;
 
 const ROUND_HEIGHT: u64 = 1;
 
+static NONCE: AtomicU64 = AtomicU64::new(0);
+
 struct TestParticipant {
     _inner: Participant,
     address: IpAddr,
@@ -57,13 +74,23 @@ struct TestCtx {
     _tokens_tmp_dir: tempfile::TempDir,
 }
 
-/// Build the rocket server for testing with the proper configuration.
+/// Build the rocket server for testing with the proper configuration, distributing the challenges through S3.
 fn build_context() -> TestCtx {
+    build_context_with_distributor(Arc::new(S3Distributor))
+}
+
+/// Build the rocket server for testing with the proper configuration and the given challenge distributor.
+fn build_context_with_distributor(distributor: Distributor) -> TestCtx {
+    build_context_with(distributor, Testing::default())
+}
+
+/// Build the rocket server for testing with the given configuration and challenge distributor.
+fn build_context_with(distributor: Distributor, testing: Testing) -> TestCtx {
     std::env::set_var("TOKEN_BLACKLIST", "true");
     std::env::set_var("NAMADA_MPC_IP_BAN", "true");
 
     // Reset storage to prevent state conflicts between tests and initialize test environment
-    let environment = coordinator::initialize_test_environment(&Testing::default().into());
+    let environment = coordinator::initialize_test_environment(&testing.into());
 
     // Create token file
     // Need a fixed-name temp dir because of the lazy_static variables based on env
@@ -124,30 +151,24 @@ fn build_context() -> TestCtx {
     let (_, locked_locators) = coordinator.try_lock(&contributor1).unwrap();
 
     let coordinator: Arc<RwLock<Coordinator>> = Arc::new(RwLock::new(coordinator));
+    let verification_worker = VerificationWorker::default();
 
     let rocket = rocket::build()
-        .mount(
-            "/",
-            routes![
-                rest::join_queue,
-                rest::lock_chunk,
-                rest::contribute_chunk,
-                rest::update_coordinator,
-                rest::heartbeat,
-                rest::stop_coordinator,
-                rest::verify_chunks,
-                rest::get_contributor_queue_status,
-                rest::post_contribution_info,
-                rest::get_contributions_info,
-                rest::get_healthcheck,
-                rest::get_contribution_url,
-                rest::get_challenge_url,
-                rest::get_coordinator_state,
-                rest::update_cohorts,
-                rest::post_attestation
-            ],
-        )
+        .mount("/", rest::routes())
         .manage(coordinator)
+        .manage(distributor)
+        .manage(NonceStore::default())
+        .manage(verification_worker.clone())
+        .attach(AdHoc::on_liftoff("Verification worker", move |rocket| {
+            Box::pin(async move {
+                // Verify the contributions in the background, as the coordinator does
+                let coordinator = rocket.state::<Arc<RwLock<Coordinator>>>().unwrap().clone();
+                let (_, shutdown) = watch::channel(false);
+                rocket::tokio::spawn(
+                    async move { verification_worker.run(coordinator, None, UPDATE_TIME, shutdown).await },
+                );
+            })
+        }))
         .register(
             "/",
             catchers![
@@ -157,7 +178,12 @@ fn build_context() -> TestCtx {
                 rest_utils::io_error,
                 rest_utils::unprocessable_entity,
                 rest_utils::mismatching_checksum,
-                rest_utils::invalid_header
+                rest_utils::invalid_header,
+                rest_utils::replayed_request,
+                rest_utils::ceremony_not_started,
+                rest_utils::incompatible_protocol_version,
+                rest_utils::payload_too_large,
+                rest_utils::invalid_participant
             ],
         );
 
@@ -190,29 +216,81 @@ fn build_context() -> TestCtx {
     }
 }
 
+/// Current time in milliseconds, the unit of the nonces.
+fn now_millis() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64
+}
+
+/// Returns a strictly increasing nonce based on the current time, as the CLI does.
+fn next_nonce() -> u64 {
+    let now = now_millis();
+    let last = NONCE
+        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |last| {
+            Some(std::cmp::max(last + 1, now))
+        })
+        .unwrap();
+
+    std::cmp::max(last + 1, now)
+}
+
 /// Add headers and optional body to the request
-fn set_request<'a, T>(mut req: LocalRequest<'a>, keypair: &'a KeyPair, body: Option<&T>) -> LocalRequest<'a>
+fn set_request<'a, T>(req: LocalRequest<'a>, keypair: &'a KeyPair, body: Option<&T>) -> LocalRequest<'a>
+where
+    T: Serialize,
+{
+    set_request_with_nonce(req, keypair, body, next_nonce())
+}
+
+/// Add headers and optional body to the request, signed with the given nonce
+fn set_request_with_nonce<'a, T>(
+    req: LocalRequest<'a>,
+    keypair: &'a KeyPair,
+    body: Option<&T>,
+    nonce: u64,
+) -> LocalRequest<'a>
 where
     T: Serialize,
 {
-    let mut msg = keypair.pubkey().to_owned();
+    let json_body = body.map(|body| serde_json::to_vec(body).unwrap());
+
+    sign_request(
+        req,
+        keypair,
+        json_body.as_deref().map(|body| (ContentType::JSON, body)),
+        nonce,
+    )
+}
+
+/// Add headers and raw body to the request
+fn set_bytes_request<'a>(req: LocalRequest<'a>, keypair: &'a KeyPair, body: &[u8]) -> LocalRequest<'a> {
+    sign_request(req, keypair, Some((ContentType::Binary, body)), next_nonce())
+}
+
+/// Add headers and optional body, of the given content type, to the request, signed with the given nonce
+fn sign_request<'a>(
+    mut req: LocalRequest<'a>,
+    keypair: &'a KeyPair,
+    body: Option<(ContentType, &[u8])>,
+    nonce: u64,
+) -> LocalRequest<'a> {
+    let mut msg = format!("{}{}", keypair.pubkey(), nonce);
     req.add_header(Header::new(PUBKEY_HEADER, keypair.pubkey().to_owned()));
+    req.add_header(Header::new(NONCE_HEADER, nonce.to_string()));
 
-    if let Some(body) = body {
+    if let Some((content_type, body)) = body {
         // Body digest
-        let json_body = serde_json::to_string(body).unwrap();
         let mut hasher = Sha256::new();
-        hasher.update(&json_body);
+        hasher.update(body);
         let digest = base64::encode(hasher.finalize());
-        msg = format!("{}{}{}", msg, json_body.len(), &digest);
+        msg = format!("{}{}{}", msg, body.len(), &digest);
         req.add_header(Header::new(BODY_DIGEST_HEADER, format!("sha-256={}", digest)));
 
         // Body length
-        req.add_header(Header::new(CONTENT_LENGTH_HEADER, json_body.len().to_string()));
+        req.add_header(Header::new(CONTENT_LENGTH_HEADER, body.len().to_string()));
 
-        // Attach json serialized body
-        req.add_header(ContentType::JSON);
-        req = req.body(&json_body);
+        // Attach the body
+        req.add_header(content_type);
+        req = req.body(body);
     }
 
     // Sign request
@@ -313,6 +391,11 @@ fn stop_coordinator() {
     let ctx = build_context();
     let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
 
+    // Wrong, unsigned request
+    let response = client.get("/stop").dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.body().is_some());
+
     // Wrong, request from non-coordinator participant
     let mut req = client.get("/stop");
     req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
@@ -369,14 +452,83 @@ fn get_contributor_queue_status() {
     }
 
     // Ok
+    let nonce = next_nonce();
+    let pubkey = ctx.contributors[0].keypair.pubkey();
     req = client.get("/contributor/queue_status");
+    req = set_request_with_nonce::<()>(req, &ctx.contributors[0].keypair, None, nonce);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    // The status is signed by the coordinator, bound to the nonce and the public key of the request
+    let signature = response
+        .headers()
+        .get_one(COORDINATOR_SIGNATURE_HEADER)
+        .expect("Missing coordinator signature")
+        .to_owned();
+    let body = response.into_string().unwrap();
+    let coordinator_pubkey = ctx.coordinator.keypair.pubkey();
+    assert!(Production.verify(
+        coordinator_pubkey,
+        &SignedJson::message(nonce, pubkey, &body),
+        &signature
+    ));
+    for message in [
+        SignedJson::message(nonce, pubkey, &body.replace("Round", "Other")),
+        SignedJson::message(nonce + 1, pubkey, &body),
+        SignedJson::message(nonce, ctx.unknown_participant.keypair.pubkey(), &body),
+        body.clone(),
+    ] {
+        assert!(!Production.verify(coordinator_pubkey, &message, &signature));
+    }
+    match serde_json::from_str::<ContributorStatus>(&body).unwrap() {
+        ContributorStatus::Round => (),
+        _ => panic!("Wrong ContributorStatus"),
+    }
+}
+
+#[test]
+fn rejected_contributor_status() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // The contribution of the current contributor fails the verification
+    let contributor = Participant::new_contributor(ctx.contributors[0].keypair.pubkey());
+    let reason = rest_utils::rejection_reason(&anyhow::Error::from(CoordinatorError::ContributionVerificationFailed {
+        reason: VerificationFailureReason::ChallengeHashMismatch,
+    }));
+    assert_eq!(VerificationFailureReason::ChallengeHashMismatch.to_string(), reason);
+    {
+        let coordinator = client.rocket().state::<Arc<RwLock<Coordinator>>>().unwrap();
+        let mut coordinator = coordinator.try_write().unwrap();
+        let sigkey = ctx.contributors[0].keypair.sigkey().to_owned();
+        coordinator
+            .run_computation(ROUND_HEIGHT, 0, 1, &contributor, &sigkey, &[0; 32])
+            .unwrap();
+        coordinator.try_contribute(&contributor, 0).unwrap();
+        assert_eq!(contributor, coordinator.reject_contribution(reason.clone()).unwrap());
+    }
+
+    // The contributor gets the reason of the rejection
+    let mut req = client.get("/contributor/queue_status");
     req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
     match response.into_json::<ContributorStatus>().unwrap() {
-        ContributorStatus::Round => (),
+        ContributorStatus::Rejected { reason: status_reason } => assert_eq!(reason, status_reason),
         _ => panic!("Wrong ContributorStatus"),
     }
+
+    // The other failures of the verification are not detailed to the contributor
+    let error = anyhow::Error::from(CoordinatorError::IOError(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "/secret/path/round_1/chunk_0/contribution_1.unverified",
+    )));
+    let reason = rest_utils::rejection_reason(&error);
+    assert!(!reason.contains("/secret/path"));
+    assert_eq!(
+        reason,
+        rest_utils::rejection_reason(&anyhow::anyhow!("Verification panicked"))
+    );
 }
 
 #[test]
@@ -388,7 +540,7 @@ fn heartbeat() {
     let mut req = client.post("/contributor/heartbeat");
     req = set_request::<()>(req, &ctx.unknown_participant.keypair, None);
     let response = req.dispatch();
-    assert_eq!(response.status(), Status::InternalServerError);
+    assert_eq!(response.status(), Status::NotFound);
     assert!(response.body().is_some());
 
     // Ok
@@ -397,6 +549,107 @@ fn heartbeat() {
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
     assert!(response.body().is_none());
+
+    // Banned contributor, evicted from the ceremony
+    let contributor = Participant::new_contributor(ctx.contributors[0].keypair.pubkey());
+    client
+        .rocket()
+        .state::<Arc<RwLock<Coordinator>>>()
+        .unwrap()
+        .try_write()
+        .unwrap()
+        .ban_participant(&contributor)
+        .unwrap();
+    req = client.post("/contributor/heartbeat");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Gone);
+    assert!(response.body().is_some());
+}
+
+/// Test that the requests signed with a malformed public key are refused before reaching the coordinator.
+#[test]
+fn malformed_pubkey() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let sigkey = ctx.contributors[0].keypair.sigkey().to_owned();
+    let pubkey = ctx.contributors[0].keypair.pubkey();
+
+    for malformed in ["not_hex", &pubkey[..62], &format!("{}00", pubkey)] {
+        let keypair = KeyPair::custom_new(sigkey.clone(), malformed.to_owned());
+        let mut req = client.post("/contributor/heartbeat");
+        req = set_request::<()>(req, &keypair, None);
+        let response = req.dispatch();
+        assert_eq!(response.status(), Status::BadRequest);
+        assert_eq!(
+            response.into_string().unwrap(),
+            format!("Coordinator failed: ParticipantInvalid({:?})", malformed)
+        );
+    }
+
+    // Well-formed public key
+    let mut req = client.post("/contributor/heartbeat");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn replayed_request() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Ok
+    let mut req = client.post("/contributor/heartbeat");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let replayed_req = req.clone();
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    // Replay of the captured request
+    let response = replayed_req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+    assert!(response.into_string().unwrap().contains("ReplayedRequest"));
+
+    // Ok, new nonce
+    req = client.post("/contributor/heartbeat");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    // Nonces out of the window around the current time
+    for nonce in [now_millis() - 2 * NONCE_WINDOW, now_millis() + 2 * NONCE_WINDOW] {
+        req = client.post("/contributor/heartbeat");
+        req = set_request_with_nonce::<()>(req, &ctx.contributors[0].keypair, None, nonce);
+        let response = req.dispatch();
+        assert_eq!(response.status(), Status::Unauthorized);
+        assert!(response.into_string().unwrap().contains("ReplayedRequest"));
+    }
+}
+
+#[test]
+fn nonce_store_eviction() {
+    let window = std::time::Duration::from_millis(200);
+    let store = NonceStore::with_window(window);
+    let now = now_millis();
+    store.check("first", now).unwrap();
+    store.check("second", now).unwrap();
+    assert_eq!(2, store.tracked_participants());
+    assert!(matches!(
+        store.check("first", now),
+        Err(CoordinatorError::ReplayedRequest)
+    ));
+
+    // Nonces issued before the store was created, e.g. before a restart, are rejected
+    assert!(matches!(
+        NonceStore::default().check("first", now - 1),
+        Err(CoordinatorError::ReplayedRequest)
+    ));
+
+    // The participants idle for longer than the window are forgotten
+    std::thread::sleep(2 * window);
+    store.check("third", now_millis()).unwrap();
+    assert_eq!(1, store.tracked_participants());
 }
 
 #[test]
@@ -444,7 +697,10 @@ fn join_queue() {
     let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
 
     // Wrong request, invalid token
-    let mut req = client.post("/contributor/join_queue").remote(socket_address);
+    let mut req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
     req = set_request::<String>(
         req,
         &ctx.unknown_participant.keypair,
@@ -457,7 +713,10 @@ fn join_queue() {
     assert!(response.body().is_some());
 
     // Ok request
-    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
     req = set_request::<String>(
         req,
         &ctx.unknown_participant.keypair,
@@ -470,7 +729,10 @@ fn join_queue() {
     assert!(response.body().is_some());
 
     // Wrong request, IP already in queue
-    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
     req = set_request::<String>(
         req,
         &ctx.contributors[1].keypair,
@@ -484,7 +746,10 @@ fn join_queue() {
 
     // Wrong request, token already in queue
     let socket_address = SocketAddr::new(IpAddr::V4("0.0.0.4".parse().unwrap()), 8080);
-    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
     req = set_request::<String>(
         req,
         &ctx.contributors[1].keypair,
@@ -497,7 +762,10 @@ fn join_queue() {
     assert!(response.body().is_some());
 
     // Wrong request, already existing contributor
-    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
     req = set_request::<String>(
         req,
         &ctx.unknown_participant.keypair,
@@ -510,6 +778,322 @@ fn join_queue() {
     assert!(response.body().is_some());
 }
 
+/// Test the public status of the ceremony.
+#[test]
+fn ceremony_status() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // No signature required
+    let response = client.get("/ceremony/status").header(ContentType::JSON).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let status: CeremonyStatus = response.into_json().unwrap();
+    assert_eq!(ROUND_HEIGHT, status.round_height);
+    assert_eq!(0, status.verification_backlog);
+}
+
+/// Test the recording of the provenance of the contributors and its retrieval, reserved to the coordinator.
+#[test]
+fn contribution_provenance() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // The client of the contributor is recorded when joining the queue
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
+    let mut req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION))
+        .header(Header::new(USER_AGENT_HEADER, "namada-ts"));
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    let expected = ClientProvenance {
+        ip_address: Some(ctx.unknown_participant.address),
+        user_agent: Some(String::from("namada-ts")),
+        client_version: Some(String::from(PROTOCOL_VERSION)),
+    };
+    assert_eq!(
+        Some(&expected),
+        client
+            .rocket()
+            .state::<Arc<RwLock<Coordinator>>>()
+            .unwrap()
+            .try_read()
+            .unwrap()
+            .state()
+            .get_client_provenance(&Participant::new_contributor(ctx.unknown_participant.keypair.pubkey()))
+    );
+
+    // Wrong request, the provenance is reserved to the coordinator
+    req = client.get("/admin/contribution_provenance");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    // Nothing was contributed from a known client yet
+    req = client.get("/admin/contribution_provenance");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let provenance: Vec<ContributionProvenance> = response.into_json().unwrap();
+    assert!(provenance.is_empty());
+}
+
+/// Test the analytics of the queue, reserved to the coordinator.
+#[test]
+fn queue_analytics() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Wrong request, the analytics are reserved to the coordinator
+    let mut req = client.get("/admin/queue/analytics");
+    req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+
+    req = client.get("/admin/queue/analytics");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let before: QueueAnalytics = response.into_json().unwrap();
+
+    // A new contributor joins the queue
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
+    req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    req = client.get("/admin/queue/analytics");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let after: QueueAnalytics = response.into_json().unwrap();
+    assert_eq!(before.joined + 1, after.joined);
+    assert_eq!(before.joined_last_hour + 1, after.joined_last_hour);
+    assert_eq!(before.waiting + 1, after.waiting);
+    assert_eq!(before.dropped, after.dropped);
+}
+
+/// Test the schedule of the cohorts and the rejection of the tokens of a future cohort.
+#[test]
+fn cohort_schedule() {
+    const COHORT_TIME: u64 = 86400;
+    let ctx = build_context_with(Arc::new(S3Distributor), Testing::default().cohort_duration(COHORT_TIME));
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Schedule a second cohort
+    let tokens = get_serialized_tokens_zip(vec![
+        "[\"9nFeNpukSn1eVwNc2vkfP7rdLh2njm5ewmCGxSLTW3GYmKP51fKjbRUvHDmntjEaQiq7iFux9tumgWEWVHwHQCs31oitpqBpMWpMydo1DnuFyLpsD6C\", \"9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek\", \"9nFeNpukSn1eVwNc2vkfP8SP4HrxTh9F86CY5pNWw8RF3jZa91q2i3yvE7ugpn9w2RzoZBZrdskgckmvJuVKq6ZWxfV8TepZYFd9SeARGHexi7tGGV2\"]",
+        "[\"9nFeNpukSn1eVwNc2vkfP8TAaw6DXNAgCNpxiQc437BxT3iF2xUMdo6wYQjqwxHwAZjVhQzdH3QMpJSbXvaDcnkVu6Ktt22AfYDypK2h72vuQK9fGNp\"]",
+    ]);
+    let mut req = client.post("/update_cohorts");
+    req = set_request::<Vec<u8>>(req, &ctx.coordinator.keypair, Some(&tokens));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    // Get the schedule, no signature required
+    let response = client.get("/cohorts").header(ContentType::JSON).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let schedule: Vec<Cohort> = response.into_json().unwrap();
+    assert_eq!(2, schedule.len());
+    assert_eq!(vec![1, 2], schedule.iter().map(|c| c.index).collect::<Vec<_>>());
+    assert_eq!(vec![3, 1], schedule.iter().map(|c| c.capacity).collect::<Vec<_>>());
+    assert_eq!(
+        time::Duration::seconds(COHORT_TIME as i64),
+        schedule[0].end - schedule[0].start
+    );
+    assert_eq!(schedule[0].end, schedule[1].start);
+
+    // Wrong request, the cohort of the token hasn't started yet
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
+    req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP8TAaw6DXNAgCNpxiQc437BxT3iF2xUMdo6wYQjqwxHwAZjVhQzdH3QMpJSbXvaDcnkVu6Ktt22AfYDypK2h72vuQK9fGNp"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert!(response.into_string().unwrap().contains("cohort 2"));
+
+    // The tokens of the current cohort are accepted
+    req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
+/// Test the check of the protocol version of the client.
+#[test]
+fn protocol_version() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+
+    // Matching version
+    let req = client
+        .get("/version")
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_json::<String>().unwrap(), PROTOCOL_VERSION);
+
+    // Mismatched major version
+    let req = client
+        .get("/version")
+        .header(Header::new(PROTOCOL_VERSION_HEADER, "0.9"));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::UpgradeRequired);
+    assert!(response.into_string().unwrap().contains("please upgrade your client"));
+
+    // Missing version, a client predating the check
+    let response = client.get("/version").dispatch();
+    assert_eq!(response.status(), Status::UpgradeRequired);
+
+    // Joining the queue with a mismatched version
+    let major: u64 = PROTOCOL_VERSION.split_once('.').unwrap().0.parse().unwrap();
+    let next_major_version = format!("{}.0", major + 1);
+    let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
+    let mut req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, next_major_version));
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::UpgradeRequired);
+
+    // Joining the queue with a matching version
+    let mut req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
+    req = set_request::<String>(
+        req,
+        &ctx.unknown_participant.keypair,
+        Some(&format!(
+            "9nFeNpukSn1eVwNc2vkfP7sQsLG3oS7623phb2Zzc23GAdXjuby4XAbwbWbx1uNaYrZorVLio4ZSt3u95sgi4fsS8hiZ3XkEttBF6q4461dGpoWv7ek"
+        )),
+    );
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
+/// Test the tracing of the requests.
+#[test]
+fn http_tracer() {
+    let build_client = |body_limit: Option<usize>| {
+        let lines = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink_lines = lines.clone();
+        let sink: rest_utils::HttpTraceSink = Arc::new(move |line| sink_lines.lock().unwrap().push(line));
+        let rocket = rocket::build()
+            .mount("/", routes![rest::get_protocol_version])
+            .attach(rest_utils::HttpTracer::with_sink(body_limit, sink))
+            .register("/", catchers![rest_utils::incompatible_protocol_version]);
+
+        (Client::tracked(rocket).expect("Invalid rocket instance"), lines)
+    };
+
+    // Enabled
+    let (client, lines) = build_client(Some(2));
+    let response = client
+        .get("/version")
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    // The body is still delivered whole
+    assert_eq!(response.into_json::<String>().unwrap(), PROTOCOL_VERSION);
+
+    let lines = lines.lock().unwrap();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("--> GET /version"));
+    assert!(lines[1].starts_with("<-- GET /version 200 OK"));
+    // The body is truncated
+    assert!(lines[1].ends_with(&format!(
+        "\"{}... ({} bytes)",
+        &PROTOCOL_VERSION[..1],
+        PROTOCOL_VERSION.len() + 2
+    )));
+
+    // Disabled
+    let (client, lines) = build_client(None);
+    let response = client
+        .get("/version")
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION))
+        .dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(lines.lock().unwrap().is_empty());
+}
+
+/// Test that a contributor reconnecting gets the task it holds back.
+#[test]
+fn get_chunk() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let locked_locators = ctx.contributors[0].locked_locators.as_ref().unwrap();
+
+    let mut tasks = Vec::new();
+    for _ in 0..2 {
+        let mut req = client.get("/contributor/chunk");
+        req = set_request::<()>(req, &ctx.contributors[0].keypair, None);
+        let response = req.dispatch();
+        assert_eq!(response.status(), Status::Ok);
+        let (task, locators): (Task, LockedLocators) = response.into_json().unwrap();
+        assert_eq!(locked_locators.next_contribution(), locators.next_contribution());
+        tasks.push(task);
+    }
+    assert_eq!(tasks[0], tasks[1]);
+    assert_eq!(
+        Task::new(
+            locked_locators.next_contribution().chunk_id(),
+            locked_locators.next_contribution().contribution_id()
+        ),
+        tasks[0]
+    );
+
+    // Wrong request, the participant is not contributing to the current round
+    let mut req = client.get("/contributor/chunk");
+    req = set_request::<()>(req, &ctx.unknown_participant.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Unauthorized);
+}
+
 /// Test wrong usage of lock_chunk.
 #[test]
 fn wrong_lock_chunk() {
@@ -524,6 +1108,113 @@ fn wrong_lock_chunk() {
     assert!(response.body().is_some());
 }
 
+/// Test lock_chunk before the beginning of the ceremony.
+#[test]
+fn lock_chunk_ceremony_not_started() {
+    // Coordinator stuck at round 0, no contributor ever joined the queue
+    let environment = coordinator::initialize_test_environment(&Testing::default().into());
+    let mut coordinator = Coordinator::new(environment, Arc::new(Production)).unwrap();
+    coordinator.initialize().unwrap();
+    assert_eq!(coordinator.current_round_height().unwrap(), 0);
+
+    let rocket = rocket::build()
+        .mount("/", routes![rest::lock_chunk])
+        .manage(Arc::new(RwLock::new(coordinator)))
+        .manage(NonceStore::default())
+        .register(
+            "/",
+            catchers![
+                rest_utils::invalid_signature,
+                rest_utils::unauthorized,
+                rest_utils::ceremony_not_started
+            ],
+        );
+    let client = Client::tracked(rocket).expect("Invalid rocket instance");
+    let keypair = KeyPair::new();
+
+    let mut req = client.get("/contributor/lock_chunk");
+    req = set_request::<u8>(req, &keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Conflict);
+    assert_eq!(response.into_string().unwrap(), "The ceremony hasn't begun yet");
+}
+
+/// Test that a read-only coordinator refuses the mutating endpoints and keeps serving the reads.
+#[test]
+fn read_only_coordinator() {
+    // Run the ceremony up to a locked chunk of round 1
+    let environment = coordinator::initialize_test_environment(&Testing::default().into());
+    let mut coordinator = Coordinator::new(environment, Arc::new(Production)).unwrap();
+    coordinator.initialize().unwrap();
+    let keypair = KeyPair::new();
+    let contributor = Participant::new_contributor(keypair.pubkey());
+    coordinator
+        .add_to_queue(
+            contributor.clone(),
+            Some(IpAddr::V4("0.0.0.1".parse().unwrap())),
+            String::from("token"),
+            10,
+        )
+        .unwrap();
+    coordinator.update().unwrap();
+    coordinator.try_lock(&contributor).unwrap();
+    let coordinator_keypair = KeyPair::custom_new(
+        coordinator.environment().default_verifier_signing_key(),
+        coordinator.environment().coordinator_verifiers()[0].address(),
+    );
+    drop(coordinator);
+
+    // Serve the same storage read-only
+    let mut coordinator = Coordinator::new(Testing::default().read_only(true).into(), Arc::new(Production)).unwrap();
+    coordinator.initialize().unwrap();
+    let rocket = rocket::build()
+        .mount(
+            "/",
+            routes![
+                rest::join_queue,
+                rest::lock_chunk,
+                rest::heartbeat,
+                rest::update_coordinator,
+                rest::get_ceremony_status,
+                rest::get_cohort_schedule
+            ],
+        )
+        .manage(Arc::new(RwLock::new(coordinator)))
+        .manage(NonceStore::default());
+    let client = Client::tracked(rocket).expect("Invalid rocket instance");
+
+    let new_keypair = KeyPair::new();
+    let mut req = client
+        .post("/contributor/join_queue")
+        .remote(SocketAddr::new(IpAddr::V4("0.0.0.2".parse().unwrap()), 8080))
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
+    req = set_request::<String>(req, &new_keypair, Some(&String::from("token")));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+    assert_eq!(response.into_string().unwrap(), "Coordinator failed: CeremonyClosed");
+
+    let mut req = client.get("/contributor/lock_chunk");
+    req = set_request::<u8>(req, &keypair, None);
+    assert_eq!(req.dispatch().status(), Status::Forbidden);
+
+    let mut req = client.post("/contributor/heartbeat");
+    req = set_request::<()>(req, &keypair, None);
+    assert_eq!(req.dispatch().status(), Status::Forbidden);
+
+    let mut req = client.get("/update");
+    req = set_request::<()>(req, &coordinator_keypair, None);
+    assert_eq!(req.dispatch().status(), Status::Forbidden);
+
+    // Reads still succeed
+    let response = client.get("/ceremony/status").header(ContentType::JSON).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let status: CeremonyStatus = response.into_json().unwrap();
+    assert_eq!(ROUND_HEIGHT, status.round_height);
+
+    let response = client.get("/cohorts").header(ContentType::JSON).dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
 /// Test wrong usage of get_challenge.
 #[test]
 fn wrong_get_challenge() {
@@ -540,6 +1231,61 @@ fn wrong_get_challenge() {
     assert!(response.body().is_some());
 }
 
+/// Challenge distributor keeping the challenges in memory, recording the requested keys.
+#[derive(Default)]
+struct MockDistributor {
+    challenges: Mutex<HashMap<String, Vec<u8>>>,
+    requested_keys: Mutex<Vec<String>>,
+}
+
+#[rocket::async_trait]
+impl ChallengeDistributor for MockDistributor {
+    async fn get_presigned_url(&self, key: &str) -> Result<Option<String>, DistributionError> {
+        self.requested_keys.lock().unwrap().push(key.to_string());
+        let challenges = self.challenges.lock().unwrap();
+
+        Ok(challenges
+            .contains_key(key)
+            .then(|| format!("https://cdn.example/{}?signed", key)))
+    }
+
+    async fn upload(&self, key: &str, challenge: Vec<u8>) -> Result<String, DistributionError> {
+        self.challenges.lock().unwrap().insert(key.to_string(), challenge);
+
+        Ok(format!("https://cdn.example/{}?signed", key))
+    }
+}
+
+/// Test that the challenge is uploaded and its presigned url requested through the distributor.
+#[test]
+fn get_challenge_url_through_distributor() {
+    let distributor = Arc::new(MockDistributor::default());
+    let ctx = build_context_with_distributor(distributor.clone());
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let key = format!("round_{}/chunk_0/contribution_0.verified", ROUND_HEIGHT);
+    let expected_url = format!("https://cdn.example/{}?signed", key);
+
+    // The first request uploads the challenge
+    let mut req = client.post("/contributor/challenge");
+    req = set_request::<u64>(req, &ctx.contributors[0].keypair, Some(&ROUND_HEIGHT));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let url: String = response.into_json().unwrap();
+    assert_eq!(expected_url, url);
+    assert_eq!(vec![key.clone()], *distributor.requested_keys.lock().unwrap());
+    assert!(!distributor.challenges.lock().unwrap()[&key].is_empty());
+
+    // The following ones only request the presigned url of the uploaded challenge
+    let mut req = client.post("/contributor/challenge");
+    req = set_request::<u64>(req, &ctx.contributors[0].keypair, Some(&ROUND_HEIGHT));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let url: String = response.into_json().unwrap();
+    assert_eq!(expected_url, url);
+    assert_eq!(vec![key.clone(), key], *distributor.requested_keys.lock().unwrap());
+    assert_eq!(1, distributor.challenges.lock().unwrap().len());
+}
+
 /// Test wrong usage of post_contribution_chunk.
 #[test]
 fn wrong_post_contribution_chunk() {
@@ -567,6 +1313,120 @@ fn wrong_post_contribution_chunk() {
     assert!(response.body().is_some());
 }
 
+/// Test that request bodies larger than the limit of their route, and uploads larger than the expected contribution,
+/// are refused before reaching the disk.
+#[test]
+fn oversize_contribution_upload() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let contribution_size = Object::anoma_contribution_file_size(ROUND_HEIGHT, 1) as usize;
+    let upload_part = Path::new("./transcript/testing/round_1/chunk_0/contribution_1.unverified.part");
+
+    // Json body larger than the default limit
+    let oversize_json = "0".repeat(MAX_JSON_BODY_SIZE as usize);
+    let mut req = client.post("/upload/chunk");
+    req = set_request(req, &ctx.contributors[0].keypair, Some(&oversize_json));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+
+    // Part larger than the limit of the upload route, refused before being read
+    let oversize_part = vec![u8::MAX; MAX_UPLOAD_PART_SIZE as usize + 1];
+    req = client.post("/upload/chunk/part/0");
+    req = set_bytes_request(req, &ctx.contributors[0].keypair, &oversize_part);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+    assert!(response.body().is_some());
+    assert!(!upload_part.exists());
+
+    // Body within the limit but carrying a part past the end of the contribution
+    let oversize_part = vec![0; contribution_size + 1];
+    req = client.post("/upload/chunk/part/0");
+    req = set_bytes_request(req, &ctx.contributors[0].keypair, &oversize_part);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+    assert!(response.body().is_some());
+    assert!(!upload_part.exists());
+}
+
+/// Test that a truncated or corrupted upload is rejected when finalized, rather than failing later at verification.
+#[test]
+fn truncated_contribution_upload() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let keypair = &ctx.contributors[0].keypair;
+    let contribution_locator = ContributionLocator::new(ROUND_HEIGHT, 0, 1, false);
+    let contribution_size = Object::anoma_contribution_file_size(ROUND_HEIGHT, 1) as usize;
+    let contribution = vec![7; contribution_size];
+    let uploaded = Path::new("./transcript/testing/round_1/chunk_0/contribution_1.unverified.uploaded");
+    let finalize = FinalizeChunkUploadRequest::new(contribution_locator, &contribution);
+    let second_half = format!("/upload/chunk/part/{}", contribution_size / 2);
+
+    // The upload is cut off halfway
+    let mut req = client.post("/upload/chunk/part/0");
+    req = set_bytes_request(req, keypair, &contribution[..contribution_size / 2]);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert_eq!(response.into_json::<u64>().unwrap(), (contribution_size / 2) as u64);
+
+    req = client.post("/upload/chunk/finalize");
+    req = set_request::<FinalizeChunkUploadRequest>(req, keypair, Some(&finalize));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::PayloadTooLarge);
+    assert!(!uploaded.exists());
+
+    // The rest of the upload is corrupted
+    let corrupted = vec![0; contribution_size - contribution_size / 2];
+    req = client.post(second_half.as_str());
+    req = set_bytes_request(req, keypair, &corrupted);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    req = client.post("/upload/chunk/finalize");
+    req = set_request::<FinalizeChunkUploadRequest>(req, keypair, Some(&finalize));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::UnprocessableEntity);
+    assert!(!uploaded.exists());
+}
+
+/// Test that the locators supplied by a contributor must be the ones issued with the lock of its chunk.
+#[test]
+fn tampered_locators() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let keypair = &ctx.contributors[0].keypair;
+
+    // The contributor locked chunk 0, not chunk 1: the parts are written to the contribution issued with the lock
+    let contribution_locator = ContributionLocator::new(ROUND_HEIGHT, 1, 1, false);
+    let mut req = client.post("/upload/chunk/part/0");
+    req = set_bytes_request(req, keypair, &[0; 64]);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    assert!(Path::new("./transcript/testing/round_1/chunk_0/contribution_1.unverified.part").exists());
+    assert!(!Path::new("./transcript/testing/round_1/chunk_1/contribution_1.unverified.part").exists());
+
+    req = client.post("/upload/chunk/finalize");
+    let finalize = FinalizeChunkUploadRequest::new(contribution_locator, &[0; 64]);
+    req = set_request::<FinalizeChunkUploadRequest>(req, keypair, Some(&finalize));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+
+    let r = PostChunkRequest::new(
+        ROUND_HEIGHT,
+        contribution_locator,
+        ContributionSignatureLocator::new(ROUND_HEIGHT, 1, 1, false),
+    );
+    req = client.post("/contributor/contribute_chunk");
+    req = set_request::<PostChunkRequest>(req, keypair, Some(&r));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+
+    // The challenge of another round
+    req = client.post("/contributor/challenge");
+    req = set_request::<u64>(req, keypair, Some(&(ROUND_HEIGHT + 1)));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Forbidden);
+}
+
 /// Test wrong usage of contribute_chunk.
 #[test]
 fn wrong_contribute_chunk() {
@@ -666,6 +1526,126 @@ fn wrong_post_contribution_info() {
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Unauthorized);
     assert!(response.body().is_some());
+
+    // Info attributed to another participant, even if validly signed by it
+    let mut contrib_info = ContributionInfo::default();
+    contrib_info.public_key = ctx.contributors[1].keypair.pubkey().to_owned();
+    contrib_info.ceremony_round = ROUND_HEIGHT;
+    contrib_info.try_sign(&ctx.contributors[1].keypair).unwrap();
+    req = client.post("/contributor/contribution_info");
+    req = set_request::<ContributionInfo>(req, &ctx.contributors[0].keypair, Some(&contrib_info));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(response.into_string().unwrap().contains("InfoPublicKeyMismatch"));
+
+    // Forged info, signed by another participant
+    contrib_info.public_key = ctx.contributors[0].keypair.pubkey().to_owned();
+    req = client.post("/contributor/contribution_info");
+    req = set_request::<ContributionInfo>(req, &ctx.contributors[0].keypair, Some(&contrib_info));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+    assert!(response.into_string().unwrap().contains("InfoSignatureInvalid"));
+
+    // Unsigned info
+    contrib_info.contributor_info_signature = String::new();
+    req = client.post("/contributor/contribution_info");
+    req = set_request::<ContributionInfo>(req, &ctx.contributors[0].keypair, Some(&contrib_info));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::BadRequest);
+
+    // Info self-signed by the current contributor
+    contrib_info.try_sign(&ctx.contributors[0].keypair).unwrap();
+    req = client.post("/contributor/contribution_info");
+    req = set_request::<ContributionInfo>(req, &ctx.contributors[0].keypair, Some(&contrib_info));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+}
+
+#[test]
+fn anonymous_contribution_info() {
+    let ctx = build_context();
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let keypair = &ctx.contributors[0].keypair;
+
+    // Info with no personal data, only the public key and the signed hashes
+    let mut contrib_info = ContributionInfo::default();
+    contrib_info.public_key = keypair.pubkey().to_owned();
+    contrib_info.ceremony_round = ROUND_HEIGHT;
+    contrib_info.contribution_hash = hex::encode([1u8; 64]);
+    contrib_info.contribution_hash_signature = Production
+        .sign(keypair.sigkey(), &contrib_info.contribution_hash)
+        .unwrap();
+    contrib_info.contribution_file_hash = hex::encode([2u8; 64]);
+    contrib_info.contribution_file_signature = Production
+        .sign(keypair.sigkey(), &contrib_info.contribution_file_hash)
+        .unwrap();
+    contrib_info.try_sign(keypair).unwrap();
+
+    let mut req = client.post("/contributor/contribution_info");
+    req = set_request::<ContributionInfo>(req, keypair, Some(&contrib_info));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    // The stored info is still verifiable and carries no personal data
+    let mut req = client.get("/admin/contribution_info");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let stored: Vec<ContributionInfo> = response.into_json().unwrap();
+    assert_eq!(1, stored.len());
+    assert!(stored[0].verify_signature().unwrap());
+    assert_eq!(contrib_info.public_key, stored[0].public_key);
+    assert!(stored[0].full_name.is_none());
+    assert!(stored[0].email.is_none());
+    assert!(stored[0].memo.is_none());
+}
+
+/// Test that the public contributions' info hides the identity of the contributors that the admin endpoint reveals.
+#[test]
+fn redacted_contribution_info() {
+    let identity_key = [7; 32];
+    let ctx = build_context_with(
+        Arc::new(S3Distributor),
+        Testing::default().redact_contributor_identity(identity_key),
+    );
+    let client = Client::tracked(ctx.rocket).expect("Invalid rocket instance");
+    let keypair = &ctx.contributors[0].keypair;
+
+    let mut contrib_info = ContributionInfo::default();
+    contrib_info.full_name = Some(String::from("Test Name"));
+    contrib_info.email = Some(String::from("test@mail.dev"));
+    contrib_info.public_key = keypair.pubkey().to_owned();
+    contrib_info.ceremony_round = ROUND_HEIGHT;
+    contrib_info.try_sign(keypair).unwrap();
+
+    let mut req = client.post("/contributor/contribution_info");
+    req = set_request::<ContributionInfo>(req, keypair, Some(&contrib_info));
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+
+    // The public endpoint only identifies the contributor by the keyed hash of its email
+    let response = client.get("/contribution_info").dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let body = response.into_string().unwrap();
+    assert!(!body.contains("Test Name"));
+    assert!(!body.contains("test@mail.dev"));
+    let summary: Vec<TrimmedContributionInfo> = serde_json::from_str(&body).unwrap();
+    assert_eq!(1, summary.len());
+    assert_eq!(None, summary[0].full_name());
+    assert_eq!(
+        Some(hash_email(&identity_key, "test@mail.dev").as_str()),
+        summary[0].email_hash()
+    );
+
+    // The admin endpoint reveals it
+    let mut req = client.get("/admin/contribution_info");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
+    let stored: Vec<ContributionInfo> = response.into_json().unwrap();
+    assert_eq!(1, stored.len());
+    assert_eq!(Some("Test Name"), stored[0].full_name.as_deref());
+    assert_eq!(Some("test@mail.dev"), stored[0].email.as_deref());
 }
 
 /// Test a full contribution:
@@ -732,7 +1712,7 @@ fn contribution() {
 
     let mut contribution: Vec<u8> = Vec::new();
     contribution.write_all(challenge_hash.as_slice()).unwrap();
-    let entropy = RandomSource::Entropy(String::from("entropy"));
+    let entropy = RandomSource::Mixed(String::from("entropy"));
     Computation::contribute_test_masp(&challenge, &mut contribution, &entropy);
 
     // Initial contribution size is 2332 but the Coordinator expect ANOMA_BASE_FILE_SIZE. Extend to this size with trailing 0s
@@ -767,6 +1747,7 @@ fn contribution() {
     let mut contrib_info = ContributionInfo::default();
     contrib_info.full_name = Some(String::from("Test Name"));
     contrib_info.email = Some(String::from("test@mail.dev"));
+    contrib_info.memo = Some(String::from("Contributed from an air-gapped machine"));
     contrib_info.public_key = ctx.contributors[0].keypair.pubkey().to_owned();
     contrib_info.ceremony_round = ctx.contributors[0]
         .locked_locators
@@ -789,14 +1770,51 @@ fn contribution() {
     req = set_request::<PostChunkRequest>(req, &ctx.contributors[0].keypair, Some(&post_chunk));
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
+
+    // The receipt of the contribution is signed by the coordinator
+    let receipt: ContributionReceipt = response.into_json().unwrap();
+    assert_eq!(receipt.round_height, ROUND_HEIGHT);
+    assert_eq!(receipt.contribution_id, 1);
+    assert_eq!(receipt.contribution_hash, hex::encode(response_hash));
+    assert!(receipt.verify(ctx.coordinator.keypair.pubkey()));
+    assert!(!receipt.verify(ctx.contributors[0].keypair.pubkey()));
+
+    // Verify chunk, the request returns without waiting for the verification worker
+    req = client.get("/verify");
+    req = set_request::<()>(req, &ctx.coordinator.keypair, None);
+    let response = req.dispatch();
+    assert_eq!(response.status(), Status::Ok);
     assert!(response.body().is_none());
 
-    // Verify chunk
+    let verify_timer = std::time::Instant::now();
+    let coordinator = client.rocket().state::<Arc<RwLock<Coordinator>>>().unwrap();
+    while !coordinator
+        .try_read()
+        .map(|coordinator| coordinator.get_pending_verifications().is_empty())
+        .unwrap_or(false)
+    {
+        assert!(verify_timer.elapsed() < std::time::Duration::from_secs(60));
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    }
+    assert_eq!(coordinator.try_read().unwrap().verification_runs(), 1);
+
+    // Verify again, nothing left to verify
     req = client.get("/verify");
     req = set_request::<()>(req, &ctx.coordinator.keypair, None);
     let response = req.dispatch();
     assert_eq!(response.status(), Status::Ok);
     assert!(response.body().is_none());
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    assert_eq!(coordinator.try_read().unwrap().verification_runs(), 1);
+
+    // A retried verification of the same response is served by the cache instead of running the verification again,
+    // and only fails at recording it twice
+    assert!(coordinator
+        .try_write()
+        .unwrap()
+        .default_verify(&Task::new(0, 1))
+        .is_err());
+    assert_eq!(coordinator.try_read().unwrap().verification_runs(), 1);
 
     // Get contributions info
     req = client.get("/contribution_info");
@@ -810,6 +1828,7 @@ fn contribution() {
     assert!(!summary[0].is_another_machine());
     assert!(!summary[0].is_own_seed_of_randomness());
     assert_eq!(summary[0].ceremony_round(), 1);
+    assert_eq!(summary[0].memo(), Some("Contributed from an air-gapped machine"));
 
     // Update cohorts
     assert!(std::fs::metadata(TOKENS_ZIP_FILE).is_err());
@@ -828,7 +1847,10 @@ fn contribution() {
     // Join queue with already contributed Ip
     let socket_address = SocketAddr::new(ctx.contributors[0].address, 8080);
 
-    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
     req = set_request::<String>(
         req,
         &ctx.unknown_participant.keypair,
@@ -843,7 +1865,10 @@ fn contribution() {
     // Join queue with already contributed Token
     let socket_address = SocketAddr::new(ctx.unknown_participant.address, 8080);
 
-    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
     req = set_request::<String>(
         req,
         &ctx.unknown_participant.keypair,
@@ -859,7 +1884,10 @@ fn contribution() {
     let sleep_time = COHORT_TIME - start_time.elapsed().as_secs();
     std::thread::sleep(std::time::Duration::from_secs(sleep_time));
 
-    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
     req = set_request::<String>(
         req,
         &ctx.unknown_participant.keypair,
@@ -918,7 +1946,10 @@ fn contribution() {
     assert!(response.body().is_none());
 
     // Try joining the queue with correct token
-    req = client.post("/contributor/join_queue").remote(socket_address);
+    req = client
+        .post("/contributor/join_queue")
+        .remote(socket_address)
+        .header(Header::new(PROTOCOL_VERSION_HEADER, PROTOCOL_VERSION));
     req = set_request::<String>(
         req,
         &ctx.unknown_participant.keypair,